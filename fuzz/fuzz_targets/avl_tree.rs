@@ -0,0 +1,63 @@
+// AvlTree fuzz target
+//
+// Arbitrary operation sequences applied to an AvlTree and to a BTreeSet as
+// the reference. After every step the tree must hold the same values in
+// sorted order, agree on every answer, and still satisfy the AVL property.
+//
+//   cargo +nightly fuzz run avl_tree
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rustnotes_collections::trees::AvlTree;
+use std::collections::BTreeSet;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Insert(u8),
+    Remove(u8),
+    Contains(u8),
+    RemoveRange(u8, u8),
+    RemoveRangeInclusive(u8, u8),
+    RemoveFrom(u8),
+    Clear,
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut tree = AvlTree::new();
+    let mut model = BTreeSet::new();
+    for op in ops {
+        match op {
+            Op::Insert(value) => {
+                tree.insert(value);
+                model.insert(value);
+            }
+            Op::Remove(value) => assert_eq!(tree.remove(&value), model.remove(&value)),
+            Op::Contains(value) => assert_eq!(tree.contains(&value), model.contains(&value)),
+            // retain rather than BTreeSet::range, which panics on start > end
+            Op::RemoveRange(start, end) => {
+                let before = model.len();
+                model.retain(|v| !(start..end).contains(v));
+                assert_eq!(tree.remove_range(start..end), before - model.len());
+            }
+            Op::RemoveRangeInclusive(start, end) => {
+                let before = model.len();
+                model.retain(|v| !(start..=end).contains(v));
+                assert_eq!(tree.remove_range(start..=end), before - model.len());
+            }
+            Op::RemoveFrom(start) => {
+                let before = model.len();
+                model.retain(|v| *v < start);
+                assert_eq!(tree.remove_range(start..), before - model.len());
+            }
+            Op::Clear => {
+                tree.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(tree.len(), model.len());
+        assert!(tree.iter().eq(model.iter()), "tree {:?} != model {:?}", tree.iter().collect::<Vec<_>>(), model);
+        assert!(tree.is_balanced());
+    }
+});
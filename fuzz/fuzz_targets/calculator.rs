@@ -0,0 +1,24 @@
+// Calculator fuzz target
+//
+// Arbitrary text, one line at a time, through the tokenizer, parser and
+// evaluator of a single Calculator (so memory and _ carry between lines).
+// Nothing may panic, and a line that evaluates must leave its value in _.
+//
+//   cargo +nightly fuzz run calculator
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use rustnotes_calc::Calculator;
+
+fuzz_target!(|data: &[u8]| {
+    // Invalid UTF-8 becomes U+FFFD, as it would when read from a terminal
+    let input = String::from_utf8_lossy(data);
+    let mut calc = Calculator::new();
+    for line in input.lines() {
+        if let Ok(value) = calc.evaluate(line) {
+            let last = calc.evaluate("_").expect("_ alone always evaluates");
+            assert!(last == value || (last.is_nan() && value.is_nan()), "{:?} gave {} but _ is {}", line, value, last);
+        }
+    }
+});
@@ -0,0 +1,107 @@
+// DoubleLinkedList fuzz target
+//
+// Arbitrary operation sequences, including NodeRef handles that go stale,
+// applied to a DoubleLinkedList and to a VecDeque as the reference. Every
+// pushed value is a fresh id, so a handle's node can be found in the model.
+// After every step the list must match front to back, and handles must be
+// valid exactly while their value is in the list. pop() and pop_end() walk
+// the next and prev links, so a broken link shows up as a mismatch; the
+// Rc::try_unwrap panics in the pops and remove_ref catch leaked references.
+//
+//   cargo +nightly fuzz run double_linked_list
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use libfuzzer_sys::fuzz_target;
+use rustnotes_collections::lists::double_linked_list::{DoubleLinkedList, NodeRef};
+use std::collections::VecDeque;
+
+#[derive(Arbitrary, Debug)]
+enum Op {
+    Push,
+    PushEnd,
+    PushRef,
+    Pop,
+    PopEnd,
+    // Handles are picked by index modulo the number taken so far
+    MoveToFront(u8),
+    RemoveRef(u8),
+    // Ids are picked modulo one past the last id, so some are never present
+    RemoveVal(u8),
+    Has(u8),
+    Reverse,
+    Clear,
+}
+
+fuzz_target!(|ops: Vec<Op>| {
+    let mut list = DoubleLinkedList::new();
+    let mut model = VecDeque::new();
+    let mut handles: Vec<(NodeRef<u32>, u32)> = Vec::new();
+    let mut next_id = 0u32;
+    for op in ops {
+        match op {
+            Op::Push => {
+                list.push(next_id);
+                model.push_front(next_id);
+                next_id += 1;
+            }
+            Op::PushEnd => {
+                list.push_end(next_id);
+                model.push_back(next_id);
+                next_id += 1;
+            }
+            Op::PushRef => {
+                handles.push((list.push_ref(next_id), next_id));
+                model.push_front(next_id);
+                next_id += 1;
+            }
+            Op::Pop => assert_eq!(list.pop(), model.pop_front()),
+            Op::PopEnd => assert_eq!(list.pop_end(), model.pop_back()),
+            Op::MoveToFront(index) if !handles.is_empty() => {
+                let (handle, id) = &handles[index as usize % handles.len()];
+                let position = model.iter().position(|v| v == id);
+                assert_eq!(list.move_to_front(handle), position.is_some());
+                if let Some(position) = position {
+                    model.remove(position);
+                    model.push_front(*id);
+                }
+            }
+            Op::RemoveRef(index) if !handles.is_empty() => {
+                let (handle, id) = &handles[index as usize % handles.len()];
+                let position = model.iter().position(|v| v == id);
+                assert_eq!(list.remove_ref(handle), position.and_then(|p| model.remove(p)));
+            }
+            Op::RemoveVal(id) => {
+                let id = id as u32 % (next_id + 1);
+                let position = model.iter().position(|v| *v == id);
+                assert_eq!(list.remove_val(&id), position.is_some());
+                if let Some(position) = position {
+                    model.remove(position);
+                }
+            }
+            Op::Has(id) => {
+                let id = id as u32 % (next_id + 1);
+                assert_eq!(list.has(&id), model.contains(&id));
+            }
+            Op::Reverse => {
+                list.reverse();
+                model.make_contiguous().reverse();
+            }
+            Op::Clear => {
+                list.clear();
+                model.clear();
+            }
+            Op::MoveToFront(_) | Op::RemoveRef(_) => {}
+        }
+
+        assert_eq!(list.len(), model.len());
+        let mut values = Vec::with_capacity(list.len());
+        list.for_each(|value| values.push(*value));
+        assert!(values.iter().eq(model.iter()), "list {:?} != model {:?}", values, model);
+        assert_eq!(list.peek().map(|value| *value), model.front().copied());
+        for (handle, id) in &handles {
+            assert_eq!(handle.is_valid(), model.contains(id), "handle to {}", id);
+        }
+    }
+});
@@ -0,0 +1,63 @@
+// Sorting Benchmarks
+//
+// merge_sort and quicksort against their parallel versions and std's
+// slice::sort (stable) and slice::sort_unstable, on random u64 values and on
+// input that is already sorted. Run with:
+//
+//   cargo bench -p rustnotes-algorithms --bench sorting
+//
+// Design choices:
+// - Input comes from the crate's XorShift64Star with a fixed seed, so every
+//   sort sees the same values
+// - iter_batched copies the input outside the timed section, so only the
+//   sort is measured
+// - The largest size is well above PARALLEL_CUTOFF, so the parallel sorts
+//   actually split; the smallest is below it and shows their fallback cost
+// - Trade-off: the parallel numbers depend on the machine's core count; on
+//   one core they only show the overhead of spawning
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rustnotes_algorithms::sorting::{merge_sort, par_merge_sort, par_quicksort, quicksort};
+use rustnotes_collections::random::{Rng, XorShift64Star};
+
+const SIZES: [usize; 3] = [1_000, 100_000, 1_000_000];
+
+type Sort = fn(&mut [u64]);
+
+const SORTS: [(&str, Sort); 6] = [
+    ("merge_sort", merge_sort),
+    ("par_merge_sort", par_merge_sort),
+    ("quicksort", quicksort),
+    ("par_quicksort", par_quicksort),
+    ("slice::sort", <[u64]>::sort),
+    ("slice::sort_unstable", <[u64]>::sort_unstable),
+];
+
+fn random(n: usize) -> Vec<u64> {
+    let mut rng = XorShift64Star::seed_from_u64(99);
+    (0..n).map(|_| rng.next_u64()).collect()
+}
+
+fn bench_input(c: &mut Criterion, group_name: &str, input: fn(usize) -> Vec<u64>) {
+    let mut group = c.benchmark_group(group_name);
+    for n in SIZES {
+        let data = input(n);
+        for (name, sort) in SORTS {
+            group.bench_with_input(BenchmarkId::new(name, n), &data, |b, data| {
+                b.iter_batched(|| data.clone(), |mut copy| sort(&mut copy), BatchSize::LargeInput)
+            });
+        }
+    }
+    group.finish();
+}
+
+fn random_input(c: &mut Criterion) {
+    bench_input(c, "sort_random", random);
+}
+
+fn sorted_input(c: &mut Criterion) {
+    bench_input(c, "sort_sorted", |n| (0..n as u64).collect());
+}
+
+criterion_group!(benches, random_input, sorted_input);
+criterion_main!(benches);
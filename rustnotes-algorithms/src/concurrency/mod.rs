@@ -0,0 +1,7 @@
+// Concurrency
+
+pub mod mpmc_queue;
+pub mod thread_pool;
+
+pub use self::mpmc_queue::MpmcQueue;
+pub use self::thread_pool::ThreadPool;
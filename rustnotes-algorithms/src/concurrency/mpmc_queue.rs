@@ -0,0 +1,383 @@
+// Concurrent MPMC Queue
+//
+// A first-in first-out queue that any number of threads can push to and pop
+// from at once (multi-producer, multi-consumer). A Mutex guards a VecDeque, and
+// two condition variables let threads sleep instead of spinning:
+//
+//   producers --push--> [ mutex: VecDeque + closed flag ] --pop--> consumers
+//                          |                      |
+//                  not_full (wakes producers)   not_empty (wakes consumers)
+//
+// pop() blocks while the queue is empty; with a capacity, push() blocks while
+// it is full, which slows producers down to the consumers' pace (back
+// pressure). close() ends the stream: pushes fail from then on, and consumers
+// drain what is left and then get None instead of waiting forever.
+//
+// Design choices:
+// - Mutex + Condvar rather than a lock-free (Michael-Scott) linked queue: the
+//   lock-free version needs unsafe pointer juggling and safe memory
+//   reclamation (hazard pointers or epochs), while this one is safe Rust and
+//   fast enough unless the lock itself is the bottleneck
+// - Condvar waits sit in while-loops, since a wakeup can be spurious or can
+//   lose the race to another thread that got the lock first
+// - notify_one after each push/pop: one item frees exactly one waiter; close()
+//   uses notify_all so every sleeper sees the flag
+// - Non-blocking variants (try_push/try_pop) and pop_timeout for consumers
+//   that must also do other work
+// - Handles are cheap clones of an Arc, so a queue is passed to threads by
+//   cloning it
+// - Trade-off: every operation takes the one lock, so throughput stops scaling
+//   once threads spend most of their time contending for it
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushError<T> {
+    // try_push on a full bounded queue; the value is handed back
+    Full(T),
+    // The queue was closed; the value is handed back
+    Closed(T),
+}
+
+impl<T> fmt::Display for PushError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PushError::Full(_) => write!(f, "queue is full"),
+            PushError::Closed(_) => write!(f, "queue is closed"),
+        }
+    }
+}
+
+impl<T: fmt::Debug> std::error::Error for PushError<T> {}
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+struct Shared<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: Option<usize>,
+}
+
+pub struct MpmcQueue<T> {
+    shared: Arc<Shared<T>>,
+}
+
+// Cloning gives another handle to the same queue
+impl<T> Clone for MpmcQueue<T> {
+    fn clone(&self) -> Self {
+        MpmcQueue { shared: Arc::clone(&self.shared) }
+    }
+}
+
+impl<T> Default for MpmcQueue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> MpmcQueue<T> {
+    // Unbounded: push never blocks
+    pub fn new() -> Self {
+        Self::with_capacity_limit(None)
+    }
+
+    // Bounded: push blocks while `capacity` items are waiting
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "a bounded queue needs room for at least one item");
+        Self::with_capacity_limit(Some(capacity))
+    }
+
+    fn with_capacity_limit(capacity: Option<usize>) -> Self {
+        MpmcQueue {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State { items: VecDeque::new(), closed: false }),
+                not_empty: Condvar::new(),
+                not_full: Condvar::new(),
+                capacity,
+            }),
+        }
+    }
+
+    fn is_full(&self, state: &State<T>) -> bool {
+        self.shared.capacity.is_some_and(|capacity| state.items.len() >= capacity)
+    }
+
+    // Block until there is room, then enqueue; fails only if the queue is closed
+    pub fn push(&self, value: T) -> Result<(), PushError<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+        while !state.closed && self.is_full(&state) {
+            state = self.shared.not_full.wait(state).unwrap();
+        }
+        if state.closed {
+            return Err(PushError::Closed(value));
+        }
+        state.items.push_back(value);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    pub fn try_push(&self, value: T) -> Result<(), PushError<T>> {
+        let mut state = self.shared.state.lock().unwrap();
+        if state.closed {
+            return Err(PushError::Closed(value));
+        }
+        if self.is_full(&state) {
+            return Err(PushError::Full(value));
+        }
+        state.items.push_back(value);
+        self.shared.not_empty.notify_one();
+        Ok(())
+    }
+
+    // Block until an item arrives; None once the queue is closed and drained
+    pub fn pop(&self) -> Option<T> {
+        let mut state = self.shared.state.lock().unwrap();
+        while state.items.is_empty() && !state.closed {
+            state = self.shared.not_empty.wait(state).unwrap();
+        }
+        let value = state.items.pop_front();
+        if value.is_some() {
+            self.shared.not_full.notify_one();
+        }
+        value
+    }
+
+    pub fn try_pop(&self) -> Option<T> {
+        let mut state = self.shared.state.lock().unwrap();
+        let value = state.items.pop_front();
+        if value.is_some() {
+            self.shared.not_full.notify_one();
+        }
+        value
+    }
+
+    // Like pop, but give up after `timeout`
+    pub fn pop_timeout(&self, timeout: Duration) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.shared.state.lock().unwrap();
+        while state.items.is_empty() && !state.closed {
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            state = self.shared.not_empty.wait_timeout(state, deadline - now).unwrap().0;
+        }
+        let value = state.items.pop_front();
+        if value.is_some() {
+            self.shared.not_full.notify_one();
+        }
+        value
+    }
+
+    // Refuse further pushes and wake every waiting thread
+    pub fn close(&self) {
+        self.shared.state.lock().unwrap().closed = true;
+        self.shared.not_empty.notify_all();
+        self.shared.not_full.notify_all();
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.shared.state.lock().unwrap().closed
+    }
+
+    pub fn len(&self) -> usize {
+        self.shared.state.lock().unwrap().items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Many producers and consumers; every item must arrive exactly once
+fn stress(producers: usize, consumers: usize, per_producer: usize, capacity: Option<usize>) -> (bool, Duration) {
+    let queue = match capacity {
+        Some(capacity) => MpmcQueue::bounded(capacity),
+        None => MpmcQueue::new(),
+    };
+    let start = Instant::now();
+    let consumer_handles: Vec<_> = (0..consumers)
+        .map(|_| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                let mut received = Vec::new();
+                while let Some(item) = queue.pop() {
+                    received.push(item);
+                }
+                received
+            })
+        })
+        .collect();
+    let producer_handles: Vec<_> = (0..producers)
+        .map(|p| {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                for i in 0..per_producer {
+                    queue.push((p, i)).unwrap();
+                }
+            })
+        })
+        .collect();
+    for handle in producer_handles {
+        handle.join().unwrap();
+    }
+    queue.close();
+
+    let mut seen = vec![vec![false; per_producer]; producers];
+    let mut ok = true;
+    for handle in consumer_handles {
+        let received = handle.join().unwrap();
+        // FIFO per producer: one consumer sees each producer's items in order
+        let mut last = vec![None; producers];
+        for (p, i) in received {
+            ok &= !seen[p][i] && last[p].is_none_or(|previous| previous < i);
+            seen[p][i] = true;
+            last[p] = Some(i);
+        }
+    }
+    ok &= seen.iter().all(|items| items.iter().all(|&s| s));
+    (ok, start.elapsed())
+}
+
+pub fn demo() {
+    println!("=== Single thread basics ===");
+    let queue = MpmcQueue::bounded(2);
+    println!("try_push(1): {:?}", queue.try_push(1));
+    println!("try_push(2): {:?}", queue.try_push(2));
+    println!("try_push(3): {:?}", queue.try_push(3));
+    println!("pop: {:?}, try_pop: {:?}, try_pop: {:?}", queue.pop(), queue.try_pop(), queue.try_pop());
+    let start = Instant::now();
+    println!("pop_timeout(50ms) on empty: {:?} after ~{}ms", queue.pop_timeout(Duration::from_millis(50)),
+             start.elapsed().as_millis() / 10 * 10);
+    queue.push(4).unwrap();
+    queue.close();
+    println!("After close: push(5) = {:?}, pop = {:?}, pop = {:?}", queue.push(5), queue.pop(), queue.pop());
+
+    println!("\n=== Blocking hand-off ===");
+    let queue = MpmcQueue::new();
+    let consumer = {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            let start = Instant::now();
+            let value = queue.pop();
+            (value, start.elapsed())
+        })
+    };
+    thread::sleep(Duration::from_millis(30));
+    queue.push("hello").unwrap();
+    let (value, waited) = consumer.join().unwrap();
+    println!("Consumer blocked ~{}ms, then got {:?}", waited.as_millis() / 10 * 10, value);
+
+    println!("\n=== Back pressure ===");
+    let queue = MpmcQueue::bounded(4);
+    let producer = {
+        let queue = queue.clone();
+        thread::spawn(move || {
+            for i in 0..20 {
+                queue.push(i).unwrap();
+            }
+        })
+    };
+    thread::sleep(Duration::from_millis(30));
+    println!("Producer of 20 items stalled with {} queued (capacity 4)", queue.len());
+    let drained: Vec<i32> = (0..20).filter_map(|_| queue.pop()).collect();
+    producer.join().unwrap();
+    println!("Drained in order: {}", drained.iter().cloned().eq(0..20));
+
+    println!("\n=== Stress: every item delivered exactly once ===");
+    for &(producers, consumers, capacity) in [(1, 1, None), (8, 1, None), (1, 8, None), (8, 8, None), (8, 8, Some(16)), (4, 4, Some(1))].iter() {
+        let (ok, elapsed) = stress(producers, consumers, 20_000, capacity);
+        println!("{:>2} producers, {:>2} consumers, capacity {:<8} {} items: {} in {:?}",
+                 producers, consumers, capacity.map_or("none".to_string(), |c| c.to_string()),
+                 producers * 20_000, if ok { "ok" } else { "FAILED" }, elapsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounded_try_push_and_close() {
+        let queue = MpmcQueue::bounded(2);
+        assert_eq!(queue.try_push(1), Ok(()));
+        assert_eq!(queue.try_push(2), Ok(()));
+        assert_eq!(queue.try_push(3), Err(PushError::Full(3)));
+        assert_eq!((queue.pop(), queue.try_pop(), queue.try_pop()), (Some(1), Some(2), None));
+        assert!(queue.is_empty());
+
+        queue.push(4).unwrap();
+        queue.close();
+        assert!(queue.is_closed());
+        assert_eq!(queue.push(5), Err(PushError::Closed(5)));
+        assert_eq!(queue.try_push(6), Err(PushError::Closed(6)));
+        // Items pushed before close are still drained
+        assert_eq!((queue.pop(), queue.pop()), (Some(4), None));
+    }
+
+    #[test]
+    fn pop_timeout_gives_up_on_an_empty_queue() {
+        let queue: MpmcQueue<u8> = MpmcQueue::new();
+        let start = Instant::now();
+        assert_eq!(queue.pop_timeout(Duration::from_millis(20)), None);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        queue.push(7).unwrap();
+        assert_eq!(queue.pop_timeout(Duration::from_millis(20)), Some(7));
+    }
+
+    #[test]
+    fn close_wakes_blocked_threads() {
+        let empty: MpmcQueue<u8> = MpmcQueue::new();
+        let consumer = {
+            let empty = empty.clone();
+            thread::spawn(move || empty.pop())
+        };
+        let full = MpmcQueue::bounded(1);
+        full.push(1).unwrap();
+        let producer = {
+            let full = full.clone();
+            thread::spawn(move || full.push(2))
+        };
+        thread::sleep(Duration::from_millis(20));
+        empty.close();
+        full.close();
+        assert_eq!(consumer.join().unwrap(), None);
+        assert_eq!(producer.join().unwrap(), Err(PushError::Closed(2)));
+    }
+
+    #[test]
+    fn bounded_push_applies_back_pressure() {
+        let queue = MpmcQueue::bounded(4);
+        let producer = {
+            let queue = queue.clone();
+            thread::spawn(move || {
+                for i in 0..20 {
+                    queue.push(i).unwrap();
+                }
+            })
+        };
+        thread::sleep(Duration::from_millis(20));
+        assert!(queue.len() <= 4);
+        let drained: Vec<i32> = (0..20).filter_map(|_| queue.pop()).collect();
+        producer.join().unwrap();
+        assert_eq!(drained, (0..20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stress_delivers_every_item_exactly_once() {
+        for &(producers, consumers, capacity) in
+            [(1, 1, None), (8, 1, None), (1, 8, None), (8, 8, None), (8, 8, Some(16)), (4, 4, Some(1))].iter()
+        {
+            let (ok, _) = stress(producers, consumers, 5_000, capacity);
+            assert!(ok, "{} producers, {} consumers, capacity {:?}", producers, consumers, capacity);
+        }
+    }
+}
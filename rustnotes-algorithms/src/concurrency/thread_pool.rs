@@ -0,0 +1,222 @@
+// Thread Pool
+//
+// A fixed set of worker threads that run closures handed to execute(). Jobs go
+// into the MpmcQueue from concurrency::mpmc_queue; each worker loops popping a job and
+// running it, so whichever worker is free takes the next one:
+//
+//   execute(job) --> [ job queue ] --> worker 0: pop, run, pop, run ...
+//                                  --> worker 1: pop, run ...
+//                                  --> worker 2: ...
+//
+// Starting a thread costs tens of microseconds; a pool pays that once and then
+// runs many short jobs on the same threads.
+//
+// Design choices:
+// - One shared queue, not per-worker deques with work stealing: simpler, and
+//   the queue's lock is cheap next to jobs of more than a few microseconds
+// - Panic isolation: each job runs under catch_unwind, so a panicking job is
+//   counted and reported by wait() but does not kill its worker or the pool
+// - wait() blocks until every job submitted so far has finished, using a
+//   pending-job counter and a Condvar
+// - Graceful shutdown: shutdown() (and Drop) closes the queue, the workers
+//   finish the jobs already queued, see None and exit, and are joined
+// - Results come back however the caller likes; the demo sends them over an
+//   mpsc channel with the job's index
+// - Trade-off: jobs must be 'static (they may outlive the caller's stack
+//   frame), so borrowed data has to be moved in or shared through Arc;
+//   std::thread::scope lifts that restriction but starts new threads each time
+
+use crate::concurrency::mpmc_queue::MpmcQueue;
+use rustnotes_collections::hashing::hash_functions::{sha256, to_hex, Digest};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Instant;
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+// Jobs submitted but not yet finished, plus how many of them panicked
+#[derive(Default)]
+struct Progress {
+    pending: usize,
+    panicked: usize,
+}
+
+struct Tracker {
+    progress: Mutex<Progress>,
+    idle: Condvar,
+}
+
+pub struct ThreadPool {
+    jobs: MpmcQueue<Job>,
+    workers: Vec<JoinHandle<()>>,
+    tracker: Arc<Tracker>,
+}
+
+impl ThreadPool {
+    pub fn new(threads: usize) -> Self {
+        assert!(threads > 0, "a thread pool needs at least one worker");
+        let jobs: MpmcQueue<Job> = MpmcQueue::new();
+        let tracker = Arc::new(Tracker { progress: Mutex::new(Progress::default()), idle: Condvar::new() });
+        let workers = (0..threads)
+            .map(|id| {
+                let jobs = jobs.clone();
+                let tracker = Arc::clone(&tracker);
+                thread::Builder::new()
+                    .name(format!("pool-worker-{}", id))
+                    .spawn(move || Self::work(jobs, tracker))
+                    .expect("failed to start worker thread")
+            })
+            .collect();
+        ThreadPool { jobs, workers, tracker }
+    }
+
+    // One worker per available CPU
+    pub fn with_available_parallelism() -> Self {
+        Self::new(thread::available_parallelism().map_or(1, |n| n.get()))
+    }
+
+    fn work(jobs: MpmcQueue<Job>, tracker: Arc<Tracker>) {
+        while let Some(job) = jobs.pop() {
+            let panicked = panic::catch_unwind(AssertUnwindSafe(job)).is_err();
+            let mut progress = tracker.progress.lock().unwrap();
+            progress.pending -= 1;
+            if panicked {
+                progress.panicked += 1;
+            }
+            if progress.pending == 0 {
+                tracker.idle.notify_all();
+            }
+        }
+    }
+
+    pub fn threads(&self) -> usize {
+        self.workers.len()
+    }
+
+    pub fn execute<F: FnOnce() + Send + 'static>(&self, job: F) {
+        self.tracker.progress.lock().unwrap().pending += 1;
+        if self.jobs.push(Box::new(job)).is_err() {
+            unreachable!("the job queue closes only in shutdown, which consumes the pool");
+        }
+    }
+
+    // Block until all submitted jobs have finished; returns how many panicked
+    // since the last wait()
+    pub fn wait(&self) -> usize {
+        let mut progress = self.tracker.progress.lock().unwrap();
+        while progress.pending > 0 {
+            progress = self.tracker.idle.wait(progress).unwrap();
+        }
+        std::mem::take(&mut progress.panicked)
+    }
+
+    // Finish the queued jobs, then stop and join every worker
+    pub fn shutdown(mut self) {
+        self.stop();
+    }
+
+    fn stop(&mut self) {
+        self.jobs.close();
+        for worker in self.workers.drain(..) {
+            // Job panics are caught inside the worker, so join only fails if
+            // the worker loop itself panicked
+            worker.join().expect("worker thread panicked outside a job");
+        }
+    }
+}
+
+impl Drop for ThreadPool {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Hash each chunk on the pool; results arrive out of order, tagged by index
+fn parallel_hashes(pool: &ThreadPool, data: &Arc<Vec<u8>>, chunk_size: usize) -> Vec<Digest> {
+    let chunks = data.len().div_ceil(chunk_size);
+    let (sender, receiver) = mpsc::channel();
+    for index in 0..chunks {
+        let data = Arc::clone(data);
+        let sender = sender.clone();
+        pool.execute(move || {
+            let end = ((index + 1) * chunk_size).min(data.len());
+            sender.send((index, sha256(&data[index * chunk_size..end]))).unwrap();
+        });
+    }
+    drop(sender);
+    let mut digests = vec![[0u8; 32]; chunks];
+    for (index, digest) in receiver {
+        digests[index] = digest;
+    }
+    digests
+}
+
+pub fn demo() {
+    println!("=== Jobs, wait and panic isolation ===");
+    let pool = ThreadPool::new(4);
+    let total = Arc::new(Mutex::new(0u64));
+    for i in 1..=100u64 {
+        let total = Arc::clone(&total);
+        pool.execute(move || *total.lock().unwrap() += i);
+    }
+    let panicked = pool.wait();
+    println!("Sum of 1..=100 computed by {} workers: {} (panics: {})", pool.threads(), *total.lock().unwrap(), panicked);
+    // Silence the default panic message for the deliberate panics below
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    for i in 0..10 {
+        pool.execute(move || {
+            if i % 3 == 0 {
+                panic!("job {} failed", i);
+            }
+        });
+    }
+    let panicked = pool.wait();
+    panic::set_hook(default_hook);
+    println!("10 jobs, {} panicked; the pool still has {} workers", panicked, pool.threads());
+    let (sender, receiver) = mpsc::channel();
+    pool.execute(move || sender.send(thread::current().name().map(String::from)).unwrap());
+    println!("Next job ran fine on {:?}", receiver.recv().unwrap().unwrap_or_default());
+
+    println!("\n=== Graceful shutdown ===");
+    let finished = Arc::new(Mutex::new(0));
+    for _ in 0..20 {
+        let finished = Arc::clone(&finished);
+        pool.execute(move || {
+            thread::sleep(std::time::Duration::from_millis(5));
+            *finished.lock().unwrap() += 1;
+        });
+    }
+    pool.shutdown();
+    println!("shutdown() returned after all 20 queued jobs ran: {}", *finished.lock().unwrap() == 20);
+
+    println!("\n=== Parallel SHA-256 of 8 MiB in 256 KiB chunks (hashing::hash_functions) ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let data: Vec<u8> = (0..8 << 20)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect();
+    let data = Arc::new(data);
+    let chunk_size = 256 << 10;
+
+    let start = Instant::now();
+    let serial: Vec<Digest> = data.chunks(chunk_size).map(sha256).collect();
+    let serial_time = start.elapsed();
+    println!("1 thread:  {:?} (this machine has {} CPU(s))", serial_time,
+             thread::available_parallelism().map_or(1, |n| n.get()));
+    for pool in IntoIterator::into_iter([ThreadPool::new(2), ThreadPool::new(4), ThreadPool::with_available_parallelism()]) {
+        let threads = pool.threads();
+        let start = Instant::now();
+        let parallel = parallel_hashes(&pool, &data, chunk_size);
+        let elapsed = start.elapsed();
+        println!("{} threads: {:?} ({:.1}x), same digests: {}", threads, elapsed,
+                 serial_time.as_secs_f64() / elapsed.as_secs_f64(), parallel == serial);
+    }
+    println!("First chunk: {}", to_hex(&serial[0]));
+}
@@ -0,0 +1,202 @@
+// Base64 Encoding and Decoding
+//
+// Base64 carries binary data through text-only channels (email, JSON, URLs, data:
+// URIs) by splitting every 3 bytes (24 bits) into four 6-bit groups and writing
+// each group as one of 64 printable characters. The output is 4/3 the size of the
+// input. When the input length is not a multiple of 3, the last group is padded
+// with '=' so the encoded length stays a multiple of 4.
+//
+//   bytes:  'M'      'a'      'n'
+//   bits:   01001101 01100001 01101110
+//   groups: 010011 010110 000101 101110
+//   chars:  T      W      F      u          -> "TWFu"
+//
+// Design choices:
+// - A Base64 value bundles an alphabet and a padding rule; the four common
+//   variants are provided as constants (STANDARD, STANDARD_NO_PAD, URL_SAFE,
+//   URL_SAFE_NO_PAD). URL-safe swaps '+' and '/' for '-' and '_'
+// - Decoding is strict: with padding the input must be padded correctly, without
+//   padding any '=' is rejected, and unused low bits in the final character must be
+//   zero, so every byte string has exactly one accepted encoding
+// - Errors say what went wrong and where (DecodeError), instead of returning None
+// - Whitespace is not skipped; strip line breaks before decoding MIME-style text
+// - Trade-off: straightforward table lookups one group at a time, not the SIMD
+//   tricks of high-throughput libraries
+
+use std::fmt;
+
+const STANDARD_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const PAD: u8 = b'=';
+const INVALID: u8 = 0xFF;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    // A character outside the alphabet (or misplaced padding) at this byte offset
+    InvalidByte { index: usize, byte: u8 },
+    // Length that no encoding can have (e.g. 4k + 1 characters)
+    InvalidLength(usize),
+    // Missing, extra, or unexpected '=' characters
+    InvalidPadding,
+    // The last character has non-zero bits that do not belong to any output byte
+    InvalidTrailingBits,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::InvalidByte { index, byte } => {
+                write!(f, "invalid byte {:?} at offset {}", *byte as char, index)
+            }
+            DecodeError::InvalidLength(len) => write!(f, "invalid input length {}", len),
+            DecodeError::InvalidPadding => write!(f, "invalid padding"),
+            DecodeError::InvalidTrailingBits => write!(f, "non-zero trailing bits"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Base64 {
+    alphabet: &'static [u8; 64],
+    pad: bool,
+}
+
+pub const STANDARD: Base64 = Base64 { alphabet: STANDARD_ALPHABET, pad: true };
+pub const STANDARD_NO_PAD: Base64 = Base64 { alphabet: STANDARD_ALPHABET, pad: false };
+pub const URL_SAFE: Base64 = Base64 { alphabet: URL_SAFE_ALPHABET, pad: true };
+pub const URL_SAFE_NO_PAD: Base64 = Base64 { alphabet: URL_SAFE_ALPHABET, pad: false };
+
+impl Base64 {
+    // Length of the encoding of `len` bytes
+    pub fn encoded_len(&self, len: usize) -> usize {
+        if self.pad {
+            len.div_ceil(3) * 4
+        } else {
+            (len * 4).div_ceil(3)
+        }
+    }
+
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut out = Vec::with_capacity(self.encoded_len(data.len()));
+        for chunk in data.chunks(3) {
+            // Pack up to three bytes into the top of a 24-bit group
+            let group = chunk.iter().enumerate().fold(0u32, |acc, (i, &b)| acc | (b as u32) << (16 - 8 * i));
+            // n bytes produce n + 1 significant characters
+            for i in 0..chunk.len() + 1 {
+                out.push(self.alphabet[(group >> (18 - 6 * i)) as usize & 0x3F]);
+            }
+            if self.pad {
+                out.extend(std::iter::repeat_n(PAD, 3 - chunk.len()));
+            }
+        }
+        String::from_utf8(out).expect("alphabet is ASCII")
+    }
+
+    fn decode_table(&self) -> [u8; 256] {
+        let mut table = [INVALID; 256];
+        for (value, &symbol) in self.alphabet.iter().enumerate() {
+            table[symbol as usize] = value as u8;
+        }
+        table
+    }
+
+    pub fn decode(&self, encoded: &str) -> Result<Vec<u8>, DecodeError> {
+        let bytes = encoded.as_bytes();
+
+        // Separate the data characters from the padding
+        let data_len = if self.pad {
+            if !bytes.len().is_multiple_of(4) {
+                return Err(DecodeError::InvalidLength(bytes.len()));
+            }
+            let padding = bytes.iter().rev().take_while(|&&b| b == PAD).count();
+            if padding > 2 {
+                return Err(DecodeError::InvalidPadding);
+            }
+            bytes.len() - padding
+        } else {
+            bytes.len()
+        };
+        if data_len % 4 == 1 {
+            return Err(DecodeError::InvalidLength(bytes.len()));
+        }
+
+        let table = self.decode_table();
+        let mut out = Vec::with_capacity(data_len * 3 / 4);
+        for (chunk_index, chunk) in bytes[..data_len].chunks(4).enumerate() {
+            let mut group = 0u32;
+            for (i, &byte) in chunk.iter().enumerate() {
+                let value = table[byte as usize];
+                if value == INVALID {
+                    let index = chunk_index * 4 + i;
+                    return Err(if byte == PAD {
+                        DecodeError::InvalidPadding
+                    } else {
+                        DecodeError::InvalidByte { index, byte }
+                    });
+                }
+                group |= (value as u32) << (18 - 6 * i);
+            }
+            // n characters carry n - 1 whole bytes; the rest of the bits must be zero
+            let produced = chunk.len() - 1;
+            if group & (0xFF_FFFF >> (8 * produced)) != 0 {
+                return Err(DecodeError::InvalidTrailingBits);
+            }
+            for i in 0..produced {
+                out.push((group >> (16 - 8 * i)) as u8);
+            }
+        }
+        Ok(out)
+    }
+}
+
+pub fn demo() {
+    println!("=== RFC 4648 test vectors ===");
+    let vectors = ["", "f", "fo", "foo", "foob", "fooba", "foobar"];
+    for text in vectors.iter() {
+        let encoded = STANDARD.encode(text.as_bytes());
+        let decoded = STANDARD.decode(&encoded).unwrap();
+        println!("{:<8} -> {:<10} -> {:?}", format!("{:?}", text), encoded,
+                 String::from_utf8(decoded).unwrap());
+    }
+
+    println!("\n=== Variants ===");
+    let data = [0xFBu8, 0xFF, 0xBF, 0x3E, 0x01];
+    for (name, codec) in [("STANDARD", STANDARD), ("STANDARD_NO_PAD", STANDARD_NO_PAD),
+                          ("URL_SAFE", URL_SAFE), ("URL_SAFE_NO_PAD", URL_SAFE_NO_PAD)].iter() {
+        let encoded = codec.encode(&data);
+        println!("{:<16} {:<10} (predicted length {}), round trip {}",
+                 name, encoded, codec.encoded_len(data.len()), codec.decode(&encoded) == Ok(data.to_vec()));
+    }
+
+    println!("\n=== Every byte value and every length ===");
+    let all: Vec<u8> = (0..=255).collect();
+    let mut ok = true;
+    for len in 0..all.len() {
+        for codec in [STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD].iter() {
+            let encoded = codec.encode(&all[..len]);
+            ok &= encoded.len() == codec.encoded_len(len);
+            ok &= codec.decode(&encoded).as_deref() == Ok(&all[..len]);
+        }
+    }
+    println!("All 256 prefixes round trip in all four variants: {}", ok);
+
+    println!("\n=== Invalid input ===");
+    let bad = [
+        ("Zm9v!mFy", STANDARD),
+        ("Zm9vYg=", STANDARD),
+        ("Zm9vY", STANDARD_NO_PAD),
+        ("Zm===", STANDARD),
+        ("Zm9vYg==", STANDARD_NO_PAD),
+        ("Zm9=YmFy", STANDARD),
+        ("Zh==", STANDARD),
+        ("-_8", STANDARD_NO_PAD),
+    ];
+    for (input, codec) in bad.iter() {
+        println!("{:<10} {}", input, codec.decode(input).unwrap_err());
+    }
+    println!("\"-_8\" in URL_SAFE_NO_PAD: {:?}", URL_SAFE_NO_PAD.decode("-_8"));
+}
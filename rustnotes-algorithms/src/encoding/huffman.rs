@@ -0,0 +1,347 @@
+// Huffman Coding
+//
+// Lossless compression that gives frequent bytes short bit codes and rare bytes
+// long ones. The codes come from a binary tree built bottom-up: start with one
+// leaf per byte value weighted by its count, then repeatedly merge the two
+// lightest trees. A byte's code length is its leaf's depth, and no code is a
+// prefix of another, so the bit stream decodes unambiguously.
+//
+// Design choices:
+// - Tree construction uses MinHeap from heaps::binary_heap; entries are
+//   (weight, node index), and since indices are unique and increasing, equal
+//   weights merge in a deterministic order
+// - The tree is only used to find code lengths; the actual codes are canonical:
+//   symbols sorted by (length, byte value) get consecutive codes. The decoder can
+//   rebuild them from the lengths alone, so the header stores just lengths
+// - Compressed layout: original length (u64 LE), number of coded symbols (u16 LE),
+//   (byte, code length) pairs, then the bit stream, most significant bit first
+// - Decoding walks canonical "first code of each length" tables, one bit at a
+//   time, instead of rebuilding a tree
+// - A single distinct byte still gets a 1-bit code so the stream is never empty
+// - Decoding returns Result<_, HuffmanError> because compressed input may be
+//   truncated or corrupted
+// - Usage: huffman                        run the demo
+//          huffman encode <input> <output>
+//          huffman decode <input> <output>
+// - Trade-off: optimal for independent bytes but blind to repetition (ABABAB...
+//   costs a bit per byte); real compressors pair it with LZ77 or similar
+
+use rustnotes_collections::heaps::binary_heap::MinHeap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::process;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum HuffmanError {
+    Truncated,
+    InvalidHeader(String),
+    InvalidCode,
+}
+
+impl fmt::Display for HuffmanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HuffmanError::Truncated => write!(f, "compressed data ends unexpectedly"),
+            HuffmanError::InvalidHeader(reason) => write!(f, "invalid header: {}", reason),
+            HuffmanError::InvalidCode => write!(f, "bit stream contains an invalid code"),
+        }
+    }
+}
+
+impl std::error::Error for HuffmanError {}
+
+pub fn count_frequencies(data: &[u8]) -> [u64; 256] {
+    let mut counts = [0u64; 256];
+    for &byte in data {
+        counts[byte as usize] += 1;
+    }
+    counts
+}
+
+// Tree nodes in an arena: leaves hold a byte, internal nodes two children
+enum Node {
+    Leaf(u8),
+    Internal(usize, usize),
+}
+
+// Depth of every byte's leaf in the Huffman tree (0 = byte does not occur)
+pub fn code_lengths(frequencies: &[u64; 256]) -> [u8; 256] {
+    let mut nodes = Vec::new();
+    let mut heap = MinHeap::new();
+    for (byte, &count) in frequencies.iter().enumerate() {
+        if count > 0 {
+            heap.push((count, nodes.len()));
+            nodes.push(Node::Leaf(byte as u8));
+        }
+    }
+
+    let mut lengths = [0u8; 256];
+    if nodes.len() == 1 {
+        if let Node::Leaf(byte) = nodes[0] {
+            lengths[byte as usize] = 1;
+        }
+        return lengths;
+    }
+
+    // The node index doubles as the tiebreak: it is unique and increases over time
+    while heap.len() > 1 {
+        let (weight_a, a) = heap.pop().unwrap();
+        let (weight_b, b) = heap.pop().unwrap();
+        heap.push((weight_a + weight_b, nodes.len()));
+        nodes.push(Node::Internal(a, b));
+    }
+
+    if let Some((_, root)) = heap.pop() {
+        let mut stack = vec![(root, 0u8)];
+        while let Some((index, depth)) = stack.pop() {
+            match nodes[index] {
+                Node::Leaf(byte) => lengths[byte as usize] = depth,
+                Node::Internal(left, right) => {
+                    stack.push((left, depth + 1));
+                    stack.push((right, depth + 1));
+                }
+            }
+        }
+    }
+    lengths
+}
+
+// Symbols ordered by (code length, byte value) - the canonical order
+fn canonical_order(lengths: &[u8; 256]) -> Vec<u8> {
+    let mut symbols: Vec<u8> = (0..=255u8).filter(|&b| lengths[b as usize] > 0).collect();
+    symbols.sort_by_key(|&b| (lengths[b as usize], b));
+    symbols
+}
+
+// Canonical codes: each code is the previous one plus one, shifted left whenever
+// the length grows. Returns (code, length) per byte.
+pub fn canonical_codes(lengths: &[u8; 256]) -> [(u64, u8); 256] {
+    let mut codes = [(0u64, 0u8); 256];
+    let mut code = 0u64;
+    let mut previous_length = 0u8;
+    for symbol in canonical_order(lengths) {
+        let length = lengths[symbol as usize];
+        assert!(length <= 64, "code length {} does not fit in a u64", length);
+        code <<= length - previous_length;
+        codes[symbol as usize] = (code, length);
+        code += 1;
+        previous_length = length;
+    }
+    codes
+}
+
+struct BitWriter {
+    bytes: Vec<u8>,
+    current: u8,
+    filled: u8,
+}
+
+impl BitWriter {
+    fn new(bytes: Vec<u8>) -> Self {
+        Self { bytes, current: 0, filled: 0 }
+    }
+
+    // Append the low `length` bits of `code`, most significant first
+    fn write(&mut self, code: u64, length: u8) {
+        for i in (0..length).rev() {
+            self.current = (self.current << 1) | ((code >> i) & 1) as u8;
+            self.filled += 1;
+            if self.filled == 8 {
+                self.bytes.push(self.current);
+                self.current = 0;
+                self.filled = 0;
+            }
+        }
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.bytes.push(self.current << (8 - self.filled));
+        }
+        self.bytes
+    }
+}
+
+pub fn compress(data: &[u8]) -> Vec<u8> {
+    let lengths = code_lengths(&count_frequencies(data));
+    let codes = canonical_codes(&lengths);
+    let symbols = canonical_order(&lengths);
+
+    let mut header = Vec::with_capacity(10 + 2 * symbols.len());
+    header.extend_from_slice(&(data.len() as u64).to_le_bytes());
+    header.extend_from_slice(&(symbols.len() as u16).to_le_bytes());
+    for &symbol in &symbols {
+        header.push(symbol);
+        header.push(lengths[symbol as usize]);
+    }
+
+    let mut writer = BitWriter::new(header);
+    for &byte in data {
+        let (code, length) = codes[byte as usize];
+        writer.write(code, length);
+    }
+    writer.finish()
+}
+
+fn take<'a>(input: &mut &'a [u8], count: usize) -> Result<&'a [u8], HuffmanError> {
+    if input.len() < count {
+        return Err(HuffmanError::Truncated);
+    }
+    let (head, rest) = input.split_at(count);
+    *input = rest;
+    Ok(head)
+}
+
+pub fn decompress(compressed: &[u8]) -> Result<Vec<u8>, HuffmanError> {
+    let mut input = compressed;
+    let mut length_bytes = [0u8; 8];
+    length_bytes.copy_from_slice(take(&mut input, 8)?);
+    let original_len = u64::from_le_bytes(length_bytes) as usize;
+    let symbol_count = u16::from_le_bytes([take(&mut input, 1)?[0], take(&mut input, 1)?[0]]) as usize;
+    if symbol_count > 256 {
+        return Err(HuffmanError::InvalidHeader(format!("{} symbols", symbol_count)));
+    }
+
+    let mut lengths = [0u8; 256];
+    for pair in take(&mut input, 2 * symbol_count)?.chunks(2) {
+        if pair[1] == 0 || pair[1] > 64 || lengths[pair[0] as usize] != 0 {
+            return Err(HuffmanError::InvalidHeader(format!("bad entry for byte {}", pair[0])));
+        }
+        lengths[pair[0] as usize] = pair[1];
+    }
+    if original_len > 0 && symbol_count == 0 {
+        return Err(HuffmanError::InvalidHeader("no symbols for non-empty data".to_string()));
+    }
+
+    // Per length: the first canonical code and where its symbols start in `symbols`
+    let symbols = canonical_order(&lengths);
+    let max_length = symbols.last().map_or(0, |&s| lengths[s as usize]) as usize;
+    let mut count_per_length = vec![0u64; max_length + 1];
+    for &s in &symbols {
+        count_per_length[lengths[s as usize] as usize] += 1;
+    }
+    let mut first_code = vec![0u64; max_length + 1];
+    let mut first_index = vec![0usize; max_length + 1];
+    let (mut code, mut index) = (0u64, 0usize);
+    for length in 1..=max_length {
+        code <<= 1;
+        first_code[length] = code;
+        first_index[length] = index;
+        code += count_per_length[length];
+        index += count_per_length[length] as usize;
+    }
+
+    // Each code is at least one bit, which bounds the output a corrupt header can claim
+    let mut output = Vec::with_capacity(original_len.min(8 * input.len()));
+    let mut bits = input.iter().flat_map(|&byte| (0..8).rev().map(move |i| (byte >> i) & 1));
+    while output.len() < original_len {
+        let mut code = 0u64;
+        let mut length = 0;
+        loop {
+            let bit = bits.next().ok_or(HuffmanError::Truncated)?;
+            code = (code << 1) | bit as u64;
+            length += 1;
+            if length > max_length {
+                return Err(HuffmanError::InvalidCode);
+            }
+            let offset = code.wrapping_sub(first_code[length]);
+            if code >= first_code[length] && offset < count_per_length[length] {
+                output.push(symbols[first_index[length] + offset as usize]);
+                break;
+            }
+        }
+    }
+    Ok(output)
+}
+
+fn run_cli(args: &[String]) -> Result<(), String> {
+    let (mode, input, output) = match args {
+        [mode, input, output] => (mode.as_str(), input, output),
+        _ => return Err("usage: huffman [encode|decode <input> <output>]".to_string()),
+    };
+    let data = fs::read(input).map_err(|e| format!("cannot read {}: {}", input, e))?;
+    let result = match mode {
+        "encode" => compress(&data),
+        "decode" => decompress(&data).map_err(|e| format!("cannot decode {}: {}", input, e))?,
+        _ => return Err(format!("unknown mode '{}', expected encode or decode", mode)),
+    };
+    fs::write(output, &result).map_err(|e| format!("cannot write {}: {}", output, e))?;
+    println!("{}: {} bytes -> {} bytes", mode, data.len(), result.len());
+    Ok(())
+}
+
+pub fn demo() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(message) = run_cli(&args) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    println!("=== Code table for \"abracadabra\" ===");
+    let text = b"abracadabra";
+    let frequencies = count_frequencies(text);
+    let lengths = code_lengths(&frequencies);
+    let codes = canonical_codes(&lengths);
+    for symbol in canonical_order(&lengths) {
+        let (code, length) = codes[symbol as usize];
+        println!("  '{}'  count {}  code {:0width$b}",
+                 symbol as char, frequencies[symbol as usize], code, width = length as usize);
+    }
+    let compressed = compress(text);
+    let header = 10 + 2 * canonical_order(&lengths).len();
+    println!("11 bytes -> {} bytes ({} header + {} payload)",
+             compressed.len(), header, compressed.len() - header);
+    println!("Round trip: {:?}", String::from_utf8(decompress(&compressed).unwrap()).unwrap());
+
+    println!("\n=== Compressing English text ===");
+    let paragraph = "It was the best of times, it was the worst of times, it was the age of \
+                     wisdom, it was the age of foolishness, it was the epoch of belief, it was \
+                     the epoch of incredulity, it was the season of Light, it was the season of \
+                     Darkness, it was the spring of hope, it was the winter of despair."
+        .repeat(20);
+    let packed = compress(paragraph.as_bytes());
+    let unpacked = decompress(&packed).unwrap();
+    println!("{} bytes -> {} bytes ({:.1}% of original), {:.2} bits per byte",
+             paragraph.len(), packed.len(), 100.0 * packed.len() as f64 / paragraph.len() as f64,
+             8.0 * packed.len() as f64 / paragraph.len() as f64);
+    println!("Round trip exact: {}", unpacked == paragraph.as_bytes());
+
+    println!("\n=== Edge cases ===");
+    for (name, data) in [
+        ("empty", Vec::new()),
+        ("one byte", vec![42]),
+        ("one symbol x1000", vec![7; 1000]),
+        ("all 256 values", (0..=255).collect()),
+    ]
+    .iter()
+    {
+        let packed = compress(data);
+        println!("{:>16}: {:>4} -> {:>4} bytes, round trip {}",
+                 name, data.len(), packed.len(), decompress(&packed).as_ref() == Ok(data));
+    }
+
+    println!("\n=== Skewed frequencies give long codes ===");
+    // Fibonacci counts produce the most lopsided tree possible
+    let mut skewed = Vec::new();
+    let (mut a, mut b) = (1usize, 1usize);
+    for symbol in 0..20u8 {
+        skewed.extend(std::iter::repeat_n(symbol, a));
+        let next = a + b;
+        a = b;
+        b = next;
+    }
+    let lengths = code_lengths(&count_frequencies(&skewed));
+    println!("20 symbols with Fibonacci counts: code lengths {:?}", &lengths[..20]);
+    println!("Round trip: {}", decompress(&compress(&skewed)).unwrap() == skewed);
+
+    println!("\n=== Corrupted input is rejected ===");
+    let mut damaged = compress(b"hello, huffman");
+    println!("Truncated:  {}", decompress(&damaged[..damaged.len() - 2]).unwrap_err());
+    damaged[9] = 0xFF;
+    println!("Bad header: {}", decompress(&damaged).unwrap_err());
+    println!("Too short:  {}", decompress(&[1, 2, 3]).unwrap_err());
+}
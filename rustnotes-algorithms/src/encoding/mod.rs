@@ -0,0 +1,10 @@
+// Encodings and Compression
+
+pub mod base64;
+pub mod huffman;
+pub mod run_length_encoding;
+pub mod serialization;
+
+pub use self::base64::Base64;
+pub use self::run_length_encoding::{RleDecoder, RleEncoder};
+pub use self::serialization::{Decode, Encode};
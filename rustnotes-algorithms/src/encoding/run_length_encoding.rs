@@ -0,0 +1,332 @@
+// Run-Length Encoding
+//
+// Replaces runs of repeated values with a count and one copy of the value:
+// "WWWWWWWWWWWWB" becomes "12WB". It is the simplest compressor there is, works
+// well on data with long runs (simple images, sparse tables, padding), and makes
+// a useful preprocessing step in front of an entropy coder such as Huffman.
+//
+// Two formats:
+// - Bytes, PackBits style: each packet starts with a header byte h
+//     h = 0..=127    the next h + 1 bytes are literals, copied as-is
+//     h = 129..=255  the next byte repeats 257 - h times (2..=128)
+//     h = 128        no-op (never produced, skipped when decoding)
+//   Literal packets are what keep data without runs from doubling in size:
+//   worst case is one extra byte per 128
+// - Text, human readable: runs of 3+ characters become <count><char>, shorter
+//   runs stay as-is, and digits or backslashes in the input are escaped with a
+//   backslash so they cannot be mistaken for counts ("aaa111\" -> "3a3\1\\")
+//
+// Design choices:
+// - The byte codec is built on streaming iterator adapters: RleEncoder wraps any
+//   Iterator<Item = u8> and yields encoded bytes; RleDecoder wraps encoded bytes
+//   and yields Result<u8, RleError>. Neither needs the whole input in memory
+// - The encoder keeps at most 128 bytes of lookahead (one maximal packet)
+// - Runs of two are folded into literal packets, since a 2-byte repeat packet
+//   would save nothing
+// - encode/decode (slices) and encode_str/decode_str are thin wrappers
+// - Trade-off: great on long runs, useless on text or already-compressed data
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::iter::Peekable;
+
+const MAX_PACKET: usize = 128;
+const MIN_RUN: usize = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RleError {
+    // Input ended in the middle of a packet or escape sequence
+    UnexpectedEnd,
+    // A backslash followed by something other than a digit or backslash
+    InvalidEscape(char),
+    // A text count of zero, or one too large for usize
+    InvalidCount(String),
+}
+
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RleError::UnexpectedEnd => write!(f, "encoded data ends in the middle of a run"),
+            RleError::InvalidEscape(c) => write!(f, "invalid escape sequence '\\{}'", c),
+            RleError::InvalidCount(count) => write!(f, "invalid run count '{}'", count),
+        }
+    }
+}
+
+impl std::error::Error for RleError {}
+
+// Streaming encoder: yields PackBits-encoded bytes for the wrapped input
+pub struct RleEncoder<I: Iterator<Item = u8>> {
+    input: I,
+    lookahead: VecDeque<u8>,
+    output: VecDeque<u8>, // Bytes of the current packet not yet yielded
+}
+
+impl<I: Iterator<Item = u8>> RleEncoder<I> {
+    pub fn new(input: I) -> Self {
+        Self { input, lookahead: VecDeque::new(), output: VecDeque::new() }
+    }
+
+    // Make sure at least `count` bytes are buffered, if the input has them
+    fn fill(&mut self, count: usize) {
+        while self.lookahead.len() < count {
+            match self.input.next() {
+                Some(byte) => self.lookahead.push_back(byte),
+                None => break,
+            }
+        }
+    }
+
+    // Length of the run of equal bytes at the front of the lookahead (at most 128)
+    fn run_length(&mut self) -> usize {
+        let mut length = 0;
+        loop {
+            self.fill(length + 1);
+            match self.lookahead.get(length) {
+                Some(&byte) if length < MAX_PACKET && byte == self.lookahead[0] => length += 1,
+                _ => return length,
+            }
+        }
+    }
+
+    // Encode the next packet into self.output; false at end of input
+    fn next_packet(&mut self) -> bool {
+        let run = self.run_length();
+        if run == 0 {
+            return false;
+        }
+        if run >= MIN_RUN {
+            let byte = self.lookahead[0];
+            self.lookahead.drain(..run);
+            self.output.push_back((257 - run) as u8);
+            self.output.push_back(byte);
+            return true;
+        }
+
+        // Gather literals until the packet is full or a worthwhile run begins
+        let mut literals = Vec::new();
+        while literals.len() < MAX_PACKET {
+            self.fill(1);
+            if self.lookahead.is_empty() || self.run_length() >= MIN_RUN {
+                break;
+            }
+            literals.push(self.lookahead.pop_front().unwrap());
+        }
+        self.output.push_back((literals.len() - 1) as u8);
+        self.output.extend(literals);
+        true
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for RleEncoder<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        if self.output.is_empty() && !self.next_packet() {
+            return None;
+        }
+        self.output.pop_front()
+    }
+}
+
+enum DecodeState {
+    Header,
+    Literal(usize), // Literal bytes still to copy
+    Repeat(u8, usize),
+    Failed,
+}
+
+// Streaming decoder: yields the original bytes, or one error and then stops
+pub struct RleDecoder<I: Iterator<Item = u8>> {
+    input: I,
+    state: DecodeState,
+}
+
+impl<I: Iterator<Item = u8>> RleDecoder<I> {
+    pub fn new(input: I) -> Self {
+        Self { input, state: DecodeState::Header }
+    }
+}
+
+impl<I: Iterator<Item = u8>> Iterator for RleDecoder<I> {
+    type Item = Result<u8, RleError>;
+
+    fn next(&mut self) -> Option<Result<u8, RleError>> {
+        loop {
+            match self.state {
+                DecodeState::Failed => return None,
+                DecodeState::Repeat(byte, remaining) => {
+                    self.state = if remaining > 1 {
+                        DecodeState::Repeat(byte, remaining - 1)
+                    } else {
+                        DecodeState::Header
+                    };
+                    return Some(Ok(byte));
+                }
+                DecodeState::Literal(remaining) => {
+                    let byte = match self.input.next() {
+                        Some(byte) => byte,
+                        None => {
+                            self.state = DecodeState::Failed;
+                            return Some(Err(RleError::UnexpectedEnd));
+                        }
+                    };
+                    self.state = if remaining > 1 {
+                        DecodeState::Literal(remaining - 1)
+                    } else {
+                        DecodeState::Header
+                    };
+                    return Some(Ok(byte));
+                }
+                DecodeState::Header => {
+                    let header = self.input.next()? as usize;
+                    self.state = match header {
+                        0..=127 => DecodeState::Literal(header + 1),
+                        128 => DecodeState::Header,
+                        _ => match self.input.next() {
+                            Some(byte) => DecodeState::Repeat(byte, 257 - header),
+                            None => {
+                                self.state = DecodeState::Failed;
+                                return Some(Err(RleError::UnexpectedEnd));
+                            }
+                        },
+                    };
+                }
+            }
+        }
+    }
+}
+
+pub fn encode(data: &[u8]) -> Vec<u8> {
+    RleEncoder::new(data.iter().copied()).collect()
+}
+
+pub fn decode(encoded: &[u8]) -> Result<Vec<u8>, RleError> {
+    RleDecoder::new(encoded.iter().copied()).collect()
+}
+
+// Text format: runs of 3+ become <count><char>; digits and '\' are escaped
+pub fn encode_str(text: &str) -> String {
+    let mut encoded = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let mut run = 1;
+        while chars.peek() == Some(&c) {
+            chars.next();
+            run += 1;
+        }
+        let escaped = if c.is_ascii_digit() || c == '\\' { format!("\\{}", c) } else { c.to_string() };
+        if run >= MIN_RUN {
+            encoded.push_str(&run.to_string());
+            encoded.push_str(&escaped);
+        } else {
+            for _ in 0..run {
+                encoded.push_str(&escaped);
+            }
+        }
+    }
+    encoded
+}
+
+// One possibly escaped character
+fn read_char<I: Iterator<Item = char>>(chars: &mut Peekable<I>) -> Result<char, RleError> {
+    match chars.next() {
+        Some('\\') => match chars.next() {
+            Some(c) if c.is_ascii_digit() || c == '\\' => Ok(c),
+            Some(c) => Err(RleError::InvalidEscape(c)),
+            None => Err(RleError::UnexpectedEnd),
+        },
+        Some(c) => Ok(c),
+        None => Err(RleError::UnexpectedEnd),
+    }
+}
+
+pub fn decode_str(encoded: &str) -> Result<String, RleError> {
+    let mut decoded = String::new();
+    let mut chars = encoded.chars().peekable();
+    while chars.peek().is_some() {
+        let mut digits = String::new();
+        while let Some(&c) = chars.peek() {
+            if !c.is_ascii_digit() {
+                break;
+            }
+            digits.push(c);
+            chars.next();
+        }
+        let count = if digits.is_empty() {
+            1
+        } else {
+            match digits.parse::<usize>() {
+                Ok(n) if n > 0 => n,
+                _ => return Err(RleError::InvalidCount(digits)),
+            }
+        };
+        let c = read_char(&mut chars)?;
+        decoded.extend(std::iter::repeat_n(c, count));
+    }
+    Ok(decoded)
+}
+
+pub fn demo() {
+    println!("=== Text format ===");
+    for text in ["WWWWWWWWWWWWBWWWWWWWWWWWWBBBWWWWWWWWWWWWWWWWWWWWWWWWBWWWWWWWWWWWWWW",
+                 "aabcccccaaa", "abc", "room 101: 555 rings\\", ""].iter() {
+        let encoded = encode_str(text);
+        let decoded = decode_str(&encoded).unwrap();
+        println!("{:?}\n  -> {:?} ({} -> {} chars), round trip {}",
+                 text, encoded, text.chars().count(), encoded.chars().count(), decoded == *text);
+    }
+    for bad in ["3", "\\x", "0a", "12\\"].iter() {
+        println!("decode_str({:?}): {}", bad, decode_str(bad).unwrap_err());
+    }
+
+    println!("\n=== Byte format (PackBits) ===");
+    let data = b"\x00\x00\x00\x00\x00\x00\x00\x00ABCDE\xFF\xFF\xFF\xFF\xFF\xFF\x01\x02";
+    let packed = encode(data);
+    println!("Input  ({} bytes): {:02X?}", data.len(), data);
+    println!("Packed ({} bytes): {:02X?}", packed.len(), packed);
+    println!("Round trip: {}", decode(&packed).unwrap() == data.to_vec());
+
+    println!("\n=== Best and worst cases ===");
+    let zeros = vec![0u8; 10_000];
+    let mut state: u64 = 0x5EED;
+    let noise: Vec<u8> = (0..10_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect();
+    // A tiny "image": rows of mostly background with a few strokes
+    let mut image = Vec::new();
+    for row in 0..64 {
+        for col in 0..64 {
+            image.push(if col == row || (20..28).contains(&col) { 0xFF } else { 0x00 });
+        }
+    }
+    for (name, data) in [("10,000 zeros", &zeros), ("random noise", &noise), ("64x64 bitmap", &image)].iter() {
+        let packed = encode(data);
+        println!("{:>13}: {:>6} -> {:>6} bytes, round trip {}",
+                 name, data.len(), packed.len(), decode(&packed).as_ref() == Ok(*data));
+    }
+
+    println!("\n=== Streaming ===");
+    // Encode a generated stream without ever materializing it, then decode lazily
+    let source = (0..1_000_000u32).map(|i| ((i / 1000) % 7) as u8);
+    let encoded: Vec<u8> = RleEncoder::new(source).collect();
+    println!("1,000,000 generated bytes -> {} encoded bytes", encoded.len());
+    let first_run: Vec<u8> = RleDecoder::new(encoded.iter().copied())
+        .take(1005)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    println!("Decoded lazily: first 1005 bytes end with {:?}", &first_run[995..]);
+    let total = RleDecoder::new(encoded.iter().copied()).filter(|b| b.is_ok()).count();
+    println!("Total decoded: {}", total);
+
+    println!("\n=== Corrupted input ===");
+    println!("Literal packet cut short: {:?}", decode(&[4, b'a', b'b']));
+    println!("Repeat header without byte: {}", decode(&[250]).unwrap_err());
+    let results: Vec<_> = RleDecoder::new([0u8, b'x', 3].iter().copied()).collect();
+    println!("Streaming decoder yields values, then the error, then stops: {:?}", results);
+}
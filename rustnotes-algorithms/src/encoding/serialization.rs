@@ -0,0 +1,576 @@
+// Binary Serialization
+//
+// A small Encode/Decode trait pair that turns values into a compact binary
+// format and back, written from scratch as an alternative to serde + bincode
+// for the types in this collection.
+//
+// The format, by type:
+//   u8, i8             1 byte as is
+//   u16 .. u64, usize  LEB128 varint: 7 bits per byte, high bit = "more follows"
+//   i16 .. i64, isize  zigzag-mapped (0, -1, 1, -2, ... -> 0, 1, 2, 3, ...) then varint
+//   f32, f64           IEEE 754 bits, little-endian
+//   bool               1 byte, 0 or 1
+//   char               varint of the code point
+//   String, Vec<T>     varint length, then the bytes / the elements
+//   Option<T>          1 byte tag (0 = None, 1 = Some) then the value
+//   tuples             the fields in order, no framing
+//   lists, trees       varint length, then the elements in iteration order
+//
+//   300u32      -> ac 02            (0b10_0101100: low 7 bits + continuation, then 2)
+//   -3i32       -> 05               (zigzag 5)
+//   "hi"        -> 02 68 69
+//   vec![1u8,2] -> 02 01 02
+//
+// Design choices:
+// - Encode appends to a Vec<u8>; Decode reads from a Reader that tracks the
+//   offset, so errors can say where the input went wrong
+// - The format is not self-describing: the reader must know the type it
+//   expects, exactly as with bincode. That keeps small values to a byte or two
+// - Varints make small integers and lengths cheap, which is the common case
+// - Decoding never trusts a length prefix for allocation: capacity is capped
+//   by the bytes actually left, so a corrupt "length = 2^60" fails cleanly
+//   instead of aborting on out-of-memory
+// - Trees are stored as their sorted contents, not their shape. BinaryTree is
+//   rebuilt by inserting medians first, so a decoded tree is balanced even if
+//   the original had degenerated into a chain; AvlTree rebalances itself
+// - from_bytes() rejects trailing bytes, so a value cannot silently decode
+//   from a prefix of a longer message
+// - Trade-off: no versioning, field names or schema evolution - adding a field
+//   to a type breaks every previously written message
+
+use rustnotes_collections::lists::linked_list::LinkedList;
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use rustnotes_collections::trees::binary_tree::BinaryTree;
+use std::convert::TryFrom;
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DecodeError {
+    // The input ended while `needed` more bytes were expected at this offset
+    UnexpectedEnd { offset: usize, needed: usize },
+    // A varint longer than 10 bytes or wider than 64 bits
+    VarintOverflow { offset: usize },
+    // A well-formed integer that does not fit the target type
+    OutOfRange { offset: usize, value: u64 },
+    // A tag byte (bool, Option) other than 0 or 1
+    InvalidTag { offset: usize, tag: u8 },
+    // String bytes that are not UTF-8, or a char that is not a Unicode scalar
+    InvalidText { offset: usize },
+    // A tree whose encoded contents held the same value twice
+    DuplicateValue { offset: usize },
+    // from_bytes() decoded a value but input remained
+    TrailingBytes { offset: usize, count: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd { offset, needed } => {
+                write!(f, "input ended at offset {} with {} more byte(s) expected", offset, needed)
+            }
+            DecodeError::VarintOverflow { offset } => write!(f, "varint too long at offset {}", offset),
+            DecodeError::OutOfRange { offset, value } => {
+                write!(f, "value {} at offset {} does not fit the target type", value, offset)
+            }
+            DecodeError::InvalidTag { offset, tag } => write!(f, "invalid tag byte {} at offset {}", tag, offset),
+            DecodeError::InvalidText { offset } => write!(f, "invalid text at offset {}", offset),
+            DecodeError::DuplicateValue { offset } => write!(f, "duplicate tree value ending at offset {}", offset),
+            DecodeError::TrailingBytes { offset, count } => {
+                write!(f, "{} trailing byte(s) after the value at offset {}", count, offset)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// A cursor over the input bytes
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes, offset: 0 }
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+
+    pub fn read_bytes(&mut self, count: usize) -> Result<&'a [u8], DecodeError> {
+        if count > self.remaining() {
+            return Err(DecodeError::UnexpectedEnd { offset: self.offset, needed: count - self.remaining() });
+        }
+        let bytes = &self.bytes[self.offset..self.offset + count];
+        self.offset += count;
+        Ok(bytes)
+    }
+
+    pub fn read_byte(&mut self) -> Result<u8, DecodeError> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    pub fn read_varint(&mut self) -> Result<u64, DecodeError> {
+        let start = self.offset;
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            // The tenth byte only has room for the top bit of a u64
+            if shift == 63 && byte > 1 {
+                return Err(DecodeError::VarintOverflow { offset: start });
+            }
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::VarintOverflow { offset: start })
+    }
+
+    // A length prefix, plus the capacity that is safe to reserve for it
+    fn read_length(&mut self) -> Result<(usize, usize), DecodeError> {
+        let start = self.offset;
+        let length = self.read_varint()?;
+        let length = usize::try_from(length).map_err(|_| DecodeError::OutOfRange { offset: start, value: length })?;
+        Ok((length, length.min(self.remaining())))
+    }
+}
+
+pub fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    (value >> 1) as i64 ^ -((value & 1) as i64)
+}
+
+pub trait Encode {
+    fn encode(&self, out: &mut Vec<u8>);
+}
+
+pub trait Decode: Sized {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError>;
+}
+
+pub fn to_bytes<T: Encode + ?Sized>(value: &T) -> Vec<u8> {
+    let mut out = Vec::new();
+    value.encode(&mut out);
+    out
+}
+
+// Decode a whole message: the value must use every byte
+pub fn from_bytes<T: Decode>(bytes: &[u8]) -> Result<T, DecodeError> {
+    let mut reader = Reader::new(bytes);
+    let value = T::decode(&mut reader)?;
+    match reader.remaining() {
+        0 => Ok(value),
+        count => Err(DecodeError::TrailingBytes { offset: reader.offset(), count }),
+    }
+}
+
+impl Encode for u8 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self);
+    }
+}
+
+impl Decode for u8 {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        reader.read_byte()
+    }
+}
+
+impl Encode for i8 {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl Decode for i8 {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        Ok(reader.read_byte()? as i8)
+    }
+}
+
+macro_rules! varint_unsigned {
+    ($($t:ty),*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                write_varint(out, *self as u64);
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                let offset = reader.offset();
+                let value = reader.read_varint()?;
+                <$t>::try_from(value).map_err(|_| DecodeError::OutOfRange { offset, value })
+            }
+        }
+    )*};
+}
+
+macro_rules! varint_signed {
+    ($($t:ty),*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                write_varint(out, zigzag(*self as i64));
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                let offset = reader.offset();
+                let value = reader.read_varint()?;
+                <$t>::try_from(unzigzag(value)).map_err(|_| DecodeError::OutOfRange { offset, value })
+            }
+        }
+    )*};
+}
+
+varint_unsigned!(u16, u32, u64, usize);
+varint_signed!(i16, i32, i64, isize);
+
+macro_rules! fixed_float {
+    ($($t:ty),*) => {$(
+        impl Encode for $t {
+            fn encode(&self, out: &mut Vec<u8>) {
+                out.extend_from_slice(&self.to_le_bytes());
+            }
+        }
+
+        impl Decode for $t {
+            fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                let mut bits = [0u8; std::mem::size_of::<$t>()];
+                bits.copy_from_slice(reader.read_bytes(std::mem::size_of::<$t>())?);
+                Ok(<$t>::from_le_bytes(bits))
+            }
+        }
+    )*};
+}
+
+fixed_float!(f32, f64);
+
+impl Encode for bool {
+    fn encode(&self, out: &mut Vec<u8>) {
+        out.push(*self as u8);
+    }
+}
+
+impl Decode for bool {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let offset = reader.offset();
+        match reader.read_byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            tag => Err(DecodeError::InvalidTag { offset, tag }),
+        }
+    }
+}
+
+impl Encode for char {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, *self as u64);
+    }
+}
+
+impl Decode for char {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let offset = reader.offset();
+        let code = u32::decode(reader)?;
+        char::from_u32(code).ok_or(DecodeError::InvalidText { offset })
+    }
+}
+
+impl Encode for str {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        out.extend_from_slice(self.as_bytes());
+    }
+}
+
+impl Encode for String {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_str().encode(out);
+    }
+}
+
+impl Decode for String {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let (length, _) = reader.read_length()?;
+        let offset = reader.offset();
+        let bytes = reader.read_bytes(length)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidText { offset })
+    }
+}
+
+impl<T: Encode> Encode for [T] {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        for item in self {
+            item.encode(out);
+        }
+    }
+}
+
+impl<T: Encode> Encode for Vec<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        self.as_slice().encode(out);
+    }
+}
+
+impl<T: Decode> Decode for Vec<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let (length, capacity) = reader.read_length()?;
+        let mut items = Vec::with_capacity(capacity);
+        for _ in 0..length {
+            items.push(T::decode(reader)?);
+        }
+        Ok(items)
+    }
+}
+
+impl<T: Encode> Encode for Option<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        match self {
+            None => out.push(0),
+            Some(value) => {
+                out.push(1);
+                value.encode(out);
+            }
+        }
+    }
+}
+
+impl<T: Decode> Decode for Option<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let offset = reader.offset();
+        match reader.read_byte()? {
+            0 => Ok(None),
+            1 => Ok(Some(T::decode(reader)?)),
+            tag => Err(DecodeError::InvalidTag { offset, tag }),
+        }
+    }
+}
+
+macro_rules! tuple {
+    ($($name:ident),+) => {
+        impl<$($name: Encode),+> Encode for ($($name,)+) {
+            #[allow(non_snake_case)]
+            fn encode(&self, out: &mut Vec<u8>) {
+                let ($($name,)+) = self;
+                $($name.encode(out);)+
+            }
+        }
+
+        impl<$($name: Decode),+> Decode for ($($name,)+) {
+            fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+                Ok(($($name::decode(reader)?,)+))
+            }
+        }
+    };
+}
+
+tuple!(A, B);
+tuple!(A, B, C);
+tuple!(A, B, C, D);
+
+// Lists are written front to back
+impl<T: Encode> Encode for LinkedList<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        for item in self.iter() {
+            item.encode(out);
+        }
+    }
+}
+
+impl<T: Decode> Decode for LinkedList<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        // push() adds at the front, so push the decoded items in reverse
+        let items = Vec::<T>::decode(reader)?;
+        let mut list = LinkedList::new();
+        for item in items.into_iter().rev() {
+            list.push(item);
+        }
+        Ok(list)
+    }
+}
+
+// Trees are written as their in-order contents
+impl<T: Encode + Ord + Clone> Encode for BinaryTree<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        for value in self.iter() {
+            value.encode(out);
+        }
+    }
+}
+
+// Insert the median of each range before its halves, giving a balanced tree
+fn insert_medians<T: Ord + Clone>(tree: &mut BinaryTree<T>, values: &[T]) {
+    if values.is_empty() {
+        return;
+    }
+    let mid = values.len() / 2;
+    tree.insert(values[mid].clone());
+    insert_medians(tree, &values[..mid]);
+    insert_medians(tree, &values[mid + 1..]);
+}
+
+impl<T: Decode + Ord + Clone> Decode for BinaryTree<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let mut values = Vec::<T>::decode(reader)?;
+        // A mirrored tree iterates in descending order; the copy is a plain BST
+        values.sort();
+        let mut tree = BinaryTree::new();
+        insert_medians(&mut tree, &values);
+        if tree.len() != values.len() {
+            return Err(DecodeError::DuplicateValue { offset: reader.offset() });
+        }
+        Ok(tree)
+    }
+}
+
+impl<T: Encode + Ord + Clone + fmt::Display + fmt::Debug> Encode for AvlTree<T> {
+    fn encode(&self, out: &mut Vec<u8>) {
+        write_varint(out, self.len() as u64);
+        for value in self.iter() {
+            value.encode(out);
+        }
+    }
+}
+
+impl<T: Decode + Ord + Clone + fmt::Display + fmt::Debug> Decode for AvlTree<T> {
+    fn decode(reader: &mut Reader) -> Result<Self, DecodeError> {
+        let values = Vec::<T>::decode(reader)?;
+        let count = values.len();
+        let mut tree = AvlTree::new();
+        for value in values {
+            tree.insert(value);
+        }
+        if tree.len() != count {
+            return Err(DecodeError::DuplicateValue { offset: reader.offset() });
+        }
+        Ok(tree)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(" ")
+}
+
+// Encode, decode, and report whether the value survived the trip
+fn round_trip<T: Encode + Decode + PartialEq + fmt::Debug>(value: T) -> bool {
+    let bytes = to_bytes(&value);
+    let ok = from_bytes::<T>(&bytes).as_ref() == Ok(&value);
+    println!("{:<30} -> {:<30} {}", format!("{:?}", value), hex(&bytes), if ok { "ok" } else { "MISMATCH" });
+    ok
+}
+
+pub fn demo() {
+    println!("=== Primitives ===");
+    round_trip(0u32);
+    round_trip(127u32);
+    round_trip(128u32);
+    round_trip(300u32);
+    round_trip(u64::MAX);
+    round_trip(-1i32);
+    round_trip(-3i64);
+    round_trip(i64::MIN);
+    round_trip(200u8);
+    round_trip(-100i8);
+    round_trip(true);
+    round_trip('é');
+    round_trip(1.5f64);
+
+    println!("\n=== Strings, vectors, options, tuples ===");
+    round_trip(String::from("hi"));
+    round_trip(vec![1u8, 2, 3]);
+    round_trip(vec![Some(1u16), None, Some(1000)]);
+    round_trip((String::from("x"), -2i32, false));
+    round_trip(vec![(1u32, String::from("one")), (2, String::from("two"))]);
+    round_trip(Vec::<Vec<i32>>::new());
+
+    println!("\n=== Varints on random integers ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut all_ok = true;
+    let (mut varint_bytes, mut fixed_bytes) = (0, 0);
+    for _ in 0..100_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        // Mostly small magnitudes, as real lengths and counters are
+        let value = (state as i64) >> (state % 64);
+        let bytes = to_bytes(&value);
+        all_ok &= from_bytes::<i64>(&bytes) == Ok(value);
+        varint_bytes += bytes.len();
+        fixed_bytes += 8;
+    }
+    println!("100000 i64 round trips: {}", all_ok);
+    println!("Varint size: {} bytes vs {} fixed-width ({:.0}%)",
+             varint_bytes, fixed_bytes, 100.0 * varint_bytes as f64 / fixed_bytes as f64);
+
+    println!("\n=== Collections from this crate ===");
+    let mut list = LinkedList::new();
+    for word in ["third", "second", "first"].iter() {
+        list.push(word.to_string());
+    }
+    let bytes = to_bytes(&list);
+    let copy: LinkedList<String> = from_bytes(&bytes).unwrap();
+    println!("LinkedList {:?} -> {} bytes -> {:?}",
+             list.iter().collect::<Vec<_>>(), bytes.len(), copy.iter().collect::<Vec<_>>());
+
+    // Sorted insertion degenerates a plain BST into a chain
+    let mut chain = BinaryTree::new();
+    for n in 0..1000u32 {
+        chain.insert(n);
+    }
+    let bytes = to_bytes(&chain);
+    let copy: BinaryTree<u32> = from_bytes(&bytes).unwrap();
+    println!("BinaryTree of 1000 sorted inserts -> {} bytes", bytes.len());
+    println!("  contents equal: {}", chain.iter().eq(copy.iter()));
+    println!("  height before: {}, after decoding: {}", chain.stats().height, copy.stats().height);
+
+    let mut avl = AvlTree::new();
+    for word in ["pear", "apple", "fig", "kiwi", "banana"].iter() {
+        avl.insert(word.to_string());
+    }
+    let bytes = to_bytes(&avl);
+    let copy: AvlTree<String> = from_bytes(&bytes).unwrap();
+    println!("AvlTree {:?} -> {} bytes, copy balanced: {}, equal: {}",
+             avl.iter().collect::<Vec<_>>(), bytes.len(), copy.is_balanced(), avl.iter().eq(copy.iter()));
+
+    println!("\n=== Malformed input ===");
+    let cases: Vec<(&str, Result<(), DecodeError>)> = vec![
+        ("truncated string", from_bytes::<String>(&[5, b'a', b'b']).map(drop)),
+        ("bool tag 2", from_bytes::<bool>(&[2]).map(drop)),
+        ("invalid UTF-8", from_bytes::<String>(&[2, 0xc3, 0x28]).map(drop)),
+        ("u16 out of range", from_bytes::<Vec<u16>>(&[1, 0xff, 0xff, 0xff, 0x01]).map(drop)),
+        ("11-byte varint", from_bytes::<u64>(&[0xff; 11]).map(drop)),
+        ("surrogate char", from_bytes::<char>(&[0x80, 0xb0, 0x03]).map(drop)),
+        ("trailing bytes", from_bytes::<u32>(&[1, 2, 3]).map(drop)),
+        ("length 2^62, 1 byte", from_bytes::<Vec<u64>>(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x40, 7]).map(drop)),
+        ("tree with a duplicate", from_bytes::<AvlTree<u8>>(&[3, 1, 2, 2]).map(drop)),
+    ];
+    for (name, result) in cases {
+        match result {
+            Ok(()) => println!("{:<22} unexpectedly decoded", name),
+            Err(e) => println!("{:<22} error: {}", name, e),
+        }
+    }
+}
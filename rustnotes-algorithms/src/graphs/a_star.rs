@@ -0,0 +1,359 @@
+// A* Pathfinding on a Grid
+//
+// Finds a cheapest path between two cells of a 2-D grid with walls and terrain
+// of different cost. A* is Dijkstra with a sense of direction: a cell's priority
+// is not just the cost g of reaching it, but g plus h, a heuristic estimate of
+// the remaining cost to the goal. Cells that lead away from the goal get a
+// worse priority and are often never expanded at all.
+//
+//   f(cell) = g(cell) + h(cell)      g: known cost from the start
+//                                    h: estimated cost to the goal
+//
+// If h never overestimates (it is "admissible"), the first time the goal is
+// taken from the queue its path is optimal. h = 0 gives plain Dijkstra; a
+// tighter h expands fewer cells; an h that overestimates is faster still but
+// can return a longer path.
+//
+// Design choices:
+// - The open set is the IndexedHeap from heaps::indexed_heap keyed by cell index
+//   (row * width + col), so a cheaper route to a queued cell is a
+//   decrease_key rather than a duplicate entry
+// - The heuristic is an enum (Zero, Manhattan, Euclidean, Octile) and movement
+//   is 4- or 8-directional, so the demo can show which pairs are admissible:
+//   Manhattan is exact for 4-way moves but overestimates diagonal ones
+// - Terrain: '.' costs 1 to enter, '~' (swamp) costs 3, '#' is a wall; the
+//   heuristics assume the cheapest terrain, which keeps them admissible
+// - Diagonal moves cost sqrt(2) times the terrain cost and may not cut a
+//   corner: both orthogonal cells beside the diagonal must be passable
+// - Ties in f are broken toward larger g (closer to the goal), which avoids
+//   expanding whole plateaus of equal-f cells on open ground
+// - Trade-off: stores g and a parent for every cell of the grid, O(width x
+//   height) memory regardless of how much of the grid the search touches
+
+use rustnotes_collections::heaps::indexed_heap::IndexedHeap;
+use std::cmp::Ordering;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Heuristic {
+    Zero,
+    Manhattan,
+    Euclidean,
+    // Exact distance on an open 8-way grid: straight steps plus sqrt(2) diagonals
+    Octile,
+}
+
+impl Heuristic {
+    pub fn estimate(self, a: (usize, usize), b: (usize, usize)) -> f64 {
+        let dr = (a.0 as f64 - b.0 as f64).abs();
+        let dc = (a.1 as f64 - b.1 as f64).abs();
+        match self {
+            Heuristic::Zero => 0.0,
+            Heuristic::Manhattan => dr + dc,
+            Heuristic::Euclidean => (dr * dr + dc * dc).sqrt(),
+            Heuristic::Octile => dr.max(dc) + (std::f64::consts::SQRT_2 - 1.0) * dr.min(dc),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Movement {
+    FourWay,
+    EightWay,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Open,
+    Swamp,
+    Wall,
+}
+
+impl Cell {
+    fn cost(self) -> Option<f64> {
+        match self {
+            Cell::Open => Some(1.0),
+            Cell::Swamp => Some(3.0),
+            Cell::Wall => None,
+        }
+    }
+}
+
+pub struct Grid {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Path {
+    pub cells: Vec<(usize, usize)>,
+    pub cost: f64,
+    pub expanded: usize, // Cells taken from the open set
+}
+
+// Priority in the open set: lower f first, then higher g
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Priority {
+    f: f64,
+    g: f64,
+}
+
+impl PartialOrd for Priority {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.f.total_cmp(&other.f).then(other.g.total_cmp(&self.g)))
+    }
+}
+
+// A 'S' or 'G' position found while parsing, if the map has one
+pub type Endpoint = Option<(usize, usize)>;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError(String);
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+impl Grid {
+    // Parse rows of '.', '~' and '#'; 'S' and 'G' mark open start and goal
+    // cells, which are returned alongside the grid
+    pub fn parse(text: &str) -> Result<(Grid, Endpoint, Endpoint), ParseError> {
+        let rows: Vec<&str> = text.lines().map(str::trim).filter(|line| !line.is_empty()).collect();
+        let width = rows.first().map_or(0, |row| row.chars().count());
+        let mut cells = Vec::with_capacity(width * rows.len());
+        let (mut start, mut goal) = (None, None);
+        for (r, row) in rows.iter().enumerate() {
+            if row.chars().count() != width {
+                return Err(ParseError(format!("row {} has {} cells, expected {}", r, row.chars().count(), width)));
+            }
+            for (c, ch) in row.chars().enumerate() {
+                cells.push(match ch {
+                    '.' => Cell::Open,
+                    '~' => Cell::Swamp,
+                    '#' => Cell::Wall,
+                    'S' => {
+                        start = Some((r, c));
+                        Cell::Open
+                    }
+                    'G' => {
+                        goal = Some((r, c));
+                        Cell::Open
+                    }
+                    other => return Err(ParseError(format!("unexpected {:?} at row {}, column {}", other, r, c))),
+                });
+            }
+        }
+        Ok((Grid { width, height: rows.len(), cells }, start, goal))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    fn cell(&self, (r, c): (usize, usize)) -> Cell {
+        self.cells[r * self.width + c]
+    }
+
+    pub fn is_wall(&self, position: (usize, usize)) -> bool {
+        self.cell(position) == Cell::Wall
+    }
+
+    // Reachable neighbours of a cell with the cost of moving there
+    fn moves(&self, (r, c): (usize, usize), movement: Movement) -> Vec<((usize, usize), f64)> {
+        let mut result = Vec::with_capacity(8);
+        for dr in -1i64..=1 {
+            for dc in -1i64..=1 {
+                let diagonal = dr != 0 && dc != 0;
+                if (dr == 0 && dc == 0) || (diagonal && movement == Movement::FourWay) {
+                    continue;
+                }
+                let (nr, nc) = (r as i64 + dr, c as i64 + dc);
+                if nr < 0 || nc < 0 || nr >= self.height as i64 || nc >= self.width as i64 {
+                    continue;
+                }
+                let next = (nr as usize, nc as usize);
+                let cost = match self.cell(next).cost() {
+                    Some(cost) => cost,
+                    None => continue,
+                };
+                // No cutting past the corner of a wall
+                if diagonal && (self.is_wall((r, nc as usize)) || self.is_wall((nr as usize, c))) {
+                    continue;
+                }
+                result.push((next, if diagonal { cost * std::f64::consts::SQRT_2 } else { cost }));
+            }
+        }
+        result
+    }
+
+    pub fn find_path(&self, start: (usize, usize), goal: (usize, usize), movement: Movement,
+                     heuristic: Heuristic) -> Option<Path> {
+        if self.is_wall(start) || self.is_wall(goal) {
+            return None;
+        }
+        let index = |(r, c): (usize, usize)| r * self.width + c;
+        let position = |i: usize| (i / self.width, i % self.width);
+        let mut g: Vec<Option<f64>> = vec![None; self.cells.len()];
+        let mut parent: Vec<Option<usize>> = vec![None; self.cells.len()];
+        let mut closed = vec![false; self.cells.len()];
+        let mut open = IndexedHeap::new();
+        let mut expanded = 0;
+
+        g[index(start)] = Some(0.0);
+        open.push(index(start), Priority { f: heuristic.estimate(start, goal), g: 0.0 });
+        while let Some((current, priority)) = open.pop() {
+            closed[current] = true;
+            expanded += 1;
+            if current == index(goal) {
+                let mut cells = vec![goal];
+                let mut at = current;
+                while let Some(previous) = parent[at] {
+                    cells.push(position(previous));
+                    at = previous;
+                }
+                cells.reverse();
+                return Some(Path { cells, cost: priority.g, expanded });
+            }
+            for (next, step) in self.moves(position(current), movement) {
+                let i = index(next);
+                if closed[i] {
+                    continue;
+                }
+                let candidate = priority.g + step;
+                if g[i].is_none_or(|known| candidate < known) {
+                    g[i] = Some(candidate);
+                    parent[i] = Some(current);
+                    open.push_or_decrease(i, Priority { f: candidate + heuristic.estimate(next, goal), g: candidate });
+                }
+            }
+        }
+        None
+    }
+
+    // The grid with the path drawn as '*' (start 'S', goal 'G')
+    pub fn render(&self, path: &Path) -> String {
+        let mut chars: Vec<Vec<char>> = (0..self.height)
+            .map(|r| {
+                (0..self.width)
+                    .map(|c| match self.cell((r, c)) {
+                        Cell::Open => '.',
+                        Cell::Swamp => '~',
+                        Cell::Wall => '#',
+                    })
+                    .collect()
+            })
+            .collect();
+        for &(r, c) in &path.cells {
+            chars[r][c] = '*';
+        }
+        if let (Some(&(sr, sc)), Some(&(gr, gc))) = (path.cells.first(), path.cells.last()) {
+            chars[sr][sc] = 'S';
+            chars[gr][gc] = 'G';
+        }
+        chars.into_iter().map(|row| row.into_iter().collect::<String>()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+const MAP: &str = "
+    S.......#...........
+    ........#...~~~~....
+    ..####..#...~~~~....
+    .....#..#...~~~~....
+    .....#..#######.....
+    .....#..............
+    .....#####.~~~~~~...
+    .........#.~~~~~~...
+    .........#.......#..
+    .........#.......#.G
+";
+
+pub fn demo() {
+    let (grid, start, goal) = Grid::parse(MAP).expect("built-in map parses");
+    let (start, goal) = (start.unwrap(), goal.unwrap());
+
+    println!("=== 4-way movement, Manhattan heuristic ===");
+    let path = grid.find_path(start, goal, Movement::FourWay, Heuristic::Manhattan).unwrap();
+    println!("{}", grid.render(&path));
+    println!("cost {}, {} steps, {} cells expanded", path.cost, path.cells.len() - 1, path.expanded);
+
+    println!("\n=== 8-way movement, octile heuristic ===");
+    let path = grid.find_path(start, goal, Movement::EightWay, Heuristic::Octile).unwrap();
+    println!("{}", grid.render(&path));
+    println!("cost {:.3}, {} steps, {} cells expanded", path.cost, path.cells.len() - 1, path.expanded);
+
+    println!("\n=== Heuristics compared ({}x{} map) ===", grid.width(), grid.height());
+    for &movement in [Movement::FourWay, Movement::EightWay].iter() {
+        for &heuristic in [Heuristic::Zero, Heuristic::Euclidean, Heuristic::Octile, Heuristic::Manhattan].iter() {
+            let path = grid.find_path(start, goal, movement, heuristic).unwrap();
+            println!("{:<9} {:<10} cost {:>7.3}, expanded {:>3}", format!("{:?}", movement),
+                     format!("{:?}", heuristic), path.cost, path.expanded);
+        }
+    }
+
+    println!("\n=== Random maps: admissible heuristics match Dijkstra ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 100
+    };
+    let (mut ok, mut maps, mut zero_expanded, mut octile_expanded, mut worse) = (true, 0, 0, 0, 0);
+    for _ in 0..200 {
+        let text: String = (0..30)
+            .map(|_| {
+                let row: String = (0..60)
+                    .map(|_| match random() {
+                        0..=24 => '#',
+                        25..=39 => '~',
+                        _ => '.',
+                    })
+                    .collect();
+                row + "\n"
+            })
+            .collect();
+        let (grid, _, _) = Grid::parse(&text).unwrap();
+        let (start, goal) = ((0, 0), (29, 59));
+        let reference = grid.find_path(start, goal, Movement::EightWay, Heuristic::Zero);
+        let octile = grid.find_path(start, goal, Movement::EightWay, Heuristic::Octile);
+        let euclidean = grid.find_path(start, goal, Movement::EightWay, Heuristic::Euclidean);
+        let four_way = (
+            grid.find_path(start, goal, Movement::FourWay, Heuristic::Zero),
+            grid.find_path(start, goal, Movement::FourWay, Heuristic::Manhattan),
+        );
+        let same = |a: &Option<Path>, b: &Option<Path>| match (a, b) {
+            (Some(a), Some(b)) => (a.cost - b.cost).abs() < 1e-9,
+            (None, None) => true,
+            _ => false,
+        };
+        ok &= same(&reference, &octile) && same(&reference, &euclidean) && same(&four_way.0, &four_way.1);
+        if let (Some(reference), Some(octile)) = (reference, octile) {
+            let manhattan = grid.find_path(start, goal, Movement::EightWay, Heuristic::Manhattan).unwrap();
+            if manhattan.cost > reference.cost + 1e-9 {
+                worse += 1;
+            }
+            maps += 1;
+            zero_expanded += reference.expanded;
+            octile_expanded += octile.expanded;
+        }
+    }
+    println!("200 random 60x30 maps, optimal costs agree: {}", ok);
+    println!("On the {} solvable maps Dijkstra expanded {} cells on average, octile A* {}",
+             maps, zero_expanded / maps.max(1), octile_expanded / maps.max(1));
+    println!("Manhattan with 8-way moves (it overestimates diagonals) returned a longer path on {} of them", worse);
+
+    println!("\n=== Errors and dead ends ===");
+    println!("Ragged map: {}", Grid::parse("..\n...").err().unwrap());
+    let (walled, start, goal) = Grid::parse("S.#.\n..#G").unwrap();
+    println!("Goal behind a wall: {:?}",
+             walled.find_path(start.unwrap(), goal.unwrap(), Movement::EightWay, Heuristic::Octile).map(|p| p.cost));
+}
@@ -0,0 +1,198 @@
+// Dijkstra Shortest Paths
+//
+// Finds the cheapest path from a source vertex to every other vertex of a graph
+// with non-negative edge weights. Vertices are settled in order of distance: the
+// closest unsettled vertex is taken from a priority queue, and each of its edges
+// is "relaxed" - if going through it is cheaper than the best known route to the
+// neighbor, the neighbor's distance is lowered in the queue.
+//
+// Design choices:
+// - Builds on Graph from graphs::graph and IndexedHeap from heaps::indexed_heap
+// - The indexed heap's decrease-key keeps each vertex in the queue at most once,
+//   so the queue never holds stale entries (O((V + E) log V) overall)
+// - Predecessor links are recorded during relaxation; paths are rebuilt by
+//   walking them back from the target
+// - shortest_path(from, to) stops as soon as the target is settled
+// - Unreachable vertices have no distance (None) rather than f64::INFINITY
+// - Weights are checked as their edges are relaxed, not in a pass over the
+//   whole graph, so an early exit really does skip the rest of it; a negative
+//   edge the search never reaches goes unnoticed
+// - Trade-off: requires non-negative weights; graphs with negative edges need
+//   Bellman-Ford instead
+
+use crate::graphs::graph::Graph;
+use rustnotes_collections::heaps::indexed_heap::IndexedHeap;
+
+// Result of a single-source search: distances and the predecessor tree
+#[derive(Debug, Clone)]
+pub struct ShortestPaths {
+    source: usize,
+    distances: Vec<Option<f64>>,
+    predecessors: Vec<Option<usize>>,
+}
+
+impl ShortestPaths {
+    pub fn source(&self) -> usize {
+        self.source
+    }
+
+    // Cost of the cheapest path to `target`, or None if unreachable
+    pub fn distance_to(&self, target: usize) -> Option<f64> {
+        self.distances[target]
+    }
+
+    // Vertices from the source to `target` inclusive, or None if unreachable
+    pub fn path_to(&self, target: usize) -> Option<Vec<usize>> {
+        self.distances[target]?;
+        let mut path = vec![target];
+        let mut current = target;
+        while let Some(previous) = self.predecessors[current] {
+            path.push(previous);
+            current = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl Graph {
+    // Distances and paths from `from` to every vertex
+    pub fn shortest_paths(&self, from: usize) -> ShortestPaths {
+        self.dijkstra(from, None)
+    }
+
+    // Cheapest path from `from` to `to` as (total cost, vertices), or None if unreachable
+    pub fn shortest_path(&self, from: usize, to: usize) -> Option<(f64, Vec<usize>)> {
+        let paths = self.dijkstra(from, Some(to));
+        let distance = paths.distance_to(to)?;
+        paths.path_to(to).map(|path| (distance, path))
+    }
+
+    fn dijkstra(&self, source: usize, target: Option<usize>) -> ShortestPaths {
+        let n = self.vertex_count();
+
+        let mut distances: Vec<Option<f64>> = vec![None; n];
+        let mut predecessors = vec![None; n];
+        let mut settled = vec![false; n];
+        let mut queue = IndexedHeap::new();
+
+        distances[source] = Some(0.0);
+        queue.push(source, 0.0);
+
+        while let Some((u, distance)) = queue.pop() {
+            settled[u] = true;
+            if Some(u) == target {
+                break;
+            }
+            for edge in self.neighbors(u) {
+                assert!(edge.weight >= 0.0, "Dijkstra requires non-negative edge weights");
+                if settled[edge.to] {
+                    continue;
+                }
+                let candidate = distance + edge.weight;
+                let improves = distances[edge.to].is_none_or(|current| candidate < current);
+                if improves {
+                    distances[edge.to] = Some(candidate);
+                    predecessors[edge.to] = Some(u);
+                    queue.push_or_decrease(edge.to, candidate);
+                }
+            }
+        }
+
+        // With an early exit some distances are only tentative; drop them so
+        // callers never see an unsettled estimate
+        if target.is_some() {
+            for (distance, &done) in distances.iter_mut().zip(settled.iter()) {
+                if !done {
+                    *distance = None;
+                }
+            }
+        }
+
+        ShortestPaths { source, distances, predecessors }
+    }
+}
+
+pub fn demo() {
+    println!("=== Directed road network ===");
+    //        7        9
+    //   A ------> B ------> C
+    //   |         ^         |
+    // 2 |       3 |         | 1
+    //   v    4    |    6    v
+    //   D ------> E ------> F      G (unreachable)
+    let names = ["A", "B", "C", "D", "E", "F", "G"];
+    let mut graph = Graph::new_directed(names.len());
+    graph.add_edge(0, 1, 7.0);
+    graph.add_edge(1, 2, 9.0);
+    graph.add_edge(0, 3, 2.0);
+    graph.add_edge(3, 4, 4.0);
+    graph.add_edge(4, 1, 3.0);
+    graph.add_edge(4, 5, 6.0);
+    graph.add_edge(2, 5, 1.0);
+
+    let paths = graph.shortest_paths(0);
+    println!("From {}:", names[paths.source()]);
+    for target in 0..names.len() {
+        match (paths.distance_to(target), paths.path_to(target)) {
+            (Some(distance), Some(path)) => {
+                let route: Vec<&str> = path.iter().map(|&v| names[v]).collect();
+                println!("  to {}: {:>4} via {}", names[target], distance, route.join(" -> "));
+            }
+            _ => println!("  to {}: unreachable", names[target]),
+        }
+    }
+
+    println!("\n=== Single pair with early exit ===");
+    match graph.shortest_path(0, 5) {
+        Some((cost, path)) => println!("A to F costs {} via {:?}", cost, path),
+        None => println!("A to F unreachable"),
+    }
+    println!("F to A: {:?}", graph.shortest_path(5, 0));
+    println!("A to A: {:?}", graph.shortest_path(0, 0));
+
+    println!("\n=== Undirected grid: Dijkstra agrees with BFS on unit weights ===");
+    let width = 6;
+    let mut grid = Graph::new_undirected(width * width);
+    for row in 0..width {
+        for col in 0..width {
+            let v = row * width + col;
+            if col + 1 < width {
+                grid.add_edge(v, v + 1, 1.0);
+            }
+            if row + 1 < width {
+                grid.add_edge(v, v + width, 1.0);
+            }
+        }
+    }
+    let from_corner = grid.shortest_paths(0);
+    let manhattan_ok = (0..width * width)
+        .all(|v| from_corner.distance_to(v) == Some(((v / width) + (v % width)) as f64));
+    println!("Every distance equals the Manhattan distance: {}", manhattan_ok);
+    let (cost, path) = grid.shortest_path(0, width * width - 1).unwrap();
+    println!("Corner to corner: cost {}, {} vertices on the path", cost, path.len());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[should_panic(expected = "Dijkstra requires non-negative edge weights")]
+    fn reached_negative_edge_panics() {
+        let mut graph = Graph::new_directed(3);
+        graph.add_edge(0, 1, 1.0);
+        graph.add_edge(1, 2, -1.0);
+        graph.shortest_paths(0);
+    }
+
+    #[test]
+    fn early_exit_ignores_unreached_edges() {
+        // The negative edge leaves a vertex that is never settled before 1
+        let mut graph = Graph::new_directed(4);
+        graph.add_edge(0, 1, 1.0);
+        graph.add_edge(0, 2, 5.0);
+        graph.add_edge(2, 3, -1.0);
+        assert_eq!(graph.shortest_path(0, 1), Some((1.0, vec![0, 1])));
+    }
+}
@@ -0,0 +1,373 @@
+// Weighted Graph Implementation
+//
+// A graph over vertices numbered 0..n, stored as adjacency lists of weighted
+// edges. The same type represents directed and undirected graphs; an undirected
+// edge is simply stored in both endpoints' lists. This is the shared graph type
+// the algorithm notes (shortest paths, topological sort, spanning trees, ...)
+// build on.
+//
+// Design choices:
+// - Vertices are plain usize indices, so algorithms can keep per-vertex state in
+//   Vecs instead of maps
+// - adjacency: Vec<Vec<Edge>> - each vertex owns the list of edges leaving it
+// - Weights are f64; unweighted graphs just use 1.0 everywhere
+// - Directedness is fixed at construction and recorded on the graph
+// - Traversals (bfs/dfs) use explicit queues/stacks rather than recursion, so
+//   long paths cannot overflow the call stack
+// - from_edge_list() reads the plain "u v [weight]" text that many datasets
+//   come in (one edge per line, '#' or '%' comment lines); errors name the line.
+//   Vertex numbers are used as they are, not renumbered, so they are capped
+//   at MAX_EDGE_LIST_VERTICES: one stray id of 10^9 would otherwise allocate
+//   a billion adjacency lists
+// - to_dot() writes Graphviz DOT, so any graph can be drawn with
+//   `dot -Tsvg graph.dot -o graph.svg`
+// - Usage: graph                              run the demo
+//          graph <file> [--undirected]       load an edge list, print stats and DOT
+// - Trade-off: O(V + E) memory and fast neighbor iteration, but checking whether
+//   a particular edge exists means scanning one adjacency list
+
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::process;
+
+// Vertex numbers from_edge_list accepts are below this (about 8 million)
+pub const MAX_EDGE_LIST_VERTICES: usize = 1 << 23;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Edge {
+    pub to: usize,
+    pub weight: f64,
+}
+
+#[derive(Debug)]
+pub enum EdgeListError {
+    Io(io::Error),
+    // A line that is not "u v" or "u v weight"; lines are numbered from 1
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for EdgeListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EdgeListError::Io(e) => write!(f, "read error: {}", e),
+            EdgeListError::Parse { line, message } => write!(f, "line {}: {}", line, message),
+        }
+    }
+}
+
+impl std::error::Error for EdgeListError {}
+
+impl From<io::Error> for EdgeListError {
+    fn from(e: io::Error) -> Self {
+        EdgeListError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph {
+    adjacency: Vec<Vec<Edge>>,
+    directed: bool,
+    edge_count: usize,
+}
+
+impl Graph {
+    pub fn new_directed(vertices: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); vertices],
+            directed: true,
+            edge_count: 0,
+        }
+    }
+
+    pub fn new_undirected(vertices: usize) -> Self {
+        Self {
+            adjacency: vec![Vec::new(); vertices],
+            directed: false,
+            edge_count: 0,
+        }
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    // Add a vertex with no edges and return its index
+    pub fn add_vertex(&mut self) -> usize {
+        self.adjacency.push(Vec::new());
+        self.adjacency.len() - 1
+    }
+
+    // Add an edge from u to v (and v to u when undirected); panics on unknown vertices
+    pub fn add_edge(&mut self, u: usize, v: usize, weight: f64) {
+        assert!(u < self.adjacency.len() && v < self.adjacency.len(), "vertex out of range");
+        self.adjacency[u].push(Edge { to: v, weight });
+        if !self.directed && u != v {
+            self.adjacency[v].push(Edge { to: u, weight });
+        }
+        self.edge_count += 1;
+    }
+
+    pub fn vertex_count(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    // Number of edges as added (an undirected edge counts once)
+    pub fn edge_count(&self) -> usize {
+        self.edge_count
+    }
+
+    pub fn neighbors(&self, u: usize) -> &[Edge] {
+        &self.adjacency[u]
+    }
+
+    pub fn has_edge(&self, u: usize, v: usize) -> bool {
+        self.adjacency[u].iter().any(|e| e.to == v)
+    }
+
+    pub fn out_degree(&self, u: usize) -> usize {
+        self.adjacency[u].len()
+    }
+
+    // Every edge as (from, to, weight); undirected edges are reported once, from
+    // the lower-numbered endpoint
+    pub fn edges(&self) -> Vec<(usize, usize, f64)> {
+        let mut edges = Vec::with_capacity(self.edge_count);
+        for (u, list) in self.adjacency.iter().enumerate() {
+            for edge in list {
+                if self.directed || u <= edge.to {
+                    edges.push((u, edge.to, edge.weight));
+                }
+            }
+        }
+        edges
+    }
+
+    // Same vertices with every edge pointing the other way
+    pub fn reversed(&self) -> Graph {
+        let mut reversed = Graph {
+            adjacency: vec![Vec::new(); self.adjacency.len()],
+            directed: self.directed,
+            edge_count: 0,
+        };
+        for (u, v, weight) in self.edges() {
+            reversed.add_edge(v, u, weight);
+        }
+        reversed
+    }
+
+    // Parse one edge per line as "u v" (weight 1) or "u v weight". Blank lines
+    // and lines starting with '#' or '%' are skipped; the vertex count is one
+    // more than the largest index seen
+    pub fn from_edge_list<R: BufRead>(reader: R, directed: bool) -> Result<Graph, EdgeListError> {
+        let mut edges = Vec::new();
+        let mut vertices = 0;
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            let parse_error = |message: String| EdgeListError::Parse { line: index + 1, message };
+            let text = line.trim();
+            if text.is_empty() || text.starts_with('#') || text.starts_with('%') {
+                continue;
+            }
+            let fields: Vec<&str> = text.split_whitespace().collect();
+            if fields.len() != 2 && fields.len() != 3 {
+                return Err(parse_error(format!("expected \"u v [weight]\", found {} fields", fields.len())));
+            }
+            let vertex = |field: &str| match field.parse::<usize>() {
+                Ok(vertex) if vertex < MAX_EDGE_LIST_VERTICES => Ok(vertex),
+                Ok(vertex) => Err(parse_error(format!(
+                    "vertex {} is past the limit of {}; number the vertices from 0",
+                    vertex,
+                    MAX_EDGE_LIST_VERTICES - 1
+                ))),
+                Err(_) => Err(parse_error(format!("{:?} is not a vertex number", field))),
+            };
+            let (u, v) = (vertex(fields[0])?, vertex(fields[1])?);
+            let weight = match fields.get(2) {
+                Some(field) => match field.parse::<f64>() {
+                    Ok(weight) if weight.is_finite() => weight,
+                    _ => return Err(parse_error(format!("{:?} is not a finite weight", field))),
+                },
+                None => 1.0,
+            };
+            vertices = vertices.max(u + 1).max(v + 1);
+            edges.push((u, v, weight));
+        }
+        let mut graph = if directed { Graph::new_directed(vertices) } else { Graph::new_undirected(vertices) };
+        for (u, v, weight) in edges {
+            graph.add_edge(u, v, weight);
+        }
+        Ok(graph)
+    }
+
+    // Graphviz DOT text; weights other than 1 become edge labels
+    pub fn to_dot(&self) -> String {
+        let (kind, arrow) = if self.directed { ("digraph", "->") } else { ("graph", "--") };
+        let mut dot = format!("{} G {{\n", kind);
+        // List every vertex so isolated ones are drawn too
+        for u in 0..self.adjacency.len() {
+            writeln!(dot, "    {};", u).unwrap();
+        }
+        for (u, v, weight) in self.edges() {
+            if weight == 1.0 {
+                writeln!(dot, "    {} {} {};", u, arrow, v).unwrap();
+            } else {
+                writeln!(dot, "    {} {} {} [label=\"{}\"];", u, arrow, v, weight).unwrap();
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    // Vertices reachable from `start`, in breadth-first order
+    pub fn bfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.adjacency.len()];
+        let mut order = Vec::new();
+        let mut queue = VecDeque::new();
+        visited[start] = true;
+        queue.push_back(start);
+
+        while let Some(u) = queue.pop_front() {
+            order.push(u);
+            for edge in &self.adjacency[u] {
+                if !visited[edge.to] {
+                    visited[edge.to] = true;
+                    queue.push_back(edge.to);
+                }
+            }
+        }
+        order
+    }
+
+    // Vertices reachable from `start`, in depth-first (preorder) order
+    pub fn dfs(&self, start: usize) -> Vec<usize> {
+        let mut visited = vec![false; self.adjacency.len()];
+        let mut order = Vec::new();
+        let mut stack = vec![start];
+
+        while let Some(u) = stack.pop() {
+            if visited[u] {
+                continue;
+            }
+            visited[u] = true;
+            order.push(u);
+            // Reverse so the first-listed neighbor is explored first
+            for edge in self.adjacency[u].iter().rev() {
+                if !visited[edge.to] {
+                    stack.push(edge.to);
+                }
+            }
+        }
+        order
+    }
+}
+
+// Load an edge list file and print a summary plus its DOT rendering
+fn run_file(args: &[String]) -> Result<(), String> {
+    let directed = !args.iter().any(|arg| arg == "--undirected");
+    let path = args.iter().find(|arg| !arg.starts_with("--")).ok_or("usage: graph <file> [--undirected]")?;
+    let file = File::open(path).map_err(|e| format!("cannot open {}: {}", path, e))?;
+    let graph = Graph::from_edge_list(BufReader::new(file), directed).map_err(|e| format!("{}: {}", path, e))?;
+    let reached = if graph.vertex_count() > 0 { graph.bfs(0).len() } else { 0 };
+    eprintln!("{} vertices, {} edges, {}; {} reachable from vertex 0",
+              graph.vertex_count(), graph.edge_count(), if directed { "directed" } else { "undirected" }, reached);
+    print!("{}", graph.to_dot());
+    Ok(())
+}
+
+pub fn demo() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(message) = run_file(&args) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    println!("=== Undirected graph ===");
+    //   0 --- 1 --- 2
+    //   |     |
+    //   3 --- 4     5 (isolated)
+    let mut graph = Graph::new_undirected(6);
+    graph.add_edge(0, 1, 1.0);
+    graph.add_edge(1, 2, 2.0);
+    graph.add_edge(0, 3, 1.5);
+    graph.add_edge(1, 4, 1.0);
+    graph.add_edge(3, 4, 0.5);
+    println!("Vertices: {}, edges: {}, directed: {}",
+             graph.vertex_count(), graph.edge_count(), graph.is_directed());
+    println!("Neighbors of 1: {:?}", graph.neighbors(1).iter().map(|e| e.to).collect::<Vec<_>>());
+    println!("Has edge 4-3: {}, has edge 2-5: {}", graph.has_edge(4, 3), graph.has_edge(2, 5));
+    println!("Edges: {:?}", graph.edges());
+    println!("BFS from 0: {:?}", graph.bfs(0));
+    println!("DFS from 0: {:?}", graph.dfs(0));
+    println!("BFS from 5: {:?}", graph.bfs(5));
+
+    println!("\n=== Directed graph ===");
+    let mut digraph = Graph::new_directed(4);
+    digraph.add_edge(0, 1, 1.0);
+    digraph.add_edge(0, 2, 1.0);
+    digraph.add_edge(2, 3, 1.0);
+    let extra = digraph.add_vertex();
+    digraph.add_edge(3, extra, 1.0);
+    println!("Vertices: {}, edges: {}", digraph.vertex_count(), digraph.edge_count());
+    println!("Out-degree of 0: {}", digraph.out_degree(0));
+    println!("Has edge 0->2: {}, has edge 2->0: {}", digraph.has_edge(0, 2), digraph.has_edge(2, 0));
+    println!("BFS from 0: {:?}", digraph.bfs(0));
+    println!("BFS from 3: {:?}", digraph.bfs(3));
+    let reversed = digraph.reversed();
+    println!("Reversed edges: {:?}", reversed.edges());
+    println!("BFS from {} in reversed graph: {:?}", extra, reversed.bfs(extra));
+
+    println!("\n=== Edge list import and DOT export ===");
+    let text = "# a small road network\n0 1 7\n0 3 2\n1 2 9\n\n% weights are optional\n3 4\n4 1 3.5\n";
+    let imported = Graph::from_edge_list(text.as_bytes(), true).unwrap();
+    println!("Parsed {} vertices and {} edges: {:?}", imported.vertex_count(), imported.edge_count(), imported.edges());
+    print!("{}", imported.to_dot());
+    // Writing the edges out as text and parsing them back gives the same edges
+    let round_trip: String = graph.edges().iter().map(|(u, v, w)| format!("{} {} {}\n", u, v, w)).collect();
+    let reread = Graph::from_edge_list(round_trip.as_bytes(), false).unwrap();
+    println!("Undirected graph round-trips through an edge list: {}", reread.edges() == graph.edges());
+    print!("{}", Graph::new_undirected(2).to_dot());
+    for bad in ["0 1\n1\n", "0 x 2\n", "0 1 heavy\n", "0 1 NaN\n", "0 1 2 3\n", "0 1000000000\n"].iter() {
+        println!("{:?} -> {}", bad, Graph::from_edge_list(bad.as_bytes(), true).unwrap_err());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_error(text: &str) -> String {
+        Graph::from_edge_list(text.as_bytes(), true).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn edge_list_round_trip() {
+        let text = "# comment\n0 1 7\n\n% weights are optional\n3 1\n";
+        let graph = Graph::from_edge_list(text.as_bytes(), true).unwrap();
+        assert_eq!(graph.vertex_count(), 4);
+        assert_eq!(graph.edges(), vec![(0, 1, 7.0), (3, 1, 1.0)]);
+    }
+
+    #[test]
+    fn edge_list_errors_name_the_line() {
+        assert_eq!(parse_error("0 1\n1\n"), "line 2: expected \"u v [weight]\", found 1 fields");
+        assert_eq!(parse_error("0 x 2\n"), "line 1: \"x\" is not a vertex number");
+        assert_eq!(parse_error("0 1 NaN\n"), "line 1: \"NaN\" is not a finite weight");
+    }
+
+    #[test]
+    fn edge_list_caps_vertex_numbers() {
+        let limit = MAX_EDGE_LIST_VERTICES - 1;
+        let message = format!("line 2: vertex {} is past the limit of {}; number the vertices from 0", usize::MAX, limit);
+        assert_eq!(parse_error(&format!("0 1\n{} 0\n", usize::MAX)), message);
+        assert!(parse_error("1000000000 1\n").starts_with("line 1: vertex 1000000000 is past the limit"));
+        let graph = Graph::from_edge_list(format!("{} 0\n", limit).as_bytes(), true).unwrap();
+        assert_eq!(graph.vertex_count(), MAX_EDGE_LIST_VERTICES);
+    }
+}
@@ -0,0 +1,224 @@
+// Minimum Spanning Tree (Kruskal and Prim)
+//
+// A spanning tree of a connected undirected graph is a subset of its edges that
+// links every vertex without forming a cycle (V - 1 edges). The minimum one has
+// the smallest total weight - the cheapest way to wire up every vertex. Both
+// algorithms here rely on the cut property: the lightest edge crossing any split
+// of the vertices into two groups belongs to some minimum spanning tree.
+//
+//   Kruskal: sort all edges, take each one     Prim: grow one tree from a start
+//   that joins two different components          vertex, always adding the lightest
+//                                                edge that leaves the tree
+//      A --1-- B      A --1-- B                     {A} -> {A,B} -> {A,B,D} -> ...
+//      |  \    |      |       |
+//      4   3   2  =>  4       2
+//      |    \  |      |       |
+//      C --5-- D      C       D   (total 7)
+//
+// Design choices:
+// - Builds on Graph from graphs::graph; both algorithms are impl Graph methods, like
+//   graphs::dijkstra and graphs::topological_sort
+// - Kruskal uses UnionFind from graphs::union_find to ask "would this edge close a
+//   cycle?" in near-constant time; sorting dominates at O(E log E)
+// - Prim uses IndexedHeap from heaps::indexed_heap keyed by the cheapest known edge
+//   into each vertex, with decrease-key when a lighter one appears: O(E log V)
+// - A disconnected graph gives a minimum spanning forest (one tree per
+//   component); Prim restarts from each vertex it has not reached yet
+// - Edges are reported as (u, v, weight) with u < v, sorted, so results from the
+//   two algorithms can be compared directly
+// - Trade-off: when several edges share a weight the minimum tree is not unique,
+//   so Kruskal and Prim can pick different edges with the same total
+
+use crate::graphs::graph::Graph;
+use crate::graphs::union_find::UnionFind;
+use rustnotes_collections::heaps::indexed_heap::IndexedHeap;
+use std::time::Instant;
+
+// Edges chosen by a spanning-tree algorithm and their combined weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpanningTree {
+    pub edges: Vec<(usize, usize, f64)>,
+    pub total_weight: f64,
+}
+
+impl SpanningTree {
+    fn from_edges(mut edges: Vec<(usize, usize, f64)>) -> Self {
+        for edge in edges.iter_mut() {
+            if edge.0 > edge.1 {
+                *edge = (edge.1, edge.0, edge.2);
+            }
+        }
+        edges.sort_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)).then(a.2.total_cmp(&b.2)));
+        let total_weight = edges.iter().map(|&(_, _, weight)| weight).sum();
+        SpanningTree { edges, total_weight }
+    }
+
+    // Number of trees in the forest for a graph with `vertices` vertices
+    pub fn component_count(&self, vertices: usize) -> usize {
+        vertices - self.edges.len()
+    }
+}
+
+impl Graph {
+    // Kruskal: lightest edges first, skipping any that would close a cycle
+    pub fn kruskal(&self) -> SpanningTree {
+        assert!(!self.is_directed(), "spanning trees are defined for undirected graphs");
+        let n = self.vertex_count();
+        let mut edges = self.edges();
+        edges.sort_by(|a, b| a.2.total_cmp(&b.2));
+
+        let mut components = UnionFind::new(n);
+        let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+        for (u, v, weight) in edges {
+            if components.union(u, v) {
+                chosen.push((u, v, weight));
+                if chosen.len() + 1 == n {
+                    break;
+                }
+            }
+        }
+        SpanningTree::from_edges(chosen)
+    }
+
+    // Prim: grow a tree from each unreached vertex, always taking the lightest
+    // edge from the tree to a vertex outside it
+    pub fn prim(&self) -> SpanningTree {
+        assert!(!self.is_directed(), "spanning trees are defined for undirected graphs");
+        let n = self.vertex_count();
+        let mut in_tree = vec![false; n];
+        // Cheapest known edge into each vertex: (weight, tree vertex it comes from)
+        let mut best: Vec<Option<(f64, usize)>> = vec![None; n];
+        let mut queue = IndexedHeap::new();
+        let mut chosen = Vec::with_capacity(n.saturating_sub(1));
+
+        for root in 0..n {
+            if in_tree[root] {
+                continue;
+            }
+            queue.push(root, 0.0);
+            while let Some((u, _)) = queue.pop() {
+                in_tree[u] = true;
+                if let Some((weight, from)) = best[u] {
+                    chosen.push((from, u, weight));
+                }
+                for edge in self.neighbors(u) {
+                    if in_tree[edge.to] {
+                        continue;
+                    }
+                    let lighter = best[edge.to].is_none_or(|(weight, _)| edge.weight < weight);
+                    if lighter {
+                        best[edge.to] = Some((edge.weight, u));
+                        queue.push_or_decrease(edge.to, edge.weight);
+                    }
+                }
+            }
+        }
+        SpanningTree::from_edges(chosen)
+    }
+}
+
+// Lightest spanning forest by trying every subset of edges (tiny graphs only)
+fn brute_force_weight(graph: &Graph) -> f64 {
+    let edges = graph.edges();
+    let n = graph.vertex_count();
+    let target = {
+        let mut components = UnionFind::new(n);
+        for &(u, v, _) in &edges {
+            components.union(u, v);
+        }
+        n - components.set_count()
+    };
+    let mut best = f64::INFINITY;
+    for mask in 0u32..1 << edges.len() {
+        if mask.count_ones() as usize != target {
+            continue;
+        }
+        let mut components = UnionFind::new(n);
+        let mut weight = 0.0;
+        let acyclic = edges.iter().enumerate().filter(|&(i, _)| mask & (1 << i) != 0).all(|(_, &(u, v, w))| {
+            weight += w;
+            components.union(u, v)
+        });
+        if acyclic {
+            best = best.min(weight);
+        }
+    }
+    best
+}
+
+fn random_graph(vertices: usize, edges: usize, max_weight: u64, state: &mut u64) -> Graph {
+    let mut next = || {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    };
+    let mut graph = Graph::new_undirected(vertices);
+    for _ in 0..edges {
+        let u = (next() % vertices as u64) as usize;
+        let v = (next() % vertices as u64) as usize;
+        if u != v {
+            graph.add_edge(u, v, (next() % max_weight + 1) as f64);
+        }
+    }
+    graph
+}
+
+pub fn demo() {
+    let names = ["A", "B", "C", "D", "E", "F", "G"];
+    let label = |tree: &SpanningTree| {
+        tree.edges.iter().map(|&(u, v, w)| format!("{}-{}({})", names[u], names[v], w)).collect::<Vec<_>>().join(" ")
+    };
+
+    println!("=== Small network ===");
+    // The classic seven-vertex example: A-B 7, A-D 5, B-C 8, B-D 9, B-E 7, C-E 5,
+    // D-E 15, D-F 6, E-F 8, E-G 9, F-G 11
+    let mut graph = Graph::new_undirected(names.len());
+    for &(u, v, w) in [(0, 1, 7.0), (0, 3, 5.0), (1, 2, 8.0), (1, 3, 9.0), (1, 4, 7.0), (2, 4, 5.0),
+                       (3, 4, 15.0), (3, 5, 6.0), (4, 5, 8.0), (4, 6, 9.0), (5, 6, 11.0)].iter() {
+        graph.add_edge(u, v, w);
+    }
+    let kruskal = graph.kruskal();
+    let prim = graph.prim();
+    println!("Kruskal: {} -> total {}", label(&kruskal), kruskal.total_weight);
+    println!("Prim:    {} -> total {}", label(&prim), prim.total_weight);
+    println!("Same tree: {}", kruskal == prim);
+
+    println!("\n=== Disconnected graph gives a forest ===");
+    let mut forest = Graph::new_undirected(names.len());
+    for &(u, v, w) in [(0, 1, 2.0), (1, 2, 1.0), (0, 2, 3.0), (3, 4, 4.0)].iter() {
+        forest.add_edge(u, v, w);
+    }
+    let kruskal = forest.kruskal();
+    let prim = forest.prim();
+    println!("Kruskal: {} -> total {}, {} trees", label(&kruskal), kruskal.total_weight, kruskal.component_count(names.len()));
+    println!("Prim:    {} -> total {}, {} trees", label(&prim), prim.total_weight, prim.component_count(names.len()));
+
+    println!("\n=== Brute force check on small random graphs ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut agree = 0;
+    let trials = 300;
+    for _ in 0..trials {
+        let graph = random_graph(6, 10, 5, &mut state);
+        let expected = brute_force_weight(&graph);
+        let (kruskal, prim) = (graph.kruskal(), graph.prim());
+        if kruskal.total_weight == expected && prim.total_weight == expected && kruskal.edges.len() == prim.edges.len() {
+            agree += 1;
+        }
+    }
+    println!("{}/{} graphs (6 vertices, up to 10 edges, weights 1-5, many ties): both match brute force", agree, trials);
+
+    println!("\n=== Kruskal vs Prim on larger graphs ===");
+    for &(vertices, edges) in [(1_000, 5_000), (10_000, 50_000), (2_000, 200_000)].iter() {
+        let graph = random_graph(vertices, edges, 1_000_000, &mut state);
+        let start = Instant::now();
+        let kruskal = graph.kruskal();
+        let kruskal_time = start.elapsed();
+        let start = Instant::now();
+        let prim = graph.prim();
+        let prim_time = start.elapsed();
+        println!("V={:>6} E={:>7}: Kruskal {:>10?}, Prim {:>10?}, totals equal: {}, {} tree(s)",
+                 vertices, graph.edge_count(), kruskal_time, prim_time,
+                 kruskal.total_weight == prim.total_weight, kruskal.component_count(vertices));
+    }
+}
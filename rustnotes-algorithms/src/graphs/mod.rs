@@ -0,0 +1,17 @@
+// Graphs
+
+pub mod a_star;
+pub mod dijkstra;
+pub mod graph;
+pub mod minimum_spanning_tree;
+pub mod strongly_connected_components;
+pub mod topological_sort;
+pub mod union_find;
+
+pub use self::a_star::Grid;
+pub use self::dijkstra::ShortestPaths;
+pub use self::graph::{Edge, Graph};
+pub use self::minimum_spanning_tree::SpanningTree;
+pub use self::strongly_connected_components::Components;
+pub use self::topological_sort::CycleError;
+pub use self::union_find::UnionFind;
@@ -0,0 +1,439 @@
+// Strongly Connected Components (Tarjan and Kosaraju)
+//
+// In a directed graph two vertices are strongly connected when each can reach
+// the other. That relation splits the vertices into components: every cycle lies
+// inside one component, and collapsing each component to a single vertex leaves
+// a DAG, the condensation, which shows how the cyclic clusters depend on each
+// other.
+//
+//   +---------+
+//   v         |
+//   0 -> 1 -> 2      3 <-> 4 -> 5      components: {0,1,2} {3,4} {5}
+//        |           ^
+//        +-----------+                 condensation: {0,1,2} -> {3,4} -> {5}
+//
+// Tarjan's algorithm does it in one depth-first search. Each vertex gets a
+// discovery index and a "low link": the smallest index reachable from its DFS
+// subtree through at most one edge back into vertices still on the stack. A
+// vertex whose low link equals its own index is the root of a component, and
+// everything above it on the stack belongs to that component.
+//
+// Design choices:
+// - Builds on Graph from graphs::graph; the algorithms are impl Graph methods, like
+//   graphs::topological_sort
+// - The DFS is iterative (explicit stack of (vertex, next edge index)), so long
+//   chains cannot overflow the call stack
+// - Tarjan finishes components sinks-first; they are renumbered in reverse, so
+//   component 0 is a source of the condensation and every condensation edge goes
+//   from a lower to a higher component number (a topological order for free)
+// - Kosaraju's two-pass algorithm (DFS finishing order, then a search of the
+//   reversed graph) is included as an independent cross-check
+// - The condensation keeps one edge per pair of components, with the lightest
+//   weight among the original edges between them
+// - Trade-off: both are O(V + E); Tarjan needs one pass and no reversed copy of
+//   the graph, Kosaraju is easier to get right but builds the reversed graph
+
+use crate::graphs::graph::Graph;
+use std::collections::HashMap;
+use std::time::Instant;
+
+// Partition of the vertices into strongly connected components, numbered in
+// topological order of the condensation
+#[derive(Debug, Clone)]
+pub struct Components {
+    component_of: Vec<usize>,
+    members: Vec<Vec<usize>>,
+}
+
+impl Components {
+    // Number the components found in reverse topological order (sinks first),
+    // flipping them so sources come first; members are listed in vertex order
+    fn from_sinks_first(vertices: usize, mut found: Vec<Vec<usize>>) -> Self {
+        found.reverse();
+        let mut component_of = vec![0; vertices];
+        for (c, members) in found.iter_mut().enumerate() {
+            members.sort_unstable();
+            for &v in members.iter() {
+                component_of[v] = c;
+            }
+        }
+        Components { component_of, members: found }
+    }
+
+    pub fn count(&self) -> usize {
+        self.members.len()
+    }
+
+    pub fn component_of(&self, vertex: usize) -> usize {
+        self.component_of[vertex]
+    }
+
+    pub fn members(&self, component: usize) -> &[usize] {
+        &self.members[component]
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &[usize]> {
+        self.members.iter().map(Vec::as_slice)
+    }
+}
+
+impl Graph {
+    // Tarjan: one DFS tracking discovery indices and low links
+    pub fn strongly_connected_components(&self) -> Components {
+        assert!(self.is_directed(), "strong connectivity needs a directed graph");
+        let n = self.vertex_count();
+        let mut index: Vec<Option<usize>> = vec![None; n];
+        let mut low_link = vec![0; n];
+        let mut on_stack = vec![false; n];
+        let mut component_stack = Vec::new();
+        let mut found = Vec::new();
+        let mut next_index = 0;
+
+        for start in 0..n {
+            if index[start].is_some() {
+                continue;
+            }
+            index[start] = Some(next_index);
+            low_link[start] = next_index;
+            next_index += 1;
+            component_stack.push(start);
+            on_stack[start] = true;
+            let mut dfs = vec![(start, 0)];
+
+            while let Some(&mut (u, ref mut next_edge)) = dfs.last_mut() {
+                let edges = self.neighbors(u);
+                if *next_edge < edges.len() {
+                    let v = edges[*next_edge].to;
+                    *next_edge += 1;
+                    match index[v] {
+                        None => {
+                            index[v] = Some(next_index);
+                            low_link[v] = next_index;
+                            next_index += 1;
+                            component_stack.push(v);
+                            on_stack[v] = true;
+                            dfs.push((v, 0));
+                        }
+                        // Edge back into the current search path (or a
+                        // component still being built): u shares its component
+                        Some(v_index) if on_stack[v] => low_link[u] = low_link[u].min(v_index),
+                        // v belongs to a component that is already complete
+                        Some(_) => {}
+                    }
+                    continue;
+                }
+
+                // All edges of u explored
+                dfs.pop();
+                if let Some(&(parent, _)) = dfs.last() {
+                    low_link[parent] = low_link[parent].min(low_link[u]);
+                }
+                if Some(low_link[u]) == index[u] {
+                    let mut component = Vec::new();
+                    loop {
+                        let w = component_stack.pop().unwrap();
+                        on_stack[w] = false;
+                        component.push(w);
+                        if w == u {
+                            break;
+                        }
+                    }
+                    found.push(component);
+                }
+            }
+        }
+        Components::from_sinks_first(n, found)
+    }
+
+    // Kosaraju: finishing order of a DFS on the graph, then collect components by
+    // searching the reversed graph in decreasing finishing time
+    pub fn strongly_connected_components_kosaraju(&self) -> Components {
+        assert!(self.is_directed(), "strong connectivity needs a directed graph");
+        let n = self.vertex_count();
+        let mut visited = vec![false; n];
+        let mut finished = Vec::with_capacity(n);
+        for start in 0..n {
+            if visited[start] {
+                continue;
+            }
+            visited[start] = true;
+            let mut stack = vec![(start, 0)];
+            while let Some(&mut (u, ref mut next_edge)) = stack.last_mut() {
+                let edges = self.neighbors(u);
+                if *next_edge == edges.len() {
+                    finished.push(u);
+                    stack.pop();
+                    continue;
+                }
+                let v = edges[*next_edge].to;
+                *next_edge += 1;
+                if !visited[v] {
+                    visited[v] = true;
+                    stack.push((v, 0));
+                }
+            }
+        }
+
+        // The latest-finishing vertex lies in a source component; in the reversed
+        // graph it reaches exactly its own component. Found sources-first, so
+        // flip the list for from_sinks_first
+        let reversed = self.reversed();
+        let mut assigned = vec![false; n];
+        let mut found = Vec::new();
+        for &root in finished.iter().rev() {
+            if assigned[root] {
+                continue;
+            }
+            assigned[root] = true;
+            let mut component = vec![root];
+            let mut stack = vec![root];
+            while let Some(u) = stack.pop() {
+                for edge in reversed.neighbors(u) {
+                    if !assigned[edge.to] {
+                        assigned[edge.to] = true;
+                        component.push(edge.to);
+                        stack.push(edge.to);
+                    }
+                }
+            }
+            found.push(component);
+        }
+        found.reverse();
+        Components::from_sinks_first(n, found)
+    }
+
+    // One vertex per component and one edge per connected pair of components,
+    // weighted by the lightest original edge between them
+    pub fn condensation(&self, components: &Components) -> Graph {
+        let mut lightest: HashMap<(usize, usize), f64> = HashMap::new();
+        for (u, v, weight) in self.edges() {
+            let (cu, cv) = (components.component_of(u), components.component_of(v));
+            if cu != cv {
+                let entry = lightest.entry((cu, cv)).or_insert(weight);
+                *entry = entry.min(weight);
+            }
+        }
+        let mut pairs: Vec<_> = lightest.into_iter().collect();
+        pairs.sort_by_key(|&(pair, _)| pair);
+        let mut dag = Graph::new_directed(components.count());
+        for ((cu, cv), weight) in pairs {
+            dag.add_edge(cu, cv, weight);
+        }
+        dag
+    }
+}
+
+// Same partition by mutual reachability, from a BFS out of every vertex (small graphs only)
+fn brute_force_same_component(graph: &Graph, components: &Components) -> bool {
+    let n = graph.vertex_count();
+    let mut reaches = vec![vec![false; n]; n];
+    for (u, row) in reaches.iter_mut().enumerate() {
+        for v in graph.bfs(u) {
+            row[v] = true;
+        }
+    }
+    (0..n).all(|u| {
+        (0..n).all(|v| {
+            let mutual = reaches[u][v] && reaches[v][u];
+            mutual == (components.component_of(u) == components.component_of(v))
+        })
+    })
+}
+
+// Same grouping of vertices, ignoring how the components are numbered (the
+// topological order of a condensation is not unique)
+fn same_partition(a: &Components, b: &Components) -> bool {
+    let mut left: Vec<&[usize]> = a.iter().collect();
+    let mut right: Vec<&[usize]> = b.iter().collect();
+    left.sort_unstable();
+    right.sort_unstable();
+    left == right
+}
+
+// Every condensation edge must point from a lower to a higher component number
+fn is_forward_dag(dag: &Graph) -> bool {
+    dag.edges().iter().all(|&(u, v, _)| u < v)
+}
+
+fn random_digraph(vertices: usize, edges: usize, state: &mut u64) -> Graph {
+    let mut next = || {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    };
+    let mut graph = Graph::new_directed(vertices);
+    for _ in 0..edges {
+        let u = (next() % vertices as u64) as usize;
+        let v = (next() % vertices as u64) as usize;
+        graph.add_edge(u, v, (next() % 9 + 1) as f64);
+    }
+    graph
+}
+
+pub fn demo() {
+    println!("=== Graph with several cycles ===");
+    // Cycles 0->1->2->0 and 3<->4 (two edges lead from the first to the second),
+    // a self loop on 6, and 5, 7 on their own
+    let mut graph = Graph::new_directed(8);
+    for &(u, v, w) in [(0, 1, 1.0), (1, 2, 1.0), (2, 0, 1.0), (1, 3, 4.0), (3, 4, 1.0), (4, 3, 1.0),
+                       (4, 5, 3.0), (2, 3, 2.0), (6, 6, 1.0), (6, 5, 5.0), (5, 7, 1.0)].iter() {
+        graph.add_edge(u, v, w);
+    }
+    let components = graph.strongly_connected_components();
+    for (c, members) in components.iter().enumerate() {
+        println!("Component {}: {:?}", c, members);
+    }
+    let dag = graph.condensation(&components);
+    println!("Condensation edges: {:?}", dag.edges());
+    println!("Kosaraju agrees: {}", same_partition(&components, &graph.strongly_connected_components_kosaraju()));
+    println!("Matches mutual reachability: {}, condensation in topological order: {}",
+             brute_force_same_component(&graph, &components), is_forward_dag(&dag));
+
+    println!("\n=== Edge cases ===");
+    let empty = Graph::new_directed(0);
+    println!("Empty graph: {} components", empty.strongly_connected_components().count());
+    let mut ring = Graph::new_directed(5);
+    for v in 0..5 {
+        ring.add_edge(v, (v + 1) % 5, 1.0);
+    }
+    let ring_components = ring.strongly_connected_components();
+    println!("Ring of 5: {} component {:?}, condensation has {} edges",
+             ring_components.count(), ring_components.members(0), ring.condensation(&ring_components).edge_count());
+    let mut dag_input = Graph::new_directed(4);
+    for &(u, v) in [(0, 1), (1, 2), (0, 3), (3, 2)].iter() {
+        dag_input.add_edge(u, v, 1.0);
+    }
+    println!("A DAG is its own condensation: {} singleton components",
+             dag_input.strongly_connected_components().count());
+
+    println!("\n=== Random graphs against brute force ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let trials = 300;
+    let mut passed = 0;
+    let mut multi_cycle = 0;
+    for trial in 0..trials {
+        let vertices = 4 + trial % 10;
+        let graph = random_digraph(vertices, vertices + trial % 7, &mut state);
+        let tarjan = graph.strongly_connected_components();
+        let kosaraju = graph.strongly_connected_components_kosaraju();
+        if tarjan.iter().filter(|members| members.len() > 1).count() > 1 {
+            multi_cycle += 1;
+        }
+        if same_partition(&tarjan, &kosaraju)
+            && brute_force_same_component(&graph, &tarjan)
+            && is_forward_dag(&graph.condensation(&tarjan))
+        {
+            passed += 1;
+        }
+    }
+    println!("{}/{} random graphs: Tarjan = Kosaraju = mutual reachability, condensation acyclic", passed, trials);
+    println!("({} of them had two or more non-trivial components)", multi_cycle);
+
+    println!("\n=== Long chain and large graph ===");
+    // A recursive DFS would overflow here
+    let n = 200_000;
+    let mut chain = Graph::new_directed(n);
+    for v in 1..n {
+        chain.add_edge(v - 1, v, 1.0);
+    }
+    chain.add_edge(n - 1, 0, 1.0);
+    println!("Chain of {} closed into a ring: {} component(s)", n, chain.strongly_connected_components().count());
+    let graph = random_digraph(100_000, 150_000, &mut state);
+    let start = Instant::now();
+    let tarjan = graph.strongly_connected_components();
+    let tarjan_time = start.elapsed();
+    let start = Instant::now();
+    let kosaraju = graph.strongly_connected_components_kosaraju();
+    let kosaraju_time = start.elapsed();
+    let largest = tarjan.iter().map(|members| members.len()).max().unwrap_or(0);
+    println!("V=100000 E=150000: {} components, largest {}; Tarjan {:?}, Kosaraju {:?}, agree: {}",
+             tarjan.count(), largest, tarjan_time, kosaraju_time, same_partition(&tarjan, &kosaraju));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn several_cycles() -> Graph {
+        let mut graph = Graph::new_directed(8);
+        for &(u, v, w) in [(0, 1, 1.0), (1, 2, 1.0), (2, 0, 1.0), (1, 3, 4.0), (3, 4, 1.0), (4, 3, 1.0),
+                           (4, 5, 3.0), (2, 3, 2.0), (6, 6, 1.0), (6, 5, 5.0), (5, 7, 1.0)].iter() {
+            graph.add_edge(u, v, w);
+        }
+        graph
+    }
+
+    #[test]
+    fn several_cycles_and_their_condensation() {
+        let graph = several_cycles();
+        let components = graph.strongly_connected_components();
+        let mut groups: Vec<&[usize]> = components.iter().collect();
+        groups.sort_unstable();
+        assert_eq!(groups, vec![&[0, 1, 2][..], &[3, 4], &[5], &[6], &[7]]);
+        assert!(same_partition(&components, &graph.strongly_connected_components_kosaraju()));
+        assert!(brute_force_same_component(&graph, &components));
+
+        // One edge per pair of components, carrying the lightest weight
+        let dag = graph.condensation(&components);
+        assert!(is_forward_dag(&dag));
+        let c = |v| components.component_of(v);
+        let mut expected = vec![(c(0), c(3), 2.0), (c(3), c(5), 3.0), (c(6), c(5), 5.0), (c(5), c(7), 1.0)];
+        let mut edges = dag.edges();
+        expected.sort_by_key(|&(u, v, _)| (u, v));
+        edges.sort_by_key(|&(u, v, _)| (u, v));
+        assert_eq!(edges, expected);
+    }
+
+    #[test]
+    fn edge_cases() {
+        assert_eq!(Graph::new_directed(0).strongly_connected_components().count(), 0);
+
+        let mut ring = Graph::new_directed(5);
+        for v in 0..5 {
+            ring.add_edge(v, (v + 1) % 5, 1.0);
+        }
+        let components = ring.strongly_connected_components();
+        assert_eq!((components.count(), components.members(0)), (1, &[0, 1, 2, 3, 4][..]));
+        assert_eq!(ring.condensation(&components).edge_count(), 0);
+
+        let mut dag = Graph::new_directed(4);
+        for &(u, v) in [(0, 1), (1, 2), (0, 3), (3, 2)].iter() {
+            dag.add_edge(u, v, 1.0);
+        }
+        let components = dag.strongly_connected_components();
+        assert_eq!(components.count(), 4);
+        assert!(is_forward_dag(&dag.condensation(&components)));
+    }
+
+    #[test]
+    fn random_graphs_match_brute_force() {
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut multi_cycle = 0;
+        for trial in 0..300 {
+            let vertices = 4 + trial % 10;
+            let graph = random_digraph(vertices, vertices + trial % 7, &mut state);
+            let tarjan = graph.strongly_connected_components();
+            if tarjan.iter().filter(|members| members.len() > 1).count() > 1 {
+                multi_cycle += 1;
+            }
+            assert!(same_partition(&tarjan, &graph.strongly_connected_components_kosaraju()), "trial {}", trial);
+            assert!(brute_force_same_component(&graph, &tarjan), "trial {}", trial);
+            assert!(is_forward_dag(&graph.condensation(&tarjan)), "trial {}", trial);
+        }
+        // The sample must exercise graphs with more than one cycle
+        assert!(multi_cycle > 0);
+    }
+
+    #[test]
+    fn long_ring_does_not_overflow_the_stack() {
+        let n = 200_000;
+        let mut ring = Graph::new_directed(n);
+        for v in 1..n {
+            ring.add_edge(v - 1, v, 1.0);
+        }
+        ring.add_edge(n - 1, 0, 1.0);
+        assert_eq!(ring.strongly_connected_components().count(), 1);
+        assert_eq!(ring.strongly_connected_components_kosaraju().count(), 1);
+    }
+}
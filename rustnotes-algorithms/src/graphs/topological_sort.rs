@@ -0,0 +1,197 @@
+// Topological Sort
+//
+// Orders the vertices of a directed graph so every edge points forward: if there
+// is an edge u -> v, u comes before v. Such an order exists exactly when the graph
+// has no directed cycle (it is a DAG), which makes topological sorting both a
+// scheduling tool and a cycle detector.
+//
+// Two classic algorithms are shown side by side:
+// - Kahn's algorithm: repeatedly output a vertex with no remaining incoming
+//   edges, then delete its outgoing edges (tracked as in-degree counters)
+// - DFS: a vertex is finished only after everything reachable from it; listing
+//   vertices in reverse finishing order gives a topological order
+//
+// Design choices:
+// - Builds on Graph from graphs::graph; both sorts are added as methods on Graph
+// - Kahn's queue is a VecDeque seeded in vertex order, so output is deterministic
+// - The DFS is iterative (explicit stack of (vertex, next edge index)), so deep
+//   dependency chains cannot overflow the call stack
+// - Three-color marking (unvisited / on current path / finished): meeting a vertex
+//   that is still on the current path means a back edge, i.e. a cycle
+// - On failure both return a CycleError listing the vertices of one actual cycle
+// - Trade-off: both are O(V + E); Kahn's makes "which tasks are ready now" explicit,
+//   while the DFS finds the offending cycle directly as a by-product
+
+use crate::graphs::graph::Graph;
+use std::collections::VecDeque;
+use std::fmt;
+
+// The graph is not a DAG; `cycle` lists the vertices of one cycle in edge order
+// (the edge from the last vertex back to the first closes it)
+#[derive(Debug, Clone, PartialEq)]
+pub struct CycleError {
+    pub cycle: Vec<usize>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "graph contains a cycle: ")?;
+        for vertex in &self.cycle {
+            write!(f, "{} -> ", vertex)?;
+        }
+        write!(f, "{}", self.cycle[0])
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Mark {
+    Unvisited,
+    OnPath,
+    Finished,
+}
+
+impl Graph {
+    // Kahn's algorithm: peel off vertices whose in-degree has dropped to zero
+    pub fn topological_sort_kahn(&self) -> Result<Vec<usize>, CycleError> {
+        assert!(self.is_directed(), "topological order needs a directed graph");
+        let n = self.vertex_count();
+        let mut in_degree = vec![0; n];
+        for u in 0..n {
+            for edge in self.neighbors(u) {
+                in_degree[edge.to] += 1;
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..n).filter(|&v| in_degree[v] == 0).collect();
+        let mut order = Vec::with_capacity(n);
+        while let Some(u) = ready.pop_front() {
+            order.push(u);
+            for edge in self.neighbors(u) {
+                in_degree[edge.to] -= 1;
+                if in_degree[edge.to] == 0 {
+                    ready.push_back(edge.to);
+                }
+            }
+        }
+
+        if order.len() == n {
+            Ok(order)
+        } else {
+            // Vertices left over all sit on or behind a cycle; let the DFS name one
+            Err(self.topological_sort_dfs().expect_err("Kahn's algorithm found a cycle"))
+        }
+    }
+
+    // Depth-first search: reverse postorder is a topological order
+    pub fn topological_sort_dfs(&self) -> Result<Vec<usize>, CycleError> {
+        assert!(self.is_directed(), "topological order needs a directed graph");
+        let n = self.vertex_count();
+        let mut marks = vec![Mark::Unvisited; n];
+        let mut finished = Vec::with_capacity(n);
+
+        for start in 0..n {
+            if marks[start] != Mark::Unvisited {
+                continue;
+            }
+            // Each stack entry is a vertex on the current path and the index of
+            // the next outgoing edge to explore from it
+            let mut stack = vec![(start, 0)];
+            marks[start] = Mark::OnPath;
+
+            while let Some(&mut (u, ref mut next_edge)) = stack.last_mut() {
+                let edges = self.neighbors(u);
+                if *next_edge == edges.len() {
+                    marks[u] = Mark::Finished;
+                    finished.push(u);
+                    stack.pop();
+                    continue;
+                }
+                let v = edges[*next_edge].to;
+                *next_edge += 1;
+
+                match marks[v] {
+                    Mark::Unvisited => {
+                        marks[v] = Mark::OnPath;
+                        stack.push((v, 0));
+                    }
+                    Mark::OnPath => {
+                        // Back edge u -> v: the path from v down to u is a cycle
+                        let begin = stack.iter().position(|&(w, _)| w == v).unwrap();
+                        let cycle = stack[begin..].iter().map(|&(w, _)| w).collect();
+                        return Err(CycleError { cycle });
+                    }
+                    Mark::Finished => {}
+                }
+            }
+        }
+
+        finished.reverse();
+        Ok(finished)
+    }
+
+    // Check that `order` is a permutation of the vertices with every edge pointing forward
+    pub fn is_topological_order(&self, order: &[usize]) -> bool {
+        let n = self.vertex_count();
+        let mut position = vec![usize::MAX; n];
+        for (i, &v) in order.iter().enumerate() {
+            if v >= n || position[v] != usize::MAX {
+                return false;
+            }
+            position[v] = i;
+        }
+        order.len() == n
+            && (0..n).all(|u| self.neighbors(u).iter().all(|e| position[u] < position[e.to]))
+    }
+}
+
+pub fn demo() {
+    println!("=== Build order for a small project ===");
+    let tasks = ["fetch deps", "configure", "compile lib", "compile app",
+                 "write docs", "run tests", "package"];
+    let mut graph = Graph::new_directed(tasks.len());
+    graph.add_edge(0, 1, 1.0); // fetch deps -> configure
+    graph.add_edge(1, 2, 1.0); // configure -> compile lib
+    graph.add_edge(2, 3, 1.0); // compile lib -> compile app
+    graph.add_edge(2, 5, 1.0); // compile lib -> run tests
+    graph.add_edge(3, 5, 1.0); // compile app -> run tests
+    graph.add_edge(5, 6, 1.0); // run tests -> package
+    graph.add_edge(4, 6, 1.0); // write docs -> package
+
+    let kahn = graph.topological_sort_kahn().unwrap();
+    let dfs = graph.topological_sort_dfs().unwrap();
+    println!("Kahn: {:?}", kahn.iter().map(|&v| tasks[v]).collect::<Vec<_>>());
+    println!("DFS:  {:?}", dfs.iter().map(|&v| tasks[v]).collect::<Vec<_>>());
+    println!("Both valid: {} {}", graph.is_topological_order(&kahn), graph.is_topological_order(&dfs));
+
+    println!("\n=== Introducing a cycle ===");
+    graph.add_edge(6, 1, 1.0); // package -> configure closes a loop
+    match graph.topological_sort_kahn() {
+        Ok(order) => println!("Kahn: {:?}", order),
+        Err(e) => println!("Kahn: {}", e),
+    }
+    match graph.topological_sort_dfs() {
+        Ok(order) => println!("DFS: {:?}", order),
+        Err(e) => {
+            let names: Vec<&str> = e.cycle.iter().map(|&v| tasks[v]).collect();
+            println!("DFS: {} ({})", e, names.join(" -> "));
+        }
+    }
+
+    println!("\n=== Edge cases ===");
+    let empty = Graph::new_directed(0);
+    println!("Empty graph: {:?}", empty.topological_sort_kahn());
+    let mut self_loop = Graph::new_directed(2);
+    self_loop.add_edge(1, 1, 1.0);
+    println!("Self loop: {}", self_loop.topological_sort_dfs().unwrap_err());
+
+    // A long chain would overflow a recursive DFS; the explicit stack copes
+    let n = 200_000;
+    let mut chain = Graph::new_directed(n);
+    for v in (1..n).rev() {
+        chain.add_edge(v, v - 1, 1.0);
+    }
+    let order = chain.topological_sort_dfs().unwrap();
+    println!("Chain of {}: starts at {}, valid: {}", n, order[0], chain.is_topological_order(&order));
+}
@@ -0,0 +1,281 @@
+// Union-Find (Disjoint Set) Implementation
+//
+// Tracks a collection of elements 0..n partitioned into disjoint sets, answering
+// "are these two in the same set?" and merging sets. Each set is a tree of parent
+// pointers whose root is the set's representative; two elements are connected
+// when find() leads them to the same root.
+//
+// Design choices:
+// - parent: Vec<usize> forest stored by index - a root is its own parent
+// - Union by rank: the shallower tree is attached under the deeper one, so trees
+//   stay O(log n) deep even without compression
+// - Path compression: after find() locates the root, every node on the way is
+//   re-pointed directly at it (two-pass, iterative - no recursion)
+// - Together these give O(α(n)) amortized time per operation, where α is the
+//   inverse Ackermann function (< 5 for any n that fits in memory)
+// - rank is u8: with union by rank a rank of k needs at least 2^k elements
+// - find() takes &mut self because compression rewrites the forest
+// - Trade-off: near-constant merges and queries, but sets can never be split
+//   again and enumerating a set's members requires a full scan
+
+#[derive(Debug, Clone)]
+pub struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    set_count: usize,
+    links_followed: u64, // Parent links walked by find (for measuring amortized cost)
+}
+
+impl UnionFind {
+    // n singleton sets {0}, {1}, ..., {n-1}
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            set_count: n,
+            links_followed: 0,
+        }
+    }
+
+    // Add a new singleton set and return its element
+    pub fn make_set(&mut self) -> usize {
+        let element = self.parent.len();
+        self.parent.push(element);
+        self.rank.push(0);
+        self.size.push(1);
+        self.set_count += 1;
+        element
+    }
+
+    // Representative (root) of the set containing x, compressing the path to it
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+            self.links_followed += 1;
+        }
+
+        let mut current = x;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    // Merge the sets containing a and b; returns false if they were already one set
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let root_a = self.find(a);
+        let root_b = self.find(b);
+        if root_a == root_b {
+            return false;
+        }
+
+        let (child, parent) = if self.rank[root_a] < self.rank[root_b] {
+            (root_a, root_b)
+        } else {
+            (root_b, root_a)
+        };
+        self.parent[child] = parent;
+        self.size[parent] += self.size[child];
+        if self.rank[child] == self.rank[parent] {
+            self.rank[parent] += 1;
+        }
+        self.set_count -= 1;
+        true
+    }
+
+    pub fn connected(&mut self, a: usize, b: usize) -> bool {
+        self.find(a) == self.find(b)
+    }
+
+    // Number of disjoint sets
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    // Number of elements in the set containing x
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    // Number of elements across all sets
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    pub fn links_followed(&self) -> u64 {
+        self.links_followed
+    }
+}
+
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut sets = UnionFind::new(10);
+    println!("Elements: {}, sets: {}", sets.len(), sets.set_count());
+    sets.union(0, 1);
+    sets.union(2, 3);
+    sets.union(1, 3);
+    sets.union(5, 6);
+    println!("After union(0,1), union(2,3), union(1,3), union(5,6): {} sets", sets.set_count());
+    println!("connected(0, 2): {}", sets.connected(0, 2));
+    println!("connected(0, 5): {}", sets.connected(0, 5));
+    println!("Union already-joined 0 and 3: {}", sets.union(0, 3));
+    println!("Size of set containing 3: {}", sets.set_size(3));
+    let extra = sets.make_set();
+    sets.union(extra, 6);
+    println!("New element {} joined {{5, 6}}: size {}, sets {}",
+             extra, sets.set_size(5), sets.set_count());
+
+    println!("\n=== Grouping friends into circles ===");
+    let people = ["ann", "bob", "cat", "dan", "eve", "fay"];
+    let friendships = [(0, 1), (1, 2), (3, 4)];
+    let mut circles = UnionFind::new(people.len());
+    for &(a, b) in friendships.iter() {
+        circles.union(a, b);
+    }
+    for root in 0..people.len() {
+        let members: Vec<&str> = (0..people.len())
+            .filter(|&p| circles.find(p) == root)
+            .map(|p| people[p])
+            .collect();
+        if !members.is_empty() {
+            println!("Circle: {:?}", members);
+        }
+    }
+
+    println!("\n=== Near-constant amortized cost ===");
+    println!("{:>10} {:>12} {:>14} {:>16}", "elements", "operations", "links/op", "max rank");
+    for &n in [1_000usize, 10_000, 100_000, 1_000_000].iter() {
+        let mut uf = UnionFind::new(n);
+        let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % n as u64) as usize
+        };
+        let operations = 4 * n;
+        for i in 0..operations {
+            let (a, b) = (next(), next());
+            if i % 2 == 0 {
+                uf.union(a, b);
+            } else {
+                uf.connected(a, b);
+            }
+        }
+        let max_rank = uf.rank.iter().max().copied().unwrap_or(0);
+        println!("{:>10} {:>12} {:>14.3} {:>16}",
+                 n, operations, uf.links_followed() as f64 / operations as f64, max_rank);
+    }
+    println!("Links followed per operation stays flat as n grows 1000x");
+
+    println!("\n=== Worst case for naive linking: a long chain ===");
+    let n = 100_000;
+    let mut chain = UnionFind::new(n);
+    for i in 1..n {
+        chain.union(i - 1, i);
+    }
+    let before = chain.links_followed();
+    for i in 0..n {
+        chain.find(i);
+    }
+    println!("{} finds after chaining {} elements followed {} links; sets left: {}",
+             n, n, chain.links_followed() - before, chain.set_count());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustnotes_collections::random::{Rng, XorShift64Star};
+
+    #[test]
+    fn union_and_connected() {
+        let mut sets = UnionFind::new(10);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 3));
+        assert_eq!(sets.set_count(), 7);
+        assert!(sets.connected(0, 2));
+        assert!(!sets.connected(0, 5));
+        assert_eq!(sets.set_size(3), 4);
+
+        let extra = sets.make_set();
+        assert_eq!(extra, 10);
+        assert!(sets.union(extra, 6));
+        assert_eq!((sets.len(), sets.set_count(), sets.set_size(6)), (11, 7, 2));
+    }
+
+    // Random unions against a label per element, relabelled on every merge
+    #[test]
+    fn matches_brute_force_labels() {
+        let n = 200;
+        let mut sets = UnionFind::new(n);
+        let mut labels: Vec<usize> = (0..n).collect();
+        let mut rng = XorShift64Star::seed_from_u64(7);
+        for _ in 0..500 {
+            let (a, b) = (rng.gen_index(0..n), rng.gen_index(0..n));
+            let (label_a, label_b) = (labels[a], labels[b]);
+            assert_eq!(sets.union(a, b), label_a != label_b);
+            labels.iter_mut().filter(|label| **label == label_b).for_each(|label| *label = label_a);
+
+            let (c, d) = (rng.gen_index(0..n), rng.gen_index(0..n));
+            assert_eq!(sets.connected(c, d), labels[c] == labels[d]);
+            assert_eq!(sets.set_size(c), labels.iter().filter(|&&label| label == labels[c]).count());
+        }
+        let mut distinct = labels.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(sets.set_count(), distinct.len());
+    }
+
+    // Links followed per operation must stay flat as n grows 100x, and union
+    // by rank must keep every rank within log2(n)
+    #[test]
+    fn amortized_cost_stays_flat() {
+        let mut costs = Vec::new();
+        for n in [1_000usize, 10_000, 100_000] {
+            let mut sets = UnionFind::new(n);
+            let mut rng = XorShift64Star::seed_from_u64(n as u64);
+            let operations = 4 * n;
+            for i in 0..operations {
+                let (a, b) = (rng.gen_index(0..n), rng.gen_index(0..n));
+                if i % 2 == 0 {
+                    sets.union(a, b);
+                } else {
+                    sets.connected(a, b);
+                }
+            }
+            let max_rank = sets.rank.iter().max().copied().unwrap_or(0);
+            assert!((max_rank as f64) <= (n as f64).log2());
+            costs.push(sets.links_followed() as f64 / operations as f64);
+        }
+        assert!(costs.iter().all(|&cost| cost < 3.0), "links per operation: {:?}", costs);
+        assert!(costs[2] < costs[0] * 1.5, "links per operation: {:?}", costs);
+    }
+
+    // A chain of unions stays shallow, so n finds follow O(n) links in all
+    #[test]
+    fn chain_of_unions_stays_shallow() {
+        let n = 100_000;
+        let mut sets = UnionFind::new(n);
+        for i in 1..n {
+            sets.union(i - 1, i);
+        }
+        let before = sets.links_followed();
+        for i in 0..n {
+            sets.find(i);
+        }
+        assert!(sets.links_followed() - before <= n as u64);
+        assert_eq!(sets.set_count(), 1);
+    }
+}
@@ -0,0 +1,25 @@
+// RustNotes Algorithms
+//
+// The notes that build on the collections: graph algorithms on top of the
+// heaps and union-find, compressors on top of the min-heap, and so on.
+//
+//   graphs       Graph and the algorithms that run on it
+//   encoding     Base64, Huffman, run-length, binary serialization
+//   text         lexer, JSON, suffix array, Markov chain, REPL
+//   math         big integers, matrices, geometry, simulation
+//   concurrency  MPMC queue and thread pool
+//   sorting      merge sort and quicksort, sequential and parallel
+//
+// Design choices:
+// - Depends only on rustnotes-collections; optional heavyweight pieces belong
+//   behind features here or in their own crate, not in the collections
+// - math::random re-exports the generators from rustnotes-collections, so the
+//   math notes keep one path for them
+// - Trade-off: a user who wants only Base64 still builds the collections crate
+
+pub mod concurrency;
+pub mod encoding;
+pub mod graphs;
+pub mod math;
+pub mod sorting;
+pub mod text;
@@ -0,0 +1,905 @@
+// Arbitrary-Precision Integers
+//
+// BigUint (unsigned) and BigInt (signed) grow to whatever size a result needs, so
+// 100! or 2^1000 are computed exactly instead of overflowing a u64. Numbers are
+// stored as "limbs": digits in base 2^32, least significant first, and the
+// arithmetic is the pencil-and-paper kind carried out on those digits.
+//
+// Design choices:
+// - Limbs are u32 so that every limb-by-limb product and carry fits in a u64
+// - The limb vector is always normalized (no high zero limbs; zero is empty), so
+//   derived equality and hashing are correct and len() orders magnitudes
+// - Multiplication is schoolbook O(n*m) for small operands and switches to
+//   Karatsuba (three half-size products instead of four, O(n^1.585)) once both
+//   operands reach KARATSUBA_THRESHOLD limbs
+// - Division is Knuth's Algorithm D (normalize, estimate each quotient limb from
+//   the top two limbs, correct at most twice), with a fast path for 1-limb divisors
+// - BigInt is a sign flag plus a BigUint magnitude; zero is never negative
+// - Signed division truncates toward zero and the remainder takes the dividend's
+//   sign, exactly like Rust's i64 / and %
+// - Operators (+ - * / %) work on both values and references; dividing by zero or
+//   subtracting a larger BigUint panics like the built-in integer types
+// - Parsing and printing support any radix 2..=36 and convert in chunks of
+//   several digits per limb operation; Display, {:x} and {:X} honor width and #
+// - Trade-off: clear, allocation-happy code rather than the in-place, assembly-tuned
+//   routines of a production bignum library (no Toom-Cook, FFT, or Newton division)
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+// Both operands need at least this many limbs before Karatsuba pays off
+const KARATSUBA_THRESHOLD: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBigIntError {
+    Empty,
+    InvalidDigit(char),
+}
+
+impl fmt::Display for ParseBigIntError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseBigIntError::Empty => write!(f, "cannot parse integer from empty string"),
+            ParseBigIntError::InvalidDigit(c) => write!(f, "invalid digit '{}' in integer", c),
+        }
+    }
+}
+
+impl std::error::Error for ParseBigIntError {}
+
+// ---------------------------------------------------------------------------
+// Limb-slice arithmetic shared by BigUint and BigInt
+// ---------------------------------------------------------------------------
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn cmp_limbs(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut result = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &x) in long.iter().enumerate() {
+        let sum = x as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        result.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        result.push(carry as u32);
+    }
+    result
+}
+
+// a - b where a >= b
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = a.to_vec();
+    sub_in_place(&mut result, b);
+    trim(&mut result);
+    result
+}
+
+// acc -= x, where acc >= x
+fn sub_in_place(acc: &mut [u32], x: &[u32]) {
+    let mut borrow = 0i64;
+    for (i, slot) in acc.iter_mut().enumerate() {
+        if i >= x.len() && borrow == 0 {
+            break;
+        }
+        let diff = *slot as i64 - x.get(i).copied().unwrap_or(0) as i64 - borrow;
+        *slot = diff as u32;
+        borrow = if diff < 0 { 1 } else { 0 };
+    }
+    assert_eq!(borrow, 0, "subtraction underflow");
+}
+
+// acc += x << (32 * shift)
+fn add_in_place(acc: &mut [u32], x: &[u32], shift: usize) {
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < x.len() || carry > 0 {
+        let sum = acc[shift + i] as u64 + x.get(i).copied().unwrap_or(0) as u64 + carry;
+        acc[shift + i] = sum as u32;
+        carry = sum >> 32;
+        i += 1;
+    }
+}
+
+fn schoolbook_mul(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut result = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        if x == 0 {
+            continue;
+        }
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            // At most (2^32-1)^2 + 2*(2^32-1) = 2^64-1, so this cannot overflow
+            let t = x as u64 * y as u64 + result[i + j] as u64 + carry;
+            result[i + j] = t as u32;
+            carry = t >> 32;
+        }
+        result[i + b.len()] = carry as u32;
+    }
+    trim(&mut result);
+    result
+}
+
+// Split a = a1*B^m + a0 and b = b1*B^m + b0; then
+// a*b = z2*B^2m + z1*B^m + z0 with z1 = (a0+a1)(b0+b1) - z0 - z2
+fn mul_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook_mul(a, b);
+    }
+    let m = a.len().min(b.len()) / 2;
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+
+    let mut z0 = mul_limbs(a0, b0);
+    let mut z2 = mul_limbs(a1, b1);
+    let mut z1 = mul_limbs(&add_limbs(a0, a1), &add_limbs(b0, b1));
+    trim(&mut z0);
+    trim(&mut z2);
+    sub_in_place(&mut z1, &z0);
+    sub_in_place(&mut z1, &z2);
+    trim(&mut z1);
+
+    let mut result = vec![0u32; a.len() + b.len()];
+    add_in_place(&mut result, &z0, 0);
+    add_in_place(&mut result, &z1, m);
+    add_in_place(&mut result, &z2, 2 * m);
+    trim(&mut result);
+    result
+}
+
+// x << shift bits (shift < 32), always one limb longer than x
+fn shl_bits(x: &[u32], shift: u32) -> Vec<u32> {
+    let mut result = Vec::with_capacity(x.len() + 1);
+    let mut carry = 0u32;
+    for &limb in x {
+        result.push((limb << shift) | carry);
+        carry = if shift == 0 { 0 } else { limb >> (32 - shift) };
+    }
+    result.push(carry);
+    result
+}
+
+// x >> shift bits (shift < 32)
+fn shr_bits(x: &[u32], shift: u32) -> Vec<u32> {
+    let mut result: Vec<u32> = (0..x.len())
+        .map(|i| {
+            let high = x.get(i + 1).copied().unwrap_or(0);
+            if shift == 0 { x[i] } else { (x[i] >> shift) | (high << (32 - shift)) }
+        })
+        .collect();
+    trim(&mut result);
+    result
+}
+
+// Divide by a single limb: (quotient, remainder)
+fn div_rem_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for i in (0..a.len()).rev() {
+        let current = (remainder << 32) | a[i] as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+// Knuth, TAOCP vol. 2, Algorithm D. Requires v.len() >= 2 and u >= v.
+fn knuth_div_rem(u: &[u32], v: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    const BASE: u64 = 1 << 32;
+    let n = v.len();
+    let m = u.len() - n;
+
+    // D1: shift so the divisor's top limb has its high bit set, which makes each
+    // quotient estimate at most 2 too large
+    let shift = v[n - 1].leading_zeros();
+    let v = &shl_bits(v, shift)[..n];
+    let mut u = shl_bits(u, shift);
+    let mut quotient = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        // D3: estimate the quotient limb from the top two limbs of the remainder
+        let top = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+        let mut qhat = top / v[n - 1] as u64;
+        let mut rhat = top % v[n - 1] as u64;
+        while qhat >= BASE || qhat * v[n - 2] as u64 > ((rhat << 32) | u[j + n - 2] as u64) {
+            qhat -= 1;
+            rhat += v[n - 1] as u64;
+            if rhat >= BASE {
+                break;
+            }
+        }
+
+        // D4: subtract qhat * v from the current window of u
+        let mut borrow = 0i64;
+        for i in 0..n {
+            let product = qhat * v[i] as u64;
+            let t = u[i + j] as i64 - borrow - (product & 0xFFFF_FFFF) as i64;
+            u[i + j] = t as u32;
+            borrow = (product >> 32) as i64 - (t >> 32);
+        }
+        let t = u[j + n] as i64 - borrow;
+        u[j + n] = t as u32;
+        quotient[j] = qhat as u32;
+
+        // D6: the estimate was one too large (rare) - add v back
+        if t < 0 {
+            quotient[j] -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+    }
+
+    // D8: the remainder is the low n limbs, shifted back
+    trim(&mut quotient);
+    (quotient, shr_bits(&u[..n], shift))
+}
+
+// Largest power of `radix` that fits in a limb, and how many digits it covers
+fn radix_chunk(radix: u32) -> (u32, usize) {
+    let mut power = radix;
+    let mut digits = 1;
+    while let Some(next) = power.checked_mul(radix) {
+        power = next;
+        digits += 1;
+    }
+    (power, digits)
+}
+
+// ---------------------------------------------------------------------------
+// BigUint
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigUint {
+    limbs: Vec<u32>, // Base 2^32, least significant first, no high zero limbs
+}
+
+impl BigUint {
+    pub fn zero() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    pub fn one() -> Self {
+        Self { limbs: vec![1] }
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> Self {
+        trim(&mut limbs);
+        Self { limbs }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // Number of significant bits (0 for zero)
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            None => 0,
+            Some(&top) => 32 * self.limbs.len() as u64 - top.leading_zeros() as u64,
+        }
+    }
+
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.len() {
+            0 => Some(0),
+            1 => Some(self.limbs[0] as u64),
+            2 => Some(((self.limbs[1] as u64) << 32) | self.limbs[0] as u64),
+            _ => None,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0, |acc, &limb| (acc << 32) | limb as u128))
+    }
+
+    // Nearest f64 (infinity if too large)
+    pub fn to_f64(&self) -> f64 {
+        self.limbs.iter().rev().fold(0.0, |acc, &limb| acc * 4_294_967_296.0 + limb as f64)
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        if cmp_limbs(&self.limbs, &other.limbs) == Ordering::Less {
+            None
+        } else {
+            Some(Self::from_limbs(sub_limbs(&self.limbs, &other.limbs)))
+        }
+    }
+
+    // Quotient and remainder; panics on division by zero
+    pub fn div_rem(&self, divisor: &BigUint) -> (BigUint, BigUint) {
+        assert!(!divisor.is_zero(), "attempt to divide by zero");
+        if cmp_limbs(&self.limbs, &divisor.limbs) == Ordering::Less {
+            return (Self::zero(), self.clone());
+        }
+        if divisor.limbs.len() == 1 {
+            let (q, r) = div_rem_small(&self.limbs, divisor.limbs[0]);
+            return (Self::from_limbs(q), Self::from_limbs(vec![r]));
+        }
+        let (q, r) = knuth_div_rem(&self.limbs, &divisor.limbs);
+        (Self::from_limbs(q), Self::from_limbs(r))
+    }
+
+    // Exponentiation by squaring
+    pub fn pow(&self, mut exponent: u32) -> BigUint {
+        let mut base = self.clone();
+        let mut result = Self::one();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        if text.is_empty() {
+            return Err(ParseBigIntError::Empty);
+        }
+        let digits: Vec<u32> = text
+            .chars()
+            .map(|c| c.to_digit(radix).ok_or(ParseBigIntError::InvalidDigit(c)))
+            .collect::<Result<_, _>>()?;
+
+        // Fold in several digits at a time: value = value * radix^k + chunk
+        let (_, chunk_digits) = radix_chunk(radix);
+        let mut limbs: Vec<u32> = Vec::new();
+        for chunk in digits.chunks(chunk_digits) {
+            let scale = radix.pow(chunk.len() as u32) as u64;
+            let mut carry = chunk.iter().fold(0u64, |acc, &d| acc * radix as u64 + d as u64);
+            for limb in limbs.iter_mut() {
+                let t = *limb as u64 * scale + carry;
+                *limb = t as u32;
+                carry = t >> 32;
+            }
+            if carry > 0 {
+                limbs.push(carry as u32);
+            }
+        }
+        Ok(Self::from_limbs(limbs))
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be in 2..=36");
+        if self.is_zero() {
+            return "0".to_string();
+        }
+        // Peel off chunks of k digits (least significant first) by dividing by radix^k
+        let (chunk_value, chunk_digits) = radix_chunk(radix);
+        let mut chunks = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (q, r) = div_rem_small(&rest, chunk_value);
+            chunks.push(r);
+            rest = q;
+        }
+
+        let mut text = String::new();
+        for (i, &chunk) in chunks.iter().rev().enumerate() {
+            let mut digits = Vec::with_capacity(chunk_digits);
+            let mut value = chunk;
+            while value > 0 {
+                digits.push(std::char::from_digit(value % radix, radix).unwrap());
+                value /= radix;
+            }
+            // Every chunk but the leading one is zero-padded to full width
+            if i > 0 {
+                digits.resize(chunk_digits, '0');
+            }
+            text.extend(digits.iter().rev());
+        }
+        text
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        cmp_limbs(&self.limbs, &other.limbs)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value as u32, (value >> 32) as u32])
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> Self {
+        Self::from_limbs((0..4).map(|i| (value >> (32 * i)) as u32).collect())
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(value: u32) -> Self {
+        Self::from(value as u64)
+    }
+}
+
+// Decimal, or hexadecimal with a 0x prefix
+impl FromStr for BigUint {
+    type Err = ParseBigIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            Some(hex) => Self::from_str_radix(hex, 16),
+            None => Self::from_str_radix(text, 10),
+        }
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_str_radix(10))
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16).to_uppercase())
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(add_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        BigUint::from_limbs(mul_limbs(&self.limbs, &other.limbs))
+    }
+}
+
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+// ---------------------------------------------------------------------------
+// BigInt
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BigInt {
+    negative: bool, // Never true for zero
+    magnitude: BigUint,
+}
+
+impl BigInt {
+    pub fn zero() -> Self {
+        Self::default()
+    }
+
+    pub fn one() -> Self {
+        Self::from(BigUint::one())
+    }
+
+    fn from_parts(negative: bool, magnitude: BigUint) -> Self {
+        let negative = negative && !magnitude.is_zero();
+        Self { negative, magnitude }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    // -1, 0 or 1
+    pub fn signum(&self) -> i32 {
+        if self.negative {
+            -1
+        } else if self.is_zero() {
+            0
+        } else {
+            1
+        }
+    }
+
+    pub fn abs(&self) -> BigInt {
+        Self::from_parts(false, self.magnitude.clone())
+    }
+
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    pub fn to_i64(&self) -> Option<i64> {
+        let magnitude = self.magnitude.to_u64()?;
+        if self.negative {
+            // -2^63 fits even though 2^63 does not
+            if magnitude <= i64::MAX as u64 + 1 {
+                Some((magnitude as i64).wrapping_neg())
+            } else {
+                None
+            }
+        } else if magnitude <= i64::MAX as u64 {
+            Some(magnitude as i64)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.negative {
+            if magnitude <= i128::MAX as u128 + 1 {
+                Some((magnitude as i128).wrapping_neg())
+            } else {
+                None
+            }
+        } else if magnitude <= i128::MAX as u128 {
+            Some(magnitude as i128)
+        } else {
+            None
+        }
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        let magnitude = self.magnitude.to_f64();
+        if self.negative { -magnitude } else { magnitude }
+    }
+
+    // Truncating division: the quotient rounds toward zero and the remainder has
+    // the dividend's sign (same as i64)
+    pub fn div_rem(&self, divisor: &BigInt) -> (BigInt, BigInt) {
+        let (q, r) = self.magnitude.div_rem(&divisor.magnitude);
+        (
+            Self::from_parts(self.negative != divisor.negative, q),
+            Self::from_parts(self.negative, r),
+        )
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        Self::from_parts(self.negative && exponent % 2 == 1, self.magnitude.pow(exponent))
+    }
+
+    // Optional leading + or -, then digits in the given radix
+    pub fn from_str_radix(text: &str, radix: u32) -> Result<Self, ParseBigIntError> {
+        let (negative, digits) = split_sign(text);
+        Ok(Self::from_parts(negative, BigUint::from_str_radix(digits, radix)?))
+    }
+
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        let digits = self.magnitude.to_str_radix(radix);
+        if self.negative { format!("-{}", digits) } else { digits }
+    }
+}
+
+fn split_sign(text: &str) -> (bool, &str) {
+    if let Some(rest) = text.strip_prefix('-') {
+        (true, rest)
+    } else {
+        (false, text.strip_prefix('+').unwrap_or(text))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> Self {
+        Self::from_parts(false, magnitude)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> Self {
+        Self::from_parts(value < 0, BigUint::from(value.unsigned_abs()))
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> Self {
+        Self::from_parts(value < 0, BigUint::from(value.unsigned_abs()))
+    }
+}
+
+impl From<i32> for BigInt {
+    fn from(value: i32) -> Self {
+        Self::from(value as i64)
+    }
+}
+
+// Optional sign, then decimal or 0x-prefixed hexadecimal
+impl FromStr for BigInt {
+    type Err = ParseBigIntError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (negative, digits) = split_sign(text);
+        Ok(Self::from_parts(negative, digits.parse()?))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_str_radix(10))
+    }
+}
+
+impl fmt::LowerHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negative, "0x", &self.magnitude.to_str_radix(16))
+    }
+}
+
+impl fmt::UpperHex for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negative, "0x", &self.magnitude.to_str_radix(16).to_uppercase())
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude.clone())
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::from_parts(!self.negative, self.magnitude)
+    }
+}
+
+impl Add<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::from_parts(self.negative, &self.magnitude + &other.magnitude);
+        }
+        // Opposite signs: subtract the smaller magnitude from the larger
+        match self.magnitude.cmp(&other.magnitude) {
+            Ordering::Equal => BigInt::zero(),
+            Ordering::Greater => {
+                BigInt::from_parts(self.negative, &self.magnitude - &other.magnitude)
+            }
+            Ordering::Less => BigInt::from_parts(other.negative, &other.magnitude - &self.magnitude),
+        }
+    }
+}
+
+impl Sub<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &(-other)
+    }
+}
+
+impl Mul<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::from_parts(self.negative != other.negative, &self.magnitude * &other.magnitude)
+    }
+}
+
+impl Div<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigInt> for &BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.div_rem(other).1
+    }
+}
+
+// Owned operands (a + b) forward to the reference implementations (&a + &b)
+macro_rules! forward_owned_binop {
+    ($ty:ident, $($trait:ident $method:ident),*) => {
+        $(
+            impl $trait<$ty> for $ty {
+                type Output = $ty;
+
+                fn $method(self, other: $ty) -> $ty {
+                    (&self).$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward_owned_binop!(BigUint, Add add, Sub sub, Mul mul, Div div, Rem rem);
+forward_owned_binop!(BigInt, Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+fn factorial(n: u32) -> BigUint {
+    (1..=n).fold(BigUint::one(), |acc, k| &acc * &BigUint::from(k))
+}
+
+// Small xorshift generator for building random test operands
+fn random_biguint(state: &mut u64, limbs: usize) -> BigUint {
+    let limbs = (0..limbs)
+        .map(|_| {
+            *state ^= *state << 13;
+            *state ^= *state >> 7;
+            *state ^= *state << 17;
+            *state as u32
+        })
+        .collect();
+    BigUint::from_limbs(limbs)
+}
+
+pub fn demo() {
+    println!("=== Beyond u64 ===");
+    let fifty = factorial(50);
+    println!("50! = {}", fifty);
+    println!("100! has {} decimal digits", factorial(100).to_string().len());
+    let two = BigUint::from(2u32);
+    let big_power = two.pow(256);
+    println!("2^256 = {}", big_power);
+    println!("2^256 in hex = {:#x}", big_power);
+    println!("2^256 - 1 has {} bits", (&big_power - &BigUint::one()).bits());
+
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+    for _ in 0..300 {
+        let next = &a + &b;
+        a = b;
+        b = next;
+    }
+    println!("Fibonacci(300) = {}", a);
+
+    println!("\n=== Parsing and printing ===");
+    let parsed: BigUint = "123456789012345678901234567890".parse().unwrap();
+    println!("Decimal round trip: {}", parsed);
+    println!("Same value as {:X}", parsed);
+    let from_hex: BigUint = "0xDEADBEEFCAFEBABE0123456789".parse().unwrap();
+    println!("0xDEADBEEFCAFEBABE0123456789 = {}", from_hex);
+    println!("In base 36: {}", from_hex.to_str_radix(36));
+    println!("Binary of 1000: {}", BigUint::from(1000u32).to_str_radix(2));
+    println!("Padded: [{:>12}] [{:+}] [{:#010x}]",
+             BigInt::from(-42), BigInt::from(42), BigInt::from(255));
+    println!("Parse errors: {:?}, {}", "".parse::<BigUint>().unwrap_err(),
+             "12a4".parse::<BigInt>().unwrap_err());
+
+    println!("\n=== Division ===");
+    let dividend = factorial(40);
+    let divisor: BigUint = "98765432109876543210".parse().unwrap();
+    let (q, r) = dividend.div_rem(&divisor);
+    println!("40! / {} = {} remainder {}", divisor, q, r);
+    println!("q * d + r == 40!: {}", &(&q * &divisor) + &r == dividend);
+    println!("100! / 98! = {}", factorial(100) / factorial(98));
+
+    println!("\n=== Signed arithmetic matches i64 ===");
+    let samples = [-1_000_003i64, -97, -7, -2, -1, 0, 1, 2, 7, 97, 1_000_003, i64::MIN / 3];
+    let mut all_match = true;
+    for &x in samples.iter() {
+        for &y in samples.iter() {
+            let (bx, by) = (BigInt::from(x), BigInt::from(y));
+            let mut ok = (&bx + &by).to_i64() == Some(x + y)
+                && (&bx - &by).to_i64() == Some(x - y)
+                && bx.cmp(&by) == x.cmp(&y);
+            if let Some(product) = x.checked_mul(y) {
+                ok &= (&bx * &by).to_i64() == Some(product);
+            }
+            if y != 0 {
+                ok &= (&bx / &by).to_i64() == Some(x / y) && (&bx % &by).to_i64() == Some(x % y);
+            }
+            all_match &= ok;
+        }
+    }
+    println!("+ - * / % and ordering agree on {} pairs: {}", samples.len() * samples.len(), all_match);
+    println!("-7 / 2 = {}, -7 % 2 = {}", BigInt::from(-7) / BigInt::from(2), BigInt::from(-7) % BigInt::from(2));
+    println!("(-3)^5 = {}, i64::MIN round trip: {:?}",
+             BigInt::from(-3).pow(5), BigInt::from(i64::MIN).to_i64());
+
+    println!("\n=== Karatsuba versus schoolbook ===");
+    let mut state = 0x1234_5678_9ABC_DEF0u64;
+    let x = random_biguint(&mut state, 3000);
+    let y = random_biguint(&mut state, 3000);
+    let start = std::time::Instant::now();
+    let fast = &x * &y;
+    let karatsuba_time = start.elapsed();
+    let start = std::time::Instant::now();
+    let slow = BigUint::from_limbs(schoolbook_mul(&x.limbs, &y.limbs));
+    let schoolbook_time = start.elapsed();
+    println!("3000-limb (~28,900 digit) operands:");
+    println!("  Karatsuba  {:>8.2?}", karatsuba_time);
+    println!("  Schoolbook {:>8.2?}", schoolbook_time);
+    println!("  Products equal: {}", fast == slow);
+
+    println!("\n=== Randomized division check ===");
+    let mut division_ok = true;
+    for round in 0..200 {
+        let d = random_biguint(&mut state, 1 + round % 7);
+        let q = random_biguint(&mut state, 1 + round % 11);
+        if d.is_zero() {
+            continue;
+        }
+        let r = &random_biguint(&mut state, 1 + round % 7) % &d;
+        let n = &(&q * &d) + &r;
+        division_ok &= n.div_rem(&d) == (q, r);
+    }
+    println!("(q*d + r).div_rem(d) == (q, r) for 200 random cases: {}", division_ok);
+}
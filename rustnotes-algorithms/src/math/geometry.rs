@@ -0,0 +1,527 @@
+// Computational Geometry Primitives
+//
+// Most planar geometry reduces to one question: walking from a to b, is c on
+// the left, on the right, or straight ahead? The answer is the sign of the cross
+// product (b - a) x (c - a), which is also twice the signed area of triangle abc.
+//
+//        c                    counter-clockwise: cross > 0
+//       /                     clockwise:         cross < 0
+//   a --------> b             collinear:         cross = 0
+//
+// Built on that orientation test:
+// - Segment intersection: two segments cross when each one's endpoints lie on
+//   opposite sides of the other; collinear segments may overlap in a segment
+// - Convex hull (Andrew's monotone chain): sort the points by x, then build the
+//   lower and upper hulls, popping the last point whenever it would make a
+//   clockwise (or straight) turn. O(n log n), dominated by the sort
+// - Polygon area (shoelace formula): sum of cross products of consecutive
+//   vertices; positive for counter-clockwise vertex order
+// - Point in polygon: count crossings of a ray going right from the point
+//
+// Design choices:
+// - Coordinates are f64 with exact comparisons against zero and no epsilon:
+//   for integer coordinates below about 2^26 every cross product is computed
+//   exactly, so results are exact; for arbitrary floats, nearly collinear cases
+//   can come out either way
+// - Point doubles as a vector (Add, Sub, cross, dot), which keeps formulas
+//   close to their textbook form
+// - Intersection results are an enum (None, a single Point, or an overlapping
+//   Segment) rather than Option<Point>, so collinear overlap is not lost
+// - The hull is counter-clockwise starting from the leftmost (then lowest)
+//   point, with collinear boundary points dropped
+// - Trade-off: no robust (adaptive-precision) predicates, so inputs with large
+//   or non-integer coordinates can give inconsistent answers in degenerate cases
+
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    // z component of the 3D cross product; twice the signed triangle area
+    pub fn cross(self, other: Point) -> f64 {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn dot(self, other: Point) -> f64 {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+    fn add(self, other: Point) -> Point {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+    fn sub(self, other: Point) -> Point {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Mul<f64> for Point {
+    type Output = Point;
+    fn mul(self, factor: f64) -> Point {
+        Point::new(self.x * factor, self.y * factor)
+    }
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    CounterClockwise,
+    Clockwise,
+    Collinear,
+}
+
+// Which way the path a -> b -> c turns at b
+pub fn orientation(a: Point, b: Point, c: Point) -> Orientation {
+    let cross = (b - a).cross(c - a);
+    if cross > 0.0 {
+        Orientation::CounterClockwise
+    } else if cross < 0.0 {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Segment {
+    pub a: Point,
+    pub b: Point,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Intersection {
+    None,
+    Point(Point),
+    // Collinear segments sharing more than one point
+    Overlap(Segment),
+}
+
+impl Segment {
+    pub fn new(a: Point, b: Point) -> Self {
+        Segment { a, b }
+    }
+
+    pub fn length(&self) -> f64 {
+        let d = self.b - self.a;
+        d.dot(d).sqrt()
+    }
+
+    // Shortest distance from p to any point of the segment
+    pub fn distance_to(&self, p: Point) -> f64 {
+        let d = self.b - self.a;
+        let length_squared = d.dot(d);
+        let t = if length_squared == 0.0 { 0.0 } else { ((p - self.a).dot(d) / length_squared).clamp(0.0, 1.0) };
+        let offset = p - (self.a + d * t);
+        offset.dot(offset).sqrt()
+    }
+
+    // p lies on the segment, endpoints included
+    pub fn contains(&self, p: Point) -> bool {
+        orientation(self.a, self.b, p) == Orientation::Collinear
+            && p.x >= self.a.x.min(self.b.x) && p.x <= self.a.x.max(self.b.x)
+            && p.y >= self.a.y.min(self.b.y) && p.y <= self.a.y.max(self.b.y)
+    }
+
+    // Shared point test from orientations alone (no division)
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let d1 = orientation(self.a, self.b, other.a);
+        let d2 = orientation(self.a, self.b, other.b);
+        let d3 = orientation(other.a, other.b, self.a);
+        let d4 = orientation(other.a, other.b, self.b);
+        let proper = d1 != d2 && d3 != d4
+            && d1 != Orientation::Collinear && d2 != Orientation::Collinear
+            && d3 != Orientation::Collinear && d4 != Orientation::Collinear;
+        proper || self.contains(other.a) || self.contains(other.b) || other.contains(self.a) || other.contains(self.b)
+    }
+
+    // Where the segments meet, if anywhere
+    pub fn intersection(&self, other: &Segment) -> Intersection {
+        if !self.intersects(other) {
+            return Intersection::None;
+        }
+        let r = self.b - self.a;
+        let s = other.b - other.a;
+        let denominator = r.cross(s);
+        if denominator != 0.0 {
+            // Not parallel: solve a + t r = c + u s for t
+            let t = (other.a - self.a).cross(s) / denominator;
+            return Intersection::Point(self.a + r * t);
+        }
+        // Collinear: the shared part runs between the middle two of the four
+        // endpoints when ordered along the line
+        let mut ends = [self.a, self.b, other.a, other.b];
+        let direction = if r == Point::new(0.0, 0.0) { s } else { r };
+        ends.sort_by(|p, q| p.dot(direction).total_cmp(&q.dot(direction)));
+        if ends[1] == ends[2] {
+            Intersection::Point(ends[1])
+        } else {
+            Intersection::Overlap(Segment::new(ends[1], ends[2]))
+        }
+    }
+}
+
+// Andrew's monotone chain: counter-clockwise hull without collinear points
+pub fn convex_hull(points: &[Point]) -> Vec<Point> {
+    let mut sorted = points.to_vec();
+    sorted.sort_by(|p, q| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y)));
+    sorted.dedup();
+    if sorted.len() < 3 {
+        return sorted;
+    }
+
+    let mut hull: Vec<Point> = Vec::with_capacity(sorted.len() + 1);
+    // Lower hull left to right, then upper hull right to left; each pass keeps
+    // only left turns
+    for pass in 0..2 {
+        let floor = hull.len();
+        let ordered: Box<dyn Iterator<Item = &Point>> =
+            if pass == 0 { Box::new(sorted.iter()) } else { Box::new(sorted.iter().rev()) };
+        for &p in ordered {
+            while hull.len() >= floor + 2
+                && orientation(hull[hull.len() - 2], hull[hull.len() - 1], p) != Orientation::CounterClockwise
+            {
+                hull.pop();
+            }
+            hull.push(p);
+        }
+        // The last point of each chain is the first point of the next
+        hull.pop();
+    }
+    hull
+}
+
+// Shoelace formula: positive when the vertices run counter-clockwise
+pub fn signed_area(polygon: &[Point]) -> f64 {
+    let n = polygon.len();
+    (0..n).map(|i| polygon[i].cross(polygon[(i + 1) % n])).sum::<f64>() / 2.0
+}
+
+pub fn polygon_area(polygon: &[Point]) -> f64 {
+    signed_area(polygon).abs()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Location {
+    Inside,
+    Boundary,
+    Outside,
+}
+
+// Ray casting to the right; an edge counts when it straddles the ray's height
+// (lower end included, upper end excluded, so vertices are not counted twice)
+pub fn locate(polygon: &[Point], p: Point) -> Location {
+    let n = polygon.len();
+    let mut inside = false;
+    for i in 0..n {
+        let (a, b) = (polygon[i], polygon[(i + 1) % n]);
+        if Segment::new(a, b).contains(p) {
+            return Location::Boundary;
+        }
+        if (a.y <= p.y) != (b.y <= p.y) {
+            // x where the edge reaches height p.y, compared without dividing
+            let crosses_right = if b.y > a.y {
+                (b - a).cross(p - a) > 0.0
+            } else {
+                (b - a).cross(p - a) < 0.0
+            };
+            if crosses_right {
+                inside = !inside;
+            }
+        }
+    }
+    if inside { Location::Inside } else { Location::Outside }
+}
+
+// Exact integer check for segment intersection: solve the two line equations
+// with Cramer's rule and keep the parameters as fractions
+fn brute_force_intersects(s: &Segment, t: &Segment) -> bool {
+    let int = |p: Point| (p.x as i64, p.y as i64);
+    let ((ax, ay), (bx, by), (cx, cy), (dx, dy)) = (int(s.a), int(s.b), int(t.a), int(t.b));
+    let (rx, ry, sx, sy) = (bx - ax, by - ay, dx - cx, dy - cy);
+    let denominator = rx * sy - ry * sx;
+    let (qx, qy) = (cx - ax, cy - ay);
+    let within = |numerator: i64, denominator: i64| {
+        if denominator > 0 {
+            (0..=denominator).contains(&numerator)
+        } else {
+            (denominator..=0).contains(&numerator)
+        }
+    };
+    if denominator != 0 {
+        return within(qx * sy - qy * sx, denominator) && within(qx * ry - qy * rx, denominator);
+    }
+    if qx * ry - qy * rx != 0 || qx * sy - qy * sx != 0 {
+        return false; // Parallel on different lines
+    }
+    // Same line (or points): compare intervals on both axes
+    let overlaps = |a: i64, b: i64, c: i64, d: i64| a.min(b) <= c.max(d) && c.min(d) <= a.max(b);
+    overlaps(ax, bx, cx, dx) && overlaps(ay, by, cy, dy)
+}
+
+// Hull from first principles: p -> q is a hull edge when no point lies to its
+// right and every collinear point lies between p and q
+fn brute_force_hull(points: &[Point]) -> Vec<Point> {
+    let mut vertices = Vec::new();
+    for &p in points {
+        for &q in points {
+            if p == q || vertices.contains(&p) {
+                continue;
+            }
+            let edge = Segment::new(p, q);
+            let is_edge = points.iter().all(|&r| match orientation(p, q, r) {
+                Orientation::CounterClockwise => true,
+                Orientation::Clockwise => false,
+                Orientation::Collinear => edge.contains(r),
+            });
+            if is_edge {
+                vertices.push(p);
+            }
+        }
+    }
+    vertices
+}
+
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 { a.abs() } else { gcd(b, a % b) }
+}
+
+// Pick's theorem for a lattice polygon: area = interior + boundary / 2 - 1,
+// with interior points counted by testing every lattice point in the bounding box
+fn pick_area(polygon: &[Point], size: i64) -> f64 {
+    let n = polygon.len();
+    let boundary: i64 = (0..n)
+        .map(|i| {
+            let d = polygon[(i + 1) % n] - polygon[i];
+            gcd(d.x as i64, d.y as i64)
+        })
+        .sum();
+    let mut interior = 0;
+    for x in 0..=size {
+        for y in 0..=size {
+            if locate(polygon, Point::new(x as f64, y as f64)) == Location::Inside {
+                interior += 1;
+            }
+        }
+    }
+    interior as f64 + boundary as f64 / 2.0 - 1.0
+}
+
+pub fn demo() {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut random = |limit: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % limit) as f64
+    };
+
+    println!("=== Orientation and segments ===");
+    let (a, b) = (Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+    for &c in [Point::new(2.0, 3.0), Point::new(2.0, -1.0), Point::new(8.0, 0.0)].iter() {
+        println!("{} -> {} -> {}: {:?}", a, b, c, orientation(a, b, c));
+    }
+    let cases = [
+        ("crossing", Segment::new(Point::new(0.0, 0.0), Point::new(4.0, 4.0)), Segment::new(Point::new(0.0, 4.0), Point::new(4.0, 0.0))),
+        ("touching at an end", Segment::new(Point::new(0.0, 0.0), Point::new(2.0, 2.0)), Segment::new(Point::new(2.0, 2.0), Point::new(5.0, 0.0))),
+        ("parallel", Segment::new(Point::new(0.0, 0.0), Point::new(4.0, 0.0)), Segment::new(Point::new(0.0, 1.0), Point::new(4.0, 1.0))),
+        ("collinear overlap", Segment::new(Point::new(0.0, 0.0), Point::new(4.0, 2.0)), Segment::new(Point::new(6.0, 3.0), Point::new(2.0, 1.0))),
+        ("collinear apart", Segment::new(Point::new(0.0, 0.0), Point::new(1.0, 1.0)), Segment::new(Point::new(2.0, 2.0), Point::new(3.0, 3.0))),
+        ("non-integer crossing", Segment::new(Point::new(0.0, 0.0), Point::new(3.0, 1.0)), Segment::new(Point::new(1.0, 1.0), Point::new(2.0, -1.0))),
+    ];
+    for (name, s, t) in cases.iter() {
+        println!("{:>20}: {:?}", name, s.intersection(t));
+    }
+
+    println!("\n=== Convex hull and area ===");
+    let points: Vec<Point> = [(0, 0), (2, 1), (4, 0), (4, 4), (2, 2), (0, 4), (1, 3), (2, 4), (3, 1), (4, 2)]
+        .iter()
+        .map(|&(x, y)| Point::new(x as f64, y as f64))
+        .collect();
+    let hull = convex_hull(&points);
+    println!("Hull of {} points: {}", points.len(), hull.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(" "));
+    println!("Hull area: {} (signed {}, counter-clockwise)", polygon_area(&hull), signed_area(&hull));
+    // An L-shaped (non-convex) polygon, listed clockwise
+    let l_shape: Vec<Point> = [(0, 0), (0, 4), (1, 4), (1, 1), (3, 1), (3, 0)]
+        .iter()
+        .map(|&(x, y)| Point::new(x as f64, y as f64))
+        .collect();
+    println!("L shape area: {} (signed {}, clockwise)", polygon_area(&l_shape), signed_area(&l_shape));
+    for &p in [Point::new(0.5, 2.0), Point::new(2.0, 2.0), Point::new(1.0, 3.0), Point::new(2.0, 0.5)].iter() {
+        println!("  {} is {:?}", p, locate(&l_shape, p));
+    }
+
+    println!("\n=== Random segments against exact fractions ===");
+    let trials = 20_000;
+    let mut agree = 0;
+    let mut hits = 0;
+    for _ in 0..trials {
+        // A small grid makes touching, collinear and degenerate cases common
+        let mut point = || Point::new(random(6), random(6));
+        let (s, t) = (Segment::new(point(), point()), Segment::new(point(), point()));
+        let expected = brute_force_intersects(&s, &t);
+        let found = s.intersection(&t);
+        let located = match found {
+            Intersection::None => true,
+            // Crossing points need not be representable, so allow rounding
+            Intersection::Point(p) => s.distance_to(p) < 1e-9 && t.distance_to(p) < 1e-9,
+            Intersection::Overlap(o) => s.contains(o.a) && s.contains(o.b) && t.contains(o.a) && t.contains(o.b),
+        };
+        if s.intersects(&t) == expected && (found != Intersection::None) == expected && located {
+            agree += 1;
+        }
+        hits += expected as usize;
+    }
+    println!("{}/{} random segment pairs agree ({} intersect)", agree, trials, hits);
+
+    println!("\n=== Random hulls against brute force and Pick's theorem ===");
+    let trials = 300;
+    let mut hulls_agree = 0;
+    let mut areas_agree = 0;
+    for trial in 0..trials {
+        let count = 3 + trial % 25;
+        let points: Vec<Point> = (0..count).map(|_| Point::new(random(12), random(12))).collect();
+        let hull = convex_hull(&points);
+        let mut expected = brute_force_hull(&points);
+        let mut found = hull.clone();
+        let key = |p: &Point, q: &Point| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y));
+        expected.sort_by(key);
+        found.sort_by(key);
+        if found == expected {
+            hulls_agree += 1;
+        }
+        // Degenerate hulls (all points collinear) have no area to compare
+        if hull.len() < 3 || (signed_area(&hull) > 0.0 && polygon_area(&hull) == pick_area(&hull, 12)) {
+            areas_agree += 1;
+        }
+    }
+    println!("{}/{} hulls match the brute-force hull, {}/{} areas match Pick's theorem",
+             hulls_agree, trials, areas_agree, trials);
+
+    let large: Vec<Point> = (0..200_000).map(|_| Point::new(random(1 << 20), random(1 << 20))).collect();
+    let start = std::time::Instant::now();
+    let hull = convex_hull(&large);
+    println!("Hull of {} random points: {} vertices in {:?}", large.len(), hull.len(), start.elapsed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustnotes_collections::random::{Rng, XorShift64Star};
+
+    fn points(coordinates: &[(i32, i32)]) -> Vec<Point> {
+        coordinates.iter().map(|&(x, y)| Point::new(x as f64, y as f64)).collect()
+    }
+
+    fn segment(a: (i32, i32), b: (i32, i32)) -> Segment {
+        Segment::new(Point::new(a.0 as f64, a.1 as f64), Point::new(b.0 as f64, b.1 as f64))
+    }
+
+    #[test]
+    fn orientation_of_a_turn() {
+        let (a, b) = (Point::new(0.0, 0.0), Point::new(4.0, 0.0));
+        assert_eq!(orientation(a, b, Point::new(2.0, 3.0)), Orientation::CounterClockwise);
+        assert_eq!(orientation(a, b, Point::new(2.0, -1.0)), Orientation::Clockwise);
+        assert_eq!(orientation(a, b, Point::new(8.0, 0.0)), Orientation::Collinear);
+    }
+
+    #[test]
+    fn segment_intersections() {
+        let meet = |s: Segment, t: Segment| (s.intersection(&t), t.intersection(&s));
+        let at = |x: f64, y: f64| Intersection::Point(Point::new(x, y));
+        assert_eq!(meet(segment((0, 0), (4, 4)), segment((0, 4), (4, 0))), (at(2.0, 2.0), at(2.0, 2.0)));
+        assert_eq!(meet(segment((0, 0), (2, 2)), segment((2, 2), (5, 0))), (at(2.0, 2.0), at(2.0, 2.0)));
+        assert_eq!(meet(segment((0, 0), (4, 0)), segment((0, 1), (4, 1))), (Intersection::None, Intersection::None));
+        assert_eq!(segment((0, 0), (4, 2)).intersection(&segment((6, 3), (2, 1))),
+                   Intersection::Overlap(segment((2, 1), (4, 2))));
+        assert_eq!(meet(segment((0, 0), (1, 1)), segment((2, 2), (3, 3))), (Intersection::None, Intersection::None));
+        // Crosses at (9/7, 3/7), which a double can only approximate
+        match segment((0, 0), (3, 1)).intersection(&segment((1, 1), (2, -1))) {
+            Intersection::Point(p) => assert!((p.x - 9.0 / 7.0).abs() < 1e-12 && (p.y - 3.0 / 7.0).abs() < 1e-12),
+            other => panic!("expected a crossing point, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn hull_area_and_location() {
+        let cloud = points(&[(0, 0), (2, 1), (4, 0), (4, 4), (2, 2), (0, 4), (1, 3), (2, 4), (3, 1), (4, 2)]);
+        let hull = convex_hull(&cloud);
+        // Collinear points such as (4, 2) and (2, 4) are left out
+        assert_eq!(hull, points(&[(0, 0), (4, 0), (4, 4), (0, 4)]));
+        assert_eq!((polygon_area(&hull), signed_area(&hull)), (16.0, 16.0));
+
+        let l_shape = points(&[(0, 0), (0, 4), (1, 4), (1, 1), (3, 1), (3, 0)]);
+        assert_eq!((polygon_area(&l_shape), signed_area(&l_shape)), (6.0, -6.0));
+        assert_eq!(locate(&l_shape, Point::new(0.5, 2.0)), Location::Inside);
+        assert_eq!(locate(&l_shape, Point::new(2.0, 2.0)), Location::Outside);
+        assert_eq!(locate(&l_shape, Point::new(1.0, 3.0)), Location::Boundary);
+        assert_eq!(locate(&l_shape, Point::new(2.0, 0.5)), Location::Inside);
+
+        assert!(convex_hull(&[]).is_empty());
+        assert_eq!(convex_hull(&points(&[(1, 1), (1, 1), (2, 2)])), points(&[(1, 1), (2, 2)]));
+    }
+
+    #[test]
+    fn random_segments_match_exact_fractions() {
+        let mut rng = XorShift64Star::seed_from_u64(11);
+        let mut hits = 0;
+        for _ in 0..20_000 {
+            // A small grid makes touching, collinear and degenerate cases common
+            let mut point = || Point::new(rng.gen_range(0..6) as f64, rng.gen_range(0..6) as f64);
+            let (s, t) = (Segment::new(point(), point()), Segment::new(point(), point()));
+            let expected = brute_force_intersects(&s, &t);
+            assert_eq!(s.intersects(&t), expected, "{:?} {:?}", s, t);
+            match s.intersection(&t) {
+                Intersection::None => assert!(!expected, "{:?} {:?}", s, t),
+                // Crossing points need not be representable, so allow rounding
+                Intersection::Point(p) => assert!(expected && s.distance_to(p) < 1e-9 && t.distance_to(p) < 1e-9),
+                Intersection::Overlap(o) => {
+                    assert!(expected && s.contains(o.a) && s.contains(o.b) && t.contains(o.a) && t.contains(o.b))
+                }
+            }
+            hits += expected as usize;
+        }
+        assert!(hits > 1000);
+    }
+
+    #[test]
+    fn random_hulls_match_brute_force_and_picks_theorem() {
+        let mut rng = XorShift64Star::seed_from_u64(12);
+        let key = |p: &Point, q: &Point| p.x.total_cmp(&q.x).then(p.y.total_cmp(&q.y));
+        for trial in 0..300 {
+            let count = 3 + trial % 25;
+            let cloud: Vec<Point> =
+                (0..count).map(|_| Point::new(rng.gen_range(0..12) as f64, rng.gen_range(0..12) as f64)).collect();
+            let hull = convex_hull(&cloud);
+            let mut expected = brute_force_hull(&cloud);
+            let mut found = hull.clone();
+            expected.sort_by(key);
+            found.sort_by(key);
+            assert_eq!(found, expected, "points {:?}", cloud);
+            // Degenerate hulls (all points collinear) have no area to compare
+            if hull.len() >= 3 {
+                assert!(signed_area(&hull) > 0.0);
+                assert_eq!(polygon_area(&hull), pick_area(&hull, 12), "hull {:?}", hull);
+            }
+        }
+    }
+}
@@ -0,0 +1,521 @@
+// Linear Algebra: Matrix and Vector
+//
+// Dense real-valued matrices and vectors with the core operations of a first
+// linear algebra course: products, transpose, determinant, inverse, and solving
+// the linear system Ax = b. The heavy lifting is an LU decomposition with partial
+// pivoting (PA = LU), which is computed once and then reused for determinants,
+// inverses and any number of right-hand sides.
+//
+// Design choices:
+// - Matrix stores its entries in one row-major Vec<f64>; m[(row, col)] indexes it
+// - Vector is a separate type so shape mistakes (matrix where a vector was meant)
+//   are caught by the compiler rather than at run time
+// - Operations that can fail (mismatched shapes, non-square, singular) return
+//   Result<_, LinAlgError> instead of panicking, so a calculator or other front
+//   end can report the problem and carry on
+// - LinAlgError implements Display, so callers that use String errors (like the
+//   calculator) can simply map_err(|e| e.to_string())
+// - Partial pivoting picks the largest remaining entry in each column, which keeps
+//   elimination numerically stable for ordinary inputs
+// - A pivot smaller than a tolerance relative to the matrix's largest entry counts
+//   as zero, so nearly singular matrices are reported instead of producing garbage
+// - Trade-off: dense O(n^3) algorithms with f64 rounding; no sparse storage,
+//   exact arithmetic, or eigenvalues
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+// Pivots smaller than this (relative to the largest entry) are treated as zero
+const SINGULAR_TOLERANCE: f64 = 1e-12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LinAlgError {
+    // Operand shapes do not fit the operation: (rows, cols) of each side
+    DimensionMismatch { left: (usize, usize), right: (usize, usize) },
+    NotSquare { rows: usize, cols: usize },
+    Singular,
+    // from_rows was given rows of different lengths, or new() the wrong entry count
+    Malformed(String),
+}
+
+impl fmt::Display for LinAlgError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LinAlgError::DimensionMismatch { left, right } => write!(
+                f,
+                "dimension mismatch: {}x{} and {}x{}",
+                left.0, left.1, right.0, right.1
+            ),
+            LinAlgError::NotSquare { rows, cols } => {
+                write!(f, "matrix must be square, got {}x{}", rows, cols)
+            }
+            LinAlgError::Singular => write!(f, "matrix is singular"),
+            LinAlgError::Malformed(reason) => write!(f, "malformed matrix: {}", reason),
+        }
+    }
+}
+
+impl std::error::Error for LinAlgError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Vector {
+    data: Vec<f64>,
+}
+
+impl Vector {
+    pub fn new(data: Vec<f64>) -> Self {
+        Self { data }
+    }
+
+    pub fn zeros(len: usize) -> Self {
+        Self { data: vec![0.0; len] }
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
+
+    fn check_same_len(&self, other: &Vector) -> Result<(), LinAlgError> {
+        if self.len() == other.len() {
+            Ok(())
+        } else {
+            Err(LinAlgError::DimensionMismatch {
+                left: (self.len(), 1),
+                right: (other.len(), 1),
+            })
+        }
+    }
+
+    pub fn dot(&self, other: &Vector) -> Result<f64, LinAlgError> {
+        self.check_same_len(other)?;
+        Ok(self.data.iter().zip(&other.data).map(|(a, b)| a * b).sum())
+    }
+
+    // Euclidean length
+    pub fn norm(&self) -> f64 {
+        self.data.iter().map(|x| x * x).sum::<f64>().sqrt()
+    }
+
+    pub fn add(&self, other: &Vector) -> Result<Vector, LinAlgError> {
+        self.check_same_len(other)?;
+        Ok(Vector::new(self.data.iter().zip(&other.data).map(|(a, b)| a + b).collect()))
+    }
+
+    pub fn sub(&self, other: &Vector) -> Result<Vector, LinAlgError> {
+        self.check_same_len(other)?;
+        Ok(Vector::new(self.data.iter().zip(&other.data).map(|(a, b)| a - b).collect()))
+    }
+
+    pub fn scale(&self, factor: f64) -> Vector {
+        Vector::new(self.data.iter().map(|x| x * factor).collect())
+    }
+}
+
+impl Index<usize> for Vector {
+    type Output = f64;
+
+    fn index(&self, index: usize) -> &f64 {
+        &self.data[index]
+    }
+}
+
+impl IndexMut<usize> for Vector {
+    fn index_mut(&mut self, index: usize) -> &mut f64 {
+        &mut self.data[index]
+    }
+}
+
+impl From<Vec<f64>> for Vector {
+    fn from(data: Vec<f64>) -> Self {
+        Self::new(data)
+    }
+}
+
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, x) in self.data.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", x)?;
+        }
+        write!(f, "]")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matrix {
+    rows: usize,
+    cols: usize,
+    data: Vec<f64>, // Row-major: entry (r, c) is data[r * cols + c]
+}
+
+impl Matrix {
+    // Build from row-major entries; data.len() must equal rows * cols
+    pub fn new(rows: usize, cols: usize, data: Vec<f64>) -> Result<Self, LinAlgError> {
+        if data.len() != rows * cols {
+            return Err(LinAlgError::Malformed(format!(
+                "{} entries for a {}x{} matrix",
+                data.len(),
+                rows,
+                cols
+            )));
+        }
+        Ok(Self { rows, cols, data })
+    }
+
+    pub fn from_rows(rows: Vec<Vec<f64>>) -> Result<Self, LinAlgError> {
+        let cols = rows.first().map_or(0, |row| row.len());
+        if let Some(row) = rows.iter().find(|row| row.len() != cols) {
+            return Err(LinAlgError::Malformed(format!(
+                "row of length {} where {} was expected",
+                row.len(),
+                cols
+            )));
+        }
+        let row_count = rows.len();
+        Self::new(row_count, cols, rows.into_iter().flatten().collect())
+    }
+
+    pub fn zeros(rows: usize, cols: usize) -> Self {
+        Self { rows, cols, data: vec![0.0; rows * cols] }
+    }
+
+    pub fn identity(n: usize) -> Self {
+        let mut m = Self::zeros(n, n);
+        for i in 0..n {
+            m[(i, i)] = 1.0;
+        }
+        m
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.cols
+    }
+
+    pub fn row(&self, r: usize) -> &[f64] {
+        &self.data[r * self.cols..(r + 1) * self.cols]
+    }
+
+    pub fn transpose(&self) -> Matrix {
+        let mut t = Matrix::zeros(self.cols, self.rows);
+        for r in 0..self.rows {
+            for c in 0..self.cols {
+                t[(c, r)] = self[(r, c)];
+            }
+        }
+        t
+    }
+
+    fn check_same_shape(&self, other: &Matrix) -> Result<(), LinAlgError> {
+        if self.shape() == other.shape() {
+            Ok(())
+        } else {
+            Err(LinAlgError::DimensionMismatch { left: self.shape(), right: other.shape() })
+        }
+    }
+
+    pub fn add(&self, other: &Matrix) -> Result<Matrix, LinAlgError> {
+        self.check_same_shape(other)?;
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a + b).collect();
+        Ok(Matrix { rows: self.rows, cols: self.cols, data })
+    }
+
+    pub fn sub(&self, other: &Matrix) -> Result<Matrix, LinAlgError> {
+        self.check_same_shape(other)?;
+        let data = self.data.iter().zip(&other.data).map(|(a, b)| a - b).collect();
+        Ok(Matrix { rows: self.rows, cols: self.cols, data })
+    }
+
+    pub fn scale(&self, factor: f64) -> Matrix {
+        let data = self.data.iter().map(|x| x * factor).collect();
+        Matrix { rows: self.rows, cols: self.cols, data }
+    }
+
+    // Matrix product: (n x m) * (m x p) = (n x p)
+    pub fn mul(&self, other: &Matrix) -> Result<Matrix, LinAlgError> {
+        if self.cols != other.rows {
+            return Err(LinAlgError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut product = Matrix::zeros(self.rows, other.cols);
+        // i-k-j loop order walks both operands row by row (cache friendly)
+        for i in 0..self.rows {
+            for k in 0..self.cols {
+                let a = self[(i, k)];
+                if a == 0.0 {
+                    continue;
+                }
+                for j in 0..other.cols {
+                    product[(i, j)] += a * other[(k, j)];
+                }
+            }
+        }
+        Ok(product)
+    }
+
+    pub fn mul_vector(&self, v: &Vector) -> Result<Vector, LinAlgError> {
+        if self.cols != v.len() {
+            return Err(LinAlgError::DimensionMismatch { left: self.shape(), right: (v.len(), 1) });
+        }
+        Ok(Vector::new(
+            (0..self.rows)
+                .map(|r| self.row(r).iter().zip(&v.data).map(|(a, b)| a * b).sum())
+                .collect(),
+        ))
+    }
+
+    // PA = LU with partial pivoting
+    pub fn lu(&self) -> Result<Lu, LinAlgError> {
+        if !self.is_square() {
+            return Err(LinAlgError::NotSquare { rows: self.rows, cols: self.cols });
+        }
+        let n = self.rows;
+        let mut lu = self.clone();
+        let mut permutation: Vec<usize> = (0..n).collect();
+        let mut sign = 1.0;
+        let largest = self.data.iter().fold(0.0f64, |m, x| m.max(x.abs()));
+        let tolerance = SINGULAR_TOLERANCE * largest.max(1.0);
+
+        for k in 0..n {
+            // Choose the row with the largest entry in column k as the pivot
+            let pivot_row = (k..n)
+                .max_by(|&a, &b| lu[(a, k)].abs().partial_cmp(&lu[(b, k)].abs()).unwrap())
+                .unwrap();
+            if lu[(pivot_row, k)].abs() <= tolerance {
+                return Err(LinAlgError::Singular);
+            }
+            if pivot_row != k {
+                lu.swap_rows(k, pivot_row);
+                permutation.swap(k, pivot_row);
+                sign = -sign;
+            }
+
+            // Eliminate below the pivot, storing the multipliers in L's place
+            let pivot = lu[(k, k)];
+            for r in k + 1..n {
+                let factor = lu[(r, k)] / pivot;
+                lu[(r, k)] = factor;
+                for c in k + 1..n {
+                    lu[(r, c)] -= factor * lu[(k, c)];
+                }
+            }
+        }
+        Ok(Lu { lu, permutation, sign })
+    }
+
+    fn swap_rows(&mut self, a: usize, b: usize) {
+        for c in 0..self.cols {
+            self.data.swap(a * self.cols + c, b * self.cols + c);
+        }
+    }
+
+    // Singular matrices have determinant 0 rather than an error
+    pub fn determinant(&self) -> Result<f64, LinAlgError> {
+        match self.lu() {
+            Ok(lu) => Ok(lu.determinant()),
+            Err(LinAlgError::Singular) => Ok(0.0),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, LinAlgError> {
+        self.lu()?.inverse()
+    }
+
+    // Solve Ax = b for x
+    pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        self.lu()?.solve(b)
+    }
+
+    // Largest entry-wise difference, for comparing results computed in floating point
+    pub fn max_abs_diff(&self, other: &Matrix) -> Result<f64, LinAlgError> {
+        self.check_same_shape(other)?;
+        Ok(self.data.iter().zip(&other.data).fold(0.0f64, |m, (a, b)| m.max((a - b).abs())))
+    }
+}
+
+impl Index<(usize, usize)> for Matrix {
+    type Output = f64;
+
+    fn index(&self, (r, c): (usize, usize)) -> &f64 {
+        assert!(r < self.rows && c < self.cols, "index ({}, {}) out of bounds", r, c);
+        &self.data[r * self.cols + c]
+    }
+}
+
+impl IndexMut<(usize, usize)> for Matrix {
+    fn index_mut(&mut self, (r, c): (usize, usize)) -> &mut f64 {
+        assert!(r < self.rows && c < self.cols, "index ({}, {}) out of bounds", r, c);
+        &mut self.data[r * self.cols + c]
+    }
+}
+
+// Columns are right-aligned to the widest entry; a precision ({:.3}) applies to every entry
+impl fmt::Display for Matrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let cells: Vec<String> = self
+            .data
+            .iter()
+            .map(|x| match f.precision() {
+                Some(precision) => format!("{:.*}", precision, x),
+                None => format!("{}", x),
+            })
+            .collect();
+        let width = cells.iter().map(|s| s.len()).max().unwrap_or(0);
+        for r in 0..self.rows {
+            write!(f, "[")?;
+            for c in 0..self.cols {
+                if c > 0 {
+                    write!(f, " ")?;
+                }
+                write!(f, "{:>width$}", cells[r * self.cols + c], width = width)?;
+            }
+            writeln!(f, "]")?;
+        }
+        Ok(())
+    }
+}
+
+// A factored square matrix: L (unit lower triangle) and U share one matrix,
+// and permutation[i] is the original row now in position i
+#[derive(Debug, Clone)]
+pub struct Lu {
+    lu: Matrix,
+    permutation: Vec<usize>,
+    sign: f64, // +1 or -1 depending on the number of row swaps
+}
+
+impl Lu {
+    // det(A) = sign * product of U's diagonal
+    pub fn determinant(&self) -> f64 {
+        (0..self.lu.rows).fold(self.sign, |det, i| det * self.lu[(i, i)])
+    }
+
+    // Forward substitution with L, then back substitution with U
+    pub fn solve(&self, b: &Vector) -> Result<Vector, LinAlgError> {
+        let n = self.lu.rows;
+        if b.len() != n {
+            return Err(LinAlgError::DimensionMismatch { left: (n, n), right: (b.len(), 1) });
+        }
+        let mut x: Vec<f64> = self.permutation.iter().map(|&p| b[p]).collect();
+        for i in 0..n {
+            for j in 0..i {
+                x[i] -= self.lu[(i, j)] * x[j];
+            }
+        }
+        for i in (0..n).rev() {
+            for j in i + 1..n {
+                x[i] -= self.lu[(i, j)] * x[j];
+            }
+            x[i] /= self.lu[(i, i)];
+        }
+        Ok(Vector::new(x))
+    }
+
+    // Solve for each column of the identity
+    pub fn inverse(&self) -> Result<Matrix, LinAlgError> {
+        let n = self.lu.rows;
+        let mut inverse = Matrix::zeros(n, n);
+        for c in 0..n {
+            let mut unit = Vector::zeros(n);
+            unit[c] = 1.0;
+            let column = self.solve(&unit)?;
+            for r in 0..n {
+                inverse[(r, c)] = column[r];
+            }
+        }
+        Ok(inverse)
+    }
+}
+
+pub fn demo() {
+    println!("=== Building matrices ===");
+    let a = Matrix::from_rows(vec![
+        vec![2.0, 1.0, -1.0],
+        vec![-3.0, -1.0, 2.0],
+        vec![-2.0, 1.0, 2.0],
+    ])
+    .unwrap();
+    println!("A =\n{}", a);
+    println!("Transpose of A =\n{}", a.transpose());
+    println!("Identity(3) =\n{}", Matrix::identity(3));
+
+    println!("=== Products ===");
+    let b = Matrix::new(3, 2, vec![1.0, 0.0, 0.0, 1.0, 1.0, 1.0]).unwrap();
+    println!("A * B (3x3 times 3x2) =\n{}", a.mul(&b).unwrap());
+    let v = Vector::new(vec![1.0, 2.0, 3.0]);
+    println!("A * {} = {}", v, a.mul_vector(&v).unwrap());
+    println!("v . v = {}, |v| = {:.4}", v.dot(&v).unwrap(), v.norm());
+    println!("B * B: {}", b.mul(&b).unwrap_err());
+
+    println!("\n=== Determinant, inverse and solving Ax = b ===");
+    println!("det(A) = {}", a.determinant().unwrap());
+    let inverse = a.inverse().unwrap();
+    println!("A^-1 =\n{:.3}", inverse);
+    let round_trip = a.mul(&inverse).unwrap();
+    println!("max |A * A^-1 - I| = {:e}", round_trip.max_abs_diff(&Matrix::identity(3)).unwrap());
+
+    // The classic Gaussian elimination example: solution x = 2, y = 3, z = -1
+    let rhs = Vector::new(vec![8.0, -11.0, -3.0]);
+    let x = a.solve(&rhs).unwrap();
+    println!("Solve A x = {}: x = [{:.6}, {:.6}, {:.6}]", rhs, x[0], x[1], x[2]);
+    let residual = a.mul_vector(&x).unwrap().sub(&rhs).unwrap();
+    println!("Residual |Ax - b| = {:e}", residual.norm());
+
+    println!("\n=== Reusing one factorization ===");
+    let lu = a.lu().unwrap();
+    for rhs in [vec![1.0, 0.0, 0.0], vec![0.0, 1.0, 0.0], vec![1.0, 1.0, 1.0]].iter() {
+        let x = lu.solve(&Vector::from(rhs.clone())).unwrap();
+        println!("b = {:?} -> x = [{:.3}, {:.3}, {:.3}]", rhs, x[0], x[1], x[2]);
+    }
+
+    println!("\n=== Pivoting is required ===");
+    // A zero in the top-left corner breaks elimination without row swaps
+    let needs_pivot = Matrix::from_rows(vec![vec![0.0, 1.0], vec![1.0, 0.0]]).unwrap();
+    println!("det [[0, 1], [1, 0]] = {}", needs_pivot.determinant().unwrap());
+    println!("inverse =\n{}", needs_pivot.inverse().unwrap());
+
+    println!("=== Error cases ===");
+    let singular = Matrix::from_rows(vec![vec![1.0, 2.0], vec![2.0, 4.0]]).unwrap();
+    println!("det of singular matrix: {}", singular.determinant().unwrap());
+    println!("inverse of singular matrix: {}", singular.inverse().unwrap_err());
+    println!("det of 3x2 matrix: {}", b.determinant().unwrap_err());
+    println!("ragged rows: {}", Matrix::from_rows(vec![vec![1.0, 2.0], vec![3.0]]).unwrap_err());
+    println!("solve with wrong length: {}", a.solve(&Vector::zeros(2)).unwrap_err());
+
+    println!("\n=== Larger diagonally dominant system ===");
+    let n = 50;
+    let mut big = Matrix::zeros(n, n);
+    for r in 0..n {
+        for c in 0..n {
+            big[(r, c)] = if r == c { n as f64 } else { 1.0 / (1.0 + (r + c) as f64) };
+        }
+    }
+    let expected = Vector::new((0..n).map(|i| i as f64).collect());
+    let rhs = big.mul_vector(&expected).unwrap();
+    let solved = big.solve(&rhs).unwrap();
+    println!("{}x{} solve error: {:e}", n, n, solved.sub(&expected).unwrap().norm());
+    let det_product = big.determinant().unwrap() * big.inverse().unwrap().determinant().unwrap();
+    println!("det(M) * det(M^-1) = {:.12}", det_product);
+}
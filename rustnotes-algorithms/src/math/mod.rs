@@ -0,0 +1,15 @@
+// Mathematics
+
+pub mod bigint;
+pub mod discrete_event_simulation;
+pub mod geometry;
+pub mod linear_algebra;
+pub mod sparse_matrix;
+
+pub use rustnotes_collections::random;
+
+pub use self::bigint::{BigInt, BigUint};
+pub use self::discrete_event_simulation::Simulation;
+pub use self::linear_algebra::{Matrix, Vector};
+pub use self::random::{Pcg32, Rng, XorShift64Star};
+pub use self::sparse_matrix::{CooMatrix, CsrMatrix};
@@ -0,0 +1,392 @@
+// Sparse Matrix (COO and CSR)
+//
+// Most matrices that come from graphs, meshes and text statistics are almost all
+// zeros. A sparse matrix stores only the non-zero entries, so memory and the cost
+// of products scale with the number of non-zeros (nnz) instead of rows * cols.
+//
+// Two formats, the usual pair:
+// - COO (coordinate): an unordered list of (row, col, value) triplets. Easy to
+//   build incrementally; duplicates are allowed and summed on conversion
+// - CSR (compressed sparse row): the entries of each row stored together, sorted
+//   by column, with row_ptr[r]..row_ptr[r + 1] giving row r's slice
+//
+//   [ 5 0 0 ]      values      = [5, 8, 3, 6]
+//   [ 0 8 3 ]      col_indices = [0, 1, 2, 1]
+//   [ 0 6 0 ]      row_ptr     = [0, 1, 3, 4]
+//
+// Design choices:
+// - Build with CooMatrix (or CsrMatrix::from_triplets), compute with CsrMatrix
+// - Reuses Matrix, Vector and LinAlgError from math::linear_algebra, so sparse and
+//   dense results can be compared directly and errors read the same
+// - Conversion to CSR sorts by (row, col), sums duplicates and drops explicit
+//   zeros, so each stored entry is a real non-zero in a unique position
+// - transpose() is a counting sort over columns: O(nnz + cols), no comparisons
+// - Sparse * sparse uses Gustavson's row-by-row algorithm with a dense
+//   accumulator for the current output row, O(flops + rows) overall
+// - Trade-off: CSR is fast for row access and products but slow to modify; adding
+//   an entry to a built CSR matrix means rebuilding it, hence the COO builder
+
+use crate::math::linear_algebra::{LinAlgError, Matrix, Vector};
+use std::fmt;
+use std::time::Instant;
+
+// Triplet-list builder
+#[derive(Debug, Clone)]
+pub struct CooMatrix {
+    rows: usize,
+    cols: usize,
+    entries: Vec<(usize, usize, f64)>,
+}
+
+impl CooMatrix {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        CooMatrix { rows, cols, entries: Vec::new() }
+    }
+
+    // Add `value` at (row, col); repeated positions are summed by to_csr()
+    pub fn push(&mut self, row: usize, col: usize, value: f64) -> Result<(), LinAlgError> {
+        if row >= self.rows || col >= self.cols {
+            return Err(LinAlgError::Malformed(format!(
+                "entry ({}, {}) outside a {}x{} matrix",
+                row, col, self.rows, self.cols
+            )));
+        }
+        self.entries.push((row, col, value));
+        Ok(())
+    }
+
+    pub fn to_csr(&self) -> CsrMatrix {
+        let mut entries = self.entries.clone();
+        entries.sort_by_key(|&(r, c, _)| (r, c));
+
+        let mut row_ptr = vec![0; self.rows + 1];
+        let mut col_indices = Vec::with_capacity(entries.len());
+        let mut values: Vec<f64> = Vec::with_capacity(entries.len());
+        let mut last = None;
+        for (r, c, v) in entries {
+            if last == Some((r, c)) {
+                *values.last_mut().unwrap() += v;
+            } else {
+                row_ptr[r + 1] += 1;
+                col_indices.push(c);
+                values.push(v);
+                last = Some((r, c));
+            }
+        }
+        for r in 0..self.rows {
+            row_ptr[r + 1] += row_ptr[r];
+        }
+        let mut csr = CsrMatrix { rows: self.rows, cols: self.cols, row_ptr, col_indices, values };
+        csr.drop_zeros();
+        csr
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsrMatrix {
+    rows: usize,
+    cols: usize,
+    row_ptr: Vec<usize>,
+    col_indices: Vec<usize>,
+    values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    pub fn from_triplets(rows: usize, cols: usize, triplets: &[(usize, usize, f64)]) -> Result<Self, LinAlgError> {
+        let mut coo = CooMatrix::new(rows, cols);
+        for &(r, c, v) in triplets {
+            coo.push(r, c, v)?;
+        }
+        Ok(coo.to_csr())
+    }
+
+    pub fn from_dense(dense: &Matrix) -> Self {
+        let mut row_ptr = Vec::with_capacity(dense.rows() + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_ptr.push(0);
+        for r in 0..dense.rows() {
+            for (c, &v) in dense.row(r).iter().enumerate() {
+                if v != 0.0 {
+                    col_indices.push(c);
+                    values.push(v);
+                }
+            }
+            row_ptr.push(values.len());
+        }
+        CsrMatrix { rows: dense.rows(), cols: dense.cols(), row_ptr, col_indices, values }
+    }
+
+    pub fn to_dense(&self) -> Matrix {
+        let mut dense = Matrix::zeros(self.rows, self.cols);
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) {
+                dense[(r, c)] = v;
+            }
+        }
+        dense
+    }
+
+    pub fn identity(n: usize) -> Self {
+        CsrMatrix {
+            rows: n,
+            cols: n,
+            row_ptr: (0..=n).collect(),
+            col_indices: (0..n).collect(),
+            values: vec![1.0; n],
+        }
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn shape(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    // Number of stored non-zero entries
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    // Fraction of entries that are non-zero
+    pub fn density(&self) -> f64 {
+        if self.rows == 0 || self.cols == 0 {
+            return 0.0;
+        }
+        self.nnz() as f64 / (self.rows * self.cols) as f64
+    }
+
+    // (column, value) pairs of one row, in column order
+    pub fn row(&self, r: usize) -> impl Iterator<Item = (usize, f64)> + '_ {
+        let span = self.row_ptr[r]..self.row_ptr[r + 1];
+        self.col_indices[span.clone()].iter().copied().zip(self.values[span].iter().copied())
+    }
+
+    // Entry at (r, c); binary search within the row
+    pub fn get(&self, r: usize, c: usize) -> f64 {
+        let span = self.row_ptr[r]..self.row_ptr[r + 1];
+        match self.col_indices[span.clone()].binary_search(&c) {
+            Ok(i) => self.values[span.start + i],
+            Err(_) => 0.0,
+        }
+    }
+
+    // Bytes used by the three arrays
+    pub fn storage_bytes(&self) -> usize {
+        (self.row_ptr.len() + self.col_indices.len()) * std::mem::size_of::<usize>()
+            + self.values.len() * std::mem::size_of::<f64>()
+    }
+
+    // Remove entries that are exactly zero (e.g. after duplicates cancelled)
+    fn drop_zeros(&mut self) {
+        let mut write = 0;
+        let mut start = 0;
+        for r in 0..self.rows {
+            let end = self.row_ptr[r + 1];
+            for read in start..end {
+                if self.values[read] != 0.0 {
+                    self.col_indices[write] = self.col_indices[read];
+                    self.values[write] = self.values[read];
+                    write += 1;
+                }
+            }
+            start = end;
+            self.row_ptr[r + 1] = write;
+        }
+        self.col_indices.truncate(write);
+        self.values.truncate(write);
+    }
+
+    // Counting sort by column: count entries per column, prefix-sum into
+    // positions, then scatter rows in order so each output row stays sorted
+    pub fn transpose(&self) -> CsrMatrix {
+        let mut row_ptr = vec![0; self.cols + 1];
+        for &c in &self.col_indices {
+            row_ptr[c + 1] += 1;
+        }
+        for c in 0..self.cols {
+            row_ptr[c + 1] += row_ptr[c];
+        }
+        let mut next = row_ptr.clone();
+        let mut col_indices = vec![0; self.nnz()];
+        let mut values = vec![0.0; self.nnz()];
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) {
+                col_indices[next[c]] = r;
+                values[next[c]] = v;
+                next[c] += 1;
+            }
+        }
+        CsrMatrix { rows: self.cols, cols: self.rows, row_ptr, col_indices, values }
+    }
+
+    pub fn mul_vector(&self, v: &Vector) -> Result<Vector, LinAlgError> {
+        if self.cols != v.len() {
+            return Err(LinAlgError::DimensionMismatch { left: self.shape(), right: (v.len(), 1) });
+        }
+        let x = v.as_slice();
+        Ok(Vector::new((0..self.rows).map(|r| self.row(r).map(|(c, a)| a * x[c]).sum()).collect()))
+    }
+
+    // Sparse * dense = dense
+    pub fn mul_dense(&self, other: &Matrix) -> Result<Matrix, LinAlgError> {
+        if self.cols != other.rows() {
+            return Err(LinAlgError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut product = Matrix::zeros(self.rows, other.cols());
+        for r in 0..self.rows {
+            for (k, a) in self.row(r) {
+                for (j, &b) in other.row(k).iter().enumerate() {
+                    product[(r, j)] += a * b;
+                }
+            }
+        }
+        Ok(product)
+    }
+
+    // Sparse * sparse = sparse (Gustavson): each output row is a sum of rows of
+    // `other`, scaled by this row's entries and gathered in a dense accumulator
+    pub fn mul(&self, other: &CsrMatrix) -> Result<CsrMatrix, LinAlgError> {
+        if self.cols != other.rows {
+            return Err(LinAlgError::DimensionMismatch { left: self.shape(), right: other.shape() });
+        }
+        let mut accumulator = vec![0.0; other.cols];
+        let mut occupied = vec![false; other.cols];
+        let mut touched = Vec::new();
+        let mut row_ptr = Vec::with_capacity(self.rows + 1);
+        let mut col_indices = Vec::new();
+        let mut values = Vec::new();
+        row_ptr.push(0);
+        for r in 0..self.rows {
+            for (k, a) in self.row(r) {
+                for (j, b) in other.row(k) {
+                    if !occupied[j] {
+                        occupied[j] = true;
+                        touched.push(j);
+                    }
+                    accumulator[j] += a * b;
+                }
+            }
+            touched.sort_unstable();
+            for &j in &touched {
+                if accumulator[j] != 0.0 {
+                    col_indices.push(j);
+                    values.push(accumulator[j]);
+                }
+                accumulator[j] = 0.0;
+                occupied[j] = false;
+            }
+            touched.clear();
+            row_ptr.push(values.len());
+        }
+        Ok(CsrMatrix { rows: self.rows, cols: other.cols, row_ptr, col_indices, values })
+    }
+}
+
+// Triplets, one per line, in row-major order
+impl fmt::Display for CsrMatrix {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}x{} sparse, {} non-zeros", self.rows, self.cols, self.nnz())?;
+        for r in 0..self.rows {
+            for (c, v) in self.row(r) {
+                write!(f, "\n  ({}, {}) = {}", r, c, v)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Random n x n matrix where each entry is non-zero with probability `density`
+fn random_sparse(n: usize, density: f64, state: &mut u64) -> CsrMatrix {
+    let mut next = || {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    };
+    let mut coo = CooMatrix::new(n, n);
+    let threshold = (density * u64::MAX as f64) as u64;
+    for r in 0..n {
+        for c in 0..n {
+            if next() < threshold {
+                coo.push(r, c, (next() % 19) as f64 - 9.0).unwrap();
+            }
+        }
+    }
+    coo.to_csr()
+}
+
+pub fn demo() {
+    println!("=== Building from triplets ===");
+    let a = CsrMatrix::from_triplets(3, 3, &[(1, 2, 3.0), (0, 0, 5.0), (2, 1, 6.0), (1, 1, 8.0)]).unwrap();
+    println!("{}", a);
+    println!("row_ptr {:?}, col_indices {:?}, values {:?}", a.row_ptr, a.col_indices, a.values);
+    println!("As dense:\n{}", a.to_dense());
+
+    let mut coo = CooMatrix::new(2, 2);
+    coo.push(0, 1, 2.5).unwrap();
+    coo.push(0, 1, 1.5).unwrap();
+    coo.push(1, 0, 4.0).unwrap();
+    coo.push(1, 0, -4.0).unwrap();
+    let summed = coo.to_csr();
+    println!("Duplicates summed, cancelled entries dropped: get(0, 1) = {}, nnz = {}", summed.get(0, 1), summed.nnz());
+    println!("Out of bounds: {}", coo.push(2, 0, 1.0).unwrap_err());
+
+    println!("\n=== Operations agree with the dense matrix ===");
+    let b = CsrMatrix::from_triplets(3, 2, &[(0, 1, 2.0), (1, 0, -1.0), (2, 0, 4.0), (2, 1, 1.0)]).unwrap();
+    let dense_a = a.to_dense();
+    let dense_b = b.to_dense();
+    let sparse_product = a.mul(&b).unwrap();
+    println!("A * B (sparse):\n{}", sparse_product.to_dense());
+    println!("matches dense A * B: {}", sparse_product.to_dense() == dense_a.mul(&dense_b).unwrap());
+    println!("A * dense B matches: {}", a.mul_dense(&dense_b).unwrap() == dense_a.mul(&dense_b).unwrap());
+    println!("transpose matches: {}", a.transpose().to_dense() == dense_a.transpose());
+    let v = Vector::new(vec![1.0, 2.0, 3.0]);
+    println!("A * {} = {}", v, a.mul_vector(&v).unwrap());
+    println!("A * I == A: {}", a.mul(&CsrMatrix::identity(3)).unwrap() == a);
+    println!("B * B: {}", b.mul(&b).unwrap_err());
+
+    println!("\n=== Benchmark: 99% zeros ===");
+    let mut state = 0x5EED_1234_ABCD_0001u64;
+    let n = 400;
+    let sparse_a = random_sparse(n, 0.01, &mut state);
+    let sparse_b = random_sparse(n, 0.01, &mut state);
+    let dense_a = sparse_a.to_dense();
+    let dense_b = sparse_b.to_dense();
+    println!("{}x{} matrices with {} and {} non-zeros (density {:.2}%)",
+             n, n, sparse_a.nnz(), sparse_b.nnz(), 100.0 * sparse_a.density());
+    println!("Storage: dense {} KB, CSR {} KB",
+             n * n * std::mem::size_of::<f64>() / 1024, sparse_a.storage_bytes() / 1024);
+
+    let start = Instant::now();
+    let dense_product = dense_a.mul(&dense_b).unwrap();
+    let dense_time = start.elapsed();
+    let start = Instant::now();
+    let sparse_product = sparse_a.mul(&sparse_b).unwrap();
+    let sparse_time = start.elapsed();
+    println!("Matrix product: dense {:?}, sparse {:?}; results differ by {:e}",
+             dense_time, sparse_time, sparse_product.to_dense().max_abs_diff(&dense_product).unwrap());
+    println!("Product has {} non-zeros (density {:.2}%)", sparse_product.nnz(), 100.0 * sparse_product.density());
+
+    let x = Vector::new((0..n).map(|i| i as f64).collect());
+    let start = Instant::now();
+    let mut dense_y = Vector::zeros(n);
+    for _ in 0..100 {
+        dense_y = dense_a.mul_vector(&x).unwrap();
+    }
+    let dense_time = start.elapsed();
+    let start = Instant::now();
+    let mut sparse_y = Vector::zeros(n);
+    for _ in 0..100 {
+        sparse_y = sparse_a.mul_vector(&x).unwrap();
+    }
+    let sparse_time = start.elapsed();
+    println!("100 matrix-vector products: dense {:?}, sparse {:?}; equal: {}",
+             dense_time, sparse_time, dense_y == sparse_y);
+}
@@ -0,0 +1,152 @@
+// Merge Sort
+//
+// Split the slice in half, sort each half, then merge the two sorted runs by
+// repeatedly taking the smaller front element. Always O(n log n) and stable:
+// equal elements keep their original order.
+//
+//   [5 2 8 1 | 9 3 7 4]     split
+//   [1 2 5 8 | 3 4 7 9]     sort each half (recursively)
+//   [1 2 3 4 5 7 8 9]       merge
+//
+// The two halves are independent, which makes merge sort the easiest sort to
+// parallelize: par_merge_sort sorts one half on a new thread while the current
+// thread sorts the other, down to a fixed depth, then merges as usual.
+//
+// Design choices:
+// - One scratch buffer the size of the input is allocated up front and split
+//   alongside the slice, so the recursion allocates nothing
+// - Runs of INSERTION_CUTOFF elements or fewer use insertion sort, which beats
+//   the merge's bookkeeping on short runs
+// - A merge is skipped when the left run's last element is already <= the
+//   right run's first, so sorted input costs O(n)
+// - Threads come from std::thread::scope, so the halves are borrowed rather than
+//   moved and T only needs Send, not 'static
+// - Spawning stops below PARALLEL_CUTOFF elements or after parallel_depth()
+//   splits, where a thread costs more than the sorting it would take on
+// - Trade-off: the merge itself is sequential, so the last merge walks all n
+//   elements on one core; a parallel merge (split both runs at a median)
+//   would scale further at the cost of a much longer note
+
+use super::{insertion_sort, parallel_depth, race, INSERTION_CUTOFF, PARALLEL_CUTOFF};
+use rustnotes_collections::random::{Rng, XorShift64Star};
+use std::thread;
+
+// Stable sort; clones each element into the scratch buffer and back
+pub fn merge_sort<T: Ord + Clone>(slice: &mut [T]) {
+    if slice.len() <= INSERTION_CUTOFF {
+        insertion_sort(slice);
+        return;
+    }
+    let mut buffer = slice.to_vec();
+    sort(slice, &mut buffer);
+}
+
+// merge_sort with the two halves of each split sorted on separate threads
+pub fn par_merge_sort<T: Ord + Clone + Send>(slice: &mut [T]) {
+    if slice.len() <= PARALLEL_CUTOFF {
+        merge_sort(slice);
+        return;
+    }
+    let mut buffer = slice.to_vec();
+    par_sort(slice, &mut buffer, parallel_depth());
+}
+
+fn sort<T: Ord + Clone>(slice: &mut [T], buffer: &mut [T]) {
+    if slice.len() <= INSERTION_CUTOFF {
+        insertion_sort(slice);
+        return;
+    }
+    let mid = slice.len() / 2;
+    {
+        let (left, right) = slice.split_at_mut(mid);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+        sort(left, left_buffer);
+        sort(right, right_buffer);
+    }
+    merge(slice, mid, buffer);
+}
+
+fn par_sort<T: Ord + Clone + Send>(slice: &mut [T], buffer: &mut [T], depth: u32) {
+    if depth == 0 || slice.len() <= PARALLEL_CUTOFF {
+        sort(slice, buffer);
+        return;
+    }
+    let mid = slice.len() / 2;
+    {
+        let (left, right) = slice.split_at_mut(mid);
+        let (left_buffer, right_buffer) = buffer.split_at_mut(mid);
+        thread::scope(|scope| {
+            scope.spawn(|| par_sort(left, left_buffer, depth - 1));
+            par_sort(right, right_buffer, depth - 1);
+        });
+    }
+    merge(slice, mid, buffer);
+}
+
+// Merge the sorted runs slice[..mid] and slice[mid..] through the buffer
+fn merge<T: Ord + Clone>(slice: &mut [T], mid: usize, buffer: &mut [T]) {
+    if slice[mid - 1] <= slice[mid] {
+        return;
+    }
+    let (mut i, mut j) = (0, mid);
+    for out in buffer.iter_mut() {
+        // Ties take from the left run, which keeps the sort stable
+        if j == slice.len() || (i < mid && slice[i] <= slice[j]) {
+            out.clone_from(&slice[i]);
+            i += 1;
+        } else {
+            out.clone_from(&slice[j]);
+            j += 1;
+        }
+    }
+    slice.clone_from_slice(buffer);
+}
+
+pub fn demo() {
+    println!("=== Sorting a small slice ===");
+    let mut values = [38, 27, 43, 3, 9, 82, 10, 3];
+    println!("Before: {:?}", values);
+    merge_sort(&mut values);
+    println!("After:  {:?}", values);
+
+    println!("\n=== Stability: equal keys keep their order ===");
+    #[derive(Debug, Clone)]
+    struct Card(u8, char);
+    impl PartialEq for Card {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+    impl Eq for Card {}
+    impl PartialOrd for Card {
+        fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+    impl Ord for Card {
+        // Compare by rank only; the suit just records the original order
+        fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+    let mut cards: Vec<Card> = (0..26).map(|i| Card(i * 7 % 5, (b'a' + i) as char)).collect();
+    merge_sort(&mut cards);
+    let rank_two: String = cards.iter().filter(|card| card.0 == 2).map(|card| card.1).collect();
+    println!("Rank 2 cards in sorted output: {} (input order)", rank_two);
+
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    println!("\n=== Sequential vs parallel ({} cores, {} split levels) ===", cores, parallel_depth());
+    let mut rng = XorShift64Star::seed_from_u64(2024);
+    for n in [100_000, 2_000_000] {
+        let data: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        println!("{} random u64 values:", n);
+        race(&data, &[
+            ("merge_sort", merge_sort),
+            ("par_merge_sort", par_merge_sort),
+            ("slice::sort", <[u64]>::sort),
+        ]);
+    }
+    let sorted: Vec<u64> = (0..2_000_000).collect();
+    println!("2000000 values already sorted (every merge skipped):");
+    race(&sorted, &[("merge_sort", merge_sort), ("par_merge_sort", par_merge_sort)]);
+}
@@ -0,0 +1,49 @@
+// Sorting
+
+pub mod merge_sort;
+pub mod quicksort;
+
+pub use self::merge_sort::{merge_sort, par_merge_sort};
+pub use self::quicksort::{par_quicksort, quicksort};
+
+use std::thread;
+use std::time::Instant;
+
+// Below this many elements both sorts finish with insertion sort
+const INSERTION_CUTOFF: usize = 24;
+
+// Below this many elements a parallel sort stops spawning threads
+const PARALLEL_CUTOFF: usize = 8_192;
+
+// Shifting insertion sort: quadratic, but the fastest choice for short runs
+fn insertion_sort<T: Ord>(slice: &mut [T]) {
+    for i in 1..slice.len() {
+        let mut j = i;
+        while j > 0 && slice[j] < slice[j - 1] {
+            slice.swap(j, j - 1);
+            j -= 1;
+        }
+    }
+}
+
+// How many times a parallel sort may split before it stops spawning: the
+// bit length of the core count, so there are more tasks than cores (up to
+// twice as many) and an uneven split still leaves work for every core
+fn parallel_depth() -> u32 {
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    usize::BITS - cores.leading_zeros()
+}
+
+type Sort = fn(&mut [u64]);
+
+// Sort a copy of data with each function, printing the time taken
+fn race(data: &[u64], sorts: &[(&str, Sort)]) {
+    let mut expected = data.to_vec();
+    expected.sort_unstable();
+    for (name, sort) in sorts {
+        let mut copy = data.to_vec();
+        let start = Instant::now();
+        sort(&mut copy);
+        println!("{:<20} {:>10.2?}  correct: {}", name, start.elapsed(), copy == expected);
+    }
+}
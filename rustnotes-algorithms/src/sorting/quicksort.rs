@@ -0,0 +1,136 @@
+// Quicksort
+//
+// Pick a pivot, partition the slice into elements less than, equal to and
+// greater than it, then sort the two outer parts. In place and O(n log n) on
+// average, but not stable and O(n^2) when pivots keep landing near the ends.
+//
+//   [5 2 8 5 1 9 5 3]       pivot 5
+//   [2 1 3 | 5 5 5 | 9 8]   partition: less | equal | greater
+//   [1 2 3 | 5 5 5 | 8 9]   sort the outer parts (recursively)
+//
+// The outer parts never overlap, so par_quicksort hands one of them to a new
+// thread after each partition, down to a fixed depth.
+//
+// Design choices:
+// - Median-of-three pivot from the quarter, middle and three-quarter points,
+//   so sorted and reversed input split evenly instead of hitting the quadratic
+//   case. The ends are not sampled: a three-way partition of reversed input
+//   leaves a sorted run with its minimum at the end, which would make the
+//   usual first/middle/last sample pick the second-smallest element
+// - Three-way (Dutch national flag) partition: every element equal to the
+//   pivot is placed once and never looked at again, so inputs with few
+//   distinct values stay O(n log n)
+// - The sequential sort recurses into the smaller part and loops on the
+//   larger, which bounds the stack at O(log n) frames
+// - Runs of INSERTION_CUTOFF elements or fewer use insertion sort
+// - Only swaps elements, so T needs neither Clone nor a scratch buffer, and the
+//   parallel version only needs T: Send
+// - Trade-off: each partition is sequential, so the first one walks all n
+//   elements on one core before any thread starts, and uneven splits leave
+//   threads idle; merge sort splits evenly but needs O(n) extra memory
+
+use super::{insertion_sort, parallel_depth, race, INSERTION_CUTOFF, PARALLEL_CUTOFF};
+use rustnotes_collections::random::{Rng, XorShift64Star};
+use std::cmp::Ordering;
+use std::thread;
+
+// Unstable, in-place sort
+pub fn quicksort<T: Ord>(mut slice: &mut [T]) {
+    while slice.len() > INSERTION_CUTOFF {
+        let (lt, gt) = partition(slice);
+        let (left, rest) = slice.split_at_mut(lt);
+        let right = &mut rest[gt - lt..];
+        if left.len() < right.len() {
+            quicksort(left);
+            slice = right;
+        } else {
+            quicksort(right);
+            slice = left;
+        }
+    }
+    insertion_sort(slice);
+}
+
+// quicksort with the two parts of each partition sorted on separate threads
+pub fn par_quicksort<T: Ord + Send>(slice: &mut [T]) {
+    par_sort(slice, parallel_depth());
+}
+
+fn par_sort<T: Ord + Send>(slice: &mut [T], depth: u32) {
+    if depth == 0 || slice.len() <= PARALLEL_CUTOFF {
+        quicksort(slice);
+        return;
+    }
+    let (lt, gt) = partition(slice);
+    let (left, rest) = slice.split_at_mut(lt);
+    let right = &mut rest[gt - lt..];
+    thread::scope(|scope| {
+        scope.spawn(|| par_sort(left, depth - 1));
+        par_sort(right, depth - 1);
+    });
+}
+
+// Partition around a median-of-three pivot. Returns (lt, gt) such that
+// slice[..lt] < pivot, slice[lt..gt] == pivot and slice[gt..] > pivot
+fn partition<T: Ord>(slice: &mut [T]) -> (usize, usize) {
+    let (low, mid, high) = (slice.len() / 4, slice.len() / 2, slice.len() * 3 / 4);
+    if slice[mid] < slice[low] {
+        slice.swap(low, mid);
+    }
+    if slice[high] < slice[low] {
+        slice.swap(low, high);
+    }
+    if slice[high] < slice[mid] {
+        slice.swap(mid, high);
+    }
+    // The median goes first; slice[lt] holds a copy of the pivot throughout
+    slice.swap(0, mid);
+    let (mut lt, mut i, mut gt) = (0, 1, slice.len());
+    while i < gt {
+        match slice[i].cmp(&slice[lt]) {
+            Ordering::Less => {
+                slice.swap(lt, i);
+                lt += 1;
+                i += 1;
+            }
+            Ordering::Greater => {
+                gt -= 1;
+                slice.swap(i, gt);
+            }
+            Ordering::Equal => i += 1,
+        }
+    }
+    (lt, gt)
+}
+
+pub fn demo() {
+    println!("=== Sorting a small slice ===");
+    let mut values = [38, 27, 43, 3, 9, 82, 10, 3];
+    println!("Before: {:?}", values);
+    quicksort(&mut values);
+    println!("After:  {:?}", values);
+
+    println!("\n=== One three-way partition ===");
+    let mut values = [5, 2, 8, 5, 1, 9, 5, 3];
+    let (lt, gt) = partition(&mut values);
+    println!("{:?} | {:?} | {:?}", &values[..lt], &values[lt..gt], &values[gt..]);
+
+    let cores = thread::available_parallelism().map_or(1, |n| n.get());
+    println!("\n=== Sequential vs parallel ({} cores, {} split levels) ===", cores, parallel_depth());
+    let mut rng = XorShift64Star::seed_from_u64(2024);
+    for n in [100_000, 2_000_000] {
+        let data: Vec<u64> = (0..n).map(|_| rng.next_u64()).collect();
+        println!("{} random u64 values:", n);
+        race(&data, &[
+            ("quicksort", quicksort),
+            ("par_quicksort", par_quicksort),
+            ("slice::sort_unstable", <[u64]>::sort_unstable),
+        ]);
+    }
+    let few: Vec<u64> = (0..2_000_000).map(|_| rng.gen_range(0..4)).collect();
+    println!("2000000 values, only 4 distinct (three-way partition):");
+    race(&few, &[("quicksort", quicksort), ("par_quicksort", par_quicksort)]);
+    let reversed: Vec<u64> = (0..2_000_000).rev().collect();
+    println!("2000000 values in reverse order (pivot sampling):");
+    race(&reversed, &[("quicksort", quicksort), ("par_quicksort", par_quicksort)]);
+}
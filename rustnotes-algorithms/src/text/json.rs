@@ -0,0 +1,516 @@
+// JSON Parser and Serializer
+//
+// Reads JSON text into a JsonValue tree and writes it back out, compact or
+// pretty-printed. It is a second worked example of the calculator's approach to
+// parsing: first tokenize the input into a flat list, then walk the tokens with a
+// recursive-descent parser that has one function per grammar rule.
+//
+//   value  := object | array | STRING | NUMBER | true | false | null
+//   object := '{' (STRING ':' value (',' STRING ':' value)*)? '}'
+//   array  := '[' (value (',' value)*)? ']'
+//
+// Design choices:
+// - Same structure as calculator: tokenize() on top of Lexer (text::lexer) gives
+//   a Vec of tokens ending in Eof, each with its Span, then parse_* functions that take (tokens, &mut pos) and return Result<_, String>
+// - Strict RFC 8259 syntax: no trailing commas, comments, single quotes, leading
+//   zeros, or bare NaN; errors name the problem and its line and column
+// - String escapes are decoded in the tokenizer, including \uXXXX surrogate pairs
+// - Numbers are f64, like JavaScript; integers up to 2^53 round-trip exactly
+// - Objects are BTreeMaps: keys come out sorted, and a repeated key keeps its
+//   last value
+// - Nesting deeper than MAX_DEPTH is rejected so hostile input cannot overflow
+//   the stack through recursion
+// - Display writes compact JSON; to_pretty_string() indents nested values
+// - Trade-off: builds the whole token list and tree in memory; a streaming parser
+//   would handle huge documents but is much harder to read
+
+use crate::text::lexer::{Lexer, Span};
+use std::collections::BTreeMap;
+use std::fmt;
+
+const MAX_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(String),
+    Array(Vec<JsonValue>),
+    Object(BTreeMap<String, JsonValue>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    LeftBrace,
+    RightBrace,
+    LeftBracket,
+    RightBracket,
+    Colon,
+    Comma,
+    String(String),
+    Number(f64),
+    True,
+    False,
+    Null,
+    Eof,
+}
+
+// Tokens paired with where each one starts, for error messages
+fn tokenize(input: &str) -> Result<Vec<(Token, Span)>, String> {
+    let mut tokens = Vec::new();
+    let mut lexer = Lexer::new(input);
+
+    loop {
+        // JSON whitespace is exactly these four; other Unicode spaces are errors
+        lexer.take_while(|ch| matches!(ch, ' ' | '\t' | '\n' | '\r'));
+        let start = lexer.position();
+        let ch = match lexer.peek() {
+            Some(ch) => ch,
+            None => break,
+        };
+        let token = match ch {
+            '{' | '}' | '[' | ']' | ':' | ',' => {
+                lexer.next_char();
+                match ch {
+                    '{' => Token::LeftBrace,
+                    '}' => Token::RightBrace,
+                    '[' => Token::LeftBracket,
+                    ']' => Token::RightBracket,
+                    ':' => Token::Colon,
+                    _ => Token::Comma,
+                }
+            }
+            '"' => {
+                lexer.next_char();
+                Token::String(read_string(&mut lexer, start)?)
+            }
+            '-' | '0'..='9' => {
+                // Take everything that could belong to a number, then validate it
+                let number = lexer.take_while(|c| c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E'));
+                Token::Number(parse_number(number).ok_or_else(|| {
+                    format!("Invalid number '{}' at {}", number, start)
+                })?)
+            }
+            'a'..='z' | 'A'..='Z' => {
+                let word = lexer.scan_identifier().unwrap();
+                match word {
+                    "true" => Token::True,
+                    "false" => Token::False,
+                    "null" => Token::Null,
+                    _ => return Err(format!("Unknown literal '{}' at {}", word, start)),
+                }
+            }
+            _ => return Err(format!("Unexpected character '{}' at {}", ch, start)),
+        };
+        tokens.push((token, lexer.span_from(start)));
+    }
+
+    tokens.push((Token::Eof, lexer.position()));
+    Ok(tokens)
+}
+
+// JSON numbers: -?(0|[1-9][0-9]*)(.[0-9]+)?([eE][+-]?[0-9]+)?
+fn parse_number(text: &str) -> Option<f64> {
+    let bytes = text.as_bytes();
+    let mut i = 0;
+    let digits = |i: &mut usize| {
+        let start = *i;
+        while *i < bytes.len() && bytes[*i].is_ascii_digit() {
+            *i += 1;
+        }
+        *i - start
+    };
+
+    if bytes.get(i) == Some(&b'-') {
+        i += 1;
+    }
+    let integer_start = i;
+    let integer_digits = digits(&mut i);
+    if integer_digits == 0 || (integer_digits > 1 && bytes[integer_start] == b'0') {
+        return None;
+    }
+    if bytes.get(i) == Some(&b'.') {
+        i += 1;
+        if digits(&mut i) == 0 {
+            return None;
+        }
+    }
+    if matches!(bytes.get(i), Some(b'e') | Some(b'E')) {
+        i += 1;
+        if matches!(bytes.get(i), Some(b'+') | Some(b'-')) {
+            i += 1;
+        }
+        if digits(&mut i) == 0 {
+            return None;
+        }
+    }
+    if i != bytes.len() {
+        return None;
+    }
+    text.parse().ok()
+}
+
+// Read a string body after its opening quote, decoding escapes
+fn read_string(lexer: &mut Lexer, start: Span) -> Result<String, String> {
+    let mut text = String::new();
+    loop {
+        let at = lexer.position();
+        let ch = lexer
+            .next_char()
+            .ok_or_else(|| format!("Unterminated string starting at {}", start))?;
+        match ch {
+            '"' => return Ok(text),
+            '\\' => {
+                let escape = lexer
+                    .next_char()
+                    .ok_or_else(|| format!("Unterminated string starting at {}", start))?;
+                match escape {
+                    '"' => text.push('"'),
+                    '\\' => text.push('\\'),
+                    '/' => text.push('/'),
+                    'b' => text.push('\u{8}'),
+                    'f' => text.push('\u{c}'),
+                    'n' => text.push('\n'),
+                    'r' => text.push('\r'),
+                    't' => text.push('\t'),
+                    'u' => {
+                        let high = read_hex4(lexer, at)?;
+                        let code = if (0xD800..0xDC00).contains(&high) {
+                            // A high surrogate must be followed by \u and a low surrogate
+                            if !(lexer.eat('\\') && lexer.eat('u')) {
+                                return Err(format!("Unpaired surrogate at {}", at));
+                            }
+                            let low = read_hex4(lexer, at)?;
+                            if !(0xDC00..0xE000).contains(&low) {
+                                return Err(format!("Unpaired surrogate at {}", at));
+                            }
+                            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                        } else {
+                            high
+                        };
+                        let decoded = std::char::from_u32(code)
+                            .ok_or_else(|| format!("Invalid \\u escape at {}", at))?;
+                        text.push(decoded);
+                    }
+                    other => return Err(format!("Invalid escape '\\{}' at {}", other, at)),
+                }
+            }
+            c if (c as u32) < 0x20 => {
+                return Err(format!("Unescaped control character in string at {}", at));
+            }
+            c => text.push(c),
+        }
+    }
+}
+
+fn read_hex4(lexer: &mut Lexer, at: Span) -> Result<u32, String> {
+    let mut value = 0;
+    for _ in 0..4 {
+        let digit = lexer
+            .next_char()
+            .and_then(|c| c.to_digit(16))
+            .ok_or_else(|| format!("Invalid \\u escape at {}", at))?;
+        value = value * 16 + digit;
+    }
+    Ok(value)
+}
+
+fn describe(token: &Token) -> String {
+    match token {
+        Token::Eof => "end of input".to_string(),
+        Token::String(s) => format!("string {:?}", s),
+        Token::Number(n) => format!("number {}", n),
+        other => format!("{:?}", other),
+    }
+}
+
+fn expect(tokens: &[(Token, Span)], pos: &mut usize, expected: Token, what: &str) -> Result<(), String> {
+    let (token, span) = &tokens[*pos];
+    if *token == expected {
+        *pos += 1;
+        Ok(())
+    } else {
+        Err(format!("Expected {} at {}, found {}", what, span, describe(token)))
+    }
+}
+
+fn parse_value(tokens: &[(Token, Span)], pos: &mut usize, depth: usize) -> Result<JsonValue, String> {
+    let (token, span) = &tokens[*pos];
+    match token {
+        Token::LeftBrace | Token::LeftBracket if depth >= MAX_DEPTH => {
+            Err(format!("Nesting deeper than {} levels at {}", MAX_DEPTH, span))
+        }
+        Token::LeftBrace => parse_object(tokens, pos, depth + 1),
+        Token::LeftBracket => parse_array(tokens, pos, depth + 1),
+        _ => {
+            let value = match token {
+                Token::String(s) => JsonValue::String(s.clone()),
+                Token::Number(n) => JsonValue::Number(*n),
+                Token::True => JsonValue::Bool(true),
+                Token::False => JsonValue::Bool(false),
+                Token::Null => JsonValue::Null,
+                other => {
+                    return Err(format!("Expected a value at {}, found {}", span, describe(other)))
+                }
+            };
+            *pos += 1;
+            Ok(value)
+        }
+    }
+}
+
+fn parse_object(tokens: &[(Token, Span)], pos: &mut usize, depth: usize) -> Result<JsonValue, String> {
+    expect(tokens, pos, Token::LeftBrace, "'{'")?;
+    let mut members = BTreeMap::new();
+    if tokens[*pos].0 == Token::RightBrace {
+        *pos += 1;
+        return Ok(JsonValue::Object(members));
+    }
+    loop {
+        let key = match &tokens[*pos] {
+            (Token::String(key), _) => key.clone(),
+            (other, span) => {
+                return Err(format!("Expected a string key at {}, found {}", span, describe(other)))
+            }
+        };
+        *pos += 1;
+        expect(tokens, pos, Token::Colon, "':' after object key")?;
+        let value = parse_value(tokens, pos, depth)?;
+        members.insert(key, value);
+
+        match &tokens[*pos] {
+            (Token::Comma, _) => *pos += 1,
+            (Token::RightBrace, _) => {
+                *pos += 1;
+                return Ok(JsonValue::Object(members));
+            }
+            (other, span) => {
+                return Err(format!("Expected ',' or '}}' at {}, found {}", span, describe(other)))
+            }
+        }
+    }
+}
+
+fn parse_array(tokens: &[(Token, Span)], pos: &mut usize, depth: usize) -> Result<JsonValue, String> {
+    expect(tokens, pos, Token::LeftBracket, "'['")?;
+    let mut items = Vec::new();
+    if tokens[*pos].0 == Token::RightBracket {
+        *pos += 1;
+        return Ok(JsonValue::Array(items));
+    }
+    loop {
+        items.push(parse_value(tokens, pos, depth)?);
+        match &tokens[*pos] {
+            (Token::Comma, _) => *pos += 1,
+            (Token::RightBracket, _) => {
+                *pos += 1;
+                return Ok(JsonValue::Array(items));
+            }
+            (other, span) => {
+                return Err(format!("Expected ',' or ']' at {}, found {}", span, describe(other)))
+            }
+        }
+    }
+}
+
+// Parse a complete JSON document
+pub fn parse(input: &str) -> Result<JsonValue, String> {
+    let tokens = tokenize(input)?;
+    let mut pos = 0;
+    let value = parse_value(&tokens, &mut pos, 0)?;
+    expect(&tokens, &mut pos, Token::Eof, "end of input")?;
+    Ok(value)
+}
+
+impl JsonValue {
+    // Member of an object
+    pub fn get(&self, key: &str) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Object(members) => members.get(key),
+            _ => None,
+        }
+    }
+
+    // Element of an array
+    pub fn at(&self, index: usize) -> Option<&JsonValue> {
+        match self {
+            JsonValue::Array(items) => items.get(index),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            JsonValue::String(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            JsonValue::Number(n) => Some(*n),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            JsonValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    pub fn is_null(&self) -> bool {
+        *self == JsonValue::Null
+    }
+
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = String::new();
+        self.write_pretty(&mut out, 0);
+        out
+    }
+
+    fn write_pretty(&self, out: &mut String, indent: usize) {
+        let pad = |out: &mut String, level: usize| out.push_str(&"  ".repeat(level));
+        match self {
+            JsonValue::Array(items) if !items.is_empty() => {
+                out.push_str("[\n");
+                for (i, item) in items.iter().enumerate() {
+                    pad(out, indent + 1);
+                    item.write_pretty(out, indent + 1);
+                    out.push_str(if i + 1 < items.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push(']');
+            }
+            JsonValue::Object(members) if !members.is_empty() => {
+                out.push_str("{\n");
+                for (i, (key, value)) in members.iter().enumerate() {
+                    pad(out, indent + 1);
+                    out.push_str(&format!("{}: ", JsonValue::String(key.clone())));
+                    value.write_pretty(out, indent + 1);
+                    out.push_str(if i + 1 < members.len() { ",\n" } else { "\n" });
+                }
+                pad(out, indent);
+                out.push('}');
+            }
+            scalar => out.push_str(&scalar.to_string()),
+        }
+    }
+}
+
+fn write_escaped(f: &mut fmt::Formatter, text: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            c if (c as u32) < 0x20 => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+// Compact JSON; non-finite numbers (which JSON cannot represent) become null
+impl fmt::Display for JsonValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonValue::Null => write!(f, "null"),
+            JsonValue::Bool(b) => write!(f, "{}", b),
+            JsonValue::Number(n) if n.is_finite() => write!(f, "{}", n),
+            JsonValue::Number(_) => write!(f, "null"),
+            JsonValue::String(s) => write_escaped(f, s),
+            JsonValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
+            JsonValue::Object(members) => {
+                write!(f, "{{")?;
+                for (i, (key, value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write_escaped(f, key)?;
+                    write!(f, ":{}", value)?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+pub fn demo() {
+    println!("=== Parsing a document ===");
+    let text = r#"
+        {
+            "name": "RustNotes",
+            "version": 1.5,
+            "tags": ["data structures", "algorithms", "parsing"],
+            "stable": true,
+            "license": null,
+            "stats": {"files": 42, "lines": 1.2e4, "ratio": -0.25},
+            "greeting": "café 🦀 \"quoted\"\ttab"
+        }
+    "#;
+    let doc = parse(text).unwrap();
+    println!("name     = {:?}", doc.get("name").and_then(|v| v.as_str()));
+    println!("version  = {:?}", doc.get("version").and_then(|v| v.as_f64()));
+    println!("tags[1]  = {:?}", doc.get("tags").and_then(|t| t.at(1)).and_then(|v| v.as_str()));
+    println!("stable   = {:?}", doc.get("stable").and_then(|v| v.as_bool()));
+    println!("license is null: {}", doc.get("license").is_some_and(|v| v.is_null()));
+    println!("lines    = {:?}", doc.get("stats").and_then(|s| s.get("lines")).and_then(|v| v.as_f64()));
+    println!("greeting = {}", doc.get("greeting").and_then(|v| v.as_str()).unwrap());
+
+    println!("\n=== Serializing ===");
+    println!("Compact: {}", doc);
+    println!("Pretty:\n{}", doc.to_pretty_string());
+    let reparsed = parse(&doc.to_string()).unwrap();
+    println!("Compact output parses back to the same value: {}", reparsed == doc);
+    let pretty_reparsed = parse(&doc.to_pretty_string()).unwrap();
+    println!("Pretty output parses back to the same value: {}", pretty_reparsed == doc);
+
+    println!("\n=== Building values in code ===");
+    let mut point = BTreeMap::new();
+    point.insert("x".to_string(), JsonValue::Number(3.0));
+    point.insert("y".to_string(), JsonValue::Number(-4.5));
+    point.insert("label".to_string(), JsonValue::String("line\nbreak".to_string()));
+    let built = JsonValue::Array(vec![JsonValue::Object(point), JsonValue::Number(f64::NAN), JsonValue::Array(vec![])]);
+    println!("{}", built);
+
+    println!("\n=== Errors ===");
+    let bad_inputs = [
+        "",
+        "{\"a\": 1,}",
+        "[1, 2",
+        "{\"a\" 1}",
+        "{1: 2}",
+        "01",
+        "1.",
+        "tru",
+        "\"unterminated",
+        "\"bad \\x escape\"",
+        "\"\\ud800 alone\"",
+        "[1] [2]",
+        "{'single': 1}",
+    ];
+    for input in bad_inputs.iter() {
+        println!("{:<22} {}", format!("{:?}", input), parse(input).unwrap_err());
+    }
+    let multiline = "{\n  \"a\": [1, 2],\n  \"b\": [3 4]\n}";
+    println!("{:<22} {}", "error on line 3", parse(multiline).unwrap_err());
+    let deep = "[".repeat(200) + &"]".repeat(200);
+    println!("{:<22} {}", "200 nested arrays", parse(&deep).unwrap_err());
+    let ok_deep = "[".repeat(100) + &"]".repeat(100);
+    println!("{:<22} parses: {}", "100 nested arrays", parse(&ok_deep).is_ok());
+}
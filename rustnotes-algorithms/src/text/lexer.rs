@@ -0,0 +1,196 @@
+// Lexer Framework
+//
+// The shared first stage of the parsers in this collection. Every tokenizer ends
+// up doing the same few things: look at the next character without consuming it,
+// consume runs of characters that match a rule (digits, letters, whitespace), and
+// remember where each token started so errors can point at it. Lexer does those
+// things once, and calculator and text::json build their own Token types on top.
+//
+//   let mut lexer = Lexer::new("x1 = 3.5e2");
+//   lexer.scan_identifier()   -> Some("x1")     span: line 1, column 1
+//   lexer.skip_whitespace(); lexer.eat('=') ...
+//   lexer.scan_number()       -> Some("3.5e2")  span: line 1, column 6
+//
+// Design choices:
+// - Works on a &str and a byte offset, not a Peekable<Chars>, so scanned text is
+//   returned as a slice of the input with no copying, and peeking two characters
+//   ahead is as easy as peeking one
+// - Tracks line and column (counted in characters, from 1) alongside the byte
+//   offset; Span records all three and displays as "line L, column C"
+// - Tokens are not defined here: each language keeps its own Token enum and
+//   decides what a number or identifier means, using the scan_* helpers only for
+//   the common shapes
+// - Errors are the caller's business too; Lexer only reports None when a scan
+//   does not match, and callers turn that into their own error type
+// - Trade-off: char-at-a-time scanning is simple and Unicode-correct, but slower
+//   than table-driven byte lexers
+
+use std::fmt;
+
+// A region of the input: byte offsets [start, end) plus the position of `start`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+pub struct Lexer<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+    column: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Lexer { input, offset: 0, line: 1, column: 1 }
+    }
+
+    pub fn input(&self) -> &'a str {
+        self.input
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.offset == self.input.len()
+    }
+
+    // Next character, without consuming it
+    pub fn peek(&self) -> Option<char> {
+        self.input[self.offset..].chars().next()
+    }
+
+    // Character `n` places ahead (peek_nth(0) == peek())
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.input[self.offset..].chars().nth(n)
+    }
+
+    // Consume and return the next character, updating line and column
+    pub fn next_char(&mut self) -> Option<char> {
+        let ch = self.peek()?;
+        self.offset += ch.len_utf8();
+        if ch == '\n' {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+        Some(ch)
+    }
+
+    // Consume the next character if it is `expected`
+    pub fn eat(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.next_char();
+            true
+        } else {
+            false
+        }
+    }
+
+    // Consume characters while `predicate` holds and return them as a slice
+    pub fn take_while<P: Fn(char) -> bool>(&mut self, predicate: P) -> &'a str {
+        let start = self.offset;
+        while self.peek().is_some_and(&predicate) {
+            self.next_char();
+        }
+        &self.input[start..self.offset]
+    }
+
+    pub fn skip_whitespace(&mut self) {
+        self.take_while(char::is_whitespace);
+    }
+
+    // Zero-width span at the current position; pass to span_from() after scanning
+    pub fn position(&self) -> Span {
+        Span { start: self.offset, end: self.offset, line: self.line, column: self.column }
+    }
+
+    // Span from an earlier position() up to the current position
+    pub fn span_from(&self, start: Span) -> Span {
+        Span { end: self.offset, ..start }
+    }
+
+    // Decimal number: digits, optional fraction, optional exponent ("12", "3.5",
+    // "6.02e23", "1E-9"). A '.' or 'e' not followed by a digit is left unconsumed
+    pub fn scan_number(&mut self) -> Option<&'a str> {
+        let start = self.offset;
+        if self.take_while(|c| c.is_ascii_digit()).is_empty() {
+            return None;
+        }
+        if self.peek() == Some('.') && self.peek_nth(1).is_some_and(|c| c.is_ascii_digit()) {
+            self.next_char();
+            self.take_while(|c| c.is_ascii_digit());
+        }
+        self.scan_exponent();
+        Some(&self.input[start..self.offset])
+    }
+
+    // Exponent suffix of a number: e or E, an optional sign, then digits
+    // ("e23", "E-9"); empty, consuming nothing, if no digit follows
+    pub fn scan_exponent(&mut self) -> &'a str {
+        let start = self.offset;
+        if matches!(self.peek(), Some('e') | Some('E')) {
+            let sign = matches!(self.peek_nth(1), Some('+') | Some('-')) as usize;
+            if self.peek_nth(1 + sign).is_some_and(|c| c.is_ascii_digit()) {
+                for _ in 0..1 + sign {
+                    self.next_char();
+                }
+                self.take_while(|c| c.is_ascii_digit());
+            }
+        }
+        &self.input[start..self.offset]
+    }
+
+    // Identifier: a letter followed by letters and digits
+    pub fn scan_identifier(&mut self) -> Option<&'a str> {
+        if !self.peek().is_some_and(char::is_alphabetic) {
+            return None;
+        }
+        Some(self.take_while(|c| c.is_alphabetic() || c.is_ascii_digit()))
+    }
+}
+
+pub fn demo() {
+    println!("=== Scanning a small program ===");
+    let source = "let width = 6.02e23;\nlet héight = width * 2.5 + x1;\nprint(héight)";
+    let mut lexer = Lexer::new(source);
+    loop {
+        lexer.skip_whitespace();
+        let start = lexer.position();
+        let kind = if lexer.scan_identifier().is_some() {
+            "identifier"
+        } else if lexer.scan_number().is_some() {
+            "number"
+        } else if lexer.next_char().is_some() {
+            "symbol"
+        } else {
+            break;
+        };
+        let span = lexer.span_from(start);
+        let text = &source[span.start..span.end];
+        println!("  {:<10} {:<8} {} (bytes {}..{})", kind, text, span, span.start, span.end);
+    }
+
+    println!("\n=== Number shapes ===");
+    for input in ["42", "3.14", "1e10", "2.5E-3", "7.", "7.x", "1e", "1e+", "9.75e+2kg"].iter() {
+        let mut lexer = Lexer::new(input);
+        let number = lexer.scan_number();
+        println!("  {:<10} scans {:<16} leaving {:?}", input, format!("{:?}", number), &input[lexer.position().start..]);
+    }
+
+    println!("\n=== Peeking ===");
+    let mut lexer = Lexer::new("**=");
+    println!("peek {:?}, peek_nth(1) {:?}, peek_nth(2) {:?}, peek_nth(3) {:?}",
+             lexer.peek(), lexer.peek_nth(1), lexer.peek_nth(2), lexer.peek_nth(3));
+    let power = lexer.eat('*') && lexer.eat('*');
+    println!("Matched '**': {}, then eat('+') = {}, eat('=') = {}, at end: {}",
+             power, lexer.eat('+'), lexer.eat('='), lexer.is_at_end());
+}
@@ -0,0 +1,437 @@
+// Line Editor
+//
+// Reads one line from a terminal the way a shell does: the cursor moves with
+// the arrow keys, text goes in and comes out anywhere in the line, Up/Down
+// walk through earlier lines and Tab completes the name before the cursor.
+// Repl::run uses it whenever stdin is a terminal, so every interactive demo
+// gets editing, history recall and completion.
+//
+//   let mut editor = LineEditor::new();
+//   let complete = |_before: &str, word: &str| names_starting_with(word);
+//   while let Some(line) = editor.read_line("> ", &history, &complete)? { ... }
+//
+//   Left/Right, Ctrl-B/Ctrl-F    move one character
+//   Home/End, Ctrl-A/Ctrl-E      move to the start or the end
+//   Up/Down, Ctrl-P/Ctrl-N       previous or next line of history
+//   Backspace, Delete            delete before or under the cursor
+//   Ctrl-U, Ctrl-K, Ctrl-W       cut to the start, to the end, the word before
+//   Ctrl-Y                       paste what was cut last
+//   Tab                          complete the name before the cursor
+//   Ctrl-C                       abandon the line
+//   Ctrl-D                       end of input on an empty line, else Delete
+//   Ctrl-L                       clear the screen
+//
+// Design choices:
+// - Raw mode through termios (libc): no echo, no line buffering and no signals,
+//   so every key arrives as it is pressed. A guard puts the old settings back
+//   when the line is done, on errors too
+// - The history is borrowed for each line rather than owned, so Up walks the
+//   caller's history (a Repl's, including lines restored from a saved session).
+//   A recalled line is edited as a copy, and Down past the newest line brings
+//   back what was being typed
+// - Completion is a function from (text before the name, the name so far) to
+//   candidates, so the caller decides what fits where: a Repl offers command
+//   names for the first word. A name is a run of letters, digits and _; Tab
+//   extends it as far as the candidates agree and lists them when it cannot
+// - edit() runs the key loop over any Read and Write and never touches the
+//   terminal, so keystrokes can be scripted, as the demo does
+// - One redraw per key: carriage return, prompt, line, erase to the end of the
+//   row, then move the cursor back; plain ANSI sequences that every terminal
+//   emulator understands
+// - Trade-off: every character is taken to be one column wide and the line to
+//   fit on one row, so wide characters or lines longer than the terminal put the
+//   cursor in the wrong place; and it is Unix only (elsewhere is_supported() is
+//   false and Repl reads plain lines)
+
+use std::io::{self, IsTerminal, Read, Write};
+
+// Tab completion: given the line before the name at the cursor and the name
+// so far, the names it could become
+pub type Complete<'a> = &'a dyn Fn(&str, &str) -> Vec<String>;
+
+// For lines with nothing to complete
+pub fn no_completion(_before: &str, _name: &str) -> Vec<String> {
+    Vec::new()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Key {
+    Char(char),
+    Enter,
+    Backspace,
+    Delete,
+    Left,
+    Right,
+    Up,
+    Down,
+    Home,
+    End,
+    // Ctrl and a letter, as the lowercase letter
+    Control(u8),
+    Unknown,
+}
+
+#[derive(Debug, Default)]
+pub struct LineEditor {
+    // Text removed by the last Ctrl-U, Ctrl-K or Ctrl-W, for Ctrl-Y
+    cut: String,
+}
+
+impl LineEditor {
+    pub fn new() -> Self {
+        LineEditor::default()
+    }
+
+    // Whether stdin and stdout are a terminal that read_line can drive
+    pub fn is_supported() -> bool {
+        cfg!(unix) && io::stdin().is_terminal() && io::stdout().is_terminal()
+    }
+
+    // Read a line from the terminal; None at end of input (Ctrl-D)
+    pub fn read_line(&mut self, prompt: &str, history: &[String], complete: Complete) -> io::Result<Option<String>> {
+        let _raw = RawMode::enable()?;
+        self.edit(prompt, history, complete, &mut io::stdin().lock(), &mut io::stdout().lock())
+    }
+
+    // The key loop: read keys from `input` and draw the line on `output` until
+    // Enter, Ctrl-C (an empty line) or end of input
+    pub fn edit<R: Read, W: Write>(
+        &mut self,
+        prompt: &str,
+        history: &[String],
+        complete: Complete,
+        input: &mut R,
+        output: &mut W,
+    ) -> io::Result<Option<String>> {
+        let mut line: Vec<char> = Vec::new();
+        let mut cursor = 0;
+        // Index of the history line shown; history.len() is the one being typed
+        let mut recalled = history.len();
+        let mut typed: Vec<char> = Vec::new();
+
+        render(output, prompt, &line, cursor)?;
+        loop {
+            let Some(key) = read_key(input)? else {
+                writeln!(output)?;
+                return Ok((!line.is_empty()).then(|| line.into_iter().collect()));
+            };
+            match key {
+                Key::Enter => {
+                    writeln!(output)?;
+                    return Ok(Some(line.into_iter().collect()));
+                }
+                Key::Control(b'c') => {
+                    writeln!(output, "^C")?;
+                    return Ok(Some(String::new()));
+                }
+                Key::Control(b'd') if line.is_empty() => {
+                    writeln!(output)?;
+                    return Ok(None);
+                }
+                Key::Char(c) => {
+                    line.insert(cursor, c);
+                    cursor += 1;
+                }
+                Key::Backspace if cursor > 0 => {
+                    cursor -= 1;
+                    line.remove(cursor);
+                }
+                Key::Delete | Key::Control(b'd') if cursor < line.len() => {
+                    line.remove(cursor);
+                }
+                Key::Left | Key::Control(b'b') => cursor = cursor.saturating_sub(1),
+                Key::Right | Key::Control(b'f') => cursor = (cursor + 1).min(line.len()),
+                Key::Home | Key::Control(b'a') => cursor = 0,
+                Key::End | Key::Control(b'e') => cursor = line.len(),
+                Key::Up | Key::Control(b'p') if recalled > 0 => {
+                    if recalled == history.len() {
+                        typed = line;
+                    }
+                    recalled -= 1;
+                    line = history[recalled].chars().collect();
+                    cursor = line.len();
+                }
+                Key::Down | Key::Control(b'n') if recalled < history.len() => {
+                    recalled += 1;
+                    line = match history.get(recalled) {
+                        Some(entry) => entry.chars().collect(),
+                        None => std::mem::take(&mut typed),
+                    };
+                    cursor = line.len();
+                }
+                Key::Control(b'u') => {
+                    self.cut = line.drain(..cursor).collect();
+                    cursor = 0;
+                }
+                Key::Control(b'k') => self.cut = line.drain(cursor..).collect(),
+                Key::Control(b'w') => {
+                    let start = word_start(&line, cursor);
+                    self.cut = line.drain(start..cursor).collect();
+                    cursor = start;
+                }
+                Key::Control(b'y') => {
+                    for c in self.cut.chars() {
+                        line.insert(cursor, c);
+                        cursor += 1;
+                    }
+                }
+                Key::Control(b'i') => {
+                    let start = name_start(&line, cursor);
+                    if start < cursor {
+                        let before: String = line[..start].iter().collect();
+                        let name: String = line[start..cursor].iter().collect();
+                        let mut candidates = complete(&before, &name);
+                        candidates.retain(|candidate| candidate.starts_with(&name));
+                        candidates.sort();
+                        candidates.dedup();
+                        let common = common_prefix(&candidates);
+                        if common.len() > name.len() {
+                            for c in common[name.len()..].chars() {
+                                line.insert(cursor, c);
+                                cursor += 1;
+                            }
+                        } else if candidates.len() > 1 {
+                            writeln!(output)?;
+                            writeln!(output, "{}", candidates.join("  "))?;
+                        }
+                    }
+                }
+                Key::Control(b'l') => write!(output, "\x1b[2J\x1b[H")?,
+                _ => {}
+            }
+            render(output, prompt, &line, cursor)?;
+        }
+    }
+}
+
+// Redraw the prompt and line, leaving the terminal cursor at `cursor`
+fn render<W: Write>(output: &mut W, prompt: &str, line: &[char], cursor: usize) -> io::Result<()> {
+    let text: String = line.iter().collect();
+    write!(output, "\r{}{}\x1b[K", prompt, text)?;
+    if cursor < line.len() {
+        write!(output, "\x1b[{}D", line.len() - cursor)?;
+    }
+    output.flush()
+}
+
+// Start of the word before `cursor`, skipping spaces first as shells do
+fn word_start(line: &[char], cursor: usize) -> usize {
+    let mut start = cursor;
+    while start > 0 && line[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    while start > 0 && !line[start - 1].is_whitespace() {
+        start -= 1;
+    }
+    start
+}
+
+// Start of the name (letters, digits and _) that ends at `cursor`
+fn name_start(line: &[char], cursor: usize) -> usize {
+    let mut start = cursor;
+    while start > 0 && (line[start - 1].is_alphanumeric() || line[start - 1] == '_') {
+        start -= 1;
+    }
+    start
+}
+
+// The longest prefix all of `words` share
+fn common_prefix(words: &[String]) -> &str {
+    let Some(first) = words.first() else {
+        return "";
+    };
+    let mut prefix = first.as_str();
+    for word in &words[1..] {
+        while !word.starts_with(prefix) {
+            prefix = &prefix[..prefix.len() - prefix.chars().last().map_or(0, char::len_utf8)];
+        }
+    }
+    prefix
+}
+
+fn read_byte<R: Read>(input: &mut R) -> io::Result<Option<u8>> {
+    let mut byte = [0];
+    loop {
+        match input.read(&mut byte) {
+            Ok(0) => return Ok(None),
+            Ok(_) => return Ok(Some(byte[0])),
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn read_key<R: Read>(input: &mut R) -> io::Result<Option<Key>> {
+    let Some(byte) = read_byte(input)? else {
+        return Ok(None);
+    };
+    let key = match byte {
+        b'\r' | b'\n' => Key::Enter,
+        0x7f | 0x08 => Key::Backspace,
+        0x1b => read_escape(input)?,
+        1..=26 => Key::Control(b'a' + byte - 1),
+        0x20..=0x7e => Key::Char(byte as char),
+        0x80.. => read_utf8(input, byte)?,
+        _ => Key::Unknown,
+    };
+    Ok(Some(key))
+}
+
+// The rest of an escape sequence: ESC [ or ESC O, optional numbers separated by
+// semicolons (a modifier such as Ctrl, which is ignored), and a final byte
+fn read_escape<R: Read>(input: &mut R) -> io::Result<Key> {
+    if !matches!(read_byte(input)?, Some(b'[' | b'O')) {
+        return Ok(Key::Unknown);
+    }
+    let mut number = 0u32;
+    let mut first = true;
+    loop {
+        let Some(byte) = read_byte(input)? else {
+            return Ok(Key::Unknown);
+        };
+        match byte {
+            b'0'..=b'9' if first => number = number.saturating_mul(10).saturating_add((byte - b'0') as u32),
+            b'0'..=b'9' => {}
+            b';' => first = false,
+            b'A' => return Ok(Key::Up),
+            b'B' => return Ok(Key::Down),
+            b'C' => return Ok(Key::Right),
+            b'D' => return Ok(Key::Left),
+            b'H' => return Ok(Key::Home),
+            b'F' => return Ok(Key::End),
+            b'~' => {
+                return Ok(match number {
+                    1 | 7 => Key::Home,
+                    3 => Key::Delete,
+                    4 | 8 => Key::End,
+                    _ => Key::Unknown,
+                })
+            }
+            _ => return Ok(Key::Unknown),
+        }
+    }
+}
+
+// A multi-byte UTF-8 character, given its first byte
+fn read_utf8<R: Read>(input: &mut R, first: u8) -> io::Result<Key> {
+    let length = match first {
+        0xc0..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf7 => 4,
+        _ => return Ok(Key::Unknown),
+    };
+    let mut bytes = vec![first];
+    for _ in 1..length {
+        match read_byte(input)? {
+            Some(byte) => bytes.push(byte),
+            None => return Ok(Key::Unknown),
+        }
+    }
+    Ok(std::str::from_utf8(&bytes).ok().and_then(|s| s.chars().next()).map_or(Key::Unknown, Key::Char))
+}
+
+// The terminal in raw mode for as long as this lives
+#[cfg(unix)]
+struct RawMode {
+    original: libc::termios,
+}
+
+#[cfg(unix)]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        // tcgetattr fills in the whole struct, so starting from zeroes is fine
+        let mut termios: libc::termios = unsafe { std::mem::zeroed() };
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        let original = termios;
+        // Output processing stays on, so "\n" still starts a new row
+        termios.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG | libc::IEXTEN);
+        termios.c_iflag &= !(libc::IXON | libc::ICRNL);
+        termios.c_cc[libc::VMIN] = 1;
+        termios.c_cc[libc::VTIME] = 0;
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &termios) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        Ok(RawMode { original })
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSADRAIN, &self.original);
+        }
+    }
+}
+
+#[cfg(not(unix))]
+struct RawMode;
+
+#[cfg(not(unix))]
+impl RawMode {
+    fn enable() -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "line editing needs a Unix terminal"))
+    }
+}
+
+pub fn demo() {
+    if std::env::args().nth(1).as_deref() == Some("interactive") {
+        if !LineEditor::is_supported() {
+            println!("stdin is not a terminal");
+            return;
+        }
+        let mut editor = LineEditor::new();
+        let mut history = Vec::new();
+        let words = ["apple", "apricot", "banana", "blueberry", "cherry"];
+        let complete = |_: &str, name: &str| words.iter().filter(|w| w.starts_with(name)).map(|w| w.to_string()).collect();
+        println!("Edit lines with the arrow keys; Up recalls earlier ones, Tab completes");
+        println!("the fruit {:?}, Ctrl-D quits\n", words);
+        while let Some(line) = editor.read_line("edit> ", &history, &complete).unwrap() {
+            println!("{:?}", line);
+            if !line.is_empty() {
+                history.push(line);
+            }
+        }
+        return;
+    }
+
+    println!("=== Scripted keystrokes ===");
+    println!("(run `line-editor interactive` to try it in a terminal)\n");
+    let history = vec!["1 + 2".to_string(), "sqrt(16)".to_string()];
+    println!("History: {:?}\n", history);
+    let functions = ["sin", "sqrt", "sqrt2", "sqrt3"];
+    let complete = |_: &str, name: &str| functions.iter().filter(|f| f.starts_with(name)).map(|f| f.to_string()).collect();
+    let cases: [(&str, &[u8]); 11] = [
+        ("typed as is", b"hello\r"),
+        ("Left, then a missing letter", b"helo\x1b[Dl\r"),
+        ("Home, then more text", b"world\x1b[Hhello \r"),
+        ("Up recalls the newest line", b"\x1b[A\r"),
+        ("Up twice, Backspace, new digit", b"\x1b[A\x1b[A\x7f3\r"),
+        ("Up then Down gives the draft back", b"draft\x1b[A\x1b[B\r"),
+        ("Ctrl-W cuts a word, Ctrl-Y pastes it", b"one two\x17three \x19\r"),
+        ("Ctrl-U clears the line", b"oops\x15fine\r"),
+        ("Tab completes as far as it can", b"2*sq\t(2)\r"),
+        ("a second letter, then Tab", b"si\t(0)\r"),
+        ("Ctrl-D on an empty line", b"\x04"),
+    ];
+    let mut editor = LineEditor::new();
+    for (what, keys) in cases.iter() {
+        let line = editor.edit("> ", &history, &complete, &mut &keys[..], &mut io::sink()).unwrap();
+        println!("{:<38} {:<40} -> {:?}", what, format!("{:?}", String::from_utf8_lossy(keys)), line);
+    }
+
+    println!("\n=== What the terminal is sent for \"ab\", Left ===");
+    let mut screen = Vec::new();
+    editor.edit("> ", &history, &no_completion, &mut &b"ab\x1b[D"[..], &mut screen).unwrap();
+    for redraw in String::from_utf8_lossy(&screen).split('\r').skip(1) {
+        println!("  \\r{}", redraw.escape_debug());
+    }
+
+    println!("\n=== Tab twice lists the candidates ===");
+    let mut screen = Vec::new();
+    editor.edit("> ", &history, &complete, &mut &b"sq\t\t\r"[..], &mut screen).unwrap();
+    let text = String::from_utf8_lossy(&screen);
+    println!("{}", text.lines().find(|row| row.contains("sqrt2")).unwrap_or_default());
+}
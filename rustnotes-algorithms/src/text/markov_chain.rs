@@ -0,0 +1,277 @@
+// Markov Chain Text Generator
+//
+// An order-k Markov model predicts the next word from the previous k words only.
+// Training slides a window of k words over the text and counts which word
+// follows each window; generating starts from some window, picks a follower at
+// random in proportion to those counts, shifts the window by one, and repeats.
+//
+//   order 2, text "the cat sat on the cat mat"
+//   [the, cat] -> sat: 1, mat: 1
+//   [cat, sat] -> on: 1
+//   [sat, on]  -> the: 1
+//   [on, the]  -> cat: 1
+//
+// Higher orders copy longer stretches of the source verbatim; order 1 produces
+// word salad that is only locally plausible.
+//
+// Design choices:
+// - The model is a HashMapOa (hashing::hash_map_open_addressing) from a window of k
+//   word ids to its followers and their counts
+// - Words are interned: each distinct word is stored once in a vocabulary and
+//   windows hold usize ids, so keys are small and cheap to hash and compare
+// - Generation starts at a window that began a sentence (start of text or after
+//   a word ending in '.', '!' or '?'), and jumps to a fresh sentence start when it
+//   reaches a window that was never followed by anything (the end of the text)
+// - Randomness comes from the seedable xorshift64* generator in math::random, so
+//   runs are reproducible
+// - Usage: markov-chain                                run the demo
+//          markov-chain <file> <words> [order] [seed]  train on a file, print text
+// - Trade-off: counting exact windows needs lots of text at higher orders; with
+//   little data every window has a single follower and the output is the input
+
+use rustnotes_collections::hashing::hash_map_open_addressing::HashMapOa;
+use rustnotes_collections::random::{Rng, XorShift64Star};
+use std::env;
+use std::fs;
+use std::process;
+
+// Words seen after one window, with how often each followed it
+struct Followers {
+    words: Vec<(usize, u32)>,
+    total: u32,
+}
+
+pub struct MarkovChain {
+    order: usize,
+    vocabulary: Vec<String>,
+    word_ids: HashMapOa<String, usize>,
+    transitions: HashMapOa<Vec<usize>, Followers>,
+    starts: Vec<Vec<usize>>, // Windows that begin a sentence
+    rng: XorShift64Star,
+}
+
+impl MarkovChain {
+    pub fn new(order: usize) -> Self {
+        Self::with_seed(order, 0x2545_F491_4F6C_DD1D)
+    }
+
+    pub fn with_seed(order: usize, seed: u64) -> Self {
+        assert!(order > 0, "order must be at least 1");
+        MarkovChain {
+            order,
+            vocabulary: Vec::new(),
+            word_ids: HashMapOa::new(),
+            transitions: HashMapOa::new(),
+            starts: Vec::new(),
+            rng: XorShift64Star::seed_from_u64(seed),
+        }
+    }
+
+    fn intern(&mut self, word: &str) -> usize {
+        if let Some(&id) = self.word_ids.get(&word.to_string()) {
+            return id;
+        }
+        let id = self.vocabulary.len();
+        self.vocabulary.push(word.to_string());
+        self.word_ids.insert(word.to_string(), id);
+        id
+    }
+
+    // Add the windows and transitions of a text; can be called repeatedly
+    pub fn train(&mut self, text: &str) {
+        let ids: Vec<usize> = text.split_whitespace().map(|word| self.intern(word)).collect();
+        if ids.len() < self.order {
+            return;
+        }
+        for start in 0..=ids.len() - self.order {
+            let window = ids[start..start + self.order].to_vec();
+            if start == 0 || ends_sentence(&self.vocabulary[ids[start - 1]]) {
+                self.starts.push(window.clone());
+            }
+            // The final window is recorded too, with no followers
+            if !self.transitions.contains_key(&window) {
+                self.transitions.insert(window.clone(), Followers { words: Vec::new(), total: 0 });
+            }
+            let next = match ids.get(start + self.order) {
+                Some(&next) => next,
+                None => continue,
+            };
+            let followers = self.transitions.get_mut(&window).unwrap();
+            followers.total += 1;
+            match followers.words.iter_mut().find(|(id, _)| *id == next) {
+                Some((_, count)) => *count += 1,
+                None => followers.words.push((next, 1)),
+            }
+        }
+    }
+
+    fn random_start(&mut self) -> Option<Vec<usize>> {
+        if self.starts.is_empty() {
+            return None;
+        }
+        let index = self.rng.gen_index(0..self.starts.len());
+        Some(self.starts[index].clone())
+    }
+
+    // Pick a follower of `window` weighted by count; None at a dead end
+    fn pick_next(&mut self, window: &[usize]) -> Option<usize> {
+        let total = self.transitions.get(&window.to_vec()).map_or(0, |f| f.total);
+        if total == 0 {
+            return None;
+        }
+        let mut target = self.rng.gen_range(0..total as u64) as u32;
+        let followers = self.transitions.get(&window.to_vec()).unwrap();
+        for &(id, count) in &followers.words {
+            if target < count {
+                return Some(id);
+            }
+            target -= count;
+        }
+        unreachable!("counts sum to total")
+    }
+
+    // Generate `count` words, or an empty string if nothing has been trained
+    pub fn generate(&mut self, count: usize) -> String {
+        let mut words: Vec<usize> = Vec::with_capacity(count);
+        let mut window = match self.random_start() {
+            Some(window) => window,
+            None => return String::new(),
+        };
+        words.extend(&window);
+        while words.len() < count {
+            match self.pick_next(&window) {
+                Some(next) => {
+                    words.push(next);
+                    window.remove(0);
+                    window.push(next);
+                }
+                None => {
+                    window = self.random_start().unwrap();
+                    words.extend(&window);
+                }
+            }
+        }
+        words.truncate(count);
+        let text: Vec<&str> = words.iter().map(|&id| self.vocabulary[id].as_str()).collect();
+        text.join(" ")
+    }
+
+    pub fn order(&self) -> usize {
+        self.order
+    }
+
+    pub fn vocabulary_size(&self) -> usize {
+        self.vocabulary.len()
+    }
+
+    pub fn state_count(&self) -> usize {
+        self.transitions.len()
+    }
+
+    // Average number of distinct followers per window: how much choice there is
+    pub fn branching_factor(&self) -> f64 {
+        if self.transitions.is_empty() {
+            return 0.0;
+        }
+        let total: usize = self.transitions.values().map(|f| f.words.len()).sum();
+        total as f64 / self.transitions.len() as f64
+    }
+}
+
+fn ends_sentence(word: &str) -> bool {
+    word.ends_with('.') || word.ends_with('!') || word.ends_with('?')
+}
+
+fn run_cli(args: &[String]) -> Result<(), String> {
+    let usage = "usage: markov-chain [<file> <words> [order] [seed]]";
+    if args.len() < 2 || args.len() > 4 {
+        return Err(usage.to_string());
+    }
+    let parse = |text: &String, what: &str| {
+        text.parse::<u64>().map_err(|_| format!("invalid {} '{}'", what, text))
+    };
+    let text = fs::read_to_string(&args[0]).map_err(|e| format!("cannot read {}: {}", args[0], e))?;
+    let words = parse(&args[1], "word count")? as usize;
+    let order = match args.get(2) {
+        Some(order) => parse(order, "order")? as usize,
+        None => 2,
+    };
+    if order == 0 {
+        return Err("order must be at least 1".to_string());
+    }
+    let mut chain = match args.get(3) {
+        Some(seed) => MarkovChain::with_seed(order, parse(seed, "seed")?),
+        None => MarkovChain::new(order),
+    };
+    chain.train(&text);
+    if chain.state_count() == 0 {
+        return Err(format!("{} has fewer than {} words", args[0], order));
+    }
+    println!("{}", chain.generate(words));
+    Ok(())
+}
+
+pub fn demo() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        if let Err(message) = run_cli(&args) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    let corpus = "\
+        The borrow checker is strict. The borrow checker is also your friend. \
+        Ownership means every value has one owner. When the owner goes out of scope \
+        the value is dropped. A reference borrows a value without taking ownership. \
+        You can have many shared references or one mutable reference, but not both. \
+        The compiler checks this at compile time, so there is no cost at run time. \
+        Lifetimes describe how long a reference is valid. Most of the time the \
+        compiler infers lifetimes for you. When it cannot, you write them yourself. \
+        A value can be moved to a new owner. After a move the old name cannot be used. \
+        Types that are cheap to copy implement Copy, and copying leaves the old name \
+        usable. Everything else is moved. The borrow checker is strict, but it \
+        catches bugs that would be crashes in other languages.";
+
+    println!("=== Training ===");
+    for order in 1..=3 {
+        let mut chain = MarkovChain::with_seed(order, 42);
+        chain.train(corpus);
+        println!("order {}: {} words in vocabulary, {} windows, {:.2} followers per window",
+                 chain.order(), chain.vocabulary_size(), chain.state_count(), chain.branching_factor());
+    }
+
+    println!("\n=== Generated text ===");
+    for order in 1..=3 {
+        let mut chain = MarkovChain::with_seed(order, 7);
+        chain.train(corpus);
+        println!("order {}:\n  {}", order, chain.generate(30));
+    }
+
+    println!("\n=== Worked example ===");
+    let mut tiny = MarkovChain::with_seed(2, 1);
+    tiny.train("the cat sat on the cat mat");
+    let window = vec![tiny.intern("the"), tiny.intern("cat")];
+    let followers = tiny.transitions.get(&window).unwrap();
+    let counts: Vec<String> = followers.words.iter()
+        .map(|&(id, count)| format!("{}: {}", tiny.vocabulary[id], count))
+        .collect();
+    println!("[the, cat] -> {}", counts.join(", "));
+    println!("Five samples of 6 words:");
+    for _ in 0..5 {
+        println!("  {}", tiny.generate(6));
+    }
+
+    println!("\n=== Reproducibility ===");
+    let run = |seed| {
+        let mut chain = MarkovChain::with_seed(2, seed);
+        chain.train(corpus);
+        chain.generate(20)
+    };
+    println!("Same seed, same text: {}", run(99) == run(99));
+    println!("Different seed, different text: {}", run(99) != run(100));
+    let mut short = MarkovChain::new(3);
+    short.train("too short");
+    println!("Chain trained on fewer words than its order generates {:?}", short.generate(10));
+}
@@ -0,0 +1,15 @@
+// Text Processing
+
+pub mod json;
+pub mod lexer;
+pub mod line_editor;
+pub mod markov_chain;
+pub mod repl;
+pub mod suffix_array;
+
+pub use self::json::JsonValue;
+pub use self::lexer::{Lexer, Span};
+pub use self::line_editor::LineEditor;
+pub use self::markov_chain::MarkovChain;
+pub use self::repl::Repl;
+pub use self::suffix_array::SuffixArray;
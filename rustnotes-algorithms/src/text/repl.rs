@@ -0,0 +1,394 @@
+// REPL Framework
+//
+// The read-eval-print loop that interactive demos share: show a prompt, read a
+// line, decide what it means, print the result or the error, repeat until the
+// user quits. Repl<S> runs that loop over any state S; a demo registers named
+// commands and a fallback for everything else, and keeps its own logic in S.
+//
+//   let mut repl = Repl::new(Calculator::new(), "> ");
+//   repl.command(&["clear"], "reset the last result", |calc, _| { ... });
+//   repl.fallback(|calc, line| calc.evaluate(line).map(|v| v.to_string()));
+//   repl.run();
+//
+// Design choices:
+// - A command is matched on the first word of the line and receives the rest as
+//   its argument string; one command can have several names (aliases)
+// - Handlers return Result<String, String>: Ok text is printed (if non-empty), Err
+//   is printed as "Error: ..." and the loop carries on
+// - Built in to every REPL: help / ? (help text plus the command list), history,
+//   and q / quit / exit. End of input (Ctrl-D, or the end of a piped script)
+//   also exits
+// - History is kept in the Repl, optionally capped by history_limit (oldest
+//   lines are dropped first); on_line hooks see every non-empty line as it is
+//   entered, e.g. to log a session or count usage
+// - run_with() reads from any BufRead and writes to any Write, so a REPL can be
+//   driven by a script; echo(true) copies each input line into the transcript
+// - run() on a terminal reads through text::line_editor, so lines can be edited
+//   in place and Up/Down recall the history (set_history lines included); with
+//   stdin redirected it falls back to run_with() on plain lines
+// - Tab completes command names in the first word, and whatever names the
+//   completer() gives for the state (a calculator's functions, say) anywhere
+// - Trade-off: completion only sees names, not grammar, so it offers a
+//   function where only a number could go
+
+use super::line_editor::LineEditor;
+use std::io::{self, BufRead, Write};
+
+pub type Handler<S> = Box<dyn FnMut(&mut S, &str) -> Result<String, String>>;
+pub type LineHook = Box<dyn FnMut(&str)>;
+pub type Completer<S> = Box<dyn Fn(&S, &str) -> Vec<String>>;
+
+struct Command<S> {
+    names: Vec<String>,
+    help: String,
+    handler: Handler<S>,
+}
+
+pub struct Repl<S> {
+    state: S,
+    prompt: String,
+    help_text: String,
+    commands: Vec<Command<S>>,
+    fallback: Option<Handler<S>>,
+    completer: Option<Completer<S>>,
+    history: Vec<String>,
+    history_limit: Option<usize>,
+    line_hooks: Vec<LineHook>,
+    echo: bool,
+}
+
+const EXIT_NAMES: [&str; 3] = ["q", "quit", "exit"];
+
+impl<S> Repl<S> {
+    pub fn new(state: S, prompt: &str) -> Self {
+        Repl {
+            state,
+            prompt: prompt.to_string(),
+            help_text: String::new(),
+            commands: Vec::new(),
+            fallback: None,
+            completer: None,
+            history: Vec::new(),
+            history_limit: None,
+            line_hooks: Vec::new(),
+            echo: false,
+        }
+    }
+
+    // Text shown at startup and by `help`, above the command list
+    pub fn help_text(&mut self, text: &str) -> &mut Self {
+        self.help_text = text.to_string();
+        self
+    }
+
+    // Register a command under one or more names
+    pub fn command<F>(&mut self, names: &[&str], help: &str, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut S, &str) -> Result<String, String> + 'static,
+    {
+        self.commands.push(Command {
+            names: names.iter().map(|name| name.to_string()).collect(),
+            help: help.to_string(),
+            handler: Box::new(handler),
+        });
+        self
+    }
+
+    // Handler for lines that match no command; it gets the whole line
+    pub fn fallback<F>(&mut self, handler: F) -> &mut Self
+    where
+        F: FnMut(&mut S, &str) -> Result<String, String> + 'static,
+    {
+        self.fallback = Some(Box::new(handler));
+        self
+    }
+
+    // Names to offer for Tab completion, given the state and the text before
+    // the name being completed; they are filtered by what has been typed
+    pub fn completer<F>(&mut self, completer: F) -> &mut Self
+    where
+        F: Fn(&S, &str) -> Vec<String> + 'static,
+    {
+        self.completer = Some(Box::new(completer));
+        self
+    }
+
+    // Called with every non-empty line before it is dispatched
+    pub fn on_line<F: FnMut(&str) + 'static>(&mut self, hook: F) -> &mut Self {
+        self.line_hooks.push(Box::new(hook));
+        self
+    }
+
+    // Keep at most `limit` lines of history
+    pub fn history_limit(&mut self, limit: usize) -> &mut Self {
+        self.history_limit = Some(limit);
+        self.trim_history();
+        self
+    }
+
+    // Start from earlier lines, such as a saved session's; the history_limit
+    // set so far applies
+    pub fn set_history(&mut self, lines: Vec<String>) -> &mut Self {
+        self.history = lines;
+        self.trim_history();
+        self
+    }
+
+    fn trim_history(&mut self) {
+        if let Some(limit) = self.history_limit {
+            let excess = self.history.len().saturating_sub(limit);
+            self.history.drain(..excess);
+        }
+    }
+
+    // Copy input lines into the output, for scripted sessions
+    pub fn echo(&mut self, echo: bool) -> &mut Self {
+        self.echo = echo;
+        self
+    }
+
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+
+    pub fn into_state(self) -> S {
+        self.state
+    }
+
+    pub fn history(&self) -> &[String] {
+        &self.history
+    }
+
+    fn help(&self) -> String {
+        let mut text = self.help_text.clone();
+        if !text.is_empty() && !text.ends_with('\n') {
+            text.push('\n');
+        }
+        text.push_str("Commands:\n");
+        let mut entries: Vec<(String, &str)> = self.commands.iter()
+            .map(|command| (command.names.join(", "), command.help.as_str()))
+            .collect();
+        entries.push(("help, ?".to_string(), "show this help"));
+        entries.push(("history".to_string(), "list the lines entered so far"));
+        entries.push((EXIT_NAMES.join(", "), "exit"));
+        let width = entries.iter().map(|(names, _)| names.len()).max().unwrap_or(0);
+        for (names, help) in entries {
+            text.push_str(&format!("  {:<width$}  {}\n", names, help, width = width));
+        }
+        text.pop();
+        text
+    }
+
+    // What Tab offers for `name`, which follows `before` on the line: the
+    // commands (built-in ones too) in the first word, then the completer's names
+    pub fn completions(&self, before: &str, name: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        if before.trim().is_empty() {
+            names.extend(self.commands.iter().flat_map(|command| command.names.iter().cloned()));
+            names.extend(["help", "history", "quit", "exit"].map(str::to_string));
+        }
+        if let Some(completer) = &self.completer {
+            names.extend(completer(&self.state, before));
+        }
+        names.retain(|candidate| candidate.starts_with(name));
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    // Run one line; None means the REPL should exit
+    pub fn dispatch(&mut self, line: &str) -> Option<Result<String, String>> {
+        let line = line.trim();
+        if line.is_empty() {
+            return Some(Ok(String::new()));
+        }
+        self.history.push(line.to_string());
+        self.trim_history();
+        for hook in self.line_hooks.iter_mut() {
+            hook(line);
+        }
+
+        let (word, args) = match line.find(char::is_whitespace) {
+            Some(split) => (&line[..split], line[split..].trim_start()),
+            None => (line, ""),
+        };
+        if EXIT_NAMES.contains(&word) && args.is_empty() {
+            return None;
+        }
+        if (word == "help" || word == "?") && args.is_empty() {
+            return Some(Ok(self.help()));
+        }
+        if word == "history" && args.is_empty() {
+            let lines: Vec<String> = self.history.iter().enumerate()
+                .map(|(i, entry)| format!("{:>4}  {}", i + 1, entry))
+                .collect();
+            return Some(Ok(lines.join("\n")));
+        }
+
+        let state = &mut self.state;
+        if let Some(command) = self.commands.iter_mut().find(|c| c.names.iter().any(|n| n == word)) {
+            return Some((command.handler)(state, args));
+        }
+        Some(match self.fallback.as_mut() {
+            Some(fallback) => fallback(state, line),
+            None => Err(format!("Unknown command '{}' (try 'help')", word)),
+        })
+    }
+
+    // Loop over lines from `input` until an exit command or end of input
+    pub fn run_with<R: BufRead, W: Write>(&mut self, mut input: R, output: &mut W) -> io::Result<()> {
+        writeln!(output, "{}\n", self.help())?;
+        loop {
+            write!(output, "{}", self.prompt)?;
+            output.flush()?;
+
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 {
+                writeln!(output)?;
+                break;
+            }
+            if self.echo {
+                writeln!(output, "{}", line.trim_end())?;
+            }
+            if !self.respond(&line, output)? {
+                break;
+            }
+        }
+        writeln!(output, "Goodbye!")
+    }
+
+    // Dispatch a line and print what it returns; false means exit
+    fn respond<W: Write>(&mut self, line: &str, output: &mut W) -> io::Result<bool> {
+        match self.dispatch(line) {
+            None => return Ok(false),
+            Some(Ok(text)) => {
+                if !text.is_empty() {
+                    writeln!(output, "{}", text)?;
+                }
+            }
+            Some(Err(message)) => writeln!(output, "Error: {}", message)?,
+        }
+        Ok(true)
+    }
+
+    // The loop of run_with() with lines read through the line editor
+    fn run_edited(&mut self) -> io::Result<()> {
+        let mut editor = LineEditor::new();
+        let mut output = io::stdout();
+        writeln!(output, "{}\n", self.help())?;
+        loop {
+            let complete = |before: &str, name: &str| self.completions(before, name);
+            let Some(line) = editor.read_line(&self.prompt, &self.history, &complete)? else {
+                break;
+            };
+            if !self.respond(&line, &mut output)? {
+                break;
+            }
+        }
+        writeln!(output, "Goodbye!")
+    }
+
+    // Interactive loop on stdin/stdout, with line editing on a terminal
+    pub fn run(&mut self) {
+        let result = if LineEditor::is_supported() {
+            self.run_edited()
+        } else {
+            self.run_with(io::stdin().lock(), &mut io::stdout().lock())
+        };
+        if let Err(e) = result {
+            eprintln!("I/O error: {}", e);
+        }
+    }
+}
+
+// Demo: a reverse Polish notation calculator, where numbers push onto a stack
+// and operators pop their operands
+fn rpn_repl() -> Repl<Vec<f64>> {
+    let mut repl = Repl::new(Vec::new(), "rpn> ");
+    repl.help_text("RPN calculator: enter numbers and operators (+ - * /) separated by spaces")
+        .command(&["stack", "s"], "show the stack", |stack, _| Ok(format!("{:?}", stack)))
+        .command(&["drop"], "discard the top value", |stack, _| {
+            stack.pop().map(|v| format!("dropped {}", v)).ok_or_else(|| "stack is empty".to_string())
+        })
+        .command(&["clear"], "empty the stack", |stack, _| {
+            stack.clear();
+            Ok(String::new())
+        })
+        .command(&["push"], "push each argument, e.g. push 1 2 3", |stack, args| {
+            for word in args.split_whitespace() {
+                stack.push(word.parse().map_err(|_| format!("'{}' is not a number", word))?);
+            }
+            Ok(format!("{:?}", stack))
+        })
+        .fallback(|stack, line| {
+            for word in line.split_whitespace() {
+                if let Ok(value) = word.parse::<f64>() {
+                    stack.push(value);
+                    continue;
+                }
+                if stack.len() < 2 {
+                    return Err(format!("'{}' needs two values on the stack", word));
+                }
+                let right = stack.pop().unwrap();
+                let left = stack.pop().unwrap();
+                stack.push(match word {
+                    "+" => left + right,
+                    "-" => left - right,
+                    "*" => left * right,
+                    "/" => left / right,
+                    _ => {
+                        stack.push(left);
+                        stack.push(right);
+                        return Err(format!("unknown word '{}'", word));
+                    }
+                });
+            }
+            Ok(stack.last().map_or(String::new(), |top| top.to_string()))
+        });
+    repl
+}
+
+pub fn demo() {
+    if std::env::args().nth(1).as_deref() == Some("interactive") {
+        rpn_repl().run();
+        return;
+    }
+
+    println!("=== Scripted RPN session ===");
+    println!("(run `repl interactive` to type commands yourself)\n");
+    let script = "3 4 +\n2 *\npush 10 20\n/\nstack\nfrobnicate 1\n5 sqrt\ndrop\nhistory\nquit\nnever reached\n";
+    let mut repl = rpn_repl();
+    let counter = std::rc::Rc::new(std::cell::Cell::new(0));
+    let seen = counter.clone();
+    repl.echo(true).on_line(move |_| seen.set(seen.get() + 1));
+    let mut transcript = Vec::new();
+    repl.run_with(script.as_bytes(), &mut transcript).unwrap();
+    print!("{}", String::from_utf8(transcript).unwrap());
+
+    println!("\n=== After the session ===");
+    println!("Lines seen by the on_line hook: {}", counter.get());
+    println!("History length: {}", repl.history().len());
+    println!("Final stack: {:?}", repl.state());
+
+    println!("\n=== Dispatching directly ===");
+    let mut repl = rpn_repl();
+    for line in ["1 2", "+", "", "drop", "drop", "exit"].iter() {
+        println!("{:<6} -> {:?}", format!("{:?}", line), repl.dispatch(line));
+    }
+
+    println!("\n=== Tab completion ===");
+    let repl = rpn_repl();
+    for (before, name) in [("", "h"), ("", "s"), ("", "d"), ("push ", "s")].iter() {
+        println!("{:?} then {:?}<Tab> -> {:?}", before, name, repl.completions(before, name));
+    }
+
+    println!("\n=== End of input exits cleanly ===");
+    let mut output = Vec::new();
+    rpn_repl().run_with("1 1 +\n".as_bytes(), &mut output).unwrap();
+    let text = String::from_utf8(output).unwrap();
+    println!("Transcript ends with: {:?}", &text[text.len() - 20..]);
+}
@@ -0,0 +1,253 @@
+// Suffix Array with LCP
+//
+// The suffix array of a text lists the starting positions of all its suffixes in
+// sorted order. Because every substring is a prefix of some suffix, all
+// occurrences of a pattern form one contiguous block of the array, which binary
+// search can find in O(m log n) for a pattern of length m. The companion LCP array
+// stores the length of the longest common prefix of each pair of neighboring
+// suffixes, which answers questions like "longest repeated substring" in O(n).
+//
+// Example for "banana":
+//   rank  start  suffix   lcp with previous
+//     0     5    a          -
+//     1     3    ana        1
+//     2     1    anana      3
+//     3     0    banana     0
+//     4     4    na         0
+//     5     2    nana       2
+//
+// Design choices:
+// - Prefix doubling: after round k every suffix has a rank for its first 2^k
+//   bytes; sorting by the pair (rank of first half, rank of second half) gives the
+//   ranks for 2^(k+1) bytes. log n rounds of an O(n log n) sort = O(n log² n)
+// - Rounds stop early once all ranks are distinct
+// - The LCP array is built with Kasai's algorithm in O(n), using the fact that the
+//   LCP drops by at most one when moving from suffix i to suffix i + 1
+// - Works on bytes, so positions are byte offsets into the &str; a valid UTF-8
+//   pattern can only ever match at character boundaries
+// - Trade-off: O(n) extra words of memory and an up-front build, in exchange for
+//   fast repeated searches; SA-IS or DC3 would build in O(n) but are far more involved
+
+use std::cmp::Ordering;
+
+pub struct SuffixArray {
+    text: Vec<u8>,
+    suffixes: Vec<usize>, // Start positions in sorted suffix order
+    lcp: Vec<usize>,      // lcp[i] = common prefix of suffixes[i - 1] and suffixes[i]; lcp[0] = 0
+}
+
+impl SuffixArray {
+    pub fn new(text: &str) -> Self {
+        let text = text.as_bytes().to_vec();
+        let suffixes = build_suffix_array(&text);
+        let lcp = build_lcp(&text, &suffixes);
+        Self { text, suffixes, lcp }
+    }
+
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn suffixes(&self) -> &[usize] {
+        &self.suffixes
+    }
+
+    pub fn lcp(&self) -> &[usize] {
+        &self.lcp
+    }
+
+    fn suffix(&self, rank: usize) -> &[u8] {
+        &self.text[self.suffixes[rank]..]
+    }
+
+    // Compare a suffix with the pattern, looking only at the first pattern.len() bytes
+    fn compare_prefix(&self, rank: usize, pattern: &[u8]) -> Ordering {
+        let suffix = self.suffix(rank);
+        let prefix = &suffix[..suffix.len().min(pattern.len())];
+        prefix.cmp(pattern)
+    }
+
+    // First rank whose suffix prefix is >= the pattern (or > it when include_equal)
+    fn lower_bound(&self, pattern: &[u8], include_equal: bool) -> usize {
+        let (mut lo, mut hi) = (0, self.suffixes.len());
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            let go_right = match self.compare_prefix(mid, pattern) {
+                Ordering::Less => true,
+                Ordering::Equal => include_equal,
+                Ordering::Greater => false,
+            };
+            if go_right {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    // Start positions of every occurrence of `pattern`, in increasing order
+    pub fn find_all(&self, pattern: &str) -> Vec<usize> {
+        let pattern = pattern.as_bytes();
+        if pattern.is_empty() {
+            return (0..self.text.len()).collect();
+        }
+        let first = self.lower_bound(pattern, false);
+        let last = self.lower_bound(pattern, true);
+        let mut positions = self.suffixes[first..last].to_vec();
+        positions.sort_unstable();
+        positions
+    }
+
+    pub fn count(&self, pattern: &str) -> usize {
+        let pattern = pattern.as_bytes();
+        self.lower_bound(pattern, true) - self.lower_bound(pattern, false)
+    }
+
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.count(pattern) > 0
+    }
+
+    // Longest substring occurring at least twice (occurrences may overlap)
+    pub fn longest_repeated_substring(&self) -> &str {
+        let best = (1..self.lcp.len()).max_by_key(|&i| (self.lcp[i], std::cmp::Reverse(i)));
+        match best {
+            Some(i) if self.lcp[i] > 0 => {
+                let start = self.suffixes[i];
+                // Trim back to a char boundary so the result is valid UTF-8
+                let mut end = start + self.lcp[i];
+                while std::str::from_utf8(&self.text[start..end]).is_err() {
+                    end -= 1;
+                }
+                std::str::from_utf8(&self.text[start..end]).unwrap()
+            }
+            _ => "",
+        }
+    }
+
+    // Each suffix contributes its length minus the prefix it shares with its neighbor
+    pub fn distinct_substring_count(&self) -> usize {
+        let n = self.text.len();
+        n * (n + 1) / 2 - self.lcp.iter().sum::<usize>()
+    }
+}
+
+// Prefix doubling: O(n log² n)
+fn build_suffix_array(text: &[u8]) -> Vec<usize> {
+    let n = text.len();
+    let mut suffixes: Vec<usize> = (0..n).collect();
+    let mut rank: Vec<usize> = text.iter().map(|&b| b as usize).collect();
+    let mut next_rank = vec![0; n];
+    let mut length = 1;
+    if n < 2 {
+        return suffixes;
+    }
+
+    loop {
+        // Key of suffix i: (rank of its first `length` bytes, rank of the next
+        // `length` bytes, shifted up by one); a suffix with no second half gets 0 and sorts first
+        let key = |i: usize| (rank[i], rank.get(i + length).copied().map(|r| r + 1).unwrap_or(0));
+        suffixes.sort_by_key(|&i| key(i));
+
+        next_rank[suffixes[0]] = 0;
+        for w in 1..n {
+            let same = key(suffixes[w - 1]) == key(suffixes[w]);
+            next_rank[suffixes[w]] = next_rank[suffixes[w - 1]] + if same { 0 } else { 1 };
+        }
+        std::mem::swap(&mut rank, &mut next_rank);
+
+        if rank[suffixes[n - 1]] == n - 1 {
+            break; // All ranks distinct: fully sorted
+        }
+        length *= 2;
+    }
+    suffixes
+}
+
+// Kasai's algorithm: O(n)
+fn build_lcp(text: &[u8], suffixes: &[usize]) -> Vec<usize> {
+    let n = text.len();
+    let mut rank_of = vec![0; n];
+    for (rank, &start) in suffixes.iter().enumerate() {
+        rank_of[start] = rank;
+    }
+    let mut lcp = vec![0; n];
+    let mut h = 0;
+    for start in 0..n {
+        if rank_of[start] == 0 {
+            h = 0;
+            continue;
+        }
+        let previous = suffixes[rank_of[start] - 1];
+        while start + h < n && previous + h < n && text[start + h] == text[previous + h] {
+            h += 1;
+        }
+        lcp[rank_of[start]] = h;
+        // The next suffix (start + 1) shares at least h - 1 bytes with its neighbor
+        h = h.saturating_sub(1);
+    }
+    lcp
+}
+
+pub fn demo() {
+    println!("=== Suffix array of \"banana\" ===");
+    let banana = SuffixArray::new("banana");
+    println!("{:>4} {:>5} {:>3}  suffix", "rank", "start", "lcp");
+    for (rank, &start) in banana.suffixes().iter().enumerate() {
+        println!("{:>4} {:>5} {:>3}  {}", rank, start, banana.lcp()[rank], &"banana"[start..]);
+    }
+    println!("find_all(\"ana\") = {:?}", banana.find_all("ana"));
+    println!("find_all(\"nab\") = {:?}", banana.find_all("nab"));
+    println!("count(\"a\") = {}, contains(\"nan\") = {}", banana.count("a"), banana.contains("nan"));
+    println!("Longest repeated substring: {:?}", banana.longest_repeated_substring());
+    println!("Distinct substrings: {}", banana.distinct_substring_count());
+
+    println!("\n=== Searching a longer text ===");
+    let text = "it was the best of times it was the worst of times it was the age of wisdom \
+                it was the age of foolishness it was the epoch of belief";
+    let index = SuffixArray::new(text);
+    for pattern in ["it was the", "of times", "age of", "epoch", "winter"].iter() {
+        println!("{:>12}: {:?}", pattern, index.find_all(pattern));
+    }
+    println!("Longest repeated substring: {:?}", index.longest_repeated_substring());
+
+    println!("\n=== Agrees with naive search ===");
+    // Pseudo-random text over a small alphabet has many repeats to find
+    let mut state: u64 = 7;
+    let random: String = (0..5000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (b'a' + (state % 4) as u8) as char
+        })
+        .collect();
+    let index = SuffixArray::new(&random);
+    let sorted = index.suffixes().windows(2).all(|w| random[w[0]..] < random[w[1]..]);
+    let mut matches = true;
+    for length in 1..8 {
+        let pattern = &random[1000..1000 + length];
+        let naive: Vec<usize> = (0..=random.len() - length)
+            .filter(|&i| &random[i..i + length] == pattern)
+            .collect();
+        matches &= index.find_all(pattern) == naive;
+    }
+    println!("Suffixes in strictly increasing order: {}", sorted);
+    println!("find_all matches a naive scan for 7 patterns: {}", matches);
+    let repeat = index.longest_repeated_substring();
+    println!("Longest repeat in 5000 random letters: {} chars, occurs {} times",
+             repeat.len(), index.count(repeat));
+
+    println!("\n=== Edge cases ===");
+    let empty = SuffixArray::new("");
+    println!("Empty text: {} suffixes, find_all(\"a\") = {:?}", empty.len(), empty.find_all("a"));
+    let same = SuffixArray::new("aaaa");
+    println!("\"aaaa\": suffixes {:?}, lcp {:?}", same.suffixes(), same.lcp());
+    let unicode = SuffixArray::new("héllo héllo");
+    println!("UTF-8 \"héllo héllo\": find_all(\"é\") = {:?} (byte offsets), longest repeat {:?}",
+             unicode.find_all("é"), unicode.longest_repeated_substring());
+}
@@ -0,0 +1,21 @@
+// With the "ffi" feature, regenerate include/rustnotes_calc.h from src/ffi.rs.
+// Only that file is parsed, so the header holds the calc_* interface and the
+// types it names, not the crate's other public items (Complex, RESULTS)
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    {
+        println!("cargo:rerun-if-changed=src/ffi.rs");
+        println!("cargo:rerun-if-changed=cbindgen.toml");
+        let crate_dir = std::env::var("CARGO_MANIFEST_DIR").unwrap();
+        let config = cbindgen::Config::from_file(format!("{}/cbindgen.toml", crate_dir))
+            .expect("cbindgen could not read cbindgen.toml");
+        // write_to_file leaves the header alone when nothing changed
+        cbindgen::Builder::new()
+            .with_config(config)
+            .with_src(format!("{}/src/ffi.rs", crate_dir))
+            .generate()
+            .expect("cbindgen could not read src/ffi.rs")
+            .write_to_file(format!("{}/include/rustnotes_calc.h", crate_dir));
+    }
+}
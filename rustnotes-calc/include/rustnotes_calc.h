@@ -0,0 +1,67 @@
+#ifndef RUSTNOTES_CALC_H
+#define RUSTNOTES_CALC_H
+
+/* Generated by cbindgen from src/ffi.rs (cargo build --features ffi); do not edit */
+
+/**
+ * Result of calc_eval.
+ */
+typedef enum CalcStatus {
+  CALC_STATUS_OK = 0,
+  CALC_STATUS_NULL_POINTER = 1,
+  CALC_STATUS_INVALID_UTF8 = 2,
+  CALC_STATUS_EVAL_ERROR = 3,
+} CalcStatus;
+
+/**
+ * A calculator with its own memory (m0-m9) and last result (_).
+ */
+typedef struct CalcHandle CalcHandle;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Creates a calculator with empty memory; release it with calc_free.
+ */
+struct CalcHandle *calc_new(void);
+
+/**
+ * Evaluates one line, as typed at the calculator prompt, and stores the
+ * value in *result on CALC_STATUS_OK. A complex result (sqrt(-1)), a
+ * quantity with units (5 km) or a matrix fails with CALC_STATUS_EVAL_ERROR.
+ *
+ * # Safety
+ *
+ * `handle` must come from calc_new and not have been freed, `expression`
+ * must be NULL or a NUL-terminated string, and `result` must be NULL or
+ * point to a writable double.
+ */
+enum CalcStatus calc_eval(struct CalcHandle *handle, const char *expression, double *result);
+
+/**
+ * The message for the last failed calc_eval on this handle ("" after a
+ * success, NULL for a NULL handle). It stays valid until the next call that
+ * takes the handle.
+ *
+ * # Safety
+ *
+ * `handle` must be NULL or come from calc_new and not have been freed.
+ */
+const char *calc_last_error(const struct CalcHandle *handle);
+
+/**
+ * Releases a calculator; NULL is ignored.
+ *
+ * # Safety
+ *
+ * `handle` must be NULL or come from calc_new, and must not be used again.
+ */
+void calc_free(struct CalcHandle *handle);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RUSTNOTES_CALC_H */
@@ -0,0 +1,357 @@
+// Exact Rationals
+//
+// The number type behind the calculator's exact mode ("mode exact"): a
+// Rational is a fraction of two arbitrary-precision integers (BigInt from
+// rustnotes-algorithms), so whole-number arithmetic never overflows or rounds
+// and division keeps the remainder as a fraction:
+//
+//   2^200           → 1606938044258990275541962092341162602522202993782792835301376
+//   factorial(50)   → 30414093201713378043612608166064768844377641568960512000000000000
+//   1/3 + 1/6       → 1/2
+//   0.1 + 0.2       → 3/10
+//
+// Design choices:
+// - Always normalized: the denominator is positive and shares no factor with
+//   the numerator, so derived equality is numeric equality and an integer is
+//   exactly a Rational whose denominator is 1
+// - Decimal literals are read from their digits (0.1 is 1/10, 6.022e23 is
+//   602200000000000000000000), not through an f64, so exact mode never sees
+//   binary rounding
+// - Only operations whose result is rational are here: + - * /, % (truncated,
+//   like f64's), integer powers, abs, round, floor, ceil, factorial and the
+//   counts of permutations and combinations (npr, ncr). The
+//   calculator falls back to f64 for the rest (sqrt, trig, logs, constants)
+// - A float becomes a fraction (the REPL's frac command) through the continued
+//   fraction of its exact binary value: the first convergent that rounds back
+//   to the same f64 is the simplest fraction it could have come from, so 0.1
+//   gives 1/10 rather than 3602879701896397/36028797018963968
+// - Powers, factorials and counts past MAX_BITS of result are refused (None)
+//   instead of running for minutes; the calculator computes those in f64
+// - Trade-off: every operation reduces by a gcd computed with Euclid's
+//   algorithm on BigInt division, which is simple and plenty for numbers of a
+//   few thousand digits but quadratic in their length
+
+use rustnotes_algorithms::math::bigint::BigInt;
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Largest result, in bits (about 315,000 decimal digits), that pow and
+// factorial will compute exactly
+const MAX_BITS: u64 = 1 << 20;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: BigInt,
+    denom: BigInt, // Always positive
+}
+
+fn gcd(a: &BigInt, b: &BigInt) -> BigInt {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while !b.is_zero() {
+        let r = &a % &b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+impl Rational {
+    // numer/denom in lowest terms; panics if denom is zero
+    pub fn new(numer: BigInt, denom: BigInt) -> Self {
+        assert!(!denom.is_zero(), "Rational with a zero denominator");
+        let (numer, denom) = if denom.is_negative() { (-numer, -denom) } else { (numer, denom) };
+        let divisor = gcd(&numer, &denom);
+        if divisor == BigInt::one() {
+            Rational { numer, denom }
+        } else {
+            Rational { numer: &numer / &divisor, denom: &denom / &divisor }
+        }
+    }
+
+    pub fn integer(n: BigInt) -> Self {
+        Rational { numer: n, denom: BigInt::one() }
+    }
+
+    pub fn zero() -> Self {
+        Self::integer(BigInt::zero())
+    }
+
+    // A decimal literal as the tokenizer sees it: digits with at most one
+    // dot, then an optional exponent ("12", "1.25", ".5", "3.", "6.022e23",
+    // "1E-9"); None for anything else, or for an exponent whose power of ten
+    // would pass MAX_BITS
+    pub fn parse_decimal(text: &str) -> Option<Self> {
+        let (mantissa, exponent) = match text.find(['e', 'E']) {
+            Some(at) => (&text[..at], text[at + 1..].parse::<i64>().ok()?),
+            None => (text, 0),
+        };
+        let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+        let digits = format!("{}{}", whole, fraction);
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return None;
+        }
+        // log2(10) < 4 bits per power of ten
+        let scale = exponent.checked_sub(fraction.len() as i64).filter(|scale| scale.unsigned_abs() * 4 <= MAX_BITS)?;
+        let numer: BigInt = digits.parse().ok()?;
+        let power = BigInt::from(10).pow(scale.unsigned_abs() as u32);
+        Some(if scale >= 0 { Self::integer(&numer * &power) } else { Self::new(numer, power) })
+    }
+
+    // A Rational as Display writes it: "42", "-7" or "22/7"
+    pub fn parse_fraction(text: &str) -> Option<Self> {
+        let (numer, denom) = text.split_once('/').unwrap_or((text, "1"));
+        let denom: BigInt = denom.parse().ok()?;
+        if denom.is_zero() {
+            return None;
+        }
+        Some(Self::new(numer.parse().ok()?, denom))
+    }
+
+    // The exact value of a finite f64, mantissa * 2^exponent
+    pub fn from_f64(x: f64) -> Option<Self> {
+        if !x.is_finite() {
+            return None;
+        }
+        let bits = x.to_bits();
+        let (mut exponent, mut mantissa) = (((bits >> 52) & 0x7ff) as i64, bits & ((1 << 52) - 1));
+        if exponent == 0 {
+            exponent = 1; // subnormal
+        } else {
+            mantissa |= 1 << 52;
+        }
+        exponent -= 1075;
+        let mantissa = BigInt::from(mantissa as i64);
+        let mantissa = if x < 0.0 { -mantissa } else { mantissa };
+        let scale = BigInt::from(2).pow(exponent.unsigned_abs() as u32);
+        Some(if exponent >= 0 { Self::integer(&mantissa * &scale) } else { Self::new(mantissa, scale) })
+    }
+
+    // The fraction with the smallest denominator that rounds to x; see the
+    // header. None for inf and NaN
+    pub fn simplest_for(x: f64) -> Option<Self> {
+        let mut rest = Self::from_f64(x)?;
+        // Convergents h/k, from h(-2)/k(-2) = 0/1 and h(-1)/k(-1) = 1/0
+        let (mut h0, mut h1) = (BigInt::zero(), BigInt::one());
+        let (mut k0, mut k1) = (BigInt::one(), BigInt::zero());
+        loop {
+            let term = rest.floor().numer;
+            let h = &(&term * &h1) + &h0;
+            let k = &(&term * &k1) + &k0;
+            let candidate = Self::new(h.clone(), k.clone());
+            let fraction = rest - Self::integer(term);
+            if candidate.to_f64() == x || fraction.is_zero() {
+                return Some(candidate);
+            }
+            rest = Self::integer(BigInt::one()) / fraction;
+            (h0, h1, k0, k1) = (h1, h, k1, k);
+        }
+    }
+
+    pub fn numer(&self) -> &BigInt {
+        &self.numer
+    }
+
+    pub fn denom(&self) -> &BigInt {
+        &self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == BigInt::one()
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.numer.is_zero()
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.numer.is_negative()
+    }
+
+    // The nearest f64, or ±inf / 0 when out of range
+    pub fn to_f64(&self) -> f64 {
+        let (numer, denom) = (self.numer.to_f64(), self.denom.to_f64());
+        if numer.is_finite() && denom.is_finite() {
+            return numer / denom;
+        }
+        // Both too large for an f64: divide as integers with a 64-bit quotient,
+        // then scale by the power of two that was taken out
+        let shift = 64 + self.denom.magnitude().bits() as i64 - self.numer.magnitude().bits() as i64;
+        let scale = BigInt::from(2).pow(shift.unsigned_abs() as u32);
+        let quotient = if shift >= 0 { &(&self.numer * &scale) / &self.denom } else { &self.numer / &(&self.denom * &scale) };
+        quotient.to_f64() * 2f64.powi(-shift.clamp(-2000, 2000) as i32)
+    }
+
+    pub fn abs(&self) -> Self {
+        Rational { numer: self.numer.abs(), denom: self.denom.clone() }
+    }
+
+    // Toward zero
+    pub fn trunc(&self) -> Self {
+        Self::integer(&self.numer / &self.denom)
+    }
+
+    pub fn floor(&self) -> Self {
+        let trunc = self.trunc();
+        if self.is_negative() && !self.is_integer() {
+            trunc - Self::integer(BigInt::one())
+        } else {
+            trunc
+        }
+    }
+
+    pub fn ceil(&self) -> Self {
+        let trunc = self.trunc();
+        if !self.is_negative() && !self.is_integer() {
+            trunc + Self::integer(BigInt::one())
+        } else {
+            trunc
+        }
+    }
+
+    // Halves away from zero, like f64::round
+    pub fn round(&self) -> Self {
+        let half = Rational::new(BigInt::from(self.numer.signum()), BigInt::from(2));
+        (self.clone() + half).trunc()
+    }
+
+    // The remainder of truncated division, with the dividend's sign (f64 %);
+    // panics if other is zero
+    pub fn rem(&self, other: &Rational) -> Self {
+        let quotient = (self.clone() / other.clone()).trunc();
+        self.clone() - quotient * other.clone()
+    }
+
+    // self^exponent, or None when the result would pass MAX_BITS or is a
+    // power of zero with a negative exponent
+    pub fn pow(&self, exponent: i64) -> Option<Self> {
+        if self.is_zero() && exponent < 0 {
+            return None;
+        }
+        let bits = self.numer.magnitude().bits().max(self.denom.magnitude().bits());
+        let power = u32::try_from(exponent.unsigned_abs()).ok()?;
+        if bits > 1 && bits.saturating_mul(power as u64) > MAX_BITS {
+            return None;
+        }
+        let (numer, denom) = (self.numer.pow(power), self.denom.pow(power));
+        Some(if exponent < 0 { Self::new(denom, numer) } else { Rational { numer, denom } })
+    }
+
+    // n! for a whole number n, or None when n is negative, not whole, or n!
+    // would pass MAX_BITS
+    pub fn factorial(&self) -> Option<Self> {
+        if !self.is_integer() || self.is_negative() {
+            return None;
+        }
+        let n = self.numer.to_i64()?;
+        // log2(n!) <= n log2(n)
+        if n > 1 && (n as f64) * (n as f64).log2() > MAX_BITS as f64 {
+            return None;
+        }
+        let product = (2..=n).fold(BigInt::one(), |acc, k| &acc * &BigInt::from(k));
+        Some(Self::integer(product))
+    }
+
+    // n and k as whole numbers 0 <= k <= n, with k replaced by n - k when
+    // `symmetric` and that is smaller, if the result of k factors of n stays
+    // under MAX_BITS; Some(None) when k > n (no ways at all)
+    fn counting_args(n: &Self, k: &Self, symmetric: bool) -> Option<Option<(i64, i64)>> {
+        if !n.is_integer() || !k.is_integer() || n.is_negative() || k.is_negative() {
+            return None;
+        }
+        let (n, k) = (n.numer.to_i64()?, k.numer.to_i64()?);
+        if k > n {
+            return Some(None);
+        }
+        let k = if symmetric { k.min(n - k) } else { k };
+        if n > 1 && (k as f64) * (n as f64).log2() > MAX_BITS as f64 {
+            return None;
+        }
+        Some(Some((n, k)))
+    }
+
+    // n! / (n-k)!, the ordered ways to pick k of n
+    pub fn permutations(n: &Self, k: &Self) -> Option<Self> {
+        let Some((n, k)) = Self::counting_args(n, k, false)? else {
+            return Some(Self::zero());
+        };
+        let product = (n - k + 1..=n).fold(BigInt::one(), |acc, factor| &acc * &BigInt::from(factor));
+        Some(Self::integer(product))
+    }
+
+    // n! / (k! (n-k)!), the unordered ways to pick k of n; each step's
+    // division is exact, since the running value is itself a binomial
+    pub fn combinations(n: &Self, k: &Self) -> Option<Self> {
+        let Some((n, k)) = Self::counting_args(n, k, true)? else {
+            return Some(Self::zero());
+        };
+        let count = (1..=k).fold(BigInt::one(), |acc, i| &(&acc * &BigInt::from(n - k + i)) / &BigInt::from(i));
+        Some(Self::integer(count))
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(n: i64) -> Self {
+        Self::integer(BigInt::from(n))
+    }
+}
+
+impl Ord for Rational {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (&self.numer * &other.denom).cmp(&(&other.numer * &self.denom))
+    }
+}
+
+impl PartialOrd for Rational {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for Rational {
+    type Output = Rational;
+    fn add(self, other: Rational) -> Rational {
+        let numer = &self.numer * &other.denom + &other.numer * &self.denom;
+        Rational::new(numer, &self.denom * &other.denom)
+    }
+}
+
+impl Sub for Rational {
+    type Output = Rational;
+    fn sub(self, other: Rational) -> Rational {
+        self + -other
+    }
+}
+
+impl Mul for Rational {
+    type Output = Rational;
+    fn mul(self, other: Rational) -> Rational {
+        Rational::new(&self.numer * &other.numer, &self.denom * &other.denom)
+    }
+}
+
+// Panics on a zero divisor, like integer division
+impl Div for Rational {
+    type Output = Rational;
+    fn div(self, other: Rational) -> Rational {
+        Rational::new(&self.numer * &other.denom, &self.denom * &other.numer)
+    }
+}
+
+impl Neg for Rational {
+    type Output = Rational;
+    fn neg(self) -> Rational {
+        Rational { numer: -self.numer, denom: self.denom }
+    }
+}
+
+// 42, -7 or 22/7
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
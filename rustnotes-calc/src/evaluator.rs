@@ -0,0 +1,767 @@
+// Calculator Evaluator
+//
+// Evaluates a parsed Ast (parser.rs) against a Context: the memory slots,
+// named values and last results, and the settings that change what an
+// expression means (angle mode and number mode). A tool embedding the
+// calculator keeps a Context of its own and needs neither the REPL nor
+// Calculator:
+//
+//   let mut context = Context::new(Settings::default());
+//   context.memory[0] = Value::Real(2.0);
+//   let value = eval(&parse("m0 ^ 10")?, &mut context)?;   // 1024, and _ too
+//
+// Design choices:
+// - eval records a successful result as _, as the REPL does, moving the one
+//   before it to _2 and so on; Context::value evaluates without touching the
+//   context at all
+// - Only the last RESULTS answers are kept (_ to _10), in a ring buffer, so a
+//   long script does not pile up every value it has computed. The fresh _ of
+//   0 counts as an answer; asking for one before it, or past _10, is an error
+// - Every check that depends on values is made here, as each operator is
+//   applied: units of the same dimensions for +, a divisor that is not zero,
+//   whole numbers for the bitwise operators, the shapes of matrices
+// - The operands are evaluated left to right and the first error wins, so
+//   (1/0) + ln(0) reports the division. if() is the exception: it evaluates
+//   its condition and then only the branch taken, so if(x == 0, 0, 1/x) is
+//   0 rather than a division by zero
+// - sum, prod, diff, integrate and solve evaluate their expression with the
+//   variable bound in a copy of the context, made once per call: the name
+//   shadows a stored value of the same name, and nothing is left behind
+//   afterwards
+// - Trade-off: the whole Ast is walked on every call, so evaluating one
+//   expression many times repeats the work of looking up functions by name
+
+use crate::bignum::Rational;
+use crate::matrix;
+use crate::parser::{Ast, BinaryOp, UnaryOp, BINDERS, FUNCTIONS};
+use crate::settings::{AngleMode, NumberMode, Settings};
+use crate::units::Quantity;
+use crate::value::Value;
+use crate::CalcError;
+use rustnotes_algorithms::math::bigint::BigInt;
+use rustnotes_algorithms::math::linear_algebra::Matrix;
+use std::collections::{BTreeMap, VecDeque};
+
+// How many answers _ and _2, _3, ... reach back
+pub const RESULTS: usize = 10;
+
+// The most terms sum and prod add up, so a typo in a bound cannot hang the REPL
+const MAX_TERMS: i128 = 1_000_000;
+
+// Newton steps solve tries before looking for a sign change, and the bisection
+// steps after finding one: plenty to pin down an f64
+const NEWTON_STEPS: usize = 100;
+const BISECTION_STEPS: usize = 2200;
+
+// How often integrate may halve an interval, and evaluate its integrand in all
+const MAX_DEPTH: u32 = 50;
+const MAX_EVALUATIONS: usize = 1_000_000;
+
+// Unit powers are i8s (units.rs) without -128, so every one can be negated
+const UNIT_POWERS: &str = "unit powers from -127 to 127";
+
+// What an expression can refer to besides itself
+#[derive(Debug, Clone, PartialEq)]
+pub struct Context {
+    // m0-m9
+    pub memory: [Value; 10],
+    // Values stored by name (store rate), sorted for listing
+    pub variables: BTreeMap<String, Value>,
+    // _
+    pub last_result: Value,
+    // _2 onwards, newest first
+    pub earlier_results: VecDeque<Value>,
+    pub settings: Settings,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new(Settings::default())
+    }
+}
+
+// Evaluate `ast`, keeping the result as _ on success
+pub fn eval(ast: &Ast, context: &mut Context) -> Result<Value, CalcError> {
+    let result = context.value(ast)?;
+    context.push_result(result.clone());
+    Ok(result)
+}
+
+impl Context {
+    // Empty memory and _ of 0
+    pub fn new(settings: Settings) -> Self {
+        Self {
+            memory: std::array::from_fn(|_| Value::Real(0.0)),
+            variables: BTreeMap::new(),
+            last_result: Value::Real(0.0),
+            earlier_results: VecDeque::with_capacity(RESULTS),
+            settings,
+        }
+    }
+
+    // Make `result` _, moving the old _ to _2 and dropping the oldest past _10
+    pub fn push_result(&mut self, result: Value) {
+        if self.earlier_results.len() == RESULTS - 1 {
+            self.earlier_results.pop_back();
+        }
+        let previous = std::mem::replace(&mut self.last_result, result);
+        self.earlier_results.push_front(previous);
+    }
+
+    // _n: _1 is _, _2 the answer before it
+    pub fn result(&self, n: usize) -> Result<Value, CalcError> {
+        match n {
+            1 => Ok(self.last_result.clone()),
+            _ => n.checked_sub(2).and_then(|i| self.earlier_results.get(i)).cloned().ok_or(CalcError::NoResult(n)),
+        }
+    }
+
+    // The value of `ast`, leaving _ as it is
+    pub fn value(&self, ast: &Ast) -> Result<Value, CalcError> {
+        match ast {
+            Ast::Number { real, exact } => Ok(match (self.settings.number_mode, exact) {
+                (NumberMode::Exact, Some(exact)) => Value::Exact(exact.clone()),
+                // An exponent too large to hold exactly stays an f64
+                _ => Value::Real(*real),
+            }),
+            Ast::Imaginary(n) => Ok(Value::complex(0.0, *n)),
+            Ast::Constant(name) => constant(name),
+            Ast::Unit(unit) => Ok(Value::Quantity(Quantity::new(1.0, unit.clone()))),
+            // The tokenizer reads m10 as a function name, and so does this
+            Ast::Memory(slot) => self.memory.get(*slot).cloned().ok_or_else(|| CalcError::UnknownFunction(format!("m{}", slot))),
+            Ast::Variable(name) => self.variables.get(name).cloned().ok_or_else(|| CalcError::UnknownVariable(name.clone())),
+            Ast::LastResult => Ok(self.last_result.clone()),
+            Ast::EarlierResult(n) => self.result(*n),
+            Ast::Unary(op, operand) => self.unary(*op, self.value(operand)?),
+            Ast::Binary(op, left, right) => {
+                let left = self.value(left)?;
+                self.binary(*op, left, self.value(right)?)
+            }
+            Ast::Call(name, args) => {
+                let args = args.iter().map(|arg| self.value(arg)).collect::<Result<Vec<_>, _>>()?;
+                self.call(name, args)
+            }
+            Ast::If(condition, then, otherwise) => {
+                let condition = self.value(condition)?;
+                if let Value::Matrix(_) = condition {
+                    return Err(CalcError::Domain { function: "if", requirement: "a number as the condition" });
+                }
+                trace!("if {}", condition);
+                self.value(if condition.is_zero() { otherwise } else { then })
+            }
+            Ast::Bound { function, body, variable, args } => {
+                let args = args.iter().map(|arg| self.value(arg)).collect::<Result<Vec<_>, _>>()?;
+                self.bound(function, body, variable, args)
+            }
+            Ast::Matrix(items) => matrix::literal(items.iter().map(|item| self.value(item)).collect::<Result<_, _>>()?),
+        }
+    }
+
+    // Trig functions take radians; in degree mode convert on the way in
+    fn angle_in(&self, angle: Value) -> Value {
+        match self.settings.angle_mode {
+            AngleMode::Radians => angle,
+            AngleMode::Degrees => angle.map_parts(f64::to_radians),
+        }
+    }
+
+    // ...and convert inverse trig results on the way out
+    fn angle_out(&self, radians: Value) -> Value {
+        match self.settings.angle_mode {
+            AngleMode::Radians => radians,
+            AngleMode::Degrees => radians.map_parts(f64::to_degrees),
+        }
+    }
+
+    // A whole number as the current mode represents it
+    fn integer(&self, n: i128) -> Value {
+        match self.settings.number_mode {
+            NumberMode::Exact => Value::Exact(Rational::integer(BigInt::from(n))),
+            NumberMode::Float => Value::Real(n as f64),
+        }
+    }
+
+    fn unary(&self, op: UnaryOp, operand: Value) -> Result<Value, CalcError> {
+        match op {
+            UnaryOp::Negate => Ok(-operand),
+            UnaryOp::Not => {
+                let n = operand
+                    .to_integer()
+                    .ok_or(CalcError::Domain { function: "~", requirement: "a whole-number operand within 128 bits" })?;
+                trace!("~{} = {}", n, !n);
+                Ok(self.integer(!n))
+            }
+            UnaryOp::Factorial => {
+                let result = operand
+                    .clone()
+                    .factorial()
+                    .ok_or(CalcError::Domain { function: "!", requirement: "a whole number >= 0" })?;
+                trace!("{}! = {}", operand, result);
+                Ok(result)
+            }
+        }
+    }
+
+    fn binary(&self, op: BinaryOp, left: Value, right: Value) -> Result<Value, CalcError> {
+        match op {
+            BinaryOp::Convert => {
+                let result = convert(&left, &right)?;
+                trace!("{} in {} = {}", left, right, result);
+                Ok(result)
+            }
+            BinaryOp::Or | BinaryOp::Xor | BinaryOp::And => {
+                let function = match op {
+                    BinaryOp::Or => "|",
+                    BinaryOp::Xor => "xor",
+                    _ => "&",
+                };
+                let (a, b) = integers(function, &left, &right)?;
+                let result = match op {
+                    BinaryOp::Or => a | b,
+                    BinaryOp::Xor => a ^ b,
+                    _ => a & b,
+                };
+                trace!("{} {} {} = {}", a, function, b, result);
+                Ok(self.integer(result))
+            }
+            // >> is arithmetic (it rounds toward minus infinity); << fails
+            // rather than drop bits off the top
+            BinaryOp::ShiftLeft | BinaryOp::ShiftRight => {
+                let shift_left = op == BinaryOp::ShiftLeft;
+                let function = if shift_left { "<<" } else { ">>" };
+                let (a, b) = integers(function, &left, &right)?;
+                let Some(shift) = u32::try_from(b).ok().filter(|&shift| shift < 128) else {
+                    return Err(CalcError::Domain { function, requirement: "a shift of 0 to 127 bits" });
+                };
+                let result = if shift_left { a << shift } else { a >> shift };
+                if shift_left && result >> shift != a {
+                    return Err(CalcError::Domain { function, requirement: "a result within 128 bits" });
+                }
+                trace!("{} {} {} = {}", a, function, b, result);
+                Ok(self.integer(result))
+            }
+            BinaryOp::Equal
+            | BinaryOp::NotEqual
+            | BinaryOp::Less
+            | BinaryOp::LessEqual
+            | BinaryOp::Greater
+            | BinaryOp::GreaterEqual => {
+                let holds = compare(op, &left, &right)?;
+                trace!("{} {:?} {} = {}", left, op, right, holds);
+                Ok(self.integer(holds as i128))
+            }
+            BinaryOp::Add => {
+                check_same_dimensions(&left, &right)?;
+                if let Some(result) = matrix::binary('+', &left, &right) {
+                    return result;
+                }
+                trace!("{} + {} = {}", left, right, left.clone() + right.clone());
+                Ok(left + right)
+            }
+            BinaryOp::Subtract => {
+                check_same_dimensions(&left, &right)?;
+                if let Some(result) = matrix::binary('-', &left, &right) {
+                    return result;
+                }
+                trace!("{} - {} = {}", left, right, left.clone() - right.clone());
+                Ok(left - right)
+            }
+            BinaryOp::Multiply => {
+                check_real_with_unit(&left, &right)?;
+                check_unit_powers("*", &left, &right)?;
+                if let Some(result) = matrix::binary('*', &left, &right) {
+                    return result;
+                }
+                trace!("{} * {} = {}", left, right, left.clone() * right.clone());
+                Ok(left * right)
+            }
+            BinaryOp::Divide => {
+                if right.is_zero() {
+                    return Err(CalcError::DivisionByZero);
+                }
+                check_real_with_unit(&left, &right)?;
+                check_unit_powers("/", &left, &right)?;
+                if let Some(result) = matrix::binary('/', &left, &right) {
+                    return result;
+                }
+                trace!("{} / {} = {}", left, right, left.clone() / right.clone());
+                Ok(left / right)
+            }
+            BinaryOp::Modulo => {
+                if matches!(left, Value::Quantity(_)) || matches!(right, Value::Quantity(_)) {
+                    return Err(CalcError::Domain { function: "%", requirement: "numbers without units" });
+                }
+                if let (Value::Exact(a), Value::Exact(b)) = (&left, &right) {
+                    if b.is_zero() {
+                        return Err(CalcError::ModuloByZero);
+                    }
+                    trace!("{} % {} = {}", a, b, a.rem(b));
+                    return Ok(Value::Exact(a.rem(b)));
+                }
+                let (Some(a), Some(b)) = (left.as_real(), right.as_real()) else {
+                    return Err(CalcError::Domain { function: "%", requirement: "real operands" });
+                };
+                if b == 0.0 {
+                    return Err(CalcError::ModuloByZero);
+                }
+                trace!("{} % {} = {}", a, b, a % b);
+                Ok(Value::Real(a % b))
+            }
+            BinaryOp::Power => {
+                check_power(&left, &right)?;
+                if let Value::Matrix(base) = &left {
+                    return matrix::power(base, &right);
+                }
+                trace!("{} ^ {} = {}", left, right, left.clone().pow(right.clone()));
+                Ok(left.pow(right))
+            }
+        }
+    }
+
+    fn bound(&self, name: &str, body: &Ast, variable: &str, args: Vec<Value>) -> Result<Value, CalcError> {
+        let Some(function) = BINDERS.iter().map(|(binder, _)| *binder).find(|binder| *binder == name) else {
+            return Err(CalcError::UnknownFunction(name.to_string()));
+        };
+        match function {
+            "sum" | "prod" => self.series(function, body, variable, &args),
+            "diff" => self.derivative(body, variable, &args[0]),
+            "integrate" => self.integral(body, variable, &args),
+            _ => self.root(body, variable, &args[0]),
+        }
+    }
+
+    // sum or prod of `body` for each whole `variable` from args[0] to args[1];
+    // the first term starts the total, so units add up, and an empty range
+    // gives 0 or 1
+    fn series(&self, function: &'static str, body: &Ast, variable: &str, args: &[Value]) -> Result<Value, CalcError> {
+        let (Some(start), Some(end)) = (args[0].to_integer(), args[1].to_integer()) else {
+            return Err(CalcError::Domain { function, requirement: "whole-number bounds" });
+        };
+        if end.saturating_sub(start) >= MAX_TERMS {
+            return Err(CalcError::Domain { function, requirement: "at most 1000000 terms" });
+        }
+        trace!("{} over {} = {}..={}", function, variable, start, end);
+        let (op, empty) = match function {
+            "sum" => (BinaryOp::Add, 0),
+            _ => (BinaryOp::Multiply, 1),
+        };
+        let mut scope = self.clone();
+        let mut total = None;
+        for k in start..=end {
+            scope.variables.insert(variable.to_string(), self.integer(k));
+            let term = scope.value(body)?;
+            total = Some(match total {
+                Some(total) => self.binary(op, total, term)?,
+                None => term,
+            });
+        }
+        Ok(total.unwrap_or_else(|| self.integer(empty)))
+    }
+
+    // d(body)/d(variable) at `point`, by the five-point central difference
+    // (f(x-2h) - 8f(x-h) + 8f(x+h) - f(x+2h)) / 12h. Its error shrinks as h^4
+    // while rounding grows as 1/h, so h near eps^(1/5), scaled to x, balances
+    // the two at about 1e-13 relative. The expression is also evaluated at x
+    // itself, so diff(1/x, x, 0) fails as 1/0 does instead of finding a slope
+    // across the pole
+    fn derivative(&self, body: &Ast, variable: &str, point: &Value) -> Result<Value, CalcError> {
+        let x = point.as_real().ok_or(CalcError::Domain { function: "diff", requirement: "a real point" })?;
+        let h = f64::EPSILON.powf(0.2) * x.abs().max(1.0);
+        trace!("diff over {} at {} with h = {}", variable, x, h);
+        let mut scope = self.clone();
+        let mut f = |x| scope.real_at("diff", body, variable, x);
+        f(x)?;
+        let slope = (f(x - 2.0 * h)? - 8.0 * f(x - h)? + 8.0 * f(x + h)? - f(x + 2.0 * h)?) / (12.0 * h);
+        Ok(Value::Real(slope))
+    }
+
+    // The integral of `body` from args[0] to args[1] by adaptive Simpson's rule:
+    // an interval is halved until Simpson's estimate over the halves agrees
+    // with the one over the whole to within the tolerance setting, which is
+    // halved along with it. An interval still short of it MAX_DEPTH halvings
+    // down keeps its estimate if that is within the tolerance for the whole
+    // integral, which is how a jump such as if(x < 1, 0, 1) is integrated. A
+    // pole, where it is not, an infinite estimate, or more than
+    // MAX_EVALUATIONS of the integrand is an error rather than an answer
+    fn integral(&self, body: &Ast, variable: &str, args: &[Value]) -> Result<Value, CalcError> {
+        let (Some(a), Some(b)) = (args[0].as_real(), args[1].as_real()) else {
+            return Err(CalcError::Domain { function: "integrate", requirement: "real bounds" });
+        };
+        if !a.is_finite() || !b.is_finite() {
+            return Err(CalcError::Domain { function: "integrate", requirement: "finite bounds" });
+        }
+        trace!("integrate over {} from {} to {}", variable, a, b);
+        let mut scope = self.clone();
+        let mut evaluations = 0;
+        let mut f = |x| {
+            evaluations += 1;
+            if evaluations > MAX_EVALUATIONS {
+                return Err(CalcError::Domain { function: "integrate", requirement: "an integral that converges" });
+            }
+            scope.real_at("integrate", body, variable, x)
+        };
+        let m = (a + b) / 2.0;
+        let (fa, fm, fb) = (f(a)?, f(m)?, f(b)?);
+        let whole = (b - a) / 6.0 * (fa + 4.0 * fm + fb);
+        let area = simpson(&mut f, (a, fa), (m, fm), (b, fb), whole, self.settings.tolerance, MAX_DEPTH)?;
+        Ok(Value::Real(area))
+    }
+
+    // A zero of `body` near `guess`: Newton's method first, with the slope by
+    // a central difference and shortened to stay where the expression is
+    // defined, which is quick near a simple zero and also finds a double one
+    // such as x^2's. It hands over to bisection as soon as two points
+    // straddle a sign change, or when it stalls, after searching outward from
+    // the guess for one. Bisection cannot miss, but a sign change can be a pole
+    // (1/x at 0), so one where the expression grew instead is an error
+    fn root(&self, body: &Ast, variable: &str, guess: &Value) -> Result<Value, CalcError> {
+        let x0 = guess.as_real().ok_or(CalcError::Domain { function: "solve", requirement: "a real guess" })?;
+        let mut scope = self.clone();
+        let mut f = |x| scope.real_at("solve", body, variable, x);
+        let scale = x0.abs().max(1.0);
+        let (mut x, mut fx) = (x0, f(x0)?);
+        trace!("solve over {} from {}", variable, x0);
+
+        let mut bracket = None;
+        for _ in 0..NEWTON_STEPS {
+            if fx == 0.0 {
+                return Ok(Value::Real(x));
+            }
+            let h = f64::EPSILON.cbrt() * x.abs().max(1.0);
+            let (Ok(ahead), Ok(behind)) = (f(x + h), f(x - h)) else { break };
+            // A step out of the expression's domain (ln(x) - 1 from 10 steps
+            // to -3) is halved until it lands inside
+            let mut step = fx * 2.0 * h / (ahead - behind);
+            if step.abs() <= f64::EPSILON * x.abs().max(scale) {
+                return Ok(Value::Real(x - step));
+            }
+            let mut landed = None;
+            for _ in 0..64 {
+                if !step.is_finite() || landed.is_some() {
+                    break;
+                }
+                landed = f(x - step).ok().map(|f_next| (x - step, f_next));
+                step /= 2.0;
+            }
+            let Some((next, f_next)) = landed else { break };
+            trace!("newton {} -> {}", x, next);
+            if f_next.signum() != fx.signum() {
+                bracket = Some(((x, fx), (next, f_next)));
+                break;
+            }
+            (x, fx) = (next, f_next);
+        }
+
+        let f0 = f(x0)?;
+        let bracket = match bracket {
+            Some(bracket) => Some(bracket),
+            None => (0..64).find_map(|i| {
+                let step = scale * 0.01 * 2f64.powi(i);
+                [x0 - step, x0 + step].into_iter().find_map(|x| {
+                    let fx = f(x).ok()?;
+                    (fx.signum() != f0.signum()).then_some(((x0, f0), (x, fx)))
+                })
+            }),
+        };
+        let Some(((mut a, mut fa), (mut b, fb))) = bracket else {
+            return Err(CalcError::Domain { function: "solve", requirement: "a guess near a zero" });
+        };
+        let largest = fa.abs().max(fb.abs());
+        for _ in 0..BISECTION_STEPS {
+            let m = a + (b - a) / 2.0;
+            if m == a || m == b {
+                break;
+            }
+            let fm = f(m)?;
+            if fm == 0.0 {
+                return Ok(Value::Real(m));
+            }
+            if fm.signum() == fa.signum() {
+                (a, fa) = (m, fm);
+            } else {
+                b = m;
+            }
+        }
+        if f(a)?.abs() > largest {
+            return Err(CalcError::Domain { function: "solve", requirement: "a zero rather than a pole" });
+        }
+        Ok(Value::Real(a))
+    }
+
+    // `body` with `variable` bound to x, for the binders that work in f64
+    fn real_at(&mut self, function: &'static str, body: &Ast, variable: &str, x: f64) -> Result<f64, CalcError> {
+        self.variables.insert(variable.to_string(), Value::Real(x));
+        self.value(body)?.as_real().ok_or(CalcError::Domain { function, requirement: "an expression with real values" })
+    }
+
+    fn call(&self, name: &str, mut args: Vec<Value>) -> Result<Value, CalcError> {
+        let Some(function) = FUNCTIONS.iter().copied().find(|function| *function == name) else {
+            return Err(CalcError::UnknownFunction(name.to_string()));
+        };
+        trace!("call {}({})", function, args.iter().map(Value::to_string).collect::<Vec<_>>().join(", "));
+
+        let expected = match function {
+            "ncr" | "npr" => 2,
+            "quadratic" => 3,
+            _ => 1,
+        };
+        if args.len() != expected {
+            return Err(CalcError::ArgumentCount { function: function.to_string(), expected });
+        }
+        if function == "quadratic" {
+            return quadratic(&args);
+        }
+        if expected == 2 {
+            let k = args.pop().unwrap();
+            let n = args.pop().unwrap();
+            let result = match function {
+                "ncr" => n.combinations(k),
+                _ => n.permutations(k),
+            };
+            return result.ok_or(CalcError::Domain { function, requirement: "whole numbers n, k >= 0" });
+        }
+        let arg = args.pop().unwrap();
+        if let Value::Matrix(m) = &arg {
+            match function {
+                "det" | "inv" | "transpose" => return matrix::function(function, m),
+                "abs" | "round" | "floor" | "ceil" => {}
+                _ => return Err(CalcError::Domain { function, requirement: "a number, not a matrix" }),
+            }
+        } else if matches!(function, "det" | "inv" | "transpose") {
+            return Err(CalcError::Domain { function, requirement: "a matrix, such as [[1, 2], [3, 4]]" });
+        }
+        if let Value::Quantity(q) = &arg {
+            match function {
+                "abs" | "round" | "floor" | "ceil" => {}
+                "sqrt" if q.value < 0.0 => return Err(CalcError::Domain { function, requirement: "a value with units that is not negative" }),
+                "sqrt" if q.unit.sqrt().is_some() => {}
+                "sqrt" => return Err(CalcError::Domain { function, requirement: "a unit with even powers, such as m^2" }),
+                _ => return Err(CalcError::Domain { function, requirement: "a number without units" }),
+            }
+        }
+
+        // Negative arguments give complex results (value.rs); only the
+        // singular points remain errors
+        match function {
+            "sin" => Ok(self.angle_in(arg).sin()),
+            "cos" => Ok(self.angle_in(arg).cos()),
+            "tan" => Ok(self.angle_in(arg).tan()),
+            "asin" => Ok(self.angle_out(arg.asin())),
+            "acos" => Ok(self.angle_out(arg.acos())),
+            "atan" => {
+                if arg == Value::complex(0.0, 1.0) || arg == Value::complex(0.0, -1.0) {
+                    return Err(CalcError::Domain { function: "atan", requirement: "argument other than i and -i" });
+                }
+                Ok(self.angle_out(arg.atan()))
+            }
+            "ln" | "log2" | "log10" if arg.is_zero() => Err(CalcError::Domain { function, requirement: "non-zero argument" }),
+            "ln" => Ok(arg.ln()),
+            "log2" => Ok(arg.log2()),
+            "log10" => Ok(arg.log10()),
+            "exp" => Ok(arg.exp()),
+            "sqrt" => Ok(arg.sqrt()),
+            "round" => Ok(arg.round()),
+            "floor" => Ok(arg.floor()),
+            "ceil" => Ok(arg.ceil()),
+            "abs" => Ok(arg.abs()),
+            _ => arg
+                .factorial()
+                .ok_or(CalcError::Domain { function: "factorial", requirement: "a whole number >= 0" }),
+        }
+    }
+}
+
+// One step of adaptive Simpson's rule over [a, b] with midpoint m, given f at
+// all three and `whole`, the estimate over [a, b]; the difference between the
+// halves and the whole, over 15, is both the error estimate and a correction.
+// The tolerance halves with each level but never asks for more than the f64
+// rounding of the estimate allows, or a large integrand would never settle
+fn simpson(
+    f: &mut dyn FnMut(f64) -> Result<f64, CalcError>,
+    (a, fa): (f64, f64),
+    (m, fm): (f64, f64),
+    (b, fb): (f64, f64),
+    whole: f64,
+    tolerance: f64,
+    depth: u32,
+) -> Result<f64, CalcError> {
+    let (left_mid, right_mid) = ((a + m) / 2.0, (m + b) / 2.0);
+    let (f_left, f_right) = (f(left_mid)?, f(right_mid)?);
+    let left = (m - a) / 6.0 * (fa + 4.0 * f_left + fm);
+    let right = (b - m) / 6.0 * (fm + 4.0 * f_right + fb);
+    let delta = left + right - whole;
+    if delta.abs() <= 15.0 * tolerance.max(f64::EPSILON * (left + right).abs()) {
+        return Ok(left + right + delta / 15.0);
+    }
+    // At the bottom, the tolerance is back to what the whole integral allows
+    let bottomed_out = depth == 0 && delta.abs() <= 15.0 * tolerance * 2f64.powi(MAX_DEPTH as i32);
+    if bottomed_out {
+        return Ok(left + right + delta / 15.0);
+    }
+    if depth == 0 || !delta.is_finite() {
+        return Err(CalcError::Domain { function: "integrate", requirement: "an integral that converges" });
+    }
+    let left = simpson(f, (a, fa), (left_mid, f_left), (m, fm), left, tolerance / 2.0, depth - 1)?;
+    Ok(left + simpson(f, (m, fm), (right_mid, f_right), (b, fb), right, tolerance / 2.0, depth - 1)?)
+}
+
+// The real roots of ax^2 + bx + c as a row, smallest first (a double root
+// twice). The root nearer zero comes from c / q rather than the textbook
+// formula, which would subtract two nearly equal numbers when b^2 >> 4ac
+fn quadratic(args: &[Value]) -> Result<Value, CalcError> {
+    let [Some(a), Some(b), Some(c)] = [&args[0], &args[1], &args[2]].map(Value::as_real) else {
+        return Err(CalcError::Domain { function: "quadratic", requirement: "real coefficients" });
+    };
+    if a == 0.0 {
+        return Err(CalcError::Domain { function: "quadratic", requirement: "a non-zero a" });
+    }
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return Err(CalcError::Domain { function: "quadratic", requirement: "real roots, b^2 >= 4ac" });
+    }
+    let q = -(b + b.signum() * discriminant.sqrt()) / 2.0;
+    let (x1, x2) = if q == 0.0 { (0.0, 0.0) } else { (q / a, c / q) };
+    trace!("quadratic({}, {}, {}) = {}, {}", a, b, c, x1, x2);
+    Ok(Value::Matrix(Matrix::from_rows(vec![vec![x1.min(x2), x1.max(x2)]])?))
+}
+
+fn constant(name: &str) -> Result<Value, CalcError> {
+    match name {
+        "pi" => Ok(Value::Real(std::f64::consts::PI)),
+        "e" => Ok(Value::Real(std::f64::consts::E)),
+        "phi" => Ok(Value::Real((1.0 + 5.0_f64.sqrt()) / 2.0)), // Golden ratio
+        "tau" => Ok(Value::Real(2.0 * std::f64::consts::PI)),   // 2π
+        "sqrt2" => Ok(Value::Real(std::f64::consts::SQRT_2)),
+        "sqrt3" => Ok(Value::Real(3.0_f64.sqrt())),
+        "i" => Ok(Value::complex(0.0, 1.0)),
+        _ => Err(CalcError::UnknownConstant(name.to_string())),
+    }
+}
+
+// Both operands of a bitwise operator as integers, or a Domain error naming it
+fn integers(function: &'static str, left: &Value, right: &Value) -> Result<(i128, i128), CalcError> {
+    match (left.to_integer(), right.to_integer()) {
+        (Some(a), Some(b)) => Ok((a, b)),
+        _ => Err(CalcError::Domain { function, requirement: "whole-number operands within 128 bits" }),
+    }
+}
+
+// Whether a comparison holds: by the sign of left - right, so 5 km > 300 m
+// compares in the same unit and exact numbers compare exactly. Complex numbers
+// and matrices are only equal or not
+fn compare(op: BinaryOp, left: &Value, right: &Value) -> Result<bool, CalcError> {
+    let function = match op {
+        BinaryOp::Equal => "==",
+        BinaryOp::NotEqual => "!=",
+        BinaryOp::Less => "<",
+        BinaryOp::LessEqual => "<=",
+        BinaryOp::Greater => ">",
+        _ => ">=",
+    };
+    check_same_dimensions(left, right)?;
+    if matches!(left, Value::Matrix(_)) || matches!(right, Value::Matrix(_)) {
+        return match op {
+            BinaryOp::Equal => Ok(left == right),
+            BinaryOp::NotEqual => Ok(left != right),
+            _ => Err(CalcError::Domain { function, requirement: "numbers, not matrices" }),
+        };
+    }
+    let difference = left.clone() - right.clone();
+    if let BinaryOp::Equal | BinaryOp::NotEqual = op {
+        return Ok(difference.is_zero() == (op == BinaryOp::Equal));
+    }
+    let sign = match difference {
+        Value::Exact(r) if r.is_zero() => 0.0,
+        Value::Exact(r) => if r.is_negative() { -1.0 } else { 1.0 },
+        Value::Quantity(q) => q.value,
+        Value::Real(x) => x,
+        Value::Complex(_) | Value::Matrix(_) => {
+            return Err(CalcError::Domain { function, requirement: "real operands" });
+        }
+    };
+    Ok(match op {
+        BinaryOp::Less => sign < 0.0,
+        BinaryOp::LessEqual => sign <= 0.0,
+        BinaryOp::Greater => sign > 0.0,
+        _ => sign >= 0.0,
+    })
+}
+
+// A value's unit for error messages
+fn unit_name(value: &Value) -> String {
+    match value {
+        Value::Quantity(q) => q.unit.to_string(),
+        _ => "a plain number".to_string(),
+    }
+}
+
+// + and - need the same dimensions on both sides: km + m, not km + s or km + 1
+fn check_same_dimensions(left: &Value, right: &Value) -> Result<(), CalcError> {
+    match (left, right) {
+        (Value::Quantity(a), Value::Quantity(b)) if a.has_dimensions_of(b) => Ok(()),
+        (Value::Quantity(_), _) | (_, Value::Quantity(_)) => {
+            Err(CalcError::IncompatibleUnits(unit_name(left), unit_name(right)))
+        }
+        _ => Ok(()),
+    }
+}
+
+// Units go with real numbers only, not complex numbers or matrices
+fn check_real_with_unit(left: &Value, right: &Value) -> Result<(), CalcError> {
+    match (left, right) {
+        (Value::Quantity(_), Value::Complex(_) | Value::Matrix(_)) | (Value::Complex(_) | Value::Matrix(_), Value::Quantity(_)) => {
+            Err(CalcError::Domain { function: "a unit", requirement: "a real number" })
+        }
+        _ => Ok(()),
+    }
+}
+
+// A value with units takes whole powers, and no exponent has units. An exact
+// 0 to a negative power is a division by zero, as 1/0 is, rather than f64's inf
+fn check_power(base: &Value, exponent: &Value) -> Result<(), CalcError> {
+    if let (Value::Exact(base), Value::Exact(exponent)) = (base, exponent) {
+        if base.is_zero() && exponent.is_negative() {
+            return Err(CalcError::DivisionByZero);
+        }
+    }
+    if let Value::Quantity(_) = exponent {
+        return Err(CalcError::Domain { function: "^", requirement: "an exponent without units" });
+    }
+    if let Value::Matrix(_) = exponent {
+        return Err(CalcError::Domain { function: "^", requirement: "a number as the exponent" });
+    }
+    if let Value::Quantity(q) = base {
+        let Some(n) = exponent.to_integer().and_then(|n| i8::try_from(n).ok()) else {
+            return Err(CalcError::Domain { function: "^", requirement: "a whole-number power of a value with units" });
+        };
+        if q.unit.powi(n).is_none() {
+            return Err(CalcError::Domain { function: "^", requirement: UNIT_POWERS });
+        }
+    }
+    Ok(())
+}
+
+// A product or quotient of values with units must keep each unit's power
+// within -127..=127
+fn check_unit_powers(function: &'static str, left: &Value, right: &Value) -> Result<(), CalcError> {
+    let unit = match (left, right) {
+        (Value::Quantity(a), Value::Quantity(b)) if function == "/" => b.unit.powi(-1).and_then(|b| a.unit.mul(&b)),
+        (Value::Quantity(a), Value::Quantity(b)) => a.unit.mul(&b.unit),
+        (_, Value::Quantity(q)) if function == "/" => q.unit.powi(-1),
+        _ => return Ok(()),
+    };
+    unit.map(|_| ()).ok_or(CalcError::Domain { function, requirement: UNIT_POWERS })
+}
+
+// `value in target`: the target must be a unit, such as km/h, of the same
+// dimensions as the value
+fn convert(value: &Value, target: &Value) -> Result<Value, CalcError> {
+    let unit = match target {
+        Value::Quantity(q) if q.value == 1.0 => &q.unit,
+        _ => return Err(CalcError::Domain { function: "in", requirement: "a unit on the right, such as km/h" }),
+    };
+    match value {
+        Value::Quantity(q) if q.unit.dimensions() == unit.dimensions() => Ok(Value::Quantity(q.convert(unit))),
+        _ => Err(CalcError::IncompatibleUnits(unit_name(value), unit.to_string())),
+    }
+}
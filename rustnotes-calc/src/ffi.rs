@@ -0,0 +1,126 @@
+// C Interface
+//
+// extern "C" functions over Calculator, compiled with the "ffi" feature. The
+// header include/rustnotes_calc.h is generated from this file by cbindgen
+// (see build.rs), so the /// comments below end up in it:
+//
+//   CalcHandle *calc = calc_new();
+//   double result;
+//   if (calc_eval(calc, "2 + 3 * 4", &result) == CALC_STATUS_OK)
+//       printf("%g\n", result);
+//   else
+//       puts(calc_last_error(calc));
+//   calc_free(calc);
+//
+// Design choices:
+// - The handle is opaque: C only sees a pointer to memory Rust owns, so it
+//   must go back to calc_free rather than free()
+// - calc_eval returns a status code and writes the value through an out
+//   pointer, errno style; the message for the last failure stays readable
+//   through calc_last_error until the next call on the same handle
+// - NULL pointers and invalid UTF-8 get their own status codes instead of
+//   undefined behavior
+// - A complex, unit or matrix result has no double to hand back, so it is an
+//   EvalError whose message names its kind and shows the value; it still
+//   becomes _ for the next line
+// - Trade-off: a handle is not synchronized, so threads need one each
+
+use crate::value::Value;
+use crate::Calculator;
+use std::ffi::{c_char, CStr, CString};
+
+/// A calculator with its own memory (m0-m9) and last result (_).
+pub struct CalcHandle {
+    calculator: Calculator,
+    last_error: CString,
+}
+
+impl CalcHandle {
+    fn fail(&mut self, status: CalcStatus, message: impl Into<Vec<u8>>) -> CalcStatus {
+        self.last_error = CString::new(message).unwrap_or_default();
+        status
+    }
+}
+
+/// Result of calc_eval.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalcStatus {
+    Ok = 0,
+    NullPointer = 1,
+    InvalidUtf8 = 2,
+    EvalError = 3,
+}
+
+/// Creates a calculator with empty memory; release it with calc_free.
+#[no_mangle]
+pub extern "C" fn calc_new() -> *mut CalcHandle {
+    Box::into_raw(Box::new(CalcHandle { calculator: Calculator::new(), last_error: CString::default() }))
+}
+
+/// Evaluates one line, as typed at the calculator prompt, and stores the
+/// value in *result on CALC_STATUS_OK. A complex result (sqrt(-1)), a
+/// quantity with units (5 km) or a matrix fails with CALC_STATUS_EVAL_ERROR.
+///
+/// # Safety
+///
+/// `handle` must come from calc_new and not have been freed, `expression`
+/// must be NULL or a NUL-terminated string, and `result` must be NULL or
+/// point to a writable double.
+#[no_mangle]
+pub unsafe extern "C" fn calc_eval(handle: *mut CalcHandle, expression: *const c_char, result: *mut f64) -> CalcStatus {
+    let Some(handle) = handle.as_mut() else {
+        return CalcStatus::NullPointer;
+    };
+    if expression.is_null() || result.is_null() {
+        return handle.fail(CalcStatus::NullPointer, "expression or result is NULL");
+    }
+    let Ok(line) = CStr::from_ptr(expression).to_str() else {
+        return handle.fail(CalcStatus::InvalidUtf8, "expression is not valid UTF-8");
+    };
+    match handle.calculator.evaluate(line) {
+        Ok(value) => match value.as_real() {
+            Some(real) => {
+                *result = real;
+                handle.last_error = CString::default();
+                CalcStatus::Ok
+            }
+            None => {
+                let kind = match value {
+                    Value::Quantity(_) => "quantity",
+                    Value::Matrix(_) => "matrix",
+                    _ => "complex",
+                };
+                handle.fail(CalcStatus::EvalError, format!("{} result {} is not a double", kind, value))
+            }
+        },
+        Err(error) => handle.fail(CalcStatus::EvalError, error.to_string()),
+    }
+}
+
+/// The message for the last failed calc_eval on this handle ("" after a
+/// success, NULL for a NULL handle). It stays valid until the next call that
+/// takes the handle.
+///
+/// # Safety
+///
+/// `handle` must be NULL or come from calc_new and not have been freed.
+#[no_mangle]
+pub unsafe extern "C" fn calc_last_error(handle: *const CalcHandle) -> *const c_char {
+    match handle.as_ref() {
+        Some(handle) => handle.last_error.as_ptr(),
+        None => std::ptr::null(),
+    }
+}
+
+/// Releases a calculator; NULL is ignored.
+///
+/// # Safety
+///
+/// `handle` must be NULL or come from calc_new, and must not be used again.
+#[no_mangle]
+pub unsafe extern "C" fn calc_free(handle: *mut CalcHandle) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
@@ -0,0 +1,740 @@
+// Calculator REPL
+//
+// A feature-rich calculator with:
+// - Operators: +, -, *, /, % (modulo), ** or ^ (power), postfix ! (factorial)
+// - Bitwise: & (and), | (or), xor, << and >> (shifts), ~ (not), on whole
+//   numbers within 128 bits; they bind looser than + and -, | loosest, as in
+//   Python: 1 + 2 << 3 is 24
+// - Comparison: == != < <= > >= give 1 or 0 and bind looser than the
+//   bitwise operators: 2 + 2 == 4 is 1, 5 km > 300 m is 1
+// - Conditionals: if(condition, then, else) is then unless the condition is
+//   0, and evaluates only that branch: if(m0 == 0, 0, 1/m0)
+// - Sums and products: sum(1/k^2, k, 1, 1000) and prod(k, k, 1, 5) evaluate
+//   an expression for each whole k in a range
+// - Derivatives: diff(x^3, x, 2) → 12, numerically by a central difference,
+//   and integrals: integrate(x^2, x, 0, 3) → 9 by adaptive Simpson's rule, to
+//   within the tolerance setting
+// - Equations: solve(x^2 - 2, x, 1) → 1.414..., a zero near the guess, and
+//   quadratic(1, -3, 2) → [1, 2], both roots of x^2 - 3x + 2 as a row
+// - Functions: sin, cos, tan, asin, acos, atan, ln, log2, log10, exp, sqrt,
+//             round, floor, ceil, abs, factorial; ncr(n, k) and npr(n, k)
+//             (combinations and permutations) take two arguments
+// - Constants: pi, e, phi (golden ratio), tau (2π), sqrt2, sqrt3
+// - Literals: decimal with an optional exponent (6.022e23, 1E-9), and 0xFF,
+//   0b1010, 0o755 for hex, binary and octal (with _ between digits allowed);
+//   'base 16' (or 2, 8) shows whole results the same way
+// - Complex numbers: i, alone or after a number (2i); see value.rs
+// - Units: 5 km + 300 m → 5.3 km, and 'in' (or 'to') converts: 100 mph in
+//   km/h; see units.rs
+// - Matrices: [[1, 2], [3, 4]], with + - * and whole powers, and det, inv and
+//   transpose; see matrix.rs
+// - Exact mode: 'mode exact' keeps integers and fractions exact with big
+//   integers (2^200, factorial(50), 1/3 + 1/6 → 1/2); see bignum.rs. 'frac'
+//   and 'dec' switch modes and show the last result as a fraction or decimal
+// - Memory: m0-m9 for storage, c0-c9 to clear, 'clear' for last result;
+//   'store rate' keeps the last result under a name of its own, used as rate
+//   in expressions, 'forget rate' drops it and 'mem' lists the names;
+//   rate = 0.05 stores a value directly
+// - Statements: a = 2; b = 3; a * b runs left to right and answers with the
+//   last result, or every one with the echo_statements setting
+// - Special: _ (last result), parentheses for grouping
+// - Errors: a syntax error is shown with the line and a caret under the spot
+//   where it was found (parser.rs)
+// - Commands: help or ?, history, q/quit/exit (the shared loop in text::repl)
+// - Editing: in a terminal the arrow keys edit the line and recall history,
+//   and Tab completes functions, constants, memory slots, commands and
+//   setting names (sq<Tab> offers sqrt, sqrt2, sqrt3)
+// - Sessions: memory, _ and history are saved to session.json on exit and
+//   restored at startup; 'save' and 'load' do it on demand (session.rs)
+// - Settings: angle mode, precision, color, history size, output format,
+//   number mode and the others 'set' lists, loaded from calculator.toml and
+//   changed with set (settings.rs)
+//
+// Usage examples:
+//   2 + 3 * 4        → 14
+//   sin(pi/2)        → 1
+//   _ * 2            → 2 (uses last result)
+//   m0               → saves last result to m0
+//   sqrt(m0)         → uses value from m0
+//   store rate       → saves last result as rate
+//   round(pi * 100) / 100  → 3.14
+//   0xFF + 0b1       → 256
+//   0xF0 | 0b1010 & ~0b10 → 248
+//   5! / ncr(5, 2)   → 12
+//   0.1 + 0.2 > 0.3  → 1
+//   (1+2i)*(3-i)     → 5+5i
+//   sqrt(-4)         → 2i
+//   100 mph in km/h  → 160.9344 km/h
+//   det([[1, 2], [3, 4]]) → -2
+//   mode exact, then 2^100 → 1267650600228229401496703205376
+//
+// Outside the REPL, Calculator::evaluate runs one line against a calculator's
+// memory and last result, and eval() runs a single expression on a fresh one;
+// both return a Value: real, complex, exact in exact mode, with units, or a
+// matrix. Underneath, parser::parse turns an expression into an Ast and
+// evaluator::eval evaluates it against a Context (memory, _ and settings), for
+// tools that embed the engine without the REPL (repl.rs). All of them fail
+// with a CalcError, so callers can tell a division by zero from a typo by
+// matching on the variant, or on its ErrorKind (lex, parse, unknown
+// identifier, domain, division by zero), rather than the message.
+//
+// run_lines() answers a whole script as the REPL would, and server::serve
+// answers lines from TCP clients. With the "ffi" feature, ffi exposes the same
+// to C (calc_new, calc_eval, calc_free) through include/rustnotes_calc.h. With
+// "trace", each line's tokens and every operation the evaluator applies are
+// logged through the log crate at trace level.
+
+// Log a parse or evaluation step with the "trace" feature; otherwise
+// nothing is evaluated
+#[cfg(feature = "trace")]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        log::trace!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
+
+pub mod bignum;
+pub mod evaluator;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod matrix;
+pub mod parser;
+mod repl;
+pub mod server;
+pub mod session;
+pub mod settings;
+pub mod units;
+pub mod value;
+
+use evaluator::Context;
+use parser::{Ast, Statement};
+use rustnotes_algorithms::math::linear_algebra::LinAlgError;
+use rustnotes_algorithms::text::json::JsonValue;
+use settings::Settings;
+use std::fmt;
+use value::Value;
+
+pub use repl::{demo, load_settings, repl, run_lines, run_repl};
+
+#[derive(Debug)]
+enum InputType {
+    MemorySave(usize),
+    MemoryClear(usize),
+    Store(String),
+    Forget(String),
+    ListMemory,
+    Expression,
+}
+
+// Why a line failed to parse or evaluate; Display gives the message the REPL
+// prints, and kind() groups the variants
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalcError {
+    // A run of digits and dots that is not a number, such as "1.2.3"
+    InvalidNumber(String),
+    // A character that starts no token, such as $
+    InvalidCharacter(char),
+    DivisionByZero,
+    ModuloByZero,
+    UnknownFunction(String),
+    UnknownConstant(String),
+    // A name nothing has been stored under
+    UnknownVariable(String),
+    // store with a word that already means something, such as sin or m0
+    InvalidName(String),
+    // _n reaching back past the fresh _ of 0, or past _10
+    NoResult(usize),
+    // A known function name not followed by "("
+    MissingParentheses(String),
+    // A function called with the wrong number of arguments, such as ncr(5)
+    ArgumentCount { function: String, expected: usize },
+    // An argument outside the function's domain, such as ln(0)
+    Domain { function: &'static str, requirement: &'static str },
+    MissingClosingParen,
+    MissingClosingBracket,
+    UnexpectedEnd,
+    // Something other than a value where one was expected, such as "2 * )"
+    UnexpectedToken,
+    // A complete expression followed by more input, such as "2 3"
+    TrailingTokens,
+    // Units of different dimensions added or converted, such as "2 km + 3 s";
+    // a plain number has none
+    IncompatibleUnits(String, String),
+    // Matrices of the wrong shape for an operation, or a singular one inverted
+    LinearAlgebra(LinAlgError),
+    // A syntax error and the byte offset in the line where it was found
+    At { offset: usize, error: Box<CalcError> },
+}
+
+impl fmt::Display for CalcError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CalcError::InvalidNumber(text) => write!(f, "Invalid number '{}'", text),
+            CalcError::InvalidCharacter(ch) => write!(f, "Invalid character '{}'", ch),
+            CalcError::DivisionByZero => write!(f, "Division by zero"),
+            CalcError::ModuloByZero => write!(f, "Modulo by zero"),
+            CalcError::UnknownFunction(name) => {
+                let binders = parser::BINDERS.iter().map(|(binder, _)| binder);
+                let functions = parser::FUNCTIONS.iter().chain(binders).copied().collect::<Vec<_>>();
+                write!(f, "Unknown function '{}'. Available functions: {}", name, functions.join(", "))
+            }
+            CalcError::UnknownConstant(name) => write!(f, "Unknown constant: {}", name),
+            CalcError::UnknownVariable(name) => write!(f, "Unknown name: {}", name),
+            CalcError::InvalidName(name) => write!(f, "Cannot store to '{}': not a free name", name),
+            CalcError::NoResult(n) => write!(f, "No result _{} (_ to _{} are kept)", n, evaluator::RESULTS),
+            CalcError::MissingParentheses(name) => write!(f, "Function '{}' requires parentheses: {}(...)", name, name),
+            CalcError::ArgumentCount { function, expected: 1 } => write!(f, "Function '{}' takes 1 argument", function),
+            CalcError::ArgumentCount { function, expected } => {
+                write!(f, "Function '{}' takes {} arguments", function, expected)
+            }
+            CalcError::Domain { function, requirement } => write!(f, "{} requires {}", function, requirement),
+            CalcError::MissingClosingParen => write!(f, "Expected closing parenthesis"),
+            CalcError::MissingClosingBracket => write!(f, "Expected closing bracket"),
+            CalcError::UnexpectedEnd => write!(f, "Unexpected end of expression"),
+            CalcError::UnexpectedToken => {
+                write!(f, "Expected number, unit, function, constant, memory location, _, matrix, or opening parenthesis")
+            }
+            CalcError::TrailingTokens => write!(f, "Unexpected tokens at end of expression"),
+            CalcError::IncompatibleUnits(left, right) => write!(f, "Incompatible units: {} and {}", left, right),
+            CalcError::LinearAlgebra(error) => write!(f, "Linear algebra: {}", error),
+            // The position is shown by caret(), under the line itself
+            CalcError::At { error, .. } => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CalcError {}
+
+// What kind of mistake a CalcError is, for callers that want to tell a line
+// that is not an expression from one that has no value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    // Text that is no token, such as 1.2.3 or $
+    Lex,
+    // Tokens the grammar does not allow, such as "2 * )" or "(1"
+    Parse,
+    // A function or constant the calculator does not know
+    UnknownIdentifier,
+    // An operation with no value for its operands: ln(0), ncr(5), km + s, a
+    // singular matrix inverted
+    Domain,
+    // Division or modulo by zero
+    DivisionByZero,
+}
+
+impl CalcError {
+    // The byte offset of a syntax error in its line
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            CalcError::At { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    // The error with its position moved `by` bytes on, for a statement that
+    // starts that far into its line
+    fn shifted(self, by: usize) -> CalcError {
+        match self {
+            CalcError::At { offset, error } => CalcError::At { offset: offset + by, error },
+            error => error,
+        }
+    }
+
+    // The error without its position, to match on
+    pub fn inner(&self) -> &CalcError {
+        match self {
+            CalcError::At { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self.inner() {
+            CalcError::InvalidNumber(_) | CalcError::InvalidCharacter(_) => ErrorKind::Lex,
+            CalcError::MissingParentheses(_)
+            | CalcError::MissingClosingParen
+            | CalcError::MissingClosingBracket
+            | CalcError::UnexpectedEnd
+            | CalcError::UnexpectedToken
+            | CalcError::TrailingTokens
+            | CalcError::InvalidName(_) => ErrorKind::Parse,
+            CalcError::UnknownFunction(_)
+            | CalcError::UnknownConstant(_)
+            | CalcError::UnknownVariable(_)
+            | CalcError::NoResult(_) => ErrorKind::UnknownIdentifier,
+            CalcError::DivisionByZero | CalcError::ModuloByZero => ErrorKind::DivisionByZero,
+            CalcError::ArgumentCount { .. }
+            | CalcError::Domain { .. }
+            | CalcError::IncompatibleUnits(..)
+            | CalcError::LinearAlgebra(_)
+            | CalcError::At { .. } => ErrorKind::Domain,
+        }
+    }
+
+    // The line, indented, with a caret under the error on the next line; None
+    // without a position
+    pub fn caret(&self, input: &str) -> Option<String> {
+        let before = input.get(..self.offset()?).unwrap_or(input);
+        // A tab stays a tab so the caret lines up however wide tabs are
+        let padding: String = before.chars().map(|ch| if ch == '\t' { '\t' } else { ' ' }).collect();
+        Some(format!("  {}\n  {}^", input.trim_end(), padding))
+    }
+}
+
+impl From<LinAlgError> for CalcError {
+    fn from(error: LinAlgError) -> Self {
+        CalcError::LinearAlgebra(error)
+    }
+}
+
+pub struct Calculator {
+    context: Context,
+}
+
+impl Default for Calculator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Calculator {
+    pub fn new() -> Self {
+        Self::with_settings(Settings::default())
+    }
+
+    pub fn with_settings(settings: Settings) -> Self {
+        Self { context: Context::new(settings) }
+    }
+
+    pub fn settings(&self) -> &Settings {
+        &self.context.settings
+    }
+
+    pub fn settings_mut(&mut self) -> &mut Settings {
+        &mut self.context.settings
+    }
+
+    // The memory, last result and settings that lines are evaluated against
+    pub fn context(&self) -> &Context {
+        &self.context
+    }
+
+    pub fn context_mut(&mut self) -> &mut Context {
+        &mut self.context
+    }
+
+    // Every name an expression can use: functions, constants, units, the
+    // operator words, the memory slots and the stored names, for tab completion
+    pub fn names(&self) -> Vec<String> {
+        let words = parser::FUNCTIONS.iter().chain(parser::CONSTANTS.iter()).chain(["xor", "in", "to", "if"].iter());
+        let words = words.chain(parser::BINDERS.iter().map(|(binder, _)| binder));
+        let words = words.copied().chain(units::names()).map(|name| name.to_string());
+        let words = words.chain((0..self.context.memory.len()).map(|slot| format!("m{}", slot)));
+        words.chain(self.context.variables.keys().cloned()).collect()
+    }
+
+    // Keep the last result under `name`
+    pub fn store(&mut self, name: &str) -> Result<(), CalcError> {
+        check_name(name)?;
+        self.context.variables.insert(name.to_string(), self.context.last_result.clone());
+        Ok(())
+    }
+
+    // Drop the value stored under `name`, returning it
+    pub fn forget(&mut self, name: &str) -> Result<Value, CalcError> {
+        self.context.variables.remove(name).ok_or_else(|| CalcError::UnknownVariable(name.to_string()))
+    }
+
+    fn is_memory_save(&self, input: &str) -> Option<usize> {
+        let input = input.trim();
+        if input.starts_with('m') && input.len() == 2 {
+            if let Some(digit) = input.chars().nth(1).unwrap().to_digit(10) {
+                if digit <= 9 {
+                    return Some(digit as usize);
+                }
+            }
+        }
+        None
+    }
+
+    fn is_memory_clear(&self, input: &str) -> Option<usize> {
+        let input = input.trim();
+        if input.starts_with('c') && input.len() == 2 {
+            if let Some(digit) = input.chars().nth(1).unwrap().to_digit(10) {
+                if digit <= 9 {
+                    return Some(digit as usize);
+                }
+            }
+        }
+        None
+    }
+
+
+    fn classify_input(&self, input: &str) -> InputType {
+        if let Some(idx) = self.is_memory_save(input) {
+            InputType::MemorySave(idx)
+        } else if let Some(idx) = self.is_memory_clear(input) {
+            InputType::MemoryClear(idx)
+        } else {
+            match input.split_whitespace().collect::<Vec<_>>().as_slice() {
+                ["store", name] => InputType::Store(name.to_string()),
+                ["forget", name] => InputType::Forget(name.to_string()),
+                ["mem"] => InputType::ListMemory,
+                _ => InputType::Expression,
+            }
+        }
+    }
+
+    // Answer one line as the REPL does, each statement between semicolons in
+    // turn: m0 saves and c0 clears a memory slot, store, forget and mem manage
+    // the named ones, name = expression assigns, and anything else is
+    // evaluated and formatted (and colored) per the settings. A syntax error
+    // is followed by the line with a caret under it. With the json setting,
+    // Ok and Err both hold the answer as a JSON object
+    pub fn respond(&mut self, input: &str) -> Result<String, String> {
+        let response = self.answer(input);
+        if !self.context.settings.json {
+            return response;
+        }
+        let (result, error) = match &response {
+            Ok(text) => (JsonValue::String(text.clone()), JsonValue::Null),
+            Err(message) => (JsonValue::Null, JsonValue::String(message.clone())),
+        };
+        let members = [("input", JsonValue::String(input.trim().to_string())), ("result", result), ("error", error)];
+        let json = JsonValue::Object(members.into_iter().map(|(key, value)| (key.to_string(), value)).collect());
+        match response {
+            Ok(_) => Ok(json.to_string()),
+            Err(_) => Err(json.to_string()),
+        }
+    }
+
+    // Each statement of the line in turn, stopping at the first error; the
+    // statements before it have still taken effect
+    fn answer(&mut self, input: &str) -> Result<String, String> {
+        let mut answers = Vec::new();
+        for (start, statement) in statements(input) {
+            match self.answer_statement(statement) {
+                Ok(answer) => answers.push(answer),
+                Err(error) => {
+                    let error = error.shifted(start);
+                    let mut message = self.context.settings.paint(&error.to_string(), 31);
+                    // A JSON answer has the message alone
+                    if let Some(caret) = error.caret(input).filter(|_| !self.context.settings.json) {
+                        message = format!("{}\n{}", message, caret);
+                    }
+                    return Err(message);
+                }
+            }
+        }
+        if self.context.settings.echo_statements {
+            Ok(answers.join("\n"))
+        } else {
+            Ok(answers.pop().unwrap_or_default())
+        }
+    }
+
+    fn answer_statement(&mut self, input: &str) -> Result<String, CalcError> {
+        match self.classify_input(input) {
+            InputType::MemorySave(idx) => {
+                self.context.memory[idx] = self.context.last_result.clone();
+                Ok(format!("Saved {} to m{}", self.context.settings.format_value(&self.context.last_result), idx))
+            }
+            InputType::MemoryClear(idx) => {
+                self.context.memory[idx] = Value::Real(0.0);
+                Ok(format!("Cleared m{}", idx))
+            }
+            InputType::Store(name) => {
+                self.store(&name)?;
+                Ok(format!("Saved {} to {}", self.context.settings.format_value(&self.context.last_result), name))
+            }
+            InputType::Forget(name) => {
+                self.forget(&name)?;
+                Ok(format!("Forgot {}", name))
+            }
+            InputType::ListMemory if self.context.variables.is_empty() => Ok("No stored names".to_string()),
+            InputType::ListMemory => {
+                let settings = &self.context.settings;
+                let lines = self.context.variables.iter().map(|(name, value)| format!("{} = {}", name, settings.format_value(value)));
+                Ok(lines.collect::<Vec<_>>().join("\n"))
+            }
+            InputType::Expression => {
+                let result = self.evaluate_statement(input)?;
+                Ok(self.context.settings.paint(&self.context.settings.format_value(&result), 32))
+            }
+        }
+    }
+
+    // Evaluate one line, its statements left to right ("m0" saves the last
+    // result, "a = 2" stores 2 as a), updating _ after each; the value is the
+    // last statement's
+    pub fn evaluate(&mut self, input: &str) -> Result<Value, CalcError> {
+        let mut result = Value::Real(0.0);
+        for (start, statement) in statements(input) {
+            result = self.evaluate_statement(statement).map_err(|error| error.shifted(start))?;
+        }
+        Ok(result)
+    }
+
+    fn evaluate_statement(&mut self, input: &str) -> Result<Value, CalcError> {
+        // Check if it's a memory save command (just m0, m1, etc.)
+        if let Some(mem_idx) = self.is_memory_save(input) {
+            self.context.memory[mem_idx] = self.context.last_result.clone();
+            return Ok(self.context.last_result.clone());
+        }
+
+        match parser::parse_statement(input, self.context.settings.lenient)? {
+            Statement::Expression(ast) => evaluator::eval(&ast, &mut self.context),
+            Statement::Assign(name, ast) => {
+                let start = input.len() - input.trim_start().len();
+                check_name(&name).map_err(|error| CalcError::At { offset: start, error: Box::new(error) })?;
+                let value = evaluator::eval(&ast, &mut self.context)?;
+                self.context.variables.insert(name, value.clone());
+                Ok(value)
+            }
+        }
+    }
+}
+
+// The statements of a line with the byte offset each starts at. Blank ones,
+// such as after a trailing semicolon, are left out, but a line with nothing
+// else (blank, or only separators such as ";;") is one blank statement at its
+// end, as an empty line would be
+fn statements(input: &str) -> Vec<(usize, &str)> {
+    let mut statements = Vec::new();
+    let mut start = 0;
+    for statement in input.split(';') {
+        if !statement.trim().is_empty() {
+            statements.push((start, statement));
+        }
+        start += statement.len() + 1;
+    }
+    if statements.is_empty() {
+        statements.push((input.len(), ""));
+    }
+    statements
+}
+
+// A name store and assignments can use: one that parses as a stored name, so
+// not a function, constant, unit or memory slot, and not one of the commands
+fn check_name(name: &str) -> Result<(), CalcError> {
+    let free = matches!(parser::parse(name), Ok(Ast::Variable(parsed)) if parsed == name);
+    if !free || ["store", "forget", "mem"].contains(&name) {
+        return Err(CalcError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+// Evaluate a single expression with empty memory
+pub fn eval(input: &str) -> Result<Value, CalcError> {
+    Calculator::new().evaluate(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use settings::NumberMode;
+
+    fn real(input: &str) -> f64 {
+        let value = eval(input).unwrap_or_else(|error| panic!("{}: {}", input, error));
+        value.as_real().unwrap_or_else(|| panic!("{}: {:?} is not real", input, value))
+    }
+
+    fn error(input: &str) -> CalcError {
+        eval(input).expect_err(input)
+    }
+
+    // Each line answered in turn by one calculator, as the REPL shows it
+    fn answers(calculator: &mut Calculator, lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| calculator.respond(line).unwrap_or_else(|message| panic!("{}: {}", line, message))).collect()
+    }
+
+    fn exact() -> Calculator {
+        let mut calculator = Calculator::new();
+        calculator.settings_mut().number_mode = NumberMode::Exact;
+        calculator
+    }
+
+    #[test]
+    fn operator_precedence() {
+        assert_eq!(real("2 + 3 * 4"), 14.0);
+        assert_eq!(real("(2 + 3) * 4"), 20.0);
+        assert_eq!(real("7 - 2 - 1"), 4.0);
+        assert_eq!(real("10 % 4 * 3"), 6.0);
+        // Power is right-associative, and a leading minus binds tighter
+        assert_eq!(real("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(real("2 ** -1"), 0.5);
+        assert_eq!(real("-2 ^ 2"), 4.0);
+        assert_eq!(real("2 * 3!"), 12.0);
+        // Bitwise operators bind looser than + and -, | loosest
+        assert_eq!(real("1 + 2 << 3"), 24.0);
+        assert_eq!(real("6 | 3 & 5"), 7.0);
+        assert_eq!(real("0xF0 | 0b1010 & ~0b10"), 248.0);
+        // Comparisons bind looser still
+        assert_eq!(real("2 + 2 == 4"), 1.0);
+        assert_eq!(real("1 | 2 == 3"), 1.0);
+        assert_eq!(real("0.1 + 0.2 > 0.3"), 1.0);
+        assert_eq!(real("if(1 - 1, 1/0, 5)"), 5.0);
+    }
+
+    #[test]
+    fn syntax_errors_carry_their_offset() {
+        let cases: [(&str, usize, CalcError); 6] = [
+            ("1 +", 3, CalcError::UnexpectedToken),
+            ("2 * )", 4, CalcError::UnexpectedToken),
+            ("(1", 2, CalcError::MissingClosingParen),
+            ("1.2.3 + 1", 0, CalcError::InvalidNumber("1.2.3".to_string())),
+            ("2 $ 3", 2, CalcError::InvalidCharacter('$')),
+            ("sin 2", 0, CalcError::MissingParentheses("sin".to_string())),
+        ];
+        for (input, offset, inner) in cases.iter() {
+            let error = error(input);
+            assert_eq!(error.offset(), Some(*offset), "{}", input);
+            assert_eq!(error.inner(), inner, "{}", input);
+        }
+        // A later statement's offset counts from the start of the line
+        assert_eq!(error("1; 2 *").offset(), Some(6));
+        // Errors found while evaluating have no position
+        assert_eq!(error("1/0").offset(), None);
+    }
+
+    #[test]
+    fn error_kinds() {
+        assert_eq!(error("1.2.3").kind(), ErrorKind::Lex);
+        assert_eq!(error("2 $ 3").kind(), ErrorKind::Lex);
+        assert_eq!(error("2 * )").kind(), ErrorKind::Parse);
+        assert_eq!(error("foo(2)").kind(), ErrorKind::UnknownIdentifier);
+        assert_eq!(error("rate").kind(), ErrorKind::UnknownIdentifier);
+        assert_eq!(error("ln(0)").kind(), ErrorKind::Domain);
+        assert_eq!(error("ncr(5)").kind(), ErrorKind::Domain);
+        assert_eq!(error("2 km + 3 s").kind(), ErrorKind::Domain);
+        assert_eq!(error("1/0").kind(), ErrorKind::DivisionByZero);
+        assert_eq!(error("5 % 0").inner(), &CalcError::ModuloByZero);
+    }
+
+    #[test]
+    fn caret_points_at_the_error() {
+        assert_eq!(error("2 * )").caret("2 * )").as_deref(), Some("  2 * )\n      ^"));
+        assert_eq!(error("1 +").caret("1 +").as_deref(), Some("  1 +\n     ^"));
+        // A tab stays a tab so the caret lines up
+        assert_eq!(error("\t1 +").caret("\t1 +").as_deref(), Some("  \t1 +\n  \t   ^"));
+        assert_eq!(error("1/0").caret("1/0"), None);
+
+        let mut calculator = Calculator::new();
+        let message = calculator.respond("1; 2 *").unwrap_err();
+        assert_eq!(message, format!("{}\n  1; 2 *\n        ^", CalcError::UnexpectedToken));
+    }
+
+    #[test]
+    fn exact_mode() {
+        let mut calculator = exact();
+        let lines = ["1/3 + 1/6", "2^100", "factorial(25)", "(2/3)^3", "2^-2", "1/3 + 0.5", "0.1 + 0.2", "ncr(30, 15)"];
+        assert_eq!(answers(&mut calculator, &lines),
+                   ["1/2", "1267650600228229401496703205376", "15511210043330985984000000", "8/27", "1/4", "5/6",
+                    "3/10", "155117520"]);
+        assert!(matches!(calculator.evaluate("1/3 * 3"), Ok(Value::Exact(_))));
+        // Operations with no exact result fall back to f64
+        assert!(matches!(calculator.evaluate("sqrt(2)"), Ok(Value::Real(_))));
+        assert!(matches!(calculator.evaluate("1/3 + pi"), Ok(Value::Real(_))));
+        assert_eq!(calculator.evaluate("0^-1").unwrap_err().kind(), ErrorKind::DivisionByZero);
+        assert_eq!(calculator.evaluate("1/(1/2 - 2/4)").unwrap_err().kind(), ErrorKind::DivisionByZero);
+        // The same line in float mode rounds
+        assert_eq!(real("0.1 + 0.2"), 0.1 + 0.2);
+    }
+
+    #[test]
+    fn units() {
+        let mut calculator = Calculator::new();
+        let lines = ["5 km + 300 m", "100 mph in km/h", "1 km in m", "2 m * 3 m", "6 m / 2 s", "(3 m)^2", "sqrt(4 m^2)",
+                     "5 km > 300 m"];
+        assert_eq!(answers(&mut calculator, &lines),
+                   ["5.3 km", "160.9344 km/h", "1000 m", "6 m^2", "3 m/s", "9 m^2", "2 m", "1"]);
+        // Units that cancel leave a plain number
+        assert_eq!(real("1 km / 1 m"), 1000.0);
+        assert_eq!(error("2 km + 3 s").inner(), &CalcError::IncompatibleUnits("km".to_string(), "s".to_string()));
+        assert_eq!(error("1 km in s").kind(), ErrorKind::Domain);
+        assert_eq!(error("sqrt(-4 m^2)").kind(), ErrorKind::Domain);
+        assert_eq!(error("m^100 * m^100").kind(), ErrorKind::Domain);
+    }
+
+    #[test]
+    fn matrices() {
+        let mut calculator = Calculator::new();
+        let lines = ["[[1, 2], [3, 4]] * [[5, 6], [7, 8]]", "[[1, 2], [3, 4]]^2", "[[1, 2], [3, 4]] * 2",
+                     "transpose([[1, 2, 3]])", "det([[1, 2], [3, 4]])", "inv([[2, 0], [0, 4]])"];
+        assert_eq!(answers(&mut calculator, &lines),
+                   ["[19 22]\n[43 50]", "[ 7 10]\n[15 22]", "[2 4]\n[6 8]", "[1]\n[2]\n[3]", "-2", "[ 0.5    0]\n[   0 0.25]"]);
+        assert!(matches!(error("inv([[1, 2], [2, 4]])"), CalcError::LinearAlgebra(_)));
+        assert!(matches!(error("[[1, 2]] + [[1, 2, 3]]"), CalcError::LinearAlgebra(_)));
+        assert_eq!(error("[[1, 2], [3, 4]").inner(), &CalcError::MissingClosingBracket);
+    }
+
+    #[test]
+    fn earlier_results() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.evaluate("_"), Ok(Value::Real(0.0)));
+        assert_eq!(calculator.evaluate("_3").unwrap_err().inner(), &CalcError::NoResult(3));
+        for n in 1..=12 {
+            calculator.evaluate(&n.to_string()).unwrap();
+        }
+        assert_eq!(calculator.evaluate("_2 * 100 + _3 * 10 + _"), Ok(Value::Real(1212.0)));
+        // That answer is now _, so the 12 moved back to _2
+        assert_eq!(calculator.evaluate("_2"), Ok(Value::Real(12.0)));
+        assert_eq!(calculator.evaluate("_10"), Ok(Value::Real(5.0)));
+        assert_eq!(calculator.evaluate("_11").unwrap_err().inner(), &CalcError::NoResult(11));
+    }
+
+    #[test]
+    fn statements_and_assignment() {
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.evaluate("a = 2; b = 3; a * b"), Ok(Value::Real(6.0)));
+        assert_eq!(calculator.evaluate("a = a + 1; a"), Ok(Value::Real(3.0)));
+        assert_eq!(calculator.evaluate("_"), Ok(Value::Real(3.0)));
+        assert_eq!(calculator.evaluate("sin = 3").unwrap_err().inner(), &CalcError::InvalidName("sin".to_string()));
+        assert_eq!(calculator.evaluate("m0 = 3").unwrap_err().kind(), ErrorKind::Parse);
+        // Statements before an error have still taken effect
+        assert!(calculator.evaluate("c = 4; 1/0").is_err());
+        assert_eq!(calculator.evaluate("c"), Ok(Value::Real(4.0)));
+
+        assert_eq!(answers(&mut calculator, &["7", "m0", "store rate", "rate * m0", "forget rate", "mem", "1;", "1;; 2"]),
+                   ["7", "Saved 7 to m0", "Saved 7 to rate", "49", "Forgot rate", "a = 3\nb = 3\nc = 4", "1", "2"]);
+        calculator.settings_mut().echo_statements = true;
+        assert_eq!(answers(&mut calculator, &["x = 1; x + 1"]), ["1\n2"]);
+    }
+
+    #[test]
+    fn set_lines_up_every_value() {
+        let (mut output, mut errors) = (Vec::new(), Vec::new());
+        run_lines(&mut Calculator::new(), "set".as_bytes(), &mut output, &mut errors).unwrap();
+        let listing = String::from_utf8(output).unwrap();
+        let columns: Vec<usize> = listing.lines().map(|line| line.rfind(' ').unwrap()).collect();
+        assert_eq!(columns.len(), settings::KEYS.len());
+        assert!(columns.iter().all(|&column| column == "echo_statements".len()), "{}", listing);
+    }
+
+    #[test]
+    fn binders() {
+        assert!((real("sum(1/k^2, k, 1, 1000)") - 1.6439345666815615).abs() < 1e-12);
+        assert_eq!(real("prod(k, k, 1, 5)"), 120.0);
+        assert_eq!(real("sum(k, k, 1, 0)"), 0.0);
+        assert!((real("diff(x^3, x, 2)") - 12.0).abs() < 1e-6);
+        assert!((real("integrate(x^2, x, 0, 3)") - 9.0).abs() < 1e-9);
+        assert!((real("solve(x^2 - 2, x, 1)") - 2f64.sqrt()).abs() < 1e-9);
+        // The bound name is local to the binder
+        assert_eq!(error("sum(k, k, 1, 3) + k").inner(), &CalcError::UnknownVariable("k".to_string()));
+        let mut calculator = Calculator::new();
+        assert_eq!(calculator.evaluate("k = 10; sum(k, k, 1, 3) + k"), Ok(Value::Real(16.0)));
+
+        assert_eq!(error("sum(k, 1, 2)").inner(), &CalcError::ArgumentCount { function: "sum".to_string(), expected: 4 });
+        assert_eq!(error("sum(k, 2, 1, 3)").kind(), ErrorKind::Domain);
+        assert_eq!(error("solve(x^2 + 1, x, 0)").kind(), ErrorKind::Domain);
+    }
+}
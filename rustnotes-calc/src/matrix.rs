@@ -0,0 +1,126 @@
+// Calculator Matrices
+//
+// Matrix literals and the matrix operations of the calculator, on top of
+// math::linear_algebra's Matrix:
+//
+//   [[1, 2], [3, 4]] * [[5], [6]]   → [[17], [39]]
+//   det([[1, 2], [3, 4]])           → -2
+//   inv([[2, 0], [0, 4]])           → [[0.5, 0], [0, 0.25]]
+//   transpose([1, 2, 3])            → a 3x1 column
+//   [[1, 1], [1, 0]]^10             → Fibonacci numbers
+//
+// Design choices:
+// - A literal is a list of items in brackets: real numbers make one row
+//   ([1, 2, 3] is 1x3), rows make a matrix ([[1, 2], [3, 4]]), and any item may
+//   be an expression (m0, [1, 2] * 2), so a matrix can be built from memory
+// - + and - take two matrices of the same shape; * is the matrix product, or
+//   scales when one side is a number; / divides by a number only (A * inv(B)
+//   divides by a matrix); ^ takes whole powers of a square matrix, negative ones
+//   through the inverse
+// - det, inv and transpose take a matrix; abs, round, floor and ceil apply to
+//   each entry; every other function needs a number
+// - Shape mistakes and singular matrices are LinAlgErrors, reported as
+//   CalcError::LinearAlgebra rather than turned into NaN entries
+// - The REPL prints a matrix as right-aligned rows, each entry formatted as a
+//   real (settings.rs); Display gives the literal, so a result can be pasted
+//   back in
+// - Trade-off: entries are f64, so exact mode and units stop at a matrix; an
+//   exact inverse would need LU over Rationals
+
+use crate::value::Value;
+use crate::CalcError;
+use rustnotes_algorithms::math::linear_algebra::{LinAlgError, Matrix};
+
+// The matrix a bracketed literal denotes
+pub(crate) fn literal(items: Vec<Value>) -> Result<Value, CalcError> {
+    if items.is_empty() {
+        return Err(LinAlgError::Malformed("a matrix needs at least one entry".to_string()).into());
+    }
+    let not_entries = CalcError::Domain { function: "[...]", requirement: "real numbers, or rows of them" };
+    if items.iter().all(|item| matches!(item, Value::Matrix(_))) {
+        let mut rows = Vec::new();
+        for item in &items {
+            match item {
+                Value::Matrix(row) if row.rows() == 1 => rows.push(row.row(0).to_vec()),
+                _ => return Err(not_entries),
+            }
+        }
+        return Ok(Value::Matrix(Matrix::from_rows(rows)?));
+    }
+    let entries: Option<Vec<f64>> = items.iter().map(|item| item.clone().inexact().as_real()).collect();
+    let entries = entries.ok_or(not_entries)?;
+    Ok(Value::Matrix(Matrix::new(1, entries.len(), entries)?))
+}
+
+// `left op right` for + - * / when either side is a matrix; None when neither is
+pub(crate) fn binary(op: char, left: &Value, right: &Value) -> Option<Result<Value, CalcError>> {
+    let function = match op {
+        '+' => "+",
+        '-' => "-",
+        '*' => "*",
+        _ => "/",
+    };
+    let scalar = |value: &Value| {
+        value.clone().inexact().as_real().ok_or(CalcError::Domain { function, requirement: "real numbers and matrices" })
+    };
+    let result = match (left, right) {
+        (Value::Matrix(a), Value::Matrix(b)) => match op {
+            '+' => a.add(b),
+            '-' => a.sub(b),
+            '*' => a.mul(b),
+            _ => return Some(Err(CalcError::Domain { function, requirement: "a number on the right (A * inv(B) divides by B)" })),
+        },
+        (Value::Matrix(a), x) => match (op, scalar(x)) {
+            (_, Err(error)) => return Some(Err(error)),
+            ('*', Ok(x)) => Ok(a.scale(x)),
+            ('/', Ok(x)) => Ok(a.scale(1.0 / x)),
+            _ => return Some(Err(CalcError::Domain { function, requirement: "two matrices of the same shape" })),
+        },
+        (x, Value::Matrix(b)) => match (op, scalar(x)) {
+            (_, Err(error)) => return Some(Err(error)),
+            ('*', Ok(x)) => Ok(b.scale(x)),
+            ('/', _) => return Some(Err(CalcError::Domain { function, requirement: "a number on the right (inv(A) inverts A)" })),
+            _ => return Some(Err(CalcError::Domain { function, requirement: "two matrices of the same shape" })),
+        },
+        _ => return None,
+    };
+    Some(result.map(Value::Matrix).map_err(CalcError::from))
+}
+
+// A square matrix to a whole power; 0 gives the identity, negative powers
+// invert first
+pub(crate) fn power(base: &Matrix, exponent: &Value) -> Result<Value, CalcError> {
+    let n = exponent
+        .to_integer()
+        .ok_or(CalcError::Domain { function: "^", requirement: "a whole-number power of a matrix" })?;
+    if !base.is_square() {
+        return Err(LinAlgError::NotSquare { rows: base.rows(), cols: base.cols() }.into());
+    }
+    let mut square = if n < 0 { base.inverse()? } else { base.clone() };
+    let mut result = Matrix::identity(base.rows());
+    let mut n = n.unsigned_abs();
+    // Square and multiply, so A^1000 takes 10 squarings
+    while n > 0 {
+        if n & 1 == 1 {
+            result = result.mul(&square)?;
+        }
+        square = square.mul(&square)?;
+        n >>= 1;
+    }
+    Ok(Value::Matrix(result))
+}
+
+// det, inv and transpose
+pub(crate) fn function(name: &str, matrix: &Matrix) -> Result<Value, CalcError> {
+    match name {
+        "det" => Ok(Value::Real(matrix.determinant()?)),
+        "inv" => Ok(Value::Matrix(matrix.inverse()?)),
+        _ => Ok(Value::Matrix(matrix.transpose())),
+    }
+}
+
+// A function of each entry
+pub(crate) fn map(matrix: &Matrix, f: fn(f64) -> f64) -> Matrix {
+    let entries = (0..matrix.rows()).flat_map(|r| matrix.row(r).iter().map(|&x| f(x)));
+    Matrix::new(matrix.rows(), matrix.cols(), entries.collect()).expect("same shape")
+}
@@ -0,0 +1,660 @@
+// Calculator Parser
+//
+// The tokenizer and the recursive-descent parser: one line of text becomes an
+// Ast, which evaluator.rs evaluates against a Context. Neither step needs the
+// other, so a tool can parse once and evaluate many times, or inspect an
+// expression without evaluating it:
+//
+//   let ast = parse("2 * (m0 + 1)")?;
+//   let value = evaluator::eval(&ast, &mut context)?;
+//
+// A line holds statements separated by semicolons, which Calculator splits
+// apart; each is an expression or an assignment, name = expression:
+//
+//   a = 2; b = 3; a * b
+//
+// Precedence, loosest first:
+//
+//   in / to                    conversion, once per expression
+//   == != < <= > >=            comparison, 1 if it holds and 0 if not; once
+//                              per expression, so 1 < 2 < 3 is an error
+//                              rather than C's (1 < 2) < 3 or Python's chain
+//   |  xor  &  << >>           bitwise, as in Python
+//   +  -
+//   *  /  %
+//   5 km                       a value followed by units
+//   ^ or **                    right associative
+//   -  +  ~                    prefix
+//   !                          postfix factorial
+//   numbers, constants, units, m0-m9, names, _, f(...), if(...),
+//   sum(...), prod(...), diff(...), integrate(...), solve(...), [...], (...)
+//
+// Design choices:
+// - A literal keeps both its f64 and its exact value, so the same Ast is
+//   evaluated in either number mode: 0.1 is 1/10 in exact mode and the nearest
+//   f64 otherwise
+// - Syntax errors come from here (a missing parenthesis, an unknown function,
+//   trailing tokens) and everything else from the evaluator, all as a
+//   CalcError. A line with both reports the syntax error
+// - Every token keeps the byte offset where it starts, and a syntax error is
+//   a CalcError::At the token the parser stopped on, so the REPL can put a
+//   caret under it:
+//
+//     > (1 + 2))
+//     Error: Unexpected tokens at end of expression
+//       (1 + 2))
+//              ^
+// - "5 km" is the product of 5 and the unit km, so the Ast needs no node of
+//   its own for quantities; unary + leaves no node at all
+// - A character that starts no token ($, @, a letter outside ASCII) is an
+//   error at that character; parse_lenient skips such characters instead, for
+//   the lenient setting
+// - if(condition, then, else) looks like a call but is a node of its own, so
+//   the evaluator can leave the branch not taken alone. The binders, sum(1/k^2,
+//   k, 1, 10), prod, diff(x^3, x, 2), integrate and solve, are nodes of their
+//   own too: the expression is evaluated once for each value of k or x, and
+//   the variable is a name the parser checks for
+// - A word that is none of the above is the name of a stored value (store
+//   rate), unless "(" follows it: then it is an unknown function. Whether the
+//   name holds anything is up to the evaluator
+// - Trade-off: the parser rejects an unknown function name, but the evaluator
+//   checks the number of arguments, so an Ast built by hand is checked the
+//   same way as a parsed one
+
+use crate::bignum::Rational;
+use crate::units::Unit;
+use crate::CalcError;
+use rustnotes_algorithms::math::bigint::BigInt;
+use rustnotes_algorithms::text::lexer::Lexer;
+
+pub(crate) const FUNCTIONS: [&str; 22] = [
+    "sin", "cos", "tan", "asin", "acos", "atan", "ln", "log2", "log10", "exp", "sqrt",
+    "round", "floor", "ceil", "abs", "factorial", "ncr", "npr", "det", "inv", "transpose",
+    "quadratic",
+];
+
+// Functions of an expression in a variable, with their argument counts: the
+// expression is kept unevaluated and the variable is only a name
+pub(crate) const BINDERS: [(&str, usize); 5] = [("sum", 4), ("prod", 4), ("diff", 3), ("integrate", 4), ("solve", 3)];
+
+pub(crate) const CONSTANTS: [&str; 7] = ["pi", "e", "phi", "tau", "sqrt2", "sqrt3", "i"];
+
+// One statement: what parse_statement makes of the text between semicolons
+#[derive(Debug, Clone, PartialEq)]
+pub enum Statement {
+    Expression(Ast),
+    // name = expression; the name is as written, for the caller to check
+    Assign(String, Ast),
+}
+
+// A parsed expression
+#[derive(Debug, Clone, PartialEq)]
+pub enum Ast {
+    // A literal: its f64, and its exact value unless its exponent is too
+    // large to hold exactly
+    Number { real: f64, exact: Option<Rational> },
+    // A number directly followed by i, such as 2i
+    Imaginary(f64),
+    // pi, e, phi, tau, sqrt2, sqrt3 or i
+    Constant(String),
+    // One of a unit, such as km
+    Unit(Unit),
+    // m0-m9
+    Memory(usize),
+    // A value stored under a name, such as rate
+    Variable(String),
+    // _
+    LastResult,
+    // _2, _3, ...: the result n answers back, _1 being _
+    EarlierResult(usize),
+    Unary(UnaryOp, Box<Ast>),
+    Binary(BinaryOp, Box<Ast>, Box<Ast>),
+    // A function and its arguments, such as ncr(5, 2)
+    Call(String, Vec<Ast>),
+    // if(condition, then, else)
+    If(Box<Ast>, Box<Ast>, Box<Ast>),
+    // One of BINDERS: sum(1/k^2, k, 1, 10) is the function, the expression,
+    // the variable it binds and the remaining arguments
+    Bound { function: String, body: Box<Ast>, variable: String, args: Vec<Ast> },
+    // The items of a bracketed literal: numbers for a row, rows for a matrix
+    Matrix(Vec<Ast>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Negate,
+    // ~, bitwise not
+    Not,
+    // Postfix !
+    Factorial,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Power,
+    And,
+    Or,
+    Xor,
+    ShiftLeft,
+    ShiftRight,
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    // in or to: the value on the left in the units on the right
+    Convert,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number { real: f64, exact: Option<Rational> },
+    Imaginary(f64),
+    Operator(char),
+    Power,
+    ShiftLeft,
+    ShiftRight,
+    // == != < <= > >=
+    Compare(BinaryOp),
+    Xor,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    Comma,
+    Function(String),
+    Memory(usize),
+    Constant(String),
+    Unit(Unit),
+    // "in" or "to", converting to the units after it
+    In,
+    // A single =, after the name an assignment stores to
+    Assign,
+    LastResult,
+    EarlierResult(usize),
+    Eof,
+}
+
+// Parse one expression; m0 alone is a memory recall here, and saving to it is
+// up to the caller (Calculator::evaluate). Syntax errors give the byte offset
+// in `input` where they were found
+pub fn parse(input: &str) -> Result<Ast, CalcError> {
+    parse_with(input, false)
+}
+
+// parse(), skipping characters that start no token instead of failing on
+// them, as the calculator did before it reported them: 2 $ 3 is 2 3
+pub fn parse_lenient(input: &str) -> Result<Ast, CalcError> {
+    parse_with(input, true)
+}
+
+fn parse_with(input: &str, lenient: bool) -> Result<Ast, CalcError> {
+    let tokens = tokenize(input, lenient)?;
+    trace!("tokens: {:?}", tokens);
+    Parser { tokens, pos: 0 }.parse_all()
+}
+
+// One statement: `name = expression` if the second token is a single =, else
+// an expression as parse() reads it; `lenient` skips characters as
+// parse_lenient does
+pub fn parse_statement(input: &str, lenient: bool) -> Result<Statement, CalcError> {
+    let mut tokens = tokenize(input, lenient)?;
+    trace!("tokens: {:?}", tokens);
+    if tokens.get(1).is_some_and(|(token, _)| *token == Token::Assign) {
+        let name = input[tokens[0].1..tokens[1].1].trim().to_string();
+        let expression = Parser { tokens: tokens.split_off(2), pos: 0 }.parse_all()?;
+        return Ok(Statement::Assign(name, expression));
+    }
+    Ok(Statement::Expression(Parser { tokens, pos: 0 }.parse_all()?))
+}
+
+// Each token with the byte offset it starts at; Eof is at the end of the input
+fn tokenize(input: &str, lenient: bool) -> Result<Vec<(Token, usize)>, CalcError> {
+    let mut tokens = Vec::new();
+    let mut lexer = Lexer::new(input);
+
+    while let Some(ch) = lexer.peek() {
+        let offset = lexer.position().start;
+        let mut push = |token| tokens.push((token, offset));
+        match ch {
+            _ if ch.is_whitespace() => {
+                lexer.next_char();
+            }
+            '0' if matches!(lexer.peek_nth(1), Some('x' | 'b' | 'o')) => {
+                lexer.next_char();
+                let (prefix, radix) = match lexer.next_char() {
+                    Some('x') => ("0x", 16),
+                    Some('b') => ("0b", 2),
+                    _ => ("0o", 8),
+                };
+                // Underscores group digits, as in Rust: 0xdead_beef
+                let digits = lexer.take_while(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+                let whole = BigInt::from_str_radix(&digits.replace('_', ""), radix)
+                    .map_err(|_| at(offset, CalcError::InvalidNumber(format!("{}{}", prefix, digits))))?;
+                push(Token::Number { real: whole.to_f64(), exact: Some(Rational::integer(whole)) });
+            }
+            '0'..='9' | '.' => {
+                // Digits and dots, then an exponent if one follows: 6.022e23,
+                // 1E-9 (but 2e alone is 2 followed by the constant e)
+                let start = lexer.position();
+                lexer.take_while(|ch| ch.is_ascii_digit() || ch == '.');
+                lexer.scan_exponent();
+                let span = lexer.span_from(start);
+                let number = &lexer.input()[span.start..span.end];
+                let real: f64 = number.parse().map_err(|_| at(offset, CalcError::InvalidNumber(number.to_string())))?;
+                // 2i, but not the start of a word such as 2in
+                let imaginary = lexer.peek() == Some('i')
+                    && !lexer.peek_nth(1).is_some_and(|ch| ch.is_ascii_alphanumeric() || ch == '_');
+                if imaginary {
+                    lexer.next_char();
+                    push(Token::Imaginary(real));
+                } else {
+                    push(Token::Number { real, exact: Rational::parse_decimal(number) });
+                }
+            }
+            // != before the factorial: 5!=120 is 5 != 120, as in Python
+            '<' | '>' | '=' | '!' if lexer.peek_nth(1) == Some('=') => {
+                lexer.next_char();
+                lexer.next_char();
+                push(Token::Compare(match ch {
+                    '<' => BinaryOp::LessEqual,
+                    '>' => BinaryOp::GreaterEqual,
+                    '=' => BinaryOp::Equal,
+                    _ => BinaryOp::NotEqual,
+                }));
+            }
+            '+' | '-' | '/' | '%' | '&' | '|' | '~' | '!' => {
+                push(Token::Operator(lexer.next_char().unwrap()));
+            }
+            '<' | '>' if lexer.peek_nth(1) == Some(ch) => {
+                lexer.next_char();
+                lexer.next_char();
+                push(if ch == '<' { Token::ShiftLeft } else { Token::ShiftRight });
+            }
+            '<' | '>' => {
+                lexer.next_char();
+                push(Token::Compare(if ch == '<' { BinaryOp::Less } else { BinaryOp::Greater }));
+            }
+            '=' => {
+                lexer.next_char();
+                push(Token::Assign);
+            }
+            '*' => {
+                lexer.next_char();
+                if lexer.eat('*') {
+                    push(Token::Power);
+                } else {
+                    push(Token::Operator('*'));
+                }
+            }
+            '(' => {
+                lexer.next_char();
+                push(Token::LeftParen);
+            }
+            ')' => {
+                lexer.next_char();
+                push(Token::RightParen);
+            }
+            ',' => {
+                lexer.next_char();
+                push(Token::Comma);
+            }
+            '[' => {
+                lexer.next_char();
+                push(Token::LeftBracket);
+            }
+            ']' => {
+                lexer.next_char();
+                push(Token::RightBracket);
+            }
+            '_' => {
+                lexer.next_char();
+                let digits = lexer.take_while(|ch| ch.is_ascii_digit());
+                if digits.is_empty() {
+                    push(Token::LastResult);
+                } else {
+                    let n = digits.parse().map_err(|_| at(offset, CalcError::InvalidNumber(format!("_{}", digits))))?;
+                    push(Token::EarlierResult(n));
+                }
+            }
+            '^' => {
+                lexer.next_char();
+                push(Token::Power);
+            }
+            'a'..='z' | 'A'..='Z' => {
+                let word = lexer.scan_identifier().unwrap().to_string();
+
+                // Check for memory locations, constants, and functions
+                match word.as_str() {
+                    _ if CONSTANTS.contains(&word.as_str()) => push(Token::Constant(word)),
+                    "xor" => push(Token::Xor),
+                    "in" | "to" => push(Token::In),
+                    _ if Unit::named(&word).is_some() => push(Token::Unit(Unit::named(&word).unwrap())),
+                    _ => match (word.starts_with('m'), word.len()) {
+                        (true, 2) => {
+                            if let Some(digit) = word.chars().nth(1).unwrap().to_digit(10) {
+                                if digit <= 9 {
+                                    push(Token::Memory(digit as usize));
+                                    continue;
+                                }
+                            }
+                            push(Token::Function(word));
+                        }
+                        _ => push(Token::Function(word)),
+                    }
+                }
+            }
+            _ if lenient => {
+                lexer.next_char();
+            }
+            _ => return Err(at(offset, CalcError::InvalidCharacter(ch))),
+        }
+    }
+
+    tokens.push((Token::Eof, input.trim_end().len()));
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+}
+
+fn at(offset: usize, error: CalcError) -> CalcError {
+    CalcError::At { offset, error: Box::new(error) }
+}
+
+fn binary(op: BinaryOp, left: Ast, right: Ast) -> Ast {
+    Ast::Binary(op, Box::new(left), Box::new(right))
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        // The Eof token is never consumed, so there is always one to see
+        &self.tokens[self.pos.min(self.tokens.len() - 1)].0
+    }
+
+    // Where the token the parser is looking at starts
+    fn offset(&self) -> usize {
+        self.tokens[self.pos.min(self.tokens.len() - 1)].1
+    }
+
+    // `error` at the token the parser is looking at
+    fn error(&self, error: CalcError) -> CalcError {
+        at(self.offset(), error)
+    }
+
+    // The whole of the tokens as one expression
+    fn parse_all(&mut self) -> Result<Ast, CalcError> {
+        let ast = self.parse_expression()?;
+
+        if self.pos < self.tokens.len() - 1 { // -1 because of Eof token
+            return Err(self.error(CalcError::TrailingTokens));
+        }
+
+        Ok(ast)
+    }
+
+    // Conversion binds loosest: 1 km + 500 m in mi converts the sum
+    fn parse_expression(&mut self) -> Result<Ast, CalcError> {
+        let value = self.parse_comparison()?;
+
+        if *self.peek() == Token::In {
+            self.pos += 1;
+            let target = self.parse_bit_or()?;
+            return Ok(binary(BinaryOp::Convert, value, target));
+        }
+
+        Ok(value)
+    }
+
+    // At most one comparison; a second is left over as trailing tokens
+    fn parse_comparison(&mut self) -> Result<Ast, CalcError> {
+        let left = self.parse_bit_or()?;
+
+        if let Token::Compare(op) = *self.peek() {
+            self.pos += 1;
+            let right = self.parse_bit_or()?;
+            return Ok(binary(op, left, right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_bit_or(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_bit_xor()?;
+
+        while *self.peek() == Token::Operator('|') {
+            self.pos += 1;
+            let right = self.parse_bit_xor()?;
+            left = binary(BinaryOp::Or, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_bit_xor(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_bit_and()?;
+
+        while *self.peek() == Token::Xor {
+            self.pos += 1;
+            let right = self.parse_bit_and()?;
+            left = binary(BinaryOp::Xor, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_bit_and(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_shift()?;
+
+        while *self.peek() == Token::Operator('&') {
+            self.pos += 1;
+            let right = self.parse_shift()?;
+            left = binary(BinaryOp::And, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_shift(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_addition()?;
+
+        loop {
+            let op = match self.peek() {
+                Token::ShiftLeft => BinaryOp::ShiftLeft,
+                Token::ShiftRight => BinaryOp::ShiftRight,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.parse_addition()?;
+            left = binary(op, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_addition(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_multiplication()?;
+
+        loop {
+            let op = match self.peek() {
+                Token::Operator('+') => BinaryOp::Add,
+                Token::Operator('-') => BinaryOp::Subtract,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.parse_multiplication()?;
+            left = binary(op, left, right);
+        }
+
+        Ok(left)
+    }
+
+    fn parse_multiplication(&mut self) -> Result<Ast, CalcError> {
+        let mut left = self.parse_quantity()?;
+
+        loop {
+            let op = match self.peek() {
+                Token::Operator('*') => BinaryOp::Multiply,
+                Token::Operator('/') => BinaryOp::Divide,
+                Token::Operator('%') => BinaryOp::Modulo,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.parse_quantity()?;
+            left = binary(op, left, right);
+        }
+
+        Ok(left)
+    }
+
+    // A value and the units right after it, which bind tighter than * and /:
+    // 5 km / 250 m is (5 km)/(250 m), and 9.81 m/s^2 is (9.81 m)/s^2
+    fn parse_quantity(&mut self) -> Result<Ast, CalcError> {
+        let mut value = self.parse_power()?;
+
+        while matches!(self.peek(), Token::Unit(_)) {
+            let unit = self.parse_power()?;
+            value = binary(BinaryOp::Multiply, value, unit);
+        }
+
+        Ok(value)
+    }
+
+    fn parse_power(&mut self) -> Result<Ast, CalcError> {
+        let left = self.parse_unary()?;
+
+        if *self.peek() == Token::Power {
+            self.pos += 1;
+            let right = self.parse_power()?; // Right associative
+            return Ok(binary(BinaryOp::Power, left, right));
+        }
+
+        Ok(left)
+    }
+
+    fn parse_unary(&mut self) -> Result<Ast, CalcError> {
+        let op = match self.peek() {
+            Token::Operator('-') => UnaryOp::Negate,
+            Token::Operator('~') => UnaryOp::Not,
+            Token::Operator('+') => {
+                self.pos += 1;
+                return self.parse_unary();
+            }
+            _ => return self.parse_postfix(),
+        };
+        self.pos += 1;
+        Ok(Ast::Unary(op, Box::new(self.parse_unary()?)))
+    }
+
+    // Postfix ! binds tighter than unary minus and ^: -3! is -6, 2^3! is 64
+    fn parse_postfix(&mut self) -> Result<Ast, CalcError> {
+        let mut value = self.parse_factor()?;
+
+        while *self.peek() == Token::Operator('!') {
+            self.pos += 1;
+            value = Ast::Unary(UnaryOp::Factorial, Box::new(value));
+        }
+
+        Ok(value)
+    }
+
+    // Expressions separated by commas, up to `close`
+    fn parse_list(&mut self, close: &Token, missing: CalcError) -> Result<Vec<Ast>, CalcError> {
+        let mut items = Vec::new();
+        if self.peek() != close {
+            items.push(self.parse_expression()?);
+            while *self.peek() == Token::Comma {
+                self.pos += 1;
+                items.push(self.parse_expression()?);
+            }
+        }
+        if self.peek() != close {
+            return Err(self.error(missing));
+        }
+        self.pos += 1;
+        Ok(items)
+    }
+
+    fn parse_factor(&mut self) -> Result<Ast, CalcError> {
+        // Errors about the token itself point at it, not past it
+        let start = self.offset();
+        let token = self.peek().clone();
+        self.pos += 1;
+
+        match token {
+            Token::Number { real, exact } => Ok(Ast::Number { real, exact }),
+            Token::Imaginary(n) => Ok(Ast::Imaginary(n)),
+            Token::LeftParen => {
+                let result = self.parse_expression()?;
+                if *self.peek() != Token::RightParen {
+                    return Err(self.error(CalcError::MissingClosingParen));
+                }
+                self.pos += 1;
+                Ok(result)
+            }
+            Token::Function(name) if name == "if" => {
+                if *self.peek() != Token::LeftParen {
+                    return Err(at(start, CalcError::MissingParentheses(name)));
+                }
+                self.pos += 1;
+                let args = self.parse_list(&Token::RightParen, CalcError::MissingClosingParen)?;
+                let Ok([condition, then, otherwise]) = <[Ast; 3]>::try_from(args) else {
+                    return Err(at(start, CalcError::ArgumentCount { function: name, expected: 3 }));
+                };
+                Ok(Ast::If(Box::new(condition), Box::new(then), Box::new(otherwise)))
+            }
+            Token::Function(name) if BINDERS.iter().any(|(binder, _)| *binder == name) => {
+                let (binder, expected) = BINDERS.into_iter().find(|(binder, _)| *binder == name).unwrap();
+                if *self.peek() != Token::LeftParen {
+                    return Err(at(start, CalcError::MissingParentheses(name)));
+                }
+                self.pos += 1;
+                let mut args = self.parse_list(&Token::RightParen, CalcError::MissingClosingParen)?;
+                if args.len() != expected {
+                    return Err(at(start, CalcError::ArgumentCount { function: name, expected }));
+                }
+                let rest = args.split_off(2);
+                let Some(Ast::Variable(variable)) = args.pop() else {
+                    return Err(at(start, CalcError::Domain { function: binder, requirement: "a name such as k as its variable" }));
+                };
+                let body = Box::new(args.pop().unwrap());
+                Ok(Ast::Bound { function: name, body, variable, args: rest })
+            }
+            Token::Function(name) => {
+                // Check if it's a known function first
+                if !FUNCTIONS.contains(&name.as_str()) {
+                    if *self.peek() != Token::LeftParen {
+                        return Ok(Ast::Variable(name));
+                    }
+                    return Err(at(start, CalcError::UnknownFunction(name)));
+                }
+
+                if *self.peek() != Token::LeftParen {
+                    return Err(at(start, CalcError::MissingParentheses(name)));
+                }
+                self.pos += 1;
+                // f() is not a call with no arguments but a missing one
+                if *self.peek() == Token::RightParen {
+                    return Err(self.error(CalcError::UnexpectedToken));
+                }
+                let args = self.parse_list(&Token::RightParen, CalcError::MissingClosingParen)?;
+                Ok(Ast::Call(name, args))
+            }
+            Token::Memory(slot) => Ok(Ast::Memory(slot)),
+            Token::Constant(name) => Ok(Ast::Constant(name)),
+            Token::Unit(unit) => Ok(Ast::Unit(unit)),
+            Token::LeftBracket => Ok(Ast::Matrix(self.parse_list(&Token::RightBracket, CalcError::MissingClosingBracket)?)),
+            Token::LastResult => Ok(Ast::LastResult),
+            Token::EarlierResult(n) => Ok(Ast::EarlierResult(n)),
+            _ => Err(at(start, CalcError::UnexpectedToken)),
+        }
+    }
+}
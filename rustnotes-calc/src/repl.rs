@@ -0,0 +1,353 @@
+// Calculator REPL Commands
+//
+// The interactive side of the calculator, over text::repl: the help text, the
+// commands that are not expressions (set, mode, base, frac, dec, save, load,
+// clear), tab completion, and the session kept between runs. Every other line
+// goes to Calculator::respond. run_lines answers a script through the same
+// commands, without the banner or prompts.
+//
+// Design choices:
+// - The REPL only holds a Calculator, so everything it does is also
+//   available without it: Calculator for lines, parser and evaluator for
+//   single expressions
+// - Commands report through Result<String, String>, which text::repl prints
+//   as output or as an error
+// - Trade-off: the history the save command writes is a copy kept by an
+//   on_line hook, since the Repl keeps its own to itself
+
+use crate::bignum::Rational;
+use crate::session::Session;
+use crate::settings::{self, NumberMode, Settings};
+use crate::value::Value;
+use crate::Calculator;
+use rustnotes_algorithms::text::repl::Repl;
+use std::cell::{Cell, RefCell};
+use std::io::{self, BufRead, Write};
+use std::mem;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+const HELP: &str = "\
+Calculator REPL
+Supported operators: +, -, *, /, %, ** (or ^), ! (factorial, postfix: 5!)
+Bitwise operators on whole numbers: & | xor << >> and ~ (not), looser than +
+Comparisons: == != < <= > >= give 1 (true) or 0 (false), looser still
+  - if(cond, a, b) is a unless cond is 0, else b; only that one is evaluated
+Sums and products over whole k from a to b: sum(1/k^2, k, 1, 1000),
+  prod(k, k, 1, 5)
+Derivative of an expression in x at a point: diff(x^3, x, 2) (about 12)
+Integral from a to b: integrate(x^2, x, 0, 3) (about 9), to within the
+  tolerance setting ('set tolerance 1e-12')
+Zero of an expression near a guess: solve(x^2 - 2, x, 1) (sqrt 2)
+  - quadratic(a, b, c) gives both real roots of ax^2 + bx + c as [x1, x2]
+Supported functions: sin, cos, tan, asin, acos, atan, ln, log2, log10, exp, sqrt
+                    round, floor, ceil, abs, factorial
+                    ncr(n, k), npr(n, k) (combinations, permutations)
+                    quadratic(a, b, c) (roots of ax^2 + bx + c)
+Constants: pi, e, phi, tau, sqrt2, sqrt3
+Literals: 255, 2.5e3, 1E-9, 0xFF, 0b1010, 0o755
+  - 'base 16' (or 2, 8, 10) shows whole results in that base
+Complex numbers: i is the imaginary unit: (1+2i)*(3-i), sqrt(-4), ln(-1)
+Units: a unit after a number: 5 km + 300 m, 9.81 m/s^2 * 70 kg
+  - 'in' or 'to' converts: 100 mph in km/h, 20 degC in degF, 1 GiB to MB
+  - Length m km cm mm um nm inch ft yd mi nmi au ly; mass kg g mg t lb oz;
+    time s ms us ns min h day week yr; A mA K degC degF mol
+  - bit B kB MB GB TB KiB MiB GiB; ha acre L mL gal mph kn Hz kHz MHz GHz;
+    N lbf J kJ cal kcal Wh kWh eV W kW hp Pa kPa bar atm psi C V ohm
+Matrices: [[1, 2], [3, 4]] (a row is [1, 2, 3]); + - *, / by a number, ^n
+  - det(A), inv(A), transpose(A); abs, round, floor, ceil act on each entry
+Exact mode: 'mode exact' computes with exact integers and fractions
+  (2^200, factorial(50), 1/3 + 1/6); 'mode float' goes back to f64
+  - 'frac' switches to exact mode and turns _ into a fraction (0.75 → 3/4)
+  - 'dec' switches to float mode and turns _ into a decimal
+Use '_' to reference the last result, '_2' the one before it, up to '_10'
+Tab completes names: sq<Tab> offers sqrt, sqrt2, sqrt3; Up/Down recall lines
+Memory locations: m0 through m9
+  - Use 'm0' on a line by itself to save last result to m0
+  - Use 'm0' in expressions to recall value from m0
+  - Use 'c0' to clear memory location m0, 'clear' to clear the last results
+Named memory: 'store rate' saves the last result as rate, used as 'rate'
+  - 'rate = 0.05' stores a value, 'mem' lists the names, 'forget rate' drops one
+Statements: 'a = 2; b = 3; a * b' runs each in turn and prints the last result
+  - 'set echo_statements on' prints every statement's result
+JSON: 'json on' (or --json) prints each answer as a JSON object:
+  {\"error\":null,\"input\":\"2+2\",\"result\":\"4\"}; 'json off' goes back
+Settings (angle_mode, precision, color, history_size, output_format,
+          number_mode, base, json, lenient, tolerance, echo_statements):
+  - 'set' lists them, 'set angle_mode degrees' changes one for this session
+  - Add 'save' ('set precision 4 save', or 'set save') to write them to
+    ~/.config/rustnotes/calculator.toml
+Sessions: memory, _ and history are kept in session.json beside the settings
+  - Saved on exit and restored at startup unless save_session is false
+  - 'save' and 'load' (optionally with a file: 'save work.json') on demand";
+
+// The set command: list, change and/or save settings
+fn set_command(calc: &mut Calculator, args: &str) -> Result<String, String> {
+    let mut words: Vec<&str> = args.split_whitespace().collect();
+    let save = words.last() == Some(&"save");
+    if save {
+        words.pop();
+    }
+    match words.as_slice() {
+        [] if !save => {
+            // Values line up one space past the longest key
+            let entries = calc.context.settings.entries();
+            let width = entries.iter().map(|(key, _)| key.len()).max().unwrap_or(0);
+            let lines: Vec<String> = entries.iter().map(|(key, value)| format!("{:<width$} {}", key, value)).collect();
+            return Ok(lines.join("\n"));
+        }
+        [] => {}
+        [key, value] => calc.context.settings.set(key, value).map_err(|error| error.to_string())?,
+        _ => return Err("usage: set [<setting> <value>] [save]".to_string()),
+    }
+    let mut message = words.first().map_or_else(String::new, |key| {
+        let value = calc.context.settings.entries().into_iter().find(|(name, _)| name == key).map(|(_, value)| value);
+        format!("{} = {}", key, value.unwrap_or_default())
+    });
+    if save {
+        let path = calc.context.settings.save().map_err(|error| error.to_string())?;
+        if !message.is_empty() {
+            message.push('\n');
+        }
+        message.push_str(&format!("Saved settings to {}", path.display()));
+    }
+    Ok(message)
+}
+
+// The mode command: show or switch the number mode for this session
+fn mode_command(calc: &mut Calculator, args: &str) -> Result<String, String> {
+    match args.trim() {
+        "" => {}
+        mode => calc.context.settings.set("number_mode", mode).map_err(|error| error.to_string())?,
+    }
+    Ok(format!("number_mode = {}", calc.context.settings.number_mode.name()))
+}
+
+// The base command: show or switch the base whole results are shown in
+fn base_command(calc: &mut Calculator, args: &str) -> Result<String, String> {
+    match args.trim() {
+        "" => {}
+        base => calc.context.settings.set("base", base).map_err(|error| error.to_string())?,
+    }
+    Ok(format!("base = {}", calc.context.settings.base))
+}
+
+// The json command: show or switch JSON answers for this session
+fn json_command(calc: &mut Calculator, args: &str) -> Result<String, String> {
+    match args.trim() {
+        "" => {}
+        json => calc.context.settings.set("json", json).map_err(|error| error.to_string())?,
+    }
+    Ok(format!("json = {}", calc.context.settings.json))
+}
+
+// The frac command: exact mode, with _ as the simplest fraction that rounds
+// to it (a complex _ is left as it is)
+fn frac_command(calc: &mut Calculator, _args: &str) -> Result<String, String> {
+    if let Value::Real(x) = calc.context.last_result {
+        let exact = Rational::simplest_for(x).ok_or_else(|| format!("{} has no fraction", x))?;
+        calc.context.last_result = Value::Exact(exact);
+    }
+    calc.context.settings.number_mode = NumberMode::Exact;
+    Ok(format!("number_mode = exact, _ = {}", calc.context.settings.format_value(&calc.context.last_result)))
+}
+
+// The dec command: float mode, with _ as an f64
+fn dec_command(calc: &mut Calculator, _args: &str) -> Result<String, String> {
+    calc.context.last_result = calc.context.last_result.clone().inexact();
+    calc.context.settings.number_mode = NumberMode::Float;
+    Ok(format!("number_mode = float, _ = {}", calc.context.settings.format_value(&calc.context.last_result)))
+}
+
+// Tab completion: setting names after set, the modes after mode, stored
+// names after forget, and otherwise the names an expression can use
+fn complete(calc: &Calculator, before: &str) -> Vec<String> {
+    match before.split_whitespace().collect::<Vec<_>>().as_slice() {
+        ["set"] => settings::KEYS.iter().map(|key| key.to_string()).collect(),
+        ["mode"] => vec!["exact".to_string(), "float".to_string()],
+        ["json"] => vec!["on".to_string(), "off".to_string()],
+        ["forget"] => calc.context.variables.keys().cloned().collect(),
+        _ => calc.names(),
+    }
+}
+
+// The session file: the argument if there is one, else Session::path()
+fn session_path(args: &str) -> Result<PathBuf, String> {
+    match args.trim() {
+        "" => Session::path().map_err(|error| error.to_string()),
+        path => Ok(PathBuf::from(path)),
+    }
+}
+
+// The REPL over a calculator, with its settings' history size
+pub fn repl(calculator: Calculator) -> Repl<Calculator> {
+    repl_with_history(calculator, Vec::new())
+}
+
+// The REPL, starting from earlier history lines (a restored session's)
+fn repl_with_history(calculator: Calculator, history: Vec<String>) -> Repl<Calculator> {
+    let mut repl = with_commands(calculator, history);
+    repl.fallback(|calc, input| {
+        if is_blank(input) {
+            return Ok(String::new());
+        }
+        match calc.respond(input) {
+            // Printed as it is rather than after "Error: ", so every answer
+            // is a line of JSON
+            Err(json) if calc.settings().json => Ok(json),
+            response => response,
+        }
+    });
+    repl
+}
+
+// The help text and the commands, without a fallback for expressions
+fn with_commands(calculator: Calculator, history: Vec<String>) -> Repl<Calculator> {
+    let history_size = calculator.context.settings.history_size;
+    // The save command needs the history, which the Repl keeps to itself, so
+    // an on_line hook keeps a copy
+    let lines = Rc::new(RefCell::new(history.clone()));
+    let (seen, saved) = (lines.clone(), lines);
+    let mut repl = Repl::new(calculator, "> ");
+    repl.help_text(HELP)
+        .history_limit(history_size)
+        .set_history(history)
+        .on_line(move |line| {
+            let mut lines = seen.borrow_mut();
+            lines.push(line.to_string());
+            let excess = lines.len().saturating_sub(history_size);
+            lines.drain(..excess);
+        })
+        .command(&["set"], "list, change or save settings (set precision 4 save)", set_command)
+        .command(&["mode"], "show or switch the number mode (mode exact, mode float)", mode_command)
+        .command(&["base"], "show or switch the output base (base 16, base 2, base 10)", base_command)
+        .command(&["frac"], "exact mode, with the last result as a fraction", frac_command)
+        .command(&["dec"], "float mode, with the last result as a decimal", dec_command)
+        .command(&["save"], "save memory, stored names, _ and history (save [file])", move |calc, args| {
+            let path = session_path(args)?;
+            Session::of(calc, &saved.borrow()).save(&path).map_err(|error| error.to_string())?;
+            Ok(format!("Saved session to {}", path.display()))
+        })
+        .command(&["load"], "restore memory, stored names and _, _2, ... from a saved session (load [file])", |calc, args| {
+            let path = session_path(args)?;
+            match Session::load(&path).map_err(|error| error.to_string())? {
+                Some(session) => {
+                    session.restore(calc);
+                    Ok(format!("Loaded session from {}", path.display()))
+                }
+                None => Err(format!("no session at {}", path.display())),
+            }
+        })
+        .command(&["clear"], "clear the last results", |calc, _| {
+            calc.context.last_result = Value::Real(0.0);
+            calc.context.earlier_results.clear();
+            Ok("Cleared last results".to_string())
+        })
+        .command(&["json"], "show or switch JSON answers (json on, json off)", json_command)
+        .completer(complete);
+    repl
+}
+
+// Answer each non-empty line of a script like the REPL would, commands
+// included but without the banner or prompts, writing results to `output` and
+// errors (with their line number) to `errors`; returns how many lines failed.
+// A # starts a comment that runs to the end of the line, and quit stops early
+pub fn run_lines<R: BufRead, W: Write, E: Write>(
+    calculator: &mut Calculator,
+    input: R,
+    output: &mut W,
+    errors: &mut E,
+) -> io::Result<usize> {
+    // A JSON answer carries its own error, so the fallback hands it back as
+    // output and counts it here
+    let failed = Rc::new(Cell::new(0));
+    let counted = failed.clone();
+    let mut repl = with_commands(mem::take(calculator), Vec::new());
+    repl.fallback(move |calc, input| match calc.respond(input) {
+        Err(json) if calc.settings().json => {
+            counted.set(counted.get() + 1);
+            Ok(json)
+        }
+        response => response,
+    });
+    let result = script(&mut repl, input, output, errors, &failed);
+    *calculator = repl.into_state();
+    result.map(|()| failed.get())
+}
+
+// The loop of run_lines, apart so the calculator goes back on an I/O error
+fn script<R: BufRead, W: Write, E: Write>(
+    repl: &mut Repl<Calculator>,
+    input: R,
+    output: &mut W,
+    errors: &mut E,
+    failed: &Cell<usize>,
+) -> io::Result<()> {
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.split('#').next().unwrap_or_default();
+        if is_blank(line) {
+            continue;
+        }
+        match repl.dispatch(line) {
+            None => break,
+            Some(Ok(text)) if text.is_empty() => {}
+            Some(Ok(text)) => writeln!(output, "{}", text)?,
+            Some(Err(message)) => {
+                failed.set(failed.get() + 1);
+                writeln!(errors, "line {}: Error: {}", index + 1, message)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+// Whether a line has nothing to run: only spaces and ; separators, which the
+// REPL and scripts pass over as they do an empty line
+fn is_blank(line: &str) -> bool {
+    line.split(';').all(|statement| statement.trim().is_empty())
+}
+
+// Settings from calculator.toml, or the defaults with a warning on stderr
+pub fn load_settings() -> Settings {
+    Settings::load().unwrap_or_else(|error| {
+        eprintln!("Ignoring calculator settings: {}", error);
+        Settings::default()
+    })
+}
+
+// Run the REPL; with save_session on, restore the saved session first and
+// save it again on exit, reporting problems on stderr
+pub fn run_repl(mut calculator: Calculator) {
+    if !calculator.context.settings.save_session {
+        repl(calculator).run();
+        return;
+    }
+    let path = match Session::path() {
+        Ok(path) => path,
+        Err(error) => {
+            eprintln!("Not saving the session: {}", error);
+            repl(calculator).run();
+            return;
+        }
+    };
+    let history = match Session::load(&path) {
+        Ok(Some(session)) => session.restore(&mut calculator),
+        Ok(None) => Vec::new(),
+        Err(error) => {
+            // Leave the file as it is rather than overwrite it on exit
+            eprintln!("Not restoring the session: {}", error);
+            repl(calculator).run();
+            return;
+        }
+    };
+    let mut repl = repl_with_history(calculator, history);
+    repl.run();
+    if let Err(error) = Session::of(repl.state(), repl.history()).save(&path) {
+        eprintln!("Could not save the session: {}", error);
+    }
+}
+
+pub fn demo() {
+    run_repl(Calculator::with_settings(load_settings()));
+}
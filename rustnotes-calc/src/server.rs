@@ -0,0 +1,73 @@
+// Calculator Server
+//
+// The calculator over TCP, one expression per line: every connection gets its
+// own Calculator (memory, last result) and a thread, reads lines, and writes
+// one line back for each, the result or "Error: ...":
+//
+//   $ calculator serve --addr 127.0.0.1:7878 &
+//   $ printf '2+3\n_ * 10\n1/0\n' | nc 127.0.0.1 7878
+//   5
+//   50
+//   Error: Division by zero
+//
+// Design choices:
+// - Lines go through Calculator::respond, so answers match the REPL's, with the
+//   same memory commands and formatting
+// - No prompt, banner or REPL commands: replies are one line per request, which
+//   keeps clients simple, and a remote client cannot "set ... save" over the
+//   server's settings file
+// - Color is turned off for every connection, since the other end is rarely a
+//   terminal
+// - "q", "quit" or "exit" closes the connection; so does the client hanging up
+// - serve() takes a bound listener, so the caller picks the address (port 0
+//   for any free port) and can print it before serving
+// - Trade-off: a thread per connection is simple and fine for a handful of
+//   clients; thousands would want a pool (concurrency::ThreadPool) or async IO
+
+use crate::settings::Settings;
+use crate::Calculator;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::thread;
+
+// Accept connections forever, each on its own thread
+pub fn serve(listener: TcpListener, settings: Settings) -> io::Result<()> {
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(error) => {
+                eprintln!("accept failed: {}", error);
+                continue;
+            }
+        };
+        let settings = Settings { color: false, ..settings.clone() };
+        thread::spawn(move || {
+            if let Err(error) = handle(stream, settings) {
+                eprintln!("connection closed: {}", error);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle(stream: TcpStream, settings: Settings) -> io::Result<()> {
+    let mut calculator = Calculator::with_settings(settings);
+    let reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        if matches!(line, "q" | "quit" | "exit") {
+            break;
+        }
+        match calculator.respond(line) {
+            Ok(text) => writeln!(writer, "{}", text)?,
+            Err(json) if calculator.settings().json => writeln!(writer, "{}", json)?,
+            Err(message) => writeln!(writer, "Error: {}", message)?,
+        }
+    }
+    Ok(())
+}
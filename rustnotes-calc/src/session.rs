@@ -0,0 +1,242 @@
+// Calculator Sessions
+//
+// The memory slots and stored names, the last results and the REPL history,
+// kept in a JSON file between runs:
+//
+//   {"earlier_results":[512],"history":["2^9","2^10","m0","store big"],
+//    "last_result":1024,"memory":[1024,0,...],"variables":{"big":1024},"version":1}
+//
+// run_repl() loads the file when the REPL starts and writes it back when the
+// REPL exits, unless the save_session setting is off; the "save" and "load"
+// commands do the same on demand, optionally with a path ("save work.json").
+//
+// Design choices:
+// - JSON through text::json, so the file can be read and edited by hand or by
+//   other tools; its keys come out sorted because JsonValue objects are BTreeMaps
+// - Every value keeps its kind: a real is a JSON number (or "inf", "-inf" or
+//   "NaN", which JSON cannot hold), a complex number {"re": .., "im": ..}, an
+//   exact one {"exact": "22/7"}, a quantity {"value": 5.3, "unit": "km"} and
+//   a matrix {"matrix": [[1, 2], [3, 4]]}, so saving and loading never rounds
+// - The file is session.json next to calculator.toml (Settings::path), so
+//   $RUSTNOTES_CALC_CONFIG moves both
+// - A missing file is a fresh session. A file that cannot be read is reported
+//   and then left alone: the REPL does not save over it on exit
+// - load replaces the memory slots, the stored names and _, _2, ... but not
+//   the history, which belongs to
+//   the running REPL; a file's history is only taken at startup
+// - Trade-off: the whole file is rewritten on every save, which is nothing for
+//   ten values and a thousand lines of history
+
+use crate::bignum::Rational;
+use crate::evaluator::RESULTS;
+use crate::settings::{Settings, SettingsError};
+use crate::units::{Quantity, Unit};
+use crate::value::{Complex, Value};
+use crate::Calculator;
+use rustnotes_algorithms::math::linear_algebra::Matrix;
+use rustnotes_algorithms::text::json::{self, JsonValue};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+const VERSION: f64 = 1.0;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Session {
+    pub memory: [Value; 10],
+    pub variables: BTreeMap<String, Value>,
+    pub last_result: Value,
+    // _2 onwards, newest first
+    pub earlier_results: Vec<Value>,
+    pub history: Vec<String>,
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    // Neither $HOME nor $XDG_CONFIG_HOME is set
+    NoConfigDir,
+    Io { path: PathBuf, error: io::Error },
+    // Not JSON, or JSON that is not a session
+    Invalid { path: PathBuf, message: String },
+}
+
+impl fmt::Display for SessionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SessionError::NoConfigDir => write!(f, "no config directory: set HOME or XDG_CONFIG_HOME"),
+            SessionError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            SessionError::Invalid { path, message } => write!(f, "{}: not a calculator session: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SessionError::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl Session {
+    // A calculator's memory and last results, with the REPL's history
+    pub fn of(calculator: &Calculator, history: &[String]) -> Self {
+        Session {
+            memory: calculator.context.memory.clone(),
+            variables: calculator.context.variables.clone(),
+            last_result: calculator.context.last_result.clone(),
+            earlier_results: calculator.context.earlier_results.iter().cloned().collect(),
+            history: history.to_vec(),
+        }
+    }
+
+    // Put the memory and last results back into a calculator; returns the
+    // history for the REPL
+    pub fn restore(self, calculator: &mut Calculator) -> Vec<String> {
+        calculator.context.memory = self.memory;
+        calculator.context.variables = self.variables;
+        calculator.context.last_result = self.last_result;
+        calculator.context.earlier_results = self.earlier_results.into_iter().take(RESULTS - 1).collect();
+        self.history
+    }
+
+    // session.json in the directory of calculator.toml
+    pub fn path() -> Result<PathBuf, SessionError> {
+        match Settings::path() {
+            Ok(settings) => Ok(settings.with_file_name("session.json")),
+            Err(SettingsError::NoConfigDir) => Err(SessionError::NoConfigDir),
+            Err(error) => Err(SessionError::Invalid { path: PathBuf::new(), message: error.to_string() }),
+        }
+    }
+
+    // The session saved at `path`, or None if there is no file
+    pub fn load(path: &Path) -> Result<Option<Self>, SessionError> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(error) => return Err(SessionError::Io { path: path.to_path_buf(), error }),
+        };
+        let invalid = |message: String| SessionError::Invalid { path: path.to_path_buf(), message };
+        let json = json::parse(&text).map_err(invalid)?;
+        Self::from_json(&json).map(Some).map_err(invalid)
+    }
+
+    // Write the session to `path`, creating its directory
+    pub fn save(&self, path: &Path) -> Result<(), SessionError> {
+        let write = || {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, self.to_json().to_pretty_string() + "\n")
+        };
+        write().map_err(|error| SessionError::Io { path: path.to_path_buf(), error })
+    }
+
+    pub fn to_json(&self) -> JsonValue {
+        let mut members = BTreeMap::new();
+        members.insert("version".to_string(), JsonValue::Number(VERSION));
+        members.insert("last_result".to_string(), value_to_json(&self.last_result));
+        members.insert("earlier_results".to_string(), JsonValue::Array(self.earlier_results.iter().map(value_to_json).collect()));
+        members.insert("memory".to_string(), JsonValue::Array(self.memory.iter().map(value_to_json).collect()));
+        let variables = self.variables.iter().map(|(name, value)| (name.clone(), value_to_json(value))).collect();
+        members.insert("variables".to_string(), JsonValue::Object(variables));
+        let history = self.history.iter().map(|line| JsonValue::String(line.clone())).collect();
+        members.insert("history".to_string(), JsonValue::Array(history));
+        JsonValue::Object(members)
+    }
+
+    // The inverse of to_json; a missing member keeps its fresh-session value
+    pub fn from_json(json: &JsonValue) -> Result<Self, String> {
+        if !matches!(json, JsonValue::Object(_)) {
+            return Err("expected an object".to_string());
+        }
+        if json.get("version").is_some_and(|version| version.as_f64() != Some(VERSION)) {
+            return Err(format!("unsupported version {}", json.get("version").unwrap()));
+        }
+        let mut session = Session::of(&Calculator::new(), &[]);
+        if let Some(last_result) = json.get("last_result") {
+            session.last_result = value_from_json(last_result).ok_or("bad last_result")?;
+        }
+        if let Some(JsonValue::Array(results)) = json.get("earlier_results") {
+            for result in results {
+                session.earlier_results.push(value_from_json(result).ok_or("bad earlier result")?);
+            }
+        }
+        if let Some(memory) = json.get("memory") {
+            for (slot, value) in session.memory.iter_mut().enumerate() {
+                if let Some(saved) = memory.at(slot) {
+                    *value = value_from_json(saved).ok_or_else(|| format!("bad memory value m{}", slot))?;
+                }
+            }
+        }
+        if let Some(JsonValue::Object(variables)) = json.get("variables") {
+            for (name, value) in variables {
+                let value = value_from_json(value).ok_or_else(|| format!("bad value for {}", name))?;
+                session.variables.insert(name.clone(), value);
+            }
+        }
+        if let Some(JsonValue::Array(lines)) = json.get("history") {
+            for line in lines {
+                session.history.push(line.as_str().ok_or("history lines must be strings")?.to_string());
+            }
+        }
+        Ok(session)
+    }
+}
+
+// A number, or its name when JSON cannot hold it
+fn real_to_json(x: f64) -> JsonValue {
+    if x.is_finite() {
+        JsonValue::Number(x)
+    } else {
+        JsonValue::String(x.to_string())
+    }
+}
+
+fn real_from_json(json: &JsonValue) -> Option<f64> {
+    match json {
+        JsonValue::Number(x) => Some(*x),
+        JsonValue::String(name) if matches!(name.as_str(), "inf" | "-inf" | "NaN") => name.parse().ok(),
+        _ => None,
+    }
+}
+
+fn value_to_json(value: &Value) -> JsonValue {
+    let object = |members: Vec<(&str, JsonValue)>| {
+        JsonValue::Object(members.into_iter().map(|(key, value)| (key.to_string(), value)).collect())
+    };
+    match value {
+        Value::Real(x) => real_to_json(*x),
+        Value::Complex(z) => object(vec![("re", real_to_json(z.re)), ("im", real_to_json(z.im))]),
+        Value::Exact(r) => object(vec![("exact", JsonValue::String(r.to_string()))]),
+        Value::Quantity(q) => object(vec![("value", real_to_json(q.value)), ("unit", JsonValue::String(q.unit.to_string()))]),
+        Value::Matrix(m) => {
+            let rows = (0..m.rows()).map(|r| JsonValue::Array(m.row(r).iter().map(|&x| real_to_json(x)).collect()));
+            object(vec![("matrix", JsonValue::Array(rows.collect()))])
+        }
+    }
+}
+
+fn value_from_json(json: &JsonValue) -> Option<Value> {
+    if let Some(JsonValue::Array(rows)) = json.get("matrix") {
+        let row_from_json = |row: &JsonValue| match row {
+            JsonValue::Array(entries) => entries.iter().map(real_from_json).collect::<Option<Vec<f64>>>(),
+            _ => None,
+        };
+        let rows = rows.iter().map(row_from_json).collect::<Option<Vec<_>>>()?;
+        return Matrix::from_rows(rows).ok().map(Value::Matrix);
+    }
+    if let Some(exact) = json.get("exact") {
+        return Rational::parse_fraction(exact.as_str()?).map(Value::Exact);
+    }
+    if let (Some(value), Some(unit)) = (json.get("value"), json.get("unit")) {
+        return Some(Quantity::new(real_from_json(value)?, Unit::parse(unit.as_str()?)?).into());
+    }
+    if let (Some(re), Some(im)) = (json.get("re"), json.get("im")) {
+        return Some(Complex::new(real_from_json(re)?, real_from_json(im)?).into());
+    }
+    real_from_json(json).map(Value::Real)
+}
@@ -0,0 +1,473 @@
+// Calculator Settings
+//
+// Preferences that outlive a session, kept in a small TOML file:
+//
+//   # ~/.config/rustnotes/calculator.toml
+//   angle_mode = "degrees"      # or "radians" (default)
+//   precision = 6               # decimal places; omit for the shortest exact form
+//   color = true                # green results, red errors
+//   history_size = 500          # lines the REPL's history keeps
+//   output_format = "fixed"     # "auto" (default), "fixed" or "scientific"
+//   number_mode = "exact"       # or "float" (default); see bignum.rs
+//   base = 16                   # 2, 8, 10 (default) or 16 for whole results
+//   save_session = false        # keep memory and history between runs (true)
+//   json = true                 # each answer as a JSON object (false)
+//   lenient = true              # skip characters such as $ instead of failing (false)
+//   tolerance = 1e-12           # error integrate aims for (1e-10)
+//   echo_statements = true      # answer each statement of a = 2; a * 3 (false)
+//
+// The REPL loads the file at startup. "set" lists the settings, "set precision
+// 4" changes one for the session, and a trailing "save" ("set precision 4 save",
+// or just "set save") writes them back. The file holds settings only; no
+// expressions are run from it.
+//
+// Design choices:
+// - Only the flat subset of TOML the file needs is read: comments, blank lines
+//   and key = value with a string, number or boolean value. That keeps the
+//   calculator free of a TOML and serde dependency; a table header or an array
+//   is reported as a syntax error rather than misread
+// - The file and the set command go through the same Settings::set, so a
+//   value is accepted or rejected the same way in both places
+// - A missing file means the defaults; any other problem is an error naming
+//   the line, and the REPL falls back to the defaults and says why
+// - The path is $RUSTNOTES_CALC_CONFIG if set (for scripts and tests), else
+//   calculator.toml under $XDG_CONFIG_HOME/rustnotes or ~/.config/rustnotes
+// - Saving rewrites the whole file from the current settings, so comments a
+//   user added by hand are not kept
+// - precision and output_format apply to f64 results only; an exact result is
+//   always printed in full
+// - base 2, 8 or 16 prints whole-number results with a 0b, 0o or 0x prefix,
+//   the same form the tokenizer reads back; fractions, complex numbers and
+//   non-integral floats stay decimal
+// - save_session makes the REPL load session.json (session.rs) at startup
+//   and save it on exit; the save and load commands work either way
+// - json prints each answer as {"error": .., "input": .., "result": ..}, the
+//   result as the REPL would print it and the error as its message, one of
+//   them null; a JSON answer is never colored
+// - lenient brings back the tokenizer's old habit of skipping characters it
+//   has no token for: 2 $ 3 is read as 2 3 instead of failing at the $
+// - tolerance is the absolute error integrate() aims for; a smaller one
+//   costs more evaluations of the integrand
+// - echo_statements answers a line of statements with one line per
+//   statement instead of the last statement's result alone
+// - history_size is handed to the REPL when it starts, so changing it with
+//   set takes effect from the next session
+// - Trade-off: the defaults reproduce the calculator's behavior before settings
+//   existed, so Calculator::new() (used by eval(), the FFI and Python) never
+//   reads a file; only the REPL does
+
+use crate::value::{join_parts, Value};
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AngleMode {
+    Radians,
+    Degrees,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NumberMode {
+    // f64 arithmetic, the calculator's original behavior
+    Float,
+    // Literals are exact rationals, kept exact where the operation allows
+    Exact,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    // Shortest form that reads back exactly, or rounded to `precision` places
+    // with trailing zeros dropped
+    Auto,
+    // Always `precision` decimal places (6 if unset)
+    Fixed,
+    // Mantissa and exponent, such as 1.5e3
+    Scientific,
+}
+
+impl AngleMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            AngleMode::Radians => "radians",
+            AngleMode::Degrees => "degrees",
+        }
+    }
+}
+
+impl NumberMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            NumberMode::Float => "float",
+            NumberMode::Exact => "exact",
+        }
+    }
+}
+
+impl OutputFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            OutputFormat::Auto => "auto",
+            OutputFormat::Fixed => "fixed",
+            OutputFormat::Scientific => "scientific",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Settings {
+    pub angle_mode: AngleMode,
+    pub precision: Option<usize>,
+    pub color: bool,
+    pub history_size: usize,
+    pub output_format: OutputFormat,
+    pub number_mode: NumberMode,
+    pub base: u32,
+    pub save_session: bool,
+    pub json: bool,
+    pub lenient: bool,
+    pub tolerance: f64,
+    pub echo_statements: bool,
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    // Neither $HOME nor $XDG_CONFIG_HOME is set
+    NoConfigDir,
+    Io { path: PathBuf, error: io::Error },
+    // A line that is not `key = value`
+    Syntax { line: usize, text: String },
+    UnknownKey(String),
+    InvalidValue { key: &'static str, value: String, expected: &'static str },
+    // An UnknownKey or InvalidValue found on a line of the file
+    AtLine { line: usize, error: Box<SettingsError> },
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::NoConfigDir => write!(f, "no config directory: set HOME or XDG_CONFIG_HOME"),
+            SettingsError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+            SettingsError::Syntax { line, text } => write!(f, "line {}: expected key = value, found '{}'", line, text),
+            SettingsError::UnknownKey(key) => write!(f, "unknown setting '{}' (try: {})", key, KEYS.join(", ")),
+            SettingsError::InvalidValue { key, value, expected } => {
+                write!(f, "invalid {} '{}': expected {}", key, value, expected)
+            }
+            SettingsError::AtLine { line, error } => write!(f, "line {}: {}", line, error),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SettingsError::Io { error, .. } => Some(error),
+            SettingsError::AtLine { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+pub const KEYS: [&str; 12] = [
+    "angle_mode",
+    "precision",
+    "color",
+    "history_size",
+    "output_format",
+    "number_mode",
+    "base",
+    "save_session",
+    "json",
+    "lenient",
+    "tolerance",
+    "echo_statements",
+];
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            angle_mode: AngleMode::Radians,
+            precision: None,
+            color: false,
+            history_size: 1000,
+            output_format: OutputFormat::Auto,
+            number_mode: NumberMode::Float,
+            base: 10,
+            save_session: true,
+            json: false,
+            lenient: false,
+            tolerance: 1e-10,
+            echo_statements: false,
+        }
+    }
+}
+
+impl Settings {
+    // Where the settings file lives; see the header for the lookup order
+    pub fn path() -> Result<PathBuf, SettingsError> {
+        if let Some(path) = env::var_os("RUSTNOTES_CALC_CONFIG") {
+            return Ok(PathBuf::from(path));
+        }
+        let config = match (env::var_os("XDG_CONFIG_HOME"), env::var_os("HOME")) {
+            (Some(config), _) if !config.is_empty() => PathBuf::from(config),
+            (_, Some(home)) => PathBuf::from(home).join(".config"),
+            _ => return Err(SettingsError::NoConfigDir),
+        };
+        Ok(config.join("rustnotes").join("calculator.toml"))
+    }
+
+    // Settings from the file at path(), or the defaults if there is none
+    pub fn load() -> Result<Self, SettingsError> {
+        let path = Self::path()?;
+        match fs::read_to_string(&path) {
+            Ok(text) => Self::parse(&text),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => Ok(Settings::default()),
+            Err(error) => Err(SettingsError::Io { path, error }),
+        }
+    }
+
+    // Write the settings to path(), creating its directory; returns the path
+    pub fn save(&self) -> Result<PathBuf, SettingsError> {
+        let path = Self::path()?;
+        let write = |path: &PathBuf| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(path, self.to_toml())
+        };
+        write(&path).map_err(|error| SettingsError::Io { path: path.clone(), error })?;
+        Ok(path)
+    }
+
+    // Read the flat TOML subset described in the header; keys not in the
+    // text keep their defaults
+    pub fn parse(text: &str) -> Result<Self, SettingsError> {
+        let mut settings = Settings::default();
+        for (index, line) in text.lines().enumerate() {
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            let syntax = || SettingsError::Syntax { line: index + 1, text: line.to_string() };
+            let (key, value) = line.split_once('=').ok_or_else(syntax)?;
+            let (key, value) = (key.trim(), value.trim());
+            let value = match value.strip_prefix('"') {
+                Some(quoted) => quoted.strip_suffix('"').ok_or_else(syntax)?,
+                None => value,
+            };
+            if key.is_empty() || value.is_empty() {
+                return Err(syntax());
+            }
+            settings
+                .set(key, value)
+                .map_err(|error| SettingsError::AtLine { line: index + 1, error: Box::new(error) })?;
+        }
+        Ok(settings)
+    }
+
+    // Change one setting from its text form, as typed after "set"
+    pub fn set(&mut self, key: &str, value: &str) -> Result<(), SettingsError> {
+        let invalid = |key: &'static str, expected: &'static str| SettingsError::InvalidValue {
+            key,
+            value: value.to_string(),
+            expected,
+        };
+        match key {
+            "angle_mode" => {
+                self.angle_mode = match value {
+                    "radians" | "rad" => AngleMode::Radians,
+                    "degrees" | "deg" => AngleMode::Degrees,
+                    _ => return Err(invalid("angle_mode", "radians or degrees")),
+                }
+            }
+            "precision" => {
+                self.precision = match value {
+                    "auto" => None,
+                    _ => Some(value.parse().ok().filter(|&p| p <= 17).ok_or_else(|| {
+                        invalid("precision", "a number of decimal places from 0 to 17, or auto")
+                    })?),
+                }
+            }
+            "color" => {
+                self.color = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(invalid("color", "true or false")),
+                }
+            }
+            "save_session" => {
+                self.save_session = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(invalid("save_session", "true or false")),
+                }
+            }
+            "json" => {
+                self.json = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(invalid("json", "true or false")),
+                }
+            }
+            "lenient" => {
+                self.lenient = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(invalid("lenient", "true or false")),
+                }
+            }
+            "echo_statements" => {
+                self.echo_statements = match value {
+                    "true" | "on" => true,
+                    "false" | "off" => false,
+                    _ => return Err(invalid("echo_statements", "true or false")),
+                }
+            }
+            "tolerance" => {
+                self.tolerance = value
+                    .parse()
+                    .ok()
+                    .filter(|&tolerance: &f64| tolerance > 0.0 && tolerance.is_finite())
+                    .ok_or_else(|| invalid("tolerance", "a positive number such as 1e-10"))?;
+            }
+            "history_size" => {
+                self.history_size = value.parse().map_err(|_| invalid("history_size", "a whole number"))?;
+            }
+            "output_format" => {
+                self.output_format = match value {
+                    "auto" => OutputFormat::Auto,
+                    "fixed" => OutputFormat::Fixed,
+                    "scientific" | "sci" => OutputFormat::Scientific,
+                    _ => return Err(invalid("output_format", "auto, fixed or scientific")),
+                }
+            }
+            "number_mode" => {
+                self.number_mode = match value {
+                    "float" => NumberMode::Float,
+                    "exact" => NumberMode::Exact,
+                    _ => return Err(invalid("number_mode", "float or exact")),
+                }
+            }
+            "base" => {
+                self.base = match value {
+                    "2" | "8" | "10" | "16" => value.parse().unwrap(),
+                    _ => return Err(invalid("base", "2, 8, 10 or 16")),
+                }
+            }
+            _ => return Err(SettingsError::UnknownKey(key.to_string())),
+        }
+        Ok(())
+    }
+
+    // The file contents save() writes
+    pub fn to_toml(&self) -> String {
+        let mut text = String::from("# RustNotes calculator settings, written by \"set ... save\"\n");
+        text.push_str(&format!("angle_mode = \"{}\"\n", self.angle_mode.name()));
+        // TOML has no null, so an unset precision is left out
+        if let Some(places) = self.precision {
+            text.push_str(&format!("precision = {}\n", places));
+        }
+        text.push_str(&format!("color = {}\n", self.color));
+        text.push_str(&format!("history_size = {}\n", self.history_size));
+        text.push_str(&format!("output_format = \"{}\"\n", self.output_format.name()));
+        text.push_str(&format!("number_mode = \"{}\"\n", self.number_mode.name()));
+        text.push_str(&format!("base = {}\n", self.base));
+        text.push_str(&format!("save_session = {}\n", self.save_session));
+        text.push_str(&format!("json = {}\n", self.json));
+        text.push_str(&format!("lenient = {}\n", self.lenient));
+        text.push_str(&format!("tolerance = {:e}\n", self.tolerance));
+        text.push_str(&format!("echo_statements = {}\n", self.echo_statements));
+        text
+    }
+
+    // Every setting as (key, value), in KEYS order, as "set" lists them
+    pub fn entries(&self) -> [(&'static str, String); 12] {
+        [
+            ("angle_mode", self.angle_mode.name().to_string()),
+            ("precision", self.precision.map_or_else(|| "auto".to_string(), |places| places.to_string())),
+            ("color", self.color.to_string()),
+            ("history_size", self.history_size.to_string()),
+            ("output_format", self.output_format.name().to_string()),
+            ("number_mode", self.number_mode.name().to_string()),
+            ("base", self.base.to_string()),
+            ("save_session", self.save_session.to_string()),
+            ("json", self.json.to_string()),
+            ("lenient", self.lenient.to_string()),
+            ("tolerance", format!("{:e}", self.tolerance)),
+            ("echo_statements", self.echo_statements.to_string()),
+        ]
+    }
+
+    // A result as the REPL prints it
+    pub fn format(&self, value: f64) -> String {
+        match (self.output_format, self.precision) {
+            (OutputFormat::Auto, None) => value.to_string(),
+            (OutputFormat::Auto, Some(places)) => {
+                let text = format!("{:.*}", places, value);
+                if text.contains('.') {
+                    text.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    text
+                }
+            }
+            (OutputFormat::Fixed, places) => format!("{:.*}", places.unwrap_or(6), value),
+            (OutputFormat::Scientific, None) => format!("{:e}", value),
+            (OutputFormat::Scientific, Some(places)) => format!("{:.*e}", places, value),
+        }
+    }
+
+    // Any value as the REPL prints it; each part of a complex number, the
+    // number of a quantity and each entry of a matrix are formatted as a real
+    // would be, and an exact value in full. Outside base
+    // 10, whole numbers are shown in the base instead
+    pub fn format_value(&self, value: &Value) -> String {
+        if self.base != 10 {
+            if let Some(text) = value.to_radix(self.base) {
+                return text;
+            }
+        }
+        match value {
+            Value::Real(x) => self.format(*x),
+            Value::Exact(r) => r.to_string(),
+            Value::Quantity(q) => format!("{} {}", self.format(q.value), q.unit),
+            Value::Matrix(m) => {
+                // One row per line, entries right-aligned as in linear_algebra
+                let cells: Vec<Vec<String>> = (0..m.rows()).map(|r| m.row(r).iter().map(|x| self.format(*x)).collect()).collect();
+                let width = cells.iter().flatten().map(String::len).max().unwrap_or(0);
+                let rows: Vec<String> = cells
+                    .iter()
+                    .map(|row| format!("[{}]", row.iter().map(|cell| format!("{:>width$}", cell)).collect::<Vec<_>>().join(" ")))
+                    .collect();
+                rows.join("\n")
+            }
+            Value::Complex(z) => {
+                join_parts(&self.format(z.re), z.im.is_sign_negative(), &self.format(z.im.abs()), z.re == 0.0)
+            }
+        }
+    }
+
+    // Wrap text in an ANSI color when color is on (and json off); code 32 is
+    // green, 31 red
+    pub fn paint(&self, text: &str, code: u8) -> String {
+        if self.color && !self.json {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+// Drop a # comment, unless the # is inside a quoted string
+fn strip_comment(line: &str) -> &str {
+    let mut quoted = false;
+    for (i, ch) in line.char_indices() {
+        match ch {
+            '"' => quoted = !quoted,
+            '#' if !quoted => return &line[..i],
+            _ => {}
+        }
+    }
+    line
+}
@@ -0,0 +1,334 @@
+// Units of Measure
+//
+// A unit name after a number makes a Quantity; arithmetic keeps track of the
+// dimensions, and "in" (or "to") converts:
+//
+//   5 km + 300 m        → 5.3 km
+//   100 mph in km/h     → 160.9344 km/h
+//   3 m * 4 m           → 12 m^2
+//   1 kWh in J          → 3600000 J
+//   20 degC in degF     → 68 degF
+//   5 km / 250 m        → 20
+//
+// Design choices:
+// - A Unit is a product of table units to whole powers (km/h is km^1 h^-1),
+//   which gives both its name and its size in SI base units. Dimensions are the
+//   exponents of m, kg, s, A, K, mol and bit
+// - A unit is a factor in the grammar, and a value directly followed by units
+//   is multiplied by them before * and / apply: 2 m^2 is 2 (m^2), 5 km / 250 m
+//   is (5 km)/(250 m), and km/h on its own is the unit that "in" converts to
+// - Powers are i8s, so a product or power that would take one (or a dimension)
+//   past ±127 is a domain error rather than wrapping around
+// - Sums keep the left operand's unit (5 km + 300 m is 5.3 km); products and
+//   quotients combine units, the same unit twice becoming a power (m*m is m^2).
+//   A result with no dimension left (5 km / 250 m) is a plain number again
+// - Adding different dimensions is an error, as is a unit on a complex number;
+//   abs, round, floor, ceil and sqrt (of even powers) keep units, the other
+//   functions need plain numbers
+// - Every unit is spelled out in the table (km, mm, kB) rather than built from
+//   prefixes, so m is metres, min minutes and in the conversion keyword (inches
+//   are inch)
+// - degC and degF have an offset, which only conversion applies: in 20 degC +
+//   5 degC the second operand is a difference, so the sum is 25 degC
+// - A converted value is rounded to 15 significant digits (of the amount
+//   before an offset is taken off), which hides the rounding noise of the
+//   factors: 160.9344 km/h, not 160.93439999999998
+// - Trade-off: magnitudes are f64 even in exact mode; exact conversions would
+//   need every factor as a Rational, and most are measured values anyway
+
+use std::fmt;
+
+// Exponents of m, kg, s, A, K, mol and bit
+pub type Dimensions = [i8; 7];
+
+const NONE: Dimensions = [0, 0, 0, 0, 0, 0, 0];
+const LENGTH: Dimensions = [1, 0, 0, 0, 0, 0, 0];
+const MASS: Dimensions = [0, 1, 0, 0, 0, 0, 0];
+const TIME: Dimensions = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: Dimensions = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: Dimensions = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: Dimensions = [0, 0, 0, 0, 0, 1, 0];
+const INFORMATION: Dimensions = [0, 0, 0, 0, 0, 0, 1];
+const AREA: Dimensions = [2, 0, 0, 0, 0, 0, 0];
+const VOLUME: Dimensions = [3, 0, 0, 0, 0, 0, 0];
+const SPEED: Dimensions = [1, 0, -1, 0, 0, 0, 0];
+const FREQUENCY: Dimensions = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: Dimensions = [1, 1, -2, 0, 0, 0, 0];
+const ENERGY: Dimensions = [2, 1, -2, 0, 0, 0, 0];
+const POWER: Dimensions = [2, 1, -3, 0, 0, 0, 0];
+const PRESSURE: Dimensions = [-1, 1, -2, 0, 0, 0, 0];
+const CHARGE: Dimensions = [0, 0, 1, 1, 0, 0, 0];
+const VOLTAGE: Dimensions = [2, 1, -3, -1, 0, 0, 0];
+const RESISTANCE: Dimensions = [2, 1, -3, -2, 0, 0, 0];
+
+// One named unit: `factor` SI base units each, after adding `offset`
+#[derive(Debug, PartialEq)]
+pub struct UnitDef {
+    pub name: &'static str,
+    pub factor: f64,
+    pub offset: f64,
+    pub dimensions: Dimensions,
+}
+
+const fn unit(name: &'static str, factor: f64, dimensions: Dimensions) -> UnitDef {
+    UnitDef { name, factor, offset: 0.0, dimensions }
+}
+
+static UNITS: [UnitDef; 74] = [
+    unit("m", 1.0, LENGTH),
+    unit("km", 1e3, LENGTH),
+    unit("cm", 1e-2, LENGTH),
+    unit("mm", 1e-3, LENGTH),
+    unit("um", 1e-6, LENGTH),
+    unit("nm", 1e-9, LENGTH),
+    unit("inch", 0.0254, LENGTH),
+    unit("ft", 0.3048, LENGTH),
+    unit("yd", 0.9144, LENGTH),
+    unit("mi", 1609.344, LENGTH),
+    unit("nmi", 1852.0, LENGTH),
+    unit("au", 1.495978707e11, LENGTH),
+    unit("ly", 9.4607304725808e15, LENGTH),
+    unit("kg", 1.0, MASS),
+    unit("g", 1e-3, MASS),
+    unit("mg", 1e-6, MASS),
+    unit("t", 1e3, MASS),
+    unit("lb", 0.45359237, MASS),
+    unit("oz", 0.028349523125, MASS),
+    unit("s", 1.0, TIME),
+    unit("ms", 1e-3, TIME),
+    unit("us", 1e-6, TIME),
+    unit("ns", 1e-9, TIME),
+    unit("min", 60.0, TIME),
+    unit("h", 3600.0, TIME),
+    unit("day", 86400.0, TIME),
+    unit("week", 604800.0, TIME),
+    // The Julian year, 365.25 days
+    unit("yr", 31557600.0, TIME),
+    unit("A", 1.0, CURRENT),
+    unit("mA", 1e-3, CURRENT),
+    unit("K", 1.0, TEMPERATURE),
+    UnitDef { name: "degC", factor: 1.0, offset: 273.15, dimensions: TEMPERATURE },
+    UnitDef { name: "degF", factor: 5.0 / 9.0, offset: 459.67, dimensions: TEMPERATURE },
+    unit("mol", 1.0, AMOUNT),
+    unit("bit", 1.0, INFORMATION),
+    unit("B", 8.0, INFORMATION),
+    unit("kB", 8e3, INFORMATION),
+    unit("MB", 8e6, INFORMATION),
+    unit("GB", 8e9, INFORMATION),
+    unit("TB", 8e12, INFORMATION),
+    unit("KiB", 8.0 * 1024.0, INFORMATION),
+    unit("MiB", 8.0 * 1024.0 * 1024.0, INFORMATION),
+    unit("GiB", 8.0 * 1024.0 * 1024.0 * 1024.0, INFORMATION),
+    unit("ha", 1e4, AREA),
+    unit("acre", 4046.8564224, AREA),
+    unit("L", 1e-3, VOLUME),
+    unit("mL", 1e-6, VOLUME),
+    // US liquid gallon
+    unit("gal", 3.785411784e-3, VOLUME),
+    unit("mph", 0.44704, SPEED),
+    unit("kn", 1852.0 / 3600.0, SPEED),
+    unit("Hz", 1.0, FREQUENCY),
+    unit("kHz", 1e3, FREQUENCY),
+    unit("MHz", 1e6, FREQUENCY),
+    unit("GHz", 1e9, FREQUENCY),
+    unit("N", 1.0, FORCE),
+    unit("lbf", 4.4482216152605, FORCE),
+    unit("J", 1.0, ENERGY),
+    unit("kJ", 1e3, ENERGY),
+    unit("cal", 4.184, ENERGY),
+    unit("kcal", 4184.0, ENERGY),
+    unit("Wh", 3600.0, ENERGY),
+    unit("kWh", 3.6e6, ENERGY),
+    unit("eV", 1.602176634e-19, ENERGY),
+    unit("W", 1.0, POWER),
+    unit("kW", 1e3, POWER),
+    unit("hp", 745.6998715822702, POWER),
+    unit("Pa", 1.0, PRESSURE),
+    unit("bar", 1e5, PRESSURE),
+    unit("atm", 101325.0, PRESSURE),
+    unit("kPa", 1e3, PRESSURE),
+    unit("psi", 6894.757293168361, PRESSURE),
+    unit("C", 1.0, CHARGE),
+    unit("V", 1.0, VOLTAGE),
+    unit("ohm", 1.0, RESISTANCE),
+];
+
+// The names of every unit, for tab completion
+pub fn names() -> impl Iterator<Item = &'static str> {
+    UNITS.iter().map(|def| def.name)
+}
+
+fn lookup(name: &str) -> Option<&'static UnitDef> {
+    UNITS.iter().find(|def| def.name == name)
+}
+
+// A product of named units to whole powers; empty is no unit at all
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    terms: Vec<(&'static UnitDef, i8)>,
+}
+
+impl Unit {
+    // The unit with this name in the table
+    pub fn named(name: &str) -> Option<Unit> {
+        lookup(name).map(|def| Unit { terms: vec![(def, 1)] })
+    }
+
+    // The inverse of Display: "km", "m^2", "kg*m/s^2", "s^-1"
+    pub fn parse(text: &str) -> Option<Unit> {
+        let mut unit = Unit { terms: Vec::new() };
+        let mut sign = 1;
+        let mut rest = text.trim();
+        loop {
+            let end = rest.find(['*', '/']).unwrap_or(rest.len());
+            let (name, power) = match rest[..end].split_once('^') {
+                Some((name, power)) => (name, power.parse::<i8>().ok()?),
+                None => (&rest[..end], 1),
+            };
+            unit = unit.mul(&Unit::named(name)?.powi(power.checked_mul(sign)?)?)?;
+            if end == rest.len() {
+                return Some(unit);
+            }
+            sign = if rest[end..].starts_with('/') { -1 } else { 1 };
+            rest = &rest[end + 1..];
+        }
+    }
+
+    pub fn dimensions(&self) -> Dimensions {
+        self.checked_dimensions().expect("mul and powi keep dimensions within i8")
+    }
+
+    fn checked_dimensions(&self) -> Option<Dimensions> {
+        let mut dimensions = NONE;
+        for (def, power) in &self.terms {
+            for (total, exponent) in dimensions.iter_mut().zip(def.dimensions) {
+                *total = exponent.checked_mul(*power).and_then(|product| total.checked_add(product))?;
+            }
+        }
+        Some(dimensions)
+    }
+
+    // The unit if every power and dimension is within -127..=127, so that
+    // dimensions() and Display's negated powers cannot overflow
+    fn checked(self) -> Option<Unit> {
+        let in_range = |power: &i8| *power != i8::MIN;
+        let dimensions = self.checked_dimensions()?;
+        (self.terms.iter().all(|(_, power)| in_range(power)) && dimensions.iter().all(in_range)).then_some(self)
+    }
+
+    pub fn is_dimensionless(&self) -> bool {
+        self.dimensions() == NONE
+    }
+
+    // SI base units in one of this unit
+    pub fn factor(&self) -> f64 {
+        self.terms.iter().map(|(def, power)| def.factor.powi(*power as i32)).product()
+    }
+
+    // Only a lone degC or degF has one
+    fn offset(&self) -> f64 {
+        match self.terms.as_slice() {
+            [(def, 1)] => def.offset,
+            _ => 0.0,
+        }
+    }
+
+    // The product, or None if a power would leave -127..=127 (m^100 * m^100)
+    pub fn mul(&self, other: &Unit) -> Option<Unit> {
+        let mut terms = self.terms.clone();
+        for &(def, power) in &other.terms {
+            match terms.iter_mut().find(|(existing, _)| existing.name == def.name) {
+                Some((_, total)) => *total = total.checked_add(power)?,
+                None => terms.push((def, power)),
+            }
+        }
+        terms.retain(|(_, power)| *power != 0);
+        Unit { terms }.checked()
+    }
+
+    // The nth power, or None if a power would leave -127..=127
+    pub fn powi(&self, n: i8) -> Option<Unit> {
+        let terms = self.terms.iter().map(|&(def, power)| Some((def, power.checked_mul(n)?)));
+        let mut terms = terms.collect::<Option<Vec<_>>>()?;
+        terms.retain(|(_, power)| *power != 0);
+        Unit { terms }.checked()
+    }
+
+    // The square root, if every power is even
+    pub fn sqrt(&self) -> Option<Unit> {
+        if self.terms.iter().any(|(_, power)| power % 2 != 0) {
+            return None;
+        }
+        Some(Unit { terms: self.terms.iter().map(|&(def, power)| (def, power / 2)).collect() })
+    }
+}
+
+// Numerator terms joined by *, then each denominator term after a /: m^2,
+// km/h, kg*m/s^2; with no numerator, negative powers: s^-1
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let term = |name: &str, power: i8| match power {
+            1 => name.to_string(),
+            _ => format!("{}^{}", name, power),
+        };
+        let numerator: Vec<String> = self.terms.iter().filter(|(_, p)| *p > 0).map(|(def, p)| term(def.name, *p)).collect();
+        if numerator.is_empty() {
+            let terms: Vec<String> = self.terms.iter().map(|(def, p)| term(def.name, *p)).collect();
+            return write!(f, "{}", terms.join("*"));
+        }
+        write!(f, "{}", numerator.join("*"))?;
+        for (def, power) in self.terms.iter().filter(|(_, p)| *p < 0) {
+            write!(f, "/{}", term(def.name, -power))?;
+        }
+        Ok(())
+    }
+}
+
+// A real number with a unit
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: Unit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: Unit) -> Self {
+        Quantity { value, unit }
+    }
+
+    pub fn has_dimensions_of(&self, other: &Quantity) -> bool {
+        self.unit.dimensions() == other.unit.dimensions()
+    }
+
+    // The same amount in `unit`, which must have the same dimensions; offsets
+    // apply, so 20 degC is 68 degF
+    pub fn convert(&self, unit: &Unit) -> Quantity {
+        let amount = (self.value + self.unit.offset()) * self.unit.factor() / unit.factor();
+        Quantity::new(round_significant(amount - unit.offset(), amount), unit.clone())
+    }
+
+    // The value in `unit` as a difference, without offsets: 5 degC is 9 degF
+    pub fn value_in(&self, unit: &Unit) -> f64 {
+        let value = self.value * (self.unit.factor() / unit.factor());
+        round_significant(value, value)
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.value, self.unit)
+    }
+}
+
+// Round x to the 15 significant digits an f64 always holds for a number the
+// size of `scale` (or of x, if larger): 527.67 - 459.67 is 68, not
+// 67.99999999999994
+fn round_significant(x: f64, scale: f64) -> f64 {
+    let magnitude = scale.abs().max(x.abs());
+    if !x.is_finite() || magnitude == 0.0 {
+        return x;
+    }
+    let decimals = 14 - magnitude.log10().floor() as i32;
+    let text = if decimals > 0 { format!("{:.*}", decimals as usize, x) } else { format!("{:.14e}", x) };
+    text.parse().unwrap_or(x)
+}
@@ -0,0 +1,631 @@
+// Calculator Values
+//
+// What an expression evaluates to: a Value is Real(f64), Complex(Complex), a
+// pair of f64s re + im*i, in exact mode Exact(Rational), a fraction of big
+// integers (bignum.rs), Quantity, a real with a unit (units.rs), or Matrix
+// (matrix.rs). The imaginary unit is written i, alone or after a number:
+//
+//   (1+2i)*(3-i)    → 5+5i
+//   sqrt(-4)        → 2i
+//   ln(-1)          → 3.141592653589793i
+//   exp(i*pi) + 1   → 0.00000000000000012246467991473532i
+//
+// Design choices:
+// - Real numbers stay plain f64 arithmetic: a line without i gives exactly the
+//   result it gave before the calculator knew about complex numbers
+// - A complex result whose imaginary part is exactly 0 becomes Real again, so
+//   (1+2i)*(1-2i) prints 5 and can be used with % and the other real-only
+//   operations; rounding noise (exp(i*pi)) is shown, not hidden
+// - Real functions switch to the complex version only outside their real
+//   domain: sqrt, ln and the logs of a negative number, asin and acos beyond
+//   ±1, and a negative base raised to a fractional power
+// - Complex functions return principal values, with the branch cuts of the
+//   usual formulas (ln along the negative real axis, asin and acos outside
+//   [-1, 1], atan outside [-i, i] on the imaginary axis)
+// - A complex number to a whole power (up to 1024) is multiplied out by
+//   squaring, so i^2 is exactly -1 rather than exp(2 ln i) with rounding noise
+// - round, floor and ceil act on each part; abs gives the modulus, a Real
+// - Exact values stay exact through + - * /, whole powers, abs, round, floor,
+//   ceil, factorial, ncr and npr; anything else (sqrt, trig, a constant such
+//   as pi, an operand that is already Real) turns them into f64s first. A
+//   Value is therefore Clone but not Copy: an Exact owns its digits
+// - A Quantity combines with the other kinds as a real number would; the
+//   parser rejects what has no meaning (km + s, a unit on a complex number,
+//   m^100 * m^100) before an operator sees it, so the operators themselves
+//   never fail
+// - Matrix arithmetic can fail on shapes, so the parser does it through
+//   matrix.rs instead of these operators, which only negate a matrix; a matrix
+//   in any other operator gives NaN
+// - Trade-off: a hand-written Complex keeps the calculator free of num-complex,
+//   at the cost of its careful overflow handling (|z| uses hypot, but the
+//   division and the inverse trig formulas are the textbook ones)
+
+use crate::bignum::Rational;
+use crate::matrix;
+use crate::units::Quantity;
+use rustnotes_algorithms::math::linear_algebra::Matrix;
+use std::f64::consts::{FRAC_PI_2, LN_10, LN_2};
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// Unit products and powers out of range are the evaluator's to reject
+const UNIT_POWERS: &str = "unit powers checked before the operator";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Complex {
+    pub re: f64,
+    pub im: f64,
+}
+
+impl Complex {
+    pub const I: Complex = Complex { re: 0.0, im: 1.0 };
+
+    pub fn new(re: f64, im: f64) -> Self {
+        Complex { re, im }
+    }
+
+    // Modulus |z|
+    pub fn abs(self) -> f64 {
+        self.re.hypot(self.im)
+    }
+
+    // Argument, in (-π, π]
+    pub fn arg(self) -> f64 {
+        self.im.atan2(self.re)
+    }
+
+    pub fn is_zero(self) -> bool {
+        self.re == 0.0 && self.im == 0.0
+    }
+
+    pub fn exp(self) -> Self {
+        let scale = self.re.exp();
+        Complex::new(scale * self.im.cos(), scale * self.im.sin())
+    }
+
+    // Principal logarithm; ln(0) is -inf
+    pub fn ln(self) -> Self {
+        Complex::new(self.abs().ln(), self.arg())
+    }
+
+    // Principal square root, with a non-negative real part
+    pub fn sqrt(self) -> Self {
+        let r = self.abs();
+        let re = ((r + self.re) / 2.0).sqrt();
+        let im = ((r - self.re) / 2.0).sqrt();
+        Complex::new(re, if self.im < 0.0 { -im } else { im })
+    }
+
+    // self^power as exp(power * ln(self)), with 0^w = 0 for w other than 0
+    pub fn pow(self, power: Complex) -> Self {
+        if self.is_zero() {
+            return if power.is_zero() { Complex::new(1.0, 0.0) } else { Complex::new(0.0, 0.0) };
+        }
+        (power * self.ln()).exp()
+    }
+
+    // self^n by repeated squaring
+    pub fn powi(self, n: i32) -> Self {
+        let mut result = Complex::new(1.0, 0.0);
+        let mut base = self;
+        let mut exponent = n.unsigned_abs();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = result * base;
+            }
+            base = base * base;
+            exponent >>= 1;
+        }
+        if n < 0 {
+            Complex::new(1.0, 0.0) / result
+        } else {
+            result
+        }
+    }
+
+    pub fn sin(self) -> Self {
+        Complex::new(self.re.sin() * self.im.cosh(), self.re.cos() * self.im.sinh())
+    }
+
+    pub fn cos(self) -> Self {
+        Complex::new(self.re.cos() * self.im.cosh(), -self.re.sin() * self.im.sinh())
+    }
+
+    pub fn tan(self) -> Self {
+        self.sin() / self.cos()
+    }
+
+    // -i ln(iz + sqrt(1 - z²))
+    pub fn asin(self) -> Self {
+        let one = Complex::new(1.0, 0.0);
+        -Complex::I * (Complex::I * self + (one - self * self).sqrt()).ln()
+    }
+
+    // π/2 - asin(z)
+    pub fn acos(self) -> Self {
+        Complex::new(FRAC_PI_2, 0.0) - self.asin()
+    }
+
+    // (i/2) (ln(1 - iz) - ln(1 + iz)); infinite at ±i
+    pub fn atan(self) -> Self {
+        let one = Complex::new(1.0, 0.0);
+        let iz = Complex::I * self;
+        Complex::new(0.0, 0.5) * ((one - iz).ln() - (one + iz).ln())
+    }
+}
+
+impl Add for Complex {
+    type Output = Complex;
+    fn add(self, other: Complex) -> Complex {
+        Complex::new(self.re + other.re, self.im + other.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Complex;
+    fn sub(self, other: Complex) -> Complex {
+        Complex::new(self.re - other.re, self.im - other.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Complex;
+    fn mul(self, other: Complex) -> Complex {
+        Complex::new(
+            self.re * other.re - self.im * other.im,
+            self.re * other.im + self.im * other.re,
+        )
+    }
+}
+
+impl Div for Complex {
+    type Output = Complex;
+    fn div(self, other: Complex) -> Complex {
+        let denominator = other.re * other.re + other.im * other.im;
+        Complex::new(
+            (self.re * other.re + self.im * other.im) / denominator,
+            (self.im * other.re - self.re * other.im) / denominator,
+        )
+    }
+}
+
+impl Neg for Complex {
+    type Output = Complex;
+    fn neg(self) -> Complex {
+        Complex::new(-self.re, -self.im)
+    }
+}
+
+impl fmt::Display for Complex {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Value::Complex(*self).fmt(f)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Real(f64),
+    // Never with an imaginary part of exactly 0; see Value::complex
+    Complex(Complex),
+    // Only produced in exact mode, from literals and the exact operations
+    Exact(Rational),
+    // Never dimensionless; see From<Quantity>
+    Quantity(Quantity),
+    Matrix(Matrix),
+}
+
+impl From<f64> for Value {
+    fn from(x: f64) -> Self {
+        Value::Real(x)
+    }
+}
+
+impl From<Rational> for Value {
+    fn from(r: Rational) -> Self {
+        Value::Exact(r)
+    }
+}
+
+impl From<Complex> for Value {
+    fn from(z: Complex) -> Self {
+        if z.im == 0.0 {
+            Value::Real(z.re)
+        } else {
+            Value::Complex(z)
+        }
+    }
+}
+
+// A quantity whose units cancel (km/m) is the plain number it amounts to
+impl From<Quantity> for Value {
+    fn from(q: Quantity) -> Self {
+        if q.unit.is_dimensionless() {
+            Value::Real(q.value * q.unit.factor())
+        } else {
+            Value::Quantity(q)
+        }
+    }
+}
+
+impl Value {
+    pub fn complex(re: f64, im: f64) -> Self {
+        Complex::new(re, im).into()
+    }
+
+    pub fn to_complex(&self) -> Complex {
+        match self {
+            Value::Real(x) => Complex::new(*x, 0.0),
+            Value::Complex(z) => *z,
+            Value::Exact(r) => Complex::new(r.to_f64(), 0.0),
+            Value::Quantity(q) => Complex::new(q.value, 0.0),
+            Value::Matrix(_) => Complex::new(f64::NAN, f64::NAN),
+        }
+    }
+
+    // The value as an f64, if it has no imaginary part and no unit
+    pub fn as_real(&self) -> Option<f64> {
+        match self {
+            Value::Real(x) => Some(*x),
+            Value::Complex(_) | Value::Quantity(_) | Value::Matrix(_) => None,
+            Value::Exact(r) => Some(r.to_f64()),
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Value::Exact(r) => r.is_zero(),
+            Value::Quantity(q) => q.value == 0.0,
+            Value::Matrix(_) => false,
+            _ => self.to_complex().is_zero(),
+        }
+    }
+
+    pub fn is_nan(&self) -> bool {
+        if let Value::Matrix(m) = self {
+            return (0..m.rows()).any(|r| m.row(r).iter().any(|x| x.is_nan()));
+        }
+        let z = self.to_complex();
+        z.re.is_nan() || z.im.is_nan()
+    }
+
+    // A whole number in base 2, 8 or 16 with its 0b, 0o or 0x prefix (-0xff),
+    // or None if the value is not a whole number
+    pub fn to_radix(&self, radix: u32) -> Option<String> {
+        let whole = match self {
+            Value::Exact(r) => r.clone(),
+            Value::Real(x) if x.fract() == 0.0 => Rational::from_f64(*x)?,
+            _ => return None,
+        };
+        if !whole.is_integer() {
+            return None;
+        }
+        let prefix = match radix {
+            2 => "0b",
+            8 => "0o",
+            16 => "0x",
+            _ => "",
+        };
+        let digits = whole.numer().abs().to_str_radix(radix);
+        let sign = if whole.is_negative() { "-" } else { "" };
+        Some(format!("{}{}{}", sign, prefix, digits))
+    }
+
+    // The value as an i128, if it is a whole number in range
+    pub fn to_integer(&self) -> Option<i128> {
+        match self {
+            Value::Exact(r) if r.is_integer() => r.numer().to_i128(),
+            // 2^127 itself is the first f64 out of range
+            Value::Real(x) if x.fract() == 0.0 && x.abs() < 2f64.powi(127) => Some(*x as i128),
+            Value::Real(x) if *x == -(2f64.powi(127)) => Some(i128::MIN),
+            _ => None,
+        }
+    }
+
+    // An Exact as the nearest Real; other values unchanged
+    pub fn inexact(self) -> Self {
+        match self {
+            Value::Exact(r) => Value::Real(r.to_f64()),
+            _ => self,
+        }
+    }
+
+    // Apply a real function to each part, to the number of a quantity, or to
+    // each entry of a matrix
+    pub fn map_parts(self, f: fn(f64) -> f64) -> Self {
+        match self.inexact() {
+            Value::Complex(z) => Value::complex(f(z.re), f(z.im)),
+            Value::Quantity(q) => Value::Quantity(Quantity::new(f(q.value), q.unit)),
+            Value::Matrix(m) => Value::Matrix(matrix::map(&m, f)),
+            other => Value::Real(f(other.as_real().unwrap_or(f64::NAN))),
+        }
+    }
+
+    // The real function when it is defined for x, else the complex one
+    fn real_or_complex(self, real: fn(f64) -> f64, in_domain: fn(f64) -> bool, complex: fn(Complex) -> Complex) -> Self {
+        match self.inexact() {
+            Value::Real(x) if in_domain(x) => Value::Real(real(x)),
+            other => complex(other.to_complex()).into(),
+        }
+    }
+
+    // An exact whole power when both sides are exact and the result is not
+    // too large (Rational::pow), else in f64
+    pub fn pow(self, power: Value) -> Self {
+        if let (Value::Quantity(q), Some(n)) = (&self, power.to_integer().and_then(|n| i8::try_from(n).ok())) {
+            return Quantity::new(q.value.powi(n as i32), q.unit.powi(n).expect(UNIT_POWERS)).into();
+        }
+        if let (Value::Exact(base), Value::Exact(exponent)) = (&self, &power) {
+            let whole = exponent.numer().to_i64().filter(|_| exponent.is_integer());
+            if let Some(result) = whole.and_then(|n| base.pow(n)) {
+                return Value::Exact(result);
+            }
+        }
+        match (self.inexact(), power.inexact()) {
+            (Value::Real(base), Value::Real(exponent)) if base >= 0.0 || exponent.fract() == 0.0 => {
+                Value::Real(base.powf(exponent))
+            }
+            (Value::Complex(z), Value::Real(n)) if n.fract() == 0.0 && n.abs() <= 1024.0 => z.powi(n as i32).into(),
+            (base, power) => base.to_complex().pow(power.to_complex()).into(),
+        }
+    }
+
+    // A quantity's unit must have even powers (Unit::sqrt); the parser checks
+    pub fn sqrt(self) -> Self {
+        if let Value::Quantity(q) = &self {
+            if let Some(unit) = q.unit.sqrt() {
+                return Quantity::new(q.value.sqrt(), unit).into();
+            }
+        }
+        self.real_or_complex(f64::sqrt, |x| x >= 0.0, Complex::sqrt)
+    }
+
+    pub fn exp(self) -> Self {
+        self.real_or_complex(f64::exp, |_| true, Complex::exp)
+    }
+
+    pub fn ln(self) -> Self {
+        self.real_or_complex(f64::ln, |x| x > 0.0, Complex::ln)
+    }
+
+    pub fn log2(self) -> Self {
+        self.real_or_complex(f64::log2, |x| x > 0.0, |z| z.ln() / Complex::new(LN_2, 0.0))
+    }
+
+    pub fn log10(self) -> Self {
+        self.real_or_complex(f64::log10, |x| x > 0.0, |z| z.ln() / Complex::new(LN_10, 0.0))
+    }
+
+    pub fn sin(self) -> Self {
+        self.real_or_complex(f64::sin, |_| true, Complex::sin)
+    }
+
+    pub fn cos(self) -> Self {
+        self.real_or_complex(f64::cos, |_| true, Complex::cos)
+    }
+
+    pub fn tan(self) -> Self {
+        self.real_or_complex(f64::tan, |_| true, Complex::tan)
+    }
+
+    pub fn asin(self) -> Self {
+        self.real_or_complex(f64::asin, |x| (-1.0..=1.0).contains(&x), Complex::asin)
+    }
+
+    pub fn acos(self) -> Self {
+        self.real_or_complex(f64::acos, |x| (-1.0..=1.0).contains(&x), Complex::acos)
+    }
+
+    pub fn atan(self) -> Self {
+        self.real_or_complex(f64::atan, |_| true, Complex::atan)
+    }
+
+    pub fn round(self) -> Self {
+        match self {
+            Value::Exact(r) => Value::Exact(r.round()),
+            _ => self.map_parts(f64::round),
+        }
+    }
+
+    pub fn floor(self) -> Self {
+        match self {
+            Value::Exact(r) => Value::Exact(r.floor()),
+            _ => self.map_parts(f64::floor),
+        }
+    }
+
+    pub fn ceil(self) -> Self {
+        match self {
+            Value::Exact(r) => Value::Exact(r.ceil()),
+            _ => self.map_parts(f64::ceil),
+        }
+    }
+
+    pub fn abs(self) -> Self {
+        match self {
+            Value::Real(x) => Value::Real(x.abs()),
+            Value::Complex(z) => Value::Real(z.abs()),
+            Value::Exact(r) => Value::Exact(r.abs()),
+            Value::Quantity(q) => Value::Quantity(Quantity::new(q.value.abs(), q.unit)),
+            Value::Matrix(m) => Value::Matrix(matrix::map(&m, f64::abs)),
+        }
+    }
+
+    // n! for a whole n >= 0, None for anything else. Exact when n is, unless
+    // the result passes bignum::MAX_BITS; in f64 it is inf past 170!
+    pub fn factorial(self) -> Option<Self> {
+        if let Value::Exact(r) = &self {
+            if let Some(result) = r.factorial() {
+                return Some(Value::Exact(result));
+            }
+        }
+        match self.inexact() {
+            Value::Real(n) if n >= 0.0 && n.fract() == 0.0 => {
+                let mut product: f64 = 1.0;
+                let mut k = 2.0;
+                while k <= n && product.is_finite() {
+                    product *= k;
+                    k += 1.0;
+                }
+                Some(Value::Real(product))
+            }
+            _ => None,
+        }
+    }
+
+    // ncr: the unordered ways to pick k of n, for whole n and k >= 0 (0 when
+    // k > n); None for anything else. Exact as for factorial
+    pub fn combinations(self, k: Value) -> Option<Self> {
+        if let (Value::Exact(n), Value::Exact(k)) = (&self, &k) {
+            if let Some(count) = Rational::combinations(n, k) {
+                return Some(Value::Exact(count));
+            }
+        }
+        let (n, k) = whole_pair(self, k)?;
+        let k = k.min(n - k);
+        let mut count: f64 = 1.0;
+        let mut i = 1.0;
+        while i <= k && count.is_finite() {
+            count = count * (n - k + i) / i;
+            i += 1.0;
+        }
+        Some(Value::Real(if k < 0.0 { 0.0 } else { count.round() }))
+    }
+
+    // npr: the ordered ways to pick k of n, n! / (n-k)!; as for combinations
+    pub fn permutations(self, k: Value) -> Option<Self> {
+        if let (Value::Exact(n), Value::Exact(k)) = (&self, &k) {
+            if let Some(count) = Rational::permutations(n, k) {
+                return Some(Value::Exact(count));
+            }
+        }
+        let (n, k) = whole_pair(self, k)?;
+        if k > n {
+            return Some(Value::Real(0.0));
+        }
+        let mut count: f64 = 1.0;
+        let mut factor = n - k + 1.0;
+        while factor <= n && count.is_finite() {
+            count *= factor;
+            factor += 1.0;
+        }
+        Some(Value::Real(count))
+    }
+}
+
+// n and k as f64s, if both are whole numbers >= 0
+fn whole_pair(n: Value, k: Value) -> Option<(f64, f64)> {
+    let whole = |value: Value| value.inexact().as_real().filter(|x| *x >= 0.0 && x.fract() == 0.0);
+    Some((whole(n)?, whole(k)?))
+}
+
+impl Add for Value {
+    type Output = Value;
+    fn add(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a + b),
+            (Value::Quantity(a), Value::Quantity(b)) => Quantity::new(a.value + b.value_in(&a.unit), a.unit).into(),
+            (a, b) => match (a.inexact(), b.inexact()) {
+                (Value::Real(a), Value::Real(b)) => Value::Real(a + b),
+                (a, b) => (a.to_complex() + b.to_complex()).into(),
+            },
+        }
+    }
+}
+
+impl Sub for Value {
+    type Output = Value;
+    fn sub(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a - b),
+            (Value::Quantity(a), Value::Quantity(b)) => Quantity::new(a.value - b.value_in(&a.unit), a.unit).into(),
+            (a, b) => match (a.inexact(), b.inexact()) {
+                (Value::Real(a), Value::Real(b)) => Value::Real(a - b),
+                (a, b) => (a.to_complex() - b.to_complex()).into(),
+            },
+        }
+    }
+}
+
+impl Mul for Value {
+    type Output = Value;
+    fn mul(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) => Value::Exact(a * b),
+            (Value::Quantity(a), Value::Quantity(b)) => Quantity::new(a.value * b.value, a.unit.mul(&b.unit).expect(UNIT_POWERS)).into(),
+            (Value::Quantity(q), x) | (x, Value::Quantity(q)) => {
+                Quantity::new(q.value * x.inexact().as_real().unwrap_or(f64::NAN), q.unit).into()
+            }
+            (a, b) => match (a.inexact(), b.inexact()) {
+                (Value::Real(a), Value::Real(b)) => Value::Real(a * b),
+                (a, b) => (a.to_complex() * b.to_complex()).into(),
+            },
+        }
+    }
+}
+
+impl Div for Value {
+    type Output = Value;
+    // An exact division by zero goes through f64 (±inf or NaN) rather than panic
+    fn div(self, other: Value) -> Value {
+        match (self, other) {
+            (Value::Exact(a), Value::Exact(b)) if !b.is_zero() => Value::Exact(a / b),
+            (Value::Quantity(a), Value::Quantity(b)) => {
+                Quantity::new(a.value / b.value, a.unit.mul(&b.unit.powi(-1).expect(UNIT_POWERS)).expect(UNIT_POWERS)).into()
+            }
+            (Value::Quantity(q), x) => Quantity::new(q.value / x.inexact().as_real().unwrap_or(f64::NAN), q.unit).into(),
+            (x, Value::Quantity(q)) => {
+                Quantity::new(x.inexact().as_real().unwrap_or(f64::NAN) / q.value, q.unit.powi(-1).expect(UNIT_POWERS)).into()
+            }
+            (a, b) => match (a.inexact(), b.inexact()) {
+                (Value::Real(a), Value::Real(b)) => Value::Real(a / b),
+                (a, b) => (a.to_complex() / b.to_complex()).into(),
+            },
+        }
+    }
+}
+
+impl Neg for Value {
+    type Output = Value;
+    fn neg(self) -> Value {
+        match self {
+            Value::Real(x) => Value::Real(-x),
+            Value::Complex(z) => Value::Complex(-z),
+            Value::Exact(r) => Value::Exact(-r),
+            Value::Quantity(q) => Value::Quantity(Quantity::new(-q.value, q.unit)),
+            Value::Matrix(m) => Value::Matrix(m.scale(-1.0)),
+        }
+    }
+}
+
+// a+bi, or bi when a is 0; each part as f64 displays itself, and an Exact as
+// 42 or 22/7, a quantity as 5.3 km, and a matrix as its literal,
+// [[1, 2], [3, 4]]
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Real(x) => write!(f, "{}", x),
+            Value::Exact(r) => write!(f, "{}", r),
+            Value::Quantity(q) => write!(f, "{}", q),
+            Value::Matrix(m) => {
+                let rows: Vec<String> = (0..m.rows())
+                    .map(|r| format!("[{}]", m.row(r).iter().map(f64::to_string).collect::<Vec<_>>().join(", ")))
+                    .collect();
+                write!(f, "[{}]", rows.join(", "))
+            }
+            Value::Complex(z) => {
+                let text = join_parts(&z.re.to_string(), z.im.is_sign_negative(), &z.im.abs().to_string(), z.re == 0.0);
+                f.write_str(&text)
+            }
+        }
+    }
+}
+
+// Put formatted real and imaginary parts together as a+bi, a-bi or bi
+pub(crate) fn join_parts(re: &str, im_negative: bool, im_abs: &str, pure_imaginary: bool) -> String {
+    match (pure_imaginary, im_negative) {
+        (true, false) => format!("{}i", im_abs),
+        (true, true) => format!("-{}i", im_abs),
+        (false, false) => format!("{}+{}i", re, im_abs),
+        (false, true) => format!("{}-{}i", re, im_abs),
+    }
+}
@@ -0,0 +1,130 @@
+// Hash Map Benchmarks
+//
+// HashMapOa (open addressing) and HashMapChained (separate chaining) against
+// std's HashMap: inserting n keys, looking up hits and misses, removing every
+// key, and iterating. Run with:
+//
+//   cargo bench -p rustnotes-collections --bench hash_maps
+//
+// Design choices:
+// - All three maps use std's RandomState, so the numbers compare table
+//   layouts and probing rather than hash functions
+// - Keys are shuffled with the crate's XorShift64Star and a fixed seed; misses
+//   look up keys n..2n, which are never inserted
+// - Removal uses iter_batched with a freshly built map, so only the removes
+//   are timed
+// - Trade-off: maps start empty and grow, so insert includes every resize;
+//   pre-sized tables would time the probing alone
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rustnotes_collections::hashing::hash_map_chaining::HashMapChained;
+use rustnotes_collections::hashing::hash_map_open_addressing::HashMapOa;
+use rustnotes_collections::random::{Rng, XorShift64Star};
+use std::collections::HashMap;
+
+const SIZES: [u64; 2] = [1_000, 10_000];
+
+fn shuffled(n: u64) -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..n).collect();
+    XorShift64Star::seed_from_u64(7).shuffle(&mut keys);
+    keys
+}
+
+fn open_addressing(keys: &[u64]) -> HashMapOa<u64, u64> {
+    let mut map = HashMapOa::new();
+    keys.iter().for_each(|&key| {
+        map.insert(key, key);
+    });
+    map
+}
+
+fn chained(keys: &[u64]) -> HashMapChained<u64, u64> {
+    let mut map = HashMapChained::new();
+    keys.iter().for_each(|&key| {
+        map.insert(key, key);
+    });
+    map
+}
+
+fn std_map(keys: &[u64]) -> HashMap<u64, u64> {
+    keys.iter().map(|&key| (key, key)).collect()
+}
+
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_insert");
+    for n in SIZES {
+        let keys = shuffled(n);
+        group.bench_with_input(BenchmarkId::new("HashMapOa", n), &keys, |b, keys| b.iter(|| open_addressing(keys)));
+        group.bench_with_input(BenchmarkId::new("HashMapChained", n), &keys, |b, keys| b.iter(|| chained(keys)));
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &keys, |b, keys| b.iter(|| std_map(keys)));
+    }
+    group.finish();
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_lookup");
+    for n in SIZES {
+        let keys = shuffled(n);
+        // Half hits, half misses
+        let probes: Vec<u64> = keys.iter().copied().chain(n..2 * n).collect();
+        let map = open_addressing(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMapOa", n), &probes, |b, probes| {
+            b.iter(|| probes.iter().filter(|key| map.contains_key(black_box(key))).count())
+        });
+        let map = chained(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMapChained", n), &probes, |b, probes| {
+            b.iter(|| probes.iter().filter(|key| map.contains_key(black_box(key))).count())
+        });
+        let map = std_map(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &probes, |b, probes| {
+            b.iter(|| probes.iter().filter(|key| map.contains_key(black_box(key))).count())
+        });
+    }
+    group.finish();
+}
+
+fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_remove");
+    for n in SIZES {
+        let keys = shuffled(n);
+        group.bench_with_input(BenchmarkId::new("HashMapOa", n), &keys, |b, keys| {
+            b.iter_batched(
+                || open_addressing(keys),
+                |mut map| keys.iter().for_each(|key| assert!(map.remove(key).is_some())),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("HashMapChained", n), &keys, |b, keys| {
+            b.iter_batched(
+                || chained(keys),
+                |mut map| keys.iter().for_each(|key| assert!(map.remove(key).is_some())),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &keys, |b, keys| {
+            b.iter_batched(
+                || std_map(keys),
+                |mut map| keys.iter().for_each(|key| assert!(map.remove(key).is_some())),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("map_iterate");
+    for n in SIZES {
+        let keys = shuffled(n);
+        let map = open_addressing(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMapOa", n), &n, |b, _| b.iter(|| map.values().sum::<u64>()));
+        let map = chained(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMapChained", n), &n, |b, _| b.iter(|| map.values().sum::<u64>()));
+        let map = std_map(&keys);
+        group.bench_with_input(BenchmarkId::new("HashMap", n), &n, |b, _| b.iter(|| map.values().sum::<u64>()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert, lookup, remove, iterate);
+criterion_main!(benches);
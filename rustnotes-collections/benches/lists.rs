@@ -0,0 +1,127 @@
+// List Benchmarks
+//
+// LinkedList and DoubleLinkedList against std's VecDeque on the workloads the
+// list headers talk about: pushing and popping at the ends, a full iteration,
+// and a linear search. Run with:
+//
+//   cargo bench -p rustnotes-collections --bench lists
+//
+// Design choices:
+// - Each workload runs at two sizes, so the per-element cost and how it
+//   scales both show up in the report
+// - Pops use iter_batched with a freshly built list, so only the pops are timed
+// - LinkedList has no push_end, so it only appears in the front workloads
+// - Trade-off: u64 elements keep the numbers about node overhead and pointer
+//   chasing; large elements would narrow the gap to VecDeque
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rustnotes_collections::lists::double_linked_list::DoubleLinkedList;
+use rustnotes_collections::lists::linked_list::LinkedList;
+use std::collections::VecDeque;
+
+const SIZES: [u64; 2] = [1_000, 10_000];
+
+fn linked_list(n: u64) -> LinkedList<u64> {
+    let mut list = LinkedList::new();
+    (0..n).for_each(|i| list.push(i));
+    list
+}
+
+fn double_linked_list(n: u64) -> DoubleLinkedList<u64> {
+    let mut list = DoubleLinkedList::new();
+    (0..n).for_each(|i| list.push_end(i));
+    list
+}
+
+fn vec_deque(n: u64) -> VecDeque<u64> {
+    (0..n).collect()
+}
+
+fn push_front(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_push_front");
+    for n in SIZES {
+        group.bench_with_input(BenchmarkId::new("LinkedList", n), &n, |b, &n| b.iter(|| linked_list(n)));
+        group.bench_with_input(BenchmarkId::new("DoubleLinkedList", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut list = DoubleLinkedList::new();
+                (0..n).for_each(|i| list.push(i));
+                list
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut deque = VecDeque::new();
+                (0..n).for_each(|i| deque.push_front(i));
+                deque
+            })
+        });
+    }
+    group.finish();
+}
+
+fn push_back(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_push_back");
+    for n in SIZES {
+        group.bench_with_input(BenchmarkId::new("DoubleLinkedList", n), &n, |b, &n| b.iter(|| double_linked_list(n)));
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, &n| {
+            b.iter(|| {
+                let mut deque = VecDeque::new();
+                (0..n).for_each(|i| deque.push_back(i));
+                deque
+            })
+        });
+    }
+    group.finish();
+}
+
+fn pop_front(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_pop_front");
+    for n in SIZES {
+        group.bench_with_input(BenchmarkId::new("LinkedList", n), &n, |b, &n| {
+            b.iter_batched(|| linked_list(n), |mut list| while list.pop().is_some() {}, BatchSize::SmallInput)
+        });
+        group.bench_with_input(BenchmarkId::new("DoubleLinkedList", n), &n, |b, &n| {
+            b.iter_batched(|| double_linked_list(n), |mut list| while list.pop().is_some() {}, BatchSize::SmallInput)
+        });
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, &n| {
+            b.iter_batched(|| vec_deque(n), |mut deque| while deque.pop_front().is_some() {}, BatchSize::SmallInput)
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("list_iterate");
+    for n in SIZES {
+        let list = linked_list(n);
+        group.bench_with_input(BenchmarkId::new("LinkedList", n), &n, |b, _| b.iter(|| list.iter().sum::<u64>()));
+        let list = double_linked_list(n);
+        group.bench_with_input(BenchmarkId::new("DoubleLinkedList", n), &n, |b, _| {
+            b.iter(|| {
+                let mut sum = 0;
+                list.for_each(|value| sum += value);
+                sum
+            })
+        });
+        let deque = vec_deque(n);
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, _| b.iter(|| deque.iter().sum::<u64>()));
+    }
+    group.finish();
+}
+
+fn contains(c: &mut Criterion) {
+    // Search for a value that is not there, so every element is visited
+    let mut group = c.benchmark_group("list_contains");
+    for n in SIZES {
+        let list = linked_list(n);
+        group.bench_with_input(BenchmarkId::new("LinkedList", n), &n, |b, &n| b.iter(|| list.contains(black_box(&n))));
+        let list = double_linked_list(n);
+        group.bench_with_input(BenchmarkId::new("DoubleLinkedList", n), &n, |b, &n| b.iter(|| list.has(black_box(&n))));
+        let deque = vec_deque(n);
+        group.bench_with_input(BenchmarkId::new("VecDeque", n), &n, |b, &n| b.iter(|| deque.contains(black_box(&n))));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, push_front, push_back, pop_front, iterate, contains);
+criterion_main!(benches);
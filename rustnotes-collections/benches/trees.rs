@@ -0,0 +1,98 @@
+// Tree Benchmarks
+//
+// AvlTree against std's BTreeSet: inserting a shuffled run of keys, looking
+// every key up, removing them all, and iterating in order. Run with:
+//
+//   cargo bench -p rustnotes-collections --bench trees
+//
+// Design choices:
+// - Keys are 0..n shuffled with the crate's own XorShift64Star and a fixed
+//   seed, so every run (and both structures) sees the same order
+// - Removal uses iter_batched with a freshly built tree, so only the removes
+//   are timed
+// - Trade-off: u64 keys compare in one instruction; with expensive keys
+//   (strings) the B-tree's fewer comparisons per level matter less than here
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use rustnotes_collections::random::{Rng, XorShift64Star};
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use std::collections::BTreeSet;
+
+const SIZES: [u64; 2] = [1_000, 10_000];
+
+fn shuffled(n: u64) -> Vec<u64> {
+    let mut keys: Vec<u64> = (0..n).collect();
+    XorShift64Star::seed_from_u64(42).shuffle(&mut keys);
+    keys
+}
+
+fn avl_tree(keys: &[u64]) -> AvlTree<u64> {
+    let mut tree = AvlTree::new();
+    keys.iter().for_each(|&key| tree.insert(key));
+    tree
+}
+
+fn insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_insert");
+    for n in SIZES {
+        let keys = shuffled(n);
+        group.bench_with_input(BenchmarkId::new("AvlTree", n), &keys, |b, keys| b.iter(|| avl_tree(keys)));
+        group.bench_with_input(BenchmarkId::new("BTreeSet", n), &keys, |b, keys| {
+            b.iter(|| keys.iter().copied().collect::<BTreeSet<u64>>())
+        });
+    }
+    group.finish();
+}
+
+fn lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_lookup");
+    for n in SIZES {
+        let keys = shuffled(n);
+        let tree = avl_tree(&keys);
+        group.bench_with_input(BenchmarkId::new("AvlTree", n), &keys, |b, keys| {
+            b.iter(|| keys.iter().filter(|key| tree.contains(black_box(key))).count())
+        });
+        let set: BTreeSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("BTreeSet", n), &keys, |b, keys| {
+            b.iter(|| keys.iter().filter(|key| set.contains(black_box(key))).count())
+        });
+    }
+    group.finish();
+}
+
+fn remove(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_remove");
+    for n in SIZES {
+        let keys = shuffled(n);
+        group.bench_with_input(BenchmarkId::new("AvlTree", n), &keys, |b, keys| {
+            b.iter_batched(
+                || avl_tree(keys),
+                |mut tree| keys.iter().for_each(|key| assert!(tree.remove(key))),
+                BatchSize::SmallInput,
+            )
+        });
+        group.bench_with_input(BenchmarkId::new("BTreeSet", n), &keys, |b, keys| {
+            b.iter_batched(
+                || keys.iter().copied().collect::<BTreeSet<u64>>(),
+                |mut set| keys.iter().for_each(|key| assert!(set.remove(key))),
+                BatchSize::SmallInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn iterate(c: &mut Criterion) {
+    let mut group = c.benchmark_group("tree_iterate");
+    for n in SIZES {
+        let keys = shuffled(n);
+        let tree = avl_tree(&keys);
+        group.bench_with_input(BenchmarkId::new("AvlTree", n), &n, |b, _| b.iter(|| tree.iter().sum::<u64>()));
+        let set: BTreeSet<u64> = keys.iter().copied().collect();
+        group.bench_with_input(BenchmarkId::new("BTreeSet", n), &n, |b, _| b.iter(|| set.iter().sum::<u64>()));
+    }
+    group.finish();
+}
+
+criterion_group!(benches, insert, lookup, remove, iterate);
+criterion_main!(benches);
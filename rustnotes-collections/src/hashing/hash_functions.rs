@@ -0,0 +1,485 @@
+// Hash Functions
+//
+// Hash functions written from scratch for the other notes in this collection.
+// SHA-256 is the cryptographic one: it turns any amount of data into a 32-byte
+// digest, and nobody knows how to find two inputs with the same digest, so a
+// digest can stand in for the data it came from (see trees::merkle_tree).
+//
+// SHA-256 in brief (FIPS 180-4):
+// - Pad the message with a 1 bit, zeros, and the 64-bit message length so it
+//   fills whole 64-byte blocks
+// - Start from eight fixed 32-bit words, and for each block expand its 16 words
+//   into 64 and run 64 rounds of additions, rotations and bit mixing
+// - The final eight words, big-endian, are the digest
+//
+// The others are fast, non-cryptographic hashes for checksums and hash tables:
+// - CRC-32 (IEEE): polynomial division over GF(2), one table lookup per byte;
+//   catches accidental corruption such as burst errors, but is linear, so
+//   anyone can forge data with a chosen checksum
+// - FNV-1a (64-bit): for each byte, XOR it in and multiply by a prime; tiny
+//   and quick for short keys
+// - SipHash-2-4: a keyed hash (the default hasher in std's HashMap). Without
+//   the 128-bit key an attacker cannot predict which keys collide, which stops
+//   hash-flooding attacks on tables fed untrusted input
+//
+// Design choices:
+// - Sha256 is incremental (update() any number of times, then finalize()), so
+//   large inputs can be hashed in pieces; sha256() is the one-shot helper
+// - Digests are plain [u8; 32] arrays; to_hex() formats them for display
+// - Crc32, Fnv1a and SipHasher24 implement core::hash::Hasher, so they plug into
+//   HashMapOa, HashMapChained and BloomFilter through a BuildHasher:
+//   BuildCrc32 and BuildFnv1a (no state), SipBuildHasher (carries the key)
+// - The CRC table is computed at compile time by a const fn
+// - Trade-off: straightforward, unoptimized implementations meant for reading;
+//   they are correct but several times slower than tuned or hardware versions
+
+use alloc::string::String;
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::vec;
+use core::hash::{BuildHasher, BuildHasherDefault, Hasher};
+
+pub const DIGEST_LEN: usize = 32;
+pub type Digest = [u8; DIGEST_LEN];
+
+// First 32 bits of the fractional parts of the cube roots of the first 64 primes
+const ROUND_CONSTANTS: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// First 32 bits of the fractional parts of the square roots of the first 8 primes
+const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+#[derive(Clone)]
+pub struct Sha256 {
+    state: [u32; 8],
+    buffer: [u8; 64],
+    buffered: usize,
+    length: u64, // Total bytes hashed so far
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Sha256 { state: INITIAL_STATE, buffer: [0; 64], buffered: 0, length: 0 }
+    }
+
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length += data.len() as u64;
+        // Top up a partially filled block first
+        if self.buffered > 0 {
+            let take = data.len().min(64 - self.buffered);
+            self.buffer[self.buffered..self.buffered + take].copy_from_slice(&data[..take]);
+            self.buffered += take;
+            data = &data[take..];
+            if self.buffered < 64 {
+                return;
+            }
+            let block = self.buffer;
+            self.compress(&block);
+            self.buffered = 0;
+        }
+        let mut blocks = data.chunks_exact(64);
+        for block in &mut blocks {
+            self.compress(block);
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffered = rest.len();
+    }
+
+    pub fn finalize(mut self) -> Digest {
+        let bit_length = self.length.wrapping_mul(8);
+        // 0x80, then zeros until 8 bytes short of a block boundary, then the length
+        self.update(&[0x80]);
+        let zeros = (64 + 56 - self.buffered) % 64;
+        self.update(&[0u8; 64][..zeros]);
+        self.update(&bit_length.to_be_bytes());
+        debug_assert_eq!(self.buffered, 0);
+
+        let mut digest = [0u8; DIGEST_LEN];
+        for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state.iter()) {
+            chunk.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn compress(&mut self, block: &[u8]) {
+        let mut schedule = [0u32; 64];
+        for (i, word) in block.chunks_exact(4).enumerate() {
+            schedule[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = schedule[i - 15].rotate_right(7) ^ schedule[i - 15].rotate_right(18) ^ (schedule[i - 15] >> 3);
+            let s1 = schedule[i - 2].rotate_right(17) ^ schedule[i - 2].rotate_right(19) ^ (schedule[i - 2] >> 10);
+            schedule[i] = schedule[i - 16].wrapping_add(s0).wrapping_add(schedule[i - 7]).wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = self.state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let choose = (e & f) ^ (!e & g);
+            let t1 = h.wrapping_add(s1).wrapping_add(choose).wrapping_add(ROUND_CONSTANTS[i]).wrapping_add(schedule[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let majority = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(majority);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, value) in self.state.iter_mut().zip([a, b, c, d, e, f, g, h].iter()) {
+            *word = word.wrapping_add(*value);
+        }
+    }
+}
+
+pub fn sha256(data: &[u8]) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update(data);
+    hasher.finalize()
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+const CRC32_TABLE: [u32; 256] = crc32_table();
+
+// Remainder of each byte value divided by the reflected polynomial 0xEDB88320
+const fn crc32_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+    let mut i = 0;
+    while i < 256 {
+        let mut crc = i as u32;
+        let mut bit = 0;
+        while bit < 8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+            bit += 1;
+        }
+        table[i] = crc;
+        i += 1;
+    }
+    table
+}
+
+#[derive(Clone)]
+pub struct Crc32 {
+    state: u32,
+}
+
+impl Default for Crc32 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Crc32 {
+    pub fn new() -> Self {
+        Crc32 { state: 0xFFFF_FFFF }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        for &byte in data {
+            self.state = (self.state >> 8) ^ CRC32_TABLE[((self.state ^ byte as u32) & 0xFF) as usize];
+        }
+    }
+
+    // Checksum of everything so far; more data can still be added
+    pub fn value(&self) -> u32 {
+        !self.state
+    }
+}
+
+impl Hasher for Crc32 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.value() as u64
+    }
+}
+
+pub fn crc32(data: &[u8]) -> u32 {
+    let mut crc = Crc32::new();
+    crc.update(data);
+    crc.value()
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xCBF2_9CE4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01B3;
+
+#[derive(Clone)]
+pub struct Fnv1a {
+    state: u64,
+}
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a { state: FNV_OFFSET_BASIS }
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.state = (self.state ^ byte as u64).wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.state
+    }
+}
+
+pub fn fnv1a(data: &[u8]) -> u64 {
+    let mut hasher = Fnv1a::default();
+    hasher.write(data);
+    hasher.finish()
+}
+
+// SipHash with 2 compression rounds per 8-byte word and 4 finalization rounds
+#[derive(Clone)]
+pub struct SipHasher24 {
+    v: [u64; 4],
+    tail: u64,       // Bytes not yet forming a whole word, little-endian
+    tail_len: usize,
+    length: usize,
+}
+
+// Little-endian u64 from up to 8 bytes
+fn le_u64(bytes: &[u8]) -> u64 {
+    bytes.iter().rev().fold(0, |word, &byte| word << 8 | byte as u64)
+}
+
+impl SipHasher24 {
+    pub fn new(key: &[u8; 16]) -> Self {
+        let (k0, k1) = (le_u64(&key[..8]), le_u64(&key[8..]));
+        SipHasher24 {
+            // "somepseudorandomlygeneratedbytes"
+            v: [k0 ^ 0x736F_6D65_7073_6575, k1 ^ 0x646F_7261_6E64_6F6D,
+                k0 ^ 0x6C79_6765_6E65_7261, k1 ^ 0x7465_6462_7974_6573],
+            tail: 0,
+            tail_len: 0,
+            length: 0,
+        }
+    }
+
+    fn round(&mut self) {
+        let [mut v0, mut v1, mut v2, mut v3] = self.v;
+        v0 = v0.wrapping_add(v1);
+        v1 = v1.rotate_left(13) ^ v0;
+        v0 = v0.rotate_left(32);
+        v2 = v2.wrapping_add(v3);
+        v3 = v3.rotate_left(16) ^ v2;
+        v0 = v0.wrapping_add(v3);
+        v3 = v3.rotate_left(21) ^ v0;
+        v2 = v2.wrapping_add(v1);
+        v1 = v1.rotate_left(17) ^ v2;
+        v2 = v2.rotate_left(32);
+        self.v = [v0, v1, v2, v3];
+    }
+
+    fn compress(&mut self, word: u64) {
+        self.v[3] ^= word;
+        self.round();
+        self.round();
+        self.v[0] ^= word;
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.length += data.len();
+        for &byte in data {
+            self.tail |= (byte as u64) << (8 * self.tail_len);
+            self.tail_len += 1;
+            if self.tail_len == 8 {
+                self.compress(self.tail);
+                self.tail = 0;
+                self.tail_len = 0;
+            }
+        }
+    }
+
+    // Hash of everything so far; more data can still be added
+    pub fn value(&self) -> u64 {
+        let mut last = self.clone();
+        // The final word holds the leftover bytes and the length mod 256
+        last.compress(self.tail | (self.length as u64) << 56);
+        last.v[2] ^= 0xFF;
+        for _ in 0..4 {
+            last.round();
+        }
+        last.v.iter().fold(0, |hash, &v| hash ^ v)
+    }
+}
+
+impl Hasher for SipHasher24 {
+    fn write(&mut self, bytes: &[u8]) {
+        self.update(bytes);
+    }
+
+    fn finish(&self) -> u64 {
+        self.value()
+    }
+}
+
+pub fn siphash24(key: &[u8; 16], data: &[u8]) -> u64 {
+    let mut hasher = SipHasher24::new(key);
+    hasher.update(data);
+    hasher.value()
+}
+
+// BuildHashers for the hash maps and the Bloom filter
+pub type BuildCrc32 = BuildHasherDefault<Crc32>;
+pub type BuildFnv1a = BuildHasherDefault<Fnv1a>;
+
+// Every hasher it builds uses the same key, so equal values hash equally
+#[derive(Clone)]
+pub struct SipBuildHasher {
+    key: [u8; 16],
+}
+
+impl SipBuildHasher {
+    pub fn new(key: [u8; 16]) -> Self {
+        SipBuildHasher { key }
+    }
+
+    // Key derived from a number, for reproducible demos
+    pub fn from_seed(seed: u64) -> Self {
+        let mut key = [0u8; 16];
+        key[..8].copy_from_slice(&seed.to_le_bytes());
+        key[8..].copy_from_slice(&(!seed).rotate_left(17).to_le_bytes());
+        SipBuildHasher { key }
+    }
+}
+
+impl BuildHasher for SipBuildHasher {
+    type Hasher = SipHasher24;
+
+    fn build_hasher(&self) -> SipHasher24 {
+        SipHasher24::new(&self.key)
+    }
+}
+
+// Chi-squared statistic of how `hash` spreads `keys` over `buckets` buckets
+// (low bits, the way the hash maps pick a slot)
+#[cfg(feature = "std")]
+fn bucket_chi_squared<F: Fn(&[u8]) -> u64>(keys: &[String], buckets: usize, hash: F) -> f64 {
+    let mut counts = vec![0u64; buckets];
+    for key in keys {
+        counts[hash(key.as_bytes()) as usize & (buckets - 1)] += 1;
+    }
+    let expected = keys.len() as f64 / buckets as f64;
+    counts.iter().map(|&c| (c as f64 - expected).powi(2) / expected).sum()
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== SHA-256 test vectors (FIPS 180-4 / NIST) ===");
+    let vectors: [(&[u8], &str); 3] = [
+        (b"", "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        (b"abc", "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"),
+        (b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq",
+         "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"),
+    ];
+    for (input, expected) in vectors.iter() {
+        let digest = to_hex(&sha256(input));
+        println!("{:<58} {}", format!("{:?}", String::from_utf8_lossy(input)), if digest == *expected { "ok" } else { "MISMATCH" });
+        println!("  {}", digest);
+    }
+    let million_a = vec![b'a'; 1_000_000];
+    let digest = to_hex(&sha256(&million_a));
+    println!("One million 'a's: {} {}",
+             digest, if digest == "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0" { "ok" } else { "MISMATCH" });
+
+    println!("\n=== Incremental hashing ===");
+    let text = b"The quick brown fox jumps over the lazy dog";
+    let one_shot = sha256(text);
+    let mut same = true;
+    for split in 0..=text.len() {
+        let mut hasher = Sha256::new();
+        hasher.update(&text[..split]);
+        hasher.update(&text[split..]);
+        same &= hasher.finalize() == one_shot;
+    }
+    println!("Every two-piece split of a 43-byte message gives the same digest: {}", same);
+    let mut hasher = Sha256::new();
+    for chunk in million_a.chunks(7) {
+        hasher.update(chunk);
+    }
+    println!("One million 'a's fed 7 bytes at a time: {}", to_hex(&hasher.finalize()) == digest);
+
+    println!("\n=== Avalanche ===");
+    let with_period = sha256(b"The quick brown fox jumps over the lazy dog.");
+    println!("...lazy dog  {}", to_hex(&one_shot));
+    println!("...lazy dog. {}", to_hex(&with_period));
+    let flipped: u32 = one_shot.iter().zip(with_period.iter()).map(|(x, y)| (x ^ y).count_ones()).sum();
+    println!("One added character flips {} of 256 bits", flipped);
+
+    println!("\n=== Checksum and hash test vectors ===");
+    let check = |name: &str, value: u64, expected: u64| {
+        println!("{:<34} {:016x} {}", name, value, if value == expected { "ok" } else { "MISMATCH" });
+    };
+    check("CRC-32 \"123456789\"", crc32(b"123456789") as u64, 0xCBF4_3926);
+    check("CRC-32 \"The quick brown fox...\"", crc32(text) as u64, 0x414F_A339);
+    check("FNV-1a \"\"", fnv1a(b""), 0xCBF2_9CE4_8422_2325);
+    check("FNV-1a \"a\"", fnv1a(b"a"), 0xAF63_DC4C_8601_EC8C);
+    check("FNV-1a \"foobar\"", fnv1a(b"foobar"), 0x8594_4171_F739_67E8);
+    // Reference vectors from the SipHash paper: key 00..0f, message 00..(n-1)
+    let mut key = [0u8; 16];
+    for (i, byte) in key.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let message: Vec<u8> = (0..15).collect();
+    check("SipHash-2-4 empty message", siphash24(&key, b""), 0x726F_DB47_DD0E_0E31);
+    check("SipHash-2-4 15-byte message", siphash24(&key, &message), 0xA129_CA61_49BE_45E5);
+    let mut crc = Crc32::new();
+    let mut sip = SipHasher24::new(&key);
+    for piece in message.chunks(4) {
+        crc.update(piece);
+        sip.update(piece);
+    }
+    println!("Fed 4 bytes at a time: CRC-32 same: {}, SipHash same: {}",
+             crc.value() == crc32(&message), sip.value() == siphash24(&key, &message));
+
+    println!("\n=== Keys ===");
+    println!("SipHash-2-4 of \"apple\" under two keys: {:016x} {:016x}",
+             siphash24(&key, b"apple"), siphash24(&[0x5A; 16], b"apple"));
+    // Through a BuildHasher, the way the hash maps use it
+    let (first, second) = (SipBuildHasher::from_seed(1), SipBuildHasher::from_seed(2));
+    println!("hash_one(\"apple\") with seeds 1, 1, 2: {:016x} {:016x} {:016x}",
+             first.hash_one("apple"), first.hash_one("apple"), second.hash_one("apple"));
+    // CRC is linear: crc(a ^ b ^ c) = crc(a) ^ crc(b) ^ crc(c) for equal lengths
+    let (a, b, c) = (b"hello world!", b"abcdefghijkl", b"0123456789ab");
+    let mixed: Vec<u8> = (0..12).map(|i| a[i] ^ b[i] ^ c[i]).collect();
+    println!("CRC-32 is linear (why it is no defense against tampering): {}",
+             crc32(&mixed) == crc32(a) ^ crc32(b) ^ crc32(c));
+
+    println!("\n=== Spreading keys over 1024 buckets ===");
+    // A uniformly random hash scores about 1023 (1023 degrees of freedom; 99% of
+    // runs fall below about 1131). CRC-32 and FNV-1a score far lower on these
+    // near-identical keys: they spread them more evenly than chance would
+    let keys: Vec<String> = (0..100_000).map(|i| format!("user-{}", i)).collect();
+    println!("CRC-32      chi2 = {:>8.1}", bucket_chi_squared(&keys, 1024, |k| crc32(k) as u64));
+    println!("FNV-1a      chi2 = {:>8.1}", bucket_chi_squared(&keys, 1024, fnv1a));
+    println!("SipHash-2-4 chi2 = {:>8.1}", bucket_chi_squared(&keys, 1024, |k| siphash24(&key, k)));
+}
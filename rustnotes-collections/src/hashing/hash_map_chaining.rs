@@ -0,0 +1,251 @@
+// Separate-Chaining Hash Map Implementation
+//
+// A generic hash map where each slot of the table is a bucket holding every entry
+// whose key hashes there. The buckets are the singly-linked LinkedList from
+// lists::linked_list, so a collision simply makes a bucket's chain one node longer.
+//
+// Design choices:
+// - Vec<LinkedList<(K, V)>> as the bucket table; bucket count is a power of two
+// - New entries are pushed onto the front of their bucket's chain (O(1))
+// - Rehashes into twice as many buckets when entries / buckets exceeds the
+//   maximum load factor (1.0 by default; chaining tolerates loads above 1)
+// - Removal unlinks the node directly, so no tombstones are needed
+// - Hasher is pluggable through BuildHasher (std's RandomState by default)
+// - Trade-off: degrades gracefully under high load and never needs tombstones,
+//   but every entry is a separate heap node and lookups chase pointers
+//
+// The demo compares average probe lengths against the open-addressing map in
+// hashing::hash_map_open_addressing on the same keys.
+
+use crate::hashing::hash_map_open_addressing::HashMapOa;
+use crate::lists::linked_list::LinkedList;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+const INITIAL_BUCKETS: usize = 8;
+
+#[derive(Debug)]
+pub struct HashMapChained<K, V, S = RandomState> {
+    buckets: Vec<LinkedList<(K, V)>>,
+    len: usize,
+    max_load_factor: f64,
+    hash_builder: S,
+}
+
+impl<K: Hash + Eq, V> Default for HashMapChained<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> HashMapChained<K, V, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    // Rehash once entries per bucket exceed `max_load_factor`
+    pub fn with_max_load_factor(max_load_factor: f64) -> Self {
+        assert!(max_load_factor > 0.0, "load factor must be positive");
+        let mut map = Self::new();
+        map.max_load_factor = max_load_factor;
+        map
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> HashMapChained<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self {
+            buckets: Self::empty_buckets(INITIAL_BUCKETS),
+            len: 0,
+            max_load_factor: 1.0,
+            hash_builder,
+        }
+    }
+
+    fn empty_buckets(count: usize) -> Vec<LinkedList<(K, V)>> {
+        (0..count).map(|_| LinkedList::new()).collect()
+    }
+
+    fn bucket_index(&self, key: &K) -> usize {
+        self.hash_builder.hash_one(key) as usize & (self.buckets.len() - 1)
+    }
+
+    // Insert a key-value pair, returning the previous value if the key existed
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        if let Some(existing) = self.get_mut(&key) {
+            return Some(std::mem::replace(existing, value));
+        }
+
+        if (self.len + 1) as f64 > self.max_load_factor * self.buckets.len() as f64 {
+            self.rehash(self.buckets.len() * 2);
+        }
+        let index = self.bucket_index(&key);
+        self.buckets[index].push((key, value));
+        self.len += 1;
+        None
+    }
+
+    // Move every entry into a new table; nodes are popped and pushed, not cloned
+    fn rehash(&mut self, bucket_count: usize) {
+        trace!("rehash {} entries from {} to {} buckets", self.len, self.buckets.len(), bucket_count);
+        let old_buckets = std::mem::replace(&mut self.buckets, Self::empty_buckets(bucket_count));
+        for mut bucket in old_buckets {
+            while let Some((key, value)) = bucket.pop() {
+                let index = self.bucket_index(&key);
+                self.buckets[index].push((key, value));
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.buckets[self.bucket_index(key)]
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        let index = self.bucket_index(key);
+        self.buckets[index]
+            .iter_mut()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.bucket_index(key);
+        let bucket = &mut self.buckets[index];
+        let position = bucket.iter().position(|(k, _)| k == key)?;
+        let (_, value) = bucket.remove(position)?;
+        self.len -= 1;
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The bucket table plus one list node per entry
+    pub fn heap_size(&self) -> usize {
+        self.buckets.capacity() * core::mem::size_of::<LinkedList<(K, V)>>()
+            + self.buckets.iter().map(LinkedList::heap_size).sum::<usize>()
+    }
+
+    pub fn bucket_count(&self) -> usize {
+        self.buckets.len()
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.buckets.len() as f64
+    }
+
+    // Average number of nodes examined by a successful lookup: the k-th entry of
+    // a chain costs k probes
+    pub fn average_probe_length(&self) -> f64 {
+        if self.len == 0 {
+            return 0.0;
+        }
+        let total: usize = self.buckets.iter()
+            .map(|bucket| bucket.len() * (bucket.len() + 1) / 2)
+            .sum();
+        total as f64 / self.len as f64
+    }
+
+    pub fn longest_chain(&self) -> usize {
+        self.buckets.iter().map(|bucket| bucket.len()).max().unwrap_or(0)
+    }
+
+    pub fn clear(&mut self) {
+        for bucket in self.buckets.iter_mut() {
+            bucket.clear();
+        }
+        self.len = 0;
+    }
+
+    // Iterate over entries bucket by bucket (effectively arbitrary order)
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.buckets.iter().flat_map(|bucket| bucket.iter().map(|(k, v)| (k, v)))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+pub fn demo() {
+    let mut map = HashMapChained::new();
+
+    println!("=== Insert and get ===");
+    map.insert("apple", 3);
+    map.insert("banana", 5);
+    map.insert("cherry", 7);
+    println!("Length: {}, buckets: {}", map.len(), map.bucket_count());
+    println!("get(apple): {:?}", map.get(&"apple"));
+    println!("get(durian): {:?}", map.get(&"durian"));
+    println!("Insert apple again, old value: {:?}", map.insert("apple", 30));
+    if let Some(count) = map.get_mut(&"banana") {
+        *count += 100;
+    }
+    println!("get(banana) after get_mut: {:?}", map.get(&"banana"));
+
+    println!("\n=== Remove unlinks the node ===");
+    println!("Remove banana: {:?}", map.remove(&"banana"));
+    println!("Remove banana again: {:?}", map.remove(&"banana"));
+    println!("Contains cherry: {}", map.contains_key(&"cherry"));
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort();
+    println!("Entries: {:?}", entries);
+
+    println!("\n=== Load-factor-driven rehashing ===");
+    let mut numbers = HashMapChained::new();
+    let mut last_buckets = numbers.bucket_count();
+    for i in 0..200 {
+        numbers.insert(i, i * i);
+        if numbers.bucket_count() != last_buckets {
+            println!("Rehashed {} -> {} buckets at {} entries",
+                     last_buckets, numbers.bucket_count(), numbers.len());
+            last_buckets = numbers.bucket_count();
+        }
+    }
+    println!("Sum of keys: {}, sum of values: {}",
+             numbers.keys().sum::<i32>(), numbers.values().sum::<i32>());
+
+    println!("\n=== Probe counts: chaining vs open addressing ===");
+    println!("{:>8} {:>12} {:>10} {:>10} {:>12} {:>10}",
+             "entries", "chain load", "probes", "longest", "oa load", "probes");
+    for &n in [100, 1000, 10000, 100000].iter() {
+        let mut chained = HashMapChained::new();
+        let mut open = HashMapOa::new();
+        for key in 0..n {
+            chained.insert(key, key);
+            open.insert(key, key);
+        }
+        println!("{:>8} {:>12.2} {:>10.3} {:>10} {:>12.2} {:>10.3}",
+                 n, chained.load_factor(), chained.average_probe_length(),
+                 chained.longest_chain(), open.load_factor(), open.average_probe_length());
+    }
+
+    println!("\n=== Heavier load factor (4.0) trades probes for fewer buckets ===");
+    let mut dense = HashMapChained::with_max_load_factor(4.0);
+    for key in 0..10000 {
+        dense.insert(key, ());
+    }
+    println!("Buckets: {}, load: {:.2}, average probes: {:.3}, longest chain: {}",
+             dense.bucket_count(), dense.load_factor(),
+             dense.average_probe_length(), dense.longest_chain());
+
+    map.clear();
+    println!("\nAfter clear: length {}, is empty: {}", map.len(), map.is_empty());
+}
@@ -0,0 +1,322 @@
+// Open-Addressing Hash Map Implementation
+//
+// A generic hash map that stores every entry directly in one flat array of slots.
+// A key hashes to a home slot; if that slot is taken the map probes the following
+// slots one by one (linear probing) until it finds the key or an empty slot.
+//
+// Design choices:
+// - Vec<Slot<K, V>> with three states: Empty, Tombstone, Occupied(key, value)
+// - Capacity is always a power of two so the home slot is hash & (capacity - 1)
+// - Linear probing keeps probe sequences contiguous and cache-friendly
+// - Removal leaves a Tombstone so later keys in the same probe run stay reachable;
+//   inserts reuse the first tombstone they pass
+// - Resizes when live entries plus tombstones exceed 3/4 of the slots: doubles when
+//   the map is genuinely full, otherwise rehashes at the same size to purge tombstones
+// - Hasher is pluggable through BuildHasher (std's RandomState by default)
+// - Trade-off: excellent locality and no per-entry allocation, but performance
+//   degrades sharply as the load factor approaches 1 and with clustered hashes
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+
+const INITIAL_CAPACITY: usize = 8;
+
+#[derive(Debug, Clone)]
+enum Slot<K, V> {
+    Empty,
+    Tombstone,
+    Occupied(K, V),
+}
+
+#[derive(Debug, Clone)]
+pub struct HashMapOa<K, V, S = RandomState> {
+    slots: Vec<Slot<K, V>>,
+    len: usize,
+    tombstones: usize,
+    hash_builder: S,
+}
+
+impl<K: Hash + Eq, V> Default for HashMapOa<K, V, RandomState> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq, V> HashMapOa<K, V, RandomState> {
+    pub fn new() -> Self {
+        Self::with_hasher(RandomState::new())
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_hasher(capacity, RandomState::new())
+    }
+}
+
+impl<K: Hash + Eq, V, S: BuildHasher> HashMapOa<K, V, S> {
+    pub fn with_hasher(hash_builder: S) -> Self {
+        Self::with_capacity_and_hasher(0, hash_builder)
+    }
+
+    // Room for `capacity` entries without resizing
+    pub fn with_capacity_and_hasher(capacity: usize, hash_builder: S) -> Self {
+        let slots = (capacity * 4 / 3 + 1).next_power_of_two().max(INITIAL_CAPACITY);
+        Self {
+            slots: Self::empty_slots(slots),
+            len: 0,
+            tombstones: 0,
+            hash_builder,
+        }
+    }
+
+    fn empty_slots(count: usize) -> Vec<Slot<K, V>> {
+        let mut slots = Vec::with_capacity(count);
+        slots.resize_with(count, || Slot::Empty);
+        slots
+    }
+
+    fn home_slot(&self, key: &K) -> usize {
+        self.hash_builder.hash_one(key) as usize & (self.slots.len() - 1)
+    }
+
+    // Walk the probe sequence for `key`: Ok(index) if found, otherwise Err with the
+    // slot an insert should use (first tombstone seen, or the terminating empty slot)
+    fn find_slot(&self, key: &K) -> Result<usize, usize> {
+        let mask = self.slots.len() - 1;
+        let mut index = self.home_slot(key);
+        let mut first_tombstone = None;
+
+        // The load-factor limit guarantees an empty slot exists, so this terminates
+        loop {
+            match &self.slots[index] {
+                Slot::Empty => return Err(first_tombstone.unwrap_or(index)),
+                Slot::Tombstone => {
+                    if first_tombstone.is_none() {
+                        first_tombstone = Some(index);
+                    }
+                }
+                Slot::Occupied(k, _) if k == key => return Ok(index),
+                Slot::Occupied(..) => {}
+            }
+            index = (index + 1) & mask;
+        }
+    }
+
+    // Insert a key-value pair, returning the previous value if the key existed
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        match self.find_slot(&key) {
+            Ok(index) => match &mut self.slots[index] {
+                Slot::Occupied(_, v) => Some(std::mem::replace(v, value)),
+                _ => unreachable!(),
+            },
+            Err(_) if (self.len + self.tombstones + 1) * 4 > self.slots.len() * 3 => {
+                self.resize();
+                self.insert(key, value)
+            }
+            Err(index) => {
+                if let Slot::Tombstone = self.slots[index] {
+                    self.tombstones -= 1;
+                }
+                self.slots[index] = Slot::Occupied(key, value);
+                self.len += 1;
+                None
+            }
+        }
+    }
+
+    // Double when live entries fill half the table, otherwise just purge tombstones
+    fn resize(&mut self) {
+        let new_capacity = if (self.len + 1) * 2 > self.slots.len() {
+            self.slots.len() * 2
+        } else {
+            self.slots.len()
+        };
+        trace!(
+            "resize {} slots to {} ({} live, {} tombstones)",
+            self.slots.len(),
+            new_capacity,
+            self.len,
+            self.tombstones
+        );
+
+        let old_slots = std::mem::replace(&mut self.slots, Self::empty_slots(new_capacity));
+        self.tombstones = 0;
+        let mask = new_capacity - 1;
+        for slot in old_slots {
+            if let Slot::Occupied(key, value) = slot {
+                // Keys are unique, so each only needs the first empty slot of its probe run
+                let mut index = self.home_slot(&key);
+                while let Slot::Occupied(..) = self.slots[index] {
+                    index = (index + 1) & mask;
+                }
+                self.slots[index] = Slot::Occupied(key, value);
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        match self.find_slot(key) {
+            Ok(index) => match &self.slots[index] {
+                Slot::Occupied(_, v) => Some(v),
+                _ => unreachable!(),
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        match self.find_slot(key) {
+            Ok(index) => match &mut self.slots[index] {
+                Slot::Occupied(_, v) => Some(v),
+                _ => unreachable!(),
+            },
+            Err(_) => None,
+        }
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.find_slot(key).is_ok()
+    }
+
+    // Remove a key, leaving a tombstone so longer probe runs stay intact
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let index = self.find_slot(key).ok()?;
+        self.len -= 1;
+        self.tombstones += 1;
+        match std::mem::replace(&mut self.slots[index], Slot::Tombstone) {
+            Slot::Occupied(_, v) => Some(v),
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The slot table: empty slots and tombstones cost as much as live entries
+    pub fn heap_size(&self) -> usize {
+        self.slots.capacity() * core::mem::size_of::<Slot<K, V>>()
+    }
+
+    // Number of slots in the table (not the number of entries)
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn load_factor(&self) -> f64 {
+        self.len as f64 / self.slots.len() as f64
+    }
+
+    // Average number of slots examined by a successful lookup: an entry sitting
+    // d slots past its home slot costs d + 1 probes
+    pub fn average_probe_length(&self) -> f64 {
+        if self.len == 0 {
+            return 0.0;
+        }
+        let mask = self.slots.len() - 1;
+        let total: usize = self.slots.iter().enumerate()
+            .filter_map(|(index, slot)| match slot {
+                Slot::Occupied(k, _) => Some((index.wrapping_sub(self.home_slot(k)) & mask) + 1),
+                _ => None,
+            })
+            .sum();
+        total as f64 / self.len as f64
+    }
+
+    pub fn clear(&mut self) {
+        for slot in self.slots.iter_mut() {
+            *slot = Slot::Empty;
+        }
+        self.len = 0;
+        self.tombstones = 0;
+    }
+
+    // Iterate over entries in slot order (effectively arbitrary)
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.slots.iter().filter_map(|slot| match slot {
+            Slot::Occupied(k, v) => Some((k, v)),
+            _ => None,
+        })
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.iter().map(|(k, _)| k)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> {
+        self.iter().map(|(_, v)| v)
+    }
+}
+
+pub fn demo() {
+    let mut map = HashMapOa::new();
+
+    println!("=== Insert and get ===");
+    map.insert("apple", 3);
+    map.insert("banana", 5);
+    map.insert("cherry", 7);
+    println!("Length: {}, capacity: {}", map.len(), map.capacity());
+    println!("get(apple): {:?}", map.get(&"apple"));
+    println!("get(durian): {:?}", map.get(&"durian"));
+    println!("Insert apple again, old value: {:?}", map.insert("apple", 30));
+    println!("get(apple): {:?}", map.get(&"apple"));
+
+    if let Some(count) = map.get_mut(&"banana") {
+        *count += 100;
+    }
+    println!("get(banana) after get_mut: {:?}", map.get(&"banana"));
+
+    println!("\n=== Remove leaves tombstones ===");
+    println!("Remove banana: {:?}", map.remove(&"banana"));
+    println!("Remove banana again: {:?}", map.remove(&"banana"));
+    println!("Contains banana: {}", map.contains_key(&"banana"));
+    println!("Contains cherry: {}", map.contains_key(&"cherry"));
+    println!("Length: {}, tombstones: {}", map.len(), map.tombstones);
+    map.insert("banana", 1);
+    println!("Re-inserted banana, tombstones: {}", map.tombstones);
+
+    println!("\n=== Word count with automatic resizing ===");
+    let text = "the quick brown fox jumps over the lazy dog the fox barks \
+                and the dog runs over the hill while the quick fox sleeps";
+    let mut counts = HashMapOa::new();
+    let mut last_capacity = counts.capacity();
+    for word in text.split_whitespace() {
+        match counts.get_mut(&word) {
+            Some(count) => *count += 1,
+            None => {
+                counts.insert(word, 1);
+            }
+        }
+        if counts.capacity() != last_capacity {
+            println!("Resized {} -> {} slots at {} entries",
+                     last_capacity, counts.capacity(), counts.len());
+            last_capacity = counts.capacity();
+        }
+    }
+    let mut sorted: Vec<(&&str, &i32)> = counts.iter().collect();
+    sorted.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    println!("Top words: {:?}", &sorted[..4]);
+    println!("Distinct words: {}, load factor: {:.2}, average probes: {:.2}",
+             counts.len(), counts.load_factor(), counts.average_probe_length());
+
+    println!("\n=== Churn: insert/remove cycles purge tombstones ===");
+    let mut churn = HashMapOa::with_capacity(16);
+    for round in 0..1000 {
+        churn.insert(round, round * round);
+        if round >= 10 {
+            churn.remove(&(round - 10));
+        }
+    }
+    println!("Length: {}, capacity: {}, tombstones: {}",
+             churn.len(), churn.capacity(), churn.tombstones);
+    let mut keys: Vec<_> = churn.keys().copied().collect();
+    keys.sort();
+    println!("Remaining keys: {:?}", keys);
+    println!("Sum of values: {}", churn.values().sum::<i32>());
+
+    map.clear();
+    println!("\nAfter clear: length {}, is empty: {}", map.len(), map.is_empty());
+}
@@ -0,0 +1,199 @@
+// LRU (Least Recently Used) Cache
+//
+// A fixed-capacity key-value cache that, when full, evicts the entry that has gone
+// longest without being read or written. Both get and put run in O(1) by pairing
+// two structures from this collection:
+// - DoubleLinkedList (lists::double_linked_list) holds the keys in recency order,
+//   most recent at the front, least recent at the back
+// - HashMapOa (hashing::hash_map_open_addressing) maps each key to its value plus a
+//   NodeRef pointing at that key's node in the list
+//
+// Design choices:
+// - A hit looks up the NodeRef in the map and calls move_to_front - no list search
+// - Eviction is pop_end() on the list followed by removing the popped key from the map
+// - Values live in the map, keys in both structures (hence K: Clone)
+// - NodeRefs are Weak pointers, so they never block the list from popping nodes
+// - peek() reads without refreshing recency; get() counts as a use
+// - Hit/miss/eviction counters make cache behavior visible in demos
+// - Trade-off: two structures to keep in sync and a node allocation per entry, in
+//   exchange for strict LRU order with O(1) operations
+
+use crate::hashing::hash_map_open_addressing::HashMapOa;
+use crate::lists::double_linked_list::{DoubleLinkedList, NodeRef};
+use std::hash::Hash;
+
+pub struct LruCache<K, V> {
+    capacity: usize,
+    map: HashMapOa<K, (V, NodeRef<K>)>,
+    order: DoubleLinkedList<K>, // Front = most recently used
+    hits: u64,
+    misses: u64,
+    evictions: u64,
+}
+
+impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
+    pub fn new(capacity: usize) -> Self {
+        assert!(capacity > 0, "LRU cache capacity must be positive");
+        Self {
+            capacity,
+            map: HashMapOa::with_capacity(capacity),
+            order: DoubleLinkedList::new(),
+            hits: 0,
+            misses: 0,
+            evictions: 0,
+        }
+    }
+
+    // Look up a key and mark it as most recently used
+    pub fn get(&mut self, key: &K) -> Option<&V> {
+        let node = match self.map.get(key) {
+            Some((_, node)) => node.clone(),
+            None => {
+                self.misses += 1;
+                return None;
+            }
+        };
+        self.hits += 1;
+        self.order.move_to_front(&node);
+        self.map.get(key).map(|(value, _)| value)
+    }
+
+    // Look up a key without affecting recency or the hit counters
+    pub fn peek(&self, key: &K) -> Option<&V> {
+        self.map.get(key).map(|(value, _)| value)
+    }
+
+    pub fn contains(&self, key: &K) -> bool {
+        self.map.contains_key(key)
+    }
+
+    // Insert or update a key, making it most recently used. Returns the previous
+    // value for the key, if any. Evicts the least recently used entry when full.
+    pub fn put(&mut self, key: K, value: V) -> Option<V> {
+        if let Some((old_value, node)) = self.map.get_mut(&key) {
+            let node = node.clone();
+            let previous = std::mem::replace(old_value, value);
+            self.order.move_to_front(&node);
+            return Some(previous);
+        }
+
+        if self.map.len() == self.capacity {
+            self.evict();
+        }
+        let node = self.order.push_ref(key.clone());
+        self.map.insert(key, (value, node));
+        None
+    }
+
+    // Drop the least recently used entry, returning it
+    fn evict(&mut self) -> Option<(K, V)> {
+        let key = self.order.pop_end()?;
+        self.evictions += 1;
+        self.map.remove(&key).map(|(value, _)| (key, value))
+    }
+
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let (value, node) = self.map.remove(key)?;
+        self.order.remove_ref(&node);
+        Some(value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    // The map (values and node handles) plus the recency list
+    pub fn heap_size(&self) -> usize {
+        self.map.heap_size() + self.order.heap_size()
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn clear(&mut self) {
+        self.map.clear();
+        self.order.clear();
+    }
+
+    // Keys from most to least recently used
+    pub fn keys_by_recency(&self) -> Vec<K> {
+        let mut keys = Vec::with_capacity(self.len());
+        self.order.for_each(|key| keys.push(key.clone()));
+        keys
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.evictions
+    }
+}
+
+pub fn demo() {
+    println!("=== Basic LRU behavior (capacity 3) ===");
+    let mut cache = LruCache::new(3);
+    cache.put("a", 1);
+    cache.put("b", 2);
+    cache.put("c", 3);
+    println!("After put a, b, c: {:?}", cache.keys_by_recency());
+    println!("get(a): {:?}", cache.get(&"a"));
+    println!("Recency after get(a): {:?}", cache.keys_by_recency());
+    cache.put("d", 4);
+    println!("put(d) evicts least recent (b): {:?}", cache.keys_by_recency());
+    println!("get(b): {:?}", cache.get(&"b"));
+    println!("put(c, 30) replaces: {:?}", cache.put("c", 30));
+    println!("Recency after update: {:?}", cache.keys_by_recency());
+    println!("peek(a) without touching recency: {:?}", cache.peek(&"a"));
+    println!("Recency unchanged: {:?}", cache.keys_by_recency());
+    println!("remove(d): {:?}, contains d: {}", cache.remove(&"d"), cache.contains(&"d"));
+    println!("Length: {}/{}", cache.len(), cache.capacity());
+    println!("Hits: {}, misses: {}, evictions: {}", cache.hits(), cache.misses(), cache.evictions());
+
+    println!("\n=== Caching slow lookups ===");
+    // Pretend computing a square is expensive; cache the last 8 results
+    let mut squares = LruCache::new(8);
+    let requests = [1, 2, 3, 1, 2, 3, 4, 5, 6, 7, 8, 9, 1, 2, 9, 9, 8, 3];
+    let mut computed = 0;
+    for &n in requests.iter() {
+        if squares.get(&n).is_none() {
+            computed += 1;
+            squares.put(n, n * n);
+        }
+    }
+    println!("{} requests, {} computations", requests.len(), computed);
+    println!("Hits: {}, misses: {}, evictions: {}",
+             squares.hits(), squares.misses(), squares.evictions());
+    println!("Cached keys by recency: {:?}", squares.keys_by_recency());
+
+    println!("\n=== Larger workload stays consistent ===");
+    let mut big = LruCache::new(100);
+    let mut state: u64 = 42;
+    for _ in 0..50_000 {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        let key = state % 300;
+        if big.get(&key).is_none() {
+            big.put(key, key * 2);
+        }
+    }
+    let keys = big.keys_by_recency();
+    let consistent = keys.len() == big.len() && keys.iter().all(|k| big.peek(k) == Some(&(k * 2)));
+    println!("Length {}, list and map agree: {}", big.len(), consistent);
+    println!("Hit rate with capacity 100 over 300 keys: {:.1}%",
+             100.0 * big.hits() as f64 / (big.hits() + big.misses()) as f64);
+
+    cache.clear();
+    println!("\nAfter clear: length {}, is empty: {}", cache.len(), cache.is_empty());
+}
@@ -0,0 +1,246 @@
+// Memoization
+//
+// Wraps a function so that each result is computed once and then looked up:
+// the first call with an argument runs the function and stores the result, and
+// later calls with the same argument return the stored copy. For recursive
+// functions whose calls overlap, such as Fibonacci or most dynamic-programming
+// recurrences, this turns exponential time into one computation per distinct
+// argument.
+//
+//   fib(5) without memo:                  with memo:
+//            5                                 5
+//          /   \                             /   \
+//         4     3                           4    (3)  <- (x) = cache hit
+//        / \   / \                         / \
+//       3   2 2   1                       3  (2)
+//      ...                               / \
+//   15 calls, fib(2) computed 3 times   2   1         9 calls, 6 computed
+//
+// Design choices:
+// - Memo::new(function) takes the function as fn(&mut Memo, A) -> R, so a
+//   recursive function makes its recursive calls through the memo (memo.call)
+//   and every level is cached, not just the outermost call
+// - The function sits behind an Rc so call() can clone it out before passing
+//   &mut self into it; closures may capture their inputs (strings, grids)
+// - Unbounded storage is HashMapOa from hashing::hash_map_open_addressing.
+//   With a capacity, storage is the LruCache from hashing::lru_cache (HashMapOa plus the
+//   DoubleLinkedList), so memory stays bounded and the least recently used
+//   results are recomputed if they are needed again
+// - memoize() covers the simple case: it turns a non-recursive Fn(&A) -> R
+//   into an FnMut(A) -> R closure that owns its own cache
+// - Results are returned by clone, so R should be cheap to clone (numbers,
+//   or Rc for large results)
+// - Trade-off: the function must be pure; a memoized function that reads a
+//   clock or mutable state returns stale results, and the cache never expires
+
+use crate::hashing::hash_map_open_addressing::HashMapOa;
+use crate::hashing::lru_cache::LruCache;
+use std::hash::Hash;
+use std::rc::Rc;
+
+type MemoFn<A, R> = Rc<dyn Fn(&mut Memo<A, R>, A) -> R>;
+
+enum Store<A, R> {
+    Unbounded(HashMapOa<A, R>),
+    Lru(LruCache<A, R>),
+}
+
+pub struct Memo<A, R> {
+    function: MemoFn<A, R>,
+    store: Store<A, R>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<A: Hash + Eq + Clone, R: Clone> Memo<A, R> {
+    // Cache every result
+    pub fn new<F: Fn(&mut Memo<A, R>, A) -> R + 'static>(function: F) -> Self {
+        Self::with_store(Store::Unbounded(HashMapOa::new()), function)
+    }
+
+    // Keep at most `capacity` results, evicting the least recently used
+    pub fn with_capacity<F: Fn(&mut Memo<A, R>, A) -> R + 'static>(capacity: usize, function: F) -> Self {
+        Self::with_store(Store::Lru(LruCache::new(capacity)), function)
+    }
+
+    fn with_store<F: Fn(&mut Memo<A, R>, A) -> R + 'static>(store: Store<A, R>, function: F) -> Self {
+        Memo { function: Rc::new(function), store, hits: 0, misses: 0 }
+    }
+
+    pub fn call(&mut self, arg: A) -> R {
+        let cached = match &mut self.store {
+            Store::Unbounded(map) => map.get(&arg).cloned(),
+            Store::Lru(cache) => cache.get(&arg).cloned(),
+        };
+        if let Some(result) = cached {
+            self.hits += 1;
+            return result;
+        }
+        self.misses += 1;
+        let function = Rc::clone(&self.function);
+        let result = function(self, arg.clone());
+        match &mut self.store {
+            Store::Unbounded(map) => {
+                map.insert(arg, result.clone());
+            }
+            Store::Lru(cache) => {
+                cache.put(arg, result.clone());
+            }
+        }
+        result
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    // Calls that ran the function
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    // Results currently stored
+    pub fn len(&self) -> usize {
+        match &self.store {
+            Store::Unbounded(map) => map.len(),
+            Store::Lru(cache) => cache.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // The cache of results so far
+    pub fn heap_size(&self) -> usize {
+        match &self.store {
+            Store::Unbounded(map) => map.heap_size(),
+            Store::Lru(cache) => cache.heap_size(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        match &mut self.store {
+            Store::Unbounded(map) => map.clear(),
+            Store::Lru(cache) => cache.clear(),
+        }
+        self.hits = 0;
+        self.misses = 0;
+    }
+}
+
+// Cache a non-recursive function in a closure
+pub fn memoize<A, R, F>(function: F) -> impl FnMut(A) -> R
+where
+    A: Hash + Eq + Clone,
+    R: Clone,
+    F: Fn(&A) -> R,
+{
+    let mut cache = HashMapOa::new();
+    move |arg| {
+        if let Some(result) = cache.get(&arg) {
+            return R::clone(result);
+        }
+        let result = function(&arg);
+        cache.insert(arg, result.clone());
+        result
+    }
+}
+
+fn naive_fib(n: u64, calls: &mut u64) -> u64 {
+    *calls += 1;
+    if n < 2 {
+        n
+    } else {
+        naive_fib(n - 1, calls) + naive_fib(n - 2, calls)
+    }
+}
+
+// Bottom-up table for edit distance, to check the memoized recursion against
+fn edit_distance_table(a: &[u8], b: &[u8]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut current = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            current[j] = substitution.min(previous[j] + 1).min(current[j - 1] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+pub fn demo() {
+    println!("=== Recursive Fibonacci ===");
+    let mut fib = Memo::new(|memo: &mut Memo<u64, u64>, n: u64| {
+        if n < 2 {
+            n
+        } else {
+            memo.call(n - 1) + memo.call(n - 2)
+        }
+    });
+    let mut calls = 0;
+    let slow = naive_fib(30, &mut calls);
+    println!("fib(30) = {}: naive recursion makes {} calls", slow, calls);
+    let fast = fib.call(30);
+    println!("fib(30) = {}: memoized makes {} calls ({} computed, {} cache hits)",
+             fast, fib.hits() + fib.misses(), fib.misses(), fib.hits());
+    println!("fib(90) = {} (only {} new computations)", fib.call(90), fib.misses() - 31);
+
+    println!("\n=== Dynamic programming: edit distance ===");
+    let pairs = [("kitten", "sitting"), ("intention", "execution"), ("", "abc"),
+                 ("memoization", "memorization"), ("dynamic programming", "dramatic programmers")];
+    for (a, b) in pairs.iter() {
+        let (x, y) = (a.as_bytes().to_vec(), b.as_bytes().to_vec());
+        // distance(i, j) = edit distance between the first i bytes of a and the first j of b
+        let mut distance = Memo::new(move |memo: &mut Memo<(usize, usize), usize>, (i, j): (usize, usize)| {
+            if i == 0 || j == 0 {
+                return i + j;
+            }
+            let substitution = memo.call((i - 1, j - 1)) + usize::from(x[i - 1] != y[j - 1]);
+            let deletion = memo.call((i - 1, j)) + 1;
+            let insertion = memo.call((i, j - 1)) + 1;
+            substitution.min(deletion).min(insertion)
+        });
+        let result = distance.call((a.len(), b.len()));
+        let expected = edit_distance_table(a.as_bytes(), b.as_bytes());
+        println!("{:>21} -> {:<22} {:>2} ({} subproblems, matches table: {})",
+                 format!("{:?}", a), format!("{:?}", b), result, distance.len(), result == expected);
+    }
+
+    println!("\n=== Bounded cache (LRU) on Collatz chain lengths ===");
+    // steps(n) = steps to reach 1; chains share tails, so caching pays off
+    fn collatz(memo: &mut Memo<u64, u32>, n: u64) -> u32 {
+        if n == 1 {
+            0
+        } else if n.is_multiple_of(2) {
+            1 + memo.call(n / 2)
+        } else {
+            1 + memo.call(3 * n + 1)
+        }
+    }
+    let mut reference = Memo::new(collatz);
+    let longest: Vec<u32> = (1..=50_000).map(|n| reference.call(n)).collect();
+    println!("Unbounded: {:>7} results stored, {:>7} computed, {:>7} hits", reference.len(), reference.misses(), reference.hits());
+    for &capacity in [100usize, 1_000, 10_000].iter() {
+        let mut bounded = Memo::with_capacity(capacity, collatz);
+        let same = (1..=50_000).all(|n| bounded.call(n) == longest[n as usize - 1]);
+        println!("LRU {:>6}: {:>7} results stored, {:>7} computed, {:>7} hits, same answers: {}",
+                 capacity, bounded.len(), bounded.misses(), bounded.hits(), same);
+    }
+    let (index, steps) = longest.iter().enumerate().max_by_key(|&(_, &s)| s).unwrap();
+    println!("Longest chain below 50000 starts at {} ({} steps)", index + 1, steps);
+
+    println!("\n=== memoize() for a plain function ===");
+    let mut calls = 0;
+    let mut slow_square = memoize(|&x: &u32| {
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        x * x
+    });
+    let start = std::time::Instant::now();
+    for x in [3, 4, 3, 3, 4, 5].iter() {
+        slow_square(*x);
+        calls += 1;
+    }
+    println!("{} calls with 3 distinct arguments took ~{}ms (3 x 20ms)", calls, start.elapsed().as_millis() / 10 * 10);
+}
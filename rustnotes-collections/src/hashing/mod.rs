@@ -0,0 +1,21 @@
+// Hashing
+
+pub mod hash_functions;
+#[cfg(feature = "std")]
+pub mod hash_map_chaining;
+#[cfg(feature = "std")]
+pub mod hash_map_open_addressing;
+#[cfg(feature = "std")]
+pub mod lru_cache;
+#[cfg(feature = "std")]
+pub mod memoize;
+
+pub use self::hash_functions::{BuildCrc32, BuildFnv1a, SipBuildHasher};
+#[cfg(feature = "std")]
+pub use self::hash_map_chaining::HashMapChained;
+#[cfg(feature = "std")]
+pub use self::hash_map_open_addressing::HashMapOa;
+#[cfg(feature = "std")]
+pub use self::lru_cache::LruCache;
+#[cfg(feature = "std")]
+pub use self::memoize::Memo;
@@ -0,0 +1,201 @@
+// Binary Min-Heap / Priority Queue Implementation
+//
+// A generic min-heap stored in a Vec: the smallest element is always at index 0,
+// and every element is no larger than its two children. Supports push, pop, peek,
+// O(n) heapify from an existing Vec, and in-place heapsort via into_sorted_vec.
+//
+// Design choices:
+// - Implicit binary tree in a Vec: children of i are 2i+1 and 2i+2, parent is (i-1)/2
+// - sift_up restores order after a push, sift_down after the root is replaced
+// - Heapify sifts down every internal node from the last one to the root, which
+//   costs O(n) in total rather than the O(n log n) of n pushes
+// - into_sorted_vec reuses the heap's own buffer: repeatedly swap the minimum to
+//   the end and shrink the heap, then reverse the descending result
+// - Requires T: Ord; wrap values in core::cmp::Reverse for max-heap behavior
+// - Trade-off: no pointers and great locality, but no efficient search, removal
+//   of arbitrary elements, or merging of two heaps
+
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone)]
+pub struct MinHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> Default for MinHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> MinHeap<T> {
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { data: Vec::with_capacity(capacity) }
+    }
+
+    // Build a heap from an arbitrary Vec in O(n)
+    pub fn heapify(data: Vec<T>) -> Self {
+        let mut heap = Self { data };
+        let len = heap.data.len();
+        for i in (0..len / 2).rev() {
+            heap.sift_down(i, len);
+        }
+        heap
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        let last = self.data.len() - 1;
+        self.sift_up(last);
+    }
+
+    // Remove and return the smallest element
+    pub fn pop(&mut self) -> Option<T> {
+        if self.data.is_empty() {
+            return None;
+        }
+        let last = self.data.len() - 1;
+        self.data.swap(0, last);
+        let min = self.data.pop();
+        let len = self.data.len();
+        self.sift_down(0, len);
+        min
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.data.capacity() * core::mem::size_of::<T>()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    // Heapsort: consume the heap and return its elements in ascending order
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut end = self.data.len();
+        while end > 1 {
+            end -= 1;
+            self.data.swap(0, end); // Current minimum goes just past the shrinking heap
+            self.sift_down(0, end);
+        }
+        self.data.reverse(); // Minimums were placed from the back, so undo that
+        self.data
+    }
+
+    // Move the element at `index` up while it is smaller than its parent
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / 2;
+            if self.data[index] >= self.data[parent] {
+                break;
+            }
+            self.data.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    // Move the element at `index` down while a child is smaller, considering
+    // only the first `len` elements as part of the heap
+    fn sift_down(&mut self, mut index: usize, len: usize) {
+        loop {
+            let left = 2 * index + 1;
+            let right = left + 1;
+            let mut smallest = index;
+
+            if left < len && self.data[left] < self.data[smallest] {
+                smallest = left;
+            }
+            if right < len && self.data[right] < self.data[smallest] {
+                smallest = right;
+            }
+            if smallest == index {
+                break;
+            }
+            self.data.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    // Verify the heap property (for debugging)
+    pub fn is_valid(&self) -> bool {
+        (1..self.data.len()).all(|i| self.data[(i - 1) / 2] <= self.data[i])
+    }
+}
+
+impl<T: Ord> From<Vec<T>> for MinHeap<T> {
+    fn from(data: Vec<T>) -> Self {
+        Self::heapify(data)
+    }
+}
+
+impl<T: Ord> core::iter::FromIterator<T> for MinHeap<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::heapify(iter.into_iter().collect())
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Push and pop ===");
+    let mut heap = MinHeap::new();
+    for value in [5, 3, 8, 1, 9, 2, 7].iter() {
+        heap.push(*value);
+        println!("Pushed {}: min is {:?}, valid: {}", value, heap.peek(), heap.is_valid());
+    }
+    print!("Popping in order: ");
+    while let Some(value) = heap.pop() {
+        print!("{} ", value);
+    }
+    println!();
+    println!("Pop on empty: {:?}", heap.pop());
+
+    println!("\n=== Heapify from a Vec ===");
+    let heap = MinHeap::heapify(vec![42, 17, 8, 99, 23, 4, 16, 15]);
+    println!("Internal layout: {:?}", heap.data);
+    println!("Valid: {}, length: {}, min: {:?}", heap.is_valid(), heap.len(), heap.peek());
+
+    println!("\n=== Heapsort via into_sorted_vec ===");
+    let words: MinHeap<&str> = "the quick brown fox jumps over the lazy dog"
+        .split_whitespace()
+        .collect();
+    println!("Sorted words: {:?}", words.into_sorted_vec());
+    let numbers = MinHeap::from(vec![3, -1, 4, 1, -5, 9, 2, 6, 5, 3, 5]);
+    println!("Sorted numbers: {:?}", numbers.into_sorted_vec());
+
+    println!("\n=== Max-heap behavior with Reverse ===");
+    let mut max_heap = MinHeap::new();
+    for value in [5, 3, 8, 1].iter() {
+        max_heap.push(core::cmp::Reverse(*value));
+    }
+    println!("Largest: {:?}", max_heap.pop().map(|r| r.0));
+
+    println!("\n=== Priority queue of tasks ===");
+    let mut tasks = MinHeap::with_capacity(4);
+    tasks.push((2, "write report"));
+    tasks.push((1, "fix outage"));
+    tasks.push((3, "lunch"));
+    tasks.push((1, "answer pager"));
+    while let Some((priority, task)) = tasks.pop() {
+        println!("[{}] {}", priority, task);
+    }
+
+    let mut heap: MinHeap<i32> = (1..=5).collect();
+    heap.clear();
+    println!("\nAfter clear: length {}, is empty: {}", heap.len(), heap.is_empty());
+}
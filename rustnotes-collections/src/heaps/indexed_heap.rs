@@ -0,0 +1,283 @@
+// Indexed d-ary Min-Heap Implementation
+//
+// A priority queue over items identified by small integer indices (0, 1, 2, ...),
+// such as graph vertices. Besides push and pop it tracks where every item sits in
+// the heap, so the priority of an item already in the queue can be lowered or
+// raised in O(log n) - the decrease-key operation Dijkstra and A* depend on.
+//
+// Design choices:
+// - heap: Vec<usize> of item indices arranged as an implicit d-ary tree
+//   (children of i are d*i+1 ..= d*i+d, parent is (i-1)/d)
+// - positions: Vec<Option<usize>> maps item -> slot in heap, kept in sync on every swap
+// - priorities: Vec<Option<P>> maps item -> current priority
+// - Arity d is chosen at construction: larger d makes the tree shallower (cheaper
+//   sift_up / decrease_key) but sift_down compares more children per level
+// - Priorities only need PartialOrd so f64 distances work; NaN priorities are not supported
+// - Item indices grow the index tables on demand, so no fixed capacity is required
+// - Trade-off: O(1) lookup of any item's position at the cost of two extra
+//   index tables sized by the largest item index ever pushed
+
+use alloc::vec::Vec;
+
+#[derive(Debug, Clone)]
+pub struct IndexedHeap<P> {
+    arity: usize,
+    heap: Vec<usize>,
+    positions: Vec<Option<usize>>,
+    priorities: Vec<Option<P>>,
+}
+
+impl<P: PartialOrd> Default for IndexedHeap<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: PartialOrd> IndexedHeap<P> {
+    // Binary heap (d = 2)
+    pub fn new() -> Self {
+        Self::with_arity(2)
+    }
+
+    pub fn with_arity(arity: usize) -> Self {
+        assert!(arity >= 2, "heap arity must be at least 2");
+        Self {
+            arity,
+            heap: Vec::new(),
+            positions: Vec::new(),
+            priorities: Vec::new(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    // The heap order plus the position and priority tables, which are sized by the
+    // largest item ever pushed rather than by len()
+    pub fn heap_size(&self) -> usize {
+        (self.heap.capacity() + self.positions.capacity()) * core::mem::size_of::<usize>()
+            + self.priorities.capacity() * core::mem::size_of::<Option<P>>()
+    }
+
+    pub fn contains(&self, item: usize) -> bool {
+        self.positions.get(item).is_some_and(|p| p.is_some())
+    }
+
+    pub fn priority(&self, item: usize) -> Option<&P> {
+        self.priorities.get(item).and_then(|p| p.as_ref())
+    }
+
+    // Add an item; returns false (and changes nothing) if it is already queued
+    pub fn push(&mut self, item: usize, priority: P) -> bool {
+        if self.contains(item) {
+            return false;
+        }
+        if item >= self.positions.len() {
+            self.positions.resize(item + 1, None);
+            self.priorities.resize_with(item + 1, || None);
+        }
+        self.priorities[item] = Some(priority);
+        self.positions[item] = Some(self.heap.len());
+        self.heap.push(item);
+        self.sift_up(self.heap.len() - 1);
+        true
+    }
+
+    // Smallest item and its priority without removing it
+    pub fn peek(&self) -> Option<(usize, &P)> {
+        let &item = self.heap.first()?;
+        self.priority(item).map(|p| (item, p))
+    }
+
+    // Remove and return the item with the smallest priority
+    pub fn pop(&mut self) -> Option<(usize, P)> {
+        let &item = self.heap.first()?;
+        self.remove(item).map(|p| (item, p))
+    }
+
+    // Remove an arbitrary item, returning its priority
+    pub fn remove(&mut self, item: usize) -> Option<P> {
+        let position = self.positions.get(item).copied().flatten()?;
+        let last = self.heap.len() - 1;
+        self.swap(position, last);
+        self.heap.pop();
+        self.positions[item] = None;
+        let priority = self.priorities[item].take();
+
+        // The element moved into the hole may need to go either way
+        if position < self.heap.len() {
+            self.sift_down(position);
+            self.sift_up(position);
+        }
+        priority
+    }
+
+    // Lower an item's priority; returns false if the item is absent or the new
+    // priority is not lower than the current one
+    pub fn decrease_key(&mut self, item: usize, priority: P) -> bool {
+        match self.priority(item) {
+            Some(current) if priority < *current => {}
+            _ => return false,
+        }
+        self.priorities[item] = Some(priority);
+        let position = self.positions[item].unwrap();
+        self.sift_up(position);
+        true
+    }
+
+    // Raise an item's priority; returns false if the item is absent or the new
+    // priority is not higher than the current one
+    pub fn increase_key(&mut self, item: usize, priority: P) -> bool {
+        match self.priority(item) {
+            Some(current) if priority > *current => {}
+            _ => return false,
+        }
+        self.priorities[item] = Some(priority);
+        let position = self.positions[item].unwrap();
+        self.sift_down(position);
+        true
+    }
+
+    // Queue the item if absent, otherwise lower its priority when the new one is
+    // smaller - the relaxation step of Dijkstra and A*. Returns true if anything changed.
+    pub fn push_or_decrease(&mut self, item: usize, priority: P) -> bool {
+        if self.contains(item) {
+            self.decrease_key(item, priority)
+        } else {
+            self.push(item, priority)
+        }
+    }
+
+    pub fn clear(&mut self) {
+        for &item in &self.heap {
+            self.positions[item] = None;
+            self.priorities[item] = None;
+        }
+        self.heap.clear();
+    }
+
+    fn less(&self, a: usize, b: usize) -> bool {
+        self.priorities[self.heap[a]] < self.priorities[self.heap[b]]
+    }
+
+    // Swap two heap slots and record the items' new positions
+    fn swap(&mut self, a: usize, b: usize) {
+        self.heap.swap(a, b);
+        self.positions[self.heap[a]] = Some(a);
+        self.positions[self.heap[b]] = Some(b);
+    }
+
+    fn sift_up(&mut self, mut index: usize) {
+        while index > 0 {
+            let parent = (index - 1) / self.arity;
+            if !self.less(index, parent) {
+                break;
+            }
+            self.swap(index, parent);
+            index = parent;
+        }
+    }
+
+    fn sift_down(&mut self, mut index: usize) {
+        loop {
+            let first_child = self.arity * index + 1;
+            let last_child = (first_child + self.arity).min(self.heap.len());
+            let mut smallest = index;
+            for child in first_child..last_child {
+                if self.less(child, smallest) {
+                    smallest = child;
+                }
+            }
+            if smallest == index {
+                break;
+            }
+            self.swap(index, smallest);
+            index = smallest;
+        }
+    }
+
+    // Verify the heap property and the position table (for debugging)
+    pub fn is_valid(&self) -> bool {
+        let ordered = (1..self.heap.len()).all(|i| !self.less(i, (i - 1) / self.arity));
+        let indexed = self.heap.iter().enumerate()
+            .all(|(slot, &item)| self.positions[item] == Some(slot));
+        ordered && indexed
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Push and pop by priority ===");
+    let mut heap = IndexedHeap::new();
+    let tasks = ["compile", "test", "lint", "deploy", "review"];
+    let priorities = [30, 50, 20, 90, 40];
+    for (item, &priority) in priorities.iter().enumerate() {
+        heap.push(item, priority);
+    }
+    println!("Length: {}, valid: {}", heap.len(), heap.is_valid());
+    println!("Push duplicate item 0: {}", heap.push(0, 1));
+    if let Some((item, priority)) = heap.peek() {
+        println!("Peek: {} ({})", tasks[item], priority);
+    }
+
+    println!("\n=== decrease_key / increase_key ===");
+    println!("Decrease deploy 90 -> 10: {}", heap.decrease_key(3, 10));
+    println!("Decrease test 50 -> 60 (not lower): {}", heap.decrease_key(1, 60));
+    println!("Increase lint 20 -> 70: {}", heap.increase_key(2, 70));
+    println!("Priority of lint: {:?}", heap.priority(2));
+    println!("Remove review: {:?}", heap.remove(4));
+    println!("Contains review: {}, valid: {}", heap.contains(4), heap.is_valid());
+    while let Some((item, priority)) = heap.pop() {
+        println!("Popped {} ({})", tasks[item], priority);
+    }
+
+    println!("\n=== Relaxation with push_or_decrease (f64 distances) ===");
+    let mut frontier = IndexedHeap::with_arity(4);
+    frontier.push_or_decrease(7, 12.5);
+    frontier.push_or_decrease(3, 4.0);
+    println!("Relax 7 to 2.5: {}", frontier.push_or_decrease(7, 2.5));
+    println!("Relax 3 to 9.0: {}", frontier.push_or_decrease(3, 9.0));
+    println!("Pop: {:?}", frontier.pop());
+    println!("Pop: {:?}", frontier.pop());
+
+    println!("\n=== Stress: random updates keep the heap valid ===");
+    for &arity in [2, 3, 4, 8].iter() {
+        let mut heap = IndexedHeap::with_arity(arity);
+        let mut state: u64 = 12345;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+        for item in 0..500 {
+            heap.push(item, next() % 10_000);
+        }
+        for _ in 0..2000 {
+            let item = (next() % 500) as usize;
+            let priority = next() % 10_000;
+            if !heap.decrease_key(item, priority) {
+                heap.increase_key(item, priority);
+            }
+        }
+        let valid = heap.is_valid();
+        let mut last = 0;
+        let mut sorted = true;
+        while let Some((_, priority)) = heap.pop() {
+            sorted &= priority >= last;
+            last = priority;
+        }
+        println!("Arity {}: valid after updates: {}, pops came out sorted: {}",
+                 arity, valid, sorted);
+    }
+
+    heap.push(1, 5);
+    heap.clear();
+    println!("\nAfter clear: length {}, is empty: {}, contains 1: {}",
+             heap.len(), heap.is_empty(), heap.contains(1));
+}
@@ -0,0 +1,324 @@
+// Min-Max Heap (Double-Ended Priority Queue)
+//
+// A binary heap whose levels alternate between min and max order: elements on
+// even levels (the root, grandchildren, ...) are no larger than all of their
+// descendants, and elements on odd levels are no smaller. The minimum is the
+// root and the maximum is one of its two children, so both ends can be peeked in
+// O(1) and popped in O(log n).
+//
+//   level 0 (min):            5
+//   level 1 (max):      70          40
+//   level 2 (min):   10    20    15    30
+//   level 3 (max):  50 60 25
+//
+// Design choices:
+// - Implicit tree in a Vec with the same index arithmetic as heaps::binary_heap
+//   (children of i are 2i+1 and 2i+2); a node's level is floor(log2(i + 1))
+// - push bubbles up along grandparents of the right kind: first compare with
+//   the parent to pick min or max, then hop two levels at a time
+// - Popping trickles down by comparing against children and grandchildren, so
+//   each step descends two levels; when the element lands on a grandchild it
+//   may also need a swap with that grandchild's parent
+// - heapify() is the same bottom-up O(n) construction as the binary heap
+// - Trade-off: about twice the comparisons of a plain binary heap per operation,
+//   in exchange for access to both ends without keeping two heaps in sync
+
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use crate::random::{Pcg32, Rng};
+
+#[derive(Debug, Clone)]
+pub struct MinMaxHeap<T> {
+    data: Vec<T>,
+}
+
+impl<T: Ord> Default for MinMaxHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// Even levels keep minima, odd levels maxima
+fn is_min_level(index: usize) -> bool {
+    (index + 1).ilog2().is_multiple_of(2)
+}
+
+impl<T: Ord> MinMaxHeap<T> {
+    pub fn new() -> Self {
+        Self { data: Vec::new() }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { data: Vec::with_capacity(capacity) }
+    }
+
+    // Build a heap from an arbitrary Vec in O(n)
+    pub fn heapify(data: Vec<T>) -> Self {
+        let mut heap = Self { data };
+        for i in (0..heap.data.len() / 2).rev() {
+            heap.trickle_down(i);
+        }
+        heap
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.data.capacity() * core::mem::size_of::<T>()
+    }
+
+    pub fn clear(&mut self) {
+        self.data.clear();
+    }
+
+    pub fn peek_min(&self) -> Option<&T> {
+        self.data.first()
+    }
+
+    pub fn peek_max(&self) -> Option<&T> {
+        self.max_index().map(|i| &self.data[i])
+    }
+
+    // The maximum is the root when alone, otherwise the larger child of the root
+    fn max_index(&self) -> Option<usize> {
+        match self.data.len() {
+            0 => None,
+            1 => Some(0),
+            2 => Some(1),
+            _ => Some(if self.data[1] >= self.data[2] { 1 } else { 2 }),
+        }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.data.push(value);
+        let last = self.data.len() - 1;
+        self.bubble_up(last);
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        self.remove_at(0)
+    }
+
+    pub fn pop_max(&mut self) -> Option<T> {
+        let index = self.max_index()?;
+        self.remove_at(index)
+    }
+
+    // Remove the element at `index` by moving the last element into its place
+    fn remove_at(&mut self, index: usize) -> Option<T> {
+        if index >= self.data.len() {
+            return None;
+        }
+        let value = self.data.swap_remove(index);
+        if index < self.data.len() {
+            self.trickle_down(index);
+        }
+        Some(value)
+    }
+
+    // Sorted ascending, consuming the heap
+    pub fn into_sorted_vec(mut self) -> Vec<T> {
+        let mut sorted = Vec::with_capacity(self.data.len());
+        while let Some(value) = self.pop_min() {
+            sorted.push(value);
+        }
+        sorted
+    }
+
+    // Heap order, not sorted
+    pub fn as_slice(&self) -> &[T] {
+        &self.data
+    }
+
+    // Is `a` closer to this level's end of the order than `b`?
+    fn before(&self, a: usize, b: usize, min_level: bool) -> bool {
+        if min_level {
+            self.data[a] < self.data[b]
+        } else {
+            self.data[a] > self.data[b]
+        }
+    }
+
+    fn bubble_up(&mut self, mut index: usize) {
+        if index == 0 {
+            return;
+        }
+        let parent = (index - 1) / 2;
+        let mut min_level = is_min_level(index);
+        // A new minimum below a max node (or maximum below a min node) swaps with
+        // the parent and then continues up the parent's kind of level
+        if self.before(parent, index, min_level) {
+            self.data.swap(index, parent);
+            index = parent;
+            min_level = !min_level;
+        }
+        while index > 2 {
+            let grandparent = ((index - 1) / 2 - 1) / 2;
+            if !self.before(index, grandparent, min_level) {
+                break;
+            }
+            self.data.swap(index, grandparent);
+            index = grandparent;
+        }
+    }
+
+    fn trickle_down(&mut self, mut index: usize) {
+        let min_level = is_min_level(index);
+        loop {
+            // The most extreme of up to two children and four grandchildren
+            let first_child = 2 * index + 1;
+            let first_grandchild = 4 * index + 3;
+            let candidates = (first_child..first_child + 2).chain(first_grandchild..first_grandchild + 4);
+            let extreme = candidates
+                .filter(|&i| i < self.data.len())
+                .fold(None, |best: Option<usize>, i| match best {
+                    Some(b) if !self.before(i, b, min_level) => Some(b),
+                    _ => Some(i),
+                });
+            let target = match extreme {
+                Some(target) if self.before(target, index, min_level) => target,
+                _ => return,
+            };
+            self.data.swap(index, target);
+            // A child is on the opposite kind of level, and the value moved into it
+            // is even further toward that level's end than the child was: done
+            if target < first_grandchild {
+                return;
+            }
+            // The value moved down to the grandchild's slot may be out of order
+            // with the grandchild's parent, which is on the opposite kind of level
+            let parent = (target - 1) / 2;
+            if self.before(parent, target, min_level) {
+                self.data.swap(parent, target);
+            }
+            index = target;
+        }
+    }
+}
+
+// Check the min-max property everywhere, for the demo
+#[cfg(feature = "std")]
+fn is_valid<T: Ord>(heap: &MinMaxHeap<T>) -> bool {
+    let data = heap.as_slice();
+    (1..data.len()).all(|i| {
+        let mut ancestor = (i - 1) / 2;
+        loop {
+            let ordered = if is_min_level(ancestor) { data[ancestor] <= data[i] } else { data[ancestor] >= data[i] };
+            if !ordered {
+                return false;
+            }
+            if ancestor == 0 {
+                return true;
+            }
+            ancestor = (ancestor - 1) / 2;
+        }
+    })
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut heap = MinMaxHeap::new();
+    for value in [40, 5, 70, 10, 20, 15, 30, 50, 60, 25].iter() {
+        heap.push(*value);
+    }
+    println!("Heap order: {:?}", heap.as_slice());
+    println!("min = {:?}, max = {:?}, valid: {}", heap.peek_min(), heap.peek_max(), is_valid(&heap));
+    print!("Alternating pops:");
+    while !heap.is_empty() {
+        print!(" {:?}", heap.pop_min().unwrap());
+        if let Some(max) = heap.pop_max() {
+            print!(" {:?}", max);
+        }
+    }
+    println!();
+
+    println!("\n=== Randomized check against a sorted Vec ===");
+    let mut rng = Pcg32::seed_from_u64(0x9E37_79B9_7F4A_7C15);
+    let mut heap = MinMaxHeap::new();
+    let mut reference: Vec<u64> = Vec::new();
+    let mut ok = true;
+    for _ in 0..20_000 {
+        match rng.gen_range(0..4) {
+            0 | 1 => {
+                let value = rng.gen_range(0..1000);
+                heap.push(value);
+                let at = reference.partition_point(|&v| v < value);
+                reference.insert(at, value);
+            }
+            2 => ok &= heap.pop_min() == (!reference.is_empty()).then(|| reference.remove(0)),
+            _ => ok &= heap.pop_max() == reference.pop(),
+        }
+        ok &= heap.peek_min() == reference.first() && heap.peek_max() == reference.last();
+    }
+    println!("20,000 random operations agree: {}, heap still valid: {}", ok, is_valid(&heap));
+    let values: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..100)).collect();
+    let built = MinMaxHeap::heapify(values.clone());
+    let mut sorted = values;
+    sorted.sort();
+    println!("heapify valid: {}, into_sorted_vec correct: {}", is_valid(&built), built.into_sorted_vec() == sorted);
+
+    println!("\n=== Bounded top-k ===");
+    // Keep the 5 largest values seen; the smallest of them is evicted in O(log k)
+    let k = 5;
+    let mut top = MinMaxHeap::with_capacity(k + 1);
+    let stream: Vec<u64> = (0..1000).map(|_| rng.gen_range(0..10_000)).collect();
+    for &value in &stream {
+        top.push(value);
+        if top.len() > k {
+            top.pop_min();
+        }
+    }
+    let mut expected = stream.clone();
+    expected.sort_unstable_by(|a, b| b.cmp(a));
+    expected.truncate(k);
+    expected.reverse();
+    let top = top.into_sorted_vec();
+    println!("Top {} of 1000: {:?}, matches sort: {}", k, top, top == expected);
+
+    println!("\n=== Trimmed mean ===");
+    // Drop outliers from both ends before averaging
+    let readings: Vec<f64> = vec![21.0, 22.5, 21.8, 250.0, 22.1, -40.0, 21.9, 22.3, 23.0, 0.0, 22.7, 21.5];
+    let mut heap = MinMaxHeap::heapify(readings.iter().map(|&r| (r * 10.0).round() as i64).collect());
+    for _ in 0..2 {
+        let low = heap.pop_min().unwrap() as f64 / 10.0;
+        let high = heap.pop_max().unwrap() as f64 / 10.0;
+        println!("Dropped {} and {}", low, high);
+    }
+    let kept = heap.into_sorted_vec();
+    let raw_mean = readings.iter().sum::<f64>() / readings.len() as f64;
+    let trimmed_mean = kept.iter().sum::<i64>() as f64 / 10.0 / kept.len() as f64;
+    println!("Raw mean {:.2}, trimmed mean {:.2}", raw_mean, trimmed_mean);
+
+    println!("\n=== Running median ===");
+    // Lower half answers "largest of the small values", upper half "smallest of
+    // the large values"; rebalancing moves one element across per insert
+    let mut lower = MinMaxHeap::new();
+    let mut upper = MinMaxHeap::new();
+    let mut medians = Vec::new();
+    for &value in [5, 15, 1, 3, 8, 7, 9, 10, 20, 2].iter() {
+        if lower.peek_max().is_none_or(|&max| value <= max) {
+            lower.push(value);
+        } else {
+            upper.push(value);
+        }
+        if lower.len() > upper.len() + 1 {
+            upper.push(lower.pop_max().unwrap());
+        } else if upper.len() > lower.len() {
+            lower.push(upper.pop_min().unwrap());
+        }
+        let median = if lower.len() > upper.len() {
+            *lower.peek_max().unwrap() as f64
+        } else {
+            (*lower.peek_max().unwrap() + *upper.peek_min().unwrap()) as f64 / 2.0
+        };
+        medians.push(median);
+    }
+    println!("Medians after each insert: {:?}", medians);
+}
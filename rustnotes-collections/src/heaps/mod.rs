@@ -0,0 +1,11 @@
+// Heaps
+
+pub mod binary_heap;
+pub mod indexed_heap;
+pub mod min_max_heap;
+pub mod pairing_heap;
+
+pub use self::binary_heap::MinHeap;
+pub use self::indexed_heap::IndexedHeap;
+pub use self::min_max_heap::MinMaxHeap;
+pub use self::pairing_heap::PairingHeap;
@@ -0,0 +1,400 @@
+// Pairing Heap Implementation
+//
+// A mergeable min-heap stored as a multi-way tree in which every node is no larger
+// than its children. It is one of the simplest heaps with a fast decrease-key:
+// - push and merge just link two trees (the larger root becomes a child): O(1),
+//   plus, for merge here, copying the other heap's arena
+// - decrease_key cuts a node's subtree out and links it back at the root: O(1)
+//   worst case here, o(log n) amortized in theory
+// - pop_min removes the root and pairs up its children left to right, then folds
+//   the pairs together right to left: O(log n) amortized
+//
+// Design choices:
+// - Nodes live in a Vec arena and refer to each other by index (child, next
+//   sibling, and "prev" = parent for a leftmost child, left sibling otherwise),
+//   which keeps the tree in safe Rust and makes unlinking a node O(1)
+// - push returns a Handle that stays valid until that item is popped, so callers
+//   can later call decrease_key on it
+// - Freed slots are reused; a handle must not be used after its item is popped
+// - merge consumes the other heap and moves its nodes into this arena; handles
+//   into the other heap do not carry over
+// - The demo benchmarks Dijkstra on the same random graph with three queues: this
+//   heap (decrease-key), the binary MinHeap (lazy duplicates), and IndexedHeap
+// - Trade-off: excellent in practice for decrease-key heavy workloads, but every
+//   node carries three links and pop_min chases pointers rather than scanning an array
+
+#[cfg(feature = "std")]
+use alloc::vec;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use crate::heaps::binary_heap::MinHeap;
+#[cfg(feature = "std")]
+use crate::heaps::indexed_heap::IndexedHeap;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+// Refers to an item pushed into a PairingHeap
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Handle(usize);
+
+struct Node<T> {
+    item: T,
+    child: Option<usize>,   // Leftmost child
+    sibling: Option<usize>, // Next sibling to the right
+    prev: Option<usize>,    // Parent if leftmost child, otherwise left sibling
+}
+
+pub struct PairingHeap<T: Ord> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: Ord> Default for PairingHeap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> PairingHeap<T> {
+    pub fn new() -> Self {
+        Self { nodes: Vec::new(), free: Vec::new(), root: None, len: 0 }
+    }
+
+    fn node(&self, index: usize) -> &Node<T> {
+        self.nodes[index].as_ref().expect("stale pairing heap handle")
+    }
+
+    fn node_mut(&mut self, index: usize) -> &mut Node<T> {
+        self.nodes[index].as_mut().expect("stale pairing heap handle")
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The node buffer (freed slots included) and the free list
+    pub fn heap_size(&self) -> usize {
+        self.nodes.capacity() * core::mem::size_of::<Option<Node<T>>>() + self.free.capacity() * core::mem::size_of::<usize>()
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.root.map(|root| &self.node(root).item)
+    }
+
+    // The item a handle refers to
+    pub fn get(&self, handle: Handle) -> &T {
+        &self.node(handle.0).item
+    }
+
+    // Make the root with the larger item the leftmost child of the other; returns
+    // the new root. Both arguments must be roots (no parent, no siblings).
+    fn link(&mut self, a: usize, b: usize) -> usize {
+        let (parent, child) = if self.node(b).item < self.node(a).item { (b, a) } else { (a, b) };
+        let old_child = self.node(parent).child;
+        if let Some(old) = old_child {
+            self.node_mut(old).prev = Some(child);
+        }
+        let node = self.node_mut(child);
+        node.sibling = old_child;
+        node.prev = Some(parent);
+        self.node_mut(parent).child = Some(child);
+        parent
+    }
+
+    fn attach_root(&mut self, index: usize) {
+        self.root = Some(match self.root {
+            Some(root) => self.link(root, index),
+            None => index,
+        });
+    }
+
+    pub fn push(&mut self, item: T) -> Handle {
+        let node = Node { item, child: None, sibling: None, prev: None };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+        self.attach_root(index);
+        self.len += 1;
+        Handle(index)
+    }
+
+    pub fn pop_min(&mut self) -> Option<T> {
+        let root = self.root?;
+        let node = self.nodes[root].take().unwrap();
+        self.free.push(root);
+        self.len -= 1;
+
+        // First pass: link children in pairs, left to right
+        let mut pairs = Vec::new();
+        let mut current = node.child;
+        while let Some(first) = current {
+            let second = self.node(first).sibling;
+            self.detach_links(first);
+            match second {
+                Some(second) => {
+                    current = self.node(second).sibling;
+                    self.detach_links(second);
+                    pairs.push(self.link(first, second));
+                }
+                None => {
+                    current = None;
+                    pairs.push(first);
+                }
+            }
+        }
+
+        // Second pass: fold the pairs together, right to left
+        self.root = pairs.pop();
+        while let Some(tree) = pairs.pop() {
+            let root = self.root.unwrap();
+            self.root = Some(self.link(tree, root));
+        }
+        Some(node.item)
+    }
+
+    fn detach_links(&mut self, index: usize) {
+        let node = self.node_mut(index);
+        node.sibling = None;
+        node.prev = None;
+    }
+
+    // Lower an item's key. Panics if `item` is greater than the current value.
+    pub fn decrease_key(&mut self, handle: Handle, item: T) {
+        let index = handle.0;
+        assert!(item <= self.node(index).item, "decrease_key cannot increase a key");
+        self.node_mut(index).item = item;
+        if self.root == Some(index) {
+            return;
+        }
+
+        // Cut the subtree out of its sibling list and link it back at the root
+        let (prev, sibling) = {
+            let node = self.node(index);
+            (node.prev.unwrap(), node.sibling)
+        };
+        if self.node(prev).child == Some(index) {
+            self.node_mut(prev).child = sibling;
+        } else {
+            self.node_mut(prev).sibling = sibling;
+        }
+        if let Some(sibling) = sibling {
+            self.node_mut(sibling).prev = Some(prev);
+        }
+        self.detach_links(index);
+        let root = self.root.unwrap();
+        self.root = Some(self.link(root, index));
+    }
+
+    // Move all of `other`'s items into this heap in O(other's size) for the copy
+    // and O(1) for the link
+    pub fn merge(&mut self, other: PairingHeap<T>) {
+        let offset = self.nodes.len();
+        let shift = |link: Option<usize>| link.map(|i| i + offset);
+        for slot in other.nodes {
+            self.nodes.push(slot.map(|node| Node {
+                item: node.item,
+                child: shift(node.child),
+                sibling: shift(node.sibling),
+                prev: shift(node.prev),
+            }));
+        }
+        self.free.extend(other.free.iter().map(|i| i + offset));
+        self.len += other.len;
+        if let Some(other_root) = other.root {
+            self.attach_root(other_root + offset);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.root = None;
+        self.len = 0;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Dijkstra benchmark on a weighted adjacency list with integer weights
+// ---------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+type Adjacency = Vec<Vec<(usize, u64)>>;
+
+#[cfg(feature = "std")]
+fn random_graph(vertices: usize, edges_per_vertex: usize, seed: u64) -> Adjacency {
+    let mut state = seed;
+    let mut next = move |bound: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    };
+    (0..vertices)
+        .map(|_| {
+            (0..edges_per_vertex)
+                .map(|_| (next(vertices as u64) as usize, 1 + next(1000)))
+                .collect()
+        })
+        .collect()
+}
+
+// Pairing heap with one entry per vertex, lowered in place by decrease_key
+#[cfg(feature = "std")]
+fn dijkstra_pairing(graph: &Adjacency, source: usize) -> Vec<Option<u64>> {
+    let mut distances = vec![None; graph.len()];
+    let mut handles: Vec<Option<Handle>> = vec![None; graph.len()];
+    let mut done = vec![false; graph.len()];
+    let mut queue = PairingHeap::new();
+    distances[source] = Some(0);
+    handles[source] = Some(queue.push((0, source)));
+    while let Some((distance, u)) = queue.pop_min() {
+        done[u] = true;
+        for &(v, weight) in &graph[u] {
+            let candidate = distance + weight;
+            if done[v] || distances[v].is_some_and(|d| d <= candidate) {
+                continue;
+            }
+            distances[v] = Some(candidate);
+            match handles[v] {
+                Some(handle) => queue.decrease_key(handle, (candidate, v)),
+                None => handles[v] = Some(queue.push((candidate, v))),
+            }
+        }
+    }
+    distances
+}
+
+// Binary heap without decrease-key: push duplicates and skip stale entries
+#[cfg(feature = "std")]
+fn dijkstra_binary(graph: &Adjacency, source: usize) -> Vec<Option<u64>> {
+    let mut distances: Vec<Option<u64>> = vec![None; graph.len()];
+    let mut queue = MinHeap::new();
+    distances[source] = Some(0);
+    queue.push((0, source));
+    while let Some((distance, u)) = queue.pop() {
+        if distances[u].is_some_and(|d| d < distance) {
+            continue; // Stale entry
+        }
+        for &(v, weight) in &graph[u] {
+            let candidate = distance + weight;
+            if distances[v].is_none_or(|d| candidate < d) {
+                distances[v] = Some(candidate);
+                queue.push((candidate, v));
+            }
+        }
+    }
+    distances
+}
+
+// Indexed binary heap with an in-place decrease-key
+#[cfg(feature = "std")]
+fn dijkstra_indexed(graph: &Adjacency, source: usize) -> Vec<Option<u64>> {
+    let mut distances: Vec<Option<u64>> = vec![None; graph.len()];
+    let mut queue = IndexedHeap::new();
+    distances[source] = Some(0);
+    queue.push(source, 0);
+    while let Some((u, distance)) = queue.pop() {
+        for &(v, weight) in &graph[u] {
+            let candidate = distance + weight;
+            if distances[v].is_none_or(|d| candidate < d) {
+                distances[v] = Some(candidate);
+                queue.push_or_decrease(v, candidate);
+            }
+        }
+    }
+    distances
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut heap = PairingHeap::new();
+    let mut handles = Vec::new();
+    for value in [50, 20, 80, 10, 70, 30].iter() {
+        handles.push((*value, heap.push(*value)));
+    }
+    println!("Pushed 50 20 80 10 70 30: len {}, min {:?}", heap.len(), heap.peek());
+    let (_, eighty) = handles[2];
+    heap.decrease_key(eighty, 5);
+    println!("decrease_key(80 -> 5): min {:?}, handle now reads {}", heap.peek(), heap.get(eighty));
+    print!("pop_min order:");
+    while let Some(value) = heap.pop_min() {
+        print!(" {}", value);
+    }
+    println!();
+
+    println!("\n=== Merge ===");
+    let mut odds = PairingHeap::new();
+    let mut evens = PairingHeap::new();
+    for x in [9, 3, 7, 1, 5].iter() {
+        odds.push(*x);
+        evens.push(*x + 1);
+    }
+    odds.merge(evens);
+    let mut merged = Vec::new();
+    while let Some(x) = odds.pop_min() {
+        merged.push(x);
+    }
+    println!("Merged and drained: {:?}", merged);
+
+    println!("\n=== Randomized check against sorting ===");
+    let mut state: u64 = 99;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % 1_000_000
+    };
+    let mut heap = PairingHeap::new();
+    let mut live: Vec<(Handle, u64)> = Vec::new();
+    for _ in 0..20_000 {
+        let value = next();
+        live.push((heap.push(value), value));
+    }
+    // Lower every third key by a random amount
+    for entry in live.iter_mut().step_by(3) {
+        let lowered = entry.1 - next() % (entry.1 + 1);
+        heap.decrease_key(entry.0, lowered);
+        entry.1 = lowered;
+    }
+    let mut expected: Vec<u64> = live.iter().map(|&(_, v)| v).collect();
+    expected.sort_unstable();
+    let mut drained = Vec::with_capacity(expected.len());
+    while let Some(value) = heap.pop_min() {
+        drained.push(value);
+    }
+    println!("20,000 pushes + decrease_keys drain in sorted order: {}", drained == expected);
+
+    println!("\n=== Dijkstra: pairing heap vs binary heaps ===");
+    println!("{:>9} {:>9} {:>14} {:>14} {:>14}",
+             "vertices", "edges", "pairing", "binary (lazy)", "indexed");
+    for &(vertices, degree) in [(10_000usize, 4usize), (10_000, 32), (100_000, 8)].iter() {
+        let graph = random_graph(vertices, degree, 0xC0FFEE);
+        let start = Instant::now();
+        let pairing = dijkstra_pairing(&graph, 0);
+        let pairing_time = start.elapsed();
+        let start = Instant::now();
+        let binary = dijkstra_binary(&graph, 0);
+        let binary_time = start.elapsed();
+        let start = Instant::now();
+        let indexed = dijkstra_indexed(&graph, 0);
+        let indexed_time = start.elapsed();
+        assert!(pairing == binary && binary == indexed, "heaps disagree on distances");
+        println!("{:>9} {:>9} {:>14.2?} {:>14.2?} {:>14.2?}",
+                 vertices, vertices * degree, pairing_time, binary_time, indexed_time);
+    }
+    println!("All three queues produced identical distances");
+}
@@ -0,0 +1,273 @@
+// Undo/Redo Journal
+//
+// The command pattern over the existing collections: every change is an
+// object that knows how to apply itself to a target and how to reverse itself.
+// Journal wraps a collection, runs commands against it, and keeps the ones
+// that changed something on an undo stack, so undo(n) reverses the last n
+// changes and redo(n) replays them.
+//
+//   execute   apply a command; a change clears the redo stack
+//   undo(n)   revert up to n changes, newest first
+//   redo(n)   re-apply up to n undone changes, in their original order
+//
+// StackOp (push, pop) works on anything implementing traits::Stack, and SetOp
+// (insert, remove) on anything implementing traits::SortedSet, so one journal
+// type covers LinkedList, SmallVec, AvlTree, SkipList and the rest.
+//
+// Design choices:
+// - Commands are plain enums rather than boxed closures: they can be printed,
+//   compared and stored without allocation per closure
+// - apply() reports whether the target changed; no-ops (popping an empty
+//   stack, inserting a value already in the set) are not recorded, so undo
+//   always reverses something real
+// - A Pop command starts empty and records the value it removed, which is what
+//   undo pushes back
+// - Executing a new change after an undo discards the redo stack, as editors
+//   do; the history is a line, not a tree
+// - The journal only reaches the target through commands, so history cannot
+//   get out of step with the contents; into_inner() ends the journal
+// - Trade-off: SetOp keeps a copy of every value it touched (T: Clone), so the
+//   history costs memory proportional to the number of changes, not the size
+//   of the collection; a snapshot per version would be the opposite trade
+
+use alloc::vec::Vec;
+use crate::traits::{SortedSet, Stack};
+#[cfg(feature = "std")]
+use crate::lists::linked_list::LinkedList;
+#[cfg(feature = "std")]
+use crate::lists::skip_list::SkipList;
+#[cfg(feature = "std")]
+use crate::trees::balanced_binary_tree::AvlTree;
+
+// A reversible change to a target of type C
+pub trait Command<C> {
+    // Perform the change; false if the target was left as it was
+    fn apply(&mut self, target: &mut C) -> bool;
+
+    // Undo a successful apply()
+    fn revert(&mut self, target: &mut C);
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StackOp<T> {
+    Push(T),
+    // The value popped, filled in by apply()
+    Pop(Option<T>),
+}
+
+impl<T: Clone, S: Stack<T>> Command<S> for StackOp<T> {
+    fn apply(&mut self, target: &mut S) -> bool {
+        match self {
+            StackOp::Push(value) => {
+                target.push(value.clone());
+                true
+            }
+            StackOp::Pop(popped) => {
+                *popped = target.pop();
+                popped.is_some()
+            }
+        }
+    }
+
+    fn revert(&mut self, target: &mut S) {
+        match self {
+            StackOp::Push(_) => {
+                target.pop();
+            }
+            StackOp::Pop(popped) => {
+                if let Some(value) = popped.take() {
+                    target.push(value);
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SetOp<T> {
+    Insert(T),
+    Remove(T),
+}
+
+impl<T: Ord + Clone, S: SortedSet<T>> Command<S> for SetOp<T> {
+    fn apply(&mut self, target: &mut S) -> bool {
+        match self {
+            SetOp::Insert(value) => target.insert(value.clone()),
+            SetOp::Remove(value) => target.remove(value),
+        }
+    }
+
+    fn revert(&mut self, target: &mut S) {
+        match self {
+            SetOp::Insert(value) => {
+                target.remove(value);
+            }
+            SetOp::Remove(value) => {
+                target.insert(value.clone());
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Journal<C, K: Command<C>> {
+    target: C,
+    done: Vec<K>,
+    undone: Vec<K>,
+}
+
+impl<C, K: Command<C>> Journal<C, K> {
+    pub fn new(target: C) -> Self {
+        Journal { target, done: Vec::new(), undone: Vec::new() }
+    }
+
+    // Read-only view of the wrapped collection
+    pub fn get(&self) -> &C {
+        &self.target
+    }
+
+    pub fn into_inner(self) -> C {
+        self.target
+    }
+
+    // Run a command, recording it if it changed the target
+    pub fn execute(&mut self, mut command: K) -> bool {
+        if !command.apply(&mut self.target) {
+            return false;
+        }
+        self.done.push(command);
+        self.undone.clear();
+        true
+    }
+
+    // Revert up to n changes; returns how many were reverted
+    pub fn undo(&mut self, n: usize) -> usize {
+        let count = n.min(self.done.len());
+        for _ in 0..count {
+            if let Some(mut command) = self.done.pop() {
+                command.revert(&mut self.target);
+                self.undone.push(command);
+            }
+        }
+        count
+    }
+
+    // Re-apply up to n undone changes; returns how many were re-applied
+    pub fn redo(&mut self, n: usize) -> usize {
+        let count = n.min(self.undone.len());
+        for _ in 0..count {
+            if let Some(mut command) = self.undone.pop() {
+                command.apply(&mut self.target);
+                self.done.push(command);
+            }
+        }
+        count
+    }
+
+    // The version number: how many changes are currently applied
+    pub fn version(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.done.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.undone.is_empty()
+    }
+
+    // Applied changes, oldest first
+    pub fn history(&self) -> &[K] {
+        &self.done
+    }
+
+    // Move to a version between 0 and version() + redo depth
+    pub fn checkout(&mut self, version: usize) {
+        if version < self.version() {
+            self.undo(self.version() - version);
+        } else {
+            self.redo(version - self.version());
+        }
+    }
+}
+
+impl<T: Clone, S: Stack<T>> Journal<S, StackOp<T>> {
+    pub fn push(&mut self, value: T) {
+        self.execute(StackOp::Push(value));
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        if !self.execute(StackOp::Pop(None)) {
+            return None;
+        }
+        match self.done.last() {
+            Some(StackOp::Pop(popped)) => popped.clone(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: Ord + Clone, S: SortedSet<T>> Journal<S, SetOp<T>> {
+    pub fn insert(&mut self, value: T) -> bool {
+        self.execute(SetOp::Insert(value))
+    }
+
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.execute(SetOp::Remove(value.clone()))
+    }
+}
+
+#[cfg(feature = "std")]
+fn contents<S: SortedSet<u64>>(set: &S) -> Vec<u64> {
+    set.iter().copied().collect()
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Undo and redo on a stack (LinkedList) ===");
+    let mut stack: Journal<LinkedList<&str>, StackOp<&str>> = Journal::new(LinkedList::new());
+    for word in ["alpha", "beta", "gamma"] {
+        stack.push(word);
+    }
+    println!("After pushing alpha, beta, gamma: top {:?}, len {}", stack.get().peek(), stack.get().len());
+    println!("pop(): {:?}", stack.pop());
+    println!("History: {:?}", stack.history());
+    println!("undo(1) brings gamma back: {}, top {:?}", stack.undo(1), stack.get().peek());
+    println!("undo(2) reverses two pushes: {}, top {:?}, len {}",
+             stack.undo(2), stack.get().peek(), stack.get().len());
+    println!("redo(1): {}, top {:?}", stack.redo(1), stack.get().peek());
+    stack.push("delta");
+    println!("push(delta) clears the redo stack: can_redo {}", stack.can_redo());
+    println!("undo(10) stops at the start: {}, empty {}", stack.undo(10), stack.get().is_empty());
+    println!("pop() on an empty stack is not recorded: {:?}, can_undo {}", stack.pop(), stack.can_undo());
+
+    println!("\n=== Versions of a sorted set (AvlTree) ===");
+    let mut set: Journal<AvlTree<u64>, SetOp<u64>> = Journal::new(AvlTree::new());
+    for value in [50, 20, 80, 20, 10] {
+        let changed = set.insert(value);
+        println!("insert({}): changed {}, version {}", value, changed, set.version());
+    }
+    set.remove(&50);
+    println!("remove(50): {:?}, version {}", contents(set.get()), set.version());
+    for version in [0, 2, set.version()] {
+        set.checkout(version);
+        println!("checkout({}): {:?}", version, contents(set.get()));
+    }
+
+    println!("\n=== The same journal type over a SkipList ===");
+    let mut skip: Journal<SkipList<u64>, SetOp<u64>> = Journal::new(SkipList::new());
+    (0..1_000).for_each(|value| {
+        skip.insert(value * 7 % 1_000);
+    });
+    (0..1_000).step_by(2).for_each(|value| {
+        skip.remove(&value);
+    });
+    println!("1000 inserts, 500 removes: len {}, version {}", skip.get().len(), skip.version());
+    skip.undo(500);
+    println!("undo(500) restores the evens: len {}", skip.get().len());
+    skip.undo(1_000);
+    println!("undo(1000) empties it: len {}", skip.get().len());
+    let replayed = skip.redo(1_500);
+    println!("redo(1500) replays {} changes: len {}, version {}", replayed, skip.get().len(), skip.version());
+}
@@ -0,0 +1,53 @@
+// RustNotes Collections
+//
+// The data structure notes: containers with no dependencies outside std, so
+// code that only needs a list, tree or map builds this crate alone. Without
+// the default "std" feature the crate is no_std and only needs alloc, for
+// embedded and WASM targets.
+//
+//   lists    linked lists, stacks and queues, skip list, arenas
+//   trees    search trees, trie, segment/k-d/quad/Merkle trees
+//   heaps    binary, min-max, pairing and indexed heaps
+//   hashing  hash functions, hash maps, LRU cache, memoization
+//   sets     bit vector, interval set, Bloom filter
+//   random   seedable pseudo-random number generators
+//   traits   Collection, Stack, Queue and SortedSet across the structures
+//   macros   linked_list![..], dlist![..] and avl![..] literals
+//   journal  undo/redo over any stack or sorted set (command pattern)
+//   steps    mutation reports for visualizers (AvlTree and the linked lists)
+//
+// With the "serde" feature, serde_support adds Serialize and Deserialize for
+// the lists and trees. With "trace", the structures report what they do
+// internally (rotations, rebuilds, resizes, allocations) through the log crate.
+//
+// Design choices:
+// - Modules are grouped by topic; each group re-exports its main types, so
+//   heaps::MinHeap and heaps::binary_heap::MinHeap are the same thing
+// - random lives here rather than with the math notes because randomized
+//   structures and their demos (the min-max heap) need it, and this crate sits
+//   at the bottom of the workspace
+// - The "std" feature gates everything that prints or needs the OS: every
+//   demo(), the RandomState-based hash maps and the RwLock-based tree; the
+//   lists, trees, heaps, sets and hash functions only use core and alloc
+// - Trade-off: each note keeps its demo() in the library, which makes the crate
+//   bigger than the data structures alone, but keeps notes readable top to bottom
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[macro_use]
+mod trace;
+
+pub mod hashing;
+pub mod heaps;
+pub mod journal;
+pub mod lists;
+mod macros;
+pub mod random;
+#[cfg(feature = "serde")]
+pub mod serde_support;
+pub mod sets;
+pub mod steps;
+pub mod traits;
+pub mod trees;
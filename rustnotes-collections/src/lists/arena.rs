@@ -0,0 +1,261 @@
+// Typed Arena with Generational Indices
+//
+// A Vec of slots that hands out small Copy handles instead of references. Linked
+// structures built on it store handles where they would store pointers, so a
+// doubly-linked list or a tree with parent links needs no Rc, RefCell, Weak or
+// unsafe: the arena owns every node and the borrow checker sees one Vec.
+//
+//   slots:  [ A gen 0 | free gen 1 -> 3 | C gen 0 | free gen 2 -> end ]
+//   free list head: 1
+//
+//   Index { slot: 1, generation: 0 }  -> stale: slot 1 was freed (now gen 1)
+//   insert(D) reuses slot 1          -> Index { slot: 1, generation: 1 }
+//
+// The generation is what makes reuse safe. Every slot counts how many times it
+// has been freed; an Index remembers the count at insertion, and a lookup with
+// an old count returns None instead of silently reaching the slot's new value
+// (the ABA problem plain indices have).
+//
+// Design choices:
+// - Freed slots form an intrusive free list (each free slot holds the next free
+//   slot number), so insert and remove are O(1) without a separate stack
+// - Index is two u32s: 8 bytes, Copy, Eq and Hash, cheap to store in nodes
+// - get2_mut() borrows two distinct slots mutably at once via split_at_mut,
+//   which is what relinking neighbours in a list needs
+// - Iteration walks the slot Vec in order, skipping free slots
+// - See lists::arena_linked_list and trees::arena_binary_tree for structures built on it
+// - Trade-off: freed slots are reused but never returned to the allocator, and
+//   a structure can still "leak" nodes inside the arena if it forgets a handle;
+//   memory is released when the whole arena is dropped
+
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Index {
+    slot: u32,
+    generation: u32,
+}
+
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "#{}v{}", self.slot, self.generation)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Slot<T> {
+    Occupied { generation: u32, value: T },
+    Free { generation: u32, next_free: Option<u32> },
+}
+
+#[derive(Debug, Clone)]
+pub struct Arena<T> {
+    slots: Vec<Slot<T>>,
+    free_head: Option<u32>,
+    len: usize,
+}
+
+impl<T> Default for Arena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Arena<T> {
+    pub fn new() -> Self {
+        Arena { slots: Vec::new(), free_head: None, len: 0 }
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        Arena { slots: Vec::with_capacity(capacity), free_head: None, len: 0 }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The slot buffer, free slots and spare capacity included
+    pub fn heap_size(&self) -> usize {
+        self.slots.capacity() * core::mem::size_of::<Slot<T>>()
+    }
+
+    // Slots allocated so far, occupied or free
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    pub fn insert(&mut self, value: T) -> Index {
+        self.len += 1;
+        match self.free_head {
+            Some(slot) => {
+                let generation = match self.slots[slot as usize] {
+                    Slot::Free { generation, next_free } => {
+                        self.free_head = next_free;
+                        generation
+                    }
+                    Slot::Occupied { .. } => unreachable!("free list points at an occupied slot"),
+                };
+                self.slots[slot as usize] = Slot::Occupied { generation, value };
+                Index { slot, generation }
+            }
+            None => {
+                let slot = u32::try_from(self.slots.len()).expect("arena is full");
+                self.slots.push(Slot::Occupied { generation: 0, value });
+                Index { slot, generation: 0 }
+            }
+        }
+    }
+
+    // Free the slot and return its value; None if the index is stale
+    pub fn remove(&mut self, index: Index) -> Option<T> {
+        if !self.contains(index) {
+            return None;
+        }
+        let freed = Slot::Free { generation: index.generation.wrapping_add(1), next_free: self.free_head };
+        self.free_head = Some(index.slot);
+        self.len -= 1;
+        match core::mem::replace(&mut self.slots[index.slot as usize], freed) {
+            Slot::Occupied { value, .. } => Some(value),
+            Slot::Free { .. } => unreachable!(),
+        }
+    }
+
+    pub fn contains(&self, index: Index) -> bool {
+        self.get(index).is_some()
+    }
+
+    pub fn get(&self, index: Index) -> Option<&T> {
+        match self.slots.get(index.slot as usize) {
+            Some(Slot::Occupied { generation, value }) if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn get_mut(&mut self, index: Index) -> Option<&mut T> {
+        match self.slots.get_mut(index.slot as usize) {
+            Some(Slot::Occupied { generation, value }) if *generation == index.generation => Some(value),
+            _ => None,
+        }
+    }
+
+    // Mutable access to two different live entries at once
+    pub fn get2_mut(&mut self, a: Index, b: Index) -> Option<(&mut T, &mut T)> {
+        if a.slot == b.slot || !self.contains(a) || !self.contains(b) {
+            return None;
+        }
+        let (low, high) = (a.slot.min(b.slot) as usize, a.slot.max(b.slot) as usize);
+        let (head, tail) = self.slots.split_at_mut(high);
+        let (first, second) = match (&mut head[low], &mut tail[0]) {
+            (Slot::Occupied { value: x, .. }, Slot::Occupied { value: y, .. }) => (x, y),
+            _ => unreachable!(),
+        };
+        Some(if a.slot < b.slot { (first, second) } else { (second, first) })
+    }
+
+    // Drop every value; all outstanding indices become stale
+    pub fn clear(&mut self) {
+        let mut next_free = None;
+        for (slot, entry) in self.slots.iter_mut().enumerate().rev() {
+            let generation = match entry {
+                Slot::Occupied { generation, .. } => generation.wrapping_add(1),
+                Slot::Free { generation, .. } => *generation,
+            };
+            *entry = Slot::Free { generation, next_free };
+            next_free = Some(slot as u32);
+        }
+        self.free_head = next_free;
+        self.len = 0;
+    }
+
+    // Live entries in slot order
+    pub fn iter(&self) -> impl Iterator<Item = (Index, &T)> {
+        self.slots.iter().enumerate().filter_map(|(slot, entry)| match entry {
+            Slot::Occupied { generation, value } => {
+                Some((Index { slot: slot as u32, generation: *generation }, value))
+            }
+            Slot::Free { .. } => None,
+        })
+    }
+}
+
+impl<T> core::ops::Index<Index> for Arena<T> {
+    type Output = T;
+
+    fn index(&self, index: Index) -> &T {
+        self.get(index).expect("stale arena index")
+    }
+}
+
+impl<T> core::ops::IndexMut<Index> for Arena<T> {
+    fn index_mut(&mut self, index: Index) -> &mut T {
+        self.get_mut(index).expect("stale arena index")
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Insert, remove, reuse ===");
+    let mut arena = Arena::new();
+    let a = arena.insert("alpha");
+    let b = arena.insert("beta");
+    let c = arena.insert("gamma");
+    println!("Inserted {} {} {}; len {}", a, b, c, arena.len());
+    println!("remove({}) = {:?}", b, arena.remove(b));
+    println!("remove({}) again = {:?}", b, arena.remove(b));
+    let d = arena.insert("delta");
+    println!("Inserted {} into the freed slot; capacity still {}", d, arena.capacity());
+    println!("get({}) = {:?}  <- stale handle, not \"delta\"", b, arena.get(b));
+    println!("get({}) = {:?}", d, arena.get(d));
+    arena[a] = "ALPHA";
+    let live: Vec<String> = arena.iter().map(|(i, v)| format!("{}={}", i, v)).collect();
+    println!("Live entries: {}", live.join(", "));
+
+    println!("\n=== Two mutable borrows ===");
+    let mut counters: Arena<Vec<u32>> = Arena::new();
+    let x = counters.insert(vec![1, 2, 3]);
+    let y = counters.insert(Vec::new());
+    if let Some((from, to)) = counters.get2_mut(x, y) {
+        to.append(from);
+    }
+    println!("After moving x into y: x = {:?}, y = {:?}", counters[x], counters[y]);
+    println!("get2_mut(x, x) = {:?}", counters.get2_mut(x, x).is_some());
+
+    println!("\n=== Clear invalidates every handle ===");
+    counters.clear();
+    println!("len {}, get(x) = {:?}, get(y) = {:?}", counters.len(), counters.get(x), counters.get(y));
+    let z = counters.insert(vec![9]);
+    println!("New insert after clear: {} (slot reused, next generation)", z);
+
+    println!("\n=== Random operations vs a reference model ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut arena = Arena::new();
+    let mut live: Vec<(Index, u64)> = Vec::new();
+    let mut dead: Vec<Index> = Vec::new();
+    let mut ok = true;
+    for step in 0..200_000u64 {
+        if live.is_empty() || next() % 3 != 0 {
+            live.push((arena.insert(step), step));
+        } else {
+            let (index, value) = live.swap_remove(next() as usize % live.len());
+            ok &= arena.remove(index) == Some(value);
+            dead.push(index);
+        }
+    }
+    ok &= arena.len() == live.len();
+    ok &= live.iter().all(|&(index, value)| arena.get(index) == Some(&value));
+    ok &= dead.iter().all(|&index| arena.get(index).is_none());
+    println!("200000 operations: {} live, {} slots, all lookups agree: {}", arena.len(), arena.capacity(), ok);
+    println!("Size of an Index: {} bytes", core::mem::size_of::<Index>());
+}
@@ -0,0 +1,350 @@
+// Arena-Backed Doubly-Linked List
+//
+// The same structure as lists::double_linked_list, but the nodes live in an
+// Arena<Node<T>> from lists::arena and link to each other by generational Index
+// instead of Rc/Weak pointers:
+//
+//   arena:  [ #0 "b" prev #2 next #1 | #1 "c" prev #0 next - | #2 "a" prev - next #0 ]
+//   head = #2, tail = #1                       list order: a <-> b <-> c
+//
+// Every NodeHandle returned by push_front/push_back/insert_after/insert_before
+// stays valid until that node is removed, so callers can remove, move or insert
+// next to a node in O(1), as with NodeRef in the Rc version. A handle to a
+// removed node is detected by its generation and rejected, even after the slot
+// has been reused for a new node: the Option methods return None, the try_
+// methods ListError::StaleHandle.
+//
+// Design choices:
+// - No Rc, RefCell or Weak: borrowing is checked at compile time, and there
+//   are no reference counts to maintain or cycles to break
+// - prev and next are both plain Option<Index>; the two directions are
+//   symmetric, unlike the owning-next/weak-prev split the Rc version needs
+// - Relinking reads a node's neighbours (Copy indices) first, then writes each
+//   neighbour separately, so no two nodes are ever borrowed at once
+// - reverse() swaps prev/next in every node and then head/tail, O(n) with no
+//   allocation
+// - Nodes sit contiguously in one Vec, which is friendlier to the cache than
+//   one heap allocation per node
+// - Trade-off: lookups go through an index check on every step, and memory for
+//   removed nodes is recycled by the arena rather than returned to the system
+
+#[cfg(feature = "std")]
+use alloc::collections::VecDeque;
+use crate::lists::arena::{Arena, Index};
+use crate::lists::error::ListError;
+
+// A node's place in one ArenaList; Copy, and checked on every use
+pub type NodeHandle = Index;
+
+struct Node<T> {
+    value: T,
+    prev: Option<Index>,
+    next: Option<Index>,
+}
+
+pub struct ArenaList<T> {
+    nodes: Arena<Node<T>>,
+    head: Option<Index>,
+    tail: Option<Index>,
+}
+
+impl<T> Default for ArenaList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ArenaList<T> {
+    pub fn new() -> Self {
+        ArenaList { nodes: Arena::new(), head: None, tail: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Every node lives in the arena's one buffer
+    pub fn heap_size(&self) -> usize {
+        self.nodes.heap_size()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.head.map(|index| &self.nodes[index].value)
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.tail.map(|index| &self.nodes[index].value)
+    }
+
+    pub fn get(&self, handle: NodeHandle) -> Option<&T> {
+        self.nodes.get(handle).map(|node| &node.value)
+    }
+
+    // False once the node has been removed (or the list cleared)
+    pub fn is_valid(&self, handle: NodeHandle) -> bool {
+        self.nodes.contains(handle)
+    }
+
+    pub fn get_mut(&mut self, handle: NodeHandle) -> Option<&mut T> {
+        self.nodes.get_mut(handle).map(|node| &mut node.value)
+    }
+
+    pub fn push_front(&mut self, value: T) -> NodeHandle {
+        let index = self.nodes.insert(Node { value, prev: None, next: None });
+        self.link_front(index);
+        index
+    }
+
+    pub fn push_back(&mut self, value: T) -> NodeHandle {
+        let index = self.nodes.insert(Node { value, prev: self.tail, next: None });
+        match self.tail {
+            Some(tail) => self.nodes[tail].next = Some(index),
+            None => self.head = Some(index),
+        }
+        self.tail = Some(index);
+        index
+    }
+
+    // Insert right after an existing node; None if the handle is stale
+    pub fn insert_after(&mut self, handle: NodeHandle, value: T) -> Option<NodeHandle> {
+        self.try_insert_after(handle, value).ok()
+    }
+
+    pub fn try_insert_after(&mut self, handle: NodeHandle, value: T) -> Result<NodeHandle, ListError> {
+        let next = self.nodes.get(handle).ok_or(ListError::StaleHandle(handle))?.next;
+        let index = self.nodes.insert(Node { value, prev: Some(handle), next });
+        self.nodes[handle].next = Some(index);
+        match next {
+            Some(next) => self.nodes[next].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        Ok(index)
+    }
+
+    // Insert right before an existing node; None if the handle is stale
+    pub fn insert_before(&mut self, handle: NodeHandle, value: T) -> Option<NodeHandle> {
+        self.try_insert_before(handle, value).ok()
+    }
+
+    pub fn try_insert_before(&mut self, handle: NodeHandle, value: T) -> Result<NodeHandle, ListError> {
+        let prev = self.nodes.get(handle).ok_or(ListError::StaleHandle(handle))?.prev;
+        let index = self.nodes.insert(Node { value, prev, next: Some(handle) });
+        self.nodes[handle].prev = Some(index);
+        match prev {
+            Some(prev) => self.nodes[prev].next = Some(index),
+            None => self.head = Some(index),
+        }
+        Ok(index)
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.head.and_then(|index| self.remove(index))
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.tail.and_then(|index| self.remove(index))
+    }
+
+    // Remove a node in O(1) and return its value; None if the handle is stale
+    pub fn remove(&mut self, handle: NodeHandle) -> Option<T> {
+        self.try_remove(handle).ok()
+    }
+
+    pub fn try_remove(&mut self, handle: NodeHandle) -> Result<T, ListError> {
+        if !self.nodes.contains(handle) {
+            return Err(ListError::StaleHandle(handle));
+        }
+        self.unlink(handle);
+        Ok(self.nodes.remove(handle).expect("live handle").value)
+    }
+
+    // Move a node to the front in O(1); false if the handle is stale
+    pub fn move_to_front(&mut self, handle: NodeHandle) -> bool {
+        if !self.nodes.contains(handle) {
+            return false;
+        }
+        if self.head != Some(handle) {
+            self.unlink(handle);
+            self.link_front(handle);
+        }
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.head = None;
+        self.tail = None;
+    }
+
+    pub fn reverse(&mut self) {
+        let mut current = self.head;
+        while let Some(index) = current {
+            let node = &mut self.nodes[index];
+            core::mem::swap(&mut node.prev, &mut node.next);
+            current = node.prev; // the old next
+        }
+        core::mem::swap(&mut self.head, &mut self.tail);
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { list: self, front: self.head, back: self.tail, remaining: self.len() }
+    }
+
+    // Detach a live node from its neighbours; its own links are left stale
+    fn unlink(&mut self, index: Index) {
+        let (prev, next) = {
+            let node = &self.nodes[index];
+            (node.prev, node.next)
+        };
+        match prev {
+            Some(prev) => self.nodes[prev].next = next,
+            None => self.head = next,
+        }
+        match next {
+            Some(next) => self.nodes[next].prev = prev,
+            None => self.tail = prev,
+        }
+    }
+
+    // Attach a live, detached node at the front
+    fn link_front(&mut self, index: Index) {
+        let old_head = self.head;
+        {
+            let node = &mut self.nodes[index];
+            node.prev = None;
+            node.next = old_head;
+        }
+        match old_head {
+            Some(head) => self.nodes[head].prev = Some(index),
+            None => self.tail = Some(index),
+        }
+        self.head = Some(index);
+    }
+}
+
+// Walks from both ends; `remaining` stops the two cursors when they meet
+pub struct Iter<'a, T> {
+    list: &'a ArenaList<T>,
+    front: Option<Index>,
+    back: Option<Index>,
+    remaining: usize,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = &self.list.nodes[self.front?];
+        self.front = node.next;
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let node = &self.list.nodes[self.back?];
+        self.back = node.prev;
+        self.remaining -= 1;
+        Some(&node.value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut list = ArenaList::new();
+    let b = list.push_back("b");
+    list.push_back("c");
+    let a = list.push_front("a");
+    list.insert_after(b, "b2");
+    println!("List: {:?}", list.iter().collect::<Vec<_>>());
+    println!("Backwards: {:?}", list.iter().rev().collect::<Vec<_>>());
+    println!("front {:?}, back {:?}, len {}", list.front(), list.back(), list.len());
+
+    println!("\n=== O(1) removal and moves by handle ===");
+    println!("remove(b) = {:?}", list.remove(b));
+    println!("List: {:?}", list.iter().collect::<Vec<_>>());
+    let d = list.push_back("d"); // reuses b's slot
+    println!("remove(b) again = {:?} (stale, although its slot now holds {:?})", list.remove(b), list.get(d));
+    list.move_to_front(d);
+    println!("After move_to_front(d): {:?}", list.iter().collect::<Vec<_>>());
+    if let Some(value) = list.get_mut(a) {
+        *value = "A";
+    }
+    list.reverse();
+    println!("After reverse: {:?}", list.iter().collect::<Vec<_>>());
+    println!("pop_front {:?}, pop_back {:?}", list.pop_front(), list.pop_back());
+    println!("List: {:?}", list.iter().collect::<Vec<_>>());
+
+    println!("\n=== Random operations vs VecDeque ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut list = ArenaList::new();
+    let mut model: VecDeque<u32> = VecDeque::new();
+    let mut ok = true;
+    for step in 0..100_000u32 {
+        match next() % 6 {
+            0 | 1 => {
+                list.push_back(step);
+                model.push_back(step);
+            }
+            2 => {
+                list.push_front(step);
+                model.push_front(step);
+            }
+            3 => ok &= list.pop_front() == model.pop_front(),
+            4 => ok &= list.pop_back() == model.pop_back(),
+            _ => {
+                if step % 1000 == 0 {
+                    list.reverse();
+                    model = model.into_iter().rev().collect();
+                }
+            }
+        }
+        ok &= list.len() == model.len() && list.front() == model.front() && list.back() == model.back();
+    }
+    ok &= list.iter().eq(model.iter()) && list.iter().rev().eq(model.iter().rev());
+    println!("100000 operations agree with VecDeque: {} (final length {})", ok, list.len());
+
+    println!("\n=== Inserting around a handle ===");
+    let mut list = ArenaList::new();
+    let middle = list.push_back(5);
+    list.insert_before(middle, 4);
+    list.insert_after(middle, 6);
+    println!("Around 5: {:?}", list.iter().collect::<Vec<_>>());
+    list.remove(middle);
+    println!("After removing 5: {:?}, handle valid: {}", list.iter().collect::<Vec<_>>(), list.is_valid(middle));
+    match list.try_insert_before(middle, 0) {
+        Ok(_) => println!("try_insert_before a stale handle succeeded?"),
+        Err(error) => println!("try_insert_before: {}", error),
+    }
+
+    println!("\n=== Handles survive other removals ===");
+    let mut list = ArenaList::new();
+    let handles: Vec<NodeHandle> = (0..10).map(|i| list.push_back(i)).collect();
+    for &handle in handles.iter().step_by(2) {
+        list.remove(handle);
+    }
+    for &handle in handles.iter().skip(1).step_by(2).rev() {
+        list.move_to_front(handle);
+    }
+    println!("Removed evens, moved odds to the front in reverse: {:?}", list.iter().collect::<Vec<_>>());
+    let valid = handles.iter().filter(|&&h| list.get(h).is_some()).count();
+    println!("{} of {} handles still valid", valid, handles.len());
+}
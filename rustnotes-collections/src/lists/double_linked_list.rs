@@ -9,10 +9,19 @@
 // - Uses Weak<RefCell<Node<T>>> for prev pointers (non-owning, breaks reference cycles)
 // - This approach eliminates all unsafe code and enables trivial memory management
 // - clear() simply drops references; reference counting handles cleanup automatically
+// - push_ref() hands out a NodeRef (a Weak pointer to the node) so callers can later
+//   move or remove that exact node in O(1); stale refs are detected via upgrade()
+// - Each list owns an Rc<()> token and every NodeRef keeps a Weak to it, so a
+//   ref handed out by another list is refused instead of relinking its nodes
+//   into this one (the Weak keeps the token's allocation, so a later list
+//   cannot reuse its address)
+// - push, push_end, pop and pop_end have *_recorded variants that report each
+//   link change, next and prev alike, to a steps::Recorder
 // - Trade-off: Runtime overhead from reference counting vs memory safety and simplicity
 
-use std::rc::{Rc, Weak};
-use std::cell::RefCell;
+use core::cell::{Ref, RefCell};
+use alloc::rc::{Rc, Weak};
+use crate::steps::{node_id, relink, Link, NodeId, Recorder, Step};
 
 #[derive(Debug)]
 struct Node<T> {
@@ -21,11 +30,41 @@ struct Node<T> {
     prev: Option<Weak<RefCell<Node<T>>>>,
 }
 
+// Non-owning handle to one node of a list, returned by push_ref().
+// Holding it does not keep the node alive or block pop().
+#[derive(Debug)]
+pub struct NodeRef<T> {
+    node: Weak<RefCell<Node<T>>>,
+    // The owner token of the list that handed it out
+    owner: Weak<()>,
+}
+
+impl<T> Clone for NodeRef<T> {
+    fn clone(&self) -> Self {
+        NodeRef { node: self.node.clone(), owner: self.owner.clone() }
+    }
+}
+
+impl<T> NodeRef<T> {
+    // False once the node has been removed from its list
+    pub fn is_valid(&self) -> bool {
+        self.node.strong_count() > 0
+    }
+}
+
 #[derive(Debug)]
 pub struct DoubleLinkedList<T> {
     head: Option<Rc<RefCell<Node<T>>>>,
     tail: Option<Weak<RefCell<Node<T>>>>,
     length: usize,
+    // Identifies this list to the NodeRefs it hands out
+    owner: Rc<()>,
+}
+
+impl<T> Default for DoubleLinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl<T> DoubleLinkedList<T> {
@@ -34,51 +73,120 @@ impl<T> DoubleLinkedList<T> {
             head: None,
             tail: None,
             length: 0,
+            owner: Rc::new(()),
         }
     }
 
+    fn id(node: &Rc<RefCell<Node<T>>>) -> NodeId {
+        node_id(&**node)
+    }
+
+    fn weak_id(node: &Option<Weak<RefCell<Node<T>>>>) -> Option<NodeId> {
+        node.as_ref().map(|weak| weak.as_ptr() as NodeId)
+    }
+
     pub fn push(&mut self, data: T) {
+        self.push_recorded(data, &mut ());
+    }
+
+    // push, reporting each step to the recorder
+    pub fn push_recorded<R: Recorder>(&mut self, data: T, recorder: &mut R) {
+        trace!("allocate front node {} ({} bytes)", self.length + 1, core::mem::size_of::<RefCell<Node<T>>>());
         let new_node = Rc::new(RefCell::new(Node {
             data,
-            next: self.head.take(),
+            next: None,
             prev: None,
         }));
-        
-        if let Some(ref old_head) = new_node.borrow().next {
-            old_head.borrow_mut().prev = Some(Rc::downgrade(&new_node));
+        recorder.record(Step::Create(Self::id(&new_node)));
+        self.link_front(new_node, recorder);
+    }
+
+    // Push to the front and return a handle to the new node
+    pub fn push_ref(&mut self, data: T) -> NodeRef<T> {
+        self.push(data);
+        NodeRef { node: Rc::downgrade(self.head.as_ref().unwrap()), owner: Rc::downgrade(&self.owner) }
+    }
+
+    // The node behind a ref, if the ref came from this list and is not stale
+    fn resolve(&self, node: &NodeRef<T>) -> Option<Rc<RefCell<Node<T>>>> {
+        if !core::ptr::eq(node.owner.as_ptr(), Rc::as_ptr(&self.owner)) {
+            return None;
+        }
+        node.node.upgrade()
+    }
+
+    // Attach a detached node as the new head
+    fn link_front<R: Recorder>(&mut self, node: Rc<RefCell<Node<T>>>, recorder: &mut R) {
+        let id = Self::id(&node);
+        {
+            let mut n = node.borrow_mut();
+            relink(recorder, Link::Prev(id), Self::weak_id(&n.prev), None);
+            n.prev = None;
+            let before = n.next.as_ref().map(Self::id);
+            n.next = self.head.take();
+            relink(recorder, Link::Next(id), before, n.next.as_ref().map(Self::id));
+        }
+
+        if let Some(ref old_head) = node.borrow().next {
+            old_head.borrow_mut().prev = Some(Rc::downgrade(&node));
+            recorder.record(Step::Link { link: Link::Prev(Self::id(old_head)), to: Some(id) });
         } else {
-            self.tail = Some(Rc::downgrade(&new_node));
+            self.tail = Some(Rc::downgrade(&node));
+            recorder.record(Step::Link { link: Link::Tail, to: Some(id) });
         }
-        
-        self.head = Some(new_node);
+
+        self.head = Some(node);
+        recorder.record(Step::Link { link: Link::Head, to: Some(id) });
         self.length += 1;
     }
 
     pub fn push_end(&mut self, data: T) {
+        self.push_end_recorded(data, &mut ());
+    }
+
+    // push_end, reporting each step to the recorder
+    pub fn push_end_recorded<R: Recorder>(&mut self, data: T, recorder: &mut R) {
+        trace!("allocate back node {} ({} bytes)", self.length + 1, core::mem::size_of::<RefCell<Node<T>>>());
         let new_node = Rc::new(RefCell::new(Node {
             data,
             next: None,
             prev: self.tail.clone(),
         }));
+        let id = Self::id(&new_node);
+        recorder.record(Step::Create(id));
+        relink(recorder, Link::Prev(id), None, Self::weak_id(&self.tail));
         
         if let Some(old_tail) = self.tail.as_ref().and_then(|w| w.upgrade()) {
             old_tail.borrow_mut().next = Some(new_node.clone());
+            recorder.record(Step::Link { link: Link::Next(Self::id(&old_tail)), to: Some(id) });
         } else {
             self.head = Some(new_node.clone());
+            recorder.record(Step::Link { link: Link::Head, to: Some(id) });
         }
         
         self.tail = Some(Rc::downgrade(&new_node));
+        recorder.record(Step::Link { link: Link::Tail, to: Some(id) });
         self.length += 1;
     }
 
     pub fn pop(&mut self) -> Option<T> {
+        self.pop_recorded(&mut ())
+    }
+
+    // pop, reporting each step to the recorder
+    pub fn pop_recorded<R: Recorder>(&mut self, recorder: &mut R) -> Option<T> {
         self.head.take().map(|old_head| {
             if let Some(new_head) = old_head.borrow_mut().next.take() {
                 new_head.borrow_mut().prev = None;
+                recorder.record(Step::Link { link: Link::Prev(Self::id(&new_head)), to: None });
+                recorder.record(Step::Link { link: Link::Head, to: Some(Self::id(&new_head)) });
                 self.head = Some(new_head);
             } else {
                 self.tail = None;
+                recorder.record(Step::Link { link: Link::Head, to: None });
+                recorder.record(Step::Link { link: Link::Tail, to: None });
             }
+            recorder.record(Step::Delete(Self::id(&old_head)));
             self.length -= 1;
             
             // Extract the data from the Rc<RefCell<Node<T>>>
@@ -90,16 +198,26 @@ impl<T> DoubleLinkedList<T> {
     }
 
     pub fn pop_end(&mut self) -> Option<T> {
+        self.pop_end_recorded(&mut ())
+    }
+
+    // pop_end, reporting each step to the recorder
+    pub fn pop_end_recorded<R: Recorder>(&mut self, recorder: &mut R) -> Option<T> {
         self.tail.as_ref()?.upgrade().map(|old_tail| {
             let prev = old_tail.borrow_mut().prev.take();
             
             if let Some(new_tail) = prev.as_ref().and_then(|w| w.upgrade()) {
                 new_tail.borrow_mut().next = None;
                 self.tail = Some(Rc::downgrade(&new_tail));
+                recorder.record(Step::Link { link: Link::Next(Self::id(&new_tail)), to: None });
+                recorder.record(Step::Link { link: Link::Tail, to: Some(Self::id(&new_tail)) });
             } else {
                 self.head = None;
                 self.tail = None;
+                recorder.record(Step::Link { link: Link::Head, to: None });
+                recorder.record(Step::Link { link: Link::Tail, to: None });
             }
+            recorder.record(Step::Delete(Self::id(&old_tail)));
             
             self.length -= 1;
             
@@ -110,10 +228,80 @@ impl<T> DoubleLinkedList<T> {
         })
     }
 
+    // Borrow the front value; the returned guard keeps the node's RefCell borrowed
+    pub fn peek(&self) -> Option<Ref<'_, T>> {
+        self.head.as_ref().map(|node| Ref::map(node.borrow(), |n| &n.data))
+    }
+
+    // Move the referenced node to the front in O(1); false if the ref is stale
+    // or belongs to another list
+    pub fn move_to_front(&mut self, node: &NodeRef<T>) -> bool {
+        let node = match self.resolve(node) {
+            Some(node) => node,
+            None => return false,
+        };
+        if self.head.as_ref().is_some_and(|head| Rc::ptr_eq(head, &node)) {
+            return true;
+        }
+        self.remove_node(node.clone());
+        self.link_front(node, &mut ());
+        true
+    }
+
+    // Remove the referenced node in O(1), returning its data; None if the ref is
+    // stale or belongs to another list
+    pub fn remove_ref(&mut self, node: &NodeRef<T>) -> Option<T> {
+        let node = self.resolve(node)?;
+        self.remove_node(node.clone());
+        node.borrow_mut().next = None;
+
+        match Rc::try_unwrap(node) {
+            Ok(cell) => Some(cell.into_inner().data),
+            Err(_) => panic!("Multiple references to node during remove_ref"),
+        }
+    }
+
+    // Visit every value from front to back
+    pub fn for_each<F: FnMut(&T)>(&self, mut f: F) {
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            f(&node.borrow().data);
+            current = node.borrow().next.clone();
+        }
+    }
+
+    // Visit every node's id and value from front to back, for drawing the list
+    // between recorded operations
+    pub fn for_each_node<F: FnMut(NodeId, &T)>(&self, mut f: F) {
+        let mut current = self.head.clone();
+        while let Some(node) = current {
+            f(Self::id(&node), &node.borrow().data);
+            current = node.borrow().next.clone();
+        }
+    }
+
+    // Visit every value from back to front, following the prev links
+    pub fn for_each_rev<F: FnMut(&T)>(&self, mut f: F) {
+        let mut current = self.tail.as_ref().and_then(|w| w.upgrade());
+        while let Some(node) = current {
+            f(&node.borrow().data);
+            current = node.borrow().prev.as_ref().and_then(|w| w.upgrade());
+        }
+    }
+
     pub fn len(&self) -> usize {
         self.length
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    // One Rc allocation per element: strong and weak counts, then the RefCell
+    pub fn heap_size(&self) -> usize {
+        self.length * (2 * core::mem::size_of::<usize>() + core::mem::size_of::<RefCell<Node<T>>>())
+    }
+
     pub fn clear(&mut self) {
         self.head = None;
         self.tail = None;
@@ -126,24 +314,48 @@ impl<T> DoubleLinkedList<T> {
         
         while let Some(node) = current {
             let next = node.borrow().next.clone();
-            
+
             // Swap next and prev pointers using tuple destructuring
             let old_prev = node.borrow().prev.as_ref().and_then(|w| w.upgrade());
             (node.borrow_mut().next, node.borrow_mut().prev) = (
                 old_prev,
-                new_head.as_ref().map(|n| Rc::downgrade(n))
+                next.as_ref().map(Rc::downgrade)
             );
             
             new_head = Some(node);
             current = next;
         }
         
-        // Swap head and tail using tuple destructuring
-        let old_tail = self.tail.as_ref().and_then(|w| w.upgrade());
-        (self.head, self.tail) = (
-            new_head,
-            old_tail.map(|t| Rc::downgrade(&t))
-        );
+        // The old head becomes the tail, the last node visited the head
+        let old_head = self.head.as_ref().map(Rc::downgrade);
+        (self.head, self.tail) = (new_head, old_head);
+    }
+
+    // Unlink a node from its neighbors (the node's own links are left as they were)
+    fn remove_node(&mut self, node: Rc<RefCell<Node<T>>>) {
+        let prev = node.borrow().prev.as_ref().and_then(|w| w.upgrade());
+        let next = node.borrow().next.clone();
+        
+        match (prev, next) {
+            (Some(p), Some(n)) => {
+                p.borrow_mut().next = Some(n.clone());
+                n.borrow_mut().prev = Some(Rc::downgrade(&p));
+            }
+            (None, Some(n)) => {
+                n.borrow_mut().prev = None;
+                self.head = Some(n);
+            }
+            (Some(p), None) => {
+                p.borrow_mut().next = None;
+                self.tail = Some(Rc::downgrade(&p));
+            }
+            (None, None) => {
+                self.head = None;
+                self.tail = None;
+            }
+        }
+        
+        self.length -= 1;
     }
 }
 
@@ -172,35 +384,10 @@ impl<T: PartialEq> DoubleLinkedList<T> {
         }
         false
     }
-    
-    fn remove_node(&mut self, node: Rc<RefCell<Node<T>>>) {
-        let prev = node.borrow().prev.as_ref().and_then(|w| w.upgrade());
-        let next = node.borrow().next.clone();
-        
-        match (prev, next) {
-            (Some(p), Some(n)) => {
-                p.borrow_mut().next = Some(n.clone());
-                n.borrow_mut().prev = Some(Rc::downgrade(&p));
-            }
-            (None, Some(n)) => {
-                n.borrow_mut().prev = None;
-                self.head = Some(n);
-            }
-            (Some(p), None) => {
-                p.borrow_mut().next = None;
-                self.tail = Some(Rc::downgrade(&p));
-            }
-            (None, None) => {
-                self.head = None;
-                self.tail = None;
-            }
-        }
-        
-        self.length -= 1;
-    }
 }
 
-fn main() {
+#[cfg(feature = "std")]
+pub fn demo() {
     let mut list = DoubleLinkedList::new();
     
     println!("=== Testing push and pop operations ===");
@@ -216,6 +403,9 @@ fn main() {
     list.push_end(5);
     println!("After push_end(4,5): length {}", list.len());
     
+    // Peek at the front without removing it
+    println!("Peek front: {:?}", list.peek().map(|v| *v));
+
     // Pop from front
     println!("Pop front: {:?}", list.pop());
     println!("Pop front: {:?}", list.pop());
@@ -234,6 +424,7 @@ fn main() {
     println!("After clear: length {}", list.len());
     
     // Test operations on empty list
+    println!("Is empty: {}", list.is_empty());
     println!("Pop on empty: {:?}", list.pop());
     println!("Pop_end on empty: {:?}", list.pop_end());
     
@@ -311,6 +502,32 @@ fn main() {
     }
     println!();
     
+    println!("\n=== Testing node refs ===");
+    let mut refs = DoubleLinkedList::new();
+    let a = refs.push_ref("a");
+    let b = refs.push_ref("b");
+    let c = refs.push_ref("c");
+    print!("Pushed a, b, c (front to back): ");
+    refs.for_each(|v| print!("{} ", v));
+    println!();
+    refs.move_to_front(&a);
+    print!("After move_to_front(a): ");
+    refs.for_each(|v| print!("{} ", v));
+    println!();
+    println!("remove_ref(b): {:?}", refs.remove_ref(&b));
+    println!("b still valid: {}, c still valid: {}", b.is_valid(), c.is_valid());
+    println!("remove_ref(b) again: {:?}", refs.remove_ref(&b));
+    println!("move_to_front(b) on stale ref: {}", refs.move_to_front(&b));
+    let mut other = DoubleLinkedList::new();
+    let foreign = other.push_ref("x");
+    println!("move_to_front with another list's ref: {}", refs.move_to_front(&foreign));
+    print!("Remaining: ");
+    refs.for_each(|v| print!("{} ", v));
+    println!("(length {})", refs.len());
+    println!("Pop back: {:?}", refs.pop_end());
+    refs.clear();
+    println!("a valid after clear: {}", a.is_valid());
+
     println!("\n=== Testing memory cleanup ===");
     
     // Create nodes and track reference counts
@@ -344,11 +561,19 @@ fn main() {
     drop(third_node);
     
     println!("\nAfter dropping local references:");
-    println!("List is empty: {}", list.len() == 0);
+    println!("List is empty: {}", list.is_empty());
     println!("Head is None: {}", list.head.is_none());
     println!("Tail is None: {}", list.tail.is_none());
     
     println!("\n=== Memory cleanup test completed ===");
     println!("If ref counts drop to 1 after clear, memory will be freed when local refs are dropped");
+
+    println!("\n=== Built with dlist![..] ===");
+    let list = crate::dlist![1, 2, 3, 4];
+    let mut forward = Vec::new();
+    list.for_each(|value| forward.push(*value));
+    let mut backward = Vec::new();
+    list.for_each_rev(|value| backward.push(*value));
+    println!("Forward: {:?}, backward: {:?}", forward, backward);
     println!("\n=== All tests completed ===");
 }
\ No newline at end of file
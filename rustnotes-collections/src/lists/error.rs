@@ -0,0 +1,37 @@
+// List Errors
+//
+// What the fallible list operations (the try_ methods) report instead of
+// panicking. The panicking versions stay for callers that treat a bad index as
+// a bug, and their panic message is this type's Display text.
+//
+// Design choices:
+// - One enum for every list, so code generic over several lists matches on
+//   one type
+// - Variants carry the values involved (index and length, or the handle),
+//   enough to build a message without the list at hand
+// - Trade-off: a failed try_insert drops the value it was given; callers that
+//   need it back check the index first
+
+use core::fmt;
+use crate::lists::arena::Index;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListError {
+    // An index at or past the end (for insertion, past len itself)
+    IndexOutOfBounds { index: usize, len: usize },
+    // A handle to a node that has been removed (ArenaList)
+    StaleHandle(Index),
+}
+
+impl fmt::Display for ListError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ListError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} out of bounds (len {})", index, len)
+            }
+            ListError::StaleHandle(handle) => write!(f, "stale handle {}: the node was removed", handle),
+        }
+    }
+}
+
+impl core::error::Error for ListError {}
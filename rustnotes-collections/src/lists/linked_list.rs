@@ -0,0 +1,323 @@
+// Singly-Linked List Implementation
+//
+// A generic singly-linked list with forward traversal only. Supports push/pop
+// from the front, indexed removal, contains checking, iteration, and basic
+// operations. Most operations work with any type T, while contains requires
+// T to implement PartialEq.
+//
+// Design choices:
+// - Uses Box<Node<T>> for next pointers (heap allocation, owned references)
+// - Forward-only traversal keeps structure simple and cache-friendly
+// - Indexed operations require O(n) traversal to find position
+// - No tail pointer - optimized for stack-like operations (push/pop front)
+// - Borrowing iterators (iter/iter_mut) walk the chain front to back
+// - Simple ownership model with automatic cleanup via Box dropping
+// - push, pop, remove and reverse have *_recorded variants that report each
+//   link change to a steps::Recorder, for visualizers
+// - Trade-off: Fast front operations, slower random access and back operations
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use crate::steps::{node_id, relink, Link, NodeId, Recorder, Step};
+
+#[derive(Debug)]
+struct Node<T> {
+    data: T,
+    next: Option<Box<Node<T>>>,
+}
+
+#[derive(Debug)]
+pub struct LinkedList<T> {
+    head: Option<Box<Node<T>>>,
+    size: usize,
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> LinkedList<T> {
+    // Create a new empty linked list
+    pub fn new() -> Self {
+        LinkedList {
+            head: None,
+            size: 0,
+        }
+    }
+
+    fn id(node: &Option<Box<Node<T>>>) -> Option<NodeId> {
+        node.as_deref().map(node_id)
+    }
+
+    // Push a value to the front of the list
+    pub fn push(&mut self, value: T) {
+        self.push_recorded(value, &mut ());
+    }
+
+    // push, reporting each step to the recorder
+    pub fn push_recorded<R: Recorder>(&mut self, value: T, recorder: &mut R) {
+        trace!("allocate node {} ({} bytes)", self.size + 1, core::mem::size_of::<Node<T>>());
+        let new_node = Box::new(Node {
+            data: value,
+            next: self.head.take(),
+        });
+        let id = node_id(&*new_node);
+        recorder.record(Step::Create(id));
+        relink(recorder, Link::Next(id), None, Self::id(&new_node.next));
+        self.head = Some(new_node);
+        recorder.record(Step::Link { link: Link::Head, to: Some(id) });
+        self.size += 1;
+    }
+
+    // Pop a value from the front of the list
+    pub fn pop(&mut self) -> Option<T> {
+        self.pop_recorded(&mut ())
+    }
+
+    // pop, reporting each step to the recorder
+    pub fn pop_recorded<R: Recorder>(&mut self, recorder: &mut R) -> Option<T> {
+        self.head.take().map(|node| {
+            let id = node_id(&*node);
+            self.head = node.next;
+            recorder.record(Step::Link { link: Link::Head, to: Self::id(&self.head) });
+            recorder.record(Step::Delete(id));
+            self.size -= 1;
+            node.data
+        })
+    }
+
+    // Look at the front value without removing it
+    pub fn peek(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.data)
+    }
+
+    // Mutable access to the front value
+    pub fn peek_mut(&mut self) -> Option<&mut T> {
+        self.head.as_mut().map(|node| &mut node.data)
+    }
+
+    // Remove the nth element (0-indexed) from the list
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        self.remove_recorded(index, &mut ())
+    }
+
+    // remove, reporting each step to the recorder
+    pub fn remove_recorded<R: Recorder>(&mut self, index: usize, recorder: &mut R) -> Option<T> {
+        if index >= self.size {
+            return None;
+        }
+
+        if index == 0 {
+            return self.pop_recorded(recorder);
+        }
+
+        let mut current = &mut self.head;
+        for _ in 0..index - 1 {
+            if let Some(node) = current {
+                recorder.record(Step::Visit(node_id(&**node)));
+                current = &mut node.next;
+            }
+        }
+
+        if let Some(node) = current {
+            let id = node_id(&**node);
+            recorder.record(Step::Visit(id));
+            if let Some(target) = node.next.take() {
+                let target_id = node_id(&*target);
+                node.next = target.next;
+                recorder.record(Step::Link { link: Link::Next(id), to: Self::id(&node.next) });
+                recorder.record(Step::Delete(target_id));
+                self.size -= 1;
+                return Some(target.data);
+            }
+        }
+        None
+    }
+
+    // Get the size of the list
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    // Check if the list is empty
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // Bytes allocated by the list itself: one boxed node per element (memory the
+    // elements own, such as a String's text, is not counted)
+    pub fn heap_size(&self) -> usize {
+        self.size * core::mem::size_of::<Node<T>>()
+    }
+    
+    // Empty the list
+	pub fn clear(&mut self) {
+    	self.head = None;
+	    self.size = 0;
+	}
+
+    // Reverse the list in-place
+    pub fn reverse(&mut self) {
+        self.reverse_recorded(&mut ());
+    }
+
+    // reverse, reporting each step to the recorder
+    pub fn reverse_recorded<R: Recorder>(&mut self, recorder: &mut R) {
+        let before = Self::id(&self.head);
+        let mut prev = None;
+        let mut current = self.head.take();
+        
+        while let Some(mut node) = current {
+            let next = node.next.take();
+            let old_next = Self::id(&next);
+            node.next = prev;
+            relink(recorder, Link::Next(node_id(&*node)), old_next, Self::id(&node.next));
+            prev = Some(node);
+            current = next;
+        }
+        
+        self.head = prev;
+        relink(recorder, Link::Head, before, Self::id(&self.head));
+    }
+
+    // Every node's id and value from front to back, for drawing the list
+    // between recorded operations
+    pub fn nodes(&self) -> Vec<(NodeId, &T)> {
+        let mut nodes = Vec::with_capacity(self.size);
+        let mut current = self.head.as_deref();
+        while let Some(node) = current {
+            nodes.push((node_id(node), &node.data));
+            current = node.next.as_deref();
+        }
+        nodes
+    }
+
+    // Iterate over references from front to back
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { next: self.head.as_deref() }
+    }
+
+    // Iterate over mutable references from front to back
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut { next: self.head.as_deref_mut() }
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+    // Check if the list contains a value
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|data| data == value)
+    }
+}
+
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.map(|node| {
+            self.next = node.next.as_deref();
+            &node.data
+        })
+    }
+}
+
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next.take().map(|node| {
+            self.next = node.next.as_deref_mut();
+            &mut node.data
+        })
+    }
+}
+
+// --- Example usage
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut list = LinkedList::new();
+    
+    // Push some values
+    list.push(1);
+    list.push(2);
+    list.push(3);
+    println!("List after pushing 1, 2, 3: {:?}", list);
+    
+    // Check contains
+    println!("Contains 2: {}", list.contains(&2));
+    println!("Contains 5: {}", list.contains(&5));
+    
+    // Peek at the front
+    println!("Peek: {:?}", list.peek());
+    if let Some(value) = list.peek_mut() {
+        *value += 10;
+    }
+    println!("Peek after peek_mut += 10: {:?}", list.peek());
+
+    // Pop a value
+    if let Some(value) = list.pop() {
+        println!("Popped: {}", value);
+    }
+    println!("List after pop: {:?}", list);
+    
+    // Remove by index (index 0)
+    match list.remove(0) {
+        Some(value) => println!("Removed at index 0: {}", value),
+        None => println!("Failed to remove at index 0"),
+    }
+    println!("List after remove: {:?}", list);
+    
+    // Add more elements for testing
+    list.push(4);
+    list.push(5);
+    list.push(6);
+    println!("List after pushing 4, 5, 6: {:?}", list);
+    
+    // Remove by non-zero index (middle element)
+    if let Some(value) = list.remove(1) {
+        println!("Removed at index 1: {}", value);
+    } else {
+        println!("Failed to remove at index 1");
+    }
+    println!("List after removing index 1: {:?}", list);
+    
+    // Try to remove out of bounds
+    match list.remove(10) {
+        Some(value) => println!("Removed at index 10: {}", value),
+        None => println!("Failed to remove at index 10 (out of bounds)"),
+    }
+    println!("List after attempting out-of-bounds remove: {:?}", list);
+    
+    println!("List length: {}", list.len());
+    
+    // Test reverse
+    println!("\n--- Testing reverse ---");
+    let mut reverse_list = crate::linked_list![4, 3, 2, 1];
+    println!("Before reverse: {:?}", reverse_list);
+    reverse_list.reverse();
+    println!("After reverse: {:?}", reverse_list);
+
+    // Test iteration
+    println!("\n--- Testing iter and iter_mut ---");
+    for value in reverse_list.iter_mut() {
+        *value *= 10;
+    }
+    let collected: Vec<&i32> = reverse_list.iter().collect();
+    println!("After multiplying by 10: {:?}", collected);
+    println!("Sum: {}", reverse_list.iter().sum::<i32>());
+    
+    // Clear the list 
+    list.clear();
+    println!("\nList after clear: {:?}", list);
+    println!("Is empty: {}", list.is_empty());
+}
\ No newline at end of file
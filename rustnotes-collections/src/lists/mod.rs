@@ -0,0 +1,21 @@
+// Lists, Stacks and Queues
+
+pub mod arena;
+pub mod arena_linked_list;
+pub mod double_linked_list;
+pub mod error;
+pub mod linked_list;
+pub mod persistent_vector;
+pub mod skip_list;
+pub mod small_vec;
+pub mod stack_and_queue;
+
+pub use self::arena::{Arena, Index};
+pub use self::arena_linked_list::{ArenaList, NodeHandle};
+pub use self::double_linked_list::DoubleLinkedList;
+pub use self::error::ListError;
+pub use self::linked_list::LinkedList;
+pub use self::persistent_vector::PersistentVector;
+pub use self::skip_list::SkipList;
+pub use self::small_vec::SmallVec;
+pub use self::stack_and_queue::{Queue, Stack};
@@ -0,0 +1,422 @@
+// Persistent Vector (Bit-Partitioned Trie)
+//
+// An immutable vector: push() and set() leave the original untouched and return
+// a new version, and both versions stay usable. Copying the whole vector for
+// every change would cost O(n); instead the elements live in a tree with 32-way
+// branching and a new version copies only the nodes on the path to the change,
+// sharing everything else with the old one (structural sharing). This is the
+// vector from Clojure and Scala.
+//
+// The index is the path: each 5-bit group of it picks a child, top bits first.
+//
+//   index 1000 = 00000 11111 01000        shift 10: three levels
+//                  |     |     +-- slot 8 in the leaf
+//                  |     +-------- child 31 of the middle node
+//                  +-------------- child 0 of the root
+//
+//   v1.set(1000, x):  new root -> new middle node -> new leaf; the other 31
+//                     children at each level are shared with v1
+//
+// With 32-way nodes the tree is at most 7 levels deep for 2^32 elements, so
+// get/set are O(log32 n), effectively constant.
+//
+// Design choices:
+// - Nodes are Rc-shared: a new version holds new Rcs to the old subtrees, and
+//   a subtree is freed when the last version using it is dropped
+// - Tail optimization: the last (up to 32) elements are kept in a separate
+//   tail buffer outside the tree, so push() usually copies one small buffer
+//   instead of a path, and only every 32nd push touches the tree
+// - pop() is the exact reverse, pulling the last leaf back out as the new tail
+//   and collapsing the root when it is left with a single child
+// - set() and push() need T: Clone, because a copied leaf holds its own copies
+//   of the 32 values (cheap for numbers, or use Rc<T> for large values)
+// - Iteration walks leaf by leaf, so a full scan is O(n), not O(n log n)
+// - Trade-off: every update allocates a few nodes of 32 slots, so for
+//   single-owner mutation a plain Vec is far faster; the payoff is cheap
+//   snapshots (undo history, readers that must not see later writes)
+
+#[cfg(feature = "std")]
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use crate::lists::error::ListError;
+
+const BITS: usize = 5;
+const WIDTH: usize = 1 << BITS;
+const MASK: usize = WIDTH - 1;
+
+enum Node<T> {
+    Branch(Vec<Rc<Node<T>>>),
+    Leaf(Vec<T>),
+}
+
+pub struct PersistentVector<T> {
+    len: usize,
+    shift: usize, // bits of the index consumed above the leaves
+    root: Rc<Node<T>>,
+    tail: Rc<Vec<T>>,
+}
+
+impl<T> Clone for PersistentVector<T> {
+    fn clone(&self) -> Self {
+        PersistentVector { len: self.len, shift: self.shift, root: Rc::clone(&self.root), tail: Rc::clone(&self.tail) }
+    }
+}
+
+impl<T: Clone> Default for PersistentVector<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// A chain of single-child branches from `level` down to `leaf`
+fn new_path<T>(level: usize, leaf: Rc<Node<T>>) -> Rc<Node<T>> {
+    if level == 0 {
+        leaf
+    } else {
+        Rc::new(Node::Branch(vec![new_path(level - BITS, leaf)]))
+    }
+}
+
+fn children<T>(node: &Node<T>) -> &Vec<Rc<Node<T>>> {
+    match node {
+        Node::Branch(children) => children,
+        Node::Leaf(_) => unreachable!("leaf above the bottom level"),
+    }
+}
+
+impl<T: Clone> PersistentVector<T> {
+    pub fn new() -> Self {
+        PersistentVector { len: 0, shift: BITS, root: Rc::new(Node::Branch(Vec::new())), tail: Rc::new(Vec::new()) }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Every node reachable from this version, counted in full even when other
+    // versions share it, so the sum over several versions overstates their total
+    pub fn heap_size(&self) -> usize {
+        let rc_counts = 2 * core::mem::size_of::<usize>();
+        let tail = rc_counts + core::mem::size_of::<Vec<T>>() + self.tail.capacity() * core::mem::size_of::<T>();
+        Self::node_heap_size(&self.root) + tail
+    }
+
+    fn node_heap_size(node: &Rc<Node<T>>) -> usize {
+        let own = 2 * core::mem::size_of::<usize>() + core::mem::size_of::<Node<T>>();
+        match &**node {
+            Node::Branch(children) => {
+                own + children.capacity() * core::mem::size_of::<Rc<Node<T>>>()
+                    + children.iter().map(Self::node_heap_size).sum::<usize>()
+            }
+            Node::Leaf(values) => own + values.capacity() * core::mem::size_of::<T>(),
+        }
+    }
+
+    // Index of the first element in the tail
+    fn tail_offset(&self) -> usize {
+        if self.len < WIDTH {
+            0
+        } else {
+            ((self.len - 1) >> BITS) << BITS
+        }
+    }
+
+    // The 32-element chunk (leaf or tail) holding `index`
+    fn chunk_for(&self, index: usize) -> &[T] {
+        if index >= self.tail_offset() {
+            return &self.tail;
+        }
+        let mut node = &self.root;
+        let mut level = self.shift;
+        loop {
+            match &**node {
+                Node::Branch(children) => {
+                    node = &children[(index >> level) & MASK];
+                    level -= BITS;
+                }
+                Node::Leaf(values) => return values,
+            }
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        if index >= self.len {
+            return None;
+        }
+        Some(&self.chunk_for(index)[index & MASK])
+    }
+
+    // A new version with element `index` replaced
+    pub fn set(&self, index: usize, value: T) -> Self {
+        self.try_set(index, value).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_set(&self, index: usize, value: T) -> Result<Self, ListError> {
+        if index >= self.len {
+            return Err(ListError::IndexOutOfBounds { index, len: self.len });
+        }
+        let mut result = self.clone();
+        if index >= self.tail_offset() {
+            let mut tail = (*self.tail).clone();
+            tail[index & MASK] = value;
+            result.tail = Rc::new(tail);
+        } else {
+            result.root = Self::set_in(&self.root, self.shift, index, value);
+        }
+        Ok(result)
+    }
+
+    fn set_in(node: &Rc<Node<T>>, level: usize, index: usize, value: T) -> Rc<Node<T>> {
+        match &**node {
+            Node::Branch(children) => {
+                let slot = (index >> level) & MASK;
+                let mut copy = children.clone(); // copies 32 Rcs, not subtrees
+                copy[slot] = Self::set_in(&children[slot], level - BITS, index, value);
+                Rc::new(Node::Branch(copy))
+            }
+            Node::Leaf(values) => {
+                let mut copy = values.clone();
+                copy[index & MASK] = value;
+                Rc::new(Node::Leaf(copy))
+            }
+        }
+    }
+
+    // A new version with `value` appended
+    pub fn push(&self, value: T) -> Self {
+        let mut result = self.clone();
+        result.len += 1;
+        if self.tail.len() < WIDTH {
+            let mut tail = Vec::with_capacity(self.tail.len() + 1);
+            tail.extend_from_slice(&self.tail);
+            tail.push(value);
+            result.tail = Rc::new(tail);
+            return result;
+        }
+        // The tail is full: it becomes a leaf of the tree, and `value` starts a new tail
+        let leaf = Rc::new(Node::Leaf((*self.tail).clone()));
+        if (self.len >> BITS) > (1 << self.shift) {
+            // The tree is full at this height: grow a new root above it
+            result.root = Rc::new(Node::Branch(vec![Rc::clone(&self.root), new_path(self.shift, leaf)]));
+            result.shift += BITS;
+        } else {
+            result.root = self.push_tail(self.shift, &self.root, leaf);
+        }
+        result.tail = Rc::new(vec![value]);
+        result
+    }
+
+    fn push_tail(&self, level: usize, parent: &Node<T>, leaf: Rc<Node<T>>) -> Rc<Node<T>> {
+        let slot = ((self.len - 1) >> level) & MASK;
+        let mut copy = children(parent).clone();
+        if level == BITS {
+            copy.push(leaf);
+        } else if let Some(child) = copy.get(slot).cloned() {
+            copy[slot] = self.push_tail(level - BITS, &child, leaf);
+        } else {
+            copy.push(new_path(level - BITS, leaf));
+        }
+        Rc::new(Node::Branch(copy))
+    }
+
+    // A new version without the last element, plus that element
+    pub fn pop(&self) -> Option<(Self, T)> {
+        let last = self.get(self.len.checked_sub(1)?)?.clone();
+        if self.len == 1 {
+            return Some((Self::new(), last));
+        }
+        let mut result = self.clone();
+        result.len -= 1;
+        if self.tail.len() > 1 {
+            result.tail = Rc::new(self.tail[..self.tail.len() - 1].to_vec());
+            return Some((result, last));
+        }
+        // The tail empties: the tree's last leaf becomes the new tail
+        result.tail = Rc::new(self.chunk_for(self.len - 2).to_vec());
+        let mut root = self.pop_tail(self.shift, &self.root).unwrap_or_else(|| Rc::new(Node::Branch(Vec::new())));
+        if result.shift > BITS && children(&root).len() == 1 {
+            root = Rc::clone(&children(&root)[0]);
+            result.shift -= BITS;
+        }
+        result.root = root;
+        Some((result, last))
+    }
+
+    // The node without its last leaf; None if nothing would remain
+    fn pop_tail(&self, level: usize, node: &Node<T>) -> Option<Rc<Node<T>>> {
+        let slot = ((self.len - 2) >> level) & MASK;
+        let mut copy = children(node)[..=slot].to_vec();
+        if level > BITS {
+            match self.pop_tail(level - BITS, &copy[slot]) {
+                Some(child) => copy[slot] = child,
+                None => {
+                    copy.pop();
+                }
+            }
+        } else {
+            copy.pop();
+        }
+        if copy.is_empty() {
+            None
+        } else {
+            Some(Rc::new(Node::Branch(copy)))
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { vector: self, index: 0, chunk: &[] }
+    }
+
+    // Distinct tree nodes reachable from this version, by address
+    #[cfg(feature = "std")]
+    fn collect_nodes(&self, seen: &mut BTreeSet<*const Node<T>>) {
+        fn walk<T>(node: &Rc<Node<T>>, seen: &mut BTreeSet<*const Node<T>>) {
+            if seen.insert(Rc::as_ptr(node)) {
+                if let Node::Branch(children) = &**node {
+                    children.iter().for_each(|child| walk(child, seen));
+                }
+            }
+        }
+        walk(&self.root, seen);
+    }
+}
+
+// Hands out one chunk at a time, looking up the next chunk only at its boundary
+pub struct Iter<'a, T> {
+    vector: &'a PersistentVector<T>,
+    index: usize,
+    chunk: &'a [T],
+}
+
+impl<'a, T: Clone> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index >= self.vector.len {
+            return None;
+        }
+        if self.index & MASK == 0 {
+            self.chunk = self.vector.chunk_for(self.index);
+        }
+        let value = &self.chunk[self.index & MASK];
+        self.index += 1;
+        Some(value)
+    }
+}
+
+impl<T: Clone> FromIterator<T> for PersistentVector<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        iter.into_iter().fold(PersistentVector::new(), |vector, value| vector.push(value))
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for PersistentVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Versions ===");
+    let empty = PersistentVector::new();
+    let v1 = empty.push("a").push("b").push("c");
+    let v2 = v1.set(1, "B");
+    let v3 = v2.push("d");
+    let (v4, popped) = v3.pop().unwrap();
+    println!("v1 = {:?}", v1);
+    println!("v2 = v1.set(1, \"B\")  = {:?}", v2);
+    println!("v3 = v2.push(\"d\")    = {:?}", v3);
+    println!("v4 = v3.pop()         = {:?} (popped {:?})", v4, popped);
+    println!("v1 is unchanged: {:?}, empty is still empty: {}", v1, empty.is_empty());
+
+    println!("\n=== Structural sharing ===");
+    let big: PersistentVector<u64> = (0..100_000).collect();
+    let edited = big.set(54_321, 0);
+    let mut big_nodes = BTreeSet::new();
+    big.collect_nodes(&mut big_nodes);
+    let mut both = big_nodes.clone();
+    edited.collect_nodes(&mut both);
+    println!("100000 elements, tree depth {}, {} tree nodes", big.shift / BITS + 1, big_nodes.len());
+    println!("set(54321) created {} new nodes; the rest are shared", both.len() - big_nodes.len());
+    println!("big[54321] = {:?}, edited[54321] = {:?}", big.get(54_321), edited.get(54_321));
+
+    println!("\n=== Random operations, every version checked against Vec ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut versions: Vec<(PersistentVector<u64>, Vec<u64>)> = vec![(PersistentVector::new(), Vec::new())];
+    for step in 0..3000u64 {
+        // Branch off a random earlier version, so old versions keep being used
+        let (base, model) = versions[next() as usize % versions.len()].clone();
+        let (vector, model) = match next() % 10 {
+            0..=5 => {
+                // Runs of pushes, to cross the 32, 1024 and 32768 boundaries
+                let (mut vector, mut model) = (base, model);
+                for _ in 0..(next() % 2000) {
+                    vector = vector.push(step);
+                    model.push(step);
+                }
+                (vector, model)
+            }
+            6 | 7 if !model.is_empty() => {
+                let index = next() as usize % model.len();
+                let mut model = model;
+                model[index] = step;
+                (base.set(index, step), model)
+            }
+            _ => {
+                let (mut vector, mut model) = (base, model);
+                for _ in 0..(next() % 1500) {
+                    match vector.pop() {
+                        Some((shorter, value)) => {
+                            assert_eq!(Some(value), model.pop());
+                            vector = shorter;
+                        }
+                        None => break,
+                    }
+                }
+                (vector, model)
+            }
+        };
+        versions.push((vector, model));
+    }
+    let all_match = versions.iter().all(|(vector, model)| vector.len() == model.len() && vector.iter().eq(model.iter()));
+    let longest = versions.iter().map(|(v, _)| v.len()).max().unwrap();
+    println!("{} versions (longest {}), all still equal to their Vec model: {}", versions.len(), longest, all_match);
+
+    println!("\n=== Snapshot cost vs cloning a Vec ===");
+    let base: Vec<u64> = (0..1_000_000).collect();
+    let start = std::time::Instant::now();
+    let mut snapshots = Vec::new();
+    let mut current = base.clone();
+    for i in 0..100 {
+        current[i * 997] = 0;
+        snapshots.push(current.clone());
+    }
+    let vec_time = start.elapsed();
+    let persistent: PersistentVector<u64> = base.iter().cloned().collect();
+    let start = std::time::Instant::now();
+    let mut versions = Vec::new();
+    let mut current = persistent;
+    for i in 0..100 {
+        current = current.set(i * 997, 0);
+        versions.push(current.clone());
+    }
+    let persistent_time = start.elapsed();
+    println!("100 edits of 1M elements, keeping every version: Vec clones {:?}, persistent {:?}",
+             vec_time, persistent_time);
+    println!("Last versions agree: {}", versions[99].iter().eq(snapshots[99].iter()));
+}
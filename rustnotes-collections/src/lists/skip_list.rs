@@ -0,0 +1,351 @@
+// Skip List Implementation
+//
+// A sorted linked list with "express lanes": every node sits on level 0, and each
+// node is promoted to the next level up with probability 1/2. A search starts on
+// the highest level, runs forward while the next value is still smaller, then
+// drops down a level - skipping over long stretches of the list. The expected
+// height is O(log n), so search, insert and remove take O(log n) expected time,
+// matching a balanced tree without any rotations or rebalancing.
+//
+// Design choices:
+// - Nodes live in a Vec arena and link to each other by index, so the structure
+//   is plain safe Rust (no Rc/RefCell or raw pointers)
+// - Removed slots go on a free list and are reused by later inserts
+// - Each node stores a Vec of forward links, one per level it appears on
+// - Node levels come from a small xorshift generator seeded per list, so runs
+//   are reproducible; the level is capped at a configurable max_level
+// - Values are unique, like a set: inserting a duplicate returns false
+// - range() accepts any RangeBounds (a..b, a..=b, a.., ..) and walks level 0 in order
+// - Trade-off: randomized rather than guaranteed balance, and roughly twice the
+//   links of a plain list, in exchange for much simpler code than AVL or red-black trees
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Bound, RangeBounds};
+
+const DEFAULT_MAX_LEVEL: usize = 16;
+
+struct Node<T> {
+    value: T,
+    forward: Vec<Option<usize>>, // forward[i] = next node on level i
+}
+
+pub struct SkipList<T: Ord> {
+    nodes: Vec<Option<Node<T>>>,
+    free: Vec<usize>,
+    head: Vec<Option<usize>>, // head[i] = first node on level i
+    level: usize,             // Number of levels currently in use
+    max_level: usize,
+    len: usize,
+    rng_state: u64,
+}
+
+impl<T: Ord> Default for SkipList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> SkipList<T> {
+    pub fn new() -> Self {
+        Self::with_max_level(DEFAULT_MAX_LEVEL)
+    }
+
+    // A max level of about log2(expected size) keeps searches logarithmic
+    pub fn with_max_level(max_level: usize) -> Self {
+        assert!(max_level >= 1, "skip list needs at least one level");
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            head: vec![None; max_level],
+            level: 0,
+            max_level,
+            len: 0,
+            rng_state: 0x2545_F491_4F6C_DD1D,
+        }
+    }
+
+    fn node(&self, index: usize) -> &Node<T> {
+        self.nodes[index].as_ref().expect("link to a freed node")
+    }
+
+    // Next node on `level` after `at` (None means starting from the head)
+    fn next(&self, at: Option<usize>, level: usize) -> Option<usize> {
+        match at {
+            None => self.head[level],
+            Some(index) => self.node(index).forward[level],
+        }
+    }
+
+    fn set_next(&mut self, at: Option<usize>, level: usize, target: Option<usize>) {
+        match at {
+            None => self.head[level] = target,
+            Some(index) => self.nodes[index].as_mut().unwrap().forward[level] = target,
+        }
+    }
+
+    // For each level, the last node whose value is less than `value` (None = head)
+    fn predecessors(&self, value: &T) -> Vec<Option<usize>> {
+        let mut preds = vec![None; self.max_level];
+        let mut current = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next(current, level) {
+                if self.node(next).value < *value {
+                    current = Some(next);
+                } else {
+                    break;
+                }
+            }
+            preds[level] = current;
+        }
+        preds
+    }
+
+    // Flip coins: each extra level has probability 1/2
+    fn random_level(&mut self) -> usize {
+        self.rng_state ^= self.rng_state << 13;
+        self.rng_state ^= self.rng_state >> 7;
+        self.rng_state ^= self.rng_state << 17;
+        (self.rng_state.trailing_ones() as usize + 1).min(self.max_level)
+    }
+
+    // Returns false if the value was already present
+    pub fn insert(&mut self, value: T) -> bool {
+        let preds = self.predecessors(&value);
+        if let Some(next) = self.next(preds[0], 0) {
+            if self.node(next).value == value {
+                return false;
+            }
+        }
+
+        let height = self.random_level();
+        let forward = (0..height).map(|level| self.next(preds[level], level)).collect();
+        let node = Node { value, forward };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = Some(node);
+                index
+            }
+            None => {
+                self.nodes.push(Some(node));
+                self.nodes.len() - 1
+            }
+        };
+
+        // Levels above the old top have the head as their predecessor (None)
+        for (level, &pred) in preds.iter().enumerate().take(height) {
+            self.set_next(pred, level, Some(index));
+        }
+        self.level = self.level.max(height);
+        self.len += 1;
+        true
+    }
+
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let preds = self.predecessors(value);
+        let index = self.next(preds[0], 0)?;
+        if self.node(index).value != *value {
+            return None;
+        }
+
+        let node = self.nodes[index].take().unwrap();
+        for (level, &next) in node.forward.iter().enumerate() {
+            self.set_next(preds[level], level, next);
+        }
+        self.free.push(index);
+        while self.level > 0 && self.head[self.level - 1].is_none() {
+            self.level -= 1;
+        }
+        self.len -= 1;
+        Some(node.value)
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let preds = self.predecessors(value);
+        self.next(preds[0], 0)
+            .is_some_and(|index| self.node(index).value == *value)
+    }
+
+    // Smallest value
+    pub fn first(&self) -> Option<&T> {
+        self.head[0].map(|index| &self.node(index).value)
+    }
+
+    // Values in ascending order within `range`
+    pub fn range<R: RangeBounds<T>>(&self, range: R) -> Range<'_, T, R> {
+        let start = match range.start_bound() {
+            Bound::Unbounded => self.head[0],
+            Bound::Included(low) => self.next(self.predecessors(low)[0], 0),
+            Bound::Excluded(low) => {
+                let first = self.next(self.predecessors(low)[0], 0);
+                match first {
+                    Some(index) if self.node(index).value == *low => self.node(index).forward[0],
+                    other => other,
+                }
+            }
+        };
+        Range { list: self, current: start, range }
+    }
+
+    pub fn iter(&self) -> Range<'_, T, core::ops::RangeFull> {
+        self.range(..)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The node buffer plus each live node's forward pointers, the head pointers
+    // and the free list
+    pub fn heap_size(&self) -> usize {
+        let forward: usize = self.nodes.iter().flatten().map(|node| node.forward.capacity()).sum();
+        self.nodes.capacity() * core::mem::size_of::<Option<Node<T>>>()
+            + (forward + self.head.capacity()) * core::mem::size_of::<Option<usize>>()
+            + self.free.capacity() * core::mem::size_of::<usize>()
+    }
+
+    pub fn max_level(&self) -> usize {
+        self.max_level
+    }
+
+    // Number of nodes on each level, bottom first
+    pub fn level_counts(&self) -> Vec<usize> {
+        (0..self.level)
+            .map(|level| {
+                let mut count = 0;
+                let mut current = self.head[level];
+                while let Some(index) = current {
+                    count += 1;
+                    current = self.node(index).forward[level];
+                }
+                count
+            })
+            .collect()
+    }
+
+    // Links followed to find `value` (for comparing against a plain list scan)
+    pub fn search_steps(&self, value: &T) -> usize {
+        let mut steps = 0;
+        let mut current = None;
+        for level in (0..self.level).rev() {
+            while let Some(next) = self.next(current, level) {
+                steps += 1;
+                if self.node(next).value < *value {
+                    current = Some(next);
+                } else {
+                    break;
+                }
+            }
+        }
+        steps
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.free.clear();
+        self.head = vec![None; self.max_level];
+        self.level = 0;
+        self.len = 0;
+    }
+}
+
+pub struct Range<'a, T: Ord, R> {
+    list: &'a SkipList<T>,
+    current: Option<usize>,
+    range: R,
+}
+
+impl<'a, T: Ord, R: RangeBounds<T>> Iterator for Range<'a, T, R> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let node = self.list.node(self.current?);
+        // The start bound was handled when the iterator was created, so failing
+        // the range check here means we have passed the end bound
+        if !self.range.contains(&node.value) {
+            self.current = None;
+            return None;
+        }
+        self.current = node.forward[0];
+        Some(&node.value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut list = SkipList::new();
+    for value in [30, 10, 50, 20, 40, 60, 10].iter() {
+        let inserted = list.insert(*value);
+        println!("insert({}) -> {}", value, inserted);
+    }
+    println!("Contents: {:?}", list.iter().collect::<Vec<_>>());
+    println!("Length: {}, first: {:?}", list.len(), list.first());
+    println!("contains(40): {}, contains(45): {}", list.contains(&40), list.contains(&45));
+    println!("remove(40): {:?}, remove(45): {:?}", list.remove(&40), list.remove(&45));
+    println!("After remove: {:?}", list.iter().collect::<Vec<_>>());
+
+    println!("\n=== Range iteration ===");
+    println!("20..50:  {:?}", list.range(20..50).collect::<Vec<_>>());
+    println!("20..=50: {:?}", list.range(20..=50).collect::<Vec<_>>());
+    println!("25..:    {:?}", list.range(25..).collect::<Vec<_>>());
+    println!("..=20:   {:?}", list.range(..=20).collect::<Vec<_>>());
+    let after_20: Vec<_> = list
+        .range((Bound::Excluded(20), Bound::Unbounded))
+        .collect();
+    println!("(20, ..): {:?}", after_20);
+
+    println!("\n=== Words stay sorted ===");
+    let mut words = SkipList::new();
+    for word in "the quick brown fox jumps over the lazy dog".split_whitespace() {
+        words.insert(word.to_string());
+    }
+    let joined: Vec<&str> = words.iter().map(|w| w.as_str()).collect();
+    println!("{}", joined.join(" "));
+
+    println!("\n=== Level structure for 10,000 values ===");
+    let mut big = SkipList::new();
+    for i in 0..10_000 {
+        big.insert((i * 7919) % 10_000); // Scrambled insertion order
+    }
+    let counts = big.level_counts();
+    println!("Levels in use: {} (max {})", counts.len(), big.max_level());
+    for (level, count) in counts.iter().enumerate().take(6) {
+        println!("  level {}: {:>5} nodes", level, count);
+    }
+    let sorted = big.iter().zip(0..).all(|(&value, expected)| value == expected);
+    println!("Iteration yields 0..10000 in order: {}", sorted);
+
+    println!("\n=== Search cost versus a linked list ===");
+    let probes = [0, 2_500, 5_000, 7_500, 9_999];
+    for &target in probes.iter() {
+        println!("Find {:>5}: {:>3} links (a plain list would walk {})",
+                 target, big.search_steps(&target), target + 1);
+    }
+
+    println!("\n=== Removal and slot reuse ===");
+    for i in (0..10_000).filter(|i| i % 2 == 1) {
+        big.remove(&i);
+    }
+    println!("After removing odd values: length {}", big.len());
+    for i in 10_000..12_000 {
+        big.insert(i);
+    }
+    println!("After inserting 2,000 more: length {}, arena slots {}", big.len(), big.nodes.len());
+    println!("range(9_990..10_004): {:?}", big.range(9_990..10_004).collect::<Vec<_>>());
+
+    println!("\n=== Small max level ===");
+    let mut flat = SkipList::with_max_level(1);
+    for i in (0..5).rev() {
+        flat.insert(i);
+    }
+    println!("max_level 1 is a sorted linked list: {:?}, levels {:?}",
+             flat.iter().collect::<Vec<_>>(), flat.level_counts());
+
+    list.clear();
+    println!("\nAfter clear: length {}, is empty: {}", list.len(), list.is_empty());
+}
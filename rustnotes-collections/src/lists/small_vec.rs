@@ -0,0 +1,488 @@
+// Small Vector with Inline Storage
+//
+// A vector that keeps up to N elements inside the struct itself, on the stack
+// or inline in whatever contains it, and only moves them to a heap-allocated
+// Vec once the (N+1)th element arrives ("spilling"):
+//
+//   SmallVec<char, 4>
+//   push a, b, c      Inline [a, b, c, _]        no allocation
+//   push d            Inline [a, b, c, d]        still none
+//   push e            Heap   Vec [a, b, c, d, e] one allocation, elements moved
+//
+// Most vectors in real programs are short - tokens of an expression, children
+// of a node, the neighbours of a graph vertex - so sizing N for the common case
+// removes an allocation per vector and keeps the data next to its owner.
+//
+// Design choices:
+// - Storage is an enum of an inline [Option<T>; N] plus a length, or a Vec<T>.
+//   Option slots keep the whole thing in safe Rust (crates like smallvec use
+//   MaybeUninit and unsafe to avoid the per-slot tag)
+// - Once spilled it stays on the heap, even if it shrinks, so a vector that
+//   hovers around N does not bounce between the two; shrink_to_fit() moves it
+//   back inline explicitly
+// - Indexing, iteration and equality behave like a Vec's; since the inline
+//   elements are not one contiguous [T] there is no as_slice()
+// - Trade-off: every operation checks which storage is active, the struct is
+//   as large as N slots even when empty, and Option<T> costs extra space per
+//   slot for types without a spare niche (e.g. u32, but not Box or &T). Those
+//   checks can cost more than a fast allocator saves (see the timing in main);
+//   the dependable wins are fewer allocations and data kept next to its owner
+
+#[cfg(feature = "std")]
+use alloc::format;
+#[cfg(feature = "std")]
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{Index, IndexMut};
+use crate::lists::error::ListError;
+
+enum Storage<T, const N: usize> {
+    Inline { items: [Option<T>; N], len: usize },
+    Heap(Vec<T>),
+}
+
+pub struct SmallVec<T, const N: usize> {
+    storage: Storage<T, N>,
+}
+
+impl<T, const N: usize> Default for SmallVec<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> SmallVec<T, N> {
+    pub fn new() -> Self {
+        SmallVec { storage: Storage::Inline { items: core::array::from_fn(|_| None), len: 0 } }
+    }
+
+    // Starts on the heap straight away if `capacity` exceeds N
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity > N {
+            SmallVec { storage: Storage::Heap(Vec::with_capacity(capacity)) }
+        } else {
+            Self::new()
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { len, .. } => *len,
+            Storage::Heap(vec) => vec.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Zero while the elements fit inline, the Vec's buffer once spilled
+    pub fn heap_size(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => 0,
+            Storage::Heap(vec) => vec.capacity() * core::mem::size_of::<T>(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        match &self.storage {
+            Storage::Inline { .. } => N,
+            Storage::Heap(vec) => vec.capacity(),
+        }
+    }
+
+    // True once the elements have moved to the heap
+    pub fn spilled(&self) -> bool {
+        matches!(self.storage, Storage::Heap(_))
+    }
+
+    pub fn push(&mut self, value: T) {
+        match &mut self.storage {
+            Storage::Inline { items, len } if *len < N => {
+                items[*len] = Some(value);
+                *len += 1;
+            }
+            Storage::Inline { .. } => {
+                self.spill(N + 1).push(value);
+            }
+            Storage::Heap(vec) => vec.push(value),
+        }
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        match &mut self.storage {
+            Storage::Inline { items, len } => {
+                if *len == 0 {
+                    return None;
+                }
+                *len -= 1;
+                items[*len].take()
+            }
+            Storage::Heap(vec) => vec.pop(),
+        }
+    }
+
+    pub fn get(&self, index: usize) -> Option<&T> {
+        match &self.storage {
+            Storage::Inline { items, len } if index < *len => items[index].as_ref(),
+            Storage::Inline { .. } => None,
+            Storage::Heap(vec) => vec.get(index),
+        }
+    }
+
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        match &mut self.storage {
+            Storage::Inline { items, len } if index < *len => items[index].as_mut(),
+            Storage::Inline { .. } => None,
+            Storage::Heap(vec) => vec.get_mut(index),
+        }
+    }
+
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    pub fn last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|i| self.get(i))
+    }
+
+    // Insert at `index`, shifting later elements right
+    pub fn insert(&mut self, index: usize, value: T) {
+        self.try_insert(index, value).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_insert(&mut self, index: usize, value: T) -> Result<(), ListError> {
+        if index > self.len() {
+            return Err(ListError::IndexOutOfBounds { index, len: self.len() });
+        }
+        match &mut self.storage {
+            Storage::Inline { items, len } if *len < N => {
+                items[index..=*len].rotate_right(1);
+                items[index] = Some(value);
+                *len += 1;
+            }
+            Storage::Inline { .. } => self.spill(N + 1).insert(index, value),
+            Storage::Heap(vec) => vec.insert(index, value),
+        }
+        Ok(())
+    }
+
+    // Remove the element at `index`, shifting later elements left
+    pub fn remove(&mut self, index: usize) -> T {
+        self.try_remove(index).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_remove(&mut self, index: usize) -> Result<T, ListError> {
+        if index >= self.len() {
+            return Err(ListError::IndexOutOfBounds { index, len: self.len() });
+        }
+        match &mut self.storage {
+            Storage::Inline { items, len } => {
+                let value = items[index].take();
+                items[index..*len].rotate_left(1);
+                *len -= 1;
+                Ok(value.unwrap())
+            }
+            Storage::Heap(vec) => Ok(vec.remove(index)),
+        }
+    }
+
+    pub fn truncate(&mut self, new_len: usize) {
+        while self.len() > new_len {
+            self.pop();
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.truncate(0);
+    }
+
+    // Move back inline if the elements fit, otherwise shrink the heap buffer
+    pub fn shrink_to_fit(&mut self) {
+        if let Storage::Heap(vec) = &mut self.storage {
+            if vec.len() > N {
+                vec.shrink_to_fit();
+                return;
+            }
+            let len = vec.len();
+            let mut drain = vec.drain(..);
+            let items = core::array::from_fn(|_| drain.next());
+            drop(drain);
+            self.storage = Storage::Inline { items, len };
+        }
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        match &self.storage {
+            Storage::Inline { items, len } => Iter::Inline(items[..*len].iter()),
+            Storage::Heap(vec) => Iter::Heap(vec.iter()),
+        }
+    }
+
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        match &mut self.storage {
+            Storage::Inline { items, len } => IterMut::Inline(items[..*len].iter_mut()),
+            Storage::Heap(vec) => IterMut::Heap(vec.iter_mut()),
+        }
+    }
+
+    pub fn into_vec(self) -> Vec<T> {
+        match self.storage {
+            Storage::Inline { items, len } => IntoIterator::into_iter(items).take(len).map(Option::unwrap).collect(),
+            Storage::Heap(vec) => vec,
+        }
+    }
+
+    // Move the inline elements into a Vec with room for `capacity`
+    fn spill(&mut self, capacity: usize) -> &mut Vec<T> {
+        if let Storage::Inline { items, len } = &mut self.storage {
+            let mut vec = Vec::with_capacity(capacity.max(2 * N));
+            vec.extend(items[..*len].iter_mut().map(|slot| slot.take().unwrap()));
+            self.storage = Storage::Heap(vec);
+        }
+        match &mut self.storage {
+            Storage::Heap(vec) => vec,
+            Storage::Inline { .. } => unreachable!(),
+        }
+    }
+}
+
+pub enum Iter<'a, T> {
+    Inline(core::slice::Iter<'a, Option<T>>),
+    Heap(core::slice::Iter<'a, T>),
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(slots) => slots.next().map(|slot| slot.as_ref().unwrap()),
+            Iter::Heap(items) => items.next(),
+        }
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        match self {
+            Iter::Inline(slots) => slots.next_back().map(|slot| slot.as_ref().unwrap()),
+            Iter::Heap(items) => items.next_back(),
+        }
+    }
+}
+
+pub enum IterMut<'a, T> {
+    Inline(core::slice::IterMut<'a, Option<T>>),
+    Heap(core::slice::IterMut<'a, T>),
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self {
+            IterMut::Inline(slots) => slots.next().map(|slot| slot.as_mut().unwrap()),
+            IterMut::Heap(items) => items.next(),
+        }
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a SmallVec<T, N> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T, const N: usize> IntoIterator for SmallVec<T, N> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.into_vec().into_iter()
+    }
+}
+
+impl<T, const N: usize> Index<usize> for SmallVec<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        let len = self.len();
+        self.get(index).unwrap_or_else(|| panic!("index {} out of bounds (len {})", index, len))
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for SmallVec<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        let len = self.len();
+        self.get_mut(index).unwrap_or_else(|| panic!("index {} out of bounds (len {})", index, len))
+    }
+}
+
+impl<T, const N: usize> Extend<T> for SmallVec<T, N> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for value in iter {
+            self.push(value);
+        }
+    }
+}
+
+impl<T, const N: usize> FromIterator<T> for SmallVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut small = SmallVec::new();
+        small.extend(iter);
+        small
+    }
+}
+
+impl<T: Clone, const N: usize> Clone for SmallVec<T, N> {
+    fn clone(&self) -> Self {
+        self.iter().cloned().collect()
+    }
+}
+
+impl<T: PartialEq, const N: usize> PartialEq for SmallVec<T, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.iter().eq(other.iter())
+    }
+}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for SmallVec<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "std")]
+fn describe<T: fmt::Debug, const N: usize>(small: &SmallVec<T, N>) -> String {
+    format!("{:?} len {} capacity {} {}", small, small.len(), small.capacity(),
+            if small.spilled() { "heap" } else { "inline" })
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== The spill transition ===");
+    let mut letters: SmallVec<char, 4> = SmallVec::new();
+    for c in "abcdef".chars() {
+        letters.push(c);
+        println!("push {:?}: {}", c, describe(&letters));
+    }
+    letters.truncate(2);
+    println!("truncate(2): {}", describe(&letters));
+    letters.shrink_to_fit();
+    println!("shrink_to_fit(): {}", describe(&letters));
+
+    let presized: SmallVec<u8, 4> = SmallVec::with_capacity(100);
+    println!("with_capacity(100) starts on the heap: {}", presized.spilled());
+
+    println!("\n=== Size and speed ===");
+    println!("size_of Vec<u32>:             {:>3} bytes (+ a heap block)", core::mem::size_of::<Vec<u32>>());
+    println!("size_of SmallVec<u32, 4>:     {:>3} bytes", core::mem::size_of::<SmallVec<u32, 4>>());
+    println!("Inline slot Option<u32>:      {:>3} bytes, Option<&u32>: {} bytes (no tag needed)",
+             core::mem::size_of::<Option<u32>>(), core::mem::size_of::<Option<&u32>>());
+    let start = std::time::Instant::now();
+    let mut total = 0u64;
+    for i in 0..1_000_000u64 {
+        let v: Vec<u64> = (0..3).map(|j| i + j).collect();
+        total += v.iter().sum::<u64>();
+    }
+    let vec_time = start.elapsed();
+    let start = std::time::Instant::now();
+    for i in 0..1_000_000u64 {
+        let v: SmallVec<u64, 4> = (0..3).map(|j| i + j).collect();
+        total += v.iter().sum::<u64>();
+    }
+    let small_time = start.elapsed();
+    println!("1M three-element vectors: Vec {:?}, SmallVec {:?} (checksum {})", vec_time, small_time, total);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{Rng, XorShift64Star};
+    use alloc::string::{String, ToString};
+
+    #[test]
+    fn spills_exactly_past_n() {
+        let mut letters: SmallVec<char, 4> = SmallVec::new();
+        for (count, c) in "abcdef".chars().enumerate() {
+            letters.push(c);
+            assert_eq!(letters.spilled(), count + 1 > 4);
+            assert!(letters.capacity() >= letters.len());
+        }
+        assert!(letters.iter().copied().eq("abcdef".chars()));
+        letters.truncate(2);
+        assert!(letters.spilled());
+        letters.shrink_to_fit();
+        assert!(!letters.spilled());
+        assert_eq!(letters.capacity(), 4);
+        assert!(letters.iter().copied().eq("ab".chars()));
+
+        let presized: SmallVec<u8, 4> = SmallVec::with_capacity(100);
+        assert!(presized.spilled());
+    }
+
+    // Every length around the boundary, with an insert at the front that
+    // spills a full inline vector and must keep the order
+    #[test]
+    fn spill_boundary_keeps_contents() {
+        for n in 0..=8usize {
+            let expected: Vec<String> = (0..n).map(|i| i.to_string()).collect();
+            let mut small: SmallVec<String, 4> = SmallVec::new();
+            small.extend(expected.iter().cloned());
+            assert_eq!(small.spilled(), n > 4, "n = {}", n);
+            assert!(small.iter().eq(expected.iter()));
+
+            small.insert(0, "x".to_string());
+            assert_eq!(small.spilled(), n >= 4, "n = {}", n);
+            assert_eq!((small[0].as_str(), small.len()), ("x", n + 1));
+            assert!(small.iter().skip(1).eq(expected.iter()));
+
+            assert_eq!(small.remove(0), "x");
+            small.shrink_to_fit();
+            assert_eq!(small.spilled(), n > 4, "n = {}", n);
+            assert_eq!(small.clone().into_vec(), expected);
+        }
+    }
+
+    #[test]
+    fn random_operations_match_vec() {
+        let mut rng = XorShift64Star::seed_from_u64(6);
+        for _ in 0..2000 {
+            let mut small: SmallVec<u64, 6> = SmallVec::new();
+            let mut model = Vec::new();
+            for _ in 0..rng.gen_range(0..20) {
+                let value = rng.gen_range(0..100);
+                match rng.gen_range(0..5) {
+                    0 | 1 => {
+                        small.push(value);
+                        model.push(value);
+                    }
+                    2 => assert_eq!(small.pop(), model.pop()),
+                    3 => {
+                        let at = rng.gen_index(0..model.len() + 1);
+                        small.insert(at, value);
+                        model.insert(at, value);
+                    }
+                    _ if model.is_empty() => assert_eq!(small.try_remove(0), Err(ListError::IndexOutOfBounds { index: 0, len: 0 })),
+                    _ => {
+                        let at = rng.gen_index(0..model.len());
+                        assert_eq!(small.remove(at), model.remove(at));
+                    }
+                }
+                assert_eq!(small.len(), model.len());
+                assert!(small.iter().eq(model.iter()));
+                assert_eq!(small.spilled(), small.capacity() > 6);
+            }
+            for value in small.iter_mut() {
+                *value += 1;
+            }
+            assert!(small.iter().rev().eq(model.iter().map(|v| v + 1).collect::<Vec<_>>().iter().rev()));
+        }
+    }
+}
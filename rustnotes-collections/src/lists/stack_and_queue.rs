@@ -0,0 +1,191 @@
+// Stack and Queue Adapters
+//
+// Thin wrappers that expose only LIFO (stack) or FIFO (queue) operations over the
+// list implementations in this collection. The underlying lists can do more, but
+// naming the discipline in the type makes the intent obvious at every call site
+// and rules out accidental misuse (e.g. removing from the middle of a queue).
+//
+// Design choices:
+// - Stack<T> wraps the singly-linked LinkedList: push and pop at the head are O(1)
+//   and need no tail pointer
+// - Queue<T> wraps DoubleLinkedList: enqueue appends at the tail, dequeue removes
+//   from the head, both O(1)
+// - peek() on the stack returns &T; front() on the queue returns a Ref guard,
+//   because the doubly linked list keeps its nodes in RefCells
+// - No extra state - each adapter is exactly as large as the list it wraps
+// - Trade-off: one node allocation per element (a Vec or VecDeque would be faster),
+//   in exchange for reusing and exercising the collection's own lists
+
+use crate::lists::double_linked_list::DoubleLinkedList;
+use crate::lists::linked_list::LinkedList;
+use core::cell::Ref;
+
+// Last-in, first-out
+pub struct Stack<T> {
+    list: LinkedList<T>,
+}
+
+impl<T> Default for Stack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Stack<T> {
+    pub fn new() -> Self {
+        Self { list: LinkedList::new() }
+    }
+
+    pub fn push(&mut self, value: T) {
+        self.list.push(value);
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    // The value the next pop() would return
+    pub fn peek(&self) -> Option<&T> {
+        self.list.peek()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.list.heap_size()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+// First-in, first-out
+pub struct Queue<T> {
+    list: DoubleLinkedList<T>,
+}
+
+impl<T> Default for Queue<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Queue<T> {
+    pub fn new() -> Self {
+        Self { list: DoubleLinkedList::new() }
+    }
+
+    pub fn enqueue(&mut self, value: T) {
+        self.list.push_end(value);
+    }
+
+    pub fn dequeue(&mut self) -> Option<T> {
+        self.list.pop()
+    }
+
+    // The value the next dequeue() would return
+    pub fn front(&self) -> Option<Ref<'_, T>> {
+        self.list.peek()
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.list.heap_size()
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+}
+
+// Classic stack exercise: every closing bracket must match the most recent opener
+#[cfg(feature = "std")]
+fn brackets_balanced(text: &str) -> bool {
+    let mut open = Stack::new();
+    for ch in text.chars() {
+        match ch {
+            '(' | '[' | '{' => open.push(ch),
+            ')' | ']' | '}' => {
+                let expected = match ch {
+                    ')' => '(',
+                    ']' => '[',
+                    _ => '{',
+                };
+                if open.pop() != Some(expected) {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    open.is_empty()
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Stack (LIFO) ===");
+    let mut stack = Stack::new();
+    for value in 1..=4 {
+        stack.push(value);
+    }
+    println!("Pushed 1..=4, length {}, peek {:?}", stack.len(), stack.peek());
+    print!("Popping: ");
+    while let Some(value) = stack.pop() {
+        print!("{} ", value);
+    }
+    println!();
+    println!("Empty: {}, peek: {:?}", stack.is_empty(), stack.peek());
+
+    for text in ["(a[b]{c})", "(]", "((", "{[()()]}"].iter() {
+        println!("Brackets balanced in {:<10} {}", text, brackets_balanced(text));
+    }
+
+    println!("\n=== Queue (FIFO) ===");
+    let mut queue = Queue::new();
+    for job in ["print", "email", "backup"].iter() {
+        queue.enqueue(job.to_string());
+    }
+    println!("Enqueued 3 jobs, length {}, front {:?}", queue.len(), queue.front().map(|j| j.clone()));
+    print!("Dequeuing: ");
+    while let Some(job) = queue.dequeue() {
+        print!("{} ", job);
+    }
+    println!();
+    println!("Empty: {}, front: {:?}", queue.is_empty(), queue.front().map(|j| j.clone()));
+
+    println!("\n=== Round-robin scheduling with a queue ===");
+    // Each task needs some units of work; the scheduler gives out 2 units per turn
+    let mut ready = Queue::new();
+    ready.enqueue(("A", 3));
+    ready.enqueue(("B", 5));
+    ready.enqueue(("C", 1));
+    let mut timeline = Vec::new();
+    while let Some((name, remaining)) = ready.dequeue() {
+        let slice = remaining.min(2);
+        timeline.push(format!("{}x{}", name, slice));
+        if remaining > slice {
+            ready.enqueue((name, remaining - slice));
+        }
+    }
+    println!("Timeline: {}", timeline.join(" "));
+
+    stack.push(1);
+    queue.enqueue("leftover".to_string());
+    stack.clear();
+    queue.clear();
+    println!("\nAfter clear: stack {}, queue {}", stack.len(), queue.len());
+}
@@ -0,0 +1,58 @@
+// Constructor Macros
+//
+// vec!-style literals for the structures that have no literal syntax of their
+// own, so an example or a test builds a populated structure in one line:
+//
+//   linked_list![1, 2, 3]    LinkedList, iterating 1, 2, 3
+//   dlist![1, 2, 3]          DoubleLinkedList, front to back 1, 2, 3
+//   avl![3, 1, 2]            AvlTree holding 1, 2, 3
+//
+// Design choices:
+// - Each macro expands to new() plus the structure's own insert method, so the
+//   result is exactly what the equivalent hand-written calls build; an AVL
+//   tree still rebalances on every insert
+// - Elements are listed in the order a reader sees them: linked_list! pushes
+//   them in reverse, since LinkedList::push adds at the front
+// - The paths start with $crate, so the macros work from other crates and
+//   under any import style; #[macro_export] puts them at the crate root
+//   (rustnotes_collections::avl!)
+// - The elements go through a temporary array, which makes them one type
+//   with one error message if they are not, and lets linked_list! reverse them
+// - A trailing comma is accepted, and an empty call builds an empty structure
+//   whose element type comes from the surrounding code
+// - Trade-off: every element is moved twice, once into the array and once
+//   into its node; a FromIterator impl would avoid that in generic code, but
+//   does not read as a literal
+
+#[macro_export]
+macro_rules! linked_list {
+    ($($value:expr),* $(,)?) => {{
+        let mut list = $crate::lists::linked_list::LinkedList::new();
+        for value in [$($value),*].into_iter().rev() {
+            list.push(value);
+        }
+        list
+    }};
+}
+
+#[macro_export]
+macro_rules! dlist {
+    ($($value:expr),* $(,)?) => {{
+        let mut list = $crate::lists::double_linked_list::DoubleLinkedList::new();
+        for value in [$($value),*] {
+            list.push_end(value);
+        }
+        list
+    }};
+}
+
+#[macro_export]
+macro_rules! avl {
+    ($($value:expr),* $(,)?) => {{
+        let mut tree = $crate::trees::balanced_binary_tree::AvlTree::new();
+        for value in [$($value),*] {
+            tree.insert(value);
+        }
+        tree
+    }};
+}
@@ -0,0 +1,284 @@
+// Pseudo-Random Number Generators
+//
+// Deterministic generators that turn a seed into a long stream of numbers that
+// look random: fine for simulations, shuffles, randomized data structures and
+// test data, and reproducible because the same seed always gives the same
+// stream. They are not cryptographically secure - the internal state can be
+// recovered from a few outputs.
+//
+//   xorshift64*:  state ^= state >> 12; state ^= state << 25; state ^= state >> 27
+//                 output = state * 0x2545F4914F6CDD1D
+//   PCG32:        state = state * MULTIPLIER + increment        (64-bit LCG)
+//                 output = rotate(xorshift(state) >> 27, state >> 59)  (32 bits)
+//
+// Design choices:
+// - The Rng trait needs only next_u64(); everything else (next_u32, next_f64,
+//   gen_range, gen_bool, shuffle, choose) is a provided method, so a new
+//   generator gets them all by implementing one function
+// - seed_from_u64() runs the seed through SplitMix64 first, so nearby seeds
+//   (0, 1, 2, ...) give unrelated streams and a zero seed is harmless
+// - xorshift64*: one u64 of state, very fast; the multiply hides the weak low
+//   bits of plain xorshift
+// - PCG32: an LCG whose output is permuted (xorshift then a data-dependent
+//   rotation); 32 bits per step, with selectable independent streams
+// - gen_range uses Lemire's multiply-and-reject method, so every value in the
+//   range is exactly equally likely; "next_u64() % n" is slightly biased
+//   toward small values whenever n does not divide 2^64
+// - shuffle is Fisher-Yates: each of the n! orders is equally likely
+// - Trade-off: fast and dependency-free but predictable; anything security
+//   related (keys, tokens, nonces) needs the operating system's generator
+
+use core::ops::Range;
+
+pub trait Rng {
+    fn next_u64(&mut self) -> u64;
+
+    // Generator whose whole state is derived from `seed`
+    fn seed_from_u64(seed: u64) -> Self
+    where
+        Self: Sized;
+
+    // Upper half of a 64-bit output (the better bits for LCG-style generators)
+    fn next_u32(&mut self) -> u32 {
+        (self.next_u64() >> 32) as u32
+    }
+
+    // Uniform in [0, 1), using the top 53 bits
+    fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    // Uniform in range.start..range.end without modulo bias; panics on an empty range
+    fn gen_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "gen_range needs a non-empty range");
+        let span = range.end - range.start;
+        // Multiply a random 64-bit value by span: the high word is the result.
+        // Low words below 2^64 % span belong to over-represented results, so
+        // those draws are rejected
+        let threshold = span.wrapping_neg() % span;
+        loop {
+            let product = self.next_u64() as u128 * span as u128;
+            if (product as u64) >= threshold {
+                return range.start + (product >> 64) as u64;
+            }
+        }
+    }
+
+    // Like gen_range for usize ranges, such as slice indices
+    fn gen_index(&mut self, range: Range<usize>) -> usize {
+        self.gen_range(range.start as u64..range.end as u64) as usize
+    }
+
+    // true with probability p
+    fn gen_bool(&mut self, p: f64) -> bool {
+        self.next_f64() < p
+    }
+
+    // Fisher-Yates: swap each position with a random one at or before it
+    fn shuffle<T>(&mut self, items: &mut [T])
+    where
+        Self: Sized,
+    {
+        for i in (1..items.len()).rev() {
+            let j = self.gen_index(0..i + 1);
+            items.swap(i, j);
+        }
+    }
+
+    fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T>
+    where
+        Self: Sized,
+    {
+        if items.is_empty() {
+            None
+        } else {
+            Some(&items[self.gen_index(0..items.len())])
+        }
+    }
+}
+
+// SplitMix64 step, used to expand seeds
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[derive(Debug, Clone)]
+pub struct XorShift64Star {
+    state: u64,
+}
+
+impl XorShift64Star {
+    // Use `state` directly; it must not be zero (zero maps to itself forever)
+    pub fn from_state(state: u64) -> Self {
+        assert!(state != 0, "xorshift state must be non-zero");
+        XorShift64Star { state }
+    }
+}
+
+impl Rng for XorShift64Star {
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    fn seed_from_u64(seed: u64) -> Self {
+        let mut mix = seed;
+        // SplitMix64 output is zero for exactly one input; step past it
+        let state = split_mix(&mut mix);
+        XorShift64Star::from_state(if state == 0 { split_mix(&mut mix) } else { state })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Pcg32 {
+    state: u64,
+    increment: u64,
+}
+
+impl Pcg32 {
+    const MULTIPLIER: u64 = 6_364_136_223_846_793_005;
+
+    // The reference pcg32_srandom: `stream` picks one of 2^63 independent sequences
+    pub fn new(seed: u64, stream: u64) -> Self {
+        let mut pcg = Pcg32 { state: 0, increment: (stream << 1) | 1 };
+        pcg.step();
+        pcg.state = pcg.state.wrapping_add(seed);
+        pcg.step();
+        pcg
+    }
+
+    fn step(&mut self) {
+        self.state = self.state.wrapping_mul(Self::MULTIPLIER).wrapping_add(self.increment);
+    }
+
+    // One 32-bit output (XSH RR: xorshift high bits, then random rotation)
+    pub fn next_u32_raw(&mut self) -> u32 {
+        let old = self.state;
+        self.step();
+        let shifted = (((old >> 18) ^ old) >> 27) as u32;
+        shifted.rotate_right((old >> 59) as u32)
+    }
+}
+
+impl Rng for Pcg32 {
+    fn next_u64(&mut self) -> u64 {
+        (self.next_u32_raw() as u64) << 32 | self.next_u32_raw() as u64
+    }
+
+    fn next_u32(&mut self) -> u32 {
+        self.next_u32_raw()
+    }
+
+    fn seed_from_u64(seed: u64) -> Self {
+        let mut mix = seed;
+        Pcg32::new(split_mix(&mut mix), split_mix(&mut mix))
+    }
+}
+
+// Pearson's chi-squared statistic for observed counts against a uniform expectation
+#[cfg(feature = "std")]
+fn chi_squared(counts: &[u64]) -> f64 {
+    let total: u64 = counts.iter().sum();
+    let expected = total as f64 / counts.len() as f64;
+    counts.iter().map(|&c| (c as f64 - expected).powi(2) / expected).sum()
+}
+
+#[cfg(feature = "std")]
+fn uniformity<R: Rng>(name: &str, rng: &mut R) {
+    let mut buckets = [0u64; 10];
+    for _ in 0..100_000 {
+        buckets[rng.gen_index(0..10)] += 1;
+    }
+    let mut bits = [0u64; 64];
+    for _ in 0..10_000 {
+        let value = rng.next_u64();
+        for (bit, count) in bits.iter_mut().enumerate() {
+            *count += value >> bit & 1;
+        }
+    }
+    let (low, high) = (bits.iter().min().unwrap(), bits.iter().max().unwrap());
+    let mean = (0..100_000).map(|_| rng.next_f64()).sum::<f64>() / 100_000.0;
+    // 9 degrees of freedom: chi-squared above 21.7 happens by chance 1% of the time
+    println!("{:<12} gen_range(0..10) chi2 = {:>5.2} (< 21.7 expected), each bit set {}..{} of 10000, mean f64 {:.4}",
+             name, chi_squared(&buckets), low, high, mean);
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Reference output ===");
+    let mut pcg = Pcg32::new(42, 54);
+    let first: Vec<String> = (0..6).map(|_| format!("0x{:08x}", pcg.next_u32())).collect();
+    println!("PCG32 seed 42 stream 54: {}", first.join(" "));
+    let expected = ["0xa15c02b7", "0x7b47f409", "0xba1d3330", "0x83d2f293", "0xbfa4784b", "0xcbed606e"];
+    println!("Matches the pcg32 reference demo: {}", first == expected);
+    let mut xorshift = XorShift64Star::from_state(1);
+    let first: Vec<String> = (0..3).map(|_| format!("0x{:016x}", xorshift.next_u64())).collect();
+    println!("xorshift64* from state 1: {}", first.join(" "));
+
+    println!("\n=== Seeding ===");
+    let run = |seed| {
+        let mut rng = XorShift64Star::seed_from_u64(seed);
+        (0..4).map(|_| rng.gen_range(0..100)).collect::<Vec<_>>()
+    };
+    println!("seed 0: {:?}, seed 1: {:?}, seed 1 again: {:?}", run(0), run(1), run(1));
+    let (mut a, mut b) = (Pcg32::new(7, 1), Pcg32::new(7, 2));
+    println!("PCG32 seed 7, streams 1 and 2: {:08x} {:08x}", a.next_u32(), b.next_u32());
+
+    println!("\n=== Uniformity ===");
+    uniformity("xorshift64*", &mut XorShift64Star::seed_from_u64(2024));
+    uniformity("PCG32", &mut Pcg32::seed_from_u64(2024));
+
+    println!("\n=== Modulo bias vs gen_range ===");
+    // With a range of 3/4 of 2^64, "% range" makes the lower third of values
+    // twice as likely; gen_range stays flat
+    let range = u64::MAX / 4 * 3;
+    let mut rng = Pcg32::seed_from_u64(5);
+    let (mut modulo, mut lemire) = ([0u64; 3], [0u64; 3]);
+    for _ in 0..60_000 {
+        modulo[(rng.next_u64() % range / (range / 3 + 1)) as usize] += 1;
+        lemire[(rng.gen_range(0..range) / (range / 3 + 1)) as usize] += 1;
+    }
+    println!("Thirds of the range with %:         {:?}", modulo);
+    println!("Thirds of the range with gen_range: {:?}", lemire);
+
+    println!("\n=== Shuffle ===");
+    let mut rng = XorShift64Star::seed_from_u64(11);
+    let mut deck: Vec<u32> = (1..=10).collect();
+    rng.shuffle(&mut deck);
+    println!("Shuffled 1..=10: {:?}", deck);
+    // All 6 orders of [0, 1, 2] should come up equally often
+    let mut orders = [0u64; 6];
+    for _ in 0..60_000 {
+        let mut items = [0, 1, 2];
+        rng.shuffle(&mut items);
+        let index = match items {
+            [0, 1, 2] => 0,
+            [0, 2, 1] => 1,
+            [1, 0, 2] => 2,
+            [1, 2, 0] => 3,
+            [2, 0, 1] => 4,
+            _ => 5,
+        };
+        orders[index] += 1;
+    }
+    println!("Counts of the 6 orders of [0, 1, 2]: {:?}, chi2 = {:.2} (< 15.1 expected)", orders, chi_squared(&orders));
+    let colors = ["red", "green", "blue"];
+    println!("choose: {:?}, gen_bool(0.25) rate: {:.3}", rng.choose(&colors),
+             (0..100_000).filter(|_| rng.gen_bool(0.25)).count() as f64 / 100_000.0);
+
+    println!("\n=== Speed ===");
+    let mut xorshift = XorShift64Star::seed_from_u64(1);
+    let mut pcg = Pcg32::seed_from_u64(1);
+    let start = std::time::Instant::now();
+    let sum = (0..5_000_000).fold(0u64, |acc, _| acc.wrapping_add(xorshift.next_u64()));
+    let xorshift_time = start.elapsed();
+    let start = std::time::Instant::now();
+    let sum2 = (0..5_000_000).fold(0u64, |acc, _| acc.wrapping_add(pcg.next_u32() as u64));
+    println!("5M outputs: xorshift64* {:?}, PCG32 {:?} (checksums {:x} {:x})", xorshift_time, start.elapsed(), sum, sum2);
+}
@@ -0,0 +1,148 @@
+// Serde Support
+//
+// Serialize and Deserialize for the collections, compiled only with the
+// "serde" feature. Every structure is written as a plain sequence of its
+// values, so any serde format (JSON, bincode, ...) stores it compactly and
+// other programs can read it as a list:
+//
+//   LinkedList [3, 1, 2]   -> [3,1,2]      (front to back)
+//   AvlTree {5, 1, 9}      -> [1,5,9]      (in order)
+//
+// Design choices:
+// - The layout matches encoding::serialization in rustnotes-algorithms: lists
+//   front to back, trees as their sorted contents rather than their shape
+// - Loading rebuilds the structure instead of trusting the input's shape:
+//   BinaryTree inserts medians first, so a saved chain comes back balanced, and
+//   the self-balancing trees insert the values one by one
+// - A tree sequence holding the same value twice is rejected with a custom
+//   error instead of silently shrinking
+// - Sorted sets go through one macro over the SortedSet trait, so a new set
+//   structure needs one line here to gain serde support
+// - Trade-off: an inverted BinaryTree is saved in descending order and comes
+//   back as a plain (uninverted) tree with the same values
+
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+use crate::lists::double_linked_list::DoubleLinkedList;
+use crate::lists::linked_list::LinkedList;
+use crate::lists::skip_list::SkipList;
+use crate::traits::SortedSet;
+use crate::trees::arena_binary_tree::ArenaTree;
+use crate::trees::balanced_binary_tree::AvlTree;
+use crate::trees::binary_tree::BinaryTree;
+use crate::trees::scapegoat_tree::ScapegoatTree;
+use serde::de::Error as _;
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+impl<T: Serialize> Serialize for LinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for LinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // push() adds at the front, so push the values in reverse
+        let values = Vec::<T>::deserialize(deserializer)?;
+        let mut list = LinkedList::new();
+        for value in values.into_iter().rev() {
+            list.push(value);
+        }
+        Ok(list)
+    }
+}
+
+impl<T: Serialize> Serialize for DoubleLinkedList<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // for_each() cannot stop early, so remember the first error and skip the rest
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        let mut result = Ok(());
+        self.for_each(|value| {
+            if result.is_ok() {
+                result = seq.serialize_element(value);
+            }
+        });
+        result?;
+        seq.end()
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for DoubleLinkedList<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut list = DoubleLinkedList::new();
+        for value in Vec::<T>::deserialize(deserializer)? {
+            list.push_end(value);
+        }
+        Ok(list)
+    }
+}
+
+impl<T: Serialize + Ord + Clone> Serialize for BinaryTree<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+// Insert the median of each range before its halves, giving a balanced tree
+fn insert_medians<T: Ord + Clone>(tree: &mut BinaryTree<T>, values: &[T]) {
+    if values.is_empty() {
+        return;
+    }
+    let mid = values.len() / 2;
+    tree.insert(values[mid].clone());
+    insert_medians(tree, &values[..mid]);
+    insert_medians(tree, &values[mid + 1..]);
+}
+
+impl<'de, T: Deserialize<'de> + Ord + Clone> Deserialize<'de> for BinaryTree<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut values = Vec::<T>::deserialize(deserializer)?;
+        // A mirrored tree iterates in descending order; the copy is a plain BST
+        values.sort();
+        let mut tree = BinaryTree::new();
+        insert_medians(&mut tree, &values);
+        if tree.len() != values.len() {
+            return Err(D::Error::custom("duplicate value in a tree sequence"));
+        }
+        Ok(tree)
+    }
+}
+
+// Serialize and Deserialize for SortedSet implementors that can start empty
+macro_rules! sorted_set_serde {
+    ($( [$($generics:tt)*] $ty:ty ),* $(,)?) => {
+        $(
+            impl<$($generics)*> Serialize for $ty
+            where
+                T: Serialize,
+            {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_seq(SortedSet::iter(self))
+                }
+            }
+
+            impl<'de, $($generics)*> Deserialize<'de> for $ty
+            where
+                T: Deserialize<'de>,
+            {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let mut set = <$ty>::default();
+                    for value in Vec::<T>::deserialize(deserializer)? {
+                        if !SortedSet::insert(&mut set, value) {
+                            return Err(D::Error::custom("duplicate value in a tree sequence"));
+                        }
+                    }
+                    Ok(set)
+                }
+            }
+        )*
+    };
+}
+
+sorted_set_serde! {
+    [T: Ord + Clone + Display + Debug] AvlTree<T>,
+    [T: Ord] ScapegoatTree<T>,
+    [T: Ord] SkipList<T>,
+    [T: Ord] ArenaTree<T>,
+}
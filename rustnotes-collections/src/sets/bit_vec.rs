@@ -0,0 +1,333 @@
+// Bit Vector (Bitset)
+//
+// A fixed-length sequence of bits packed 64 to a machine word. Compared with
+// Vec<bool>, which spends a whole byte per flag, a BitVec is 8x smaller, and
+// set operations (AND, OR, XOR) process 64 bits per instruction.
+//
+// Design choices:
+// - Bits live in a Vec<u64>; bit i is bit (i % 64) of word i / 64
+// - Unused high bits of the last word are always kept zero, so count_ones() and
+//   equality can work on whole words without masking
+// - get/set/flip panic on an out-of-range index, like slice indexing
+// - &a & &b, |, ^ and ! build new vectors; &=, |=, ^= update in place; both
+//   operands must have the same length
+// - iter_ones() jumps straight to set bits using trailing_zeros(), skipping
+//   empty words entirely
+// - Trade-off: no per-bit references (&mut bool) can be handed out, and growing
+//   is only possible at the end (push)
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+const WORD_BITS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct BitVec {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl BitVec {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // `len` bits, all cleared
+    pub fn zeros(len: usize) -> Self {
+        Self { words: vec![0; len.div_ceil(WORD_BITS)], len }
+    }
+
+    // `len` bits, all set
+    pub fn ones(len: usize) -> Self {
+        let mut bits = Self { words: vec![!0; len.div_ceil(WORD_BITS)], len };
+        bits.clear_unused_bits();
+        bits
+    }
+
+    // Restore the invariant that bits past `len` are zero
+    fn clear_unused_bits(&mut self) {
+        let used = self.len % WORD_BITS;
+        if used != 0 {
+            if let Some(last) = self.words.last_mut() {
+                *last &= (1u64 << used) - 1;
+            }
+        }
+    }
+
+    fn check_index(&self, index: usize) {
+        assert!(index < self.len, "bit index {} out of range for length {}", index, self.len);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // The word buffer, spare capacity included (storage_bytes counts used words)
+    pub fn heap_size(&self) -> usize {
+        self.words.capacity() * core::mem::size_of::<u64>()
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        self.check_index(index);
+        self.words[index / WORD_BITS] >> (index % WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        self.check_index(index);
+        let mask = 1u64 << (index % WORD_BITS);
+        if value {
+            self.words[index / WORD_BITS] |= mask;
+        } else {
+            self.words[index / WORD_BITS] &= !mask;
+        }
+    }
+
+    // Toggle a bit and return its new value
+    pub fn flip(&mut self, index: usize) -> bool {
+        self.check_index(index);
+        self.words[index / WORD_BITS] ^= 1u64 << (index % WORD_BITS);
+        self.get(index)
+    }
+
+    pub fn push(&mut self, value: bool) {
+        if self.len.is_multiple_of(WORD_BITS) {
+            self.words.push(0);
+        }
+        self.len += 1;
+        self.set(self.len - 1, value);
+    }
+
+    // Set or clear every bit
+    pub fn fill(&mut self, value: bool) {
+        for word in self.words.iter_mut() {
+            *word = if value { !0 } else { 0 };
+        }
+        self.clear_unused_bits();
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn count_zeros(&self) -> usize {
+        self.len - self.count_ones()
+    }
+
+    pub fn any(&self) -> bool {
+        self.words.iter().any(|&w| w != 0)
+    }
+
+    // Indices of set bits in increasing order
+    pub fn iter_ones(&self) -> IterOnes<'_> {
+        IterOnes {
+            words: &self.words,
+            word_index: 0,
+            current: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    // Every bit in order, as bools
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(move |i| self.get(i))
+    }
+
+    // Bytes used by the bit storage
+    pub fn storage_bytes(&self) -> usize {
+        self.words.len() * core::mem::size_of::<u64>()
+    }
+
+    fn check_same_len(&self, other: &BitVec) {
+        assert_eq!(self.len, other.len, "bit vectors must have the same length");
+    }
+
+    fn zip_words<F: Fn(u64, u64) -> u64>(&self, other: &BitVec, op: F) -> BitVec {
+        self.check_same_len(other);
+        let words = self.words.iter().zip(&other.words).map(|(&a, &b)| op(a, b)).collect();
+        BitVec { words, len: self.len }
+    }
+}
+
+pub struct IterOnes<'a> {
+    words: &'a [u64],
+    word_index: usize,
+    current: u64, // Bits of words[word_index] not yet returned
+}
+
+impl Iterator for IterOnes<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word_index += 1;
+            self.current = *self.words.get(self.word_index)?;
+        }
+        let bit = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1; // Clear the lowest set bit
+        Some(self.word_index * WORD_BITS + bit)
+    }
+}
+
+impl FromIterator<bool> for BitVec {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        let mut bits = BitVec::new();
+        for value in iter {
+            bits.push(value);
+        }
+        bits
+    }
+}
+
+impl BitAnd for &BitVec {
+    type Output = BitVec;
+
+    fn bitand(self, other: &BitVec) -> BitVec {
+        self.zip_words(other, |a, b| a & b)
+    }
+}
+
+impl BitOr for &BitVec {
+    type Output = BitVec;
+
+    fn bitor(self, other: &BitVec) -> BitVec {
+        self.zip_words(other, |a, b| a | b)
+    }
+}
+
+impl BitXor for &BitVec {
+    type Output = BitVec;
+
+    fn bitxor(self, other: &BitVec) -> BitVec {
+        self.zip_words(other, |a, b| a ^ b)
+    }
+}
+
+impl Not for &BitVec {
+    type Output = BitVec;
+
+    fn not(self) -> BitVec {
+        let mut result = BitVec { words: self.words.iter().map(|w| !w).collect(), len: self.len };
+        result.clear_unused_bits();
+        result
+    }
+}
+
+impl BitAndAssign<&BitVec> for BitVec {
+    fn bitand_assign(&mut self, other: &BitVec) {
+        self.check_same_len(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+}
+
+impl BitOrAssign<&BitVec> for BitVec {
+    fn bitor_assign(&mut self, other: &BitVec) {
+        self.check_same_len(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+}
+
+impl BitXorAssign<&BitVec> for BitVec {
+    fn bitxor_assign(&mut self, other: &BitVec) {
+        self.check_same_len(other);
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a ^= b;
+        }
+    }
+}
+
+// Bits as 0s and 1s, index 0 first
+impl fmt::Display for BitVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for bit in self.iter() {
+            write!(f, "{}", if bit { '1' } else { '0' })?;
+        }
+        Ok(())
+    }
+}
+
+// Sieve of Eratosthenes: bit n is set when n is prime
+#[cfg(feature = "std")]
+fn sieve(limit: usize) -> BitVec {
+    let mut is_prime = BitVec::ones(limit + 1);
+    for n in 0..(limit + 1).min(2) {
+        is_prime.set(n, false);
+    }
+    let mut p = 2;
+    while p * p <= limit {
+        if is_prime.get(p) {
+            // Smaller multiples were already crossed off by smaller primes
+            for multiple in (p * p..=limit).step_by(p) {
+                is_prime.set(multiple, false);
+            }
+        }
+        p += 1;
+    }
+    is_prime
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Basic operations ===");
+    let mut bits = BitVec::zeros(10);
+    bits.set(1, true);
+    bits.set(4, true);
+    bits.set(9, true);
+    println!("After setting 1, 4, 9:  {}", bits);
+    println!("get(4) = {}, get(5) = {}", bits.get(4), bits.get(5));
+    println!("flip(4) -> {}, flip(5) -> {}", bits.flip(4), bits.flip(5));
+    println!("After flips:           {}", bits);
+    println!("Ones: {}, zeros: {}, set indices: {:?}",
+             bits.count_ones(), bits.count_zeros(), bits.iter_ones().collect::<Vec<_>>());
+    bits.push(true);
+    println!("After push(true):      {} (length {})", bits, bits.len());
+
+    println!("\n=== Set operations ===");
+    let evens: BitVec = (0..16).map(|i| i % 2 == 0).collect();
+    let threes: BitVec = (0..16).map(|i| i % 3 == 0).collect();
+    println!("multiples of 2:  {}", evens);
+    println!("multiples of 3:  {}", threes);
+    println!("AND (of 6):      {}", &evens & &threes);
+    println!("OR:              {}", &evens | &threes);
+    println!("XOR:             {}", &evens ^ &threes);
+    println!("NOT evens:       {}", !&evens);
+    let mut odd_threes = threes.clone();
+    odd_threes &= &!&evens;
+    println!("3s &= !evens:    {} ({} set)", odd_threes, odd_threes.count_ones());
+    odd_threes ^= &threes;
+    println!("^= 3s -> 6s:     {}", odd_threes);
+
+    println!("\n=== Unused bits stay clear ===");
+    let all = BitVec::ones(70);
+    println!("ones(70): count_ones = {}, NOT has {} ones, any = {}",
+             all.count_ones(), (!&all).count_ones(), (!&all).any());
+
+    println!("\n=== Sieve of Eratosthenes ===");
+    let small = sieve(50);
+    println!("Primes up to 50: {:?}", small.iter_ones().collect::<Vec<_>>());
+    let limit = 10_000_000;
+    let primes = sieve(limit);
+    println!("Primes below {}: {}", limit, primes.count_ones());
+    println!("Largest: {:?}", primes.iter_ones().last());
+    println!("Storage: {} KiB as BitVec vs {} KiB as Vec<bool>",
+             primes.storage_bytes() / 1024, (limit + 1) / 1024);
+
+    println!("\n=== Twin primes via shifted AND ===");
+    // p and p + 2 both prime: AND the sieve with itself shifted down by two
+    let n = 1000;
+    let below = sieve(n + 2);
+    let shifted: BitVec = (0..=n + 2).map(|i| i + 2 <= n + 2 && below.get(i + 2)).collect();
+    let twins = &below & &shifted;
+    let pairs: Vec<(usize, usize)> = twins.iter_ones().take(8).map(|p| (p, p + 2)).collect();
+    println!("First twin prime pairs: {:?}", pairs);
+    println!("Twin pairs below {}: {}", n, twins.count_ones());
+}
@@ -0,0 +1,190 @@
+// Bloom Filter
+//
+// A set that answers "have I seen this?" in a fixed, small amount of memory by
+// allowing one kind of mistake: it may say yes for an item never inserted (a
+// false positive), but never no for one that was. Inserting sets k bits chosen
+// by hashing the item; a lookup checks those same k bits.
+//
+//   insert("cat"): bits 3, 9, 14     insert("dog"): bits 1, 9, 20
+//   [0 1 0 1 0 0 0 0 0 1 0 0 0 0 1 0 0 0 0 0 1 0 0 0]
+//   "cow" -> bits 1, 3, 14: all set, so "probably present" (a false positive)
+//   "emu" -> bits 3, 7, 20: bit 7 clear, so "definitely absent"
+//
+// With m bits, n items and k hash functions the false positive rate is about
+// (1 - e^(-kn/m))^k, smallest when k = (m/n) ln 2. A 1% rate needs about 9.6
+// bits per item whatever the items are - far less than storing them.
+//
+// Design choices:
+// - Bits are a BitVec from sets::bit_vec
+// - new(expected_items, false_positive_rate) sizes m and k from the formulas
+//   above; with_size() takes them directly
+// - The hasher is pluggable through BuildHasher, like the hash maps, so the
+//   CRC-32, FNV-1a and SipHash builders from hashing::hash_functions can be swapped in
+//   (std's RandomState by default); the demo plugs the same builders into
+//   HashMapOa and HashMapChained as well
+// - The k bit positions come from two hashes (the item hashed with two
+//   different prefixes) combined as h1 + i * h2 (Kirsch-Mitzenmacher double
+//   hashing), which performs like k independent hashes at the cost of two
+// - estimated_false_positive_rate() uses the fraction of bits actually set
+// - Trade-off: items cannot be removed (clearing a bit could erase other items)
+//   or listed; a counting Bloom filter trades 4+ bits per slot for removal
+
+use crate::hashing::hash_functions::{BuildCrc32, BuildFnv1a, SipBuildHasher};
+use crate::hashing::hash_map_chaining::HashMapChained;
+use crate::hashing::hash_map_open_addressing::HashMapOa;
+use crate::sets::bit_vec::BitVec;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hash};
+use std::time::Instant;
+
+pub struct BloomFilter<S = RandomState> {
+    bits: BitVec,
+    hashes: u32,
+    inserted: usize,
+    hash_builder: S,
+}
+
+impl BloomFilter<RandomState> {
+    // Sized for `expected_items` at the given false positive rate
+    pub fn new(expected_items: usize, false_positive_rate: f64) -> Self {
+        Self::with_hasher(expected_items, false_positive_rate, RandomState::new())
+    }
+}
+
+impl<S: BuildHasher> BloomFilter<S> {
+    pub fn with_hasher(expected_items: usize, false_positive_rate: f64, hash_builder: S) -> Self {
+        assert!(false_positive_rate > 0.0 && false_positive_rate < 1.0, "false positive rate must be in (0, 1)");
+        let n = expected_items.max(1) as f64;
+        let ln2 = std::f64::consts::LN_2;
+        let bits = (-n * false_positive_rate.ln() / (ln2 * ln2)).ceil() as usize;
+        let hashes = ((bits as f64 / n) * ln2).round().max(1.0) as u32;
+        Self::with_size(bits, hashes, hash_builder)
+    }
+
+    // Exactly `bits` bits and `hashes` bit positions per item
+    pub fn with_size(bits: usize, hashes: u32, hash_builder: S) -> Self {
+        assert!(bits > 0 && hashes > 0, "a Bloom filter needs at least one bit and one hash");
+        BloomFilter { bits: BitVec::zeros(bits), hashes, inserted: 0, hash_builder }
+    }
+
+    // The k bit positions for an item
+    fn positions<T: Hash + ?Sized>(&self, item: &T) -> impl Iterator<Item = usize> {
+        let h1 = self.hash_builder.hash_one((1u8, item));
+        // Odd, so successive positions never repeat while k < m
+        let h2 = self.hash_builder.hash_one((2u8, item)) | 1;
+        let m = self.bits.len() as u64;
+        (0..self.hashes as u64).map(move |i| (h1.wrapping_add(i.wrapping_mul(h2)) % m) as usize)
+    }
+
+    pub fn insert<T: Hash + ?Sized>(&mut self, item: &T) {
+        for position in self.positions(item).collect::<Vec<_>>() {
+            self.bits.set(position, true);
+        }
+        self.inserted += 1;
+    }
+
+    // false means definitely never inserted; true means probably inserted
+    pub fn contains<T: Hash + ?Sized>(&self, item: &T) -> bool {
+        self.positions(item).all(|position| self.bits.get(position))
+    }
+
+    // Number of insert() calls (duplicates included)
+    pub fn len(&self) -> usize {
+        self.inserted
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.inserted == 0
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.bits.heap_size()
+    }
+
+    pub fn bit_count(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn hash_count(&self) -> u32 {
+        self.hashes
+    }
+
+    // Chance that a lookup of a new item finds all its bits set
+    pub fn estimated_false_positive_rate(&self) -> f64 {
+        let fill = self.bits.count_ones() as f64 / self.bits.len() as f64;
+        fill.powi(self.hashes as i32)
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.fill(false);
+        self.inserted = 0;
+    }
+}
+
+// Insert `count` keys, then count false positives among `count` keys never inserted
+fn measure<S: BuildHasher>(name: &str, mut filter: BloomFilter<S>, count: usize) {
+    let start = Instant::now();
+    for i in 0..count {
+        filter.insert(&format!("user-{}", i));
+    }
+    let missed = (0..count).filter(|i| !filter.contains(&format!("user-{}", i))).count();
+    let false_positives = (count..2 * count).filter(|i| filter.contains(&format!("user-{}", i))).count();
+    println!("{:<26} false negatives: {}, false positives: {:.3}% (estimated {:.3}%), {:?}",
+             name, missed, 100.0 * false_positives as f64 / count as f64,
+             100.0 * filter.estimated_false_positive_rate(), start.elapsed());
+}
+
+pub fn demo() {
+    println!("=== Basics ===");
+    let mut filter = BloomFilter::new(1000, 0.01);
+    println!("Sized for 1000 items at 1%: {} bits ({:.1} per item, {} bytes), {} hashes",
+             filter.bit_count(), filter.bit_count() as f64 / 1000.0, filter.bit_count().div_ceil(8), filter.hash_count());
+    for word in ["apple", "banana", "cherry"].iter() {
+        filter.insert(word);
+    }
+    for word in ["apple", "cherry", "durian", "elderberry"].iter() {
+        println!("  contains({:?}) = {}", word, filter.contains(word));
+    }
+
+    println!("\n=== False positive rate vs size (100000 items, RandomState) ===");
+    for &rate in [0.1, 0.01, 0.001].iter() {
+        let filter = BloomFilter::new(100_000, rate);
+        let label = format!("{}%: m = {}, k = {}", rate * 100.0, filter.bit_count(), filter.hash_count());
+        measure(&label, filter, 100_000);
+    }
+
+    println!("\n=== Pluggable hashers (100000 items, 1% target) ===");
+    measure("RandomState", BloomFilter::new(100_000, 0.01), 100_000);
+    measure("CRC-32", BloomFilter::with_hasher(100_000, 0.01, BuildCrc32::default()), 100_000);
+    measure("FNV-1a", BloomFilter::with_hasher(100_000, 0.01, BuildFnv1a::default()), 100_000);
+    measure("SipHash-2-4", BloomFilter::with_hasher(100_000, 0.01, SipBuildHasher::from_seed(42)), 100_000);
+
+    println!("\n=== The same hashers in the hash maps ===");
+    let keys: Vec<String> = (0..50_000).map(|i| format!("user-{}", i)).collect();
+    fn fill_open<S: BuildHasher>(name: &str, mut map: HashMapOa<String, usize, S>, keys: &[String]) {
+        let start = Instant::now();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(key.clone(), i);
+        }
+        let found = keys.iter().enumerate().all(|(i, key)| map.get(key) == Some(&i));
+        println!("HashMapOa      {:<12} average probe {:.2}, all found: {}, {:?}",
+                 name, map.average_probe_length(), found, start.elapsed());
+    }
+    fn fill_chained<S: BuildHasher>(name: &str, mut map: HashMapChained<String, usize, S>, keys: &[String]) {
+        let start = Instant::now();
+        for (i, key) in keys.iter().enumerate() {
+            map.insert(key.clone(), i);
+        }
+        let found = keys.iter().enumerate().all(|(i, key)| map.get(key) == Some(&i));
+        println!("HashMapChained {:<12} longest chain {}, all found: {}, {:?}",
+                 name, map.longest_chain(), found, start.elapsed());
+    }
+    fill_open("RandomState", HashMapOa::new(), &keys);
+    fill_open("CRC-32", HashMapOa::with_hasher(BuildCrc32::default()), &keys);
+    fill_open("FNV-1a", HashMapOa::with_hasher(BuildFnv1a::default()), &keys);
+    fill_open("SipHash-2-4", HashMapOa::with_hasher(SipBuildHasher::from_seed(42)), &keys);
+    fill_chained("RandomState", HashMapChained::new(), &keys);
+    fill_chained("CRC-32", HashMapChained::with_hasher(BuildCrc32::default()), &keys);
+    fill_chained("FNV-1a", HashMapChained::with_hasher(BuildFnv1a::default()), &keys);
+    fill_chained("SipHash-2-4", HashMapChained::with_hasher(SipBuildHasher::from_seed(42)), &keys);
+}
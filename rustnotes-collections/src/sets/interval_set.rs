@@ -0,0 +1,286 @@
+// Interval Set
+//
+// Stores a set of points as a sorted list of disjoint half-open ranges
+// [start, end). Adding a range merges it with every range it overlaps or
+// touches; removing a range trims the ranges it covers and splits one that it
+// cuts through the middle. Large runs of consecutive values cost one entry no
+// matter how long they are.
+//
+//   insert 1..4, 6..9:   [1, 4) [6, 9)
+//   insert 3..7:         [1, 9)            (3..7 bridges both)
+//   remove 4..5:         [1, 4) [5, 9)     (split)
+//   gaps within 0..12:   [0, 1) [4, 5) [9, 12)
+//
+// Typical uses: free lists of address ranges, booked time slots, received byte
+// ranges of a download, sets of line numbers.
+//
+// Design choices:
+// - Ranges live in a std BTreeMap from start to end, so the range containing or
+//   preceding a point is one ordered lookup (range(..=point).next_back())
+// - Half-open ranges, like Rust's start..end: adjacent ranges such as 1..4 and
+//   4..6 touch without overlapping and are merged into 1..6
+// - Empty ranges (start >= end) are ignored by insert and remove
+// - Values only need Ord + Copy, so integers, chars or timestamps all work
+// - insert and remove are O(k log n) where k is the number of ranges merged or
+//   removed; contains is O(log n)
+// - Trade-off: ranges carry no values (it is a set, not a range map), and
+//   covered_len() needs integer-like values that can be subtracted
+
+use alloc::collections::btree_map;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::{FromIterator, Sum};
+use core::ops::{Range, Sub};
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct IntervalSet<T> {
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Ord + Copy> Default for IntervalSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Copy> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet { ranges: BTreeMap::new() }
+    }
+
+    // Number of disjoint ranges
+    pub fn range_count(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // An estimate: one (start, end) entry per range; the BTreeMap's node headers
+    // and spare slots are private to std and not counted
+    pub fn heap_size(&self) -> usize {
+        self.ranges.len() * core::mem::size_of::<(T, T)>()
+    }
+
+    // Add every point of `range`, merging with overlapping or adjacent ranges
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        // A range starting at or before `start` that reaches it absorbs the new one
+        if let Some((&s, &e)) = self.ranges.range(..=start).next_back() {
+            if e >= start {
+                start = s;
+                end = end.max(e);
+            }
+        }
+        // Every range starting inside [start, end] is swallowed
+        let swallowed: Vec<(T, T)> = self.ranges.range(start..=end).map(|(&s, &e)| (s, e)).collect();
+        for (s, e) in swallowed {
+            self.ranges.remove(&s);
+            end = end.max(e);
+        }
+        self.ranges.insert(start, end);
+    }
+
+    // Remove every point of `range`, trimming or splitting the ranges it touches
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.start >= range.end {
+            return;
+        }
+        // A range starting before the cut may stick out on either side of it
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.ranges.insert(s, range.start);
+                if e > range.end {
+                    self.ranges.insert(range.end, e);
+                }
+            }
+        }
+        // Ranges starting inside the cut lose their front; the last may keep a tail
+        let inside: Vec<(T, T)> = self.ranges.range(range.start..range.end).map(|(&s, &e)| (s, e)).collect();
+        for (s, e) in inside {
+            self.ranges.remove(&s);
+            if e > range.end {
+                self.ranges.insert(range.end, e);
+            }
+        }
+    }
+
+    pub fn contains(&self, point: T) -> bool {
+        self.ranges.range(..=point).next_back().is_some_and(|(_, &end)| point < end)
+    }
+
+    // Every point of `range` is in the set
+    pub fn contains_range(&self, range: Range<T>) -> bool {
+        range.start >= range.end
+            || self.ranges.range(..=range.start).next_back().is_some_and(|(_, &end)| range.end <= end)
+    }
+
+    // Some point of `range` is in the set
+    pub fn overlaps(&self, range: Range<T>) -> bool {
+        range.start < range.end
+            && self.ranges.range(..range.end).next_back().is_some_and(|(_, &end)| end > range.start)
+    }
+
+    // The stored ranges in increasing order
+    pub fn iter(&self) -> impl Iterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    // The uncovered parts of `within`, in increasing order
+    pub fn gaps(&self, within: Range<T>) -> Gaps<'_, T> {
+        // Start from the range that may cover within.start, if any
+        let first = self.ranges.range(..=within.start).next_back().map_or(within.start, |(&s, _)| s);
+        Gaps { ranges: self.ranges.range(first..), cursor: within.start, end: within.end }
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+}
+
+impl<T: Ord + Copy + Sub<Output = T> + Sum> IntervalSet<T> {
+    // Total number of points covered (for integer-like T)
+    pub fn covered_len(&self) -> T {
+        self.ranges.iter().map(|(&start, &end)| end - start).sum()
+    }
+}
+
+pub struct Gaps<'a, T> {
+    ranges: btree_map::Range<'a, T, T>,
+    cursor: T,
+    end: T,
+}
+
+impl<'a, T: Ord + Copy> Iterator for Gaps<'a, T> {
+    type Item = Range<T>;
+
+    fn next(&mut self) -> Option<Range<T>> {
+        while self.cursor < self.end {
+            match self.ranges.next() {
+                Some((&start, &end)) => {
+                    if start > self.cursor {
+                        let gap = self.cursor..start.min(self.end);
+                        self.cursor = self.cursor.max(end);
+                        return Some(gap);
+                    }
+                    self.cursor = self.cursor.max(end);
+                }
+                None => {
+                    let gap = self.cursor..self.end;
+                    self.cursor = self.end;
+                    return Some(gap);
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<T: Ord + Copy> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.ranges.iter().map(|(start, end)| start..end)).finish()
+    }
+}
+
+// Ranges of set bits in a Vec<bool>, to compare against
+#[cfg(feature = "std")]
+fn runs(bits: &[bool]) -> Vec<Range<u32>> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < bits.len() {
+        if bits[i] {
+            let start = i;
+            while i < bits.len() && bits[i] {
+                i += 1;
+            }
+            runs.push(start as u32..i as u32);
+        } else {
+            i += 1;
+        }
+    }
+    runs
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Merging and splitting ===");
+    let mut set = IntervalSet::new();
+    set.insert(1..4);
+    set.insert(6..9);
+    println!("insert 1..4, 6..9:  {:?}", set);
+    set.insert(3..7);
+    println!("insert 3..7:        {:?}", set);
+    set.remove(4..5);
+    println!("remove 4..5:        {:?}", set);
+    set.insert(9..12);
+    println!("insert 9..12:       {:?} (adjacent, merged)", set);
+    println!("gaps within 0..14:  {:?}", set.gaps(0..14).collect::<Vec<_>>());
+    println!("contains 3: {}, 4: {}, 11: {}, 12: {}", set.contains(3), set.contains(4), set.contains(11), set.contains(12));
+    println!("contains_range 5..12: {}, 3..6: {}; overlaps 12..20: {}, 0..2: {}",
+             set.contains_range(5..12), set.contains_range(3..6), set.overlaps(12..20), set.overlaps(0..2));
+    println!("{} ranges covering {} points", set.range_count(), set.covered_len());
+
+    println!("\n=== Free meeting slots (minutes after 9:00) ===");
+    let busy: IntervalSet<u32> = vec![0..30, 45..90, 60..120, 180..240, 240..255, 300..480].into_iter().collect();
+    let clock = |minutes: u32| format!("{:02}:{:02}", 9 + minutes / 60, minutes % 60);
+    println!("Busy: {}", busy.iter().map(|r| format!("{}-{}", clock(r.start), clock(r.end))).collect::<Vec<_>>().join(", "));
+    let free: Vec<String> = busy
+        .gaps(0..480)
+        .filter(|gap| gap.end - gap.start >= 30)
+        .map(|gap| format!("{}-{}", clock(gap.start), clock(gap.end)))
+        .collect();
+    println!("Free for at least 30 minutes: {}", free.join(", "));
+
+    println!("\n=== Randomized check against a Vec<bool> ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = |limit: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state % limit) as u32
+    };
+    let size = 200;
+    let mut set = IntervalSet::new();
+    let mut bits = vec![false; size as usize];
+    let mut ok = true;
+    for _ in 0..20_000 {
+        let start = next(size as u64);
+        let end = (start + next(40)).min(size);
+        let adding = next(3) != 0;
+        if adding {
+            set.insert(start..end);
+        } else {
+            set.remove(start..end);
+        }
+        for bit in &mut bits[start as usize..end as usize] {
+            *bit = adding;
+        }
+        let point = next(size as u64);
+        let (a, b) = (next(size as u64), next(size as u64));
+        let query = a.min(b)..a.max(b);
+        let gaps: Vec<bool> = (0..size).map(|p| set.gaps(0..size).any(|gap| gap.contains(&p))).collect();
+        ok &= set.iter().collect::<Vec<_>>() == runs(&bits)
+            && set.contains(point) == bits[point as usize]
+            && set.contains_range(query.clone()) == bits[query.start as usize..query.end as usize].iter().all(|&b| b)
+            && set.overlaps(query.clone()) == bits[query.start as usize..query.end as usize].iter().any(|&b| b)
+            && gaps.iter().zip(bits.iter()).all(|(&gap, &bit)| gap != bit)
+            && set.covered_len() == bits.iter().filter(|&&b| b).count() as u32;
+    }
+    println!("20,000 random inserts and removes on 0..{}: ranges, contains, contains_range,", size);
+    println!("overlaps, gaps and covered_len all match the Vec<bool>: {} ({} ranges at the end)", ok, set.range_count());
+}
@@ -0,0 +1,11 @@
+// Sets
+
+pub mod bit_vec;
+#[cfg(feature = "std")]
+pub mod bloom_filter;
+pub mod interval_set;
+
+pub use self::bit_vec::BitVec;
+#[cfg(feature = "std")]
+pub use self::bloom_filter::BloomFilter;
+pub use self::interval_set::IntervalSet;
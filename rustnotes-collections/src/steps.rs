@@ -0,0 +1,107 @@
+// Step-by-Step Mutations
+//
+// The structures normally just perform an operation; a visualizer also needs
+// to know what the operation did. The *_recorded variants of the mutating
+// methods (AvlTree::insert_recorded, LinkedList::push_recorded, ...) report
+// every change to the node graph to a Recorder as it happens:
+//
+//   let mut steps = Vec::new();
+//   tree.insert_recorded(4, &mut steps);
+//   // [Visit(a), Visit(b), Create(c), Link { link: Right(b), to: Some(c) },
+//   //  RotateLeft(a), ...]
+//
+// and nodes() on the same structures lists the current nodes with their ids
+// and links, so a page can draw the structure after each operation and
+// animate the steps in between (see rustnotes/src/wasm.rs).
+//
+// Design choices:
+// - A node's id is its heap address. A node never moves while it is in the
+//   structure, so the id is stable, unique among live nodes, and costs nothing
+//   to store; an id may be reused after a Delete, which always comes first
+// - Steps carry ids, not values: they need no Clone or Debug bound on T, and
+//   duplicates in a list stay distinct. nodes() pairs ids with values
+// - Link names the pointer that changed, including the structure's own root,
+//   head and tail, so replaying the Link, Create and Delete steps on a copy
+//   of the previous nodes() gives the next one (tests/steps.rs checks this)
+// - A pointer that is reassigned to the node it already held is not reported;
+//   the recursive code does that on every level of the path it walks back up
+// - Rotations are reported as a label before the links they change, so a
+//   viewer can name them; the links alone are enough to redraw
+// - The plain methods pass (), whose record() is empty and inlined away, so
+//   insert() costs what it did before the recorder existed
+// - Trade-off: only AvlTree, LinkedList and DoubleLinkedList report steps, and
+//   only for their main operations; range removal, move_to_front and the
+//   other structures still just perform the change
+
+use alloc::vec::Vec;
+
+// Heap address of a node, see the header
+pub type NodeId = usize;
+
+// A pointer in the node graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Link {
+    // The structure's own pointers
+    Root,
+    Head,
+    Tail,
+    // A node's pointers
+    Left(NodeId),
+    Right(NodeId),
+    Next(NodeId),
+    Prev(NodeId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    // A search compared against this node
+    Visit(NodeId),
+    // A node was allocated, with no links yet
+    Create(NodeId),
+    // A node was freed; its value left the structure
+    Delete(NodeId),
+    // The pointer now refers to `to`, or nothing
+    Link { link: Link, to: Option<NodeId> },
+    // A rotation at this node: its child on the other side takes its place
+    RotateLeft(NodeId),
+    RotateRight(NodeId),
+}
+
+// Receives the steps of a recorded operation
+pub trait Recorder {
+    fn record(&mut self, step: Step);
+}
+
+// Discards every step; what the plain methods use
+impl Recorder for () {
+    #[inline]
+    fn record(&mut self, _step: Step) {}
+}
+
+// Collects the steps in order
+impl Recorder for Vec<Step> {
+    fn record(&mut self, step: Step) {
+        self.push(step);
+    }
+}
+
+// One node of a tree, as nodes() lists them
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeNode<'a, T> {
+    pub id: NodeId,
+    pub value: &'a T,
+    pub height: u8,
+    pub left: Option<NodeId>,
+    pub right: Option<NodeId>,
+}
+
+pub(crate) fn node_id<N>(node: &N) -> NodeId {
+    node as *const N as usize
+}
+
+// Record a Link step if the pointer changed
+pub(crate) fn relink<R: Recorder>(recorder: &mut R, link: Link, before: Option<NodeId>, after: Option<NodeId>) {
+    if before != after {
+        recorder.record(Step::Link { link, to: after });
+    }
+}
@@ -0,0 +1,33 @@
+// Trace Events
+//
+// With the "trace" feature, trace! sends an event through the log facade at
+// trace level: AVL rotations, scapegoat rebuilds, hash map resizes and list
+// node allocations. Whatever logger the program installs decides where they
+// go (the demos print them to stderr, see rustnotes::trace). Without the
+// feature the macro expands to nothing.
+//
+// Design choices:
+// - The log facade rather than tracing: it works under no_std and costs one
+//   small dependency
+// - Events use the default target, the module path, so a logger can pick out
+//   one structure ("balanced_binary_tree") by target
+// - Messages describe shape and sizes, never element values, so no Debug bound
+//   is added to the containers
+// - Trade-off: with the feature on, every event checks the log level even when
+//   no logger is installed; off, the arguments are type-checked but never run
+
+#[cfg(feature = "trace")]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        log::trace!($($arg)+)
+    };
+}
+
+#[cfg(not(feature = "trace"))]
+macro_rules! trace {
+    ($($arg:tt)+) => {
+        if false {
+            let _ = format_args!($($arg)+);
+        }
+    };
+}
@@ -0,0 +1,588 @@
+// Common Collection Traits
+//
+// The lists, trees and heaps here each grew their own inherent methods, so code
+// written against one of them cannot be pointed at another. These traits name
+// the shared shapes, letting generic code (and benchmarks) be written once:
+//
+//   Collection          len, is_empty, clear,       every container
+//                       heap_size
+//   Stack<T>            push, pop, peek             LIFO: LinkedList, Stack, SmallVec, ArenaList, Vec
+//   Queue<T>            enqueue, dequeue            FIFO: Queue, DoubleLinkedList, ArenaList, VecDeque
+//   SortedSet<T>        insert, remove, contains,   AvlTree, ScapegoatTree, SkipList, ArenaTree,
+//                       iter (ascending), first     BTreeSet
+//
+// Design choices:
+// - The traits only forward to the inherent methods, which stay as they were;
+//   inherent methods win method lookup, so existing callers are unaffected and
+//   the impls below cannot recurse into themselves
+// - All impls live in this file, so one place shows which structure offers what
+// - Collection is object safe, so a Vec<Box<dyn Collection>> can hold
+//   unrelated containers
+// - heap_size is an estimate in bytes of what the container allocates itself
+//   (nodes, buffers, spare capacity), not what its elements own; allocator
+//   headers and padding between blocks are left out. For BTreeSet, whose node
+//   layout is private to std, it is the elements alone, a lower bound
+// - Queue has no front(): DoubleLinkedList and the Queue adapter keep nodes in
+//   RefCells and can only lend a Ref guard, not a plain &T
+// - SortedSet::insert and remove report whether the set changed; the trees whose
+//   inherent insert returns () are checked by comparing len() before and after
+// - SortedSet::iter is a generic associated type, so each implementor returns
+//   its own iterator without boxing
+// - std's Vec, VecDeque and BTreeSet implement the traits too, as baselines
+// - BinaryTree is only a Collection: invert() flips its order, so it cannot
+//   promise ascending iteration
+// - Trade-off: one more import at the call site (use rustnotes_collections::
+//   traits::Stack) and trait names that shadow the lists::Stack/Queue adapters
+//   when both are imported unqualified
+
+use alloc::collections::{btree_set, BTreeSet, VecDeque};
+use alloc::vec::Vec;
+use core::fmt::{Debug, Display};
+#[cfg(feature = "std")]
+use core::hash::{BuildHasher, Hash};
+use core::ops::RangeFull;
+#[cfg(feature = "std")]
+use crate::hashing::hash_map_chaining::HashMapChained;
+#[cfg(feature = "std")]
+use crate::hashing::hash_map_open_addressing::HashMapOa;
+#[cfg(feature = "std")]
+use crate::hashing::lru_cache::LruCache;
+use crate::heaps::binary_heap::MinHeap;
+use crate::heaps::indexed_heap::IndexedHeap;
+use crate::heaps::min_max_heap::MinMaxHeap;
+use crate::heaps::pairing_heap::PairingHeap;
+use crate::lists::arena::Arena;
+use crate::lists::arena_linked_list::ArenaList;
+use crate::lists::double_linked_list::DoubleLinkedList;
+use crate::lists::linked_list::LinkedList;
+use crate::lists::skip_list::{self, SkipList};
+use crate::lists::small_vec::SmallVec;
+use crate::lists::stack_and_queue;
+#[cfg(feature = "std")]
+use crate::random::{Pcg32, Rng};
+use crate::trees::arena_binary_tree::{self, ArenaTree};
+use crate::trees::balanced_binary_tree::{self, AvlTree};
+use crate::trees::binary_tree::BinaryTree;
+#[cfg(feature = "std")]
+use crate::trees::concurrent_avl_tree::ConcurrentAvlTree;
+use crate::trees::scapegoat_tree::{self, ScapegoatTree};
+use crate::trees::trie::Trie;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+pub trait Collection {
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Remove every element
+    fn clear(&mut self);
+
+    // Estimated bytes allocated by the container for its own storage
+    fn heap_size(&self) -> usize;
+}
+
+// Last-in, first-out
+pub trait Stack<T>: Collection {
+    fn push(&mut self, value: T);
+
+    fn pop(&mut self) -> Option<T>;
+
+    // The value the next pop() would return
+    fn peek(&self) -> Option<&T>;
+}
+
+// First-in, first-out
+pub trait Queue<T>: Collection {
+    fn enqueue(&mut self, value: T);
+
+    fn dequeue(&mut self) -> Option<T>;
+}
+
+// Distinct values kept in ascending order
+pub trait SortedSet<T: Ord>: Collection {
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    // false if the value was already present
+    fn insert(&mut self, value: T) -> bool;
+
+    // false if the value was not present
+    fn remove(&mut self, value: &T) -> bool;
+
+    fn contains(&self, value: &T) -> bool;
+
+    // Every value in ascending order
+    fn iter(&self) -> Self::Iter<'_>;
+
+    // The smallest value
+    fn first(&self) -> Option<&T> {
+        self.iter().next()
+    }
+}
+
+// Collection for types whose inherent len() and clear() already match
+macro_rules! impl_collection {
+    ($( [$($generics:tt)*] $ty:ty ),* $(,)?) => {
+        $(
+            impl<$($generics)*> Collection for $ty {
+                // Spelled as paths so a &self inherent clear() (ConcurrentAvlTree)
+                // is still found before the trait's &mut self one
+                fn len(&self) -> usize {
+                    <$ty>::len(self)
+                }
+
+                fn clear(&mut self) {
+                    <$ty>::clear(self);
+                }
+
+                fn heap_size(&self) -> usize {
+                    <$ty>::heap_size(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_collection! {
+    [T] LinkedList<T>,
+    [T] DoubleLinkedList<T>,
+    [T] stack_and_queue::Stack<T>,
+    [T] stack_and_queue::Queue<T>,
+    [T] Arena<T>,
+    [T] ArenaList<T>,
+    [T, const N: usize] SmallVec<T, N>,
+    [T: Ord] SkipList<T>,
+    [T: Ord + Clone] BinaryTree<T>,
+    [T: Ord + Clone + Display + Debug] AvlTree<T>,
+    [T: Ord] ScapegoatTree<T>,
+    [T: Ord] ArenaTree<T>,
+    [] Trie,
+    [T: Ord] MinHeap<T>,
+    [T: Ord] MinMaxHeap<T>,
+    [T: Ord] PairingHeap<T>,
+    [P: PartialOrd] IndexedHeap<P>,
+}
+
+// std's collections have no heap_size of their own
+macro_rules! impl_std_collection {
+    ($( $ty:ident => $heap_size:expr ),* $(,)?) => {
+        $(
+            impl<T> Collection for $ty<T> {
+                fn len(&self) -> usize {
+                    $ty::len(self)
+                }
+
+                fn clear(&mut self) {
+                    $ty::clear(self);
+                }
+
+                fn heap_size(&self) -> usize {
+                    let size: fn(&$ty<T>) -> usize = $heap_size;
+                    size(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_std_collection! {
+    Vec => |vec| vec.capacity() * core::mem::size_of::<T>(),
+    VecDeque => |deque| deque.capacity() * core::mem::size_of::<T>(),
+    BTreeSet => |set| set.len() * core::mem::size_of::<T>(),
+}
+
+// The hash maps and the locked tree need std (RandomState, RwLock)
+#[cfg(feature = "std")]
+impl_collection! {
+    [T: Ord + Clone + Display + Debug] ConcurrentAvlTree<T>,
+    [K: Hash + Eq, V, S: BuildHasher] HashMapOa<K, V, S>,
+    [K: Hash + Eq, V, S: BuildHasher] HashMapChained<K, V, S>,
+    [K: Hash + Eq + Clone, V] LruCache<K, V>,
+}
+
+impl<T> Stack<T> for LinkedList<T> {
+    fn push(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+}
+
+impl<T> Stack<T> for stack_and_queue::Stack<T> {
+    fn push(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.peek()
+    }
+}
+
+impl<T, const N: usize> Stack<T> for SmallVec<T, N> {
+    fn push(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+// The back of the list is the top of the stack
+impl<T> Stack<T> for ArenaList<T> {
+    fn push(&mut self, value: T) {
+        self.push_back(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop_back()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.back()
+    }
+}
+
+impl<T> Stack<T> for Vec<T> {
+    fn push(&mut self, value: T) {
+        self.push(value);
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        self.pop()
+    }
+
+    fn peek(&self) -> Option<&T> {
+        self.last()
+    }
+}
+
+impl<T> Queue<T> for stack_and_queue::Queue<T> {
+    fn enqueue(&mut self, value: T) {
+        self.enqueue(value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.dequeue()
+    }
+}
+
+// Append at the tail, take from the head
+impl<T> Queue<T> for DoubleLinkedList<T> {
+    fn enqueue(&mut self, value: T) {
+        self.push_end(value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop()
+    }
+}
+
+impl<T> Queue<T> for ArenaList<T> {
+    fn enqueue(&mut self, value: T) {
+        self.push_back(value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+impl<T> Queue<T> for VecDeque<T> {
+    fn enqueue(&mut self, value: T) {
+        self.push_back(value);
+    }
+
+    fn dequeue(&mut self) -> Option<T> {
+        self.pop_front()
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> SortedSet<T> for AvlTree<T> {
+    type Iter<'a> = balanced_binary_tree::Iter<'a, T> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        let before = self.len();
+        self.insert(value);
+        self.len() > before
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove(value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> SortedSet<T> for ScapegoatTree<T> {
+    type Iter<'a> = scapegoat_tree::Iter<'a, T> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        self.insert(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove(value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> SortedSet<T> for SkipList<T> {
+    type Iter<'a> = skip_list::Range<'a, T, RangeFull> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        self.insert(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+
+    fn first(&self) -> Option<&T> {
+        self.first()
+    }
+}
+
+impl<T: Ord> SortedSet<T> for ArenaTree<T> {
+    type Iter<'a> = arena_binary_tree::Iter<'a, T> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        self.insert(value).1
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove(value).is_some()
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+}
+
+impl<T: Ord> SortedSet<T> for BTreeSet<T> {
+    type Iter<'a> = btree_set::Iter<'a, T> where T: 'a;
+
+    fn insert(&mut self, value: T) -> bool {
+        self.insert(value)
+    }
+
+    fn remove(&mut self, value: &T) -> bool {
+        self.remove(value)
+    }
+
+    fn contains(&self, value: &T) -> bool {
+        self.contains(value)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.iter()
+    }
+
+    fn first(&self) -> Option<&T> {
+        self.first()
+    }
+}
+
+// Random pushes and pops (pushes twice as likely), then drain; returns a
+// checksum of everything popped in order, so equal results mean equal behavior
+#[cfg(feature = "std")]
+fn stack_workload<S: Stack<u64>>(name: &str, mut stack: S, operations: usize) -> u64 {
+    let mut rng = Pcg32::seed_from_u64(7);
+    let mut checksum = 0u64;
+    let start = Instant::now();
+    for i in 0..operations as u64 {
+        if rng.gen_index(0..3) == 0 {
+            if let Some(value) = stack.pop() {
+                checksum = checksum.wrapping_mul(31).wrapping_add(value);
+            }
+        } else {
+            stack.push(i);
+        }
+    }
+    let top = stack.peek().copied();
+    let remaining = stack.len();
+    while let Some(value) = stack.pop() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(value);
+    }
+    println!("{:<18} top {:?}, {} left before draining, checksum {:016x}, {:?}",
+             name, top, remaining, checksum, start.elapsed());
+    checksum
+}
+
+#[cfg(feature = "std")]
+fn queue_workload<Q: Queue<u64>>(name: &str, mut queue: Q, operations: usize) -> u64 {
+    let mut rng = Pcg32::seed_from_u64(7);
+    let mut checksum = 0u64;
+    let start = Instant::now();
+    for i in 0..operations as u64 {
+        if rng.gen_index(0..3) == 0 {
+            if let Some(value) = queue.dequeue() {
+                checksum = checksum.wrapping_mul(31).wrapping_add(value);
+            }
+        } else {
+            queue.enqueue(i);
+        }
+    }
+    let remaining = queue.len();
+    while let Some(value) = queue.dequeue() {
+        checksum = checksum.wrapping_mul(31).wrapping_add(value);
+    }
+    println!("{:<18} {} left before draining, checksum {:016x}, {:?}",
+             name, remaining, checksum, start.elapsed());
+    checksum
+}
+
+// Random inserts, removes and lookups over a small key range; returns the
+// answers to every operation plus the final contents
+#[cfg(feature = "std")]
+fn set_workload<S: SortedSet<u64>>(name: &str, mut set: S, operations: usize) -> (Vec<bool>, Vec<u64>) {
+    let mut rng = Pcg32::seed_from_u64(11);
+    let mut answers = Vec::with_capacity(operations);
+    let start = Instant::now();
+    for _ in 0..operations {
+        let key = rng.gen_range(0..2_000);
+        let answer = match rng.gen_index(0..3) {
+            0 => set.insert(key),
+            1 => set.remove(&key),
+            _ => set.contains(&key),
+        };
+        answers.push(answer);
+    }
+    let contents: Vec<u64> = set.iter().copied().collect();
+    println!("{:<18} {} values, first {:?}, {:?}", name, set.len(), set.first(), start.elapsed());
+    (answers, contents)
+}
+
+// Fill a structure with 0..n through its Stack, Queue or SortedSet impl and
+// report what heap_size says it costs
+#[cfg(feature = "std")]
+fn memory_row<C: Collection>(name: &str, mut collection: C, n: u64, add: fn(&mut C, u64)) {
+    (0..n).for_each(|value| add(&mut collection, value));
+    let bytes = collection.heap_size();
+    println!("{:<18} {:>9} bytes, {:>5.1} bytes per u64", name, bytes, bytes as f64 / n as f64);
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Stack<T>: the same workload on every stack ===");
+    let n = 200_000;
+    let results = [
+        stack_workload("LinkedList", LinkedList::new(), n),
+        stack_workload("lists::Stack", stack_and_queue::Stack::new(), n),
+        stack_workload("SmallVec<_, 16>", SmallVec::<u64, 16>::new(), n),
+        stack_workload("ArenaList", ArenaList::new(), n),
+        stack_workload("Vec", Vec::new(), n),
+    ];
+    println!("All stacks agree: {}", results.iter().all(|&r| r == results[0]));
+
+    println!("\n=== Queue<T>: the same workload on every queue ===");
+    let results = [
+        queue_workload("lists::Queue", stack_and_queue::Queue::new(), n),
+        queue_workload("DoubleLinkedList", DoubleLinkedList::new(), n),
+        queue_workload("ArenaList", ArenaList::new(), n),
+        queue_workload("VecDeque", VecDeque::new(), n),
+    ];
+    println!("All queues agree: {}", results.iter().all(|&r| r == results[0]));
+
+    println!("\n=== SortedSet<T>: the same workload on every sorted set ===");
+    let n = 100_000;
+    let results = [
+        set_workload("AvlTree", AvlTree::new(), n),
+        set_workload("ScapegoatTree", ScapegoatTree::new(), n),
+        set_workload("SkipList", SkipList::new(), n),
+        set_workload("ArenaTree", ArenaTree::new(), n),
+        set_workload("BTreeSet", BTreeSet::new(), n),
+    ];
+    let sorted = results[0].1.windows(2).all(|pair| pair[0] < pair[1]);
+    println!("All sets agree on every answer and on the contents: {}, contents ascending: {}",
+             results.iter().all(|r| *r == results[0]), sorted);
+
+    println!("\n=== Collection as a trait object ===");
+    let mut tree = BinaryTree::new();
+    let mut heap = MinHeap::new();
+    let mut map = HashMapOa::new();
+    let mut trie = Trie::new();
+    for i in 0..10 {
+        tree.insert(i);
+        heap.push(i);
+        map.insert(i, i * i);
+    }
+    for word in ["tea", "ten", "to"].iter() {
+        trie.insert(word);
+    }
+    let mut containers: Vec<(&str, Box<dyn Collection>)> = vec![
+        ("BinaryTree", Box::new(tree)),
+        ("MinHeap", Box::new(heap)),
+        ("HashMapOa", Box::new(map)),
+        ("Trie", Box::new(trie)),
+        ("VecDeque", Box::new(VecDeque::from(vec![1, 2, 3]))),
+    ];
+    for (name, container) in &containers {
+        println!("{:<10} len {:>2}, empty: {}", name, container.len(), container.is_empty());
+    }
+    for (_, container) in &mut containers {
+        container.clear();
+    }
+    println!("After clear() through the trait: lengths {:?}",
+             containers.iter().map(|(_, c)| c.len()).collect::<Vec<_>>());
+
+    println!("\n=== heap_size(): the memory cost of each design ===");
+    let n = 10_000;
+    println!("Lists holding {} u64 values ({} bytes of payload):", n, n * 8);
+    memory_row("LinkedList (Box)", LinkedList::new(), n, Stack::push);
+    memory_row("DoubleLinkedList", DoubleLinkedList::new(), n, Queue::enqueue);
+    memory_row("ArenaList", ArenaList::new(), n, Queue::enqueue);
+    memory_row("SmallVec<_, 16>", SmallVec::<u64, 16>::new(), n, Stack::push);
+    memory_row("Vec", Vec::new(), n, Stack::push);
+    memory_row("VecDeque", VecDeque::new(), n, Queue::enqueue);
+    println!("Sorted sets holding the same values:");
+    memory_row("AvlTree (Box)", AvlTree::new(), n, |set, value| { SortedSet::insert(set, value); });
+    memory_row("ScapegoatTree", ScapegoatTree::new(), n, |set, value| { SortedSet::insert(set, value); });
+    memory_row("SkipList", SkipList::new(), n, |set, value| { SortedSet::insert(set, value); });
+    memory_row("ArenaTree", ArenaTree::new(), n, |set, value| { SortedSet::insert(set, value); });
+    memory_row("BTreeSet (payload)", BTreeSet::new(), n, |set, value| { SortedSet::insert(set, value); });
+}
@@ -0,0 +1,318 @@
+// Arena-Backed Binary Search Tree with Parent Links
+//
+// A binary search tree whose nodes live in an Arena<Node<T>> from lists::arena.
+// Each node links to its children and to its parent by generational Index:
+//
+//            5 (#0)              node #3: value 4, parent #1, left -, right -
+//           /      \
+//       3 (#1)    8 (#2)
+//          \
+//         4 (#3)
+//
+// With Box children a parent pointer is impossible without Rc/Weak or unsafe,
+// because a node cannot be owned by its parent and also referenced by its
+// child. In an arena it is just one more Index field, and it enables the
+// textbook algorithms that walk upwards: in-order successor and predecessor
+// without a stack, iteration that can start at any node, and removal by
+// handle.
+//
+// Design choices:
+// - Handles returned by insert()/find() stay valid until that value is
+//   removed. remove() relinks nodes (CLRS "transplant") instead of copying the
+//   successor's value into the removed node, so no other handle changes meaning
+// - successor()/predecessor() climb parent links when there is no subtree to
+//   descend into, O(height) each and O(1) amortized across a full traversal
+// - The iterator is just "current handle + successor()", no stack
+// - No duplicates: inserting an existing value returns the existing handle
+// - Unbalanced, like trees::binary_tree; the point here is the node storage
+// - Trade-off: every node carries three Option<Index> (36 bytes) versus two
+//   boxes (16 bytes), and a stale handle costs a generation check per step
+
+#[cfg(feature = "std")]
+use alloc::collections::BTreeSet;
+use core::cmp::Ordering;
+use crate::lists::arena::{Arena, Index};
+
+struct Node<T> {
+    value: T,
+    parent: Option<Index>,
+    left: Option<Index>,
+    right: Option<Index>,
+}
+
+pub struct ArenaTree<T> {
+    nodes: Arena<Node<T>>,
+    root: Option<Index>,
+}
+
+impl<T: Ord> Default for ArenaTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> ArenaTree<T> {
+    pub fn new() -> Self {
+        ArenaTree { nodes: Arena::new(), root: None }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    // Every node lives in the arena's one buffer
+    pub fn heap_size(&self) -> usize {
+        self.nodes.heap_size()
+    }
+
+    // Drop every node; handles from before the clear stop resolving
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.root = None;
+    }
+
+    pub fn get(&self, handle: Index) -> Option<&T> {
+        self.nodes.get(handle).map(|node| &node.value)
+    }
+
+    // Insert a value, returning its handle and whether it was new
+    pub fn insert(&mut self, value: T) -> (Index, bool) {
+        let mut parent = None;
+        let mut current = self.root;
+        let mut goes_left = false;
+        while let Some(index) = current {
+            let node = &self.nodes[index];
+            match value.cmp(&node.value) {
+                Ordering::Equal => return (index, false),
+                Ordering::Less => {
+                    goes_left = true;
+                    current = node.left;
+                }
+                Ordering::Greater => {
+                    goes_left = false;
+                    current = node.right;
+                }
+            }
+            parent = Some(index);
+        }
+        let index = self.nodes.insert(Node { value, parent, left: None, right: None });
+        match parent {
+            None => self.root = Some(index),
+            Some(parent) if goes_left => self.nodes[parent].left = Some(index),
+            Some(parent) => self.nodes[parent].right = Some(index),
+        }
+        (index, true)
+    }
+
+    pub fn find(&self, value: &T) -> Option<Index> {
+        let mut current = self.root;
+        while let Some(index) = current {
+            let node = &self.nodes[index];
+            current = match value.cmp(&node.value) {
+                Ordering::Equal => return Some(index),
+                Ordering::Less => node.left,
+                Ordering::Greater => node.right,
+            };
+        }
+        None
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.find(value).is_some()
+    }
+
+    pub fn first(&self) -> Option<Index> {
+        self.root.map(|root| self.leftmost(root))
+    }
+
+    pub fn last(&self) -> Option<Index> {
+        self.root.map(|root| self.rightmost(root))
+    }
+
+    // The next node in sorted order
+    pub fn successor(&self, handle: Index) -> Option<Index> {
+        let node = self.nodes.get(handle)?;
+        if let Some(right) = node.right {
+            return Some(self.leftmost(right));
+        }
+        // Climb until we come up from a left child
+        let mut child = handle;
+        let mut parent = node.parent;
+        while let Some(index) = parent {
+            if self.nodes[index].left == Some(child) {
+                return Some(index);
+            }
+            child = index;
+            parent = self.nodes[index].parent;
+        }
+        None
+    }
+
+    // The previous node in sorted order
+    pub fn predecessor(&self, handle: Index) -> Option<Index> {
+        let node = self.nodes.get(handle)?;
+        if let Some(left) = node.left {
+            return Some(self.rightmost(left));
+        }
+        let mut child = handle;
+        let mut parent = node.parent;
+        while let Some(index) = parent {
+            if self.nodes[index].right == Some(child) {
+                return Some(index);
+            }
+            child = index;
+            parent = self.nodes[index].parent;
+        }
+        None
+    }
+
+    pub fn remove(&mut self, value: &T) -> Option<T> {
+        let handle = self.find(value)?;
+        self.remove_handle(handle)
+    }
+
+    // Remove the node behind a handle; None if the handle is stale
+    pub fn remove_handle(&mut self, handle: Index) -> Option<T> {
+        let (left, right) = {
+            let node = self.nodes.get(handle)?;
+            (node.left, node.right)
+        };
+        match (left, right) {
+            (None, _) => self.transplant(handle, right),
+            (_, None) => self.transplant(handle, left),
+            (Some(left), Some(right)) => {
+                // The successor has no left child; lift it into handle's place
+                let successor = self.leftmost(right);
+                if successor != right {
+                    let successor_right = self.nodes[successor].right;
+                    self.transplant(successor, successor_right);
+                    self.nodes[successor].right = Some(right);
+                    self.nodes[right].parent = Some(successor);
+                }
+                self.transplant(handle, Some(successor));
+                self.nodes[successor].left = Some(left);
+                self.nodes[left].parent = Some(successor);
+            }
+        }
+        self.nodes.remove(handle).map(|node| node.value)
+    }
+
+    pub fn height(&self) -> usize {
+        fn height<T>(nodes: &Arena<Node<T>>, index: Option<Index>) -> usize {
+            index.map_or(0, |i| 1 + height(nodes, nodes[i].left).max(height(nodes, nodes[i].right)))
+        }
+        height(&self.nodes, self.root)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter { tree: self, current: self.first() }
+    }
+
+    // Sorted iteration starting at a given node
+    pub fn iter_from(&self, handle: Index) -> Iter<'_, T> {
+        Iter { tree: self, current: Some(handle).filter(|&h| self.nodes.contains(h)) }
+    }
+
+    fn leftmost(&self, mut index: Index) -> Index {
+        while let Some(left) = self.nodes[index].left {
+            index = left;
+        }
+        index
+    }
+
+    fn rightmost(&self, mut index: Index) -> Index {
+        while let Some(right) = self.nodes[index].right {
+            index = right;
+        }
+        index
+    }
+
+    // Put `replacement` where `target` hangs from its parent
+    fn transplant(&mut self, target: Index, replacement: Option<Index>) {
+        let parent = self.nodes[target].parent;
+        match parent {
+            None => self.root = replacement,
+            Some(p) if self.nodes[p].left == Some(target) => self.nodes[p].left = replacement,
+            Some(p) => self.nodes[p].right = replacement,
+        }
+        if let Some(r) = replacement {
+            self.nodes[r].parent = parent;
+        }
+    }
+}
+
+pub struct Iter<'a, T> {
+    tree: &'a ArenaTree<T>,
+    current: Option<Index>,
+}
+
+impl<'a, T: Ord> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.current?;
+        self.current = self.tree.successor(index);
+        Some(&self.tree.nodes[index].value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Handles and parent links ===");
+    let mut tree = ArenaTree::new();
+    let mut handles = Vec::new();
+    for &value in [50, 30, 70, 20, 40, 60, 80, 35, 45].iter() {
+        handles.push(tree.insert(value).0);
+    }
+    println!("Sorted: {:?}", tree.iter().collect::<Vec<_>>());
+    let forty = tree.find(&40).unwrap();
+    println!("successor(40) = {:?}, predecessor(40) = {:?}",
+             tree.successor(forty).and_then(|h| tree.get(h)),
+             tree.predecessor(forty).and_then(|h| tree.get(h)));
+    println!("From 40 onward: {:?}", tree.iter_from(forty).collect::<Vec<_>>());
+    println!("insert(40) again: new = {}", tree.insert(40).1);
+
+    println!("\n=== Removal keeps other handles valid ===");
+    println!("remove(30) (two children) = {:?}", tree.remove(&30));
+    println!("Sorted: {:?}", tree.iter().collect::<Vec<_>>());
+    let still_valid: Vec<i32> = handles.iter().filter_map(|&h| tree.get(h).copied()).collect();
+    println!("Old handles still resolving: {:?}", still_valid);
+    println!("Handle of 40 still points at {:?}", tree.get(forty));
+    println!("remove_handle(root) = {:?}", tree.remove_handle(handles[0]));
+    println!("remove_handle(root) again = {:?}", tree.remove_handle(handles[0]));
+    println!("Sorted: {:?}, height {}", tree.iter().collect::<Vec<_>>(), tree.height());
+
+    println!("\n=== Random operations vs BTreeSet ===");
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut next = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    let mut tree = ArenaTree::new();
+    let mut model = BTreeSet::new();
+    let mut ok = true;
+    for _ in 0..50_000 {
+        let value = next() % 2000;
+        if next() % 3 == 0 {
+            ok &= tree.remove(&value).is_some() == model.remove(&value);
+        } else {
+            ok &= tree.insert(value).1 == model.insert(value);
+        }
+    }
+    ok &= tree.len() == model.len() && tree.iter().eq(model.iter());
+    // Walk backwards with predecessor() as well
+    let mut backwards = Vec::new();
+    let mut current = tree.last();
+    while let Some(handle) = current {
+        backwards.push(*tree.get(handle).unwrap());
+        current = tree.predecessor(handle);
+    }
+    ok &= backwards.iter().eq(model.iter().rev());
+    println!("50000 operations agree with BTreeSet: {} ({} values, height {})", ok, tree.len(), tree.height());
+}
@@ -0,0 +1,574 @@
+// Complete AVL Tree Implementation
+//
+// A self-balancing binary search tree where the heights of the two child 
+// subtrees of any node differ by at most one. Both insertion and deletion
+// maintain this property through rotations, guaranteeing O(log n) operations.
+//
+// Design choices:
+// - Uses Box<Node<T>> for child pointers (heap allocation, owned references)
+// - Each node stores its height for efficient balance factor calculation
+// - Four rotation types: left, right, left-right, right-left for rebalancing
+// - Recursive insertion/deletion with rebalancing on the way back up
+// - Range removal splits the tree around the range and joins the outer parts,
+//   so cost depends on the tree height plus the number of removed nodes
+// - AVL property maintained: |height(left) - height(right)| <= 1 for all nodes
+// - insert_recorded/remove_recorded report each visit, link change and
+//   rotation to a steps::Recorder; insert/remove pass () and record nothing
+// - Trade-off: Extra height storage and rotation overhead for guaranteed O(log n) performance
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use core::ops::{Bound, RangeBounds};
+use crate::steps::{node_id, relink, Link, NodeId, Recorder, Step, TreeNode};
+use crate::trees::binary_tree::TreeStats;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+    height: u8,
+}
+
+// Owned subtree link, used where a signature carries several of them
+type Subtree<T> = Option<Box<Node<T>>>;
+
+#[derive(Debug)]
+pub struct AvlTree<T> {
+    root: Option<Box<Node<T>>>,
+    size: usize,
+}
+
+impl<T: Ord + Clone + core::fmt::Display + core::fmt::Debug> Default for AvlTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone + core::fmt::Display + core::fmt::Debug> AvlTree<T> {
+    pub fn new() -> Self {
+        Self { root: None, size: 0 }
+    }
+
+    fn node_height(node: &Option<Box<Node<T>>>) -> u8 {
+        node.as_ref().map_or(0, |n| n.height)
+    }
+
+    fn update_height(node: &mut Node<T>) {
+        node.height = 1 + Self::node_height(&node.left).max(Self::node_height(&node.right));
+    }
+
+    fn balance_factor(node: &Node<T>) -> i8 {
+        Self::node_height(&node.left) as i8 - Self::node_height(&node.right) as i8
+    }
+
+    fn id(node: &Subtree<T>) -> Option<NodeId> {
+        node.as_deref().map(node_id)
+    }
+
+    fn rotate_right<R: Recorder>(mut root: Box<Node<T>>, recorder: &mut R) -> Box<Node<T>> {
+        recorder.record(Step::RotateRight(node_id(&*root)));
+        let mut new_root = root.left.take().unwrap();
+        root.left = new_root.right.take();
+        recorder.record(Step::Link { link: Link::Left(node_id(&*root)), to: Self::id(&root.left) });
+        Self::update_height(&mut root);
+        new_root.right = Some(root);
+        recorder.record(Step::Link { link: Link::Right(node_id(&*new_root)), to: Self::id(&new_root.right) });
+        Self::update_height(&mut new_root);
+        new_root
+    }
+
+    fn rotate_left<R: Recorder>(mut root: Box<Node<T>>, recorder: &mut R) -> Box<Node<T>> {
+        recorder.record(Step::RotateLeft(node_id(&*root)));
+        let mut new_root = root.right.take().unwrap();
+        root.right = new_root.left.take();
+        recorder.record(Step::Link { link: Link::Right(node_id(&*root)), to: Self::id(&root.right) });
+        Self::update_height(&mut root);
+        new_root.left = Some(root);
+        recorder.record(Step::Link { link: Link::Left(node_id(&*new_root)), to: Self::id(&new_root.left) });
+        Self::update_height(&mut new_root);
+        new_root
+    }
+
+    fn rebalance<R: Recorder>(mut node: Box<Node<T>>, recorder: &mut R) -> Box<Node<T>> {
+        Self::update_height(&mut node);
+        let balance = Self::balance_factor(&node);
+        
+        // Left heavy
+        if balance > 1 {
+            let left_balance = if let Some(ref left) = node.left {
+                Self::balance_factor(left)
+            } else { 0 };
+            
+            // Left-Right case
+            if left_balance < 0 {
+                trace!("left-right case at height {}: rotate left child left, then right", node.height);
+                let before = Self::id(&node.left);
+                node.left = Some(Self::rotate_left(node.left.take().unwrap(), recorder));
+                relink(recorder, Link::Left(node_id(&*node)), before, Self::id(&node.left));
+            } else {
+                trace!("left-left case at height {}: rotate right", node.height);
+            }
+            // Left-Left case
+            Self::rotate_right(node, recorder)
+        }
+        // Right heavy 
+        else if balance < -1 {
+            let right_balance = if let Some(ref right) = node.right {
+                Self::balance_factor(right)
+            } else { 0 };
+            
+            // Right-Left case
+            if right_balance > 0 {
+                trace!("right-left case at height {}: rotate right child right, then left", node.height);
+                let before = Self::id(&node.right);
+                node.right = Some(Self::rotate_right(node.right.take().unwrap(), recorder));
+                relink(recorder, Link::Right(node_id(&*node)), before, Self::id(&node.right));
+            } else {
+                trace!("right-right case at height {}: rotate left", node.height);
+            }
+            // Right-Right case
+            Self::rotate_left(node, recorder)
+        }
+        // Already balanced
+        else {
+            node
+        }
+    }
+
+    pub fn insert(&mut self, value: T) {
+        self.insert_recorded(value, &mut ());
+    }
+
+    // Insert, reporting each step to the recorder; false if the value was present
+    pub fn insert_recorded<R: Recorder>(&mut self, value: T, recorder: &mut R) -> bool {
+        let before = Self::id(&self.root);
+        let (new_root, inserted) = Self::insert_node(self.root.take(), value, recorder);
+        self.root = new_root;
+        relink(recorder, Link::Root, before, Self::id(&self.root));
+        if inserted {
+            self.size += 1;
+        }
+        inserted
+    }
+
+    fn insert_node<R: Recorder>(node: Subtree<T>, value: T, recorder: &mut R) -> (Subtree<T>, bool) {
+        match node {
+            None => {
+                let new_node = Box::new(Node {
+                    value,
+                    left: None,
+                    right: None,
+                    height: 1,
+                });
+                recorder.record(Step::Create(node_id(&*new_node)));
+                (Some(new_node), true)
+            }
+            Some(mut n) => {
+                let id = node_id(&*n);
+                recorder.record(Step::Visit(id));
+                let inserted = match value.cmp(&n.value) {
+                    core::cmp::Ordering::Less => {
+                        let before = Self::id(&n.left);
+                        let (left, ins) = Self::insert_node(n.left.take(), value, recorder);
+                        n.left = left;
+                        relink(recorder, Link::Left(id), before, Self::id(&n.left));
+                        ins
+                    }
+                    core::cmp::Ordering::Greater => {
+                        let before = Self::id(&n.right);
+                        let (right, ins) = Self::insert_node(n.right.take(), value, recorder);
+                        n.right = right;
+                        relink(recorder, Link::Right(id), before, Self::id(&n.right));
+                        ins
+                    }
+                    core::cmp::Ordering::Equal => false, // No duplicates
+                };
+                
+                let result_node = if inserted { Self::rebalance(n, recorder) } else { n };
+                (Some(result_node), inserted)
+            }
+        }
+    }
+
+    pub fn remove(&mut self, value: &T) -> bool {
+        self.remove_recorded(value, &mut ())
+    }
+
+    // Remove, reporting each step to the recorder; false if the value was absent
+    pub fn remove_recorded<R: Recorder>(&mut self, value: &T, recorder: &mut R) -> bool {
+        let before = Self::id(&self.root);
+        let (new_root, removed) = Self::remove_node(self.root.take(), value, recorder);
+        self.root = new_root;
+        relink(recorder, Link::Root, before, Self::id(&self.root));
+        if removed {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    fn remove_node<R: Recorder>(node: Subtree<T>, value: &T, recorder: &mut R) -> (Subtree<T>, bool) {
+        match node {
+            None => (None, false),
+            Some(mut n) => {
+                let id = node_id(&*n);
+                recorder.record(Step::Visit(id));
+                let removed = match value.cmp(&n.value) {
+                    core::cmp::Ordering::Less => {
+                        let before = Self::id(&n.left);
+                        let (left, rem) = Self::remove_node(n.left.take(), value, recorder);
+                        n.left = left;
+                        relink(recorder, Link::Left(id), before, Self::id(&n.left));
+                        rem
+                    }
+                    core::cmp::Ordering::Greater => {
+                        let before = Self::id(&n.right);
+                        let (right, rem) = Self::remove_node(n.right.take(), value, recorder);
+                        n.right = right;
+                        relink(recorder, Link::Right(id), before, Self::id(&n.right));
+                        rem
+                    }
+                    core::cmp::Ordering::Equal => {
+                        return match (n.left.take(), n.right.take()) {
+                            (None, None) => {
+                                recorder.record(Step::Delete(id));
+                                (None, true)
+                            }
+                            (Some(child), None) | (None, Some(child)) => {
+                                recorder.record(Step::Delete(id));
+                                (Some(child), true)
+                            }
+                            (Some(left), Some(right)) => {
+                                // The successor's value moves into a new node in this one's place
+                                let (successor, new_right) = Self::extract_min(right, recorder);
+                                recorder.record(Step::Delete(id));
+                                let mut new_node = Box::new(Node {
+                                    value: successor,
+                                    left: Some(left),
+                                    right: new_right,
+                                    height: 1,
+                                });
+                                let new_id = node_id(&*new_node);
+                                recorder.record(Step::Create(new_id));
+                                relink(recorder, Link::Left(new_id), None, Self::id(&new_node.left));
+                                relink(recorder, Link::Right(new_id), None, Self::id(&new_node.right));
+                                Self::update_height(&mut new_node);
+                                (Some(Self::rebalance(new_node, recorder)), true)
+                            }
+                        };
+                    }
+                };
+                
+                let result_node = if removed { Self::rebalance(n, recorder) } else { n };
+                (Some(result_node), removed)
+            }
+        }
+    }
+
+    fn extract_min<R: Recorder>(mut node: Box<Node<T>>, recorder: &mut R) -> (T, Subtree<T>) {
+        let id = node_id(&*node);
+        match node.left.take() {
+            None => {
+                recorder.record(Step::Delete(id));
+                (node.value, node.right)
+            }
+            Some(left) => {
+                let before = Some(node_id(&*left));
+                let (min_val, new_left) = Self::extract_min(left, recorder);
+                node.left = new_left;
+                relink(recorder, Link::Left(id), before, Self::id(&node.left));
+                let rebalanced = Self::rebalance(node, recorder);
+                (min_val, Some(rebalanced))
+            }
+        }
+    }
+
+    // Remove every value inside the range, returning how many were removed.
+    // The tree is split into (below range, rest), the rest into (in range, above range),
+    // and the two outer trees are joined back together.
+    pub fn remove_range<R: RangeBounds<T>>(&mut self, range: R) -> usize {
+        let below_start = |v: &T| match range.start_bound() {
+            Bound::Included(start) => v < start,
+            Bound::Excluded(start) => v <= start,
+            Bound::Unbounded => false,
+        };
+        let within_end = |v: &T| match range.end_bound() {
+            Bound::Included(end) => v <= end,
+            Bound::Excluded(end) => v < end,
+            Bound::Unbounded => true,
+        };
+
+        let (below, rest) = Self::split(self.root.take(), &below_start);
+        let (inside, above) = Self::split(rest, &within_end);
+        let removed = Self::count_nodes(&inside);
+
+        self.root = Self::join_trees(below, above);
+        self.size -= removed;
+        removed
+    }
+
+    // Split into (values where goes_left is true, the rest); goes_left must be
+    // true for a prefix of the sorted order
+    fn split<F: Fn(&T) -> bool>(node: Subtree<T>, goes_left: &F) -> (Subtree<T>, Subtree<T>) {
+        match node {
+            None => (None, None),
+            Some(n) => {
+                let Node { value, left, right, .. } = *n;
+                if goes_left(&value) {
+                    let (right_low, right_high) = Self::split(right, goes_left);
+                    (Some(Self::join(left, value, right_low)), right_high)
+                } else {
+                    let (left_low, left_high) = Self::split(left, goes_left);
+                    (left_low, Some(Self::join(left_high, value, right)))
+                }
+            }
+        }
+    }
+
+    // Join two AVL trees and a middle value where left < value < right.
+    // Descends the spine of the taller tree until the heights are within one,
+    // attaches there, and rebalances on the way back up.
+    fn join(left: Subtree<T>, value: T, right: Subtree<T>) -> Box<Node<T>> {
+        let left_height = Self::node_height(&left);
+        let right_height = Self::node_height(&right);
+
+        if left_height > right_height + 1 {
+            let mut l = left.unwrap();
+            l.right = Some(Self::join(l.right.take(), value, right));
+            Self::rebalance(l, &mut ())
+        } else if right_height > left_height + 1 {
+            let mut r = right.unwrap();
+            r.left = Some(Self::join(left, value, r.left.take()));
+            Self::rebalance(r, &mut ())
+        } else {
+            let mut node = Box::new(Node { value, left, right, height: 1 });
+            Self::update_height(&mut node);
+            node
+        }
+    }
+
+    // Join two AVL trees where every value in left < every value in right,
+    // using the minimum of the right tree as the middle value
+    fn join_trees(left: Subtree<T>, right: Subtree<T>) -> Subtree<T> {
+        match right {
+            None => left,
+            Some(r) => {
+                let (min_val, rest) = Self::extract_min(r, &mut ());
+                Some(Self::join(left, min_val, rest))
+            }
+        }
+    }
+
+    fn count_nodes(node: &Option<Box<Node<T>>>) -> usize {
+        node.as_ref()
+            .map_or(0, |n| 1 + Self::count_nodes(&n.left) + Self::count_nodes(&n.right))
+    }
+
+    // The stored value equal to `value`; when T orders by a key alone, this
+    // finds the rest of an entry from just its key
+    pub fn get(&self, value: &T) -> Option<&T> {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                core::cmp::Ordering::Equal => return Some(&node.value),
+                core::cmp::Ordering::Less => current = &node.left,
+                core::cmp::Ordering::Greater => current = &node.right,
+            }
+        }
+        None
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.get(value).is_some()
+    }
+
+    pub fn len(&self) -> usize { 
+        self.size 
+    }
+
+    pub fn is_empty(&self) -> bool { 
+        self.size == 0 
+    }
+
+    // One boxed node per element
+    pub fn heap_size(&self) -> usize {
+        self.size * core::mem::size_of::<Node<T>>()
+    }
+
+    pub fn height(&self) -> u8 { 
+        Self::node_height(&self.root) 
+    }
+
+    pub fn clear(&mut self) { 
+        self.root = None; 
+        self.size = 0; 
+    }
+
+    #[cfg(feature = "std")]
+    pub fn print_root(&self) {
+        match &self.root {
+            None => println!("Root: None (empty tree)"),
+            Some(node) => {
+                println!("Root: {} (height: {}, balance: {}, left: {:?}, right: {:?})", 
+                    node.value,
+                    node.height,
+                    Self::balance_factor(node),
+                    node.left.as_ref().map(|n| &n.value),
+                    node.right.as_ref().map(|n| &n.value)
+                );
+            }
+        }
+    }
+
+    // Verify AVL property (for debugging)
+    pub fn is_balanced(&self) -> bool {
+        Self::check_balanced(&self.root).is_some()
+    }
+
+    fn check_balanced(node: &Option<Box<Node<T>>>) -> Option<u8> {
+        match node {
+            None => Some(0),
+            Some(n) => {
+                let left_height = Self::check_balanced(&n.left)?;
+                let right_height = Self::check_balanced(&n.right)?;
+                
+                let balance = left_height as i8 - right_height as i8;
+                if balance.abs() > 1 {
+                    None // Not balanced
+                } else {
+                    Some(1 + left_height.max(right_height))
+                }
+            }
+        }
+    }
+
+    // Every node with its id and links, root first (pre-order), for drawing
+    // the tree between recorded operations
+    pub fn nodes(&self) -> Vec<TreeNode<'_, T>> {
+        let mut nodes = Vec::with_capacity(self.size);
+        let mut stack: Vec<&Node<T>> = self.root.as_deref().into_iter().collect();
+        while let Some(node) = stack.pop() {
+            nodes.push(TreeNode {
+                id: node_id(node),
+                value: &node.value,
+                height: node.height,
+                left: Self::id(&node.left),
+                right: Self::id(&node.right),
+            });
+            stack.extend(node.right.as_deref());
+            stack.extend(node.left.as_deref());
+        }
+        nodes
+    }
+
+    // Borrowing in-order iterator: yields values in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+
+    // Gather shape statistics (level widths, depths, balance factors) in one pass
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::collect(self.root.as_deref(), |n| (n.left.as_deref(), n.right.as_deref()))
+    }
+}
+
+// In-order iterator; the stack holds nodes whose left subtrees are done
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut node: &'a Option<Box<Node<T>>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = &n.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some(&node.value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut tree = AvlTree::new();
+    
+    println!("=== AVL Tree Test: Insert 1-10 ===");
+    for i in 1..=10 {
+        tree.insert(i);
+        println!("After inserting {}: height {}, balanced: {}", 
+                 i, tree.height(), tree.is_balanced());
+        tree.print_root();
+    }
+    
+    println!("\n=== Remove 1, 2, 3 ===");
+    for i in 1..=3 {
+        tree.remove(&i);
+        println!("After removing {}: {} nodes, height {}, balanced: {}", 
+                 i, tree.len(), tree.height(), tree.is_balanced());
+        tree.print_root();
+    }
+    
+    println!("\n=== Insert 11-25 ===");
+    for i in 11..=25 {
+        tree.insert(i);
+        println!("After inserting {}: {} nodes, height {}, balanced: {}", 
+                 i, tree.len(), tree.height(), tree.is_balanced());
+        tree.print_root();
+    }
+    
+    println!("\n=== Final Verification ===");
+    println!("Final tree: {} nodes, height {}", tree.len(), tree.height());
+    println!("Is balanced: {}", tree.is_balanced());
+    println!("Contains 3: {}", tree.contains(&3));
+    println!("Contains 20: {}", tree.contains(&20));
+
+    println!("\n=== Remove ranges ===");
+    let removed = tree.remove_range(5..15);
+    println!("remove_range(5..15) removed {}: {} nodes, height {}, balanced: {}",
+             removed, tree.len(), tree.height(), tree.is_balanced());
+    println!("Contains 5: {}, contains 14: {}, contains 15: {}",
+             tree.contains(&5), tree.contains(&14), tree.contains(&15));
+    let removed = tree.remove_range(20..=22);
+    println!("remove_range(20..=22) removed {}: {} nodes, balanced: {}",
+             removed, tree.len(), tree.is_balanced());
+    let removed = tree.remove_range(24..);
+    println!("remove_range(24..) removed {}: {} nodes, balanced: {}",
+             removed, tree.len(), tree.is_balanced());
+    let removed = tree.remove_range(100..200);
+    println!("remove_range(100..200) removed {}: {} nodes", removed, tree.len());
+    tree.print_root();
+
+    let mut big = AvlTree::new();
+    for i in 0..1000 {
+        big.insert(i);
+    }
+    let removed = big.remove_range(100..900);
+    println!("Bulk: removed {} of 1000, {} left, height {}, balanced: {}",
+             removed, big.len(), big.height(), big.is_balanced());
+
+    println!("\n=== Stats ===");
+    println!("{}", tree.stats());
+
+    // Same sequential input that degenerates a plain BST into a list
+    let mut sequential = AvlTree::new();
+    for i in 1..=15 {
+        sequential.insert(i);
+    }
+    println!("\n=== Stats after sequential inserts 1..=15 ===");
+    println!("{}", sequential.stats());
+
+    println!("\n=== Built with avl![..] ===");
+    let literal = crate::avl![50, 30, 70, 20, 40, 60, 80, 30];
+    println!("In order: {:?}, height {}, balanced: {}",
+             literal.iter().collect::<Vec<_>>(), literal.height(), literal.is_balanced());
+}
\ No newline at end of file
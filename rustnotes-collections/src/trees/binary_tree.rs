@@ -0,0 +1,451 @@
+// Binary Search Tree Implementation
+//
+// A generic binary search tree that maintains sorted order. Values are inserted
+// based on comparison (left < parent < right). Supports insert, remove, contains,
+// and basic operations. Does not self-balance - may become unbalanced with 
+// sequential insertions.
+//
+// Design choices:
+// - Uses Box<Node<T>> for child pointers (heap allocation, owned references)
+// - Recursive algorithms for all operations (insert, remove, contains)
+// - No duplicates allowed - inserting existing value is ignored
+// - Remove uses in-order successor for nodes with two children
+// - Simple owned pointer design eliminates reference counting overhead
+// - invert() mirrors the tree in place and flips a flag so searches compare in
+//   reverse; in-order traversal of a mirrored tree yields descending order
+// - In-order iterator walks the tree with an explicit stack of borrowed nodes
+// - Trade-off: May degrade to O(n) performance with sequential insertions
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+#[derive(Debug)]
+pub struct BinaryTree<T> {
+    root: Option<Box<Node<T>>>,
+    size: usize,
+    mirrored: bool,
+}
+
+impl<T: Ord + Clone> Default for BinaryTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone> BinaryTree<T> {
+    pub fn new() -> Self {
+        BinaryTree {
+            root: None,
+            size: 0,
+            mirrored: false,
+        }
+    }
+
+    // Ordering used for navigation: reversed once the tree has been mirrored
+    fn compare(value: &T, node_value: &T, mirrored: bool) -> core::cmp::Ordering {
+        let ordering = value.cmp(node_value);
+        if mirrored { ordering.reverse() } else { ordering }
+    }
+
+    pub fn insert(&mut self, value: T) {
+        match self.root.take() {
+            None => {
+                self.root = Some(Box::new(Node {
+                    value,
+                    left: None,
+                    right: None,
+                }));
+                self.size += 1;
+            }
+            Some(node) => {
+                self.root = Some(Self::insert_node(node, value, &mut self.size, self.mirrored));
+            }
+        }
+    }
+
+    fn insert_node(mut node: Box<Node<T>>, value: T, size: &mut usize, mirrored: bool) -> Box<Node<T>> {
+        match Self::compare(&value, &node.value, mirrored) {
+            core::cmp::Ordering::Less => {
+                match node.left.take() {
+                    None => {
+                        node.left = Some(Box::new(Node {
+                            value,
+                            left: None,
+                            right: None,
+                        }));
+                        *size += 1;
+                    }
+                    Some(left_node) => {
+                        node.left = Some(Self::insert_node(left_node, value, size, mirrored));
+                    }
+                }
+            }
+            core::cmp::Ordering::Greater => {
+                match node.right.take() {
+                    None => {
+                        node.right = Some(Box::new(Node {
+                            value,
+                            left: None,
+                            right: None,
+                        }));
+                        *size += 1;
+                    }
+                    Some(right_node) => {
+                        node.right = Some(Self::insert_node(right_node, value, size, mirrored));
+                    }
+                }
+            }
+            core::cmp::Ordering::Equal => {
+                // Value already exists, don't insert duplicate
+            }
+        }
+        node
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        Self::contains_node(&self.root, value, self.mirrored)
+    }
+
+    fn contains_node(node: &Option<Box<Node<T>>>, value: &T, mirrored: bool) -> bool {
+        match node {
+            None => false,
+            Some(n) => match Self::compare(value, &n.value, mirrored) {
+                core::cmp::Ordering::Equal => true,
+                core::cmp::Ordering::Less => Self::contains_node(&n.left, value, mirrored),
+                core::cmp::Ordering::Greater => Self::contains_node(&n.right, value, mirrored),
+            },
+        }
+    }
+
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (new_root, removed) = Self::remove_node(self.root.take(), value, self.mirrored);
+        self.root = new_root;
+        if removed {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    fn remove_node(node: Option<Box<Node<T>>>, value: &T, mirrored: bool) -> (Option<Box<Node<T>>>, bool) {
+        match node {
+            None => (None, false),
+            Some(mut n) => match Self::compare(value, &n.value, mirrored) {
+                core::cmp::Ordering::Less => {
+                    let (new_left, removed) = Self::remove_node(n.left.take(), value, mirrored);
+                    n.left = new_left;
+                    (Some(n), removed)
+                }
+                core::cmp::Ordering::Greater => {
+                    let (new_right, removed) = Self::remove_node(n.right.take(), value, mirrored);
+                    n.right = new_right;
+                    (Some(n), removed)
+                }
+                core::cmp::Ordering::Equal => {
+                    match (n.left.take(), n.right.take()) {
+                        (None, None) => (None, true),
+                        (Some(left), None) => (Some(left), true),
+                        (None, Some(right)) => (Some(right), true),
+                        (Some(left), Some(right)) => {
+                            let (min_value, new_right) = Self::extract_min(right);
+                            let new_node = Box::new(Node {
+                                value: min_value,
+                                left: Some(left),
+                                right: new_right,
+                            });
+                            (Some(new_node), true)
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    // Leftmost node of the subtree: the in-order successor when removing
+    fn extract_min(mut node: Box<Node<T>>) -> (T, Option<Box<Node<T>>>) {
+        match node.left.take() {
+            None => (node.value, node.right),
+            Some(left) => {
+                let (min_value, new_left) = Self::extract_min(left);
+                node.left = new_left;
+                (min_value, Some(node))
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+        self.mirrored = false;
+    }
+
+    // Mirror the tree in place by swapping every node's children.
+    // Invert twice to get back the original shape and ascending order.
+    pub fn invert(&mut self) {
+        Self::invert_node(&mut self.root);
+        self.mirrored = !self.mirrored;
+    }
+
+    fn invert_node(node: &mut Option<Box<Node<T>>>) {
+        if let Some(n) = node {
+            core::mem::swap(&mut n.left, &mut n.right);
+            Self::invert_node(&mut n.left);
+            Self::invert_node(&mut n.right);
+        }
+    }
+
+    pub fn is_mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    // Check whether the tree's shape is its own mirror image. Values are not
+    // compared: a search tree without duplicates can only be value-symmetric
+    // when it has at most one node.
+    pub fn is_symmetric(&self) -> bool {
+        match &self.root {
+            None => true,
+            Some(n) => Self::is_mirror_pair(&n.left, &n.right),
+        }
+    }
+
+    fn is_mirror_pair(a: &Option<Box<Node<T>>>, b: &Option<Box<Node<T>>>) -> bool {
+        match (a, b) {
+            (None, None) => true,
+            (Some(x), Some(y)) => {
+                Self::is_mirror_pair(&x.left, &y.right) && Self::is_mirror_pair(&x.right, &y.left)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // One boxed node per element
+    pub fn heap_size(&self) -> usize {
+        self.size * core::mem::size_of::<Node<T>>()
+    }
+
+    // Borrowing in-order iterator: yields values in sorted order without consuming the tree
+    // (descending order while the tree is mirrored)
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+
+    // Gather shape statistics (level widths, depths, balance factors) in one pass
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::collect(self.root.as_deref(), |n| (n.left.as_deref(), n.right.as_deref()))
+    }
+}
+
+// In-order iterator. The stack holds the nodes whose left subtrees have been
+// visited but which have not been yielded yet; the top is always the next value.
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut node: &'a Option<Box<Node<T>>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = &n.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some(&node.value)
+    }
+}
+
+impl<'a, T: Ord + Clone> IntoIterator for &'a BinaryTree<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// Prints the contents in sorted order, e.g. [1, 4, 5, 7]
+impl<T: Ord + Clone + fmt::Display> fmt::Display for BinaryTree<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "[")?;
+        for (i, value) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, "]")
+    }
+}
+
+// Shape statistics for a tree: how many nodes sit on each level, how deep the
+// average node is, and how often each balance factor (left height - right height)
+// occurs. A balanced tree keeps every factor within -1..=1. The AVL and
+// scapegoat trees report their shape with the same struct.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TreeStats {
+    pub node_count: usize,
+    pub height: usize,
+    pub nodes_per_level: Vec<usize>,
+    pub average_depth: f64,
+    pub balance_histogram: BTreeMap<i32, usize>,
+}
+
+impl TreeStats {
+    // Statistics of the tree under `root`, for any node type whose children
+    // `children` returns, so the BST, AVL and scapegoat trees share one walk
+    pub(crate) fn collect<N>(root: Option<&N>, children: fn(&N) -> (Option<&N>, Option<&N>)) -> TreeStats {
+        let mut stats = TreeStats {
+            node_count: 0,
+            height: 0,
+            nodes_per_level: Vec::new(),
+            average_depth: 0.0,
+            balance_histogram: BTreeMap::new(),
+        };
+        let mut depth_total = 0;
+        stats.height = stats.visit(root, children, 0, &mut depth_total);
+        if stats.node_count > 0 {
+            stats.average_depth = depth_total as f64 / stats.node_count as f64;
+        }
+        stats
+    }
+
+    // Returns the height of the subtree while recording each node's level and balance
+    fn visit<N>(
+        &mut self,
+        node: Option<&N>,
+        children: fn(&N) -> (Option<&N>, Option<&N>),
+        depth: usize,
+        depth_total: &mut usize,
+    ) -> usize {
+        let Some(node) = node else { return 0 };
+        if self.nodes_per_level.len() <= depth {
+            self.nodes_per_level.push(0);
+        }
+        self.nodes_per_level[depth] += 1;
+        self.node_count += 1;
+        *depth_total += depth;
+
+        let (left, right) = children(node);
+        let left_height = self.visit(left, children, depth + 1, depth_total);
+        let right_height = self.visit(right, children, depth + 1, depth_total);
+        let balance = left_height as i32 - right_height as i32;
+        *self.balance_histogram.entry(balance).or_insert(0) += 1;
+        1 + left_height.max(right_height)
+    }
+}
+
+impl fmt::Display for TreeStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Nodes: {}, height: {}, average depth: {:.2}",
+                 self.node_count, self.height, self.average_depth)?;
+        writeln!(f, "Nodes per level:")?;
+        for (level, count) in self.nodes_per_level.iter().enumerate() {
+            writeln!(f, "  level {:>2}: {}", level, count)?;
+        }
+        write!(f, "Balance factor histogram:")?;
+        for (balance, count) in &self.balance_histogram {
+            write!(f, "\n  {:>+4}: {}", balance, count)?;
+        }
+        Ok(())
+    }
+}
+
+// Example usage
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut tree = BinaryTree::new();
+    
+    // Insert some values
+    tree.insert(5);
+    tree.insert(3);
+    tree.insert(7);
+    tree.insert(1);
+    tree.insert(4);
+    tree.insert(6);
+    tree.insert(9);
+    
+    println!("Tree length: {}", tree.len());
+    println!("Tree contents: {}", tree);
+    
+    // Iterate in sorted order without consuming the tree
+    let doubled: Vec<i32> = tree.iter().map(|v| v * 2).collect();
+    println!("Doubled in order: {:?}", doubled);
+    let mut sum = 0;
+    for value in &tree {
+        sum += value;
+    }
+    println!("Sum via for loop: {}", sum);
+    
+    // Test contains
+    println!("Contains 5: {}", tree.contains(&5));
+    println!("Contains 8: {}", tree.contains(&8));
+    
+    // Test remove
+    println!("Removing 3: {}", tree.remove(&3));
+    println!("Tree length after removal: {}", tree.len());
+    println!("Contains 3 after removal: {}", tree.contains(&3));
+    println!("Tree contents after removal: {}", tree);
+    
+    // Shape statistics
+    println!("\n--- Stats after mixed-order inserts ---");
+    println!("{}", tree.stats());
+
+    let mut sequential = BinaryTree::new();
+    for i in 1..=15 {
+        sequential.insert(i);
+    }
+    println!("\n--- Stats after sequential inserts 1..=15 (degenerate) ---");
+    println!("{}", sequential.stats());
+
+    // Mirror the tree
+    println!("\n--- Testing invert and is_symmetric ---");
+    println!("Symmetric before invert: {}", tree.is_symmetric());
+    tree.invert();
+    println!("After invert: {} (mirrored: {})", tree, tree.is_mirrored());
+    tree.insert(8);
+    println!("Insert 8 into mirrored tree: {}", tree);
+    println!("Contains 8: {}, contains 4: {}", tree.contains(&8), tree.contains(&4));
+    println!("Removing 5: {}", tree.remove(&5));
+    tree.invert();
+    println!("After second invert: {} (mirrored: {})", tree, tree.is_mirrored());
+
+    let mut perfect = BinaryTree::new();
+    for value in [4, 2, 6, 1, 3, 5, 7].iter() {
+        perfect.insert(*value);
+    }
+    println!("Perfect tree {} symmetric: {}", perfect, perfect.is_symmetric());
+    perfect.remove(&7);
+    println!("After removing 7 symmetric: {}", perfect.is_symmetric());
+
+    // Clear the tree
+    tree.clear();
+    println!("Tree length after clear: {}", tree.len());
+    println!("Is empty: {}", tree.is_empty());
+    println!("Empty tree contents: {}", tree);
+    println!("Stats of empty tree: height {}, levels {}",
+             tree.stats().height, tree.stats().nodes_per_level.len());
+}
@@ -0,0 +1,252 @@
+// Concurrent AVL Tree
+//
+// A thread-safe ordered set built by wrapping the AVL tree from
+// trees::balanced_binary_tree in a reader-writer lock. Any number of threads can
+// query the set at the same time; an insert or remove waits for the readers to
+// finish and then has the tree to itself while it rotates.
+//
+// Design choices:
+// - Coarse-grained locking: one RwLock guards the whole tree, so every reader
+//   sees a fully rebalanced tree and never a half-finished rotation
+// - Arc<RwLock<AvlTree<T>>> inside the handle, so clone() gives another handle
+//   to the same set that can be moved into a thread
+// - read()/write() accept closures for compound operations that must see a
+//   consistent snapshot (e.g. check-then-insert)
+// - Fine-grained (hand-over-hand) locking is not used: AVL rotations touch the
+//   nodes above the insertion point, which would force writers to lock paths
+//   from the root anyway
+// - Trade-off: writers serialize and block readers, but the implementation
+//   reuses the single-threaded tree unchanged
+
+use crate::trees::balanced_binary_tree::AvlTree;
+use std::fmt::{Debug, Display};
+use std::ops::RangeBounds;
+use std::sync::{Arc, Barrier, RwLock};
+use std::thread;
+
+pub struct ConcurrentAvlTree<T> {
+    inner: Arc<RwLock<AvlTree<T>>>,
+}
+
+// Cloning shares the tree rather than copying it
+impl<T> Clone for ConcurrentAvlTree<T> {
+    fn clone(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> Default for ConcurrentAvlTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> ConcurrentAvlTree<T> {
+    pub fn new() -> Self {
+        Self { inner: Arc::new(RwLock::new(AvlTree::new())) }
+    }
+
+    // Run a closure with shared access; other readers may run concurrently
+    pub fn read<R, F: FnOnce(&AvlTree<T>) -> R>(&self, f: F) -> R {
+        let guard = self.inner.read().expect("AVL tree lock poisoned");
+        f(&guard)
+    }
+
+    // Run a closure with exclusive access
+    pub fn write<R, F: FnOnce(&mut AvlTree<T>) -> R>(&self, f: F) -> R {
+        let mut guard = self.inner.write().expect("AVL tree lock poisoned");
+        f(&mut guard)
+    }
+
+    // Returns true if the value was newly inserted
+    pub fn insert(&self, value: T) -> bool {
+        self.write(|tree| {
+            let before = tree.len();
+            tree.insert(value);
+            tree.len() > before
+        })
+    }
+
+    pub fn remove(&self, value: &T) -> bool {
+        self.write(|tree| tree.remove(value))
+    }
+
+    pub fn remove_range<R: RangeBounds<T>>(&self, range: R) -> usize {
+        self.write(|tree| tree.remove_range(range))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        self.read(|tree| tree.contains(value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.read(|tree| tree.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.read(|tree| tree.is_empty())
+    }
+
+    // The shared Arc allocation (counts and lock) plus the tree's nodes
+    pub fn heap_size(&self) -> usize {
+        2 * core::mem::size_of::<usize>() + core::mem::size_of::<RwLock<AvlTree<T>>>() + self.read(|tree| tree.heap_size())
+    }
+
+    pub fn height(&self) -> u8 {
+        self.read(|tree| tree.height())
+    }
+
+    pub fn clear(&self) {
+        self.write(|tree| tree.clear())
+    }
+}
+
+// Run `readers` threads against one writer filling the (empty) set with
+// 0..writes; returns how many snapshots each reader checked
+fn readers_during_writes(set: &ConcurrentAvlTree<i32>, writes: i32, readers: usize) -> Vec<usize> {
+    // All threads start together so the readers overlap with the writes
+    let start = Arc::new(Barrier::new(readers + 1));
+
+    // One writer inserts 0..writes in order, which forces constant rotations
+    let writer = {
+        let set = set.clone();
+        let start = Arc::clone(&start);
+        thread::spawn(move || {
+            start.wait();
+            for i in 0..writes {
+                assert!(set.insert(i));
+                thread::yield_now();
+            }
+        })
+    };
+
+    // Readers check that every snapshot they see is a valid, balanced AVL tree
+    // and that the set only grows while the writer is inserting
+    let readers: Vec<_> = (0..readers)
+        .map(|id| {
+            let set = set.clone();
+            let start = Arc::clone(&start);
+            thread::spawn(move || {
+                start.wait();
+                let mut last_len = 0;
+                let mut snapshots = 0;
+                loop {
+                    let (len, balanced, has_first, has_last) = set.read(|tree| {
+                        (tree.len(), tree.is_balanced(), tree.contains(&0),
+                         tree.contains(&(tree.len() as i32 - 1)))
+                    });
+                    assert!(balanced, "reader {} saw an unbalanced tree", id);
+                    assert!(len >= last_len, "reader {} saw the set shrink", id);
+                    if len > 0 {
+                        // Inserts are in order, so the set is always exactly 0..len
+                        assert!(has_first && has_last, "reader {} saw a gap", id);
+                    }
+                    last_len = len;
+                    snapshots += 1;
+                    if len == writes as usize {
+                        return snapshots;
+                    }
+                }
+            })
+        })
+        .collect();
+
+    writer.join().expect("writer panicked");
+    readers.into_iter().map(|reader| reader.join().expect("reader panicked")).collect()
+}
+
+pub fn demo() {
+    println!("=== Single-threaded use ===");
+    let set = ConcurrentAvlTree::new();
+    for i in 1..=10 {
+        set.insert(i);
+    }
+    println!("Length: {}, height: {}", set.len(), set.height());
+    println!("Insert duplicate 5: {}", set.insert(5));
+    println!("Remove 5: {}", set.remove(&5));
+    println!("Contains 5: {}", set.contains(&5));
+
+    println!("\n=== Concurrent readers during writes ===");
+    set.clear();
+    const WRITES: i32 = 2000;
+    let snapshots = readers_during_writes(&set, WRITES, 4);
+    for (id, count) in snapshots.iter().enumerate() {
+        println!("Reader {} checked {} consistent snapshots", id, count);
+    }
+    println!("Final length: {}, height: {}", set.len(), set.height());
+
+    println!("\n=== Concurrent writers on disjoint ranges ===");
+    let writers: Vec<_> = (0..4)
+        .map(|t| {
+            let set = set.clone();
+            thread::spawn(move || {
+                let start = WRITES + t * 500;
+                for i in start..start + 500 {
+                    set.insert(i);
+                }
+                // Each writer also removes a slice of the original values
+                set.remove_range(t * 100..t * 100 + 50)
+            })
+        })
+        .collect();
+    let removed: usize = writers.into_iter().map(|w| w.join().expect("writer panicked")).sum();
+    println!("Removed {} values, final length: {}", removed, set.len());
+    assert_eq!(set.len(), WRITES as usize + 2000 - removed);
+    println!("Balanced: {}", set.read(|tree| tree.is_balanced()));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_threaded_set() {
+        let set = ConcurrentAvlTree::new();
+        assert!(set.is_empty());
+        for i in 1..=10 {
+            assert!(set.insert(i));
+        }
+        assert!(!set.insert(5));
+        assert_eq!((set.len(), set.height()), (10, 4));
+        assert!(set.remove(&5));
+        assert!(!set.remove(&5));
+        assert!(!set.contains(&5));
+        assert_eq!(set.remove_range(..=3), 3);
+        assert_eq!(set.read(|tree| tree.iter().cloned().collect::<Vec<_>>()), vec![4, 6, 7, 8, 9, 10]);
+        set.clear();
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn readers_see_consistent_snapshots_during_writes() {
+        let set = ConcurrentAvlTree::new();
+        let snapshots = readers_during_writes(&set, 500, 4);
+        assert_eq!(snapshots.len(), 4);
+        assert!(snapshots.iter().all(|&count| count > 0));
+        assert_eq!(set.len(), 500);
+        assert!(set.read(|tree| tree.is_balanced() && tree.iter().cloned().eq(0..500)));
+    }
+
+    #[test]
+    fn writers_on_disjoint_ranges() {
+        let set = ConcurrentAvlTree::new();
+        for i in 0..400 {
+            set.insert(i);
+        }
+        let writers: Vec<_> = (0..4)
+            .map(|t| {
+                let set = set.clone();
+                thread::spawn(move || {
+                    let start = 1000 + t * 500;
+                    let inserted = (start..start + 500).filter(|&i| set.insert(i)).count();
+                    (inserted, set.remove_range(t * 100..t * 100 + 50))
+                })
+            })
+            .collect();
+        for writer in writers {
+            assert_eq!(writer.join().expect("writer panicked"), (500, 50));
+        }
+        let expected: Vec<i32> = (0..400).filter(|i| i % 100 >= 50).chain(1000..3000).collect();
+        assert!(set.read(|tree| tree.is_balanced() && tree.iter().cloned().eq(expected.iter().cloned())));
+    }
+}
@@ -0,0 +1,39 @@
+// Tree Errors
+//
+// What the fallible tree operations (the try_ methods and constructors)
+// report instead of panicking: positions outside a segment tree and invalid
+// balance parameters. The panicking versions stay, with this type's Display
+// text as their panic message.
+//
+// Design choices:
+// - One enum for every tree, so callers holding several trees match on one type
+// - Ranges are reported half-open, as start..end after resolving inclusive and
+//   unbounded ends, so 2..=4 on a length-3 tree reads "range 2..5"
+// - Trade-off: InvalidAlpha holds an f64, so the enum is PartialEq but not Eq
+
+use core::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TreeError {
+    IndexOutOfBounds { index: usize, len: usize },
+    // start > end, or end past the length
+    RangeOutOfBounds { start: usize, end: usize, len: usize },
+    // A scapegoat tree's alpha outside (0.5, 0.95]
+    InvalidAlpha(f64),
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TreeError::IndexOutOfBounds { index, len } => {
+                write!(f, "index {} out of bounds for length {}", index, len)
+            }
+            TreeError::RangeOutOfBounds { start, end, len } => {
+                write!(f, "range {}..{} out of bounds for length {}", start, end, len)
+            }
+            TreeError::InvalidAlpha(alpha) => write!(f, "alpha must be above 0.5 and at most 0.95, got {}", alpha),
+        }
+    }
+}
+
+impl core::error::Error for TreeError {}
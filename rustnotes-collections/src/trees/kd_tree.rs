@@ -0,0 +1,329 @@
+// K-d Tree for Nearest-Neighbor Search
+//
+// A binary tree over points in K dimensions. Each level splits the points in
+// half along one coordinate axis, cycling x, y, z, ... with depth; the median
+// point sits at the node, smaller coordinates go left, larger go right. A search
+// descends toward the query point first, then backs out and only crosses a
+// splitting plane when the plane is closer than the best match found so far.
+//
+//   points (2,3) (5,4) (9,6) (4,7) (8,1) (7,2), split x then y:
+//
+//              (7,2)          x = 7
+//             /     \
+//         (5,4)     (9,6)     y = 4 | y = 6
+//         /   \      /
+//      (2,3) (4,7) (8,1)
+//
+// Design choices:
+// - Const generic dimension K with points as [f64; K], so 2-D, 3-D and
+//   higher-dimensional trees share one implementation and mismatched
+//   dimensions are compile errors
+// - Implicit layout: build() reorders the points so that every subtree is a
+//   contiguous slice with its median in the middle. No child pointers, no
+//   allocation per node, and the tree is perfectly balanced
+// - Medians are found with select_nth_unstable_by (quickselect), giving O(n log n)
+//   construction instead of sorting at every level
+// - k_nearest keeps the best k candidates in the MinMaxHeap from heaps::min_max_heap:
+//   peek_max is the current k-th distance used for pruning, pop_max evicts it
+// - Distances are compared squared; no square roots during search
+// - Trade-off: static - there is no insert or delete, rebuild to change the set.
+//   Pruning also weakens as K grows (beyond ~20 dimensions a k-d tree visits
+//   nearly every node and brute force is as good)
+
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use crate::heaps::min_max_heap::MinMaxHeap;
+
+pub struct KdTree<T, const K: usize> {
+    points: Vec<([f64; K], T)>,
+}
+
+// A search result candidate, ordered by distance
+struct Candidate {
+    distance: f64,
+    index: usize,
+}
+
+// State threaded through one k-nearest search
+struct Search<'a, const K: usize> {
+    target: &'a [f64; K],
+    k: usize,
+    best: MinMaxHeap<Candidate>,
+    visited: usize,
+}
+
+impl PartialEq for Candidate {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Candidate {}
+
+impl PartialOrd for Candidate {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Candidate {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.distance.total_cmp(&other.distance).then(self.index.cmp(&other.index))
+    }
+}
+
+pub fn distance_squared<const K: usize>(a: &[f64; K], b: &[f64; K]) -> f64 {
+    a.iter().zip(b.iter()).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+// Reorder `points` so the median along this depth's axis is in the middle, recursively
+fn build_recursive<T, const K: usize>(points: &mut [([f64; K], T)], depth: usize) {
+    if points.len() <= 1 {
+        return;
+    }
+    let axis = depth % K;
+    let mid = points.len() / 2;
+    points.select_nth_unstable_by(mid, |a, b| a.0[axis].total_cmp(&b.0[axis]));
+    let (left, right) = points.split_at_mut(mid);
+    build_recursive(left, depth + 1);
+    build_recursive(&mut right[1..], depth + 1);
+}
+
+impl<T, const K: usize> KdTree<T, K> {
+    pub fn build(mut points: Vec<([f64; K], T)>) -> Self {
+        assert!(K > 0, "a k-d tree needs at least one dimension");
+        build_recursive(&mut points, 0);
+        KdTree { points }
+    }
+
+    pub fn len(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.points.is_empty()
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.points.capacity() * core::mem::size_of::<([f64; K], T)>()
+    }
+
+    pub fn nearest(&self, target: &[f64; K]) -> Option<(&[f64; K], &T)> {
+        self.k_nearest(target, 1).into_iter().next()
+    }
+
+    // The k closest points, nearest first (fewer if the tree is smaller)
+    pub fn k_nearest(&self, target: &[f64; K], k: usize) -> Vec<(&[f64; K], &T)> {
+        self.k_nearest_counting(target, k).0
+    }
+
+    // k_nearest plus the number of nodes whose distance was computed
+    pub fn k_nearest_counting(&self, target: &[f64; K], k: usize) -> (Vec<(&[f64; K], &T)>, usize) {
+        let mut search = Search { target, k, best: MinMaxHeap::with_capacity(k + 1), visited: 0 };
+        if k > 0 {
+            self.search(0, self.points.len(), 0, &mut search);
+        }
+        let found = search
+            .best
+            .into_sorted_vec()
+            .into_iter()
+            .map(|c| (&self.points[c.index].0, &self.points[c.index].1))
+            .collect();
+        (found, search.visited)
+    }
+
+    // Search the subtree stored in points[lo..hi]
+    fn search(&self, lo: usize, hi: usize, depth: usize, search: &mut Search<K>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = lo + (hi - lo) / 2;
+        let (point, _) = &self.points[mid];
+        search.visited += 1;
+        search.best.push(Candidate { distance: distance_squared(point, search.target), index: mid });
+        if search.best.len() > search.k {
+            search.best.pop_max();
+        }
+
+        // Search the side containing the target first, the other side only if the
+        // splitting plane is closer than the current k-th best
+        let axis = depth % K;
+        let offset = search.target[axis] - point[axis];
+        let (near, far) = if offset < 0.0 { ((lo, mid), (mid + 1, hi)) } else { ((mid + 1, hi), (lo, mid)) };
+        self.search(near.0, near.1, depth + 1, search);
+        let worst = search.best.peek_max().map_or(f64::INFINITY, |c| c.distance);
+        if search.best.len() < search.k || offset * offset < worst {
+            self.search(far.0, far.1, depth + 1, search);
+        }
+    }
+}
+
+// Brute-force reference: distances of the k closest points, ascending
+#[cfg(any(test, feature = "std"))]
+fn brute_force<T, const K: usize>(points: &[([f64; K], T)], target: &[f64; K], k: usize) -> Vec<f64> {
+    let mut distances: Vec<f64> = points.iter().map(|(p, _)| distance_squared(p, target)).collect();
+    distances.sort_by(|a, b| a.total_cmp(b));
+    distances.truncate(k);
+    distances
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== The textbook example ===");
+    let cities = vec![
+        ([2.0, 3.0], "A"), ([5.0, 4.0], "B"), ([9.0, 6.0], "C"),
+        ([4.0, 7.0], "D"), ([8.0, 1.0], "E"), ([7.0, 2.0], "F"),
+    ];
+    let tree = KdTree::build(cities);
+    let layout: Vec<&str> = tree.points.iter().map(|(_, name)| *name).collect();
+    println!("Implicit layout (each subtree's median in the middle): {:?}", layout);
+    let (point, name) = tree.nearest(&[9.0, 2.0]).unwrap();
+    println!("Nearest to (9, 2): {} at {:?}", name, point);
+    let three: Vec<&str> = tree.k_nearest(&[5.0, 5.0], 3).into_iter().map(|(_, name)| *name).collect();
+    println!("3 nearest to (5, 5): {:?}", three);
+    println!("10 nearest of 6 points returns {}", tree.k_nearest(&[0.0, 0.0], 10).len());
+
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+
+    println!("\n=== 2-D: checked against brute force ===");
+    let points: Vec<([f64; 2], usize)> = (0..50_000).map(|i| ([random() * 1000.0, random() * 1000.0], i)).collect();
+    let tree = KdTree::build(points.clone());
+    let mut ok = true;
+    let mut total_visited = 0;
+    for _ in 0..500 {
+        let target = [random() * 1200.0 - 100.0, random() * 1200.0 - 100.0];
+        for &k in [1, 5, 20].iter() {
+            let (found, visited) = tree.k_nearest_counting(&target, k);
+            let distances: Vec<f64> = found.iter().map(|(p, _)| distance_squared(p, &target)).collect();
+            ok &= distances == brute_force(&points, &target, k);
+            if k == 1 {
+                total_visited += visited;
+            }
+        }
+    }
+    println!("500 queries x k in {{1, 5, 20}} match brute force: {}", ok);
+    println!("Nearest search visits {:.1} of {} points on average", total_visited as f64 / 500.0, tree.len());
+
+    println!("\n=== Higher dimensions ===");
+    fn check<const K: usize>(random: &mut dyn FnMut() -> f64) {
+        let points: Vec<([f64; K], ())> = (0..20_000)
+            .map(|_| {
+                let mut p = [0.0; K];
+                p.iter_mut().for_each(|x| *x = random());
+                (p, ())
+            })
+            .collect();
+        let tree = KdTree::build(points.clone());
+        let mut ok = true;
+        let mut visited = 0;
+        for _ in 0..100 {
+            let mut target = [0.0; K];
+            target.iter_mut().for_each(|x| *x = random());
+            let (found, count) = tree.k_nearest_counting(&target, 10);
+            let distances: Vec<f64> = found.iter().map(|(p, _)| distance_squared(p, &target)).collect();
+            ok &= distances == brute_force(&points, &target, 10);
+            visited += count;
+        }
+        println!("K = {:>2}: 10-nearest matches brute force: {}, visits {:>7.1} of 20000 points",
+                 K, ok, visited as f64 / 100.0);
+    }
+    check::<3>(&mut random);
+    check::<6>(&mut random);
+    check::<12>(&mut random);
+
+    println!("\n=== Edge cases ===");
+    let empty: KdTree<(), 2> = KdTree::build(Vec::new());
+    println!("Empty tree nearest: {:?}", empty.nearest(&[0.0, 0.0]));
+    let same = KdTree::build(vec![([1.0, 1.0], 'a'), ([1.0, 1.0], 'b'), ([1.0, 1.0], 'c')]);
+    println!("Duplicate points, 2 nearest: {:?}", same.k_nearest(&[0.0, 0.0], 2).len());
+    println!("k = 0 returns {:?}", same.k_nearest(&[0.0, 0.0], 0));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random::{Rng, XorShift64Star};
+    use alloc::vec;
+
+    fn random_points<const K: usize>(rng: &mut XorShift64Star, count: usize) -> Vec<([f64; K], usize)> {
+        (0..count)
+            .map(|id| {
+                let mut point = [0.0; K];
+                point.iter_mut().for_each(|x| *x = rng.next_f64() * 1000.0);
+                (point, id)
+            })
+            .collect()
+    }
+
+    // k_nearest distances must equal brute force for every query; returns the
+    // average number of points a nearest-neighbor search visited
+    fn check_against_brute_force<const K: usize>(count: usize, queries: usize, ks: &[usize]) -> f64 {
+        let mut rng = XorShift64Star::seed_from_u64(K as u64);
+        let points = random_points::<K>(&mut rng, count);
+        let tree = KdTree::build(points.clone());
+        let mut visited = 0;
+        for _ in 0..queries {
+            let mut target = [0.0; K];
+            target.iter_mut().for_each(|x| *x = rng.next_f64() * 1200.0 - 100.0);
+            for &k in ks {
+                let (found, count) = tree.k_nearest_counting(&target, k);
+                let distances: Vec<f64> = found.iter().map(|(p, _)| distance_squared(p, &target)).collect();
+                assert_eq!(distances, brute_force(&points, &target, k), "K = {}, k = {}", K, k);
+                for (point, &id) in &found {
+                    assert_eq!(**point, points[id].0);
+                }
+                if k == ks[0] {
+                    visited += count;
+                }
+            }
+        }
+        visited as f64 / queries as f64
+    }
+
+    #[test]
+    fn textbook_example() {
+        let cities = vec![
+            ([2.0, 3.0], "A"), ([5.0, 4.0], "B"), ([9.0, 6.0], "C"),
+            ([4.0, 7.0], "D"), ([8.0, 1.0], "E"), ([7.0, 2.0], "F"),
+        ];
+        let tree = KdTree::build(cities);
+        assert_eq!(tree.len(), 6);
+        assert_eq!(tree.nearest(&[9.0, 2.0]).map(|(_, name)| *name), Some("E"));
+        let two: Vec<&str> = tree.k_nearest(&[5.0, 5.0], 2).into_iter().map(|(_, name)| *name).collect();
+        assert_eq!(two, ["B", "D"]);
+        assert_eq!(tree.k_nearest(&[0.0, 0.0], 10).len(), 6);
+    }
+
+    #[test]
+    fn k_nearest_matches_brute_force_in_2d() {
+        let visited = check_against_brute_force::<2>(10_000, 100, &[1, 5, 20]);
+        // Pruning is the point of the tree: a nearest search looks at a few
+        // dozen of the 10000 points, not all of them
+        assert!(visited < 100.0, "visited {} points on average", visited);
+    }
+
+    #[test]
+    fn k_nearest_matches_brute_force_in_higher_dimensions() {
+        check_against_brute_force::<3>(2_000, 50, &[10]);
+        check_against_brute_force::<6>(2_000, 50, &[10]);
+        check_against_brute_force::<12>(2_000, 20, &[10]);
+    }
+
+    #[test]
+    fn edge_cases() {
+        let empty: KdTree<(), 2> = KdTree::build(Vec::new());
+        assert!(empty.is_empty());
+        assert!(empty.nearest(&[0.0, 0.0]).is_none());
+
+        let same = KdTree::build(vec![([1.0, 1.0], 'a'), ([1.0, 1.0], 'b'), ([1.0, 1.0], 'c')]);
+        assert_eq!(same.k_nearest(&[0.0, 0.0], 2).len(), 2);
+        assert!(same.k_nearest(&[0.0, 0.0], 0).is_empty());
+        assert_eq!(same.nearest(&[5.0, 5.0]).map(|(point, _)| *point), Some([1.0, 1.0]));
+    }
+}
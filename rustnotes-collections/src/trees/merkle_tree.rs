@@ -0,0 +1,241 @@
+// Merkle Tree
+//
+// A binary tree of hashes over a list of data blocks. Each leaf is the hash of
+// one block and each internal node is the hash of its two children, so the root
+// hash commits to every block: change one byte anywhere and the root changes.
+//
+//                  root = H(1 | H01 | H23)
+//                 /                       \
+//        H01 = H(1 | H0 | H1)      H23 = H(1 | H2 | H3)
+//         /          \              /          \
+//   H0 = H(0 | b0)  H1 ...        H2 ...      H3 = H(0 | b3)
+//
+// The point is the inclusion proof: to convince someone who only knows the root
+// that block 2 is in the tree, send block 2 plus the sibling hashes on its path
+// (H3, then H01). They recompute H2, H23 and the root and compare. The proof has
+// one hash per level, O(log n) instead of all n blocks.
+//
+// Design choices:
+// - Hashing is SHA-256 from hashing::hash_functions
+// - Leaves and internal nodes are hashed with different prefix bytes (0 and 1,
+//   as in RFC 6962), so an internal node can never be passed off as a leaf
+// - An odd node at the end of a level is promoted unchanged to the next level
+//   rather than paired with a copy of itself; duplicating it (as Bitcoin does)
+//   lets two different block lists share a root
+// - The tree keeps every level, so proofs are read off directly and update()
+//   rehashes just one path
+// - A proof stores the leaf index, the leaf count and the sibling hashes; the
+//   verifier derives from the index and count which side each sibling is on and
+//   where a level had no sibling, so a proof cannot lie about the path shape
+// - The root of an empty tree is the hash of the empty string
+// - Trade-off: keeps 2n hashes in memory for fast proofs; a verifier or an
+//   append-only log could store far less
+
+use alloc::vec;
+use alloc::vec::Vec;
+use crate::hashing::hash_functions::{sha256, Digest, Sha256};
+#[cfg(feature = "std")]
+use crate::hashing::hash_functions::to_hex;
+
+const LEAF_PREFIX: u8 = 0;
+const NODE_PREFIX: u8 = 1;
+
+fn hash_leaf(block: &[u8]) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update(&[LEAF_PREFIX]);
+    hasher.update(block);
+    hasher.finalize()
+}
+
+fn hash_node(left: &Digest, right: &Digest) -> Digest {
+    let mut hasher = Sha256::new();
+    hasher.update(&[NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize()
+}
+
+// The level above `level`: pairs hashed together, an odd last node promoted
+fn parent_level(level: &[Digest]) -> Vec<Digest> {
+    level
+        .chunks(2)
+        .map(|pair| if pair.len() == 2 { hash_node(&pair[0], &pair[1]) } else { pair[0] })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct InclusionProof {
+    pub index: usize,
+    pub leaf_count: usize,
+    pub siblings: Vec<Digest>,
+}
+
+pub struct MerkleTree {
+    levels: Vec<Vec<Digest>>, // levels[0] = leaves, last = [root]
+}
+
+impl MerkleTree {
+    pub fn new<B: AsRef<[u8]>>(blocks: &[B]) -> Self {
+        let mut levels = vec![blocks.iter().map(|b| hash_leaf(b.as_ref())).collect::<Vec<_>>()];
+        while levels.last().unwrap().len() > 1 {
+            let next = parent_level(levels.last().unwrap());
+            levels.push(next);
+        }
+        MerkleTree { levels }
+    }
+
+    pub fn len(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.levels[0].is_empty()
+    }
+
+    // The level vectors and the digests in each level
+    pub fn heap_size(&self) -> usize {
+        let digests: usize = self.levels.iter().map(|level| level.capacity()).sum();
+        self.levels.capacity() * core::mem::size_of::<Vec<Digest>>() + digests * core::mem::size_of::<Digest>()
+    }
+
+    pub fn root(&self) -> Digest {
+        match self.levels.last().unwrap().first() {
+            Some(root) => *root,
+            None => sha256(b""),
+        }
+    }
+
+    // Number of levels above the leaves
+    pub fn height(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    // Sibling hashes from the leaf up to (not including) the root
+    pub fn prove(&self, index: usize) -> Option<InclusionProof> {
+        if index >= self.len() {
+            return None;
+        }
+        let mut siblings = Vec::new();
+        let mut position = index;
+        for level in &self.levels[..self.levels.len() - 1] {
+            let sibling = position ^ 1;
+            if sibling < level.len() {
+                siblings.push(level[sibling]);
+            }
+            position /= 2;
+        }
+        Some(InclusionProof { index, leaf_count: self.len(), siblings })
+    }
+
+    // Replace one block and rehash the path above it
+    pub fn update(&mut self, index: usize, block: &[u8]) {
+        let mut position = index;
+        self.levels[0][position] = hash_leaf(block);
+        for depth in 1..self.levels.len() {
+            let below = &self.levels[depth - 1];
+            let first = position & !1;
+            let hash = match below.get(first + 1) {
+                Some(right) => hash_node(&below[first], right),
+                None => below[first],
+            };
+            position /= 2;
+            self.levels[depth][position] = hash;
+        }
+    }
+}
+
+// Check that `block` is leaf `proof.index` of a tree with this root
+pub fn verify(root: &Digest, block: &[u8], proof: &InclusionProof) -> bool {
+    if proof.index >= proof.leaf_count {
+        return false;
+    }
+    let mut hash = hash_leaf(block);
+    let mut position = proof.index;
+    let mut width = proof.leaf_count;
+    let mut siblings = proof.siblings.iter();
+    while width > 1 {
+        if position % 2 == 1 {
+            match siblings.next() {
+                Some(left) => hash = hash_node(left, &hash),
+                None => return false,
+            }
+        } else if position + 1 < width {
+            match siblings.next() {
+                Some(right) => hash = hash_node(&hash, right),
+                None => return false,
+            }
+        } // else: the last node of an odd level, promoted without a sibling
+        position /= 2;
+        width = width.div_ceil(2);
+    }
+    siblings.next().is_none() && hash == *root
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Building a tree ===");
+    let blocks: Vec<String> = ["alice pays bob 5", "bob pays carol 2", "carol pays dave 7",
+                               "dave pays erin 1", "erin pays alice 3"]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    let tree = MerkleTree::new(&blocks);
+    println!("{} blocks, height {}", tree.len(), tree.height());
+    for (depth, level) in tree.levels.iter().enumerate() {
+        let short: Vec<String> = level.iter().map(|h| to_hex(&h[..4])).collect();
+        println!("  level {}: {}", depth, short.join(" "));
+    }
+    let root = tree.root();
+    println!("root: {}", to_hex(&root));
+
+    println!("\n=== Inclusion proofs ===");
+    for (index, block) in blocks.iter().enumerate() {
+        let proof = tree.prove(index).unwrap();
+        println!("block {} ({:?}): {} sibling hashes, verifies: {}",
+                 index, block, proof.siblings.len(), verify(&root, block.as_bytes(), &proof));
+    }
+
+    println!("\n=== Tampering is detected ===");
+    let proof = tree.prove(2).unwrap();
+    println!("altered block:            {}", verify(&root, b"carol pays dave 700", &proof));
+    println!("right block, wrong index: {}",
+             verify(&root, blocks[2].as_bytes(), &InclusionProof { index: 3, ..proof.clone() }));
+    let mut forged = proof.clone();
+    forged.siblings[0][0] ^= 1;
+    println!("altered sibling hash:     {}", verify(&root, blocks[2].as_bytes(), &forged));
+    println!("wrong leaf count:         {}",
+             verify(&root, blocks[2].as_bytes(), &InclusionProof { leaf_count: 4, ..proof.clone() }));
+    let mut short = proof.clone();
+    short.siblings.pop();
+    println!("truncated proof:          {}", verify(&root, blocks[2].as_bytes(), &short));
+    // An internal node's two children presented as if they were one leaf block
+    let mut disguised = Vec::new();
+    disguised.extend_from_slice(&tree.levels[0][0]);
+    disguised.extend_from_slice(&tree.levels[0][1]);
+    let fake = InclusionProof { index: 0, leaf_count: 3, siblings: tree.prove(0).unwrap().siblings[1..].to_vec() };
+    println!("internal node posing as a leaf: {}", verify(&root, &disguised, &fake));
+
+    println!("\n=== Updating one block ===");
+    let mut tree = tree;
+    tree.update(4, b"erin pays alice 30");
+    let mut edited = blocks.clone();
+    edited[4] = "erin pays alice 30".to_string();
+    println!("root changed: {}", tree.root() != root);
+    println!("matches a fresh build: {}", tree.root() == MerkleTree::new(&edited).root());
+
+    println!("\n=== Proof size grows with log n ===");
+    for &n in [1usize, 2, 3, 1000, 100_000].iter() {
+        let blocks: Vec<Vec<u8>> = (0..n).map(|i| (i as u64).to_le_bytes().to_vec()).collect();
+        let tree = MerkleTree::new(&blocks);
+        let mut all_verify = true;
+        for index in (0..n).step_by((n / 50).max(1)) {
+            let proof = tree.prove(index).unwrap();
+            all_verify &= verify(&tree.root(), &blocks[index], &proof);
+        }
+        let proof = tree.prove(n - 1).unwrap();
+        println!("{:>7} blocks: height {:>2}, proof of last block {:>2} hashes ({:>3} bytes), sampled proofs verify: {}",
+                 n, tree.height(), proof.siblings.len(), proof.siblings.len() * 32, all_verify);
+    }
+    let empty = MerkleTree::new::<&[u8]>(&[]);
+    println!("Empty tree root is SHA-256(\"\"): {}", empty.root() == sha256(b""));
+}
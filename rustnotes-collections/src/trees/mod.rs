@@ -0,0 +1,27 @@
+// Trees
+
+pub mod arena_binary_tree;
+pub mod balanced_binary_tree;
+pub mod binary_tree;
+#[cfg(feature = "std")]
+pub mod concurrent_avl_tree;
+pub mod error;
+pub mod kd_tree;
+pub mod merkle_tree;
+pub mod quadtree;
+pub mod scapegoat_tree;
+pub mod segment_tree;
+pub mod trie;
+
+pub use self::arena_binary_tree::ArenaTree;
+pub use self::balanced_binary_tree::AvlTree;
+pub use self::binary_tree::{BinaryTree, TreeStats};
+#[cfg(feature = "std")]
+pub use self::concurrent_avl_tree::ConcurrentAvlTree;
+pub use self::error::TreeError;
+pub use self::kd_tree::KdTree;
+pub use self::merkle_tree::MerkleTree;
+pub use self::quadtree::Quadtree;
+pub use self::scapegoat_tree::ScapegoatTree;
+pub use self::segment_tree::{LazySegmentTree, SegmentTree};
+pub use self::trie::Trie;
@@ -0,0 +1,362 @@
+// Quadtree Spatial Index
+//
+// A region quadtree divides a rectangle into four equal quadrants, and each
+// quadrant again, wherever there are too many points to keep in one list. Dense
+// areas end up finely divided and empty areas stay as single large cells, so a
+// search only has to look at the few cells near the area it cares about.
+//
+//   +-------+---+---+
+//   |       | * | * |      a cell splits once it holds more than
+//   |   *   +---+---+      LEAF_CAPACITY points
+//   |       |  *|*  |
+//   +-------+---+---+
+//   |       |       |
+//   |     * |   *   |
+//   +-------+-------+
+//
+// Design choices:
+// - Each node owns its bounds and either a list of points (leaf) or exactly four
+//   children (internal); values of any type ride along with their points
+// - Quadrants are chosen by comparing against the cell's midpoint, so a point on
+//   a dividing line goes to exactly one child (east and north win ties)
+// - Splitting stops at MAX_DEPTH so many copies of one point cannot recurse
+//   forever; such a leaf simply holds more than LEAF_CAPACITY points
+// - range() skips any cell that does not overlap the query rectangle
+// - nearest() is a depth-first branch and bound: children are visited closest
+//   first, and a cell is skipped when even its nearest edge is farther than the
+//   best point found so far
+// - render() draws the subdivision and points as ASCII art for small demos
+// - Trade-off: simple and adaptive, but a bad fit for points that are all nearly
+//   collinear or clustered in one spot; a k-d tree splits on the data instead of
+//   at fixed midpoints
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloc::vec;
+
+const LEAF_CAPACITY: usize = 4;
+const MAX_DEPTH: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    pub fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    pub fn distance_squared(&self, other: &Point) -> f64 {
+        let (dx, dy) = (self.x - other.x, self.y - other.y);
+        dx * dx + dy * dy
+    }
+}
+
+// Axis-aligned rectangle, edges included
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Rect {
+    pub fn new(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Self {
+        Rect { min: Point::new(min_x, min_y), max: Point::new(max_x, max_y) }
+    }
+
+    pub fn contains(&self, p: &Point) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn intersects(&self, other: &Rect) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x
+            && self.min.y <= other.max.y && other.min.y <= self.max.y
+    }
+
+    // Squared distance from p to the closest point of the rectangle (0 inside)
+    pub fn distance_squared(&self, p: &Point) -> f64 {
+        let dx = (self.min.x - p.x).max(0.0).max(p.x - self.max.x);
+        let dy = (self.min.y - p.y).max(0.0).max(p.y - self.max.y);
+        dx * dx + dy * dy
+    }
+
+    fn center(&self) -> Point {
+        Point::new((self.min.x + self.max.x) / 2.0, (self.min.y + self.max.y) / 2.0)
+    }
+
+    // Quadrant index for a point: bit 0 = east, bit 1 = north
+    fn quadrant(&self, p: &Point) -> usize {
+        let center = self.center();
+        (p.x >= center.x) as usize | ((p.y >= center.y) as usize) << 1
+    }
+
+    // The four quadrants, in quadrant() index order: SW, SE, NW, NE
+    fn split(&self) -> [Rect; 4] {
+        let c = self.center();
+        [
+            Rect::new(self.min.x, self.min.y, c.x, c.y),
+            Rect::new(c.x, self.min.y, self.max.x, c.y),
+            Rect::new(self.min.x, c.y, c.x, self.max.y),
+            Rect::new(c.x, c.y, self.max.x, self.max.y),
+        ]
+    }
+}
+
+struct Node<T> {
+    bounds: Rect,
+    points: Vec<(Point, T)>,
+    children: Option<Box<[Node<T>; 4]>>,
+}
+
+impl<T> Node<T> {
+    fn new(bounds: Rect) -> Self {
+        Node { bounds, points: Vec::new(), children: None }
+    }
+
+    fn insert(&mut self, point: Point, value: T, depth: usize) {
+        if let Some(children) = self.children.as_mut() {
+            let q = self.bounds.quadrant(&point);
+            children[q].insert(point, value, depth + 1);
+            return;
+        }
+        self.points.push((point, value));
+        if self.points.len() > LEAF_CAPACITY && depth < MAX_DEPTH {
+            let [sw, se, nw, ne] = self.bounds.split();
+            let mut children = Box::new([Node::new(sw), Node::new(se), Node::new(nw), Node::new(ne)]);
+            for (p, v) in self.points.drain(..) {
+                children[self.bounds.quadrant(&p)].insert(p, v, depth + 1);
+            }
+            self.children = Some(children);
+        }
+    }
+
+    fn range<'a>(&'a self, area: &Rect, found: &mut Vec<(&'a Point, &'a T)>) {
+        if !self.bounds.intersects(area) {
+            return;
+        }
+        found.extend(self.points.iter().filter(|(p, _)| area.contains(p)).map(|(p, v)| (p, v)));
+        if let Some(children) = &self.children {
+            for child in children.iter() {
+                child.range(area, found);
+            }
+        }
+    }
+
+    fn nearest<'a>(&'a self, target: &Point, best: &mut Option<(f64, &'a Point, &'a T)>) {
+        for (p, v) in &self.points {
+            let d = p.distance_squared(target);
+            if best.is_none_or(|(best_d, _, _)| d < best_d) {
+                *best = Some((d, p, v));
+            }
+        }
+        if let Some(children) = &self.children {
+            let mut order: Vec<(f64, &Node<T>)> =
+                children.iter().map(|child| (child.bounds.distance_squared(target), child)).collect();
+            order.sort_by(|a, b| a.0.total_cmp(&b.0));
+            for (cell_distance, child) in order {
+                if best.is_some_and(|(best_d, _, _)| cell_distance >= best_d) {
+                    break; // The rest are at least as far away
+                }
+                child.nearest(target, best);
+            }
+        }
+    }
+
+    fn depth(&self) -> usize {
+        match &self.children {
+            Some(children) => 1 + children.iter().map(|c| c.depth()).max().unwrap(),
+            None => 1,
+        }
+    }
+
+    fn node_count(&self) -> usize {
+        1 + self.children.as_ref().map_or(0, |children| children.iter().map(|c| c.node_count()).sum())
+    }
+
+    fn heap_size(&self) -> usize {
+        let points = self.points.capacity() * core::mem::size_of::<(Point, T)>();
+        let children = self.children.as_ref().map_or(0, |children| {
+            core::mem::size_of::<[Node<T>; 4]>() + children.iter().map(|c| c.heap_size()).sum::<usize>()
+        });
+        points + children
+    }
+}
+
+pub struct Quadtree<T> {
+    root: Node<T>,
+    len: usize,
+}
+
+impl<T> Quadtree<T> {
+    pub fn new(bounds: Rect) -> Self {
+        Quadtree { root: Node::new(bounds), len: 0 }
+    }
+
+    pub fn bounds(&self) -> Rect {
+        self.root.bounds
+    }
+
+    // Insert a point; returns false (and stores nothing) if it is out of bounds
+    pub fn insert(&mut self, point: Point, value: T) -> bool {
+        if !self.root.bounds.contains(&point) {
+            return false;
+        }
+        self.root.insert(point, value, 0);
+        self.len += 1;
+        true
+    }
+
+    // All points inside `area` (edges included), in no particular order
+    pub fn range(&self, area: &Rect) -> Vec<(&Point, &T)> {
+        let mut found = Vec::new();
+        self.root.range(area, &mut found);
+        found
+    }
+
+    pub fn nearest(&self, target: &Point) -> Option<(&Point, &T)> {
+        let mut best = None;
+        self.root.nearest(target, &mut best);
+        best.map(|(_, p, v)| (p, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    // Each node's point buffer plus the boxed groups of four children
+    pub fn heap_size(&self) -> usize {
+        self.root.heap_size()
+    }
+
+    pub fn depth(&self) -> usize {
+        self.root.depth()
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.root.node_count()
+    }
+
+    // ASCII drawing: cell borders as '-', '|' and '+', points as '*'
+    pub fn render(&self, width: usize, height: usize) -> String {
+        let bounds = self.root.bounds;
+        // Adding 0.5 before truncating rounds the non-negative offsets, without f64::round
+        let column = |x: f64| (((x - bounds.min.x) / (bounds.max.x - bounds.min.x)) * (width - 1) as f64 + 0.5) as usize;
+        // Row 0 is the top of the picture, i.e. max y
+        let row = |y: f64| (((bounds.max.y - y) / (bounds.max.y - bounds.min.y)) * (height - 1) as f64 + 0.5) as usize;
+
+        // Mark horizontal and vertical border segments; where they meet is a '+'
+        let mut horizontal = vec![vec![false; width]; height];
+        let mut vertical = vec![vec![false; width]; height];
+        let mut points = Vec::new();
+        let mut stack = vec![&self.root];
+        while let Some(node) = stack.pop() {
+            let (left, right) = (column(node.bounds.min.x), column(node.bounds.max.x));
+            let (top, bottom) = (row(node.bounds.max.y), row(node.bounds.min.y));
+            for r in [top, bottom].iter() {
+                horizontal[*r][left..=right].iter_mut().for_each(|cell| *cell = true);
+            }
+            for line in vertical[top..=bottom].iter_mut() {
+                line[left] = true;
+                line[right] = true;
+            }
+            points.extend(node.points.iter().map(|(p, _)| (row(p.y), column(p.x))));
+            if let Some(children) = &node.children {
+                stack.extend(children.iter());
+            }
+        }
+
+        let mut grid: Vec<Vec<char>> = (0..height)
+            .map(|r| {
+                (0..width)
+                    .map(|c| match (horizontal[r][c], vertical[r][c]) {
+                        (true, true) => '+',
+                        (true, false) => '-',
+                        (false, true) => '|',
+                        (false, false) => ' ',
+                    })
+                    .collect()
+            })
+            .collect();
+        for (r, c) in points {
+            grid[r][c] = '*';
+        }
+        let lines: Vec<String> = grid.into_iter().map(|line| line.into_iter().collect()).collect();
+        lines.join("\n")
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut state: u64 = 0x2545_F491_4F6C_DD1D;
+    let mut random = move || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+
+    println!("=== Subdivision ===");
+    let mut small = Quadtree::new(Rect::new(0.0, 0.0, 100.0, 100.0));
+    // A cluster in the north-east corner and a scattering elsewhere
+    for i in 0..12 {
+        small.insert(Point::new(70.0 + random() * 25.0, 70.0 + random() * 25.0), i);
+    }
+    for i in 12..20 {
+        small.insert(Point::new(random() * 100.0, random() * 60.0), i);
+    }
+    println!("{}", small.render(65, 25));
+    println!("{} points, {} nodes, depth {}", small.len(), small.node_count(), small.depth());
+    println!("Out of bounds insert accepted: {}", small.insert(Point::new(150.0, 50.0), 99));
+
+    println!("\n=== Range and nearest-neighbor queries ===");
+    let area = Rect::new(60.0, 60.0, 100.0, 100.0);
+    let mut in_area: Vec<i32> = small.range(&area).into_iter().map(|(_, &id)| id).collect();
+    in_area.sort();
+    println!("Points in [60,100]x[60,100]: {:?}", in_area);
+    let target = Point::new(50.0, 50.0);
+    let (p, id) = small.nearest(&target).unwrap();
+    println!("Nearest to (50, 50): point {} at ({:.1}, {:.1})", id, p.x, p.y);
+
+    println!("\n=== Checking against brute force ===");
+    let mut points = Vec::new();
+    let mut tree = Quadtree::new(Rect::new(-1000.0, -1000.0, 1000.0, 1000.0));
+    for id in 0..20_000 {
+        let p = Point::new(random() * 2000.0 - 1000.0, random() * 2000.0 - 1000.0);
+        tree.insert(p, id);
+        points.push(p);
+    }
+    println!("{} points, {} nodes, depth {}", tree.len(), tree.node_count(), tree.depth());
+    let mut range_ok = true;
+    let mut nearest_ok = true;
+    for _ in 0..200 {
+        let (x, y) = (random() * 2000.0 - 1000.0, random() * 2000.0 - 1000.0);
+        let area = Rect::new(x, y, x + random() * 300.0, y + random() * 300.0);
+        let mut found: Vec<usize> = tree.range(&area).into_iter().map(|(_, &id)| id).collect();
+        found.sort();
+        let expected: Vec<usize> = (0..points.len()).filter(|&i| area.contains(&points[i])).collect();
+        range_ok &= found == expected;
+
+        let target = Point::new(random() * 2400.0 - 1200.0, random() * 2400.0 - 1200.0);
+        let best = points.iter().map(|p| p.distance_squared(&target)).fold(f64::INFINITY, f64::min);
+        let (found, _) = tree.nearest(&target).unwrap();
+        nearest_ok &= found.distance_squared(&target) == best;
+    }
+    println!("200 range queries match: {}", range_ok);
+    println!("200 nearest queries match (including targets outside the bounds): {}", nearest_ok);
+
+    println!("\n=== Duplicate points ===");
+    let mut dupes = Quadtree::new(Rect::new(0.0, 0.0, 1.0, 1.0));
+    for i in 0..100 {
+        dupes.insert(Point::new(0.5, 0.5), i);
+    }
+    println!("100 copies of one point: depth {} (capped by MAX_DEPTH), range finds {}",
+             dupes.depth(), dupes.range(&Rect::new(0.4, 0.4, 0.6, 0.6)).len());
+    println!("Empty tree nearest: {:?}", Quadtree::<()>::new(Rect::new(0.0, 0.0, 1.0, 1.0)).nearest(&target));
+}
@@ -0,0 +1,378 @@
+// Scapegoat Tree Implementation
+//
+// A self-balancing binary search tree that stores no balance information in its
+// nodes. Instead, when an insertion lands deeper than log_{1/α}(n), the tree walks
+// back up to find a "scapegoat" ancestor whose subtree is too lopsided and rebuilds
+// that subtree into a perfectly balanced one. Deletions trigger a full rebuild once
+// enough nodes have been removed since the last one.
+//
+// Design choices:
+// - Uses Box<Node<T>> for child pointers (heap allocation, owned references)
+// - Nodes hold only value and children - no height, color, or size fields
+// - α = 2/3 by default; smaller α keeps the tree tighter at the cost of more rebuilds.
+//   α is capped at 0.95: near 1 the depth limit log_{1/α}(n) grows without
+//   bound and the tree is allowed to degrade towards a list
+// - The depth limit's thresholds (1/α)^k are kept in a table that grows with
+//   the tree, since core has no f64::ln; an insert looks its limit up there
+// - Subtree sizes are counted on demand, only along the path of a too-deep insert
+// - Rebuilds flatten the subtree in order and rebuild it with the middle value as root
+// - Trade-off: O(log n) amortized (not worst-case) updates, but lookups are
+//   guaranteed O(log n) and the nodes are as small as in the plain BST
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use crate::trees::binary_tree::TreeStats;
+use crate::trees::error::TreeError;
+
+#[derive(Debug, Clone)]
+struct Node<T> {
+    value: T,
+    left: Option<Box<Node<T>>>,
+    right: Option<Box<Node<T>>>,
+}
+
+#[derive(Debug)]
+pub struct ScapegoatTree<T> {
+    root: Option<Box<Node<T>>>,
+    size: usize,
+    max_size: usize, // Largest size since the last full rebuild
+    alpha: f64,
+    rebuilds: usize,
+    // powers[k] = (1/α)^(k+1), up to the first one past the largest n asked about
+    powers: Vec<f64>,
+}
+
+// Outcome of a recursive insert, reported back up the search path
+enum Insertion {
+    Duplicate,
+    Done,
+    // The new node is too deep; carries the size of the subtree just returned from
+    TooDeep(usize),
+}
+
+impl<T: Ord> Default for ScapegoatTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Ord> ScapegoatTree<T> {
+    pub fn new() -> Self {
+        Self::with_alpha(2.0 / 3.0)
+    }
+
+    // Alpha must lie in (0.5, 0.95]: 0.5 demands perfect balance, and the
+    // closer to 1.0 the less often the tree rebuilds
+    pub fn with_alpha(alpha: f64) -> Self {
+        Self::try_with_alpha(alpha).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    // For an alpha that comes from user input or a config file
+    pub fn try_with_alpha(alpha: f64) -> Result<Self, TreeError> {
+        if !(alpha > 0.5 && alpha <= 0.95) {
+            return Err(TreeError::InvalidAlpha(alpha));
+        }
+        Ok(Self {
+            root: None,
+            size: 0,
+            max_size: 0,
+            alpha,
+            rebuilds: 0,
+            powers: Vec::new(),
+        })
+    }
+
+    // Deepest depth allowed for n nodes: floor(log_{1/α}(n)), the number of
+    // thresholds at most n; the table only grows when n passes its last entry
+    fn depth_limit(&mut self, n: usize) -> usize {
+        let base = 1.0 / self.alpha;
+        while self.powers.last().is_none_or(|&last| last <= n as f64) {
+            let next = self.powers.last().map_or(base, |&last| last * base);
+            self.powers.push(next);
+        }
+        self.powers.partition_point(|&power| power <= n as f64)
+    }
+
+    pub fn insert(&mut self, value: T) -> bool {
+        let limit = self.depth_limit(self.size + 1);
+        let alpha = self.alpha;
+        let mut rebuilds = 0;
+        let result = Self::insert_node(&mut self.root, value, 0, limit, alpha, &mut rebuilds);
+        self.rebuilds += rebuilds;
+
+        match result {
+            Insertion::Duplicate => false,
+            // With α > 0.5 some ancestor is always a scapegoat, so TooDeep
+            // never actually reaches the root
+            Insertion::Done | Insertion::TooDeep(_) => {
+                self.size += 1;
+                self.max_size = self.max_size.max(self.size);
+                true
+            }
+        }
+    }
+
+    fn insert_node(
+        link: &mut Option<Box<Node<T>>>,
+        value: T,
+        depth: usize,
+        limit: usize,
+        alpha: f64,
+        rebuilds: &mut usize,
+    ) -> Insertion {
+        let node = match link {
+            None => {
+                *link = Some(Box::new(Node {
+                    value,
+                    left: None,
+                    right: None,
+                }));
+                return if depth > limit { Insertion::TooDeep(1) } else { Insertion::Done };
+            }
+            Some(node) => node,
+        };
+
+        let went_left = match value.cmp(&node.value) {
+            core::cmp::Ordering::Equal => return Insertion::Duplicate,
+            core::cmp::Ordering::Less => true,
+            core::cmp::Ordering::Greater => false,
+        };
+        let result = if went_left {
+            Self::insert_node(&mut node.left, value, depth + 1, limit, alpha, rebuilds)
+        } else {
+            Self::insert_node(&mut node.right, value, depth + 1, limit, alpha, rebuilds)
+        };
+
+        match result {
+            Insertion::TooDeep(child_size) => {
+                let sibling = if went_left { &node.right } else { &node.left };
+                let total = child_size + Self::count_nodes(sibling) + 1;
+                if child_size as f64 > alpha * total as f64 {
+                    // This node is the scapegoat: rebuild its whole subtree
+                    *link = Self::rebuild(link.take(), total);
+                    *rebuilds += 1;
+                    Insertion::Done
+                } else {
+                    Insertion::TooDeep(total)
+                }
+            }
+            other => other,
+        }
+    }
+
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (new_root, removed) = Self::remove_node(self.root.take(), value);
+        self.root = new_root;
+        if !removed {
+            return false;
+        }
+
+        self.size -= 1;
+        if (self.size as f64) < self.alpha * self.max_size as f64 {
+            self.root = Self::rebuild(self.root.take(), self.size);
+            self.max_size = self.size;
+            self.rebuilds += 1;
+        }
+        true
+    }
+
+    fn remove_node(node: Option<Box<Node<T>>>, value: &T) -> (Option<Box<Node<T>>>, bool) {
+        match node {
+            None => (None, false),
+            Some(mut n) => match value.cmp(&n.value) {
+                core::cmp::Ordering::Less => {
+                    let (new_left, removed) = Self::remove_node(n.left.take(), value);
+                    n.left = new_left;
+                    (Some(n), removed)
+                }
+                core::cmp::Ordering::Greater => {
+                    let (new_right, removed) = Self::remove_node(n.right.take(), value);
+                    n.right = new_right;
+                    (Some(n), removed)
+                }
+                core::cmp::Ordering::Equal => match (n.left.take(), n.right.take()) {
+                    (None, None) => (None, true),
+                    (Some(left), None) => (Some(left), true),
+                    (None, Some(right)) => (Some(right), true),
+                    (Some(left), Some(right)) => {
+                        let (min_value, new_right) = Self::extract_min(right);
+                        let new_node = Box::new(Node {
+                            value: min_value,
+                            left: Some(left),
+                            right: new_right,
+                        });
+                        (Some(new_node), true)
+                    }
+                },
+            },
+        }
+    }
+
+    fn extract_min(mut node: Box<Node<T>>) -> (T, Option<Box<Node<T>>>) {
+        match node.left.take() {
+            None => (node.value, node.right),
+            Some(left) => {
+                let (min_value, new_left) = Self::extract_min(left);
+                node.left = new_left;
+                (min_value, Some(node))
+            }
+        }
+    }
+
+    // Flatten a subtree of `count` nodes and rebuild it perfectly balanced
+    fn rebuild(node: Option<Box<Node<T>>>, count: usize) -> Option<Box<Node<T>>> {
+        trace!("rebuild a subtree of {} nodes", count);
+        let mut values = Vec::with_capacity(count);
+        Self::flatten(node, &mut values);
+        let len = values.len();
+        Self::build_balanced(&mut values.into_iter(), len)
+    }
+
+    fn flatten(node: Option<Box<Node<T>>>, out: &mut Vec<T>) {
+        if let Some(n) = node {
+            let Node { value, left, right } = *n;
+            Self::flatten(left, out);
+            out.push(value);
+            Self::flatten(right, out);
+        }
+    }
+
+    // Consume `count` values from a sorted iterator, building left subtree, root, right subtree
+    fn build_balanced<I: Iterator<Item = T>>(values: &mut I, count: usize) -> Option<Box<Node<T>>> {
+        if count == 0 {
+            return None;
+        }
+        let left_count = count / 2;
+        let left = Self::build_balanced(values, left_count);
+        let value = values.next().expect("rebuild consumed more values than counted");
+        let right = Self::build_balanced(values, count - left_count - 1);
+        Some(Box::new(Node { value, left, right }))
+    }
+
+    fn count_nodes(node: &Option<Box<Node<T>>>) -> usize {
+        node.as_ref()
+            .map_or(0, |n| 1 + Self::count_nodes(&n.left) + Self::count_nodes(&n.right))
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let mut current = &self.root;
+        while let Some(node) = current {
+            match value.cmp(&node.value) {
+                core::cmp::Ordering::Equal => return true,
+                core::cmp::Ordering::Less => current = &node.left,
+                core::cmp::Ordering::Greater => current = &node.right,
+            }
+        }
+        false
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // One boxed node per element
+    pub fn heap_size(&self) -> usize {
+        self.size * core::mem::size_of::<Node<T>>()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = None;
+        self.size = 0;
+        self.max_size = 0;
+    }
+
+    // In-order (ascending) iteration
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter { stack: Vec::new() };
+        iter.push_left_spine(&self.root);
+        iter
+    }
+
+    pub fn height(&self) -> usize {
+        Self::node_height(&self.root)
+    }
+
+    fn node_height(node: &Option<Box<Node<T>>>) -> usize {
+        node.as_ref()
+            .map_or(0, |n| 1 + Self::node_height(&n.left).max(Self::node_height(&n.right)))
+    }
+
+    // Number of subtree rebuilds performed so far
+    pub fn rebuild_count(&self) -> usize {
+        self.rebuilds
+    }
+
+    // Gather shape statistics (level widths, depths, balance factors) in one pass
+    pub fn stats(&self) -> TreeStats {
+        TreeStats::collect(self.root.as_deref(), |n| (n.left.as_deref(), n.right.as_deref()))
+    }
+}
+
+pub struct Iter<'a, T> {
+    stack: Vec<&'a Node<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, mut node: &'a Option<Box<Node<T>>>) {
+        while let Some(n) = node {
+            self.stack.push(n);
+            node = &n.left;
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.push_left_spine(&node.right);
+        Some(&node.value)
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut tree = ScapegoatTree::new();
+
+    println!("=== Scapegoat Tree Test: Insert 1-100 in order ===");
+    for i in 1..=100 {
+        tree.insert(i);
+        if i % 20 == 0 {
+            println!("After inserting {}: height {}, rebuilds so far {}",
+                     i, tree.height(), tree.rebuild_count());
+        }
+    }
+    println!("Depth limit for 100 nodes: {}", tree.depth_limit(100));
+    println!("Insert duplicate 50: {}", tree.insert(50));
+    println!("Length: {}", tree.len());
+
+    println!("\n=== Stats after sequential inserts ===");
+    println!("{}", tree.stats());
+
+    println!("\n=== Remove 1-60 ===");
+    for i in 1..=60 {
+        tree.remove(&i);
+    }
+    println!("After removals: {} nodes, height {}, rebuilds {}",
+             tree.len(), tree.height(), tree.rebuild_count());
+    println!("Contains 30: {}", tree.contains(&30));
+    println!("Contains 75: {}", tree.contains(&75));
+    println!("Remove missing 30: {}", tree.remove(&30));
+    println!("First five in order: {:?}", tree.iter().take(5).collect::<Vec<_>>());
+
+    println!("\n=== Tighter alpha (0.55) on the same input ===");
+    let mut tight = ScapegoatTree::with_alpha(0.55);
+    for i in 1..=100 {
+        tight.insert(i);
+    }
+    println!("Height {}, rebuilds {}", tight.height(), tight.rebuild_count());
+
+    println!("\n=== Clear ===");
+    tree.clear();
+    println!("Length after clear: {}, is empty: {}", tree.len(), tree.is_empty());
+}
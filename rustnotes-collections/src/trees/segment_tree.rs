@@ -0,0 +1,387 @@
+// Segment Tree Implementation
+//
+// Answers "combine everything in positions l..r" (sum, minimum, maximum, gcd, ...)
+// in O(log n) while still allowing the underlying values to change. Each internal
+// node stores the combined value of a contiguous segment; a query stitches
+// together O(log n) precomputed segments, and an update only recomputes the
+// nodes on the path from one leaf to the root.
+//
+// Two flavors:
+// - SegmentTree<T, F>: generic over the value type and the combine function,
+//   with point updates
+// - LazySegmentTree: i64 values with range updates ("add 5 to every element in
+//   l..r") using lazy propagation, tracking sum, min and max together
+//
+// Design choices:
+// - SegmentTree is the compact iterative layout: 2n slots, leaves at n..2n, node
+//   i's children at 2i and 2i+1 - no recursion and no padding to a power of two
+// - combine must be associative with `identity` as its neutral element; it need
+//   not be commutative (queries keep left and right partial results separate)
+// - Ranges are anything implementing RangeBounds<usize> (2..5, ..=3, ..)
+// - Out-of-range positions panic, like slice indexing; the try_ versions
+//   return a TreeError instead
+// - LazySegmentTree defers a range add at the highest nodes that cover it and
+//   pushes the pending amount down only when a later operation needs to look
+//   inside those nodes, so range updates are O(log n) as well
+// - Trade-off: fixed length after construction, and O(n) extra memory; for plain
+//   prefix sums without updates a prefix-sum array is simpler and faster
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::{Bound, RangeBounds};
+use crate::trees::error::TreeError;
+
+// Convert any range over 0..len into half-open [start, end)
+fn resolve_range<R: RangeBounds<usize>>(range: R, len: usize) -> Result<(usize, usize), TreeError> {
+    let start = match range.start_bound() {
+        Bound::Included(&s) => s,
+        Bound::Excluded(&s) => s + 1,
+        Bound::Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Bound::Included(&e) => e + 1,
+        Bound::Excluded(&e) => e,
+        Bound::Unbounded => len,
+    };
+    if start > end || end > len {
+        return Err(TreeError::RangeOutOfBounds { start, end, len });
+    }
+    Ok((start, end))
+}
+
+fn check_index(index: usize, len: usize) -> Result<(), TreeError> {
+    if index < len {
+        Ok(())
+    } else {
+        Err(TreeError::IndexOutOfBounds { index, len })
+    }
+}
+
+pub struct SegmentTree<T, F> {
+    len: usize,
+    tree: Vec<T>, // tree[len..2*len] are the leaves; tree[0] is unused
+    identity: T,
+    combine: F,
+}
+
+impl<T: Clone, F: Fn(&T, &T) -> T> SegmentTree<T, F> {
+    pub fn new(values: &[T], identity: T, combine: F) -> Self {
+        let len = values.len();
+        let mut tree = vec![identity.clone(); 2 * len];
+        tree[len..].clone_from_slice(values);
+        for i in (1..len).rev() {
+            tree[i] = combine(&tree[2 * i], &tree[2 * i + 1]);
+        }
+        Self { len, tree, identity, combine }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.tree.capacity() * core::mem::size_of::<T>()
+    }
+
+    pub fn get(&self, index: usize) -> &T {
+        self.try_get(index).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_get(&self, index: usize) -> Result<&T, TreeError> {
+        check_index(index, self.len)?;
+        Ok(&self.tree[self.len + index])
+    }
+
+    // Replace one value and recompute its ancestors
+    pub fn update(&mut self, index: usize, value: T) {
+        self.try_update(index, value).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_update(&mut self, index: usize, value: T) -> Result<(), TreeError> {
+        check_index(index, self.len)?;
+        let mut i = self.len + index;
+        self.tree[i] = value;
+        while i > 1 {
+            i /= 2;
+            self.tree[i] = (self.combine)(&self.tree[2 * i], &self.tree[2 * i + 1]);
+        }
+        Ok(())
+    }
+
+    // Combine of all values in `range`, left to right (identity if empty)
+    pub fn query<R: RangeBounds<usize>>(&self, range: R) -> T {
+        self.try_query(range).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_query<R: RangeBounds<usize>>(&self, range: R) -> Result<T, TreeError> {
+        let (start, end) = resolve_range(range, self.len)?;
+        let mut left_result = self.identity.clone();
+        let mut right_result = self.identity.clone();
+        let (mut l, mut r) = (start + self.len, end + self.len);
+        // Climb from both ends; when the left boundary is a right child (l odd) or
+        // the right boundary is a left child (r - 1 even), its parent would stick out
+        // of the range, so that node is taken on its own and the boundary moves in
+        while l < r {
+            if l % 2 == 1 {
+                left_result = (self.combine)(&left_result, &self.tree[l]);
+                l += 1;
+            }
+            if r % 2 == 1 {
+                r -= 1;
+                right_result = (self.combine)(&self.tree[r], &right_result);
+            }
+            l /= 2;
+            r /= 2;
+        }
+        Ok((self.combine)(&left_result, &right_result))
+    }
+}
+
+// Sum, minimum and maximum of a segment
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Summary {
+    pub sum: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+impl Summary {
+    fn leaf(value: i64) -> Self {
+        Summary { sum: value, min: value, max: value }
+    }
+
+    fn merge(&self, other: &Summary) -> Summary {
+        Summary {
+            sum: self.sum + other.sum,
+            min: self.min.min(other.min),
+            max: self.max.max(other.max),
+        }
+    }
+}
+
+pub struct LazySegmentTree {
+    len: usize,
+    nodes: Vec<Summary>, // Recursive layout: root 1, children 2i and 2i+1
+    pending: Vec<i64>,   // Amount still to be added to every element below a node
+}
+
+impl LazySegmentTree {
+    pub fn new(values: &[i64]) -> Self {
+        let len = values.len();
+        let slots = 4 * len.max(1);
+        let mut tree = Self {
+            len,
+            nodes: vec![Summary::leaf(0); slots],
+            pending: vec![0; slots],
+        };
+        if len > 0 {
+            tree.build(1, 0, len, values);
+        }
+        tree
+    }
+
+    fn build(&mut self, node: usize, lo: usize, hi: usize, values: &[i64]) {
+        if hi - lo == 1 {
+            self.nodes[node] = Summary::leaf(values[lo]);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.build(2 * node, lo, mid, values);
+        self.build(2 * node + 1, mid, hi, values);
+        self.nodes[node] = self.nodes[2 * node].merge(&self.nodes[2 * node + 1]);
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn heap_size(&self) -> usize {
+        self.nodes.capacity() * core::mem::size_of::<Summary>() + self.pending.capacity() * core::mem::size_of::<i64>()
+    }
+
+    // Add delta to every element of the segment [lo, hi) rooted at node
+    fn apply(&mut self, node: usize, lo: usize, hi: usize, delta: i64) {
+        let summary = &mut self.nodes[node];
+        summary.sum += delta * (hi - lo) as i64;
+        summary.min += delta;
+        summary.max += delta;
+        self.pending[node] += delta;
+    }
+
+    // Hand a node's pending add to its children before looking inside it
+    fn push_down(&mut self, node: usize, lo: usize, mid: usize, hi: usize) {
+        let delta = core::mem::replace(&mut self.pending[node], 0);
+        if delta != 0 {
+            self.apply(2 * node, lo, mid, delta);
+            self.apply(2 * node + 1, mid, hi, delta);
+        }
+    }
+
+    pub fn range_add<R: RangeBounds<usize>>(&mut self, range: R, delta: i64) {
+        self.try_range_add(range, delta).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_range_add<R: RangeBounds<usize>>(&mut self, range: R, delta: i64) -> Result<(), TreeError> {
+        let (start, end) = resolve_range(range, self.len)?;
+        if start < end {
+            self.add_in(1, 0, self.len, start, end, delta);
+        }
+        Ok(())
+    }
+
+    fn add_in(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize, delta: i64) {
+        if end <= lo || hi <= start {
+            return;
+        }
+        if start <= lo && hi <= end {
+            self.apply(node, lo, hi, delta);
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        self.push_down(node, lo, mid, hi);
+        self.add_in(2 * node, lo, mid, start, end, delta);
+        self.add_in(2 * node + 1, mid, hi, start, end, delta);
+        self.nodes[node] = self.nodes[2 * node].merge(&self.nodes[2 * node + 1]);
+    }
+
+    // Sum, min and max over `range`, or None if the range is empty
+    pub fn query<R: RangeBounds<usize>>(&mut self, range: R) -> Option<Summary> {
+        self.try_query(range).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_query<R: RangeBounds<usize>>(&mut self, range: R) -> Result<Option<Summary>, TreeError> {
+        let (start, end) = resolve_range(range, self.len)?;
+        if start == end {
+            return Ok(None);
+        }
+        Ok(Some(self.query_in(1, 0, self.len, start, end)))
+    }
+
+    // Takes &mut self because pending adds are pushed down on the way
+    fn query_in(&mut self, node: usize, lo: usize, hi: usize, start: usize, end: usize) -> Summary {
+        if start <= lo && hi <= end {
+            return self.nodes[node];
+        }
+        let mid = (lo + hi) / 2;
+        self.push_down(node, lo, mid, hi);
+        if end <= mid {
+            self.query_in(2 * node, lo, mid, start, end)
+        } else if start >= mid {
+            self.query_in(2 * node + 1, mid, hi, start, end)
+        } else {
+            let left = self.query_in(2 * node, lo, mid, start, end);
+            let right = self.query_in(2 * node + 1, mid, hi, start, end);
+            left.merge(&right)
+        }
+    }
+
+    pub fn get(&mut self, index: usize) -> i64 {
+        self.try_get(index).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn try_get(&mut self, index: usize) -> Result<i64, TreeError> {
+        check_index(index, self.len)?;
+        Ok(self.query_in(1, 0, self.len, index, index + 1).sum)
+    }
+}
+
+#[cfg(feature = "std")]
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    println!("=== Range sum, min and max ===");
+    let values = [5i64, 2, 8, 1, 9, 3, 7, 4];
+    println!("Values: {:?}", values);
+    let mut sums = SegmentTree::new(&values, 0, |a, b| a + b);
+    let mins = SegmentTree::new(&values, i64::MAX, |a: &i64, b: &i64| *a.min(b));
+    let maxes = SegmentTree::new(&values, i64::MIN, |a: &i64, b: &i64| *a.max(b));
+    println!("sum(2..6) = {}, min(2..6) = {}, max(2..6) = {}",
+             sums.query(2..6), mins.query(2..6), maxes.query(2..6));
+    println!("sum(..) = {}, sum(..=0) = {}, sum(3..3) = {}",
+             sums.query(..), sums.query(..=0), sums.query(3..3));
+    sums.update(3, 10);
+    println!("After update(3, 10): get(3) = {}, sum(2..6) = {}", sums.get(3), sums.query(2..6));
+
+    println!("\n=== Any associative combine ===");
+    let numbers = [12u64, 18, 24, 36, 7, 14, 21];
+    let gcds = SegmentTree::new(&numbers, 0, |a, b| gcd(*a, *b));
+    println!("gcd of {:?}: first four {}, last three {}", numbers, gcds.query(..4), gcds.query(4..));
+
+    // Concatenation is associative but not commutative, so order matters
+    let words: Vec<String> = "the quick brown fox jumps".split(' ').map(String::from).collect();
+    let mut text = SegmentTree::new(&words, String::new(), |a, b| format!("{}{}", a, b));
+    println!("concat(1..4) = {:?}", text.query(1..4));
+    text.update(2, "red".to_string());
+    println!("after update(2, \"red\"): concat(..) = {:?}", text.query(..));
+
+    println!("\n=== Lazy propagation: range add ===");
+    let mut lazy = LazySegmentTree::new(&values);
+    println!("Before: {:?}", lazy.query(..));
+    lazy.range_add(2..6, 10);
+    println!("After adding 10 to 2..6: {:?}", lazy.query(..));
+    println!("Query 0..3: {:?}", lazy.query(0..3));
+    lazy.range_add(..=1, -5);
+    let current: Vec<i64> = (0..lazy.len()).map(|i| lazy.get(i)).collect();
+    println!("After adding -5 to ..=1: {:?}", current);
+    println!("Empty range: {:?}", lazy.query(4..4));
+
+    println!("\n=== Randomized check against brute force ===");
+    let n = 1000;
+    let mut state: u64 = 0xDEAD_BEEF_1234_5678;
+    let mut next = move |bound: u64| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state % bound
+    };
+    let mut plain: Vec<i64> = (0..n).map(|_| next(1000) as i64 - 500).collect();
+    let mut point_tree = SegmentTree::new(&plain, i64::MAX, |a: &i64, b: &i64| *a.min(b));
+    let mut lazy_tree = LazySegmentTree::new(&plain);
+    let mut all_ok = true;
+    for _ in 0..5000 {
+        let a = next(n as u64) as usize;
+        let b = next(n as u64) as usize;
+        let (l, r) = (a.min(b), a.max(b) + 1);
+        match next(3) {
+            0 => {
+                let value = next(1000) as i64 - 500;
+                plain[a] = value;
+                point_tree.update(a, value);
+                let delta = value - lazy_tree.get(a);
+                lazy_tree.range_add(a..=a, delta);
+            }
+            1 => {
+                let delta = next(100) as i64 - 50;
+                for x in &mut plain[l..r] {
+                    *x += delta;
+                }
+                lazy_tree.range_add(l..r, delta);
+                for (i, &x) in plain.iter().enumerate().take(r).skip(l) {
+                    point_tree.update(i, x);
+                }
+            }
+            _ => {
+                let slice = &plain[l..r];
+                let expected = Summary {
+                    sum: slice.iter().sum(),
+                    min: *slice.iter().min().unwrap(),
+                    max: *slice.iter().max().unwrap(),
+                };
+                all_ok &= lazy_tree.query(l..r) == Some(expected);
+                all_ok &= point_tree.query(l..r) == expected.min;
+            }
+        }
+    }
+    println!("5000 random updates and queries on {} elements all match: {}", n, all_ok);
+}
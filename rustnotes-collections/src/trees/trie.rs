@@ -0,0 +1,239 @@
+// Trie (Prefix Tree) Implementation
+//
+// Stores a set of strings by sharing their common prefixes: each edge is labelled
+// with one character, and a word is the path from the root to a node marked as the
+// end of a word. Lookups cost O(length of the word) no matter how many words are
+// stored, and every word sharing a prefix lives under the same subtree.
+//
+// Design choices:
+// - Each node keeps its children in a BTreeMap<char, Node>, so traversal visits
+//   words in lexicographic order and sparse alphabets (Unicode) cost nothing extra
+// - Nodes are owned directly by their parent's map - no Box or Rc needed
+// - remove() prunes nodes that no longer lead to any word, keeping the trie minimal
+// - words_with_prefix() returns a lazy iterator driven by an explicit stack
+// - Trade-off: fast prefix queries and ordered output, but each character costs a
+//   map entry, so memory use is far higher than storing the strings flat
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+#[derive(Debug, Default, Clone)]
+struct Node {
+    children: BTreeMap<char, Node>,
+    is_word: bool,
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct Trie {
+    root: Node,
+    size: usize,
+}
+
+impl Trie {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // Insert a word, returning false if it was already present
+    pub fn insert(&mut self, word: &str) -> bool {
+        let mut node = &mut self.root;
+        for ch in word.chars() {
+            node = node.children.entry(ch).or_default();
+        }
+        if node.is_word {
+            return false;
+        }
+        node.is_word = true;
+        self.size += 1;
+        true
+    }
+
+    // Follow the path spelled by `prefix`, if it exists
+    fn find_node(&self, prefix: &str) -> Option<&Node> {
+        let mut node = &self.root;
+        for ch in prefix.chars() {
+            node = node.children.get(&ch)?;
+        }
+        Some(node)
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.find_node(word).is_some_and(|node| node.is_word)
+    }
+
+    // Check whether any stored word begins with `prefix`. Removal prunes dead
+    // branches, so only the root can be a node with no word below it.
+    pub fn starts_with(&self, prefix: &str) -> bool {
+        self.find_node(prefix).is_some_and(|node| node.is_word || !node.children.is_empty())
+    }
+
+    // Remove a word, pruning branches that no longer lead to any word
+    pub fn remove(&mut self, word: &str) -> bool {
+        let chars: Vec<char> = word.chars().collect();
+        let removed = Self::remove_from(&mut self.root, &chars);
+        if removed {
+            self.size -= 1;
+        }
+        removed
+    }
+
+    fn remove_from(node: &mut Node, chars: &[char]) -> bool {
+        match chars.split_first() {
+            None => {
+                let was_word = node.is_word;
+                node.is_word = false;
+                was_word
+            }
+            Some((ch, rest)) => {
+                let child = match node.children.get_mut(ch) {
+                    Some(child) => child,
+                    None => return false,
+                };
+                let removed = Self::remove_from(child, rest);
+                if removed && !child.is_word && child.children.is_empty() {
+                    node.children.remove(ch);
+                }
+                removed
+            }
+        }
+    }
+
+    // Lazily iterate, in lexicographic order, over every word starting with `prefix`
+    pub fn words_with_prefix(&self, prefix: &str) -> WordsWithPrefix<'_> {
+        let stack = match self.find_node(prefix) {
+            Some(node) => vec![(node, prefix.to_string())],
+            None => Vec::new(),
+        };
+        WordsWithPrefix { stack }
+    }
+
+    // All words in lexicographic order
+    pub fn iter(&self) -> WordsWithPrefix<'_> {
+        self.words_with_prefix("")
+    }
+
+    // Longest prefix shared by every stored word: walk down while there is
+    // exactly one way to continue and no word ends along the way
+    pub fn longest_common_prefix(&self) -> String {
+        let mut prefix = String::new();
+        let mut node = &self.root;
+        while !node.is_word && node.children.len() == 1 {
+            let (&ch, child) = node.children.iter().next().unwrap();
+            prefix.push(ch);
+            node = child;
+        }
+        prefix
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    // An estimate: each node other than the root is one (char, Node) entry in its
+    // parent's BTreeMap; the B-tree's own node headers and spare slots are private
+    // to std and not counted
+    pub fn heap_size(&self) -> usize {
+        (self.node_count() - 1) * core::mem::size_of::<(char, Node)>()
+    }
+
+    pub fn clear(&mut self) {
+        self.root = Node::default();
+        self.size = 0;
+    }
+
+    // Total number of nodes, including the root (for comparing memory use)
+    pub fn node_count(&self) -> usize {
+        fn count(node: &Node) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+        count(&self.root)
+    }
+}
+
+// Depth-first walk; the stack holds nodes still to visit along with the word
+// spelled on the way to them. Children are pushed in reverse so the smallest
+// character is visited first.
+pub struct WordsWithPrefix<'a> {
+    stack: Vec<(&'a Node, String)>,
+}
+
+impl<'a> Iterator for WordsWithPrefix<'a> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        while let Some((node, word)) = self.stack.pop() {
+            for (&ch, child) in node.children.iter().rev() {
+                let mut next_word = word.clone();
+                next_word.push(ch);
+                self.stack.push((child, next_word));
+            }
+            if node.is_word {
+                return Some(word);
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn demo() {
+    let mut trie = Trie::new();
+
+    println!("=== Insert and lookup ===");
+    for word in ["car", "card", "care", "careful", "cart", "cat", "dog", "do"].iter() {
+        trie.insert(word);
+    }
+    println!("Insert duplicate 'car': {}", trie.insert("car"));
+    println!("Words: {}, nodes: {}", trie.len(), trie.node_count());
+    println!("Contains 'care': {}", trie.contains("care"));
+    println!("Contains 'ca' (prefix only): {}", trie.contains("ca"));
+    println!("Starts with 'ca': {}", trie.starts_with("ca"));
+    println!("Starts with 'cow': {}", trie.starts_with("cow"));
+
+    println!("\n=== Prefix iteration ===");
+    println!("All words: {:?}", trie.iter().collect::<Vec<_>>());
+    println!("Prefix 'car': {:?}", trie.words_with_prefix("car").collect::<Vec<_>>());
+    println!("Prefix 'do': {:?}", trie.words_with_prefix("do").collect::<Vec<_>>());
+    println!("Prefix 'x': {:?}", trie.words_with_prefix("x").collect::<Vec<_>>());
+    println!("First two 'ca' words: {:?}", trie.words_with_prefix("ca").take(2).collect::<Vec<_>>());
+
+    println!("\n=== Remove with pruning ===");
+    println!("Remove 'careful': {}", trie.remove("careful"));
+    println!("Remove 'careful' again: {}", trie.remove("careful"));
+    println!("Remove 'ca' (not a word): {}", trie.remove("ca"));
+    println!("Contains 'care' still: {}", trie.contains("care"));
+    println!("Words: {}, nodes after pruning: {}", trie.len(), trie.node_count());
+
+    println!("\n=== Longest common prefix ===");
+    let mut flowers = Trie::new();
+    for word in ["flower", "flow", "flight"].iter() {
+        flowers.insert(word);
+    }
+    println!("flower, flow, flight -> {:?}", flowers.longest_common_prefix());
+    let mut interns = Trie::new();
+    for word in ["interview", "internet", "internal", "interval"].iter() {
+        interns.insert(word);
+    }
+    println!("interview, internet, internal, interval -> {:?}", interns.longest_common_prefix());
+    interns.insert("in");
+    println!("... plus 'in' -> {:?}", interns.longest_common_prefix());
+    println!("Mixed trie -> {:?}", trie.longest_common_prefix());
+
+    println!("\n=== Unicode ===");
+    let mut words = Trie::new();
+    for word in ["naïve", "naïveté", "日本", "日本語"].iter() {
+        words.insert(word);
+    }
+    println!("Prefix '日': {:?}", words.words_with_prefix("日").collect::<Vec<_>>());
+    println!("Prefix 'naï': {:?}", words.words_with_prefix("naï").collect::<Vec<_>>());
+
+    trie.clear();
+    println!("\nAfter clear: length {}, is empty: {}, nodes: {}",
+             trie.len(), trie.is_empty(), trie.node_count());
+}
@@ -0,0 +1,167 @@
+// Model tests for the hash maps and the LRU cache
+//
+// The two hash maps run the same random operations as a std BTreeMap and must
+// agree on every answer, on their length, and on their contents once sorted.
+// The LRU cache is checked against a Vec of (key, value) pairs kept in
+// recency order, most recent first, trimmed to the capacity after each put.
+//
+// Design choices:
+// - Keys are u8, so collisions, tombstones and updates of existing keys all
+//   come up within a few hundred operations
+// - The maps have no shared trait in the crate, so a local TestMap trait
+//   (implemented by a macro) lets one check cover both collision strategies
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::hashing::hash_map_chaining::HashMapChained;
+use rustnotes_collections::hashing::hash_map_open_addressing::HashMapOa;
+use rustnotes_collections::hashing::lru_cache::LruCache;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone)]
+enum MapOp {
+    Insert(u8, u32),
+    Remove(u8),
+    Get(u8),
+    Clear,
+}
+
+fn map_op() -> impl Strategy<Value = MapOp> {
+    prop_oneof![
+        6 => (any::<u8>(), any::<u32>()).prop_map(|(key, value)| MapOp::Insert(key, value)),
+        3 => any::<u8>().prop_map(MapOp::Remove),
+        3 => any::<u8>().prop_map(MapOp::Get),
+        1 => Just(MapOp::Clear),
+    ]
+}
+
+// The operations both hash maps share
+trait TestMap: Default {
+    fn insert(&mut self, key: u8, value: u32) -> Option<u32>;
+    fn remove(&mut self, key: &u8) -> Option<u32>;
+    fn get(&self, key: &u8) -> Option<&u32>;
+    fn contains_key(&self, key: &u8) -> bool;
+    fn len(&self) -> usize;
+    fn clear(&mut self);
+    fn sorted(&self) -> Vec<(u8, u32)>;
+}
+
+macro_rules! impl_test_map {
+    ($map:ty) => {
+        impl TestMap for $map {
+            fn insert(&mut self, key: u8, value: u32) -> Option<u32> {
+                <$map>::insert(self, key, value)
+            }
+            fn remove(&mut self, key: &u8) -> Option<u32> {
+                <$map>::remove(self, key)
+            }
+            fn get(&self, key: &u8) -> Option<&u32> {
+                <$map>::get(self, key)
+            }
+            fn contains_key(&self, key: &u8) -> bool {
+                <$map>::contains_key(self, key)
+            }
+            fn len(&self) -> usize {
+                <$map>::len(self)
+            }
+            fn clear(&mut self) {
+                <$map>::clear(self)
+            }
+            fn sorted(&self) -> Vec<(u8, u32)> {
+                let mut pairs: Vec<(u8, u32)> = self.iter().map(|(&key, &value)| (key, value)).collect();
+                pairs.sort();
+                pairs
+            }
+        }
+    };
+}
+
+impl_test_map!(HashMapOa<u8, u32>);
+impl_test_map!(HashMapChained<u8, u32>);
+
+fn check_map<M: TestMap>(ops: &[MapOp]) {
+    let mut map = M::default();
+    let mut model = BTreeMap::new();
+    for op in ops {
+        match *op {
+            MapOp::Insert(key, value) => assert_eq!(map.insert(key, value), model.insert(key, value)),
+            MapOp::Remove(key) => assert_eq!(map.remove(&key), model.remove(&key)),
+            MapOp::Get(key) => {
+                assert_eq!(map.get(&key), model.get(&key));
+                assert_eq!(map.contains_key(&key), model.contains_key(&key));
+            }
+            MapOp::Clear => {
+                map.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(map.len(), model.len());
+        assert!(map.sorted().into_iter().eq(model.iter().map(|(&key, &value)| (key, value))));
+    }
+}
+
+#[derive(Debug, Clone)]
+enum CacheOp {
+    Put(u8, u32),
+    Get(u8),
+    Peek(u8),
+    Remove(u8),
+}
+
+fn cache_op() -> impl Strategy<Value = CacheOp> {
+    // Keys from a small range, so the cache is usually full and evicting
+    prop_oneof![
+        4 => (0..16u8, any::<u32>()).prop_map(|(key, value)| CacheOp::Put(key, value)),
+        3 => (0..16u8).prop_map(CacheOp::Get),
+        1 => (0..16u8).prop_map(CacheOp::Peek),
+        1 => (0..16u8).prop_map(CacheOp::Remove),
+    ]
+}
+
+// Take a key out of the recency list, returning its value
+fn take(model: &mut Vec<(u8, u32)>, key: u8) -> Option<u32> {
+    let index = model.iter().position(|&(k, _)| k == key)?;
+    Some(model.remove(index).1)
+}
+
+proptest! {
+    #[test]
+    fn open_addressing_map_matches_btree_map(ops in prop::collection::vec(map_op(), 0..400)) {
+        check_map::<HashMapOa<u8, u32>>(&ops);
+    }
+
+    #[test]
+    fn chained_map_matches_btree_map(ops in prop::collection::vec(map_op(), 0..400)) {
+        check_map::<HashMapChained<u8, u32>>(&ops);
+    }
+
+    #[test]
+    fn lru_cache_matches_recency_list(capacity in 1..8usize, ops in prop::collection::vec(cache_op(), 0..300)) {
+        let mut cache = LruCache::new(capacity);
+        let mut model: Vec<(u8, u32)> = Vec::new();
+        for op in ops {
+            match op {
+                CacheOp::Put(key, value) => {
+                    prop_assert_eq!(cache.put(key, value), take(&mut model, key));
+                    model.insert(0, (key, value));
+                    model.truncate(capacity);
+                }
+                CacheOp::Get(key) => {
+                    let expected = take(&mut model, key);
+                    prop_assert_eq!(cache.get(&key).copied(), expected);
+                    if let Some(value) = expected {
+                        model.insert(0, (key, value));
+                    }
+                }
+                CacheOp::Peek(key) => {
+                    let expected = model.iter().find(|&&(k, _)| k == key).map(|&(_, value)| value);
+                    prop_assert_eq!(cache.peek(&key).copied(), expected);
+                }
+                CacheOp::Remove(key) => prop_assert_eq!(cache.remove(&key), take(&mut model, key)),
+            }
+            prop_assert_eq!(cache.len(), model.len());
+            prop_assert_eq!(cache.keys_by_recency(), model.iter().map(|&(key, _)| key).collect::<Vec<_>>());
+        }
+    }
+}
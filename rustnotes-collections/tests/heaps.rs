@@ -0,0 +1,254 @@
+// Model tests for the heaps
+//
+// Each test runs a random sequence of operations on one heap and on a plain
+// Vec or BTreeMap side by side. After every step the heap must report the
+// same length and the same minimum (and maximum, for the min-max heap) as the
+// model, and the heaps with a validity check must pass it.
+//
+// Design choices:
+// - Pairing heap items are (key, id) pairs, so a popped item says which
+//   handle died; only live handles are passed to decrease_key
+// - The indexed heap's items are small integers and its model maps item to
+//   priority; ties between equal priorities may pop either item, so only the
+//   popped priority is compared
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::heaps::binary_heap::MinHeap;
+use rustnotes_collections::heaps::indexed_heap::IndexedHeap;
+use rustnotes_collections::heaps::min_max_heap::MinMaxHeap;
+use rustnotes_collections::heaps::pairing_heap::{Handle, PairingHeap};
+use std::collections::{BTreeMap, BTreeSet};
+
+// Remove one copy of the smallest value from an unsorted model
+fn take_min(model: &mut Vec<u16>) -> Option<u16> {
+    let (index, _) = model.iter().enumerate().min_by_key(|&(_, value)| value)?;
+    Some(model.swap_remove(index))
+}
+
+fn take_max(model: &mut Vec<u16>) -> Option<u16> {
+    let (index, _) = model.iter().enumerate().max_by_key(|&(_, value)| value)?;
+    Some(model.swap_remove(index))
+}
+
+#[derive(Debug, Clone)]
+enum HeapOp {
+    Push(u16),
+    PopMin,
+    PopMax,
+    Clear,
+}
+
+fn heap_op() -> impl Strategy<Value = HeapOp> {
+    prop_oneof![
+        6 => (0..100u16).prop_map(HeapOp::Push),
+        2 => Just(HeapOp::PopMin),
+        2 => Just(HeapOp::PopMax),
+        1 => Just(HeapOp::Clear),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum PairingOp {
+    Push(u16),
+    PopMin,
+    // Lower a live handle's key by the given amount
+    DecreaseKey(usize, u16),
+    // Merge in a second heap holding these keys
+    Merge(Vec<u16>),
+    Clear,
+}
+
+fn pairing_op() -> impl Strategy<Value = PairingOp> {
+    prop_oneof![
+        6 => (0..1000u16).prop_map(PairingOp::Push),
+        3 => Just(PairingOp::PopMin),
+        3 => (any::<usize>(), 0..500u16).prop_map(|(pick, by)| PairingOp::DecreaseKey(pick, by)),
+        1 => prop::collection::vec(0..1000u16, 0..10).prop_map(PairingOp::Merge),
+        1 => Just(PairingOp::Clear),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum IndexedOp {
+    Push(usize, u16),
+    Pop,
+    Remove(usize),
+    DecreaseKey(usize, u16),
+    IncreaseKey(usize, u16),
+    PushOrDecrease(usize, u16),
+}
+
+fn indexed_op() -> impl Strategy<Value = IndexedOp> {
+    prop_oneof![
+        4 => (0..32usize, 0..100u16).prop_map(|(item, priority)| IndexedOp::Push(item, priority)),
+        2 => Just(IndexedOp::Pop),
+        1 => (0..32usize).prop_map(IndexedOp::Remove),
+        2 => (0..32usize, 0..100u16).prop_map(|(item, priority)| IndexedOp::DecreaseKey(item, priority)),
+        2 => (0..32usize, 0..100u16).prop_map(|(item, priority)| IndexedOp::IncreaseKey(item, priority)),
+        2 => (0..32usize, 0..100u16).prop_map(|(item, priority)| IndexedOp::PushOrDecrease(item, priority)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn min_heap_matches_vec(ops in prop::collection::vec(heap_op(), 0..300)) {
+        // MinHeap has no pop_max; PopMax pops the minimum as well
+        let mut heap = MinHeap::new();
+        let mut model = Vec::new();
+        for op in ops {
+            match op {
+                HeapOp::Push(value) => {
+                    heap.push(value);
+                    model.push(value);
+                }
+                HeapOp::PopMin | HeapOp::PopMax => prop_assert_eq!(heap.pop(), take_min(&mut model)),
+                HeapOp::Clear => {
+                    heap.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(heap.len(), model.len());
+            prop_assert_eq!(heap.peek(), model.iter().min());
+            prop_assert!(heap.is_valid());
+        }
+    }
+
+    #[test]
+    fn heapify_then_sort_matches_sort(mut values in prop::collection::vec(any::<u16>(), 0..300)) {
+        let heap = MinHeap::heapify(values.clone());
+        prop_assert!(heap.is_valid());
+        values.sort();
+        prop_assert_eq!(heap.into_sorted_vec(), values.clone());
+        prop_assert_eq!(MinMaxHeap::heapify(values.iter().rev().copied().collect()).into_sorted_vec(), values);
+    }
+
+    #[test]
+    fn min_max_heap_matches_vec(ops in prop::collection::vec(heap_op(), 0..300)) {
+        let mut heap = MinMaxHeap::new();
+        let mut model = Vec::new();
+        for op in ops {
+            match op {
+                HeapOp::Push(value) => {
+                    heap.push(value);
+                    model.push(value);
+                }
+                HeapOp::PopMin => prop_assert_eq!(heap.pop_min(), take_min(&mut model)),
+                HeapOp::PopMax => prop_assert_eq!(heap.pop_max(), take_max(&mut model)),
+                HeapOp::Clear => {
+                    heap.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(heap.len(), model.len());
+            prop_assert_eq!(heap.peek_min(), model.iter().min());
+            prop_assert_eq!(heap.peek_max(), model.iter().max());
+        }
+    }
+
+    #[test]
+    fn pairing_heap_matches_btree_set(ops in prop::collection::vec(pairing_op(), 0..300)) {
+        let mut heap = PairingHeap::new();
+        let mut model = BTreeSet::new();
+        let mut handles: BTreeMap<u32, Handle> = BTreeMap::new();
+        let mut next_id = 0u32;
+        for op in ops {
+            match op {
+                PairingOp::Push(key) => {
+                    handles.insert(next_id, heap.push((key, next_id)));
+                    model.insert((key, next_id));
+                    next_id += 1;
+                }
+                PairingOp::PopMin => {
+                    let popped = heap.pop_min();
+                    prop_assert_eq!(popped, model.pop_first());
+                    if let Some((_, id)) = popped {
+                        handles.remove(&id);
+                    }
+                }
+                PairingOp::DecreaseKey(pick, by) if !handles.is_empty() => {
+                    let (&id, &handle) = handles.iter().nth(pick % handles.len()).unwrap();
+                    let (key, _) = *heap.get(handle);
+                    model.remove(&(key, id));
+                    let lowered = (key.saturating_sub(by), id);
+                    heap.decrease_key(handle, lowered);
+                    model.insert(lowered);
+                }
+                PairingOp::DecreaseKey(..) => {}
+                PairingOp::Merge(keys) => {
+                    // Items merged in from the other heap have no handles here
+                    let mut other = PairingHeap::new();
+                    for key in keys {
+                        other.push((key, next_id));
+                        model.insert((key, next_id));
+                        next_id += 1;
+                    }
+                    heap.merge(other);
+                }
+                PairingOp::Clear => {
+                    heap.clear();
+                    model.clear();
+                    handles.clear();
+                }
+            }
+            prop_assert_eq!(heap.len(), model.len());
+            prop_assert_eq!(heap.peek(), model.first());
+            for (&id, &handle) in &handles {
+                prop_assert!(model.contains(heap.get(handle)));
+                prop_assert_eq!(heap.get(handle).1, id);
+            }
+        }
+    }
+
+    #[test]
+    fn indexed_heap_matches_btree_map(arity in 2..5usize, ops in prop::collection::vec(indexed_op(), 0..300)) {
+        let mut heap = IndexedHeap::with_arity(arity);
+        let mut model: BTreeMap<usize, u16> = BTreeMap::new();
+        for op in ops {
+            match op {
+                IndexedOp::Push(item, priority) => {
+                    let absent = !model.contains_key(&item);
+                    prop_assert_eq!(heap.push(item, priority), absent);
+                    model.entry(item).or_insert(priority);
+                }
+                IndexedOp::Pop => match heap.pop() {
+                    Some((item, priority)) => {
+                        prop_assert_eq!(model.remove(&item), Some(priority));
+                        prop_assert!(model.values().all(|&other| other >= priority));
+                    }
+                    None => prop_assert!(model.is_empty()),
+                },
+                IndexedOp::Remove(item) => prop_assert_eq!(heap.remove(item), model.remove(&item)),
+                IndexedOp::DecreaseKey(item, priority) => {
+                    let lower = model.get(&item).is_some_and(|&current| priority < current);
+                    prop_assert_eq!(heap.decrease_key(item, priority), lower);
+                    if lower {
+                        model.insert(item, priority);
+                    }
+                }
+                IndexedOp::IncreaseKey(item, priority) => {
+                    let higher = model.get(&item).is_some_and(|&current| priority > current);
+                    prop_assert_eq!(heap.increase_key(item, priority), higher);
+                    if higher {
+                        model.insert(item, priority);
+                    }
+                }
+                IndexedOp::PushOrDecrease(item, priority) => {
+                    let changes = model.get(&item).is_none_or(|&current| priority < current);
+                    prop_assert_eq!(heap.push_or_decrease(item, priority), changes);
+                    if changes {
+                        model.insert(item, priority);
+                    }
+                }
+            }
+            prop_assert_eq!(heap.len(), model.len());
+            prop_assert!(heap.is_valid());
+            prop_assert_eq!(heap.peek().map(|(_, &priority)| priority), model.values().min().copied());
+            for item in 0..32 {
+                prop_assert_eq!(heap.priority(item), model.get(&item));
+                prop_assert_eq!(heap.contains(item), model.contains_key(&item));
+            }
+        }
+    }
+}
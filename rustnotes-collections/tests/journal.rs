@@ -0,0 +1,101 @@
+// Model tests for the undo/redo journal
+//
+// Random sequences of changes, undos and redos run through a Journal, while
+// the test keeps a snapshot of the expected contents at every version. After
+// each step the wrapped collection must match the snapshot for the journal's
+// current version, and undo/redo must report how far they actually moved.
+//
+// Design choices:
+// - A Journal over AvlTree covers SetOp and one over LinkedList covers StackOp;
+//   the other implementors share the same trait impls
+// - Snapshots past the current version are kept until a new change arrives,
+//   which is exactly when the journal drops its redo stack
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::journal::{Journal, SetOp, StackOp};
+use rustnotes_collections::lists::linked_list::LinkedList;
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone)]
+enum Step {
+    Add(u8),
+    Take(u8),
+    Undo(usize),
+    Redo(usize),
+}
+
+fn step() -> impl Strategy<Value = Step> {
+    prop_oneof![
+        4 => (0..32u8).prop_map(Step::Add),
+        3 => (0..32u8).prop_map(Step::Take),
+        2 => (0..6usize).prop_map(Step::Undo),
+        2 => (0..6usize).prop_map(Step::Redo),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn set_journal_matches_snapshots(steps in prop::collection::vec(step(), 0..200)) {
+        let mut journal: Journal<AvlTree<u8>, SetOp<u8>> = Journal::new(AvlTree::new());
+        let mut snapshots = vec![BTreeSet::new()];
+        for step in steps {
+            let version = journal.version();
+            let mut next = snapshots[version].clone();
+            let changed = match step {
+                Step::Add(value) => Some((journal.insert(value), next.insert(value))),
+                Step::Take(value) => Some((journal.remove(&value), next.remove(&value))),
+                Step::Undo(n) => {
+                    prop_assert_eq!(journal.undo(n), n.min(version));
+                    None
+                }
+                Step::Redo(n) => {
+                    prop_assert_eq!(journal.redo(n), n.min(snapshots.len() - 1 - version));
+                    None
+                }
+            };
+            if let Some((actual, expected)) = changed {
+                prop_assert_eq!(actual, expected);
+                if expected {
+                    snapshots.truncate(version + 1);
+                    snapshots.push(next);
+                }
+            }
+            let contents: Vec<u8> = journal.get().iter().copied().collect();
+            prop_assert!(contents.iter().eq(snapshots[journal.version()].iter()));
+        }
+    }
+
+    #[test]
+    fn stack_journal_matches_snapshots(steps in prop::collection::vec(step(), 0..200)) {
+        let mut journal: Journal<LinkedList<u8>, StackOp<u8>> = Journal::new(LinkedList::new());
+        let mut snapshots: Vec<Vec<u8>> = vec![Vec::new()];
+        for step in steps {
+            let version = journal.version();
+            match step {
+                Step::Add(value) => {
+                    let mut next = snapshots[version].clone();
+                    next.push(value);
+                    journal.push(value);
+                    snapshots.truncate(version + 1);
+                    snapshots.push(next);
+                }
+                Step::Take(_) => {
+                    let mut next = snapshots[version].clone();
+                    let expected = next.pop();
+                    prop_assert_eq!(journal.pop(), expected);
+                    if expected.is_some() {
+                        snapshots.truncate(version + 1);
+                        snapshots.push(next);
+                    }
+                }
+                Step::Undo(n) => prop_assert_eq!(journal.undo(n), n.min(version)),
+                Step::Redo(n) => prop_assert_eq!(journal.redo(n), n.min(snapshots.len() - 1 - version)),
+            }
+            let contents: Vec<u8> = journal.get().iter().copied().collect();
+            prop_assert!(contents.iter().rev().eq(snapshots[journal.version()].iter()));
+        }
+    }
+}
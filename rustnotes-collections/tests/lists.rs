@@ -0,0 +1,524 @@
+// Model tests for the lists
+//
+// Each test runs a random sequence of operations on one structure and on a
+// std reference (Vec or VecDeque) side by side, and after every step checks
+// that both answered the same and hold the same values in the same order.
+// The doubly linked lists are walked in both directions, so a broken prev or
+// next link fails the step that broke it.
+//
+// Design choices:
+// - Stacks and queues go through the Stack and Queue traits, one generic
+//   check per trait for every structure that implements it
+// - Handles (NodeRef, NodeHandle) are kept for every pushed value, including
+//   removed ones, so stale handles are exercised as well as live ones; one
+//   test shares handles between two DoubleLinkedLists to check that each
+//   refuses the other's
+// - Pushed values are fresh ids, so a handle's value can be found in the model
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::lists::arena_linked_list::{ArenaList, NodeHandle};
+use rustnotes_collections::lists::double_linked_list::{DoubleLinkedList, NodeRef};
+use rustnotes_collections::lists::error::ListError;
+use rustnotes_collections::lists::linked_list::LinkedList;
+use rustnotes_collections::lists::persistent_vector::PersistentVector;
+use rustnotes_collections::lists::small_vec::SmallVec;
+use rustnotes_collections::lists::stack_and_queue;
+use rustnotes_collections::traits::{Queue, Stack};
+use std::collections::VecDeque;
+
+#[derive(Debug, Clone)]
+enum StackOp {
+    Push(u16),
+    Pop,
+    Clear,
+}
+
+fn stack_op() -> impl Strategy<Value = StackOp> {
+    prop_oneof![
+        6 => any::<u16>().prop_map(StackOp::Push),
+        3 => Just(StackOp::Pop),
+        1 => Just(StackOp::Clear),
+    ]
+}
+
+fn check_stack<S: Stack<u16> + Default>(ops: &[StackOp]) {
+    let mut stack = S::default();
+    let mut model = Vec::new();
+    for op in ops {
+        match op {
+            StackOp::Push(value) => {
+                stack.push(*value);
+                model.push(*value);
+            }
+            StackOp::Pop => assert_eq!(stack.pop(), model.pop()),
+            StackOp::Clear => {
+                stack.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(stack.len(), model.len());
+        assert_eq!(stack.is_empty(), model.is_empty());
+        assert_eq!(stack.peek(), model.last());
+    }
+}
+
+#[derive(Debug, Clone)]
+enum QueueOp {
+    Enqueue(u16),
+    Dequeue,
+    Clear,
+}
+
+fn queue_op() -> impl Strategy<Value = QueueOp> {
+    prop_oneof![
+        6 => any::<u16>().prop_map(QueueOp::Enqueue),
+        3 => Just(QueueOp::Dequeue),
+        1 => Just(QueueOp::Clear),
+    ]
+}
+
+fn check_queue<Q: Queue<u16> + Default>(ops: &[QueueOp]) {
+    let mut queue = Q::default();
+    let mut model = VecDeque::new();
+    for op in ops {
+        match op {
+            QueueOp::Enqueue(value) => {
+                queue.enqueue(*value);
+                model.push_back(*value);
+            }
+            QueueOp::Dequeue => assert_eq!(queue.dequeue(), model.pop_front()),
+            QueueOp::Clear => {
+                queue.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(queue.len(), model.len());
+        assert_eq!(queue.is_empty(), model.is_empty());
+    }
+}
+
+proptest! {
+    #[test]
+    fn linked_list_is_a_stack(ops in prop::collection::vec(stack_op(), 0..200)) {
+        check_stack::<LinkedList<u16>>(&ops);
+    }
+
+    #[test]
+    fn stack_adapter_is_a_stack(ops in prop::collection::vec(stack_op(), 0..200)) {
+        check_stack::<stack_and_queue::Stack<u16>>(&ops);
+    }
+
+    #[test]
+    fn small_vec_is_a_stack(ops in prop::collection::vec(stack_op(), 0..200)) {
+        check_stack::<SmallVec<u16, 4>>(&ops);
+    }
+
+    #[test]
+    fn arena_list_is_a_stack(ops in prop::collection::vec(stack_op(), 0..200)) {
+        check_stack::<ArenaList<u16>>(&ops);
+    }
+
+    #[test]
+    fn queue_adapter_is_a_queue(ops in prop::collection::vec(queue_op(), 0..200)) {
+        check_queue::<stack_and_queue::Queue<u16>>(&ops);
+    }
+
+    #[test]
+    fn double_linked_list_is_a_queue(ops in prop::collection::vec(queue_op(), 0..200)) {
+        check_queue::<DoubleLinkedList<u16>>(&ops);
+    }
+
+    #[test]
+    fn arena_list_is_a_queue(ops in prop::collection::vec(queue_op(), 0..200)) {
+        check_queue::<ArenaList<u16>>(&ops);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum LinkedListOp {
+    Push(u8),
+    Pop,
+    Remove(usize),
+    Reverse,
+    Contains(u8),
+}
+
+fn linked_list_op() -> impl Strategy<Value = LinkedListOp> {
+    prop_oneof![
+        4 => any::<u8>().prop_map(LinkedListOp::Push),
+        2 => Just(LinkedListOp::Pop),
+        2 => (0..40usize).prop_map(LinkedListOp::Remove),
+        1 => Just(LinkedListOp::Reverse),
+        2 => any::<u8>().prop_map(LinkedListOp::Contains),
+    ]
+}
+
+// Operations shared by both doubly linked lists; handles and ids are picked
+// modulo how many exist, so they always name something pushed earlier
+#[derive(Debug, Clone)]
+enum DequeOp {
+    PushFront,
+    PushBack,
+    PopFront,
+    PopBack,
+    MoveToFront(usize),
+    Remove(usize),
+    Reverse,
+    Clear,
+}
+
+fn deque_op() -> impl Strategy<Value = DequeOp> {
+    prop_oneof![
+        3 => Just(DequeOp::PushFront),
+        3 => Just(DequeOp::PushBack),
+        1 => Just(DequeOp::PopFront),
+        1 => Just(DequeOp::PopBack),
+        2 => any::<usize>().prop_map(DequeOp::MoveToFront),
+        2 => any::<usize>().prop_map(DequeOp::Remove),
+        1 => Just(DequeOp::Reverse),
+        1 => Just(DequeOp::Clear),
+    ]
+}
+
+fn model_position(model: &VecDeque<u32>, id: u32) -> Option<usize> {
+    model.iter().position(|&value| value == id)
+}
+
+#[derive(Debug, Clone)]
+enum PersistentOp {
+    Push(u16),
+    Pop,
+    Set(usize, u16),
+    // Go back to an earlier version and continue from there; every version is
+    // checked again at the end
+    Checkout(usize),
+}
+
+fn persistent_op() -> impl Strategy<Value = PersistentOp> {
+    prop_oneof![
+        8 => any::<u16>().prop_map(PersistentOp::Push),
+        2 => Just(PersistentOp::Pop),
+        3 => (any::<usize>(), any::<u16>()).prop_map(|(index, value)| PersistentOp::Set(index, value)),
+        1 => any::<usize>().prop_map(PersistentOp::Checkout),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn linked_list_matches_vec(ops in prop::collection::vec(linked_list_op(), 0..200)) {
+        // The model keeps the list's front at index 0
+        let mut list = LinkedList::new();
+        let mut model: Vec<u8> = Vec::new();
+        for op in ops {
+            match op {
+                LinkedListOp::Push(value) => {
+                    list.push(value);
+                    model.insert(0, value);
+                }
+                LinkedListOp::Pop => {
+                    let expected = if model.is_empty() { None } else { Some(model.remove(0)) };
+                    prop_assert_eq!(list.pop(), expected);
+                }
+                LinkedListOp::Remove(index) => {
+                    let expected = (index < model.len()).then(|| model.remove(index));
+                    prop_assert_eq!(list.remove(index), expected);
+                }
+                LinkedListOp::Reverse => {
+                    list.reverse();
+                    model.reverse();
+                }
+                LinkedListOp::Contains(value) => prop_assert_eq!(list.contains(&value), model.contains(&value)),
+            }
+            prop_assert_eq!(list.len(), model.len());
+            prop_assert_eq!(list.iter().copied().collect::<Vec<_>>(), model.clone());
+        }
+    }
+
+    #[test]
+    fn double_linked_list_matches_vec_deque(ops in prop::collection::vec(deque_op(), 0..200)) {
+        let mut list = DoubleLinkedList::new();
+        let mut model = VecDeque::new();
+        let mut handles: Vec<(NodeRef<u32>, u32)> = Vec::new();
+        let mut next_id = 0u32;
+        for op in ops {
+            match op {
+                // Every other front push goes through push_ref to get a handle
+                DequeOp::PushFront if next_id.is_multiple_of(2) => {
+                    handles.push((list.push_ref(next_id), next_id));
+                    model.push_front(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PushFront => {
+                    list.push(next_id);
+                    model.push_front(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PushBack => {
+                    list.push_end(next_id);
+                    model.push_back(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PopFront => prop_assert_eq!(list.pop(), model.pop_front()),
+                DequeOp::PopBack => prop_assert_eq!(list.pop_end(), model.pop_back()),
+                DequeOp::MoveToFront(pick) if !handles.is_empty() => {
+                    let (handle, id) = &handles[pick % handles.len()];
+                    let position = model_position(&model, *id);
+                    prop_assert_eq!(list.move_to_front(handle), position.is_some());
+                    if let Some(position) = position {
+                        model.remove(position);
+                        model.push_front(*id);
+                    }
+                }
+                DequeOp::Remove(pick) if !handles.is_empty() => {
+                    let (handle, id) = &handles[pick % handles.len()];
+                    let expected = model_position(&model, *id).and_then(|position| model.remove(position));
+                    prop_assert_eq!(list.remove_ref(handle), expected);
+                }
+                DequeOp::MoveToFront(_) | DequeOp::Remove(_) => {}
+                DequeOp::Reverse => {
+                    list.reverse();
+                    model.make_contiguous().reverse();
+                }
+                DequeOp::Clear => {
+                    list.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(list.len(), model.len());
+            let mut forward = Vec::new();
+            list.for_each(|&value| forward.push(value));
+            prop_assert_eq!(&forward, &model.iter().copied().collect::<Vec<_>>());
+            let mut backward = Vec::new();
+            list.for_each_rev(|&value| backward.push(value));
+            prop_assert_eq!(&backward, &model.iter().rev().copied().collect::<Vec<_>>());
+            for (handle, id) in &handles {
+                prop_assert_eq!(handle.is_valid(), model.contains(id));
+            }
+        }
+    }
+
+    // Handles from both lists are used on either one; a list must refuse the
+    // other's handles and leave both lists as they were
+    #[test]
+    fn double_linked_lists_refuse_each_others_refs(ops in prop::collection::vec((0..2usize, deque_op()), 0..200)) {
+        let mut lists = [DoubleLinkedList::new(), DoubleLinkedList::new()];
+        let mut models = [VecDeque::new(), VecDeque::new()];
+        let mut handles: Vec<(NodeRef<u32>, usize, u32)> = Vec::new();
+        let mut next_id = 0u32;
+        for (target, op) in ops {
+            let (list, model) = (&mut lists[target], &mut models[target]);
+            match op {
+                DequeOp::PushFront => {
+                    handles.push((list.push_ref(next_id), target, next_id));
+                    model.push_front(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PushBack => {
+                    list.push_end(next_id);
+                    model.push_back(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PopFront => prop_assert_eq!(list.pop(), model.pop_front()),
+                DequeOp::PopBack => prop_assert_eq!(list.pop_end(), model.pop_back()),
+                DequeOp::MoveToFront(pick) if !handles.is_empty() => {
+                    let (handle, owner, id) = &handles[pick % handles.len()];
+                    let position = model_position(model, *id).filter(|_| *owner == target);
+                    prop_assert_eq!(list.move_to_front(handle), position.is_some());
+                    if let Some(position) = position {
+                        model.remove(position);
+                        model.push_front(*id);
+                    }
+                }
+                DequeOp::Remove(pick) if !handles.is_empty() => {
+                    let (handle, owner, id) = &handles[pick % handles.len()];
+                    let position = model_position(model, *id).filter(|_| *owner == target);
+                    prop_assert_eq!(list.remove_ref(handle), position.and_then(|position| model.remove(position)));
+                }
+                DequeOp::MoveToFront(_) | DequeOp::Remove(_) => {}
+                DequeOp::Reverse => {
+                    list.reverse();
+                    model.make_contiguous().reverse();
+                }
+                DequeOp::Clear => {
+                    list.clear();
+                    model.clear();
+                }
+            }
+            for (list, model) in lists.iter().zip(models.iter()) {
+                prop_assert_eq!(list.len(), model.len());
+                let mut forward = Vec::new();
+                list.for_each(|&value| forward.push(value));
+                prop_assert_eq!(&forward, &model.iter().copied().collect::<Vec<_>>());
+                let mut backward = Vec::new();
+                list.for_each_rev(|&value| backward.push(value));
+                prop_assert_eq!(&backward, &model.iter().rev().copied().collect::<Vec<_>>());
+            }
+            for (handle, owner, id) in &handles {
+                prop_assert_eq!(handle.is_valid(), models[*owner].contains(id));
+            }
+        }
+    }
+
+    #[test]
+    fn arena_list_matches_vec_deque(ops in prop::collection::vec(deque_op(), 0..200)) {
+        let mut list = ArenaList::new();
+        let mut model = VecDeque::new();
+        let mut handles: Vec<(NodeHandle, u32)> = Vec::new();
+        let mut next_id = 0u32;
+        for op in ops {
+            match op {
+                // Every other front push inserts before a random node, live or not
+                DequeOp::PushFront if next_id % 2 == 1 && !handles.is_empty() => {
+                    let (handle, id) = handles[next_id as usize % handles.len()];
+                    let inserted = list.try_insert_before(handle, next_id);
+                    match model_position(&model, id) {
+                        Some(position) => {
+                            model.insert(position, next_id);
+                            handles.push((inserted.expect("live handle"), next_id));
+                        }
+                        None => prop_assert_eq!(inserted, Err(ListError::StaleHandle(handle))),
+                    }
+                    next_id += 1;
+                }
+                DequeOp::PushFront => {
+                    handles.push((list.push_front(next_id), next_id));
+                    model.push_front(next_id);
+                    next_id += 1;
+                }
+                // Every other back push inserts after a random live node instead
+                DequeOp::PushBack if next_id % 2 == 1 && !handles.is_empty() => {
+                    let (handle, id) = handles[next_id as usize % handles.len()];
+                    let inserted = list.insert_after(handle, next_id);
+                    match model_position(&model, id) {
+                        Some(position) => {
+                            model.insert(position + 1, next_id);
+                            handles.push((inserted.expect("live handle"), next_id));
+                        }
+                        None => prop_assert_eq!(inserted, None),
+                    }
+                    next_id += 1;
+                }
+                DequeOp::PushBack => {
+                    handles.push((list.push_back(next_id), next_id));
+                    model.push_back(next_id);
+                    next_id += 1;
+                }
+                DequeOp::PopFront => prop_assert_eq!(list.pop_front(), model.pop_front()),
+                DequeOp::PopBack => prop_assert_eq!(list.pop_back(), model.pop_back()),
+                DequeOp::MoveToFront(pick) if !handles.is_empty() => {
+                    let (handle, id) = handles[pick % handles.len()];
+                    let position = model_position(&model, id);
+                    prop_assert_eq!(list.move_to_front(handle), position.is_some());
+                    if let Some(position) = position {
+                        model.remove(position);
+                        model.push_front(id);
+                    }
+                }
+                DequeOp::Remove(pick) if !handles.is_empty() => {
+                    let (handle, id) = handles[pick % handles.len()];
+                    let expected = model_position(&model, id).and_then(|position| model.remove(position));
+                    prop_assert_eq!(list.try_remove(handle).ok(), expected);
+                    prop_assert!(!list.is_valid(handle));
+                }
+                DequeOp::MoveToFront(_) | DequeOp::Remove(_) => {}
+                DequeOp::Reverse => {
+                    list.reverse();
+                    model.make_contiguous().reverse();
+                }
+                DequeOp::Clear => {
+                    list.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(list.len(), model.len());
+            prop_assert_eq!(list.front(), model.front());
+            prop_assert_eq!(list.back(), model.back());
+            prop_assert!(list.iter().eq(model.iter()));
+            prop_assert!(list.iter().rev().eq(model.iter().rev()));
+            for &(handle, id) in &handles {
+                let expected = model.contains(&id).then_some(&id);
+                prop_assert_eq!(list.get(handle), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn small_vec_matches_vec(ops in prop::collection::vec((0..4u8, any::<usize>(), any::<u16>()), 0..200)) {
+        let mut small: SmallVec<u16, 4> = SmallVec::new();
+        let mut model = Vec::new();
+        for (kind, index, value) in ops {
+            match kind {
+                0 => {
+                    small.push(value);
+                    model.push(value);
+                }
+                1 => {
+                    let index = index % (model.len() + 1);
+                    small.insert(index, value);
+                    model.insert(index, value);
+                }
+                2 if !model.is_empty() => {
+                    let index = index % model.len();
+                    prop_assert_eq!(small.remove(index), model.remove(index));
+                }
+                _ => {
+                    let len = index % (model.len() + 1);
+                    small.truncate(len);
+                    model.truncate(len);
+                }
+            }
+            prop_assert_eq!(small.len(), model.len());
+            prop_assert!(small.capacity() >= small.len());
+            prop_assert_eq!(small.spilled(), small.capacity() > 4);
+            prop_assert!(small.iter().eq(model.iter()));
+            prop_assert!(small.iter().rev().eq(model.iter().rev()));
+        }
+    }
+
+    #[test]
+    fn persistent_vector_keeps_every_version(ops in prop::collection::vec(persistent_op(), 0..300)) {
+        // Every version ever made, next to the Vec it should still equal
+        let mut versions = vec![(PersistentVector::new(), Vec::new())];
+        let mut current = 0;
+        for op in ops {
+            let (vector, model) = &versions[current];
+            let next = match op {
+                PersistentOp::Push(value) => {
+                    let mut model = model.clone();
+                    model.push(value);
+                    Some((vector.push(value), model))
+                }
+                PersistentOp::Pop => {
+                    let popped = vector.pop();
+                    prop_assert_eq!(popped.as_ref().map(|(_, value)| *value), model.last().copied());
+                    popped.map(|(vector, _)| (vector, model[..model.len() - 1].to_vec()))
+                }
+                PersistentOp::Set(index, value) if !model.is_empty() => {
+                    let index = index % model.len();
+                    let mut model = model.clone();
+                    model[index] = value;
+                    Some((vector.set(index, value), model))
+                }
+                PersistentOp::Set(..) => None,
+                PersistentOp::Checkout(pick) => {
+                    current = pick % versions.len();
+                    None
+                }
+            };
+            if let Some(version) = next {
+                versions.push(version);
+                current = versions.len() - 1;
+            }
+            let (vector, model) = &versions[current];
+            prop_assert_eq!(vector.len(), model.len());
+            prop_assert!(vector.iter().eq(model.iter()));
+            prop_assert!((0..model.len()).all(|index| vector.get(index) == Some(&model[index])));
+        }
+        // Later versions share nodes with earlier ones but must not have changed them
+        for (vector, model) in &versions {
+            prop_assert!(vector.iter().eq(model.iter()));
+        }
+    }
+}
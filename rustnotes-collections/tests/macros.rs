@@ -0,0 +1,41 @@
+// Tests for the constructor macros
+//
+// Each macro must build the same structure as the hand-written calls it
+// stands for: elements in the order written (sorted for avl!), an empty call
+// giving an empty structure, and a trailing comma accepted.
+
+#![cfg(feature = "std")]
+
+use rustnotes_collections::lists::double_linked_list::DoubleLinkedList;
+use rustnotes_collections::lists::linked_list::LinkedList;
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use rustnotes_collections::{avl, dlist, linked_list};
+
+#[test]
+fn linked_list_keeps_written_order() {
+    let list = linked_list![1, 2, 3];
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [1, 2, 3]);
+    assert_eq!(list.peek(), Some(&1));
+    assert_eq!(linked_list!["a", "b",].len(), 2);
+    let empty: LinkedList<u8> = linked_list![];
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn dlist_keeps_written_order() {
+    let mut list = dlist![1, 2, 3];
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.pop(), Some(1));
+    assert_eq!(list.pop_end(), Some(3));
+    let empty: DoubleLinkedList<u8> = dlist![];
+    assert!(empty.is_empty());
+}
+
+#[test]
+fn avl_sorts_and_deduplicates() {
+    let tree = avl![5, 3, 8, 3, 1,];
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), [1, 3, 5, 8]);
+    assert!(tree.is_balanced());
+    let empty: AvlTree<u8> = avl![];
+    assert!(empty.is_empty());
+}
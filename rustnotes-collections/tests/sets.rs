@@ -0,0 +1,172 @@
+// Model tests for the bit vector, the interval set and the Bloom filter
+//
+// BitVec runs the same random operations as a Vec<bool>; after every step the
+// two must have the same length, bits, and count of ones. IntervalSet is
+// compared against a BTreeSet of the individual points it covers, and its
+// ranges must stay sorted, non-empty, and separated by at least one gap.
+// BloomFilter may claim items it never saw, so its model only pins down what
+// it must get right: every inserted item is found, and a cleared filter finds
+// nothing.
+//
+// Design choices:
+// - Interval endpoints are u8, so the point model stays small and every
+//   point can be checked with contains after each step
+// - Bit indices are picked as a fraction of the current length, so set and
+//   flip always hit a valid bit once the vector is non-empty
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::sets::bit_vec::BitVec;
+use rustnotes_collections::sets::bloom_filter::BloomFilter;
+use rustnotes_collections::sets::interval_set::IntervalSet;
+use std::collections::hash_map::RandomState;
+use std::collections::BTreeSet;
+
+#[derive(Debug, Clone)]
+enum BitOp {
+    Push(bool),
+    Set(usize, bool),
+    Flip(usize),
+    Fill(bool),
+}
+
+fn bit_op() -> impl Strategy<Value = BitOp> {
+    prop_oneof![
+        6 => any::<bool>().prop_map(BitOp::Push),
+        3 => (any::<usize>(), any::<bool>()).prop_map(|(pick, value)| BitOp::Set(pick, value)),
+        3 => any::<usize>().prop_map(BitOp::Flip),
+        1 => any::<bool>().prop_map(BitOp::Fill),
+    ]
+}
+
+#[derive(Debug, Clone)]
+enum IntervalOp {
+    Insert(u8, u8),
+    Remove(u8, u8),
+}
+
+fn interval_op() -> impl Strategy<Value = IntervalOp> {
+    // Endpoints in either order, so empty and inverted ranges come up too
+    prop_oneof![
+        3 => (any::<u8>(), any::<u8>()).prop_map(|(start, end)| IntervalOp::Insert(start, end)),
+        2 => (any::<u8>(), any::<u8>()).prop_map(|(start, end)| IntervalOp::Remove(start, end)),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn bit_vec_matches_vec_of_bool(ops in prop::collection::vec(bit_op(), 0..400)) {
+        let mut bits = BitVec::new();
+        let mut model: Vec<bool> = Vec::new();
+        for op in ops {
+            match op {
+                BitOp::Push(value) => {
+                    bits.push(value);
+                    model.push(value);
+                }
+                BitOp::Set(pick, value) if !model.is_empty() => {
+                    let index = pick % model.len();
+                    bits.set(index, value);
+                    model[index] = value;
+                }
+                BitOp::Flip(pick) if !model.is_empty() => {
+                    let index = pick % model.len();
+                    model[index] = !model[index];
+                    prop_assert_eq!(bits.flip(index), model[index]);
+                }
+                BitOp::Set(..) | BitOp::Flip(_) => {}
+                BitOp::Fill(value) => {
+                    bits.fill(value);
+                    model.fill(value);
+                }
+            }
+            prop_assert_eq!(bits.len(), model.len());
+            prop_assert!(bits.iter().eq(model.iter().copied()));
+            prop_assert_eq!(bits.count_ones(), model.iter().filter(|&&bit| bit).count());
+            prop_assert_eq!(bits.any(), model.contains(&true));
+            prop_assert!(bits.iter_ones().eq((0..model.len()).filter(|&i| model[i])));
+        }
+    }
+
+    #[test]
+    fn bit_vec_operators_match_zip(pairs in prop::collection::vec(any::<(bool, bool)>(), 0..300)) {
+        let a: BitVec = pairs.iter().map(|&(x, _)| x).collect();
+        let b: BitVec = pairs.iter().map(|&(_, y)| y).collect();
+        prop_assert!((&a & &b).iter().eq(pairs.iter().map(|&(x, y)| x & y)));
+        prop_assert!((&a | &b).iter().eq(pairs.iter().map(|&(x, y)| x | y)));
+        prop_assert!((&a ^ &b).iter().eq(pairs.iter().map(|&(x, y)| x ^ y)));
+        // Bits past the end must stay clear after a negation
+        let not_a = !&a;
+        prop_assert!(not_a.iter().eq(pairs.iter().map(|&(x, _)| !x)));
+        prop_assert_eq!(not_a.count_ones(), pairs.iter().filter(|&&(x, _)| !x).count());
+    }
+
+    #[test]
+    fn interval_set_matches_point_set(ops in prop::collection::vec(interval_op(), 0..100)) {
+        let mut set = IntervalSet::new();
+        let mut model = BTreeSet::new();
+        for op in ops {
+            match op {
+                IntervalOp::Insert(start, end) => {
+                    set.insert(start..end);
+                    model.extend(start..end);
+                }
+                IntervalOp::Remove(start, end) => {
+                    set.remove(start..end);
+                    model.retain(|point| !(start..end).contains(point));
+                }
+            }
+            let ranges: Vec<_> = set.iter().collect();
+            prop_assert!(ranges.iter().all(|range| range.start < range.end));
+            // Touching ranges would have been merged, so each gap is at least one point
+            prop_assert!(ranges.windows(2).all(|pair| pair[0].end < pair[1].start));
+            prop_assert!(ranges.iter().flat_map(|range| range.clone()).eq(model.iter().copied()));
+            prop_assert_eq!(set.is_empty(), model.is_empty());
+            for point in 0..=u8::MAX {
+                prop_assert_eq!(set.contains(point), model.contains(&point));
+            }
+        }
+    }
+
+    #[test]
+    fn bloom_filter_finds_every_inserted_item(
+        ops in prop::collection::vec(prop_oneof![
+            8 => any::<u16>().prop_map(Some),
+            1 => Just(None),
+        ], 0..300),
+        bits in 1..512usize,
+        hashes in 1..6u32,
+    ) {
+        // None stands for clear(). Small filters fill up and answer yes to
+        // almost anything, which is allowed; answering no for an item that was
+        // inserted is not
+        let mut filter = BloomFilter::with_size(bits, hashes, RandomState::new());
+        let mut model = BTreeSet::new();
+        let mut inserts = 0;
+        for op in ops {
+            match op {
+                Some(item) => {
+                    filter.insert(&item);
+                    model.insert(item);
+                    inserts += 1;
+                }
+                None => {
+                    filter.clear();
+                    model.clear();
+                    inserts = 0;
+                }
+            }
+            prop_assert_eq!(filter.len(), inserts);
+            prop_assert_eq!(filter.bit_count(), bits);
+            for item in &model {
+                prop_assert!(filter.contains(item));
+            }
+            if model.is_empty() {
+                prop_assert!(filter.is_empty());
+                prop_assert!(!filter.contains(&0u16));
+                prop_assert_eq!(filter.estimated_false_positive_rate(), 0.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+// Replay tests for the recorded operations
+//
+// A model keeps only node ids and links. Random operations run through the
+// *_recorded methods, the model applies the steps they report, and after each
+// operation it must describe the same graph as the structure's own nodes():
+// the steps are a complete account of what changed, not a summary.
+//
+// Design choices:
+// - The model checks every step on the way (a link may only point at a live
+//   node, a node is created once), so an out-of-order step fails where it is
+//   reported rather than in the final comparison
+// - Values are kept small so inserts hit duplicates and removes hit present
+//   values, and the AVL tree rotates often
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::lists::double_linked_list::DoubleLinkedList;
+use rustnotes_collections::lists::linked_list::LinkedList;
+use rustnotes_collections::steps::{Link, NodeId, Step};
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use std::collections::HashMap;
+
+// Ids and links only; a node's links are [left, right] or [next, prev]
+#[derive(Debug, Default)]
+struct Model {
+    root: Option<NodeId>,
+    head: Option<NodeId>,
+    tail: Option<NodeId>,
+    nodes: HashMap<NodeId, [Option<NodeId>; 2]>,
+}
+
+impl Model {
+    fn apply(&mut self, steps: &[Step]) {
+        for &step in steps {
+            match step {
+                Step::Create(id) => assert!(self.nodes.insert(id, [None, None]).is_none(), "{:?} twice", step),
+                Step::Delete(id) => assert!(self.nodes.remove(&id).is_some(), "{:?} of a dead node", step),
+                Step::Visit(id) | Step::RotateLeft(id) | Step::RotateRight(id) => {
+                    assert!(self.nodes.contains_key(&id), "{:?} of a dead node", step)
+                }
+                Step::Link { link, to } => {
+                    if let Some(to) = to {
+                        assert!(self.nodes.contains_key(&to), "{:?} to a dead node", step);
+                    }
+                    match link {
+                        Link::Root => self.root = to,
+                        Link::Head => self.head = to,
+                        Link::Tail => self.tail = to,
+                        Link::Left(id) | Link::Next(id) => self.links(id)[0] = to,
+                        Link::Right(id) | Link::Prev(id) => self.links(id)[1] = to,
+                    }
+                }
+            }
+        }
+    }
+
+    fn links(&mut self, id: NodeId) -> &mut [Option<NodeId>; 2] {
+        self.nodes.get_mut(&id).expect("link from a dead node")
+    }
+
+    // Ids from the head along the next links
+    fn chain(&self) -> Vec<NodeId> {
+        let mut ids = Vec::new();
+        let mut current = self.head;
+        while let Some(id) = current {
+            ids.push(id);
+            current = self.nodes[&id][0];
+        }
+        ids
+    }
+}
+
+#[derive(Debug, Clone)]
+enum TreeOp {
+    Insert(u8),
+    Remove(u8),
+}
+
+#[derive(Debug, Clone)]
+enum ListOp {
+    Push(u8),
+    PushEnd(u8),
+    Pop,
+    PopEnd,
+    Remove(usize),
+    Reverse,
+}
+
+fn tree_op() -> impl Strategy<Value = TreeOp> {
+    prop_oneof![
+        3 => (0..40u8).prop_map(TreeOp::Insert),
+        2 => (0..40u8).prop_map(TreeOp::Remove),
+    ]
+}
+
+fn list_op() -> impl Strategy<Value = ListOp> {
+    prop_oneof![
+        3 => any::<u8>().prop_map(ListOp::Push),
+        3 => any::<u8>().prop_map(ListOp::PushEnd),
+        2 => Just(ListOp::Pop),
+        2 => Just(ListOp::PopEnd),
+        2 => (0..12usize).prop_map(ListOp::Remove),
+        1 => Just(ListOp::Reverse),
+    ]
+}
+
+proptest! {
+    #[test]
+    fn avl_steps_replay_to_nodes(ops in prop::collection::vec(tree_op(), 0..200)) {
+        let mut tree = AvlTree::new();
+        let mut model = Model::default();
+        for op in ops {
+            let mut steps = Vec::new();
+            match op {
+                TreeOp::Insert(value) => tree.insert_recorded(value, &mut steps),
+                TreeOp::Remove(value) => tree.remove_recorded(&value, &mut steps),
+            };
+            model.apply(&steps);
+
+            let nodes = tree.nodes();
+            prop_assert_eq!(model.root, nodes.first().map(|node| node.id));
+            prop_assert_eq!(model.nodes.len(), nodes.len());
+            for node in &nodes {
+                prop_assert_eq!(model.nodes.get(&node.id), Some(&[node.left, node.right]));
+            }
+        }
+    }
+
+    #[test]
+    fn linked_list_steps_replay_to_nodes(ops in prop::collection::vec(list_op(), 0..200)) {
+        let mut list = LinkedList::new();
+        let mut model = Model::default();
+        for op in ops {
+            let mut steps = Vec::new();
+            match op {
+                ListOp::Push(value) | ListOp::PushEnd(value) => list.push_recorded(value, &mut steps),
+                ListOp::Pop | ListOp::PopEnd => drop(list.pop_recorded(&mut steps)),
+                ListOp::Remove(index) => drop(list.remove_recorded(index, &mut steps)),
+                ListOp::Reverse => list.reverse_recorded(&mut steps),
+            }
+            model.apply(&steps);
+
+            let ids: Vec<NodeId> = list.nodes().iter().map(|&(id, _)| id).collect();
+            prop_assert_eq!(model.nodes.len(), ids.len());
+            prop_assert_eq!(model.chain(), ids);
+        }
+    }
+
+    #[test]
+    fn double_linked_list_steps_replay_to_nodes(ops in prop::collection::vec(list_op(), 0..200)) {
+        let mut list = DoubleLinkedList::new();
+        let mut model = Model::default();
+        for op in ops {
+            let mut steps = Vec::new();
+            match op {
+                ListOp::Push(value) => list.push_recorded(value, &mut steps),
+                ListOp::PushEnd(value) => list.push_end_recorded(value, &mut steps),
+                ListOp::Pop => drop(list.pop_recorded(&mut steps)),
+                // No recorded indexed remove or reverse here; pop_end stands in
+                ListOp::PopEnd | ListOp::Remove(_) | ListOp::Reverse => drop(list.pop_end_recorded(&mut steps)),
+            }
+            model.apply(&steps);
+
+            let mut ids = Vec::new();
+            list.for_each_node(|id, _| ids.push(id));
+            prop_assert_eq!(model.nodes.len(), ids.len());
+            prop_assert_eq!(&model.chain(), &ids);
+            prop_assert_eq!(model.tail, ids.last().copied());
+            for (i, id) in ids.iter().enumerate() {
+                let prev = i.checked_sub(1).map(|p| ids[p]);
+                prop_assert_eq!(model.nodes[id][1], prev);
+            }
+        }
+    }
+}
+
+#[test]
+fn first_insert_creates_the_root() {
+    let mut tree = AvlTree::new();
+    let mut steps = Vec::new();
+    tree.insert_recorded(1, &mut steps);
+    let root = tree.nodes()[0].id;
+    assert_eq!(steps, [Step::Create(root), Step::Link { link: Link::Root, to: Some(root) }]);
+}
+
+#[test]
+fn ascending_inserts_report_a_left_rotation() {
+    let mut tree = AvlTree::new();
+    tree.insert(1);
+    tree.insert(2);
+    let one = tree.nodes()[0].id;
+    let mut steps = Vec::new();
+    tree.insert_recorded(3, &mut steps);
+    assert!(steps.contains(&Step::RotateLeft(one)));
+    assert_eq!(tree.nodes()[0].value, &2);
+    assert_eq!(steps.last(), Some(&Step::Link { link: Link::Root, to: Some(tree.nodes()[0].id) }));
+}
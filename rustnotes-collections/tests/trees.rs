@@ -0,0 +1,440 @@
+// Model tests for the trees and ordered sets
+//
+// Each test runs a random sequence of operations on one structure and on a
+// std BTreeSet side by side. After every step the structure must have given
+// the same answers, hold the same values in sorted order, and still satisfy
+// its own shape invariant: AVL balance, the scapegoat height bound, skip list
+// level counts, or successor/predecessor links that agree with iteration.
+// The segment trees are checked against a Vec and a fold over the queried
+// slice, and the k-d tree and quadtree against a brute-force scan of the
+// points they were given.
+//
+// Design choices:
+// - Ordered sets go through the SortedSet trait, so one generic check covers
+//   AvlTree, ScapegoatTree, SkipList and ArenaTree, each with its own
+//   invariant passed in as a closure
+// - Values are u8, so random operations hit existing values often
+// - Points have whole coordinates on a small grid, so duplicates and ties in
+//   distance come up; nearest-neighbor answers are compared by distance, since
+//   either of two equally near points is right
+
+#![cfg(feature = "std")]
+
+use proptest::prelude::*;
+use rustnotes_collections::lists::skip_list::SkipList;
+use rustnotes_collections::traits::SortedSet;
+use rustnotes_collections::trees::arena_binary_tree::ArenaTree;
+use rustnotes_collections::trees::balanced_binary_tree::AvlTree;
+use rustnotes_collections::trees::binary_tree::BinaryTree;
+use rustnotes_collections::trees::concurrent_avl_tree::ConcurrentAvlTree;
+use rustnotes_collections::trees::kd_tree::{distance_squared, KdTree};
+use rustnotes_collections::trees::quadtree::{Point, Quadtree, Rect};
+use rustnotes_collections::trees::scapegoat_tree::ScapegoatTree;
+use rustnotes_collections::trees::segment_tree::{LazySegmentTree, SegmentTree, Summary};
+use rustnotes_collections::trees::trie::Trie;
+use std::collections::BTreeSet;
+use std::ops::Bound;
+
+#[derive(Debug, Clone)]
+enum SetOp {
+    Insert(u8),
+    Remove(u8),
+    Contains(u8),
+    Clear,
+}
+
+fn set_op() -> impl Strategy<Value = SetOp> {
+    prop_oneof![
+        6 => any::<u8>().prop_map(SetOp::Insert),
+        3 => any::<u8>().prop_map(SetOp::Remove),
+        2 => any::<u8>().prop_map(SetOp::Contains),
+        1 => Just(SetOp::Clear),
+    ]
+}
+
+fn check_sorted_set<S, F>(ops: &[SetOp], invariant: F)
+where
+    S: SortedSet<u8> + Default,
+    F: Fn(&S),
+{
+    let mut set = S::default();
+    let mut model = BTreeSet::new();
+    for op in ops {
+        match op {
+            SetOp::Insert(value) => assert_eq!(set.insert(*value), model.insert(*value)),
+            SetOp::Remove(value) => assert_eq!(set.remove(value), model.remove(value)),
+            SetOp::Contains(value) => assert_eq!(set.contains(value), model.contains(value)),
+            SetOp::Clear => {
+                set.clear();
+                model.clear();
+            }
+        }
+        assert_eq!(set.len(), model.len());
+        assert_eq!(set.first(), model.first());
+        assert!(set.iter().eq(model.iter()));
+        invariant(&set);
+    }
+}
+
+// log base 1/alpha of n, for the scapegoat height bound
+fn log_base(n: usize, alpha: f64) -> f64 {
+    (n as f64).ln() / (1.0 / alpha).ln()
+}
+
+// Range bounds over u8 as data, so proptest can generate and print them
+#[derive(Debug, Clone)]
+struct RangeOp(Bound<u8>, Bound<u8>);
+
+fn bound() -> impl Strategy<Value = Bound<u8>> {
+    prop_oneof![
+        any::<u8>().prop_map(Bound::Included),
+        any::<u8>().prop_map(Bound::Excluded),
+        Just(Bound::Unbounded),
+    ]
+}
+
+fn in_range(value: u8, range: &RangeOp) -> bool {
+    let above_start = match range.0 {
+        Bound::Included(start) => value >= start,
+        Bound::Excluded(start) => value > start,
+        Bound::Unbounded => true,
+    };
+    let below_end = match range.1 {
+        Bound::Included(end) => value <= end,
+        Bound::Excluded(end) => value < end,
+        Bound::Unbounded => true,
+    };
+    above_start && below_end
+}
+
+proptest! {
+    #[test]
+    fn avl_tree_matches_btree_set(ops in prop::collection::vec(set_op(), 0..300)) {
+        check_sorted_set::<AvlTree<u8>, _>(&ops, |tree| {
+            assert!(tree.is_balanced());
+            // An AVL tree of n nodes is at most about 1.44 log2(n) high
+            assert!(tree.height() as f64 <= 1.45 * ((tree.len() + 2) as f64).log2());
+        });
+    }
+
+    #[test]
+    fn scapegoat_tree_matches_btree_set(ops in prop::collection::vec(set_op(), 0..300)) {
+        check_sorted_set::<ScapegoatTree<u8>, _>(&ops, |tree| {
+            // Depth stays within log_{1/α}(n) of the largest size since the last
+            // rebuild, which is at most n/α: height <= log_{1/α}(n) + 2 levels
+            if !tree.is_empty() {
+                assert!(tree.height() as f64 <= log_base(tree.len(), 2.0 / 3.0).floor() + 2.0);
+            }
+        });
+    }
+
+    #[test]
+    fn skip_list_matches_btree_set(ops in prop::collection::vec(set_op(), 0..300)) {
+        check_sorted_set::<SkipList<u8>, _>(&ops, |list| {
+            // Every node is on the bottom level, and each level is a subset of the one below
+            let counts = list.level_counts();
+            assert_eq!(counts.first().copied().unwrap_or(0), list.len());
+            assert!(counts.windows(2).all(|pair| pair[0] >= pair[1]));
+            assert!(counts.len() <= list.max_level());
+        });
+    }
+
+    #[test]
+    fn arena_tree_matches_btree_set(ops in prop::collection::vec(set_op(), 0..300)) {
+        check_sorted_set::<ArenaTree<u8>, _>(&ops, |tree| {
+            // Walking successor links from first() and predecessor links from last()
+            // must give the same order as iteration
+            let forward: Vec<u8> = std::iter::successors(tree.first(), |&handle| tree.successor(handle))
+                .map(|handle| *tree.get(handle).unwrap())
+                .collect();
+            assert!(forward.iter().eq(tree.iter()));
+            let backward: Vec<u8> = std::iter::successors(tree.last(), |&handle| tree.predecessor(handle))
+                .map(|handle| *tree.get(handle).unwrap())
+                .collect();
+            assert!(backward.iter().eq(forward.iter().rev()));
+        });
+    }
+
+    #[test]
+    fn avl_remove_range_matches_btree_set(
+        values in prop::collection::vec(any::<u8>(), 0..200),
+        ops in prop::collection::vec(prop_oneof![
+            2 => (bound(), bound()).prop_map(|(start, end)| Err(RangeOp(start, end))),
+            3 => set_op().prop_map(Ok),
+        ], 1..40),
+    ) {
+        // Err stands for remove_range, between the usual set operations, so
+        // ranges are cut from trees that inserts and removes have reshaped
+        let mut tree = AvlTree::new();
+        let mut model = BTreeSet::new();
+        for value in values {
+            tree.insert(value);
+            model.insert(value);
+        }
+        for op in ops {
+            match op {
+                Err(range) => {
+                    // retain rather than BTreeSet::range, which panics on an inverted range
+                    let before = model.len();
+                    model.retain(|&value| !in_range(value, &range));
+                    prop_assert_eq!(tree.remove_range((range.0, range.1)), before - model.len());
+                }
+                Ok(SetOp::Insert(value)) => prop_assert_eq!(SortedSet::insert(&mut tree, value), model.insert(value)),
+                Ok(SetOp::Remove(value)) => prop_assert_eq!(tree.remove(&value), model.remove(&value)),
+                Ok(SetOp::Contains(value)) => prop_assert_eq!(tree.contains(&value), model.contains(&value)),
+                Ok(SetOp::Clear) => {
+                    tree.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            prop_assert!(tree.iter().eq(model.iter()));
+            prop_assert!(tree.is_balanced());
+        }
+    }
+
+    #[test]
+    fn concurrent_avl_tree_matches_btree_set(ops in prop::collection::vec(set_op(), 0..200)) {
+        let tree = ConcurrentAvlTree::new();
+        let mut model = BTreeSet::new();
+        for op in ops {
+            match op {
+                SetOp::Insert(value) => prop_assert_eq!(tree.insert(value), model.insert(value)),
+                SetOp::Remove(value) => prop_assert_eq!(tree.remove(&value), model.remove(&value)),
+                SetOp::Contains(value) => prop_assert_eq!(tree.contains(&value), model.contains(&value)),
+                SetOp::Clear => {
+                    tree.clear();
+                    model.clear();
+                }
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            prop_assert!(tree.read(|inner| inner.is_balanced() && inner.iter().eq(model.iter())));
+        }
+    }
+
+    #[test]
+    fn binary_tree_matches_btree_set(ops in prop::collection::vec(prop_oneof![
+        8 => set_op().prop_map(Some),
+        1 => Just(None),
+    ], 0..200)) {
+        // None stands for invert(); a mirrored tree iterates in descending order
+        let mut tree = BinaryTree::new();
+        let mut model = BTreeSet::new();
+        for op in ops {
+            match op {
+                Some(SetOp::Insert(value)) => {
+                    let expected_new = model.insert(value);
+                    let before = tree.len();
+                    tree.insert(value);
+                    prop_assert_eq!(tree.len() > before, expected_new);
+                }
+                Some(SetOp::Remove(value)) => prop_assert_eq!(tree.remove(&value), model.remove(&value)),
+                Some(SetOp::Contains(value)) => prop_assert_eq!(tree.contains(&value), model.contains(&value)),
+                Some(SetOp::Clear) => {
+                    tree.clear();
+                    model.clear();
+                }
+                None => tree.invert(),
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            if tree.is_mirrored() {
+                prop_assert!(tree.iter().eq(model.iter().rev()));
+            } else {
+                prop_assert!(tree.iter().eq(model.iter()));
+            }
+        }
+    }
+
+    #[test]
+    fn trie_matches_btree_set(ops in prop::collection::vec((0..3u8, "[ab]{0,4}"), 0..200)) {
+        // Two letters and short words, so words share prefixes and repeat often
+        let mut trie = Trie::new();
+        let mut model = BTreeSet::new();
+        for (kind, word) in ops {
+            match kind {
+                0 => prop_assert_eq!(trie.insert(&word), model.insert(word.clone())),
+                1 => prop_assert_eq!(trie.remove(&word), model.remove(&word)),
+                _ => {
+                    let expected: Vec<&String> = model.iter().filter(|w| w.starts_with(word.as_str())).collect();
+                    prop_assert!(trie.words_with_prefix(&word).eq(expected.iter().map(|w| w.to_string())));
+                    prop_assert_eq!(trie.starts_with(&word), !expected.is_empty());
+                }
+            }
+            prop_assert_eq!(trie.len(), model.len());
+            prop_assert!(trie.iter().eq(model.iter().cloned()));
+            for word in &model {
+                prop_assert!(trie.contains(word));
+            }
+        }
+    }
+}
+
+// The accepted alpha range is (0.5, 0.95]; at the top of it, sorted inserts
+// (the worst case for an unbalanced tree) still stay within the depth bound
+#[test]
+fn scapegoat_alpha_range() {
+    for alpha in [0.5, 0.951, 1.0 - f64::EPSILON / 2.0, 1.0, f64::NAN] {
+        assert_eq!(ScapegoatTree::<u8>::try_with_alpha(alpha).err().map(|error| error.to_string()),
+                   Some(format!("alpha must be above 0.5 and at most 0.95, got {}", alpha)));
+    }
+    let mut tree = ScapegoatTree::try_with_alpha(0.95).unwrap();
+    for value in 0..2000 {
+        assert!(tree.insert(value));
+        assert!(tree.height() as f64 <= log_base(tree.len(), 0.95).floor() + 2.0);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum SegmentOp {
+    Update(usize, i64),
+    Query(usize, usize),
+    RangeAdd(usize, usize, i64),
+}
+
+// Positions are picked at random and reduced modulo the length (or length + 1
+// for a range end) in the test, so they are always in bounds
+fn segment_op() -> impl Strategy<Value = SegmentOp> {
+    prop_oneof![
+        3 => (any::<usize>(), -1000..1000i64).prop_map(|(index, value)| SegmentOp::Update(index, value)),
+        3 => (any::<usize>(), any::<usize>()).prop_map(|(a, b)| SegmentOp::Query(a, b)),
+        2 => (any::<usize>(), any::<usize>(), -100..100i64).prop_map(|(a, b, delta)| SegmentOp::RangeAdd(a, b, delta)),
+    ]
+}
+
+// A range start..end within 0..=len from two random picks
+fn segment_range(a: usize, b: usize, len: usize) -> (usize, usize) {
+    let (a, b) = (a % (len + 1), b % (len + 1));
+    (a.min(b), a.max(b))
+}
+
+fn grid_point() -> impl Strategy<Value = [f64; 3]> {
+    [0..8i32, 0..8i32, 0..8i32].prop_map(|[x, y, z]| [x as f64, y as f64, z as f64])
+}
+
+// Quadtree points, some outside the 0..=64 bounds so insert can refuse them
+fn plane_point() -> impl Strategy<Value = Point> {
+    (-4..68i32, -4..68i32).prop_map(|(x, y)| Point::new(x as f64, y as f64))
+}
+
+proptest! {
+    #[test]
+    fn segment_tree_matches_vec(
+        values in prop::collection::vec("[a-z]", 1..64),
+        ops in prop::collection::vec(segment_op(), 0..100),
+    ) {
+        // Concatenation is associative but not commutative, so a query that
+        // joins its pieces in the wrong order gives the wrong string
+        let mut model = values;
+        let mut tree = SegmentTree::new(&model, String::new(), |a: &String, b: &String| format!("{}{}", a, b));
+        for op in ops {
+            match op {
+                SegmentOp::Update(index, value) | SegmentOp::RangeAdd(index, _, value) => {
+                    let index = index % model.len();
+                    let letter = char::from(b'a' + value.rem_euclid(26) as u8).to_string();
+                    tree.update(index, letter.clone());
+                    model[index] = letter;
+                }
+                SegmentOp::Query(a, b) => {
+                    let (start, end) = segment_range(a, b, model.len());
+                    prop_assert_eq!(tree.query(start..end), model[start..end].concat());
+                }
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            prop_assert_eq!(tree.query(..), model.concat());
+            for (index, value) in model.iter().enumerate() {
+                prop_assert_eq!(tree.get(index), value);
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_segment_tree_matches_vec(
+        values in prop::collection::vec(-1000..1000i64, 1..64),
+        ops in prop::collection::vec(segment_op(), 0..100),
+    ) {
+        let mut model = values;
+        let mut tree = LazySegmentTree::new(&model);
+        for op in ops {
+            match op {
+                SegmentOp::Update(index, delta) => {
+                    // A point update is a range add over one position
+                    let index = index % model.len();
+                    tree.range_add(index..=index, delta);
+                    model[index] += delta;
+                }
+                SegmentOp::RangeAdd(a, b, delta) => {
+                    let (start, end) = segment_range(a, b, model.len());
+                    tree.range_add(start..end, delta);
+                    model[start..end].iter_mut().for_each(|value| *value += delta);
+                }
+                SegmentOp::Query(a, b) => {
+                    let (start, end) = segment_range(a, b, model.len());
+                    let slice = &model[start..end];
+                    let expected = slice.iter().min().map(|&min| Summary {
+                        sum: slice.iter().sum(),
+                        min,
+                        max: *slice.iter().max().unwrap(),
+                    });
+                    prop_assert_eq!(tree.query(start..end), expected);
+                }
+            }
+            prop_assert_eq!(tree.len(), model.len());
+            for (index, &value) in model.iter().enumerate() {
+                prop_assert_eq!(tree.get(index), value);
+            }
+        }
+    }
+
+    #[test]
+    fn kd_tree_k_nearest_matches_brute_force(
+        points in prop::collection::vec(grid_point(), 0..150),
+        targets in prop::collection::vec((grid_point(), 0..12usize), 1..20),
+    ) {
+        let tree = KdTree::build(points.iter().copied().enumerate().map(|(id, point)| (point, id)).collect());
+        prop_assert_eq!(tree.len(), points.len());
+        for (target, k) in targets {
+            let mut expected: Vec<f64> = points.iter().map(|point| distance_squared(point, &target)).collect();
+            expected.sort_by(|a, b| a.total_cmp(b));
+            expected.truncate(k);
+            let found = tree.k_nearest(&target, k);
+            // Each answer must be one of the points given, nearest first
+            for (point, &id) in &found {
+                prop_assert_eq!(**point, points[id]);
+            }
+            let distances: Vec<f64> = found.iter().map(|(point, _)| distance_squared(point, &target)).collect();
+            prop_assert_eq!(distances, expected);
+            prop_assert_eq!(
+                tree.nearest(&target).map(|(point, _)| distance_squared(point, &target)),
+                points.iter().map(|point| distance_squared(point, &target)).min_by(|a, b| a.total_cmp(b))
+            );
+        }
+    }
+
+    #[test]
+    fn quadtree_matches_brute_force(
+        points in prop::collection::vec(plane_point(), 0..200),
+        queries in prop::collection::vec((plane_point(), plane_point()), 1..20),
+    ) {
+        let bounds = Rect::new(0.0, 0.0, 64.0, 64.0);
+        let mut tree = Quadtree::new(bounds);
+        let mut model = Vec::new();
+        for (id, point) in points.into_iter().enumerate() {
+            prop_assert_eq!(tree.insert(point, id), bounds.contains(&point));
+            if bounds.contains(&point) {
+                model.push((point, id));
+            }
+            prop_assert_eq!(tree.len(), model.len());
+        }
+        for (a, b) in queries {
+            let area = Rect::new(a.x.min(b.x), a.y.min(b.y), a.x.max(b.x), a.y.max(b.y));
+            let mut found: Vec<usize> = tree.range(&area).into_iter().map(|(_, &id)| id).collect();
+            found.sort_unstable();
+            let expected: Vec<usize> = model.iter().filter(|(point, _)| area.contains(point)).map(|&(_, id)| id).collect();
+            prop_assert_eq!(found, expected);
+
+            let nearest = tree.nearest(&a).map(|(point, _)| point.distance_squared(&a));
+            let expected = model.iter().map(|(point, _)| point.distance_squared(&a)).min_by(|x, y| x.total_cmp(y));
+            prop_assert_eq!(nearest, expected);
+        }
+    }
+}
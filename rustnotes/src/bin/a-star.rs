@@ -0,0 +1,4 @@
+// A* Pathfinding on a Grid
+fn main() {
+    rustnotes::graphs::a_star::demo();
+}
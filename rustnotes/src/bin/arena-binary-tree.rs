@@ -0,0 +1,4 @@
+// Arena-Backed Binary Search Tree with Parent Links
+fn main() {
+    rustnotes::trees::arena_binary_tree::demo();
+}
@@ -0,0 +1,4 @@
+// Arena-Backed Doubly-Linked List
+fn main() {
+    rustnotes::lists::arena_linked_list::demo();
+}
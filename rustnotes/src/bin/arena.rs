@@ -0,0 +1,4 @@
+// Typed Arena with Generational Indices
+fn main() {
+    rustnotes::lists::arena::demo();
+}
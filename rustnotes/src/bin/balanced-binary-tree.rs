@@ -0,0 +1,6 @@
+// Complete AVL Tree Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::trees::balanced_binary_tree::demo();
+}
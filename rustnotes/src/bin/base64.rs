@@ -0,0 +1,4 @@
+// Base64 Encoding and Decoding
+fn main() {
+    rustnotes::encoding::base64::demo();
+}
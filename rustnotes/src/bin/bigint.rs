@@ -0,0 +1,4 @@
+// Arbitrary-Precision Integers
+fn main() {
+    rustnotes::math::bigint::demo();
+}
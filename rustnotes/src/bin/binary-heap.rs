@@ -0,0 +1,4 @@
+// Binary Min-Heap / Priority Queue Implementation
+fn main() {
+    rustnotes::heaps::binary_heap::demo();
+}
@@ -0,0 +1,4 @@
+// Binary Search Tree Implementation
+fn main() {
+    rustnotes::trees::binary_tree::demo();
+}
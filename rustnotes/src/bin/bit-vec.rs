@@ -0,0 +1,4 @@
+// Bit Vector (Bitset)
+fn main() {
+    rustnotes::sets::bit_vec::demo();
+}
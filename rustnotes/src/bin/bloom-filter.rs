@@ -0,0 +1,4 @@
+// Bloom Filter
+fn main() {
+    rustnotes::sets::bloom_filter::demo();
+}
@@ -0,0 +1,211 @@
+// Calculator REPL
+//
+// The calculator's command line. With no subcommand it starts the interactive
+// REPL, as before; the subcommands cover the non-interactive uses:
+//
+//   calculator                          interactive REPL
+//   echo 'sin(pi/6)' | calculator       evaluate standard input, as run -
+//   calculator eval '2+3*4' 'sqrt(2)'   print each result, in order
+//   calculator -e '2+3*4' -e 'sqrt(2)'  the same
+//   calculator run session.txt          evaluate a file, one line at a time
+//   calculator session.txt              the same
+//   calculator serve --addr 0.0.0.0:7878
+//   calculator --mode degrees eval 'sin(30)'
+//   calculator --exact eval 'factorial(50)'
+//   calculator --base 16 eval '0b1010 + 0o17'
+//   calculator --json eval '2+2' '1/0'  {"error":null,"input":"2+2","result":"4"} ...
+//   calculator completions bash > ~/.local/share/bash-completion/completions/calculator
+//
+// Design choices:
+// - clap's derive API: the structs below are the documentation, and --help,
+//   errors for bad flags and the completion scripts all come from them
+// - --precision, --mode, --exact, --base and --json are global, so they go
+//   before or after the subcommand, and override calculator.toml for this run
+//   without saving it
+// - Without a subcommand, stdin that is not a terminal is read as run - would
+//   read it, so a pipeline sees results and errors but no banner or prompts;
+//   REPL commands (mode exact, json on, set ...) still work there, and
+//   "calculator repl" starts the REPL itself on piped input
+// - -e and a file name are shorthands for eval and run, for scripts that
+//   would rather not spell out a subcommand; a file may have # comments, and
+//   either one with a subcommand is an error rather than quietly dropped
+// - eval and run share one Calculator, so _ and m0-m9 carry from one
+//   expression to the next, and exit with status 1 if any line failed
+// - Completions are generated by clap_complete for bash, zsh, fish, elvish
+//   and PowerShell, printed to stdout for the user to install
+// - Trade-off: clap is the largest dependency of the demo crate; it stays out
+//   of rustnotes-calc, whose library API takes Settings rather than flags
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use clap_complete::Shell;
+use rustnotes::calculator::settings::{AngleMode, NumberMode, Settings};
+use rustnotes::calculator::{self, server, Calculator};
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal};
+use std::mem;
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::process;
+
+#[derive(Parser)]
+#[command(name = "calculator", about = "Expression calculator: REPL, one-shot evaluation, scripts and a TCP server")]
+struct Cli {
+    #[arg(long, global = true, value_name = "PLACES",
+          help = "Decimal places in results (0-17), or \"auto\" for the shortest exact form")]
+    precision: Option<String>,
+
+    #[arg(long, global = true, value_enum, help = "Angle unit for sin, cos, tan and their inverses")]
+    mode: Option<Mode>,
+
+    #[arg(long, global = true, help = "Exact integer and fraction arithmetic (the REPL's \"mode exact\")")]
+    exact: bool,
+
+    #[arg(long, global = true, value_parser = ["2", "8", "10", "16"], help = "Base for whole-number results")]
+    base: Option<String>,
+
+    #[arg(long, global = true, help = "Print each answer as a JSON object with input, result and error")]
+    json: bool,
+
+    #[arg(short = 'e', long = "expression", value_name = "EXPRESSION", allow_hyphen_values = true,
+          help = "Evaluate an expression and print its result (repeatable), as eval does")]
+    expressions: Vec<String>,
+
+    #[arg(value_name = "FILE", conflicts_with = "expressions",
+          help = "Evaluate a file line by line, or standard input for \"-\", as run does")]
+    file: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Mode {
+    Radians,
+    Degrees,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "Start the interactive REPL (the default)")]
+    Repl,
+    #[command(about = "Evaluate each expression and print its result")]
+    Eval {
+        #[arg(required = true, value_name = "EXPRESSION", allow_hyphen_values = true)]
+        expressions: Vec<String>,
+    },
+    #[command(about = "Evaluate a file line by line, or standard input for \"-\"")]
+    Run {
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+    },
+    #[command(about = "Answer expressions from TCP clients, one per line")]
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:7878")]
+        addr: String,
+    },
+    #[command(about = "Print a shell completion script")]
+    Completions {
+        #[arg(value_enum)]
+        shell: Shell,
+    },
+}
+
+// calculator.toml with the command-line overrides applied
+fn settings(cli: &Cli) -> Result<Settings, String> {
+    let mut settings = calculator::load_settings();
+    for (key, value) in [("precision", &cli.precision), ("base", &cli.base)] {
+        if let Some(value) = value {
+            settings.set(key, value).map_err(|error| error.to_string())?;
+        }
+    }
+    match cli.mode {
+        Some(Mode::Radians) => settings.angle_mode = AngleMode::Radians,
+        Some(Mode::Degrees) => settings.angle_mode = AngleMode::Degrees,
+        None => {}
+    }
+    if cli.exact {
+        settings.number_mode = NumberMode::Exact;
+    }
+    if cli.json {
+        settings.json = true;
+    }
+    Ok(settings)
+}
+
+fn run(mut cli: Cli) -> Result<i32, String> {
+    // Loaded by the commands that evaluate, so completions never reads the file
+    let command = match cli.command.take() {
+        Some(command) => command,
+        None if !cli.expressions.is_empty() => Command::Eval { expressions: mem::take(&mut cli.expressions) },
+        None => match cli.file.take() {
+            Some(file) => Command::Run { file },
+            // Piped input is a script: results only, without the help or prompts
+            None if !io::stdin().is_terminal() => Command::Run { file: PathBuf::from("-") },
+            None => Command::Repl,
+        },
+    };
+    let settings = || settings(&cli);
+    match command {
+        Command::Completions { shell } => {
+            clap_complete::generate(shell, &mut Cli::command(), "calculator", &mut io::stdout());
+        }
+        Command::Repl => calculator::run_repl(Calculator::with_settings(settings()?)),
+        Command::Eval { expressions } => {
+            let mut calculator = Calculator::with_settings(settings()?);
+            let mut failed = false;
+            for expression in expressions {
+                match calculator.respond(&expression) {
+                    Ok(text) => println!("{}", text),
+                    Err(json) if calculator.settings().json => {
+                        failed = true;
+                        println!("{}", json);
+                    }
+                    Err(message) => {
+                        failed = true;
+                        eprintln!("{}: Error: {}", expression, message);
+                    }
+                }
+            }
+            return Ok(i32::from(failed));
+        }
+        Command::Run { file } => {
+            let mut calculator = Calculator::with_settings(settings()?);
+            let (stdout, stderr) = (&mut io::stdout(), &mut io::stderr());
+            let failed = if file.as_os_str() == "-" {
+                calculator::run_lines(&mut calculator, io::stdin().lock(), stdout, stderr)
+            } else {
+                let input = File::open(&file).map_err(|error| format!("{}: {}", file.display(), error))?;
+                calculator::run_lines(&mut calculator, BufReader::new(input), stdout, stderr)
+            };
+            return Ok(i32::from(failed.map_err(|error| error.to_string())? > 0));
+        }
+        Command::Serve { addr } => {
+            let listener = TcpListener::bind(&addr).map_err(|error| format!("{}: {}", addr, error))?;
+            let local = listener.local_addr().map_err(|error| error.to_string())?;
+            eprintln!("Calculator listening on {}", local);
+            server::serve(listener, settings()?).map_err(|error| error.to_string())?;
+        }
+    }
+    Ok(0)
+}
+
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    let cli = Cli::parse();
+    // A subcommand is not an argument clap can conflict with, and
+    // args_conflicts_with_subcommands would turn away the global flags too
+    if cli.command.is_some() && (!cli.expressions.is_empty() || cli.file.is_some()) {
+        Cli::command()
+            .error(ErrorKind::ArgumentConflict, "-e and FILE cannot be used with a subcommand")
+            .exit();
+    }
+    match run(cli) {
+        Ok(code) => process::exit(code),
+        Err(message) => {
+            eprintln!("calculator: {}", message);
+            process::exit(2);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// Common Collection Traits
+fn main() {
+    rustnotes::traits::demo();
+}
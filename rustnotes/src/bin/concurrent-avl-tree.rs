@@ -0,0 +1,4 @@
+// Concurrent AVL Tree
+fn main() {
+    rustnotes::trees::concurrent_avl_tree::demo();
+}
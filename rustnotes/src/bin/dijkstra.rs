@@ -0,0 +1,4 @@
+// Dijkstra Shortest Paths
+fn main() {
+    rustnotes::graphs::dijkstra::demo();
+}
@@ -0,0 +1,4 @@
+// Discrete-Event Simulation
+fn main() {
+    rustnotes::math::discrete_event_simulation::demo();
+}
@@ -0,0 +1,6 @@
+// Minimalistic Double-Linked List Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::lists::double_linked_list::demo();
+}
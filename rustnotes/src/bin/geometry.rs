@@ -0,0 +1,4 @@
+// Computational Geometry Primitives
+fn main() {
+    rustnotes::math::geometry::demo();
+}
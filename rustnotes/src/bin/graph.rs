@@ -0,0 +1,4 @@
+// Weighted Graph Implementation
+fn main() {
+    rustnotes::graphs::graph::demo();
+}
@@ -0,0 +1,4 @@
+// Hash Functions
+fn main() {
+    rustnotes::hashing::hash_functions::demo();
+}
@@ -0,0 +1,6 @@
+// Separate-Chaining Hash Map Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::hashing::hash_map_chaining::demo();
+}
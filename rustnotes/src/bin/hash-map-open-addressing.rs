@@ -0,0 +1,6 @@
+// Open-Addressing Hash Map Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::hashing::hash_map_open_addressing::demo();
+}
@@ -0,0 +1,4 @@
+// Huffman Coding
+fn main() {
+    rustnotes::encoding::huffman::demo();
+}
@@ -0,0 +1,4 @@
+// Indexed d-ary Min-Heap Implementation
+fn main() {
+    rustnotes::heaps::indexed_heap::demo();
+}
@@ -0,0 +1,4 @@
+// Interval Set
+fn main() {
+    rustnotes::sets::interval_set::demo();
+}
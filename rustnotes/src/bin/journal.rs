@@ -0,0 +1,4 @@
+// Undo/Redo Journal
+fn main() {
+    rustnotes::journal::demo();
+}
@@ -0,0 +1,4 @@
+// JSON Parser and Serializer
+fn main() {
+    rustnotes::text::json::demo();
+}
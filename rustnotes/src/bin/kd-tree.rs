@@ -0,0 +1,4 @@
+// K-d Tree for Nearest-Neighbor Search
+fn main() {
+    rustnotes::trees::kd_tree::demo();
+}
@@ -0,0 +1,4 @@
+// Lexer Framework
+fn main() {
+    rustnotes::text::lexer::demo();
+}
@@ -0,0 +1,4 @@
+// Line Editor
+fn main() {
+    rustnotes::text::line_editor::demo();
+}
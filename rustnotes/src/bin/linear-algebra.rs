@@ -0,0 +1,4 @@
+// Linear Algebra: Matrix and Vector
+fn main() {
+    rustnotes::math::linear_algebra::demo();
+}
@@ -0,0 +1,6 @@
+// Singly-Linked List Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::lists::linked_list::demo();
+}
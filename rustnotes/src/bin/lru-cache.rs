@@ -0,0 +1,6 @@
+// LRU (Least Recently Used) Cache
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::hashing::lru_cache::demo();
+}
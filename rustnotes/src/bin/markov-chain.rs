@@ -0,0 +1,4 @@
+// Markov Chain Text Generator
+fn main() {
+    rustnotes::text::markov_chain::demo();
+}
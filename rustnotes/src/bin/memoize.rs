@@ -0,0 +1,4 @@
+// Memoization
+fn main() {
+    rustnotes::hashing::memoize::demo();
+}
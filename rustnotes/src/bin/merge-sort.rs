@@ -0,0 +1,4 @@
+// Merge Sort
+fn main() {
+    rustnotes::sorting::merge_sort::demo();
+}
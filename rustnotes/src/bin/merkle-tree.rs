@@ -0,0 +1,4 @@
+// Merkle Tree
+fn main() {
+    rustnotes::trees::merkle_tree::demo();
+}
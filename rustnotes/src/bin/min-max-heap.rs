@@ -0,0 +1,4 @@
+// Min-Max Heap (Double-Ended Priority Queue)
+fn main() {
+    rustnotes::heaps::min_max_heap::demo();
+}
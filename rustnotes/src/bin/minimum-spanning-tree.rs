@@ -0,0 +1,4 @@
+// Minimum Spanning Tree (Kruskal and Prim)
+fn main() {
+    rustnotes::graphs::minimum_spanning_tree::demo();
+}
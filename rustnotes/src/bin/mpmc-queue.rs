@@ -0,0 +1,4 @@
+// Concurrent MPMC Queue
+fn main() {
+    rustnotes::concurrency::mpmc_queue::demo();
+}
@@ -0,0 +1,4 @@
+// Pairing Heap Implementation
+fn main() {
+    rustnotes::heaps::pairing_heap::demo();
+}
@@ -0,0 +1,4 @@
+// Persistent Vector (Bit-Partitioned Trie)
+fn main() {
+    rustnotes::lists::persistent_vector::demo();
+}
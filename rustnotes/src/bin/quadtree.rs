@@ -0,0 +1,4 @@
+// Quadtree Spatial Index
+fn main() {
+    rustnotes::trees::quadtree::demo();
+}
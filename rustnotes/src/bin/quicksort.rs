@@ -0,0 +1,4 @@
+// Quicksort
+fn main() {
+    rustnotes::sorting::quicksort::demo();
+}
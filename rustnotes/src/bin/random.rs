@@ -0,0 +1,4 @@
+// Pseudo-Random Number Generators
+fn main() {
+    rustnotes::math::random::demo();
+}
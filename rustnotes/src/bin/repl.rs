@@ -0,0 +1,4 @@
+// REPL Framework
+fn main() {
+    rustnotes::text::repl::demo();
+}
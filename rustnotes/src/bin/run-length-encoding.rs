@@ -0,0 +1,4 @@
+// Run-Length Encoding
+fn main() {
+    rustnotes::encoding::run_length_encoding::demo();
+}
@@ -0,0 +1,168 @@
+// RustNotes Launcher
+//
+// One entry point for the whole collection. Every other program in src/bin is
+// a thin wrapper around one note's demo(); the launcher finds them, shows a
+// menu with each one's title, and builds and runs the one you pick.
+//
+//   rustnotes                       interactive menu
+//   rustnotes list                  print the demos and exit
+//   rustnotes <demo> [args...]      run one demo, passing the arguments through
+//   rustnotes huffman encode a b    e.g. the Huffman compressor's CLI
+//
+// Design choices:
+// - Demos are discovered, not listed by hand: every .rs file in src/bin except
+//   this one, titled by the first line of its header comment. A new binary
+//   shows up in the menu without touching the launcher
+// - The crate directory is $RUSTNOTES_DIR, or the one this launcher was built from
+// - A demo runs through "cargo run --release --bin <demo>", so cargo decides
+//   whether anything needs rebuilding
+// - The demo runs as a child process with the terminal's stdin/stdout, so
+//   interactive demos such as the calculator work, and its exit code is passed on
+// - Demos can be picked by menu number, full name, or any unambiguous prefix
+//   ("skip" for skip-list)
+// - The menu is itself a Repl (text::repl): "list" shows the demos again,
+//   anything else is a demo to run followed by its arguments
+// - Trade-off: needs cargo on the PATH at run time; in exchange each demo keeps
+//   its own process, arguments and exit code
+
+use rustnotes::text::repl::Repl;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{self, Command};
+
+const LAUNCHER: &str = "rustnotes";
+
+struct Demo {
+    name: String,
+    title: String,
+}
+
+// Directory holding Cargo.toml
+fn crate_dir() -> PathBuf {
+    env::var_os("RUSTNOTES_DIR").map_or_else(|| PathBuf::from(env!("CARGO_MANIFEST_DIR")), PathBuf::from)
+}
+
+fn discover(crate_dir: &Path) -> Result<Vec<Demo>, String> {
+    let dir = crate_dir.join("src").join("bin");
+    let entries = fs::read_dir(&dir).map_err(|e| format!("cannot read {}: {}", dir.display(), e))?;
+    let mut demos = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = match (path.extension(), path.file_stem()) {
+            (Some(ext), Some(stem)) if ext == "rs" => stem.to_string_lossy().into_owned(),
+            _ => continue,
+        };
+        if name == LAUNCHER {
+            continue;
+        }
+        let source = fs::read_to_string(&path).unwrap_or_default();
+        if !source.contains("fn main()") {
+            continue;
+        }
+        let title = source
+            .lines()
+            .next()
+            .and_then(|line| line.strip_prefix("//"))
+            .map(|line| line.trim().trim_end_matches(" Implementation").to_string())
+            .unwrap_or_default();
+        demos.push(Demo { name, title });
+    }
+    demos.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(demos)
+}
+
+fn menu(demos: &[Demo]) -> String {
+    let width = demos.iter().map(|demo| demo.name.len()).max().unwrap_or(0);
+    let lines: Vec<String> = demos
+        .iter()
+        .enumerate()
+        .map(|(i, demo)| format!("{:>3}. {:<width$}  {}", i + 1, demo.name, demo.title, width = width))
+        .collect();
+    lines.join("\n")
+}
+
+// Look a demo up by menu number, exact name, or unique name prefix
+fn find<'a>(demos: &'a [Demo], query: &str) -> Result<&'a Demo, String> {
+    if let Ok(number) = query.parse::<usize>() {
+        return number
+            .checked_sub(1)
+            .and_then(|index| demos.get(index))
+            .ok_or_else(|| format!("no demo number {} (1-{})", number, demos.len()));
+    }
+    if let Some(demo) = demos.iter().find(|demo| demo.name == query) {
+        return Ok(demo);
+    }
+    let matches: Vec<&Demo> = demos.iter().filter(|demo| demo.name.starts_with(query)).collect();
+    match matches.len() {
+        0 => Err(format!("no demo named '{}' (try 'list')", query)),
+        1 => Ok(matches[0]),
+        _ => {
+            let names: Vec<&str> = matches.iter().map(|demo| demo.name.as_str()).collect();
+            Err(format!("'{}' is ambiguous: {}", query, names.join(", ")))
+        }
+    }
+}
+
+// Build and run a demo with the given arguments; returns its exit code
+fn launch(demos: &[Demo], query: &str, args: &[String]) -> Result<i32, String> {
+    let demo = find(demos, query)?;
+    // A launcher built with tracing runs the demos with it too
+    let features: &[&str] = if cfg!(feature = "trace") { &["--features", "trace"] } else { &[] };
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release"])
+        .args(features)
+        .args(["--bin", &demo.name, "--"])
+        .args(args)
+        .current_dir(crate_dir())
+        .status()
+        .map_err(|e| format!("cannot run cargo: {}", e))?;
+    Ok(status.code().unwrap_or(1))
+}
+
+fn interactive(demos: Vec<Demo>) {
+    let help = format!(
+        "RustNotes demos (run one by number or name, optionally followed by arguments):\n{}",
+        menu(&demos)
+    );
+    let mut repl = Repl::new(demos, "rustnotes> ");
+    repl.help_text(&help)
+        .command(&["list", "ls"], "list the demos", |demos, _| Ok(menu(demos)))
+        .fallback(|demos, line| {
+            let mut words = line.split_whitespace();
+            let query = words.next().unwrap_or_default();
+            let args: Vec<String> = words.map(str::to_string).collect();
+            match launch(demos, query, &args)? {
+                0 => Ok(String::new()),
+                code => Ok(format!("(exited with status {})", code)),
+            }
+        });
+    repl.run();
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let demos = match discover(&crate_dir()) {
+        Ok(demos) if !demos.is_empty() => demos,
+        Ok(_) => {
+            eprintln!("no demos found in {}; set RUSTNOTES_DIR", crate_dir().display());
+            process::exit(1);
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+    };
+
+    match args.first().map(String::as_str) {
+        None => interactive(demos),
+        Some("list") => println!("{}", menu(&demos)),
+        Some(query) => match launch(&demos, query, &args[1..]) {
+            Ok(code) => process::exit(code),
+            Err(message) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+        },
+    }
+}
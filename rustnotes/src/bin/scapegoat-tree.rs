@@ -0,0 +1,6 @@
+// Scapegoat Tree Implementation
+fn main() {
+    #[cfg(feature = "trace")]
+    rustnotes::trace::init();
+    rustnotes::trees::scapegoat_tree::demo();
+}
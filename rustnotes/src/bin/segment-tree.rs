@@ -0,0 +1,4 @@
+// Segment Tree Implementation
+fn main() {
+    rustnotes::trees::segment_tree::demo();
+}
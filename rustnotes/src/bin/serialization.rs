@@ -0,0 +1,4 @@
+// Binary Serialization
+fn main() {
+    rustnotes::encoding::serialization::demo();
+}
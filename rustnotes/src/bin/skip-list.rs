@@ -0,0 +1,4 @@
+// Skip List Implementation
+fn main() {
+    rustnotes::lists::skip_list::demo();
+}
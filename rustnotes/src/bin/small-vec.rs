@@ -0,0 +1,4 @@
+// Small Vector with Inline Storage
+fn main() {
+    rustnotes::lists::small_vec::demo();
+}
@@ -0,0 +1,4 @@
+// Sparse Matrix (COO and CSR)
+fn main() {
+    rustnotes::math::sparse_matrix::demo();
+}
@@ -0,0 +1,4 @@
+// Stack and Queue Adapters
+fn main() {
+    rustnotes::lists::stack_and_queue::demo();
+}
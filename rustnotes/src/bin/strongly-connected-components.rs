@@ -0,0 +1,4 @@
+// Strongly Connected Components (Tarjan and Kosaraju)
+fn main() {
+    rustnotes::graphs::strongly_connected_components::demo();
+}
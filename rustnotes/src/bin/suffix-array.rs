@@ -0,0 +1,4 @@
+// Suffix Array with LCP
+fn main() {
+    rustnotes::text::suffix_array::demo();
+}
@@ -0,0 +1,4 @@
+// Thread Pool
+fn main() {
+    rustnotes::concurrency::thread_pool::demo();
+}
@@ -0,0 +1,4 @@
+// Topological Sort
+fn main() {
+    rustnotes::graphs::topological_sort::demo();
+}
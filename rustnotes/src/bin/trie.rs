@@ -0,0 +1,4 @@
+// Trie (Prefix Tree) Implementation
+fn main() {
+    rustnotes::trees::trie::demo();
+}
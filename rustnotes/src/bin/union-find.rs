@@ -0,0 +1,4 @@
+// Union-Find (Disjoint Set) Implementation
+fn main() {
+    rustnotes::graphs::union_find::demo();
+}
@@ -0,0 +1,31 @@
+// RustNotes
+//
+// The demo crate: one binary per note in src/bin plus the rustnotes launcher.
+// This library only gathers the workspace crates under one name, so a demo
+// wrapper reads rustnotes::<group>::<note>::demo() whichever crate the note
+// lives in.
+//
+//   rustnotes-collections  lists, trees, heaps, hashing, sets, random
+//   rustnotes-algorithms   graphs, encoding, text, math, concurrency, sorting
+//   rustnotes-calc         the expression calculator
+//
+// With the "python" feature it is also a Python extension module (python.rs),
+// and with "wasm" the WebAssembly side of the playground page (wasm.rs).
+// With "trace", trace::init() prints the structures' internal events.
+//
+// Design choices:
+// - The workspace is split so the data structure crates build without the
+//   calculator, the launcher, or any optional heavyweight dependencies
+// - Trade-off: building the demos builds every crate
+
+pub use rustnotes_algorithms::{concurrency, encoding, graphs, math, sorting, text};
+pub use rustnotes_calc as calculator;
+pub use rustnotes_collections::{hashing, heaps, journal, lists, random, sets, traits, trees};
+pub use rustnotes_collections::{avl, dlist, linked_list};
+
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "trace")]
+pub mod trace;
+#[cfg(feature = "wasm")]
+mod wasm;
@@ -0,0 +1,297 @@
+// Python Bindings
+//
+// A pyo3 extension module named rustnotes, compiled only with the "python"
+// feature and built with maturin (see pyproject.toml):
+//
+//   >>> import rustnotes
+//   >>> rustnotes.eval("2 + 3 * 4")
+//   14.0
+//   >>> rustnotes.eval("sqrt(-4)")
+//   2j
+//   >>> rustnotes.Calculator(exact=True).eval("2^100 / 6")
+//   Fraction(633825300114114700748351602688, 3)
+//   >>> s = rustnotes.AvlSet([5, 1, 9]); s.add(3); list(s)
+//   [1, 3, 5, 9]
+//   >>> m = rustnotes.AvlMap(); m["b"] = 2; m["a"] = [1]; m.items()
+//   [('a', [1]), ('b', 2)]
+//
+// Design choices:
+// - Thin wrappers: each Python class owns one Rust structure and forwards to
+//   it, so this file doubles as an FFI example rather than new behavior
+// - Keys cross the boundary as ints or strings (Key), which have a total order
+//   on both sides; ints sort before strings, and anything else is a TypeError
+// - AvlMap stores (key, slot) entries in an AvlTree ordered by key alone, and
+//   the Python values in a slot vector, so the tree never holds Python objects
+// - Calculator results become float, or complex when they have an imaginary
+//   part; an exact-mode result becomes int or fractions.Fraction, built from
+//   its digits, and a result with units a (float, "km/h") tuple. Calculator
+//   errors become ValueError, missing map keys KeyError
+// - Trade-off: iterating copies the contents into a Python list first, so an
+//   iterator never borrows the tree while Python code runs
+
+use core::cmp::Ordering;
+use core::fmt;
+use pyo3::exceptions::{PyKeyError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyComplex, PyFloat, PyIterator, PyList};
+use rustnotes_calc as calculator;
+use rustnotes_calc::settings::NumberMode;
+use rustnotes_calc::value::Value;
+use rustnotes_collections::traits::SortedSet;
+use rustnotes_collections::trees::AvlTree;
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, FromPyObject, IntoPyObject)]
+enum Key {
+    Int(i64),
+    Str(String),
+}
+
+impl fmt::Display for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Key::Int(value) => write!(f, "{}", value),
+            Key::Str(value) => write!(f, "'{}'", value),
+        }
+    }
+}
+
+fn to_python(py: Python<'_>, value: Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Real(x) => PyFloat::new(py, x).into_any().unbind(),
+        Value::Complex(z) => PyComplex::from_doubles(py, z.re, z.im).into_any().unbind(),
+        Value::Exact(r) => {
+            let (module, class) = if r.is_integer() { ("builtins", "int") } else { ("fractions", "Fraction") };
+            py.import(module)?.getattr(class)?.call1((r.to_string(),))?.unbind()
+        }
+        Value::Quantity(q) => (q.value, q.unit.to_string()).into_pyobject(py)?.into_any().unbind(),
+        Value::Matrix(m) => {
+            let rows: Vec<Vec<f64>> = (0..m.rows()).map(|r| m.row(r).to_vec()).collect();
+            rows.into_pyobject(py)?.into_any().unbind()
+        }
+    })
+}
+
+// Evaluate one calculator expression
+#[pyfunction]
+fn eval(py: Python<'_>, expression: &str) -> PyResult<PyObject> {
+    let value = calculator::eval(expression).map_err(|error| PyValueError::new_err(error.to_string()))?;
+    to_python(py, value)
+}
+
+// A calculator that keeps its memory and last result (_) between lines
+#[pyclass(name = "Calculator")]
+struct PyCalculator(calculator::Calculator);
+
+#[pymethods]
+impl PyCalculator {
+    // exact=True is the REPL's "mode exact"
+    #[new]
+    #[pyo3(signature = (exact = false))]
+    fn new(exact: bool) -> Self {
+        let mut calculator = calculator::Calculator::new();
+        if exact {
+            calculator.settings_mut().number_mode = NumberMode::Exact;
+        }
+        PyCalculator(calculator)
+    }
+
+    fn eval(&mut self, py: Python<'_>, line: &str) -> PyResult<PyObject> {
+        let value = self.0.evaluate(line).map_err(|error| PyValueError::new_err(error.to_string()))?;
+        to_python(py, value)
+    }
+}
+
+#[pyclass]
+struct AvlSet {
+    tree: AvlTree<Key>,
+}
+
+#[pymethods]
+impl AvlSet {
+    #[new]
+    #[pyo3(signature = (values = None))]
+    fn new(values: Option<Vec<Key>>) -> Self {
+        let mut tree = AvlTree::new();
+        for value in values.into_iter().flatten() {
+            tree.insert(value);
+        }
+        AvlSet { tree }
+    }
+
+    // True if the value was not already present
+    fn add(&mut self, value: Key) -> bool {
+        SortedSet::insert(&mut self.tree, value)
+    }
+
+    // True if the value was present
+    fn discard(&mut self, value: Key) -> bool {
+        self.tree.remove(&value)
+    }
+
+    fn height(&self) -> u8 {
+        self.tree.height()
+    }
+
+    fn __len__(&self) -> usize {
+        self.tree.len()
+    }
+
+    fn __contains__(&self, value: Key) -> bool {
+        self.tree.contains(&value)
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.tree.iter().cloned().collect::<Vec<_>>())?.try_iter()
+    }
+
+    fn __repr__(&self) -> String {
+        let values: Vec<String> = self.tree.iter().map(Key::to_string).collect();
+        format!("AvlSet([{}])", values.join(", "))
+    }
+}
+
+// Map entry in the tree: compared by key alone, slot indexes AvlMap::values
+#[derive(Debug, Clone)]
+struct Entry {
+    key: Key,
+    slot: usize,
+}
+
+impl Entry {
+    // Search key for lookups; the slot takes no part in comparisons
+    fn probe(key: Key) -> Self {
+        Entry { key, slot: 0 }
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.key)
+    }
+}
+
+#[pyclass]
+struct AvlMap {
+    tree: AvlTree<Entry>,
+    values: Vec<Option<PyObject>>,
+    // Slots emptied by deletions, reused before values grows
+    free: Vec<usize>,
+}
+
+impl AvlMap {
+    fn slot(&self, key: Key) -> Option<usize> {
+        self.tree.get(&Entry::probe(key)).map(|entry| entry.slot)
+    }
+
+    fn value(&self, py: Python<'_>, slot: usize) -> PyObject {
+        self.values[slot].as_ref().expect("live entry has a value").clone_ref(py)
+    }
+}
+
+#[pymethods]
+impl AvlMap {
+    #[new]
+    fn new() -> Self {
+        AvlMap { tree: AvlTree::new(), values: Vec::new(), free: Vec::new() }
+    }
+
+    fn __setitem__(&mut self, key: Key, value: PyObject) {
+        if let Some(slot) = self.slot(key.clone()) {
+            self.values[slot] = Some(value);
+            return;
+        }
+        let slot = match self.free.pop() {
+            Some(slot) => {
+                self.values[slot] = Some(value);
+                slot
+            }
+            None => {
+                self.values.push(Some(value));
+                self.values.len() - 1
+            }
+        };
+        self.tree.insert(Entry { key, slot });
+    }
+
+    fn __getitem__(&self, py: Python<'_>, key: Key) -> PyResult<PyObject> {
+        match self.slot(key.clone()) {
+            Some(slot) => Ok(self.value(py, slot)),
+            None => Err(PyKeyError::new_err(key)),
+        }
+    }
+
+    fn __delitem__(&mut self, key: Key) -> PyResult<()> {
+        let Some(slot) = self.slot(key.clone()) else {
+            return Err(PyKeyError::new_err(key));
+        };
+        self.tree.remove(&Entry::probe(key));
+        self.values[slot] = None;
+        self.free.push(slot);
+        Ok(())
+    }
+
+    #[pyo3(signature = (key, default = None))]
+    fn get(&self, py: Python<'_>, key: Key, default: Option<PyObject>) -> Option<PyObject> {
+        match self.slot(key) {
+            Some(slot) => Some(self.value(py, slot)),
+            None => default,
+        }
+    }
+
+    fn __len__(&self) -> usize {
+        self.tree.len()
+    }
+
+    fn __contains__(&self, key: Key) -> bool {
+        self.slot(key).is_some()
+    }
+
+    fn keys(&self) -> Vec<Key> {
+        self.tree.iter().map(|entry| entry.key.clone()).collect()
+    }
+
+    fn items(&self, py: Python<'_>) -> Vec<(Key, PyObject)> {
+        self.tree.iter().map(|entry| (entry.key.clone(), self.value(py, entry.slot))).collect()
+    }
+
+    fn __iter__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyIterator>> {
+        PyList::new(py, self.keys())?.try_iter()
+    }
+
+    fn __repr__(&self, py: Python<'_>) -> PyResult<String> {
+        let mut items = Vec::with_capacity(self.tree.len());
+        for entry in self.tree.iter() {
+            items.push(format!("{}: {}", entry.key, self.value(py, entry.slot).bind(py).repr()?));
+        }
+        Ok(format!("AvlMap({{{}}})", items.join(", ")))
+    }
+}
+
+#[pymodule]
+fn rustnotes(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_function(wrap_pyfunction!(eval, m)?)?;
+    m.add_class::<PyCalculator>()?;
+    m.add_class::<AvlSet>()?;
+    m.add_class::<AvlMap>()?;
+    Ok(())
+}
@@ -0,0 +1,48 @@
+// Trace Output
+//
+// The logger behind the "trace" feature: the collections and the calculator
+// send trace! events through the log facade, and init() installs this logger
+// so the demos print them to stderr as they happen:
+//
+//   cargo run --features trace --bin balanced-binary-tree
+//   [balanced_binary_tree] left-left case at height 3: rotate right
+//
+// RUSTNOTES_TRACE narrows the output to targets containing its value, e.g.
+// RUSTNOTES_TRACE=hash_map for the two hash maps only.
+//
+// Design choices:
+// - A dozen lines of logger instead of env_logger; the events are the point,
+//   not the formatting
+// - Targets are printed without the crate prefix, so lines stay short
+// - Trade-off: stderr is unbuffered, so a demo with many events runs slower
+//   with tracing on
+
+use log::{LevelFilter, Log, Metadata, Record};
+use std::env;
+
+struct StderrLogger {
+    filter: Option<String>,
+}
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.filter.as_ref().is_none_or(|filter| metadata.target().contains(filter.as_str()))
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            let target = record.target().rsplit("::").next().unwrap_or_default();
+            eprintln!("[{}] {}", target, record.args());
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+// Print trace events to stderr; only the first call has any effect
+pub fn init() {
+    let filter = env::var("RUSTNOTES_TRACE").ok().filter(|filter| !filter.is_empty());
+    if log::set_boxed_logger(Box::new(StderrLogger { filter })).is_ok() {
+        log::set_max_level(LevelFilter::Trace);
+    }
+}
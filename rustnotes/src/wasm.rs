@@ -0,0 +1,210 @@
+// WebAssembly Playground
+//
+// The wasm-bindgen side of the data structure playground (www/index.html),
+// compiled only with the "wasm" feature and built with wasm-pack:
+//
+//   wasm-pack build rustnotes --target web --features wasm
+//   python3 -m http.server -d rustnotes    # then open localhost:8000/www/
+//
+// One class per structure, each owning it: AvlPlayground (AvlTree),
+// ListPlayground (LinkedList) and DequePlayground (DoubleLinkedList). Every
+// operation runs through the structure's *_recorded method and returns JSON
+// with the steps it took and the nodes afterwards:
+//
+//   {"changed":true,
+//    "steps":[{"op":"visit","id":1040},{"op":"create","id":1072},
+//             {"op":"link","link":"right","from":1040,"to":1072}, ...],
+//    "nodes":[{"id":1040,"value":5,"height":2,"left":null,"right":1072}, ...]}
+//
+// Design choices:
+// - The structures report their own mutations (rustnotes_collections::steps),
+//   so the page animates what the Rust code did rather than a JavaScript
+//   re-implementation of it
+// - Results cross the boundary as JSON text, written by hand: the shapes are
+//   small and fixed, and it keeps serde and serde-wasm-bindgen out of the build
+// - Values are i32, which JavaScript numbers hold exactly; ids are wasm32
+//   addresses, which they hold too
+// - Trade-off: the page parses the whole node list after every operation,
+//   which is simple and fine for the few dozen nodes anyone animates
+
+use rustnotes_collections::lists::{DoubleLinkedList, LinkedList};
+use rustnotes_collections::steps::{Link, NodeId, Step};
+use rustnotes_collections::trees::AvlTree;
+use std::fmt::Write;
+use wasm_bindgen::prelude::*;
+
+fn id_json(id: Option<NodeId>) -> String {
+    id.map_or_else(|| "null".to_string(), |id| id.to_string())
+}
+
+fn step_json(step: &Step) -> String {
+    match *step {
+        Step::Visit(id) => format!(r#"{{"op":"visit","id":{}}}"#, id),
+        Step::Create(id) => format!(r#"{{"op":"create","id":{}}}"#, id),
+        Step::Delete(id) => format!(r#"{{"op":"delete","id":{}}}"#, id),
+        Step::RotateLeft(id) => format!(r#"{{"op":"rotate_left","id":{}}}"#, id),
+        Step::RotateRight(id) => format!(r#"{{"op":"rotate_right","id":{}}}"#, id),
+        Step::Link { link, to } => {
+            let (name, from) = match link {
+                Link::Root => ("root", None),
+                Link::Head => ("head", None),
+                Link::Tail => ("tail", None),
+                Link::Left(id) => ("left", Some(id)),
+                Link::Right(id) => ("right", Some(id)),
+                Link::Next(id) => ("next", Some(id)),
+                Link::Prev(id) => ("prev", Some(id)),
+            };
+            format!(r#"{{"op":"link","link":"{}","from":{},"to":{}}}"#, name, id_json(from), id_json(to))
+        }
+    }
+}
+
+// The JSON every operation returns; `nodes` is already a JSON array
+fn report(changed: bool, steps: &[Step], nodes: &str) -> String {
+    let steps: Vec<String> = steps.iter().map(step_json).collect();
+    format!(r#"{{"changed":{},"steps":[{}],"nodes":{}}}"#, changed, steps.join(","), nodes)
+}
+
+// A list's nodes, front to back
+fn list_json<'a>(nodes: impl Iterator<Item = (NodeId, &'a i32)>) -> String {
+    let mut json = String::from("[");
+    for (i, (id, value)) in nodes.enumerate() {
+        let comma = if i == 0 { "" } else { "," };
+        let _ = write!(json, r#"{}{{"id":{},"value":{}}}"#, comma, id, value);
+    }
+    json.push(']');
+    json
+}
+
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct AvlPlayground {
+    tree: AvlTree<i32>,
+}
+
+#[wasm_bindgen]
+impl AvlPlayground {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, value: i32) -> String {
+        let mut steps = Vec::new();
+        let changed = self.tree.insert_recorded(value, &mut steps);
+        report(changed, &steps, &self.nodes())
+    }
+
+    pub fn remove(&mut self, value: i32) -> String {
+        let mut steps = Vec::new();
+        let changed = self.tree.remove_recorded(&value, &mut steps);
+        report(changed, &steps, &self.nodes())
+    }
+
+    // Root first, in pre-order
+    pub fn nodes(&self) -> String {
+        let nodes: Vec<String> = self
+            .tree
+            .nodes()
+            .iter()
+            .map(|node| {
+                format!(
+                    r#"{{"id":{},"value":{},"height":{},"left":{},"right":{}}}"#,
+                    node.id,
+                    node.value,
+                    node.height,
+                    id_json(node.left),
+                    id_json(node.right)
+                )
+            })
+            .collect();
+        format!("[{}]", nodes.join(","))
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct ListPlayground {
+    list: LinkedList<i32>,
+}
+
+#[wasm_bindgen]
+impl ListPlayground {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, value: i32) -> String {
+        let mut steps = Vec::new();
+        self.list.push_recorded(value, &mut steps);
+        report(true, &steps, &self.nodes())
+    }
+
+    pub fn pop(&mut self) -> String {
+        let mut steps = Vec::new();
+        let changed = self.list.pop_recorded(&mut steps).is_some();
+        report(changed, &steps, &self.nodes())
+    }
+
+    pub fn remove(&mut self, index: usize) -> String {
+        let mut steps = Vec::new();
+        let changed = self.list.remove_recorded(index, &mut steps).is_some();
+        report(changed, &steps, &self.nodes())
+    }
+
+    pub fn reverse(&mut self) -> String {
+        let mut steps = Vec::new();
+        self.list.reverse_recorded(&mut steps);
+        report(!steps.is_empty(), &steps, &self.nodes())
+    }
+
+    pub fn nodes(&self) -> String {
+        list_json(self.list.nodes().into_iter())
+    }
+}
+
+#[wasm_bindgen]
+#[derive(Default)]
+pub struct DequePlayground {
+    list: DoubleLinkedList<i32>,
+}
+
+#[wasm_bindgen]
+impl DequePlayground {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, value: i32) -> String {
+        let mut steps = Vec::new();
+        self.list.push_recorded(value, &mut steps);
+        report(true, &steps, &self.nodes())
+    }
+
+    pub fn push_end(&mut self, value: i32) -> String {
+        let mut steps = Vec::new();
+        self.list.push_end_recorded(value, &mut steps);
+        report(true, &steps, &self.nodes())
+    }
+
+    pub fn pop(&mut self) -> String {
+        let mut steps = Vec::new();
+        let changed = self.list.pop_recorded(&mut steps).is_some();
+        report(changed, &steps, &self.nodes())
+    }
+
+    pub fn pop_end(&mut self) -> String {
+        let mut steps = Vec::new();
+        let changed = self.list.pop_end_recorded(&mut steps).is_some();
+        report(changed, &steps, &self.nodes())
+    }
+
+    // Front to back; each node's prev is the one before it
+    pub fn nodes(&self) -> String {
+        let mut nodes = Vec::new();
+        self.list.for_each_node(|id, &value| nodes.push((id, value)));
+        list_json(nodes.iter().map(|(id, value)| (*id, value)))
+    }
+}